@@ -870,6 +870,7 @@ export interface CreateBlogRequest {
   publish_start?: string;
   publish_end?: string;
   site_ids: string[];
+  environment?: EnvironmentType;
 }
 
 export interface UpdateBlogRequest {
@@ -956,6 +957,7 @@ export interface CreatePageRequest {
   publish_start?: string;
   publish_end?: string;
   site_ids: string[];
+  environment?: EnvironmentType;
 }
 
 export interface UpdatePageRequest {
@@ -1168,6 +1170,9 @@ export interface SiteSettingsResponse {
   posts_per_page: number;
   editorial_workflow_enabled: boolean;
   preview_templates: PreviewTemplate[];
+  avif_quality: number;
+  avif_speed: number;
+  blog_path_prefix: string;
 }
 
 export interface UpdateSiteSettingsRequest {
@@ -1179,6 +1184,9 @@ export interface UpdateSiteSettingsRequest {
   posts_per_page?: number;
   editorial_workflow_enabled?: boolean;
   preview_templates?: PreviewTemplate[];
+  avif_quality?: number;
+  avif_speed?: number;
+  blog_path_prefix?: string;
 }
 
 // Clerk User Management
@@ -0,0 +1,8 @@
+-- Content version history
+-- content_versions already exists (migration 3); this adds the audit action
+-- recorded when a version is restored, which the Rust enum already declares.
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'restore';
+
+-- Version numbers were SMALLINT and would run out at 32767 edits
+ALTER TABLE contents ALTER COLUMN current_version TYPE INTEGER;
+ALTER TABLE content_versions ALTER COLUMN version_number TYPE INTEGER;
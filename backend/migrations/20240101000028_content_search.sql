@@ -0,0 +1,104 @@
+-- Full-text search over content and document localizations
+-- Each localization keeps a weighted tsvector built with the text search
+-- configuration that matches its locale (title A, subtitle/meta title B,
+-- excerpt/meta description C, body D).
+
+-- Map a locale code ('de', 'de-AT', 'pt_BR') to a text search configuration
+CREATE OR REPLACE FUNCTION search_config_for_locale(locale_code TEXT)
+RETURNS regconfig AS $$
+    SELECT CASE lower(split_part(replace(locale_code, '_', '-'), '-', 1))
+        WHEN 'ar' THEN 'arabic'
+        WHEN 'da' THEN 'danish'
+        WHEN 'de' THEN 'german'
+        WHEN 'el' THEN 'greek'
+        WHEN 'en' THEN 'english'
+        WHEN 'es' THEN 'spanish'
+        WHEN 'fi' THEN 'finnish'
+        WHEN 'fr' THEN 'french'
+        WHEN 'hu' THEN 'hungarian'
+        WHEN 'id' THEN 'indonesian'
+        WHEN 'it' THEN 'italian'
+        WHEN 'lt' THEN 'lithuanian'
+        WHEN 'nb' THEN 'norwegian'
+        WHEN 'nl' THEN 'dutch'
+        WHEN 'nn' THEN 'norwegian'
+        WHEN 'no' THEN 'norwegian'
+        WHEN 'pt' THEN 'portuguese'
+        WHEN 'ro' THEN 'romanian'
+        WHEN 'ru' THEN 'russian'
+        WHEN 'sv' THEN 'swedish'
+        WHEN 'tr' THEN 'turkish'
+        ELSE 'simple'
+    END::regconfig
+$$ LANGUAGE sql IMMUTABLE;
+
+-- ============================================
+-- CONTENT LOCALIZATIONS
+-- ============================================
+
+ALTER TABLE content_localizations ADD COLUMN search_vector tsvector;
+
+CREATE OR REPLACE FUNCTION content_localizations_search_vector_update()
+RETURNS TRIGGER AS $$
+DECLARE
+    cfg regconfig := search_config_for_locale(
+        (SELECT code FROM locales WHERE id = NEW.locale_id)
+    );
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector(cfg, COALESCE(NEW.title, '')), 'A') ||
+        setweight(to_tsvector(cfg, COALESCE(NEW.subtitle, '') || ' ' || COALESCE(NEW.meta_title, '')), 'B') ||
+        setweight(to_tsvector(cfg, COALESCE(NEW.excerpt, '') || ' ' || COALESCE(NEW.meta_description, '')), 'C') ||
+        setweight(to_tsvector(cfg, COALESCE(NEW.body, '')), 'D');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER content_localizations_search_vector
+    BEFORE INSERT OR UPDATE OF locale_id, title, subtitle, excerpt, body, meta_title, meta_description
+    ON content_localizations
+    FOR EACH ROW EXECUTE FUNCTION content_localizations_search_vector_update();
+
+-- ============================================
+-- DOCUMENT LOCALIZATIONS
+-- ============================================
+
+ALTER TABLE document_localizations ADD COLUMN search_vector tsvector;
+
+CREATE OR REPLACE FUNCTION document_localizations_search_vector_update()
+RETURNS TRIGGER AS $$
+DECLARE
+    cfg regconfig := search_config_for_locale(
+        (SELECT code FROM locales WHERE id = NEW.locale_id)
+    );
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector(cfg, COALESCE(NEW.name, '')), 'A') ||
+        setweight(to_tsvector(cfg, COALESCE(NEW.description, '')), 'C');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER document_localizations_search_vector
+    BEFORE INSERT OR UPDATE OF locale_id, name, description
+    ON document_localizations
+    FOR EACH ROW EXECUTE FUNCTION document_localizations_search_vector_update();
+
+-- ============================================
+-- BACKFILL (without touching updated_at)
+-- ============================================
+
+ALTER TABLE content_localizations DISABLE TRIGGER update_content_localizations_updated_at;
+UPDATE content_localizations SET title = title;
+ALTER TABLE content_localizations ENABLE TRIGGER update_content_localizations_updated_at;
+
+ALTER TABLE document_localizations DISABLE TRIGGER update_document_localizations_updated_at;
+UPDATE document_localizations SET name = name;
+ALTER TABLE document_localizations ENABLE TRIGGER update_document_localizations_updated_at;
+
+-- ============================================
+-- INDEXES
+-- ============================================
+
+CREATE INDEX idx_content_localizations_search ON content_localizations USING gin(search_vector);
+CREATE INDEX idx_document_localizations_search ON document_localizations USING gin(search_vector);
@@ -0,0 +1,16 @@
+-- Shareable draft preview tokens
+-- The token itself is never stored: it is an HMAC over the row's ID, scope and
+-- expiry, so a row can be listed and revoked without exposing the secret.
+CREATE TABLE preview_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    content_id UUID NOT NULL REFERENCES contents(id) ON DELETE CASCADE,
+    locale_id UUID REFERENCES locales(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_preview_tokens_content_id ON preview_tokens(content_id);
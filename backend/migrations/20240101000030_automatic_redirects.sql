@@ -0,0 +1,9 @@
+-- Redirects generated when a blog slug or page route changes, or when content
+-- is deleted/archived with a fallback. They are marked so they can be told
+-- apart from manual ones, and remember which content produced them.
+ALTER TABLE redirects
+    ADD COLUMN is_auto_generated BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN content_id UUID REFERENCES contents(id) ON DELETE SET NULL;
+
+-- Chain collapsing rewrites redirects by destination
+CREATE INDEX idx_redirects_destination ON redirects(site_id, destination_path);
@@ -0,0 +1,29 @@
+-- Pattern redirects, additional status codes and hit statistics
+
+CREATE TYPE redirect_match_type AS ENUM ('exact', 'wildcard', 'regex');
+CREATE TYPE redirect_query_handling AS ENUM ('ignore', 'preserve', 'exact');
+
+ALTER TABLE redirects
+    ADD COLUMN match_type redirect_match_type NOT NULL DEFAULT 'exact',
+    ADD COLUMN query_handling redirect_query_handling NOT NULL DEFAULT 'ignore',
+    ADD COLUMN hit_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN last_hit_at TIMESTAMPTZ,
+    -- 410 Gone has nowhere to go
+    ALTER COLUMN destination_path DROP NOT NULL;
+
+ALTER TABLE redirects DROP CONSTRAINT chk_redirect_status_code;
+ALTER TABLE redirects
+    ADD CONSTRAINT chk_redirect_status_code CHECK (status_code IN (301, 302, 307, 308, 410)),
+    ADD CONSTRAINT chk_redirect_gone_destination
+        CHECK ((status_code = 410) = (destination_path IS NULL));
+
+-- Patterns are evaluated in the application, exact matches stay index lookups
+CREATE INDEX idx_redirects_patterns ON redirects(site_id, created_at)
+    WHERE is_active = TRUE AND match_type <> 'exact';
+
+-- Counting a hit is not an edit
+DROP TRIGGER update_redirects_updated_at ON redirects;
+CREATE TRIGGER update_redirects_updated_at BEFORE UPDATE ON redirects
+    FOR EACH ROW
+    WHEN (OLD.hit_count IS NOT DISTINCT FROM NEW.hit_count)
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,14 @@
+-- Advisory edit locks
+-- One row per content item being edited. Locks are not enforced on writes;
+-- they only tell other editors who is working on what. A lock whose
+-- expires_at has passed is treated as free and may be taken over.
+CREATE TABLE content_edit_locks (
+    content_id UUID PRIMARY KEY REFERENCES contents(id) ON DELETE CASCADE,
+    holder_id UUID NOT NULL,
+    holder_clerk_user_id VARCHAR(255),
+    holder_name VARCHAR(255),
+    acquired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
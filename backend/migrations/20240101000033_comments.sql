@@ -0,0 +1,30 @@
+-- Threaded blog comments with a moderation queue
+-- site_id is the site the comment was submitted through and whose members moderate it.
+
+CREATE TYPE comment_status AS ENUM ('pending', 'approved', 'spam', 'deleted');
+
+CREATE TABLE comments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL REFERENCES blogs(id) ON DELETE CASCADE,
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    locale_id UUID REFERENCES locales(id) ON DELETE SET NULL,
+    parent_id UUID REFERENCES comments(id) ON DELETE CASCADE,
+    author_name VARCHAR(100) NOT NULL,
+    author_email VARCHAR(255),
+    author_url VARCHAR(500),
+    body TEXT NOT NULL,
+    status comment_status NOT NULL DEFAULT 'pending',
+    ip_address VARCHAR(45),
+    user_agent VARCHAR(500),
+    moderated_by UUID,
+    moderated_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_comments_blog_id ON comments(blog_id, created_at);
+CREATE INDEX idx_comments_site_status ON comments(site_id, status, created_at DESC);
+CREATE INDEX idx_comments_parent_id ON comments(parent_id);
+
+CREATE TRIGGER update_comments_updated_at BEFORE UPDATE ON comments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,33 @@
+-- Site-scoped form definitions (e.g. contact forms) and their submission inbox
+-- fields holds the field definitions as a JSON array; labels are keyed by locale code.
+
+CREATE TABLE forms (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    slug VARCHAR(100) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    fields JSONB NOT NULL DEFAULT '[]',
+    recipient_email VARCHAR(255),
+    retention_days INTEGER CHECK (retention_days IS NULL OR retention_days > 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (site_id, slug)
+);
+
+CREATE TRIGGER update_forms_updated_at BEFORE UPDATE ON forms
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE form_submissions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    form_id UUID NOT NULL REFERENCES forms(id) ON DELETE CASCADE,
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    locale_id UUID REFERENCES locales(id) ON DELETE SET NULL,
+    data JSONB NOT NULL,
+    ip_address VARCHAR(45),
+    user_agent VARCHAR(500),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_form_submissions_form_id ON form_submissions(form_id, created_at DESC);
@@ -0,0 +1,38 @@
+-- Reusable sections shared across the pages of a site
+-- A page section with global_section_id set is a reference: its type, cover image,
+-- CTA route, settings and localizations come from the global section.
+
+CREATE TABLE global_sections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    section_type section_type NOT NULL,
+    cover_image_id UUID REFERENCES media_files(id),
+    call_to_action_route TEXT,
+    settings JSONB DEFAULT '{}',
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(site_id, name)
+);
+
+CREATE TABLE global_section_localizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    global_section_id UUID NOT NULL REFERENCES global_sections(id) ON DELETE CASCADE,
+    locale_id UUID NOT NULL REFERENCES locales(id),
+    title TEXT,
+    text TEXT,
+    button_text TEXT,
+    UNIQUE(global_section_id, locale_id)
+);
+
+-- Deleting a global section must not silently remove the page sections that
+-- reference it; the API rejects the delete instead
+ALTER TABLE page_sections
+    ADD COLUMN global_section_id UUID REFERENCES global_sections(id) ON DELETE RESTRICT;
+
+CREATE INDEX idx_page_sections_global ON page_sections(global_section_id)
+    WHERE global_section_id IS NOT NULL;
+
+CREATE TRIGGER update_global_sections_updated_at BEFORE UPDATE ON global_sections
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
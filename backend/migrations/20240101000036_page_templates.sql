@@ -0,0 +1,22 @@
+-- Page templates: a page type plus an ordered section layout to start new pages from.
+-- sections holds the layout as a JSON array; placeholder text is keyed by locale code.
+
+CREATE TABLE page_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+    page_type page_type NOT NULL DEFAULT 'static',
+    template VARCHAR(100),
+    sections JSONB NOT NULL DEFAULT '[]',
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_page_templates_site_name UNIQUE (site_id, name)
+);
+
+CREATE INDEX idx_page_templates_site_id ON page_templates(site_id);
+
+CREATE TRIGGER update_page_templates_updated_at BEFORE UPDATE ON page_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,38 @@
+-- Environment promotion: copies of a content item in different environments share a
+-- lineage_id (the id of the first copy), and every applied promotion is recorded.
+
+ALTER TABLE contents ADD COLUMN lineage_id UUID;
+UPDATE contents SET lineage_id = id;
+ALTER TABLE contents ALTER COLUMN lineage_id SET NOT NULL;
+
+CREATE OR REPLACE FUNCTION set_content_lineage_id()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.lineage_id := COALESCE(NEW.lineage_id, NEW.id);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_contents_lineage_id BEFORE INSERT ON contents
+    FOR EACH ROW EXECUTE FUNCTION set_content_lineage_id();
+
+-- At most one live copy per environment
+CREATE UNIQUE INDEX idx_contents_lineage_environment
+    ON contents(lineage_id, environment_id) WHERE is_deleted = FALSE;
+
+CREATE TABLE environment_promotions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    source_environment_id UUID NOT NULL REFERENCES environments(id),
+    target_environment_id UUID NOT NULL REFERENCES environments(id),
+    changeset JSONB NOT NULL DEFAULT '[]',
+    created_count INTEGER NOT NULL DEFAULT 0,
+    updated_count INTEGER NOT NULL DEFAULT 0,
+    deleted_count INTEGER NOT NULL DEFAULT 0,
+    promoted_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_environment_promotions_distinct
+        CHECK (source_environment_id <> target_environment_id)
+);
+
+CREATE INDEX idx_environment_promotions_site ON environment_promotions(site_id, created_at DESC);
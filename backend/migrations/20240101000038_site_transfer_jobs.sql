@@ -0,0 +1,34 @@
+-- Background jobs for site export and import archives
+-- site_id is the exported site, or the site created by an import once it exists.
+-- archive_path is the storage backend key of the export archive or the uploaded
+-- import archive. owner_instance is the server process running the job; it
+-- refreshes heartbeat_at while the job runs, so any replica can fail jobs whose
+-- owner went away.
+
+CREATE TYPE site_transfer_kind AS ENUM ('export', 'import');
+CREATE TYPE site_transfer_status AS ENUM ('queued', 'running', 'completed', 'failed');
+
+CREATE TABLE site_transfer_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind site_transfer_kind NOT NULL,
+    site_id UUID REFERENCES sites(id) ON DELETE CASCADE,
+    status site_transfer_status NOT NULL DEFAULT 'queued',
+    stage VARCHAR(100),
+    completed_steps INTEGER NOT NULL DEFAULT 0,
+    total_steps INTEGER NOT NULL DEFAULT 0,
+    result JSONB,
+    error TEXT,
+    archive_path VARCHAR(500),
+    owner_instance UUID,
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_site_transfer_jobs_site ON site_transfer_jobs(site_id, created_at DESC);
+CREATE INDEX idx_site_transfer_jobs_active ON site_transfer_jobs(heartbeat_at)
+    WHERE status IN ('queued', 'running');
+CREATE INDEX idx_site_transfer_jobs_archive ON site_transfer_jobs(finished_at)
+    WHERE archive_path IS NOT NULL;
@@ -0,0 +1,54 @@
+-- Content releases: named sets of content and navigation changes applied together
+-- Each item records the state it replaced so a published release can be rolled back,
+-- and the version it published so a rollback can leave later edits alone.
+
+CREATE TYPE content_release_status AS ENUM ('draft', 'scheduled', 'published', 'rolled_back', 'failed');
+
+CREATE TABLE content_releases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    site_id UUID NOT NULL REFERENCES sites(id) ON DELETE CASCADE,
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+    status content_release_status NOT NULL DEFAULT 'draft',
+    scheduled_at TIMESTAMPTZ,
+    published_at TIMESTAMPTZ,
+    published_by UUID,
+    rolled_back_at TIMESTAMPTZ,
+    rolled_back_by UUID,
+    error TEXT,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_release_schedule CHECK (status <> 'scheduled' OR scheduled_at IS NOT NULL)
+);
+
+CREATE INDEX idx_content_releases_site ON content_releases(site_id, created_at DESC);
+CREATE INDEX idx_content_releases_due ON content_releases(scheduled_at) WHERE status = 'scheduled';
+
+-- An item either sets the status of a content item or shows/hides a navigation item
+CREATE TABLE content_release_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    release_id UUID NOT NULL REFERENCES content_releases(id) ON DELETE CASCADE,
+    content_id UUID REFERENCES contents(id) ON DELETE CASCADE,
+    navigation_item_id UUID REFERENCES navigation_items(id) ON DELETE CASCADE,
+    target_status content_status,
+    target_active BOOLEAN,
+    previous_status content_status,
+    previous_published_at TIMESTAMPTZ,
+    previous_publish_start TIMESTAMPTZ,
+    previous_publish_end TIMESTAMPTZ,
+    previous_active BOOLEAN,
+    published_version INTEGER,
+    rollback_skipped BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_release_item_target CHECK (
+        (content_id IS NOT NULL AND target_status IS NOT NULL
+            AND navigation_item_id IS NULL AND target_active IS NULL)
+        OR (navigation_item_id IS NOT NULL AND target_active IS NOT NULL
+            AND content_id IS NULL AND target_status IS NULL)
+    ),
+    UNIQUE (release_id, content_id),
+    UNIQUE (release_id, navigation_item_id)
+);
+
+CREATE INDEX idx_content_release_items_release ON content_release_items(release_id);
@@ -0,0 +1,55 @@
+-- Trash bin: deletion times for soft-deleted media, and foreign keys that let
+-- soft-deleted content and media be purged for good.
+
+ALTER TABLE media_files ADD COLUMN deleted_at TIMESTAMPTZ;
+UPDATE media_files SET deleted_at = updated_at WHERE is_deleted = TRUE;
+UPDATE contents SET deleted_at = updated_at WHERE is_deleted = TRUE AND deleted_at IS NULL;
+
+CREATE INDEX idx_media_files_trash ON media_files(deleted_at) WHERE is_deleted = TRUE;
+CREATE INDEX idx_contents_trash ON contents(deleted_at) WHERE is_deleted = TRUE;
+
+-- Purging a media file clears the references to it
+ALTER TABLE blogs
+    DROP CONSTRAINT blogs_cover_image_id_fkey,
+    ADD CONSTRAINT blogs_cover_image_id_fkey
+        FOREIGN KEY (cover_image_id) REFERENCES media_files(id) ON DELETE SET NULL,
+    DROP CONSTRAINT blogs_header_image_id_fkey,
+    ADD CONSTRAINT blogs_header_image_id_fkey
+        FOREIGN KEY (header_image_id) REFERENCES media_files(id) ON DELETE SET NULL;
+
+ALTER TABLE cv_entries
+    DROP CONSTRAINT cv_entries_company_logo_id_fkey,
+    ADD CONSTRAINT cv_entries_company_logo_id_fkey
+        FOREIGN KEY (company_logo_id) REFERENCES media_files(id) ON DELETE SET NULL;
+
+ALTER TABLE page_sections
+    DROP CONSTRAINT page_sections_cover_image_id_fkey,
+    ADD CONSTRAINT page_sections_cover_image_id_fkey
+        FOREIGN KEY (cover_image_id) REFERENCES media_files(id) ON DELETE SET NULL;
+
+ALTER TABLE global_sections
+    DROP CONSTRAINT global_sections_cover_image_id_fkey,
+    ADD CONSTRAINT global_sections_cover_image_id_fkey
+        FOREIGN KEY (cover_image_id) REFERENCES media_files(id) ON DELETE SET NULL;
+
+ALTER TABLE blog_photos
+    DROP CONSTRAINT blog_photos_media_file_id_fkey,
+    ADD CONSTRAINT blog_photos_media_file_id_fkey
+        FOREIGN KEY (media_file_id) REFERENCES media_files(id) ON DELETE CASCADE;
+
+ALTER TABLE blog_attachments
+    DROP CONSTRAINT blog_attachments_media_file_id_fkey,
+    ADD CONSTRAINT blog_attachments_media_file_id_fkey
+        FOREIGN KEY (media_file_id) REFERENCES media_files(id) ON DELETE CASCADE;
+
+-- Purging a page turns its children into top-level pages and removes menu
+-- items that link to it (they would have neither a page nor a URL)
+ALTER TABLE pages
+    DROP CONSTRAINT pages_parent_page_id_fkey,
+    ADD CONSTRAINT pages_parent_page_id_fkey
+        FOREIGN KEY (parent_page_id) REFERENCES pages(id) ON DELETE SET NULL;
+
+ALTER TABLE navigation_items
+    DROP CONSTRAINT navigation_items_page_id_fkey,
+    ADD CONSTRAINT navigation_items_page_id_fkey
+        FOREIGN KEY (page_id) REFERENCES pages(id) ON DELETE CASCADE;
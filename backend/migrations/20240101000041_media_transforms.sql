@@ -0,0 +1,16 @@
+-- Cached results of on-the-fly image transformations (/media/{id}/transform).
+-- One row per media file and normalized set of options; the image itself lives
+-- in the storage backend.
+
+CREATE TABLE media_transforms (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    media_file_id UUID NOT NULL REFERENCES media_files(id) ON DELETE CASCADE,
+    transform_key TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    width SMALLINT NOT NULL,
+    height SMALLINT NOT NULL,
+    file_size INTEGER NOT NULL,
+    storage_path TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (media_file_id, transform_key)
+);
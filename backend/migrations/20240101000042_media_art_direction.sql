@@ -0,0 +1,12 @@
+-- Art direction for images: a focal point (fractions of width and height,
+-- NULL = center) and named crop rectangles in source pixels, e.g.
+-- {"16:9": {"x": 0, "y": 120, "width": 1600, "height": 900}}
+
+ALTER TABLE media_files
+    ADD COLUMN focal_x DOUBLE PRECISION,
+    ADD COLUMN focal_y DOUBLE PRECISION,
+    ADD COLUMN crops JSONB NOT NULL DEFAULT '{}',
+    ADD CONSTRAINT chk_media_focal_point CHECK (
+        (focal_x IS NULL AND focal_y IS NULL)
+        OR (focal_x BETWEEN 0 AND 1 AND focal_y BETWEEN 0 AND 1)
+    );
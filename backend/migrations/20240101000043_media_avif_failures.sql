@@ -0,0 +1,6 @@
+-- Images whose AVIF variant could not be generated. The scheduler's AVIF
+-- backfill skips them, so a file that cannot be decoded is not retried on
+-- every run or after every restart.
+
+ALTER TABLE media_files
+    ADD COLUMN avif_failed_at TIMESTAMPTZ;
@@ -4,11 +4,13 @@
 //! and configuration files.
 
 mod database;
+mod scheduler;
 mod security;
 mod settings;
 mod storage;
 
 pub use database::DatabaseConfig;
+pub use scheduler::SchedulerConfig;
 pub use security::SecurityConfig;
 pub use settings::Settings;
 pub use storage::StorageConfig;
@@ -0,0 +1,66 @@
+//! Background scheduler configuration
+
+use serde::Deserialize;
+
+/// Configuration for the background publishing scheduler
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchedulerConfig {
+    /// Run the scheduler in this process (default: true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Seconds between scheduler ticks (default: 60)
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+
+    /// Days deleted content and media stay in the trash before they are
+    /// purged; 0 keeps them until purged by hand (default: 30)
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+
+    /// Images given an AVIF variant per tick while older uploads lack one;
+    /// 0 disables the backfill (default: 10)
+    #[serde(default = "default_avif_backfill_batch")]
+    pub avif_backfill_batch: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval_seconds() -> u64 {
+    60
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_avif_backfill_batch() -> u32 {
+    10
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval_seconds: default_interval_seconds(),
+            trash_retention_days: default_trash_retention_days(),
+            avif_backfill_batch: default_avif_backfill_batch(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduler_config_defaults() {
+        let config = SchedulerConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.interval_seconds, 60);
+        assert_eq!(config.trash_retention_days, 30);
+        assert_eq!(config.avif_backfill_batch, 10);
+    }
+}
@@ -31,6 +31,14 @@ pub struct SecurityConfig {
     #[serde(default = "default_rate_limit_per_minute")]
     pub rate_limit_per_minute: u32,
 
+    /// Rate limiting: comment submissions per minute per IP
+    #[serde(default = "default_comment_rate_limit_per_minute")]
+    pub comment_rate_limit_per_minute: u32,
+
+    /// Rate limiting: form submissions per minute per IP
+    #[serde(default = "default_form_rate_limit_per_minute")]
+    pub form_rate_limit_per_minute: u32,
+
     /// Rate limiting: burst size (max concurrent requests)
     #[serde(default = "default_rate_limit_burst")]
     pub rate_limit_burst: u32,
@@ -78,6 +86,16 @@ pub struct SecurityConfig {
     /// Path to TLS private key (PEM format)
     #[serde(default)]
     pub tls_key_path: String,
+
+    /// Secret used to sign draft preview tokens (empty = random per process,
+    /// development only)
+    #[serde(default)]
+    pub preview_token_secret: String,
+
+    /// Secret used to sign image transformation URLs (empty = random per
+    /// process, development only)
+    #[serde(default)]
+    pub image_url_secret: String,
 }
 
 // 10 MB
@@ -108,6 +126,14 @@ fn default_rate_limit_per_minute() -> u32 {
     500
 }
 
+fn default_comment_rate_limit_per_minute() -> u32 {
+    5
+}
+
+fn default_form_rate_limit_per_minute() -> u32 {
+    5
+}
+
 fn default_rate_limit_burst() -> u32 {
     20
 }
@@ -145,6 +171,8 @@ impl Default for SecurityConfig {
             max_file_size: default_max_file_size(),
             rate_limit_per_second: default_rate_limit_per_second(),
             rate_limit_per_minute: default_rate_limit_per_minute(),
+            comment_rate_limit_per_minute: default_comment_rate_limit_per_minute(),
+            form_rate_limit_per_minute: default_form_rate_limit_per_minute(),
             rate_limit_burst: default_rate_limit_burst(),
             max_json_depth: default_max_json_depth(),
             max_array_items: default_max_array_items(),
@@ -157,6 +185,8 @@ impl Default for SecurityConfig {
             system_admin_clerk_ids: String::new(),
             tls_cert_path: String::new(),
             tls_key_path: String::new(),
+            preview_token_secret: String::new(),
+            image_url_secret: String::new(),
         }
     }
 }
@@ -173,6 +203,36 @@ impl SecurityConfig {
             ("string", self.max_body_size),
         ]
     }
+
+    /// IP limits for public comment submissions (one per second, configurable per minute)
+    pub fn comment_limits(&self) -> SecurityConfig {
+        self.submission_limits(self.comment_rate_limit_per_minute)
+    }
+
+    /// IP limits for public form submissions (one per second, configurable per minute)
+    pub fn form_limits(&self) -> SecurityConfig {
+        self.submission_limits(self.form_rate_limit_per_minute)
+    }
+
+    /// Environment variables of the signing secrets that are not set
+    pub fn missing_signing_secrets(&self) -> Vec<&'static str> {
+        [
+            ("PREVIEW_TOKEN_SECRET", &self.preview_token_secret),
+            ("IMAGE_URL_SECRET", &self.image_url_secret),
+        ]
+        .into_iter()
+        .filter(|(_, secret)| secret.is_empty())
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    fn submission_limits(&self, per_minute: u32) -> SecurityConfig {
+        SecurityConfig {
+            rate_limit_per_second: 1,
+            rate_limit_per_minute: per_minute,
+            ..self.clone()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +246,29 @@ mod tests {
         assert_eq!(config.max_json_size, 15 * 1024 * 1024);
         assert_eq!(config.rate_limit_per_second, 50);
         assert_eq!(config.rate_limit_per_minute, 500);
+        assert_eq!(config.comment_rate_limit_per_minute, 5);
+        assert_eq!(config.form_rate_limit_per_minute, 5);
+    }
+
+    #[test]
+    fn test_submission_limits() {
+        let config = SecurityConfig {
+            form_rate_limit_per_minute: 3,
+            ..SecurityConfig::default()
+        };
+        let limits = config.comment_limits();
+        assert_eq!(limits.rate_limit_per_second, 1);
+        assert_eq!(limits.rate_limit_per_minute, 5);
+        assert_eq!(config.form_limits().rate_limit_per_minute, 3);
+    }
+
+    #[test]
+    fn test_missing_signing_secrets() {
+        let config = SecurityConfig {
+            preview_token_secret: "preview".to_string(),
+            ..SecurityConfig::default()
+        };
+        assert_eq!(config.missing_signing_secrets(), vec!["IMAGE_URL_SECRET"]);
     }
 
     #[test]
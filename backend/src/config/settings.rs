@@ -2,7 +2,7 @@
 
 use serde::Deserialize;
 
-use super::{DatabaseConfig, SecurityConfig, StorageConfig};
+use super::{DatabaseConfig, SchedulerConfig, SecurityConfig, StorageConfig};
 
 /// Application settings
 #[derive(Debug, Clone, Deserialize)]
@@ -31,6 +31,10 @@ pub struct Settings {
     #[serde(default)]
     pub storage: StorageConfig,
 
+    /// Background scheduler configuration
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+
     /// Log level
     #[serde(default = "default_log_level")]
     pub log_level: String,
@@ -73,6 +77,7 @@ impl Default for Settings {
             database: DatabaseConfig::default(),
             security: SecurityConfig::default(),
             storage: StorageConfig::default(),
+            scheduler: SchedulerConfig::default(),
             log_level: default_log_level(),
             enable_tracing: default_true(),
             cors_origins: None,
@@ -102,6 +107,11 @@ impl Settings {
             .set_default("storage.provider", "local")?
             .set_default("storage.local_upload_dir", "./uploads")?
             .set_default("storage.local_base_url", "/uploads")?
+            // Scheduler defaults
+            .set_default("scheduler.enabled", true)?
+            .set_default("scheduler.interval_seconds", 60)?
+            .set_default("scheduler.trash_retention_days", 30)?
+            .set_default("scheduler.avif_backfill_batch", 10)?
             // Security defaults
             .set_default("security.max_body_size", 10 * 1024 * 1024)?
             .set_default("security.max_json_size", 15 * 1024 * 1024)?
@@ -109,6 +119,8 @@ impl Settings {
             .set_default("security.max_file_size", 50 * 1024 * 1024)?
             .set_default("security.rate_limit_per_second", 10)?
             .set_default("security.rate_limit_per_minute", 100)?
+            .set_default("security.comment_rate_limit_per_minute", 5)?
+            .set_default("security.form_rate_limit_per_minute", 5)?
             .set_default("security.rate_limit_burst", 20)?
             .set_default("security.max_json_depth", 10)?
             .set_default("security.max_array_items", 1000)?
@@ -149,6 +161,16 @@ impl Settings {
             )?
             // TLS_KEY_PATH override
             .set_override_option("security.tls_key_path", std::env::var("TLS_KEY_PATH").ok())?
+            // PREVIEW_TOKEN_SECRET override
+            .set_override_option(
+                "security.preview_token_secret",
+                std::env::var("PREVIEW_TOKEN_SECRET").ok(),
+            )?
+            // IMAGE_URL_SECRET override
+            .set_override_option(
+                "security.image_url_secret",
+                std::env::var("IMAGE_URL_SECRET").ok(),
+            )?
             // Storage overrides
             .set_override_option("storage.provider", std::env::var("STORAGE_PROVIDER").ok())?
             .set_override_option(
@@ -166,9 +188,43 @@ impl Settings {
                 "storage.s3_endpoint",
                 std::env::var("STORAGE_S3_ENDPOINT").ok(),
             )?
+            .set_override_option(
+                "storage.import_source_dir",
+                std::env::var("STORAGE_IMPORT_SOURCE_DIR").ok(),
+            )?
+            // Scheduler overrides
+            .set_override_option("scheduler.enabled", std::env::var("SCHEDULER_ENABLED").ok())?
+            .set_override_option(
+                "scheduler.interval_seconds",
+                std::env::var("SCHEDULER_INTERVAL_SECONDS").ok(),
+            )?
+            .set_override_option(
+                "scheduler.trash_retention_days",
+                std::env::var("TRASH_RETENTION_DAYS").ok(),
+            )?
+            .set_override_option(
+                "scheduler.avif_backfill_batch",
+                std::env::var("AVIF_BACKFILL_BATCH").ok(),
+            )?
             .build()?;
 
-        settings.try_deserialize()
+        let settings: Self = settings.try_deserialize()?;
+        settings.check_signing_secrets()?;
+        Ok(settings)
+    }
+
+    /// Require the signing secrets outside development, where a random
+    /// per-process secret would break signatures across restarts and replicas
+    fn check_signing_secrets(&self) -> Result<(), config::ConfigError> {
+        let missing = self.security.missing_signing_secrets();
+        if self.is_development() || missing.is_empty() {
+            return Ok(());
+        }
+        Err(config::ConfigError::Message(format!(
+            "{} must be set when APP__ENVIRONMENT is '{}'",
+            missing.join(" and "),
+            self.environment
+        )))
     }
 
     /// Check if running in development mode
@@ -32,6 +32,11 @@ pub struct StorageConfig {
     /// Custom S3 endpoint (for MinIO or compatible services)
     #[serde(default)]
     pub s3_endpoint: Option<String>,
+
+    /// Local copy of a source site for importers; attachment URLs are read
+    /// from here by path instead of being downloaded
+    #[serde(default)]
+    pub import_source_dir: Option<String>,
 }
 
 fn default_provider() -> String {
@@ -56,6 +61,7 @@ impl Default for StorageConfig {
             s3_region: None,
             s3_prefix: None,
             s3_endpoint: None,
+            import_source_dir: None,
         }
     }
 }
@@ -71,5 +77,6 @@ mod tests {
         assert_eq!(config.local_upload_dir, "./uploads");
         assert_eq!(config.local_base_url, "/uploads");
         assert!(config.s3_bucket.is_none());
+        assert!(config.import_source_dir.is_none());
     }
 }
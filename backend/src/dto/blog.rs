@@ -5,11 +5,13 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::dto::content::LocalizationResponse;
+use crate::dto::content_block::LocalizationWithBlocksResponse;
 use crate::dto::document::BlogDocumentResponse;
+use crate::dto::redirect::validate_destination_path;
 use crate::dto::taxonomy::CategoryResponse;
 use crate::models::blog::BlogWithContent;
 use crate::models::content::ContentStatus;
+use crate::models::environment::EnvironmentType;
 use crate::utils::pagination::Paginated;
 use crate::utils::validation::validate_slug;
 
@@ -62,6 +64,10 @@ pub struct CreateBlogRequest {
     /// Site IDs to associate this blog with
     #[validate(length(min = 1, message = "At least one site ID is required"))]
     pub site_ids: Vec<Uuid>,
+
+    /// Environment to create the blog in (default: the live environment)
+    #[schema(example = "Staging")]
+    pub environment: Option<EnvironmentType>,
 }
 
 fn default_true() -> bool {
@@ -110,6 +116,12 @@ pub struct UpdateBlogRequest {
 
     pub publish_start: Option<DateTime<Utc>>,
     pub publish_end: Option<DateTime<Utc>>,
+
+    /// When archiving, redirect the old URL here (path or absolute URL)
+    #[schema(example = "/blog")]
+    #[validate(length(max = 2000, message = "Redirect target cannot exceed 2000 characters"))]
+    #[validate(custom(function = "validate_destination_path"))]
+    pub redirect_to: Option<String>,
 }
 
 /// Blog list response item
@@ -203,13 +215,13 @@ impl From<BlogWithContent> for BlogResponse {
     }
 }
 
-/// Blog detail response with localizations, categories, and documents
+/// Blog detail response with localizations (and their blocks), categories, and documents
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
-#[schema(description = "Blog post with localizations, categories, and documents")]
+#[schema(description = "Blog post with localizations, content blocks, categories, and documents")]
 pub struct BlogDetailResponse {
     #[serde(flatten)]
     pub blog: BlogResponse,
-    pub localizations: Vec<LocalizationResponse>,
+    pub localizations: Vec<LocalizationWithBlocksResponse>,
     pub categories: Vec<CategoryResponse>,
     pub documents: Vec<BlogDocumentResponse>,
 }
@@ -237,6 +249,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -256,6 +269,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -276,6 +290,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -298,6 +313,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -320,6 +336,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -339,6 +356,7 @@ mod tests {
             status: Some(ContentStatus::Published),
             publish_start: None,
             publish_end: None,
+            redirect_to: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -357,6 +375,7 @@ mod tests {
             status: None,
             publish_start: None,
             publish_end: None,
+            redirect_to: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -0,0 +1,214 @@
+//! Comment DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::bulk::BulkAction;
+use crate::models::comment::{Comment, CommentStatus};
+use crate::utils::pagination::Paginated;
+
+/// Request to submit a comment on a blog post
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Submit a comment or reply; it waits for moderation before it is shown")]
+pub struct CreateCommentRequest {
+    /// Comment being replied to (must be an approved comment on the same post)
+    pub parent_id: Option<Uuid>,
+
+    /// Locale code the comment was written in
+    #[validate(length(min = 2, max = 10, message = "Locale must be 2-10 characters"))]
+    #[schema(example = "en")]
+    pub locale: Option<String>,
+
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    #[schema(example = "Jane Reader")]
+    pub author_name: String,
+
+    /// Only visible to moderators
+    #[validate(email(message = "Must be a valid email address"))]
+    #[validate(length(max = 255, message = "Email cannot exceed 255 characters"))]
+    #[schema(example = "jane@example.com")]
+    pub author_email: Option<String>,
+
+    #[validate(url(message = "Must be a valid URL"))]
+    #[validate(length(max = 500, message = "URL cannot exceed 500 characters"))]
+    #[schema(example = "https://jane.example.com")]
+    pub author_url: Option<String>,
+
+    #[validate(length(min = 1, max = 10000, message = "Comment must be 1-10000 characters"))]
+    #[schema(example = "Great post, thanks!")]
+    pub body: String,
+}
+
+/// Request to moderate a single comment
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Change the moderation status of a comment")]
+pub struct UpdateCommentRequest {
+    pub status: CommentStatus,
+}
+
+/// Bulk moderation request
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Bulk moderation request for comments")]
+pub struct BulkCommentRequest {
+    /// IDs of the comments to act on (1–100)
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "ids must contain between 1 and 100 items"
+    ))]
+    pub ids: Vec<Uuid>,
+
+    /// `UpdateStatus` moves comments to `status`; `Delete` removes them permanently
+    pub action: BulkAction,
+
+    /// Target status (required when action is UpdateStatus)
+    pub status: Option<CommentStatus>,
+}
+
+/// Comment as seen by moderators
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Comment with moderation details")]
+pub struct CommentResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    pub site_id: Uuid,
+    pub locale_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    #[schema(example = "Jane Reader")]
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub author_url: Option<String>,
+    pub body: String,
+    pub status: CommentStatus,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub moderated_by: Option<Uuid>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Comment> for CommentResponse {
+    fn from(c: Comment) -> Self {
+        Self {
+            id: c.id,
+            blog_id: c.blog_id,
+            site_id: c.site_id,
+            locale_id: c.locale_id,
+            parent_id: c.parent_id,
+            author_name: c.author_name,
+            author_email: c.author_email,
+            author_url: c.author_url,
+            body: c.body,
+            status: c.status,
+            ip_address: c.ip_address,
+            user_agent: c.user_agent,
+            moderated_by: c.moderated_by,
+            moderated_at: c.moderated_at,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+        }
+    }
+}
+
+/// Approved comment with its approved replies, as shown on the public site
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Public comment with nested replies")]
+pub struct CommentThreadResponse {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub locale_id: Option<Uuid>,
+    #[schema(example = "Jane Reader")]
+    pub author_name: String,
+    pub author_url: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    /// Replies, oldest first
+    #[schema(no_recursion)]
+    pub replies: Vec<CommentThreadResponse>,
+}
+
+impl From<Comment> for CommentThreadResponse {
+    fn from(c: Comment) -> Self {
+        Self {
+            id: c.id,
+            parent_id: c.parent_id,
+            locale_id: c.locale_id,
+            author_name: c.author_name,
+            author_url: c.author_url,
+            body: c.body,
+            created_at: c.created_at,
+            replies: Vec::new(),
+        }
+    }
+}
+
+/// Acknowledgement of a submitted comment
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Submitted comment awaiting moderation")]
+pub struct CommentSubmittedResponse {
+    pub id: Uuid,
+    pub status: CommentStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated moderation queue
+pub type PaginatedComments = Paginated<CommentResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> CreateCommentRequest {
+        CreateCommentRequest {
+            parent_id: None,
+            locale: Some("en".to_string()),
+            author_name: "Jane Reader".to_string(),
+            author_email: Some("jane@example.com".to_string()),
+            author_url: Some("https://jane.example.com".to_string()),
+            body: "Great post, thanks!".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_create_request_valid() {
+        assert!(request().validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_request_rejects_bad_fields() {
+        let mut req = request();
+        req.body = String::new();
+        assert!(req.validate().is_err());
+
+        let mut req = request();
+        req.author_name = "a".repeat(101);
+        assert!(req.validate().is_err());
+
+        let mut req = request();
+        req.author_email = Some("not-an-email".to_string());
+        assert!(req.validate().is_err());
+
+        let mut req = request();
+        req.author_url = Some("javascript".to_string());
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_bulk_request_id_limits() {
+        let mut req = BulkCommentRequest {
+            ids: vec![],
+            action: BulkAction::UpdateStatus,
+            status: Some(CommentStatus::Approved),
+        };
+        assert!(req.validate().is_err());
+        req.ids = vec![Uuid::new_v4(); 101];
+        assert!(req.validate().is_err());
+        req.ids = vec![Uuid::new_v4()];
+        assert!(req.validate().is_ok());
+    }
+}
@@ -0,0 +1,213 @@
+//! Content block DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::content::LocalizationResponse;
+use crate::models::content::ContentLocalization;
+use crate::models::content_block::{BlockType, ContentBlock};
+
+/// Request to create a content block
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Create a content block")]
+pub struct CreateContentBlockRequest {
+    pub block_type: BlockType,
+
+    /// Block payload, validated against the schema for `block_type`
+    #[schema(value_type = Object, example = json!({"text": "Hello world"}))]
+    #[serde(default = "empty_object")]
+    pub block_data: serde_json::Value,
+
+    /// Insert at this position; omit to append
+    #[schema(example = 0)]
+    #[validate(range(min = 0, max = 9999, message = "Position must be between 0 and 9999"))]
+    pub position: Option<i16>,
+}
+
+/// Request to update a content block
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Update a content block")]
+pub struct UpdateContentBlockRequest {
+    /// Change the block type (requires `block_data` valid for the new type)
+    pub block_type: Option<BlockType>,
+
+    /// Replacement payload, validated against the schema for the block type
+    #[schema(value_type = Option<Object>)]
+    pub block_data: Option<serde_json::Value>,
+}
+
+/// Single item in a block reorder request
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "A content block ID with its new position")]
+pub struct ReorderContentBlockItem {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+
+    #[schema(example = 0)]
+    #[validate(range(
+        min = 0,
+        max = 9999,
+        message = "Block order must be between 0 and 9999"
+    ))]
+    pub block_order: i16,
+}
+
+/// Request to batch-reorder the blocks of a localization
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Batch reorder content blocks")]
+pub struct ReorderContentBlocksRequest {
+    #[validate(nested)]
+    pub items: Vec<ReorderContentBlockItem>,
+}
+
+/// Content block response
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Content block")]
+pub struct ContentBlockResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    #[schema(example = "660e8400-e29b-41d4-a716-446655440000")]
+    pub content_localization_id: Uuid,
+    pub block_type: BlockType,
+    #[schema(example = 0)]
+    pub block_order: i16,
+    #[schema(value_type = Object)]
+    pub block_data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ContentBlock> for ContentBlockResponse {
+    fn from(block: ContentBlock) -> Self {
+        Self {
+            id: block.id,
+            content_localization_id: block.content_localization_id,
+            block_type: block.block_type,
+            block_order: block.block_order,
+            block_data: block.block_data,
+            created_at: block.created_at,
+            updated_at: block.updated_at,
+        }
+    }
+}
+
+/// Localization with its blocks inline
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Content localization with its blocks")]
+pub struct LocalizationWithBlocksResponse {
+    #[serde(flatten)]
+    pub localization: LocalizationResponse,
+    pub blocks: Vec<ContentBlockResponse>,
+}
+
+impl LocalizationWithBlocksResponse {
+    /// Pair each localization with its blocks (blocks may belong to any of them)
+    pub fn from_parts(
+        localizations: Vec<ContentLocalization>,
+        blocks: Vec<ContentBlock>,
+    ) -> Vec<Self> {
+        localizations
+            .into_iter()
+            .map(|loc| {
+                let loc_blocks = blocks
+                    .iter()
+                    .filter(|b| b.content_localization_id == loc.id)
+                    .cloned()
+                    .map(ContentBlockResponse::from)
+                    .collect();
+                Self {
+                    localization: LocalizationResponse::from(loc),
+                    blocks: loc_blocks,
+                }
+            })
+            .collect()
+    }
+}
+
+/// JSON schema for a block type
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(description = "JSON schema for a block type's data")]
+pub struct BlockSchemaResponse {
+    pub block_type: BlockType,
+    #[schema(value_type = Object)]
+    pub schema: serde_json::Value,
+}
+
+fn empty_object() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::content::TranslationStatus;
+    use chrono::Utc;
+
+    fn localization(id: Uuid) -> ContentLocalization {
+        ContentLocalization {
+            id,
+            content_id: Uuid::new_v4(),
+            locale_id: Uuid::new_v4(),
+            title: "Title".to_string(),
+            subtitle: None,
+            excerpt: None,
+            body: None,
+            meta_title: None,
+            meta_description: None,
+            translation_status: TranslationStatus::Pending,
+            translated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn block(localization_id: Uuid, order: i16) -> ContentBlock {
+        ContentBlock {
+            id: Uuid::new_v4(),
+            content_localization_id: localization_id,
+            block_type: BlockType::Paragraph,
+            block_order: order,
+            block_data: serde_json::json!({ "text": "Hi" }),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_create_request_defaults_block_data() {
+        let req: CreateContentBlockRequest =
+            serde_json::from_str(r#"{"block_type": "Divider"}"#).unwrap();
+        assert_eq!(req.block_type, BlockType::Divider);
+        assert_eq!(req.block_data, serde_json::json!({}));
+        assert!(req.position.is_none());
+    }
+
+    #[test]
+    fn test_create_request_rejects_negative_position() {
+        let req: CreateContentBlockRequest =
+            serde_json::from_str(r#"{"block_type": "Divider", "position": -1}"#).unwrap();
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_localization_with_blocks_groups_by_localization() {
+        let en = Uuid::new_v4();
+        let de = Uuid::new_v4();
+        let blocks = vec![block(en, 0), block(de, 0), block(en, 1)];
+
+        let result = LocalizationWithBlocksResponse::from_parts(
+            vec![localization(en), localization(de)],
+            blocks,
+        );
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].blocks.len(), 2);
+        assert_eq!(result[0].blocks[1].block_order, 1);
+        assert_eq!(result[1].blocks.len(), 1);
+
+        let json = serde_json::to_value(&result[0]).unwrap();
+        assert_eq!(json["title"], "Title");
+        assert!(json["blocks"].is_array());
+    }
+}
@@ -0,0 +1,126 @@
+//! Content site DTOs (cross-site syndication)
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::nullable;
+use crate::models::content_site::ContentSite;
+use crate::utils::validation::validate_slug;
+
+/// Request to share existing content with another site
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Share a content item with another site")]
+pub struct ShareContentRequest {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub site_id: Uuid,
+
+    /// Slug on the target site; defaults to the content's own slug
+    #[schema(example = "spring-launch")]
+    #[validate(custom(function = "validate_slug"))]
+    pub site_specific_slug: Option<String>,
+
+    #[serde(default)]
+    pub is_featured: bool,
+
+    #[schema(example = 1)]
+    #[validate(range(
+        min = 0,
+        max = 9999,
+        message = "Display order must be between 0 and 9999"
+    ))]
+    pub display_order: Option<i16>,
+}
+
+/// Request to change how a content item appears on one site
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+#[schema(description = "Update per-site settings; omitted fields are left unchanged")]
+pub struct UpdateContentSiteRequest {
+    /// Use null to fall back to the content's own slug
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>, example = "spring-launch")]
+    #[validate(custom(function = "validate_slug"))]
+    pub site_specific_slug: Option<Option<String>>,
+
+    pub is_featured: Option<bool>,
+
+    /// Use null to remove the pinned position
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<i16>, example = 1)]
+    #[validate(range(
+        min = 0,
+        max = 9999,
+        message = "Display order must be between 0 and 9999"
+    ))]
+    pub display_order: Option<Option<i16>>,
+}
+
+/// A site a content item appears on
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Site assignment of a content item")]
+pub struct ContentSiteResponse {
+    pub content_id: Uuid,
+    pub site_id: Uuid,
+    /// Owning sites created the content; shared sites can be removed again
+    pub is_owner: bool,
+    pub is_featured: bool,
+    pub display_order: Option<i16>,
+    #[schema(example = "spring-launch")]
+    pub site_specific_slug: Option<String>,
+    /// Slug the content is reachable by on this site
+    #[schema(example = "spring-launch")]
+    pub slug: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ContentSite> for ContentSiteResponse {
+    fn from(cs: ContentSite) -> Self {
+        Self {
+            content_id: cs.content_id,
+            site_id: cs.site_id,
+            is_owner: cs.is_owner,
+            is_featured: cs.is_featured,
+            display_order: cs.display_order,
+            site_specific_slug: cs.site_specific_slug,
+            slug: cs.slug,
+            created_at: cs.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_distinguishes_null_from_missing() {
+        let req: UpdateContentSiteRequest =
+            serde_json::from_value(serde_json::json!({ "site_specific_slug": null })).unwrap();
+        assert_eq!(req.site_specific_slug, Some(None));
+        assert_eq!(req.display_order, None);
+
+        let req: UpdateContentSiteRequest =
+            serde_json::from_value(serde_json::json!({ "display_order": 3 })).unwrap();
+        assert_eq!(req.site_specific_slug, None);
+        assert_eq!(req.display_order, Some(Some(3)));
+    }
+
+    #[test]
+    fn test_slug_override_is_validated() {
+        let req = UpdateContentSiteRequest {
+            site_specific_slug: Some(Some("Not A Slug".to_string())),
+            ..Default::default()
+        };
+        assert!(req.validate().is_err());
+
+        let req = ShareContentRequest {
+            site_id: Uuid::new_v4(),
+            site_specific_slug: Some("spring-launch".to_string()),
+            is_featured: true,
+            display_order: Some(10000),
+        };
+        assert!(req.validate().is_err());
+    }
+}
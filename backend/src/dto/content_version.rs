@@ -0,0 +1,121 @@
+//! Content version DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::content_version::ContentVersion;
+use crate::utils::pagination::Paginated;
+
+/// Version list item (without the snapshot payload)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Content version summary")]
+pub struct ContentVersionListItem {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    #[schema(example = "660e8400-e29b-41d4-a716-446655440000")]
+    pub content_id: Uuid,
+    #[schema(example = 3)]
+    pub version_number: i32,
+    #[schema(example = "Updated blog")]
+    pub change_summary: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ContentVersion> for ContentVersionListItem {
+    fn from(v: ContentVersion) -> Self {
+        Self {
+            id: v.id,
+            content_id: v.content_id,
+            version_number: v.version_number,
+            change_summary: v.change_summary,
+            created_by: v.created_by,
+            created_at: v.created_at,
+        }
+    }
+}
+
+/// Full content version including its snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Content version with full snapshot")]
+pub struct ContentVersionResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    #[schema(example = "660e8400-e29b-41d4-a716-446655440000")]
+    pub content_id: Uuid,
+    #[schema(example = 3)]
+    pub version_number: i32,
+    /// Entity row, content fields and localizations at this version
+    pub snapshot: serde_json::Value,
+    #[schema(example = "Updated blog")]
+    pub change_summary: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ContentVersion> for ContentVersionResponse {
+    fn from(v: ContentVersion) -> Self {
+        Self {
+            id: v.id,
+            content_id: v.content_id,
+            version_number: v.version_number,
+            snapshot: v.snapshot,
+            change_summary: v.change_summary,
+            created_by: v.created_by,
+            created_at: v.created_at,
+        }
+    }
+}
+
+/// A single field that differs between two versions
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Field-level difference between two versions")]
+pub struct VersionFieldChange {
+    /// Dotted path of the field, e.g. `entity.author` or `localizations.<locale_id>.title`
+    #[schema(example = "entity.author")]
+    pub field: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+}
+
+/// Diff between two versions of the same content
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Field-by-field diff between two content versions")]
+pub struct VersionDiffResponse {
+    pub content_id: Uuid,
+    #[schema(example = 2)]
+    pub from_version: i32,
+    #[schema(example = 3)]
+    pub to_version: i32,
+    pub changes: Vec<VersionFieldChange>,
+}
+
+/// Paginated content versions
+pub type PaginatedContentVersions = Paginated<ContentVersionListItem>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_item_omits_snapshot() {
+        let version = ContentVersion {
+            id: Uuid::new_v4(),
+            content_id: Uuid::new_v4(),
+            version_number: 2,
+            snapshot: serde_json::json!({ "entity": { "author": "Jane" } }),
+            change_summary: Some("Updated blog".to_string()),
+            created_by: None,
+            created_at: Utc::now(),
+        };
+
+        let json = serde_json::to_value(ContentVersionListItem::from(version.clone())).unwrap();
+        assert!(json.get("snapshot").is_none());
+        assert_eq!(json["version_number"], 2);
+
+        let json = serde_json::to_value(ContentVersionResponse::from(version)).unwrap();
+        assert_eq!(json["snapshot"]["entity"]["author"], "Jane");
+    }
+}
@@ -0,0 +1,100 @@
+//! Edit lock DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::edit_lock::EditLock;
+
+/// Seconds a lock stays valid after it was acquired or last heartbeat
+pub const EDIT_LOCK_TTL_SECONDS: i64 = 120;
+
+/// Request to acquire an edit lock
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+#[schema(description = "Acquire the edit lock on a content item")]
+pub struct AcquireEditLockRequest {
+    /// Name shown to other editors while the lock is held
+    #[validate(length(min = 1, max = 255, message = "Holder name must be 1-255 characters"))]
+    #[schema(example = "Jane Editor")]
+    pub holder_name: Option<String>,
+}
+
+/// An edit lock
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Advisory edit lock")]
+pub struct EditLockResponse {
+    #[schema(example = "660e8400-e29b-41d4-a716-446655440000")]
+    pub content_id: Uuid,
+    /// API key or user ID of the editor holding the lock
+    pub holder_id: Uuid,
+    #[schema(example = "user_abc123")]
+    pub holder_clerk_user_id: Option<String>,
+    #[schema(example = "Jane Editor")]
+    pub holder_name: Option<String>,
+    /// Whether the caller is the holder
+    pub held_by_caller: bool,
+    pub acquired_at: DateTime<Utc>,
+    pub heartbeat_at: DateTime<Utc>,
+    /// The lock is released automatically at this time unless renewed
+    pub expires_at: DateTime<Utc>,
+}
+
+impl EditLockResponse {
+    pub fn from_lock(lock: EditLock, caller_id: Uuid) -> Self {
+        Self {
+            held_by_caller: lock.holder_id == caller_id,
+            content_id: lock.content_id,
+            holder_id: lock.holder_id,
+            holder_clerk_user_id: lock.holder_clerk_user_id,
+            holder_name: lock.holder_name,
+            acquired_at: lock.acquired_at,
+            heartbeat_at: lock.heartbeat_at,
+            expires_at: lock.expires_at,
+        }
+    }
+}
+
+/// Lock state of a content item
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Whether a content item is currently being edited, and by whom")]
+pub struct EditLockStatusResponse {
+    pub locked: bool,
+    /// The active lock; null when nobody is editing
+    pub lock: Option<EditLockResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquire_request_holder_name_length() {
+        assert!(AcquireEditLockRequest::default().validate().is_ok());
+        let mut req = AcquireEditLockRequest {
+            holder_name: Some(String::new()),
+        };
+        assert!(req.validate().is_err());
+        req.holder_name = Some("a".repeat(256));
+        assert!(req.validate().is_err());
+        req.holder_name = Some("Jane Editor".to_string());
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_response_marks_caller_as_holder() {
+        let holder = Uuid::new_v4();
+        let lock = EditLock {
+            content_id: Uuid::new_v4(),
+            holder_id: holder,
+            holder_clerk_user_id: None,
+            holder_name: None,
+            acquired_at: Utc::now(),
+            heartbeat_at: Utc::now(),
+            expires_at: Utc::now(),
+        };
+        assert!(EditLockResponse::from_lock(lock.clone(), holder).held_by_caller);
+        assert!(!EditLockResponse::from_lock(lock, Uuid::new_v4()).held_by_caller);
+    }
+}
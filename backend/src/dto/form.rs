@@ -0,0 +1,323 @@
+//! Form DTOs
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::dto::nullable;
+use crate::models::form::{Form, FormSubmission};
+use crate::utils::pagination::Paginated;
+use crate::utils::validation::validate_slug;
+
+/// Input type of a form field
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum FormFieldType {
+    Text,
+    Textarea,
+    Email,
+    Url,
+    Phone,
+    Number,
+    /// Boolean; a required checkbox must be ticked (e.g. consent)
+    Checkbox,
+    /// One of `options`
+    Select,
+}
+
+/// Field names are used as keys in submissions and CSV columns
+fn validate_field_name(name: &str) -> Result<(), ValidationError> {
+    let valid = name.len() <= 64
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        let mut err = ValidationError::new("invalid_field_name");
+        err.message = Some(
+            "Field name must start with a lowercase letter and contain only lowercase letters, numbers, and underscores (max 64)".into(),
+        );
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Definition of one form field
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+#[schema(description = "Form field definition with localized labels and validation rules")]
+pub struct FormField {
+    /// Key of the value in submissions
+    #[validate(custom(function = "validate_field_name"))]
+    #[schema(example = "email")]
+    pub name: String,
+
+    pub field_type: FormFieldType,
+
+    #[serde(default)]
+    pub required: bool,
+
+    /// Label per locale code; codes must be languages of the site
+    #[serde(default)]
+    #[schema(example = json!({"en": "Email address", "de": "E-Mail-Adresse"}))]
+    pub labels: BTreeMap<String, String>,
+
+    /// Allowed values of a Select field
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+
+    /// Minimum length of text values (characters)
+    pub min_length: Option<u32>,
+
+    /// Maximum length of text values (characters, at most 10000)
+    #[validate(range(max = 10000, message = "max_length cannot exceed 10000"))]
+    pub max_length: Option<u32>,
+
+    /// Regular expression text values must match
+    #[validate(length(max = 500, message = "Pattern cannot exceed 500 characters"))]
+    pub pattern: Option<String>,
+}
+
+/// Request to create a form
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Create a form on a site")]
+pub struct CreateFormRequest {
+    #[validate(custom(function = "validate_slug"))]
+    #[schema(example = "contact")]
+    pub slug: String,
+
+    #[validate(length(min = 1, max = 255, message = "Name must be 1-255 characters"))]
+    #[schema(example = "Contact form")]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 50, message = "A form needs 1-50 fields"))]
+    #[validate(nested)]
+    pub fields: Vec<FormField>,
+
+    /// Where submissions should be sent; defaults to the site's contact email
+    #[validate(email(message = "Must be a valid email address"))]
+    #[validate(length(max = 255, message = "Email cannot exceed 255 characters"))]
+    pub recipient_email: Option<String>,
+
+    /// Delete submissions older than this many days (null keeps them forever)
+    #[validate(range(min = 1, max = 3650, message = "Retention must be 1-3650 days"))]
+    #[schema(example = 90)]
+    pub retention_days: Option<i32>,
+
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Request to update a form
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+#[schema(description = "Update a form; omitted fields are left unchanged")]
+pub struct UpdateFormRequest {
+    #[validate(custom(function = "validate_slug"))]
+    pub slug: Option<String>,
+
+    #[validate(length(min = 1, max = 255, message = "Name must be 1-255 characters"))]
+    pub name: Option<String>,
+
+    /// Replaces all field definitions
+    #[validate(length(min = 1, max = 50, message = "A form needs 1-50 fields"))]
+    #[validate(nested)]
+    pub fields: Option<Vec<FormField>>,
+
+    /// Use null to fall back to the site's contact email
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(email(message = "Must be a valid email address"))]
+    #[validate(length(max = 255, message = "Email cannot exceed 255 characters"))]
+    pub recipient_email: Option<Option<String>>,
+
+    /// Use null to keep submissions forever
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(min = 1, max = 3650, message = "Retention must be 1-3650 days"))]
+    pub retention_days: Option<Option<i32>>,
+
+    pub is_active: Option<bool>,
+}
+
+/// A form as managed by editors
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Form definition")]
+pub struct FormResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    pub site_id: Uuid,
+    #[schema(example = "contact")]
+    pub slug: String,
+    #[schema(example = "Contact form")]
+    pub name: String,
+    pub fields: Vec<FormField>,
+    pub recipient_email: Option<String>,
+    pub retention_days: Option<i32>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Form> for FormResponse {
+    fn from(f: Form) -> Self {
+        Self {
+            fields: f.field_definitions(),
+            id: f.id,
+            site_id: f.site_id,
+            slug: f.slug,
+            name: f.name,
+            recipient_email: f.recipient_email,
+            retention_days: f.retention_days,
+            is_active: f.is_active,
+            created_at: f.created_at,
+            updated_at: f.updated_at,
+        }
+    }
+}
+
+/// A form field with its label resolved for one locale
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LocalizedFormField {
+    pub name: String,
+    pub field_type: FormFieldType,
+    pub required: bool,
+    #[schema(example = "Email address")]
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    pub min_length: Option<u32>,
+    pub max_length: Option<u32>,
+    pub pattern: Option<String>,
+}
+
+/// A form as rendered on the public site
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Form definition with labels for one locale")]
+pub struct PublicFormResponse {
+    pub id: Uuid,
+    #[schema(example = "contact")]
+    pub slug: String,
+    pub name: String,
+    /// Locale the labels are in
+    #[schema(example = "en")]
+    pub locale: Option<String>,
+    pub fields: Vec<LocalizedFormField>,
+}
+
+/// Public form submission
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Values for a form's fields")]
+pub struct SubmitFormRequest {
+    /// Locale code the form was shown in
+    #[validate(length(min = 2, max = 10, message = "Locale must be 2-10 characters"))]
+    #[schema(example = "en")]
+    pub locale: Option<String>,
+
+    /// Field values keyed by field name
+    #[schema(value_type = Object, example = json!({"name": "Jane", "email": "jane@example.com", "message": "Hello!"}))]
+    pub data: serde_json::Map<String, serde_json::Value>,
+
+    /// Bind this to a hidden input; humans leave it empty
+    pub honeypot: Option<String>,
+}
+
+/// Acknowledgement of a submission
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Submission received")]
+pub struct FormSubmittedResponse {
+    pub id: Uuid,
+    pub received_at: DateTime<Utc>,
+}
+
+/// A stored submission
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Form submission")]
+pub struct FormSubmissionResponse {
+    pub id: Uuid,
+    pub form_id: Uuid,
+    pub locale_id: Option<Uuid>,
+    #[schema(value_type = Object)]
+    pub data: serde_json::Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<FormSubmission> for FormSubmissionResponse {
+    fn from(s: FormSubmission) -> Self {
+        Self {
+            id: s.id,
+            form_id: s.form_id,
+            locale_id: s.locale_id,
+            data: s.data,
+            ip_address: s.ip_address,
+            user_agent: s.user_agent,
+            created_at: s.created_at,
+        }
+    }
+}
+
+/// Paginated form list
+pub type PaginatedForms = Paginated<FormResponse>;
+
+/// Paginated submissions inbox
+pub type PaginatedFormSubmissions = Paginated<FormSubmissionResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> FormField {
+        FormField {
+            name: name.to_string(),
+            field_type: FormFieldType::Text,
+            required: true,
+            labels: BTreeMap::new(),
+            options: vec![],
+            min_length: None,
+            max_length: None,
+            pattern: None,
+        }
+    }
+
+    #[test]
+    fn test_field_name_rules() {
+        assert!(field("email").validate().is_ok());
+        assert!(field("first_name2").validate().is_ok());
+        assert!(field("Email").validate().is_err());
+        assert!(field("2nd").validate().is_err());
+        assert!(field("full-name").validate().is_err());
+        assert!(field(&"a".repeat(65)).validate().is_err());
+    }
+
+    #[test]
+    fn test_create_request_validates_fields() {
+        let mut req = CreateFormRequest {
+            slug: "contact".to_string(),
+            name: "Contact".to_string(),
+            fields: vec![field("email")],
+            recipient_email: Some("team@example.com".to_string()),
+            retention_days: Some(90),
+            is_active: true,
+        };
+        assert!(req.validate().is_ok());
+        req.fields.push(field("Bad Name"));
+        assert!(req.validate().is_err());
+        req.fields = vec![];
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_request_null_clears() {
+        let req: UpdateFormRequest =
+            serde_json::from_str(r#"{"recipient_email": null, "name": "Contact"}"#).unwrap();
+        assert_eq!(req.recipient_email, Some(None));
+        assert_eq!(req.retention_days, None);
+        let req: UpdateFormRequest = serde_json::from_str(r#"{"retention_days": 30}"#).unwrap();
+        assert_eq!(req.retention_days, Some(Some(30)));
+    }
+}
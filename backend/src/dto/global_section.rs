@@ -0,0 +1,167 @@
+//! Global section DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::page::validate_section_settings;
+use crate::models::content::ContentStatus;
+use crate::models::global_section::{GlobalSection, GlobalSectionLocalization, GlobalSectionUsage};
+use crate::models::page::SectionType;
+use crate::utils::pagination::Paginated;
+
+/// Request to create a global section
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Create a reusable section for a site")]
+pub struct CreateGlobalSectionRequest {
+    /// Name shown to editors; unique per site
+    #[schema(example = "Footer CTA")]
+    #[validate(length(min = 1, max = 255, message = "Name must be 1-255 characters"))]
+    pub name: String,
+
+    pub section_type: SectionType,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub cover_image_id: Option<Uuid>,
+
+    #[schema(example = "/contact")]
+    #[validate(length(max = 500, message = "CTA route cannot exceed 500 characters"))]
+    pub call_to_action_route: Option<String>,
+
+    #[validate(custom(function = "validate_section_settings"))]
+    pub settings: Option<serde_json::Value>,
+}
+
+/// Request to update a global section
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Update a global section; changes apply to every page using it")]
+pub struct UpdateGlobalSectionRequest {
+    #[schema(example = "Footer CTA")]
+    #[validate(length(min = 1, max = 255, message = "Name must be 1-255 characters"))]
+    pub name: Option<String>,
+
+    pub section_type: Option<SectionType>,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub cover_image_id: Option<Uuid>,
+
+    #[schema(example = "/contact")]
+    #[validate(length(max = 500, message = "CTA route cannot exceed 500 characters"))]
+    pub call_to_action_route: Option<String>,
+
+    #[validate(custom(function = "validate_section_settings"))]
+    pub settings: Option<serde_json::Value>,
+}
+
+/// Global section response
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Reusable section")]
+pub struct GlobalSectionResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    pub site_id: Uuid,
+    #[schema(example = "Footer CTA")]
+    pub name: String,
+    pub section_type: SectionType,
+    pub cover_image_id: Option<Uuid>,
+    #[schema(example = "/contact")]
+    pub call_to_action_route: Option<String>,
+    pub settings: Option<serde_json::Value>,
+    /// Number of sections on non-deleted pages referencing this section
+    #[schema(example = 12)]
+    pub usage_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<GlobalSection> for GlobalSectionResponse {
+    fn from(section: GlobalSection) -> Self {
+        Self {
+            id: section.id,
+            site_id: section.site_id,
+            name: section.name,
+            section_type: section.section_type,
+            cover_image_id: section.cover_image_id,
+            call_to_action_route: section.call_to_action_route,
+            settings: section.settings,
+            usage_count: section.usage_count,
+            created_at: section.created_at,
+            updated_at: section.updated_at,
+        }
+    }
+}
+
+/// Global section localization response
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Global section localization content")]
+pub struct GlobalSectionLocalizationResponse {
+    pub id: Uuid,
+    pub global_section_id: Uuid,
+    pub locale_id: Uuid,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub button_text: Option<String>,
+}
+
+impl From<GlobalSectionLocalization> for GlobalSectionLocalizationResponse {
+    fn from(loc: GlobalSectionLocalization) -> Self {
+        Self {
+            id: loc.id,
+            global_section_id: loc.global_section_id,
+            locale_id: loc.locale_id,
+            title: loc.title,
+            text: loc.text,
+            button_text: loc.button_text,
+        }
+    }
+}
+
+/// Page section referencing a global section
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Where a global section is used")]
+pub struct GlobalSectionUsageResponse {
+    pub page_section_id: Uuid,
+    pub page_id: Uuid,
+    #[schema(example = "/pricing")]
+    pub route: String,
+    #[schema(example = "pricing")]
+    pub slug: Option<String>,
+    pub status: ContentStatus,
+    pub display_order: i16,
+}
+
+impl From<GlobalSectionUsage> for GlobalSectionUsageResponse {
+    fn from(usage: GlobalSectionUsage) -> Self {
+        Self {
+            page_section_id: usage.page_section_id,
+            page_id: usage.page_id,
+            route: usage.route,
+            slug: usage.slug,
+            status: usage.status,
+            display_order: usage.display_order,
+        }
+    }
+}
+
+/// Paginated global section list
+pub type PaginatedGlobalSections = Paginated<GlobalSectionResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_validation() {
+        let mut req = CreateGlobalSectionRequest {
+            name: "Footer CTA".to_string(),
+            section_type: SectionType::Cta,
+            cover_image_id: None,
+            call_to_action_route: Some("/contact".to_string()),
+            settings: Some(serde_json::json!({"variant": "dark"})),
+        };
+        assert!(req.validate().is_ok());
+        req.name = String::new();
+        assert!(req.validate().is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Markdown import/export DTOs
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Counts for one kind of imported content
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+pub struct MarkdownImportCount {
+    #[schema(example = 3)]
+    pub created: u64,
+    /// Matched by slug (blogs) or route (pages) and overwritten from the files
+    #[schema(example = 12)]
+    pub updated: u64,
+    /// Could not be imported; see the warnings
+    #[schema(example = 0)]
+    pub failed: u64,
+}
+
+/// Outcome of a Markdown import
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[schema(description = "What a Markdown import wrote")]
+pub struct MarkdownImportReport {
+    pub blogs: MarkdownImportCount,
+    pub pages: MarkdownImportCount,
+    /// Localizations created or updated, one per file
+    #[schema(example = 30)]
+    pub localizations: u64,
+    /// Files, locales, terms or images that could not be used
+    pub warnings: Vec<String>,
+}
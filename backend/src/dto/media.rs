@@ -1,12 +1,14 @@
 //! Media DTOs
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::models::media::{
-    MediaFile, MediaVariant, MediaVariantType, MediaWithVariants, StorageProvider,
+    CropRect, MediaFile, MediaVariant, MediaVariantType, MediaWithVariants, StorageProvider,
 };
 use crate::utils::pagination::Paginated;
 use crate::utils::validation::validate_url;
@@ -146,6 +148,72 @@ pub struct UpdateMediaRequest {
     pub folder_id: Option<Uuid>,
 }
 
+/// Maximum number of named crops per media file
+pub const MAX_CROPS: usize = 10;
+
+/// Focal point of an image as fractions of its width and height
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct FocalPoint {
+    /// 0.0 = left edge, 1.0 = right edge
+    #[schema(example = 0.62)]
+    #[validate(range(min = 0.0, max = 1.0, message = "x must be between 0 and 1"))]
+    pub x: f64,
+    /// 0.0 = top edge, 1.0 = bottom edge
+    #[schema(example = 0.35)]
+    #[validate(range(min = 0.0, max = 1.0, message = "y must be between 0 and 1"))]
+    pub y: f64,
+}
+
+/// Request to set the focal point and crops of an image
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Replace the focal point and named crops of an image")]
+pub struct UpdateArtDirectionRequest {
+    /// Point kept in view when the image is cropped to a different aspect ratio (null = center)
+    #[validate(nested)]
+    pub focal_point: Option<FocalPoint>,
+
+    /// Crop rectangles by name, in source image pixels
+    #[serde(default)]
+    #[validate(custom(function = "validate_crops"))]
+    pub crops: BTreeMap<String, CropRect>,
+}
+
+/// Validate crop names and sizes; bounds are checked against the image later
+fn validate_crops(crops: &BTreeMap<String, CropRect>) -> Result<(), validator::ValidationError> {
+    if crops.len() > MAX_CROPS {
+        let mut err = validator::ValidationError::new("too_many_crops");
+        err.message = Some(format!("At most {} crops are allowed", MAX_CROPS).into());
+        return Err(err);
+    }
+    for (name, rect) in crops {
+        let valid_name = name.len() <= 32
+            && name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '_' | '-'));
+        if !valid_name {
+            let mut err = validator::ValidationError::new("invalid_crop_name");
+            err.message = Some(
+                format!(
+                    "Invalid crop name '{}': use up to 32 letters, digits, ':', '_' or '-'",
+                    name
+                )
+                .into(),
+            );
+            return Err(err);
+        }
+        if rect.width == 0 || rect.height == 0 {
+            let mut err = validator::ValidationError::new("empty_crop");
+            err.message = Some(format!("Crop '{}' must not be empty", name).into());
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
 /// Request to add media metadata (alt text, caption, etc.)
 #[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
 #[schema(description = "Add media metadata")]
@@ -285,6 +353,49 @@ impl From<MediaVariant> for MediaVariantResponse {
     }
 }
 
+/// Signed image transformation URL
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Signed URL of an on-the-fly image transformation")]
+pub struct TransformUrlResponse {
+    /// Path relative to the API host; usable without an API key
+    #[schema(
+        example = "/api/v1/media/550e8400-e29b-41d4-a716-446655440000/transform?w=400&h=300&fit=cover&format=webp&s=3f2a..."
+    )]
+    pub url: String,
+}
+
+/// Signed rendition of a crop
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CropSourceResponse {
+    #[schema(example = 800)]
+    pub width: u32,
+    #[schema(example = 450)]
+    pub height: u32,
+    /// Signed transformation URL, relative to the API host
+    #[schema(
+        example = "/api/v1/media/550e8400-e29b-41d4-a716-446655440000/transform?crop=16:9&w=800&format=webp&s=3f2a..."
+    )]
+    pub url: String,
+}
+
+/// Named crop of an image
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Named crop with signed renditions for a srcset")]
+pub struct MediaCropResponse {
+    #[schema(example = "16:9")]
+    pub name: String,
+    #[schema(example = 0)]
+    pub x: u32,
+    #[schema(example = 120)]
+    pub y: u32,
+    #[schema(example = 1600)]
+    pub width: u32,
+    #[schema(example = 900)]
+    pub height: u32,
+    /// WebP renditions, narrowest first
+    pub sources: Vec<CropSourceResponse>,
+}
+
 /// Full media response with variants
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[schema(description = "Full media file with variants")]
@@ -313,6 +424,10 @@ pub struct MediaResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub variants: Vec<MediaVariantResponse>,
+    /// Focal point (null = center)
+    pub focal_point: Option<FocalPoint>,
+    /// Named crops, ordered by name
+    pub crops: Vec<MediaCropResponse>,
 }
 
 impl From<MediaWithVariants> for MediaResponse {
@@ -336,6 +451,22 @@ impl From<MediaWithVariants> for MediaResponse {
                 .into_iter()
                 .map(MediaVariantResponse::from)
                 .collect(),
+            focal_point: media
+                .focal_x
+                .zip(media.focal_y)
+                .map(|(x, y)| FocalPoint { x, y }),
+            crops: serde_json::from_value::<BTreeMap<String, CropRect>>(media.crops)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, rect)| MediaCropResponse {
+                    name,
+                    x: rect.x,
+                    y: rect.y,
+                    width: rect.width,
+                    height: rect.height,
+                    sources: Vec::new(),
+                })
+                .collect(),
         }
     }
 }
@@ -475,6 +606,39 @@ mod tests {
         assert!(request.validate().is_ok());
     }
 
+    #[test]
+    fn test_update_art_direction_request_validation() {
+        let rect = CropRect {
+            x: 0,
+            y: 120,
+            width: 1600,
+            height: 900,
+        };
+        let request = UpdateArtDirectionRequest {
+            focal_point: Some(FocalPoint { x: 0.6, y: 0.4 }),
+            crops: BTreeMap::from([("16:9".to_string(), rect)]),
+        };
+        assert!(request.validate().is_ok());
+
+        let outside = UpdateArtDirectionRequest {
+            focal_point: Some(FocalPoint { x: 1.2, y: 0.4 }),
+            ..request.clone()
+        };
+        assert!(outside.validate().is_err());
+
+        let bad_name = UpdateArtDirectionRequest {
+            focal_point: None,
+            crops: BTreeMap::from([("hero crop".to_string(), rect)]),
+        };
+        assert!(bad_name.validate().is_err());
+
+        let empty = UpdateArtDirectionRequest {
+            focal_point: None,
+            crops: BTreeMap::from([("1:1".to_string(), CropRect { width: 0, ..rect })]),
+        };
+        assert!(empty.validate().is_err());
+    }
+
     #[test]
     fn test_media_list_item_serialization() {
         let item = MediaListItem {
@@ -8,27 +8,56 @@ pub mod auth;
 pub mod blog;
 pub mod bulk;
 pub mod clerk;
+pub mod comment;
 pub mod config;
 pub mod content;
+pub mod content_block;
+pub mod content_site;
 pub mod content_template;
+pub mod content_version;
 pub mod cv;
 pub mod document;
+pub mod edit_lock;
 pub mod environment;
+pub mod form;
+pub mod global_section;
 pub mod health;
 pub mod legal;
 pub mod locale;
+pub mod markdown_transfer;
 pub mod media;
 pub mod media_folder;
 pub mod navigation;
 pub mod navigation_menu;
 pub mod notification;
 pub mod page;
+pub mod page_template;
+pub mod preview_token;
+pub mod promotion;
 pub mod redirect;
+pub mod release;
 pub mod review;
+pub mod search;
 pub mod site;
 pub mod site_locale;
 pub mod site_membership;
 pub mod site_settings;
+pub mod site_transfer;
 pub mod social;
 pub mod taxonomy;
+pub mod trash;
 pub mod webhook;
+pub mod wordpress_import;
+
+use serde::{Deserialize, Deserializer};
+
+/// Distinguish an explicit `null` (clear the value) from a missing field (keep it).
+///
+/// Use with `#[serde(default, deserialize_with = "nullable")]` on `Option<Option<T>>`.
+pub(crate) fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
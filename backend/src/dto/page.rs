@@ -5,9 +5,11 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::dto::redirect::validate_destination_path;
 use crate::models::content::ContentStatus;
+use crate::models::environment::EnvironmentType;
 use crate::models::page::{
-    PageSection, PageSectionLocalization, PageType, PageWithContent, SectionType,
+    PageSection, PageSectionLocalization, PageTreeRow, PageType, PageWithContent, SectionType,
 };
 use crate::utils::pagination::Paginated;
 use crate::utils::validation::{validate_json_depth, validate_route, validate_slug};
@@ -65,6 +67,10 @@ pub struct CreatePageRequest {
     /// Site IDs to associate this page with
     #[validate(length(min = 1, message = "At least one site ID is required"))]
     pub site_ids: Vec<Uuid>,
+
+    /// Environment to create the page in (default: the live environment)
+    #[schema(example = "Staging")]
+    pub environment: Option<EnvironmentType>,
 }
 
 /// Request to update a page
@@ -113,6 +119,12 @@ pub struct UpdatePageRequest {
 
     pub publish_start: Option<DateTime<Utc>>,
     pub publish_end: Option<DateTime<Utc>>,
+
+    /// When archiving, redirect the old URL here (path or absolute URL)
+    #[schema(example = "/blog")]
+    #[validate(length(max = 2000, message = "Redirect target cannot exceed 2000 characters"))]
+    #[validate(custom(function = "validate_destination_path"))]
+    pub redirect_to: Option<String>,
 }
 
 /// Request to create a page section
@@ -141,7 +153,7 @@ pub struct CreatePageSectionRequest {
 }
 
 /// Validate section settings JSON
-fn validate_section_settings(
+pub fn validate_section_settings(
     settings: &serde_json::Value,
 ) -> Result<(), validator::ValidationError> {
     validate_json_depth(settings, 5)
@@ -172,6 +184,22 @@ pub struct UpdatePageSectionRequest {
     pub settings: Option<serde_json::Value>,
 }
 
+/// Request to place a global section on a page
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[schema(description = "Reference a global section from a page")]
+pub struct AttachGlobalSectionRequest {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub global_section_id: Uuid,
+
+    #[schema(example = 0)]
+    #[validate(range(
+        min = 0,
+        max = 9999,
+        message = "Display order must be between 0 and 9999"
+    ))]
+    pub display_order: i16,
+}
+
 /// Page list item response
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[schema(description = "Page summary for lists")]
@@ -278,6 +306,9 @@ pub struct PageSectionResponse {
     #[schema(example = "/contact")]
     pub call_to_action_route: Option<String>,
     pub settings: Option<serde_json::Value>,
+    /// Set when the section references a global section; type, cover image, CTA,
+    /// settings and localizations then come from the global section
+    pub global_section_id: Option<Uuid>,
 }
 
 impl From<PageSection> for PageSectionResponse {
@@ -290,6 +321,7 @@ impl From<PageSection> for PageSectionResponse {
             cover_image_id: section.cover_image_id,
             call_to_action_route: section.call_to_action_route,
             settings: section.settings,
+            global_section_id: section.global_section_id,
         }
     }
 }
@@ -339,6 +371,111 @@ pub struct UpsertSectionLocalizationRequest {
     pub button_text: Option<String>,
 }
 
+/// Request to move a page to another parent
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Move a page within the hierarchy")]
+pub struct MovePageRequest {
+    /// New parent page; null or omitted moves the page to the top level
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub parent_page_id: Option<Uuid>,
+
+    /// Rebase the page's route onto the new parent's route and rewrite the
+    /// route prefix of all descendants. Old routes of published pages redirect
+    /// to the new ones.
+    #[serde(default)]
+    pub rewrite_routes: bool,
+}
+
+/// A route rewritten by a page move
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PageRouteChange {
+    pub page_id: Uuid,
+    #[schema(example = "/about/team")]
+    pub old_route: String,
+    #[schema(example = "/company/team")]
+    pub new_route: String,
+}
+
+/// Result of a page move
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Moved page and the routes that changed")]
+pub struct MovePageResponse {
+    pub page: PageResponse,
+    /// Empty unless `rewrite_routes` was set
+    pub route_changes: Vec<PageRouteChange>,
+}
+
+/// Page in the site hierarchy
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Page with its child pages")]
+pub struct PageTreeNode {
+    pub id: Uuid,
+    pub parent_page_id: Option<Uuid>,
+    #[schema(example = "/about")]
+    pub route: String,
+    #[schema(example = "about")]
+    pub slug: Option<String>,
+    /// Title in the requested locale, falling back to the default language, the slug, then the route
+    #[schema(example = "About us")]
+    pub title: String,
+    pub page_type: PageType,
+    pub status: ContentStatus,
+    pub is_in_navigation: bool,
+    pub navigation_order: Option<i16>,
+    /// Child pages in navigation order
+    #[schema(no_recursion)]
+    pub children: Vec<PageTreeNode>,
+}
+
+impl From<PageTreeRow> for PageTreeNode {
+    fn from(row: PageTreeRow) -> Self {
+        Self {
+            title: display_title(&row),
+            id: row.id,
+            parent_page_id: row.parent_page_id,
+            route: row.route,
+            slug: row.slug,
+            page_type: row.page_type,
+            status: row.status,
+            is_in_navigation: row.is_in_navigation,
+            navigation_order: row.navigation_order,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// One step of a breadcrumb trail
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Breadcrumb entry")]
+pub struct BreadcrumbItem {
+    pub id: Uuid,
+    #[schema(example = "/about")]
+    pub route: String,
+    #[schema(example = "about")]
+    pub slug: Option<String>,
+    #[schema(example = "About us")]
+    pub title: String,
+}
+
+impl From<&PageTreeRow> for BreadcrumbItem {
+    fn from(row: &PageTreeRow) -> Self {
+        Self {
+            id: row.id,
+            route: row.route.clone(),
+            slug: row.slug.clone(),
+            title: display_title(row),
+        }
+    }
+}
+
+/// Localized title, or the slug / route when the page has no localization
+fn display_title(row: &PageTreeRow) -> String {
+    row.title
+        .clone()
+        .or_else(|| row.slug.clone())
+        .unwrap_or_else(|| row.route.clone())
+}
+
 /// Paginated page list
 pub type PaginatedPages = Paginated<PageListItem>;
 
@@ -361,6 +498,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -379,6 +517,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -398,6 +537,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![Uuid::new_v4()],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -417,6 +557,7 @@ mod tests {
             publish_start: None,
             publish_end: None,
             site_ids: vec![],
+            environment: None,
         };
         let result = request.validate();
         assert!(result.is_err());
@@ -468,6 +609,7 @@ mod tests {
             status: Some(ContentStatus::Published),
             publish_start: None,
             publish_end: None,
+            redirect_to: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -485,6 +627,7 @@ mod tests {
             status: None,
             publish_start: None,
             publish_end: None,
+            redirect_to: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -0,0 +1,258 @@
+//! Page template DTOs
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::page::validate_section_settings;
+use crate::models::content::ContentStatus;
+use crate::models::page::{PageType, SectionType};
+use crate::models::page_template::PageTemplate;
+use crate::utils::pagination::Paginated;
+use crate::utils::validation::{validate_route, validate_slug};
+
+/// Placeholder text of a template section in one language
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+#[schema(description = "Placeholder text for a section localization")]
+pub struct SectionPlaceholder {
+    #[schema(example = "Your headline")]
+    #[validate(length(max = 500, message = "Title cannot exceed 500 characters"))]
+    pub title: Option<String>,
+
+    #[schema(example = "Describe what this page is about.")]
+    #[validate(length(max = 50000, message = "Text cannot exceed 50000 characters"))]
+    pub text: Option<String>,
+
+    #[schema(example = "Learn more")]
+    #[validate(length(max = 200, message = "Button text cannot exceed 200 characters"))]
+    pub button_text: Option<String>,
+}
+
+/// One section of a template layout; sections are created in list order
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+#[schema(description = "Section of a page template with default settings and placeholder text")]
+pub struct PageTemplateSection {
+    pub section_type: SectionType,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub cover_image_id: Option<Uuid>,
+
+    #[schema(example = "/contact")]
+    #[validate(length(max = 500, message = "CTA route cannot exceed 500 characters"))]
+    pub call_to_action_route: Option<String>,
+
+    #[validate(custom(function = "validate_section_settings"))]
+    pub settings: Option<serde_json::Value>,
+
+    /// Placeholder text per locale code; codes must be languages of the site
+    #[serde(default)]
+    #[validate(nested)]
+    #[schema(example = json!({"en": {"title": "Your headline"}, "de": {"title": "Ihre Überschrift"}}))]
+    pub placeholders: BTreeMap<String, SectionPlaceholder>,
+}
+
+/// Request to create a page template
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Create a page template")]
+pub struct CreatePageTemplateRequest {
+    #[schema(example = "Landing page")]
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Name must be between 1 and 200 characters"
+    ))]
+    pub name: String,
+
+    #[schema(example = "Hero, features and a call to action")]
+    #[validate(length(max = 2000, message = "Description cannot exceed 2000 characters"))]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub page_type: PageType,
+
+    /// Value for the `template` field of pages created from this template
+    #[schema(example = "landing")]
+    #[validate(length(max = 100, message = "Template name cannot exceed 100 characters"))]
+    pub template: Option<String>,
+
+    #[serde(default)]
+    #[validate(length(max = 50, message = "A template can have at most 50 sections"))]
+    #[validate(nested)]
+    pub sections: Vec<PageTemplateSection>,
+
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Request to update a page template
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Update a page template; omitted fields are left unchanged")]
+pub struct UpdatePageTemplateRequest {
+    #[schema(example = "Landing page")]
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Name must be between 1 and 200 characters"
+    ))]
+    pub name: Option<String>,
+
+    #[validate(length(max = 2000, message = "Description cannot exceed 2000 characters"))]
+    pub description: Option<String>,
+
+    pub page_type: Option<PageType>,
+
+    #[validate(length(max = 100, message = "Template name cannot exceed 100 characters"))]
+    pub template: Option<String>,
+
+    /// Replaces the whole section layout
+    #[validate(length(max = 50, message = "A template can have at most 50 sections"))]
+    #[validate(nested)]
+    pub sections: Option<Vec<PageTemplateSection>>,
+
+    pub is_active: Option<bool>,
+}
+
+/// Request to create a page from a template
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Create a page with the template's page type and section layout")]
+pub struct CreatePageFromTemplateRequest {
+    #[schema(example = "/spring-sale")]
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Route must be between 1 and 500 characters"
+    ))]
+    #[validate(custom(function = "validate_route"))]
+    pub route: String,
+
+    #[schema(example = "spring-sale")]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Slug must be between 1 and 100 characters"
+    ))]
+    #[validate(custom(function = "validate_slug"))]
+    pub slug: String,
+
+    #[serde(default)]
+    pub is_in_navigation: bool,
+
+    #[validate(range(
+        min = 0,
+        max = 9999,
+        message = "Navigation order must be between 0 and 9999"
+    ))]
+    pub navigation_order: Option<i16>,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub parent_page_id: Option<Uuid>,
+
+    #[serde(default)]
+    pub status: ContentStatus,
+
+    pub publish_start: Option<DateTime<Utc>>,
+    pub publish_end: Option<DateTime<Utc>>,
+
+    /// Sites of the new page; defaults to the template's site, which must be included
+    pub site_ids: Option<Vec<Uuid>>,
+}
+
+/// Page template response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Page template details")]
+pub struct PageTemplateResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    pub site_id: Uuid,
+    #[schema(example = "Landing page")]
+    pub name: String,
+    pub description: Option<String>,
+    pub page_type: PageType,
+    #[schema(example = "landing")]
+    pub template: Option<String>,
+    pub sections: Vec<PageTemplateSection>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PageTemplate> for PageTemplateResponse {
+    fn from(t: PageTemplate) -> Self {
+        Self {
+            sections: t.section_layout(),
+            id: t.id,
+            site_id: t.site_id,
+            name: t.name,
+            description: t.description,
+            page_type: t.page_type,
+            template: t.template,
+            is_active: t.is_active,
+            created_at: t.created_at,
+            updated_at: t.updated_at,
+        }
+    }
+}
+
+/// Paginated page templates response
+pub type PaginatedPageTemplates = Paginated<PageTemplateResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(settings: Option<serde_json::Value>) -> PageTemplateSection {
+        PageTemplateSection {
+            section_type: SectionType::Hero,
+            cover_image_id: None,
+            call_to_action_route: None,
+            settings,
+            placeholders: BTreeMap::from([(
+                "en".to_string(),
+                SectionPlaceholder {
+                    title: Some("Your headline".to_string()),
+                    ..Default::default()
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_create_page_template_validation() {
+        let mut req = CreatePageTemplateRequest {
+            name: "Landing page".to_string(),
+            description: None,
+            page_type: PageType::Landing,
+            template: None,
+            sections: vec![section(Some(serde_json::json!({"layout": "split"})))],
+            is_active: true,
+        };
+        assert!(req.validate().is_ok());
+
+        req.sections[0]
+            .placeholders
+            .get_mut("en")
+            .unwrap()
+            .button_text = Some("a".repeat(201));
+        assert!(req.validate().is_err());
+
+        let too_deep = serde_json::json!({"a": {"b": {"c": {"d": {"e": {"f": {}}}}}}});
+        req.sections = vec![section(Some(too_deep))];
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_section_placeholders_default_to_empty() {
+        let section: PageTemplateSection =
+            serde_json::from_value(serde_json::json!({"section_type": "Cta"})).unwrap();
+        assert!(section.placeholders.is_empty());
+        assert!(section.settings.is_none());
+    }
+}
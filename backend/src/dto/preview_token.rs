@@ -0,0 +1,101 @@
+//! Preview token DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::preview_token::PreviewToken;
+use crate::utils::pagination::Paginated;
+
+/// Default token lifetime in hours
+pub const DEFAULT_EXPIRES_IN_HOURS: i64 = 24;
+
+/// Request to mint a preview token
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+#[schema(description = "Mint a preview token for one content item")]
+pub struct CreatePreviewTokenRequest {
+    /// Restrict the token to one locale
+    pub locale_id: Option<Uuid>,
+
+    /// Lifetime in hours (default 24, max 720)
+    #[validate(range(min = 1, max = 720, message = "Expiry must be between 1 and 720 hours"))]
+    #[schema(example = 24)]
+    pub expires_in_hours: Option<i64>,
+}
+
+/// Preview token (without the token string)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Draft preview token")]
+pub struct PreviewTokenResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    #[schema(example = "660e8400-e29b-41d4-a716-446655440000")]
+    pub content_id: Uuid,
+    /// Locale the token is scoped to (all locales when null)
+    pub locale_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[schema(example = 3)]
+    pub use_count: i32,
+    /// Neither expired nor revoked
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PreviewToken> for PreviewTokenResponse {
+    fn from(t: PreviewToken) -> Self {
+        Self {
+            is_active: t.is_active(),
+            id: t.id,
+            content_id: t.content_id,
+            locale_id: t.locale_id,
+            expires_at: t.expires_at,
+            revoked_at: t.revoked_at,
+            last_used_at: t.last_used_at,
+            use_count: t.use_count,
+            created_by: t.created_by,
+            created_at: t.created_at,
+        }
+    }
+}
+
+/// Newly minted preview token
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Preview token including the shareable token string")]
+pub struct CreatePreviewTokenResponse {
+    /// Pass as `preview_token` to the by-slug / by-route endpoints - only shown at creation!
+    #[schema(example = "oyp_550e8400e29b41d4a716446655440000.9f86d081884c7d65...")]
+    pub token: String,
+    #[serde(flatten)]
+    pub preview: PreviewTokenResponse,
+}
+
+/// Paginated preview token list
+pub type PaginatedPreviewTokens = Paginated<PreviewTokenResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_default_valid() {
+        assert!(CreatePreviewTokenRequest::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_request_expiry_range() {
+        let mut req = CreatePreviewTokenRequest {
+            locale_id: None,
+            expires_in_hours: Some(0),
+        };
+        assert!(req.validate().is_err());
+        req.expires_in_hours = Some(721);
+        assert!(req.validate().is_err());
+        req.expires_in_hours = Some(720);
+        assert!(req.validate().is_ok());
+    }
+}
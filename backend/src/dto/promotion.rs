@@ -0,0 +1,160 @@
+//! Environment promotion DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::content_version::VersionFieldChange;
+use crate::models::environment::EnvironmentType;
+use crate::models::environment_promotion::EnvironmentPromotion;
+use crate::utils::pagination::Paginated;
+
+/// Request to preview or apply a promotion
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Promote a site's content from one environment to another")]
+pub struct PromotionRequest {
+    #[schema(example = "Staging")]
+    pub source: EnvironmentType,
+
+    #[schema(example = "Production")]
+    pub target: EnvironmentType,
+
+    /// Limit the promotion to these content items (IDs in the source environment)
+    #[validate(length(max = 500, message = "At most 500 content items per promotion"))]
+    pub content_ids: Option<Vec<Uuid>>,
+
+    /// Checksum of a previewed changeset; applying fails with 412 if the changeset has changed since
+    #[schema(example = "3f0a9c...")]
+    pub checksum: Option<String>,
+}
+
+/// What a promotion does to one content item in the target environment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum PromotionAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// Part of a content item touched by a change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+pub enum PromotionArea {
+    Content,
+    Localizations,
+    Media,
+    Navigation,
+    Taxonomy,
+}
+
+/// A content item that differs between the two environments
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Changes to one content item")]
+pub struct PromotionItem {
+    pub source_content_id: Uuid,
+    /// Copy in the target environment; absent when it is created by the promotion
+    pub target_content_id: Option<Uuid>,
+    #[schema(example = "blog")]
+    pub entity_type: String,
+    #[schema(example = "spring-launch")]
+    pub slug: Option<String>,
+    pub action: PromotionAction,
+    pub areas: Vec<PromotionArea>,
+    /// Field-level diff from the target copy to the source copy
+    pub changes: Vec<VersionFieldChange>,
+}
+
+/// Changes a promotion would apply
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Diff between two environments of a site")]
+pub struct PromotionChangeset {
+    pub site_id: Uuid,
+    pub source_environment_id: Uuid,
+    pub target_environment_id: Uuid,
+    /// Pass back when applying to make sure the reviewed changeset is the one applied
+    pub checksum: String,
+    pub items: Vec<PromotionItem>,
+}
+
+/// An applied promotion
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Record of an applied promotion")]
+pub struct PromotionResponse {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub source_environment_id: Uuid,
+    pub target_environment_id: Uuid,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub deleted_count: i32,
+    pub promoted_by: Option<Uuid>,
+    pub items: Vec<PromotionItem>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<EnvironmentPromotion> for PromotionResponse {
+    fn from(p: EnvironmentPromotion) -> Self {
+        Self {
+            items: serde_json::from_value(p.changeset).unwrap_or_default(),
+            id: p.id,
+            site_id: p.site_id,
+            source_environment_id: p.source_environment_id,
+            target_environment_id: p.target_environment_id,
+            created_count: p.created_count,
+            updated_count: p.updated_count,
+            deleted_count: p.deleted_count,
+            promoted_by: p.promoted_by,
+            created_at: p.created_at,
+        }
+    }
+}
+
+/// Paginated promotion history
+pub type PaginatedPromotions = Paginated<PromotionResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_accepts_environment_names() {
+        let req: PromotionRequest = serde_json::from_value(serde_json::json!({
+            "source": "Staging",
+            "target": "Production"
+        }))
+        .unwrap();
+        assert_eq!(req.source, EnvironmentType::Staging);
+        assert_eq!(req.target, EnvironmentType::Production);
+        assert!(req.content_ids.is_none());
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_response_reads_stored_changeset() {
+        let item = PromotionItem {
+            source_content_id: Uuid::new_v4(),
+            target_content_id: None,
+            entity_type: "blog".to_string(),
+            slug: Some("spring-launch".to_string()),
+            action: PromotionAction::Create,
+            areas: vec![PromotionArea::Content],
+            changes: vec![],
+        };
+        let promotion = EnvironmentPromotion {
+            id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            source_environment_id: Uuid::new_v4(),
+            target_environment_id: Uuid::new_v4(),
+            changeset: serde_json::to_value(vec![item]).unwrap(),
+            created_count: 1,
+            updated_count: 0,
+            deleted_count: 0,
+            promoted_by: None,
+            created_at: Utc::now(),
+        };
+        let response = PromotionResponse::from(promotion);
+        assert_eq!(response.items.len(), 1);
+        assert_eq!(response.items[0].action, PromotionAction::Create);
+    }
+}
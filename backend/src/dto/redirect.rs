@@ -6,13 +6,15 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::models::redirect::Redirect;
+use crate::models::redirect::{Redirect, RedirectMatchType, RedirectQueryHandling};
 use crate::utils::pagination::Paginated;
 
 /// Request to create a redirect
 #[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 #[schema(description = "Create a URL redirect")]
+#[validate(schema(function = "validate_create_redirect"))]
 pub struct CreateRedirectRequest {
+    /// Exact path, wildcard (`/blog/*`) or regex (`/posts/(\d+)`), depending on `match_type`
     #[schema(example = "/old-blog-post")]
     #[validate(length(
         min = 1,
@@ -22,6 +24,7 @@ pub struct CreateRedirectRequest {
     #[validate(custom(function = "validate_source_path"))]
     pub source_path: String,
 
+    /// Omit for 410 Gone. Pattern redirects may reference captures as `$1` or `${1}`.
     #[schema(example = "/new-blog-post")]
     #[validate(length(
         min = 1,
@@ -29,7 +32,7 @@ pub struct CreateRedirectRequest {
         message = "Destination path must be between 1 and 2000 characters"
     ))]
     #[validate(custom(function = "validate_destination_path"))]
-    pub destination_path: String,
+    pub destination_path: Option<String>,
 
     #[schema(example = 301)]
     #[validate(custom(function = "validate_redirect_status_code"))]
@@ -42,6 +45,12 @@ pub struct CreateRedirectRequest {
     #[validate(length(max = 500, message = "Description cannot exceed 500 characters"))]
     pub description: Option<String>,
 
+    /// Defaults to `Exact`
+    pub match_type: Option<RedirectMatchType>,
+
+    /// Defaults to `Ignore`
+    pub query_handling: Option<RedirectQueryHandling>,
+
     /// Site ID (overridden by path param)
     #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
     pub site_id: Uuid,
@@ -69,6 +78,7 @@ pub struct UpdateRedirectRequest {
     #[validate(custom(function = "validate_destination_path"))]
     pub destination_path: Option<String>,
 
+    /// Changing to 410 clears the destination
     #[schema(example = 302)]
     #[validate(custom(function = "validate_redirect_status_code"))]
     pub status_code: Option<i16>,
@@ -79,6 +89,10 @@ pub struct UpdateRedirectRequest {
     #[schema(example = "Updated description")]
     #[validate(length(max = 500, message = "Description cannot exceed 500 characters"))]
     pub description: Option<String>,
+
+    pub match_type: Option<RedirectMatchType>,
+
+    pub query_handling: Option<RedirectQueryHandling>,
 }
 
 /// Redirect response
@@ -91,14 +105,26 @@ pub struct RedirectResponse {
     pub site_id: Uuid,
     #[schema(example = "/old-blog-post")]
     pub source_path: String,
+    /// `null` for 410 Gone
     #[schema(example = "/new-blog-post")]
-    pub destination_path: String,
+    pub destination_path: Option<String>,
     #[schema(example = 301)]
     pub status_code: i16,
     #[schema(example = true)]
     pub is_active: bool,
     #[schema(example = "Blog post slug changed")]
     pub description: Option<String>,
+    /// Created automatically when a slug or route changed, or content was removed with a fallback
+    #[schema(example = false)]
+    pub is_auto_generated: bool,
+    /// Content whose URL change produced this redirect
+    pub content_id: Option<Uuid>,
+    pub match_type: RedirectMatchType,
+    pub query_handling: RedirectQueryHandling,
+    /// Lookups that resolved to this redirect
+    #[schema(example = 42)]
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -113,6 +139,12 @@ impl From<Redirect> for RedirectResponse {
             status_code: r.status_code,
             is_active: r.is_active,
             description: r.description,
+            is_auto_generated: r.is_auto_generated,
+            content_id: r.content_id,
+            match_type: r.match_type,
+            query_handling: r.query_handling,
+            hit_count: r.hit_count,
+            last_hit_at: r.last_hit_at,
             created_at: r.created_at,
             updated_at: r.updated_at,
         }
@@ -123,8 +155,9 @@ impl From<Redirect> for RedirectResponse {
 #[derive(Debug, Clone, Serialize, ToSchema)]
 #[schema(description = "Redirect lookup result")]
 pub struct RedirectLookupResponse {
+    /// Final destination with captures substituted; `null` for 410 Gone
     #[schema(example = "/new-blog-post")]
-    pub destination_path: String,
+    pub destination_path: Option<String>,
     #[schema(example = 301)]
     pub status_code: i16,
 }
@@ -132,23 +165,195 @@ pub struct RedirectLookupResponse {
 /// Paginated redirects response
 pub type PaginatedRedirects = Paginated<RedirectResponse>;
 
-/// Validate source path: must start with `/`, no `..`
+/// One redirect in a CSV/JSON import or export.
+///
+/// CSV files use these field names as header columns; only `source_path` is required.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Redirect row for bulk import and export")]
+pub struct RedirectTransferRow {
+    #[schema(example = "/old-blog-post")]
+    pub source_path: String,
+    #[schema(example = "/new-blog-post")]
+    #[serde(default)]
+    pub destination_path: Option<String>,
+    /// Defaults to 301
+    #[schema(example = 301)]
+    #[serde(default)]
+    pub status_code: Option<i16>,
+    #[serde(default)]
+    pub match_type: Option<RedirectMatchType>,
+    #[serde(default)]
+    pub query_handling: Option<RedirectQueryHandling>,
+    #[schema(example = true)]
+    #[serde(default)]
+    pub is_active: Option<bool>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl RedirectTransferRow {
+    /// Equivalent create request for validation and insertion
+    pub fn to_create_request(&self, site_id: Uuid) -> CreateRedirectRequest {
+        CreateRedirectRequest {
+            source_path: self.source_path.clone(),
+            destination_path: self.destination_path.clone().filter(|d| !d.is_empty()),
+            status_code: self.status_code.unwrap_or(301),
+            is_active: self.is_active,
+            description: self.description.clone().filter(|d| !d.is_empty()),
+            match_type: self.match_type,
+            query_handling: self.query_handling,
+            site_id,
+        }
+    }
+}
+
+impl From<Redirect> for RedirectTransferRow {
+    fn from(r: Redirect) -> Self {
+        Self {
+            source_path: r.source_path,
+            destination_path: r.destination_path,
+            status_code: Some(r.status_code),
+            match_type: Some(r.match_type),
+            query_handling: Some(r.query_handling),
+            is_active: Some(r.is_active),
+            description: r.description,
+        }
+    }
+}
+
+/// Kind of problem found while checking an import
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum RedirectImportIssueKind {
+    /// The row could not be parsed or failed validation
+    Invalid,
+    /// The source path already has a redirect on the site (`uq_redirects_site_source`)
+    Duplicate,
+    /// The source path appears more than once in the file
+    DuplicateInFile,
+    /// Source and destination are the same
+    SelfRedirect,
+    /// Following destinations leads back around in a cycle
+    Loop,
+    /// The destination is itself redirected, so visitors take more than one hop
+    Chain,
+}
+
+/// Whether an issue keeps the row from being imported
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum RedirectImportSeverity {
+    /// The row is skipped
+    Error,
+    /// The row is imported
+    Warning,
+}
+
+/// A problem with one import row
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RedirectImportIssue {
+    /// 1-based data row (the CSV header is not counted)
+    #[schema(example = 3)]
+    pub row: usize,
+    #[schema(example = "/old-blog-post")]
+    pub source_path: Option<String>,
+    pub kind: RedirectImportIssueKind,
+    pub severity: RedirectImportSeverity,
+    #[schema(example = "Destination /a is redirected to /b")]
+    pub message: String,
+}
+
+/// Outcome (or, for dry runs, predicted outcome) of a redirect import
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Redirect import report")]
+pub struct RedirectImportReport {
+    /// True when nothing was written
+    pub dry_run: bool,
+    #[schema(example = 120)]
+    pub total_rows: usize,
+    #[schema(example = 110)]
+    pub created: usize,
+    /// Existing redirects replaced (only with `overwrite=true`)
+    #[schema(example = 5)]
+    pub updated: usize,
+    #[schema(example = 5)]
+    pub skipped: usize,
+    pub issues: Vec<RedirectImportIssue>,
+}
+
+/// Validate source path: must start with `/`
 fn validate_source_path(path: &str) -> Result<(), validator::ValidationError> {
     if !path.starts_with('/') {
         let mut err = validator::ValidationError::new("invalid_source_path");
         err.message = Some("Source path must start with /".into());
         return Err(err);
     }
-    if path.contains("..") {
-        let mut err = validator::ValidationError::new("invalid_source_path");
-        err.message = Some("Source path must not contain '..'".into());
-        return Err(err);
+    Ok(())
+}
+
+fn validate_create_redirect(req: &CreateRedirectRequest) -> Result<(), validator::ValidationError> {
+    validate_redirect_rule(
+        req.match_type.unwrap_or_default(),
+        &req.source_path,
+        req.destination_path.as_deref(),
+        req.status_code,
+    )
+}
+
+/// Cross-field checks on the effective values of a redirect.
+///
+/// 410 Gone takes no destination and every other status requires one. Pattern
+/// sources must compile; non-regex sources must not contain `..`.
+pub fn validate_redirect_rule(
+    match_type: RedirectMatchType,
+    source_path: &str,
+    destination_path: Option<&str>,
+    status_code: i16,
+) -> Result<(), validator::ValidationError> {
+    let invalid = |code: &'static str, message: String| {
+        let mut err = validator::ValidationError::new(code);
+        err.message = Some(message.into());
+        Err(err)
+    };
+
+    match (status_code, destination_path) {
+        (410, Some(_)) => {
+            return invalid(
+                "invalid_destination_path",
+                "410 Gone redirects have no destination".to_string(),
+            )
+        }
+        (410, None) => {}
+        (_, None) => {
+            return invalid(
+                "invalid_destination_path",
+                "Destination path is required".to_string(),
+            )
+        }
+        (_, Some(destination)) if destination == source_path => {
+            return invalid(
+                "invalid_destination_path",
+                "Source and destination paths must be different".to_string(),
+            )
+        }
+        _ => {}
+    }
+
+    if match_type != RedirectMatchType::Regex && source_path.contains("..") {
+        return invalid(
+            "invalid_source_path",
+            "Source path must not contain '..'".to_string(),
+        );
+    }
+    if let Err(e) = match_type.compile(source_path) {
+        return invalid(
+            "invalid_source_pattern",
+            format!("Invalid source pattern: {}", e),
+        );
     }
     Ok(())
 }
 
 /// Validate destination path: must start with `/` or `http(s)://`
-fn validate_destination_path(path: &str) -> Result<(), validator::ValidationError> {
+pub fn validate_destination_path(path: &str) -> Result<(), validator::ValidationError> {
     if !path.starts_with('/') && !path.starts_with("http://") && !path.starts_with("https://") {
         let mut err = validator::ValidationError::new("invalid_destination_path");
         err.message = Some("Destination must start with / or http(s)://".into());
@@ -157,11 +362,11 @@ fn validate_destination_path(path: &str) -> Result<(), validator::ValidationErro
     Ok(())
 }
 
-/// Validate redirect status code: must be 301 or 302
+/// Validate redirect status code: must be 301, 302, 307, 308 or 410
 fn validate_redirect_status_code(code: i16) -> Result<(), validator::ValidationError> {
-    if code != 301 && code != 302 {
+    if ![301, 302, 307, 308, 410].contains(&code) {
         let mut err = validator::ValidationError::new("invalid_status_code");
-        err.message = Some("Status code must be 301 or 302".into());
+        err.message = Some("Status code must be 301, 302, 307, 308 or 410".into());
         return Err(err);
     }
     Ok(())
@@ -176,10 +381,12 @@ mod tests {
     fn test_create_redirect_valid() {
         let req = CreateRedirectRequest {
             source_path: "/old-page".to_string(),
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 301,
             is_active: None,
             description: Some("Moved".to_string()),
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_ok());
@@ -189,10 +396,12 @@ mod tests {
     fn test_create_redirect_external_destination() {
         let req = CreateRedirectRequest {
             source_path: "/old-page".to_string(),
-            destination_path: "https://example.com/new-page".to_string(),
+            destination_path: Some("https://example.com/new-page".to_string()),
             status_code: 302,
             is_active: Some(true),
             description: None,
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_ok());
@@ -202,10 +411,12 @@ mod tests {
     fn test_create_redirect_invalid_source_no_slash() {
         let req = CreateRedirectRequest {
             source_path: "old-page".to_string(),
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 301,
             is_active: None,
             description: None,
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_err());
@@ -215,10 +426,12 @@ mod tests {
     fn test_create_redirect_invalid_source_dotdot() {
         let req = CreateRedirectRequest {
             source_path: "/old/../etc/passwd".to_string(),
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 301,
             is_active: None,
             description: None,
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_err());
@@ -228,10 +441,12 @@ mod tests {
     fn test_create_redirect_invalid_destination() {
         let req = CreateRedirectRequest {
             source_path: "/old-page".to_string(),
-            destination_path: "not-a-path".to_string(),
+            destination_path: Some("not-a-path".to_string()),
             status_code: 301,
             is_active: None,
             description: None,
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_err());
@@ -241,10 +456,12 @@ mod tests {
     fn test_create_redirect_invalid_status_code() {
         let req = CreateRedirectRequest {
             source_path: "/old-page".to_string(),
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 404,
             is_active: None,
             description: None,
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_err());
@@ -254,10 +471,89 @@ mod tests {
     fn test_create_redirect_description_too_long() {
         let req = CreateRedirectRequest {
             source_path: "/old-page".to_string(),
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 301,
             is_active: None,
             description: Some("a".repeat(501)),
+            match_type: None,
+            query_handling: None,
+            site_id: Uuid::new_v4(),
+        };
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_create_redirect_new_status_codes() {
+        for code in [307, 308] {
+            let req = CreateRedirectRequest {
+                source_path: "/old-page".to_string(),
+                destination_path: Some("/new-page".to_string()),
+                status_code: code,
+                is_active: None,
+                description: None,
+                match_type: None,
+                query_handling: None,
+                site_id: Uuid::new_v4(),
+            };
+            assert!(req.validate().is_ok(), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_create_redirect_gone_has_no_destination() {
+        let mut req = CreateRedirectRequest {
+            source_path: "/removed".to_string(),
+            destination_path: None,
+            status_code: 410,
+            is_active: None,
+            description: None,
+            match_type: None,
+            query_handling: None,
+            site_id: Uuid::new_v4(),
+        };
+        assert!(req.validate().is_ok());
+
+        req.destination_path = Some("/elsewhere".to_string());
+        assert!(req.validate().is_err());
+
+        req.destination_path = None;
+        req.status_code = 301;
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_create_redirect_patterns() {
+        let mut req = CreateRedirectRequest {
+            source_path: r"/posts/(\d+)/..*".to_string(),
+            destination_path: Some("/blog/$1".to_string()),
+            status_code: 301,
+            is_active: None,
+            description: None,
+            match_type: Some(RedirectMatchType::Regex),
+            query_handling: None,
+            site_id: Uuid::new_v4(),
+        };
+        // `..` is regex syntax here, not a path traversal
+        assert!(req.validate().is_ok());
+
+        req.source_path = "/posts/(".to_string();
+        assert!(req.validate().is_err());
+
+        req.match_type = Some(RedirectMatchType::Wildcard);
+        req.source_path = "/blog/*".to_string();
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_redirect_same_source_and_destination() {
+        let req = CreateRedirectRequest {
+            source_path: "/same".to_string(),
+            destination_path: Some("/same".to_string()),
+            status_code: 301,
+            is_active: None,
+            description: None,
+            match_type: None,
+            query_handling: None,
             site_id: Uuid::new_v4(),
         };
         assert!(req.validate().is_err());
@@ -271,6 +567,8 @@ mod tests {
             status_code: Some(302),
             is_active: Some(false),
             description: Some("Updated".to_string()),
+            match_type: None,
+            query_handling: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -283,6 +581,8 @@ mod tests {
             status_code: None,
             is_active: None,
             description: None,
+            match_type: None,
+            query_handling: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -293,10 +593,16 @@ mod tests {
             id: Uuid::new_v4(),
             site_id: Uuid::new_v4(),
             source_path: "/old".to_string(),
-            destination_path: "/new".to_string(),
+            destination_path: Some("/new".to_string()),
             status_code: 301,
             is_active: true,
             description: None,
+            is_auto_generated: false,
+            content_id: None,
+            match_type: RedirectMatchType::Exact,
+            query_handling: RedirectQueryHandling::Ignore,
+            hit_count: 0,
+            last_hit_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -308,7 +614,7 @@ mod tests {
     #[test]
     fn test_redirect_lookup_response_serialization() {
         let resp = RedirectLookupResponse {
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 301,
         };
         let json = serde_json::to_string(&resp).unwrap();
@@ -0,0 +1,273 @@
+//! Content release DTOs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::content::ContentStatus;
+use crate::models::content_release::{ContentRelease, ContentReleaseItem, ContentReleaseStatus};
+use crate::utils::pagination::Paginated;
+
+/// Request to create a release
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Create a draft release")]
+pub struct CreateReleaseRequest {
+    #[schema(example = "Spring campaign")]
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Name must be between 1 and 200 characters"
+    ))]
+    pub name: String,
+
+    #[schema(example = "Launch posts, landing page, menu entry and updated terms")]
+    #[validate(length(max = 2000, message = "Description cannot exceed 2000 characters"))]
+    pub description: Option<String>,
+}
+
+/// Request to update a release
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Rename a release or change its description")]
+pub struct UpdateReleaseRequest {
+    #[schema(example = "Spring campaign")]
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Name must be between 1 and 200 characters"
+    ))]
+    pub name: Option<String>,
+
+    #[validate(length(max = 2000, message = "Description cannot exceed 2000 characters"))]
+    pub description: Option<String>,
+}
+
+/// Request to schedule a release
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[schema(description = "Publish a release at a given time")]
+pub struct ScheduleReleaseRequest {
+    #[schema(example = "2025-03-20T08:00:00Z")]
+    pub scheduled_at: DateTime<Utc>,
+}
+
+/// Request to add an item to a release
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(description = "Set the status of a content item, or show or hide a navigation item")]
+#[validate(schema(function = "validate_release_item"))]
+pub struct AddReleaseItemRequest {
+    /// Content item (blog, page, CV entry or legal document)
+    pub content_id: Option<Uuid>,
+
+    /// Status the content gets: `Published`, `Draft` or `Archived`
+    #[schema(example = "Published")]
+    pub target_status: Option<ContentStatus>,
+
+    pub navigation_item_id: Option<Uuid>,
+
+    /// Whether the navigation item is shown once the release is published
+    #[schema(example = true)]
+    pub target_active: Option<bool>,
+}
+
+fn validate_release_item(req: &AddReleaseItemRequest) -> Result<(), validator::ValidationError> {
+    let invalid = |code: &'static str, message: &'static str| {
+        let mut err = validator::ValidationError::new(code);
+        err.message = Some(message.into());
+        Err(err)
+    };
+
+    match (
+        req.content_id,
+        &req.target_status,
+        req.navigation_item_id,
+        req.target_active,
+    ) {
+        (Some(_), Some(status), None, None) => match status {
+            ContentStatus::Published | ContentStatus::Draft | ContentStatus::Archived => Ok(()),
+            _ => invalid(
+                "release_target_status",
+                "target_status must be Published, Draft or Archived",
+            ),
+        },
+        (None, None, Some(_), Some(_)) => Ok(()),
+        _ => invalid(
+            "release_item",
+            "Pass either content_id with target_status, or navigation_item_id with target_active",
+        ),
+    }
+}
+
+/// One item of a release
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Change made by a release, with the state it replaced once published")]
+pub struct ReleaseItemResponse {
+    pub id: Uuid,
+    pub content_id: Option<Uuid>,
+    /// Blog, page, CV entry or legal document ID of the content
+    pub entity_id: Option<Uuid>,
+    #[schema(example = "blog")]
+    pub entity_type: Option<String>,
+    #[schema(example = "spring-launch")]
+    pub slug: Option<String>,
+    pub target_status: Option<ContentStatus>,
+    pub navigation_item_id: Option<Uuid>,
+    pub target_active: Option<bool>,
+    /// Status before the release was published
+    pub previous_status: Option<ContentStatus>,
+    /// Visibility before the release was published
+    pub previous_active: Option<bool>,
+    /// The rollback left this item alone because it changed after the release
+    pub rollback_skipped: bool,
+}
+
+impl From<ContentReleaseItem> for ReleaseItemResponse {
+    fn from(i: ContentReleaseItem) -> Self {
+        Self {
+            id: i.id,
+            content_id: i.content_id,
+            entity_id: i.entity_id,
+            entity_type: i.entity_type,
+            slug: i.slug,
+            target_status: i.target_status,
+            navigation_item_id: i.navigation_item_id,
+            target_active: i.target_active,
+            previous_status: i.previous_status,
+            previous_active: i.previous_active,
+            rollback_skipped: i.rollback_skipped,
+        }
+    }
+}
+
+/// A release with its items
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Named set of changes published together")]
+pub struct ReleaseResponse {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    #[schema(example = "Spring campaign")]
+    pub name: String,
+    pub description: Option<String>,
+    pub status: ContentReleaseStatus,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub published_by: Option<Uuid>,
+    pub rolled_back_at: Option<DateTime<Utc>>,
+    pub rolled_back_by: Option<Uuid>,
+    /// Why the scheduler could not publish the release
+    pub error: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub items: Vec<ReleaseItemResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReleaseResponse {
+    pub fn new(release: ContentRelease, items: Vec<ContentReleaseItem>) -> Self {
+        Self {
+            id: release.id,
+            site_id: release.site_id,
+            name: release.name,
+            description: release.description,
+            status: release.status,
+            scheduled_at: release.scheduled_at,
+            published_at: release.published_at,
+            published_by: release.published_by,
+            rolled_back_at: release.rolled_back_at,
+            rolled_back_by: release.rolled_back_by,
+            error: release.error,
+            created_by: release.created_by,
+            items: items.into_iter().map(ReleaseItemResponse::from).collect(),
+            created_at: release.created_at,
+            updated_at: release.updated_at,
+        }
+    }
+}
+
+/// Release in a list (without items)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReleaseListItem {
+    pub id: Uuid,
+    #[schema(example = "Spring campaign")]
+    pub name: String,
+    pub status: ContentReleaseStatus,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub rolled_back_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ContentRelease> for ReleaseListItem {
+    fn from(r: ContentRelease) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            status: r.status,
+            scheduled_at: r.scheduled_at,
+            published_at: r.published_at,
+            rolled_back_at: r.rolled_back_at,
+            created_at: r.created_at,
+        }
+    }
+}
+
+/// Paginated release list
+pub type PaginatedReleases = Paginated<ReleaseListItem>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(value: serde_json::Value) -> AddReleaseItemRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_content_item_valid() {
+        let req = item(serde_json::json!({
+            "content_id": Uuid::new_v4(),
+            "target_status": "Published"
+        }));
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_navigation_item_valid() {
+        let req = item(serde_json::json!({
+            "navigation_item_id": Uuid::new_v4(),
+            "target_active": false
+        }));
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_item_needs_exactly_one_target() {
+        assert!(item(serde_json::json!({})).validate().is_err());
+        assert!(item(serde_json::json!({ "content_id": Uuid::new_v4() }))
+            .validate()
+            .is_err());
+        let both = item(serde_json::json!({
+            "content_id": Uuid::new_v4(),
+            "target_status": "Published",
+            "navigation_item_id": Uuid::new_v4(),
+            "target_active": true
+        }));
+        assert!(both.validate().is_err());
+    }
+
+    #[test]
+    fn test_item_rejects_workflow_statuses() {
+        let req = item(serde_json::json!({
+            "content_id": Uuid::new_v4(),
+            "target_status": "Scheduled"
+        }));
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_create_request_requires_name() {
+        let req: CreateReleaseRequest =
+            serde_json::from_value(serde_json::json!({ "name": "" })).unwrap();
+        assert!(req.validate().is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Full-text search DTOs
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+use crate::models::content::ContentStatus;
+use crate::models::search::SearchHit;
+use crate::utils::pagination::Paginated;
+
+/// Entity types that can be searched
+pub const SEARCHABLE_TYPES: [&str; 3] = ["blog", "page", "document"];
+
+/// Maximum length of a search query
+const MAX_QUERY_LENGTH: usize = 200;
+
+/// Query parameters for the search endpoint
+#[derive(Debug, Clone, Default)]
+pub struct SearchParams {
+    pub q: Option<String>,
+    pub locale: Option<String>,
+    pub types: Option<String>,
+}
+
+impl SearchParams {
+    /// The trimmed query text; rejects empty and overly long queries.
+    pub fn query(&self) -> Result<&str, ApiError> {
+        let q = self.q.as_deref().map(str::trim).unwrap_or_default();
+        if q.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Search query 'q' is required".to_string(),
+            ));
+        }
+        if q.chars().count() > MAX_QUERY_LENGTH {
+            return Err(ApiError::BadRequest(format!(
+                "Search query cannot exceed {} characters",
+                MAX_QUERY_LENGTH
+            )));
+        }
+        Ok(q)
+    }
+
+    /// Parses the comma-separated `types` filter; all types when omitted.
+    pub fn entity_types(&self) -> Result<Vec<String>, ApiError> {
+        let Some(types) = self.types.as_deref().filter(|t| !t.trim().is_empty()) else {
+            return Ok(SEARCHABLE_TYPES.iter().map(|t| t.to_string()).collect());
+        };
+
+        let mut parsed: Vec<String> = Vec::new();
+        for t in types.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if !SEARCHABLE_TYPES.contains(&t) {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown search type '{}'. Expected one of: {}",
+                    t,
+                    SEARCHABLE_TYPES.join(", ")
+                )));
+            }
+            if !parsed.iter().any(|p| p == t) {
+                parsed.push(t.to_string());
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// A single search result
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(description = "Ranked full-text search result")]
+pub struct SearchResultResponse {
+    /// `blog`, `page` or `document`
+    #[schema(example = "blog")]
+    pub entity_type: String,
+    /// ID of the blog, page or document
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub entity_id: Uuid,
+    /// Content ID (blogs and pages only)
+    pub content_id: Option<Uuid>,
+    #[schema(example = "my-first-post")]
+    pub slug: Option<String>,
+    /// Page route (pages only)
+    pub route: Option<String>,
+    /// Content status (blogs and pages only)
+    pub status: Option<ContentStatus>,
+    pub locale_id: Uuid,
+    #[schema(example = "en")]
+    pub locale_code: String,
+    #[schema(example = "Getting started with Rust")]
+    pub title: String,
+    /// Title with matched terms wrapped in `<mark>` tags
+    #[schema(example = "Getting started with <mark>Rust</mark>")]
+    pub title_highlight: String,
+    /// Excerpt/body (or document description) fragments with matches wrapped in `<mark>` tags
+    #[schema(example = "… a short tour of <mark>Rust</mark> ownership …")]
+    pub snippet: String,
+    /// Relevance score (higher is better)
+    #[schema(example = 0.4)]
+    pub rank: f32,
+}
+
+impl From<SearchHit> for SearchResultResponse {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            entity_type: hit.entity_type,
+            entity_id: hit.entity_id,
+            content_id: hit.content_id,
+            slug: hit.slug,
+            route: hit.route,
+            status: hit.status,
+            locale_id: hit.locale_id,
+            locale_code: hit.locale_code,
+            title: hit.title,
+            title_highlight: hit.title_highlight,
+            snippet: hit.snippet,
+            rank: hit.rank,
+        }
+    }
+}
+
+/// Paginated search results
+pub type PaginatedSearchResults = Paginated<SearchResultResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(q: Option<&str>, types: Option<&str>) -> SearchParams {
+        SearchParams {
+            q: q.map(String::from),
+            locale: None,
+            types: types.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_query_trimmed() {
+        assert_eq!(params(Some("  rust  "), None).query().unwrap(), "rust");
+    }
+
+    #[test]
+    fn test_query_required() {
+        assert!(params(None, None).query().is_err());
+        assert!(params(Some("   "), None).query().is_err());
+    }
+
+    #[test]
+    fn test_query_too_long() {
+        let long = "a".repeat(MAX_QUERY_LENGTH + 1);
+        assert!(params(Some(&long), None).query().is_err());
+    }
+
+    #[test]
+    fn test_entity_types_default_to_all() {
+        assert_eq!(
+            params(Some("x"), None).entity_types().unwrap(),
+            vec!["blog", "page", "document"]
+        );
+    }
+
+    #[test]
+    fn test_entity_types_parsed_and_deduplicated() {
+        assert_eq!(
+            params(Some("x"), Some("page, blog,page"))
+                .entity_types()
+                .unwrap(),
+            vec!["page", "blog"]
+        );
+    }
+
+    #[test]
+    fn test_entity_types_unknown_rejected() {
+        assert!(params(Some("x"), Some("blog,media"))
+            .entity_types()
+            .is_err());
+    }
+}
@@ -5,10 +5,11 @@ use std::collections::HashMap;
 use validator::Validate;
 
 use crate::models::site_settings::{
-    KEY_ANALYTICS_ENABLED, KEY_CONTACT_EMAIL, KEY_EDITORIAL_WORKFLOW_ENABLED, KEY_MAINTENANCE_MODE,
+    KEY_ANALYTICS_ENABLED, KEY_AVIF_QUALITY, KEY_AVIF_SPEED, KEY_BLOG_PATH_PREFIX,
+    KEY_CONTACT_EMAIL, KEY_EDITORIAL_WORKFLOW_ENABLED, KEY_MAINTENANCE_MODE,
     KEY_MAX_DOCUMENT_FILE_SIZE, KEY_MAX_MEDIA_FILE_SIZE, KEY_POSTS_PER_PAGE, KEY_PREVIEW_TEMPLATES,
 };
-use crate::utils::validation::validate_email;
+use crate::utils::validation::{validate_email, validate_route};
 
 /// A preview template entry (name + URL of a dev server)
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
@@ -38,6 +39,15 @@ pub struct SiteSettingsResponse {
     #[schema(example = false)]
     pub editorial_workflow_enabled: bool,
     pub preview_templates: Vec<PreviewTemplate>,
+    /// AVIF variant quality (1 – 100)
+    #[schema(example = 60)]
+    pub avif_quality: i64,
+    /// AVIF encoder speed (1 = smallest files, 10 = fastest)
+    #[schema(example = 8)]
+    pub avif_speed: i64,
+    /// Path under which blog posts are served on the public site
+    #[schema(example = "/blog")]
+    pub blog_path_prefix: String,
 }
 
 impl SiteSettingsResponse {
@@ -77,6 +87,19 @@ impl SiteSettingsResponse {
                 .get(KEY_PREVIEW_TEMPLATES)
                 .and_then(|v| serde_json::from_value::<Vec<PreviewTemplate>>(v.clone()).ok())
                 .unwrap_or_default(),
+            avif_quality: map
+                .get(KEY_AVIF_QUALITY)
+                .and_then(|v| v.as_i64())
+                .unwrap_or(60),
+            avif_speed: map
+                .get(KEY_AVIF_SPEED)
+                .and_then(|v| v.as_i64())
+                .unwrap_or(8),
+            blog_path_prefix: map
+                .get(KEY_BLOG_PATH_PREFIX)
+                .and_then(|v| v.as_str())
+                .unwrap_or("/blog")
+                .to_string(),
         }
     }
 }
@@ -114,6 +137,21 @@ pub struct UpdateSiteSettingsRequest {
     pub editorial_workflow_enabled: Option<bool>,
 
     pub preview_templates: Option<Vec<PreviewTemplate>>,
+
+    /// AVIF variant quality (1 – 100)
+    #[validate(range(min = 1, max = 100))]
+    #[schema(example = 60)]
+    pub avif_quality: Option<i64>,
+
+    /// AVIF encoder speed (1 = smallest files, 10 = fastest)
+    #[validate(range(min = 1, max = 10))]
+    #[schema(example = 8)]
+    pub avif_speed: Option<i64>,
+
+    /// Path under which blog posts are served on the public site (`/` for the root)
+    #[validate(custom(function = "validate_route"))]
+    #[schema(example = "/blog")]
+    pub blog_path_prefix: Option<String>,
 }
 
 impl UpdateSiteSettingsRequest {
@@ -145,6 +183,15 @@ impl UpdateSiteSettingsRequest {
         if let Some(ref v) = self.preview_templates {
             out.push((KEY_PREVIEW_TEMPLATES, serde_json::json!(v), false));
         }
+        if let Some(v) = self.avif_quality {
+            out.push((KEY_AVIF_QUALITY, serde_json::json!(v), false));
+        }
+        if let Some(v) = self.avif_speed {
+            out.push((KEY_AVIF_SPEED, serde_json::json!(v), false));
+        }
+        if let Some(ref v) = self.blog_path_prefix {
+            out.push((KEY_BLOG_PATH_PREFIX, serde_json::json!(v), false));
+        }
 
         out
     }
@@ -167,6 +214,9 @@ mod tests {
         assert_eq!(resp.posts_per_page, 10);
         assert!(!resp.editorial_workflow_enabled);
         assert!(resp.preview_templates.is_empty());
+        assert_eq!(resp.avif_quality, 60);
+        assert_eq!(resp.avif_speed, 8);
+        assert_eq!(resp.blog_path_prefix, "/blog");
     }
 
     #[test]
@@ -193,6 +243,9 @@ mod tests {
             posts_per_page: Some(20),
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -208,6 +261,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_err());
     }
@@ -223,6 +279,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_err());
     }
@@ -238,6 +297,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_err());
     }
@@ -253,6 +315,9 @@ mod tests {
             posts_per_page: Some(0),
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_err());
     }
@@ -268,6 +333,45 @@ mod tests {
             posts_per_page: Some(101),
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
+        };
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_request_avif_out_of_range() {
+        let req = UpdateSiteSettingsRequest {
+            max_document_file_size: None,
+            max_media_file_size: None,
+            analytics_enabled: None,
+            maintenance_mode: None,
+            contact_email: None,
+            posts_per_page: None,
+            editorial_workflow_enabled: None,
+            preview_templates: None,
+            avif_quality: Some(60),
+            avif_speed: Some(11),
+            blog_path_prefix: None,
+        };
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_request_blog_path_prefix_must_be_a_path() {
+        let req = UpdateSiteSettingsRequest {
+            max_document_file_size: None,
+            max_media_file_size: None,
+            analytics_enabled: None,
+            maintenance_mode: None,
+            contact_email: None,
+            posts_per_page: None,
+            editorial_workflow_enabled: None,
+            preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: Some("blog".to_string()),
         };
         assert!(req.validate().is_err());
     }
@@ -283,6 +387,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -298,6 +405,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_err());
     }
@@ -313,6 +423,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -329,6 +442,9 @@ mod tests {
             posts_per_page: None,
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -344,6 +460,9 @@ mod tests {
             posts_per_page: Some(20),
             editorial_workflow_enabled: None,
             preview_templates: None,
+            avif_quality: None,
+            avif_speed: None,
+            blog_path_prefix: None,
         };
         let vec = req.to_settings_vec();
         assert_eq!(vec.len(), 3);
@@ -363,6 +482,9 @@ mod tests {
             posts_per_page: 10,
             editorial_workflow_enabled: false,
             preview_templates: vec![],
+            avif_quality: 60,
+            avif_speed: 8,
+            blog_path_prefix: "/blog".to_string(),
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("\"max_document_file_size\":10485760"));
@@ -0,0 +1,146 @@
+//! Site export/import DTOs
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::site_transfer::{SiteTransferJob, SiteTransferKind, SiteTransferStatus};
+
+/// Outcome of a finished export or import
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[schema(description = "What a site transfer wrote")]
+pub struct SiteTransferReport {
+    /// Rows exported or imported per table
+    pub rows: BTreeMap<String, u64>,
+    /// Existing tags, categories, skills and media reused by an import instead of copied
+    #[serde(default)]
+    pub reused: BTreeMap<String, u64>,
+    /// Media binaries written to the archive or to storage
+    pub media_files: u64,
+    /// Size of the export archive in bytes
+    pub archive_bytes: Option<u64>,
+    /// Slug of the exported site, or of the site created by the import
+    #[schema(example = "my-site")]
+    pub site_slug: Option<String>,
+    /// Rows or files that could not be transferred
+    pub warnings: Vec<String>,
+}
+
+/// A site export or import job
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(description = "Background site export or import with progress")]
+pub struct SiteTransferJobResponse {
+    pub id: Uuid,
+    pub kind: SiteTransferKind,
+    /// Exported site, or the site created by an import once it exists
+    pub site_id: Option<Uuid>,
+    pub status: SiteTransferStatus,
+    /// Step currently running, e.g. a table name or `media`
+    #[schema(example = "content_localizations")]
+    pub stage: Option<String>,
+    pub completed_steps: i32,
+    pub total_steps: i32,
+    /// Completion in percent (0-100)
+    #[schema(example = 42)]
+    pub progress: i32,
+    pub report: Option<SiteTransferReport>,
+    pub error: Option<String>,
+    /// Where to fetch the archive of a completed export
+    pub download_url: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<SiteTransferJob> for SiteTransferJobResponse {
+    fn from(job: SiteTransferJob) -> Self {
+        let progress = match job.status {
+            SiteTransferStatus::Completed => 100,
+            _ if job.total_steps > 0 => {
+                (job.completed_steps.min(job.total_steps) * 100) / job.total_steps
+            }
+            _ => 0,
+        };
+        let download_url = (job.kind == SiteTransferKind::Export
+            && job.status == SiteTransferStatus::Completed)
+            .then(|| format!("/api/v1/site-transfers/{}/download", job.id));
+
+        Self {
+            id: job.id,
+            kind: job.kind,
+            site_id: job.site_id,
+            status: job.status,
+            stage: job.stage,
+            completed_steps: job.completed_steps,
+            total_steps: job.total_steps,
+            progress,
+            report: job.result.and_then(|r| serde_json::from_value(r).ok()),
+            error: job.error,
+            download_url,
+            created_by: job.created_by,
+            created_at: job.created_at,
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(kind: SiteTransferKind, status: SiteTransferStatus) -> SiteTransferJob {
+        SiteTransferJob {
+            id: Uuid::new_v4(),
+            kind,
+            site_id: Some(Uuid::new_v4()),
+            status,
+            stage: Some("media".to_string()),
+            completed_steps: 3,
+            total_steps: 12,
+            result: None,
+            error: None,
+            archive_path: None,
+            owner_instance: None,
+            heartbeat_at: Utc::now(),
+            created_by: None,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    #[test]
+    fn test_progress_of_running_job() {
+        let response = SiteTransferJobResponse::from(job(
+            SiteTransferKind::Export,
+            SiteTransferStatus::Running,
+        ));
+        assert_eq!(response.progress, 25);
+        assert!(response.download_url.is_none());
+    }
+
+    #[test]
+    fn test_completed_export_has_download_url() {
+        let mut completed = job(SiteTransferKind::Export, SiteTransferStatus::Completed);
+        completed.result = Some(serde_json::to_value(SiteTransferReport::default()).unwrap());
+        let id = completed.id;
+        let response = SiteTransferJobResponse::from(completed);
+        assert_eq!(response.progress, 100);
+        assert!(response.report.is_some());
+        assert_eq!(
+            response.download_url,
+            Some(format!("/api/v1/site-transfers/{}/download", id))
+        );
+
+        let import = SiteTransferJobResponse::from(job(
+            SiteTransferKind::Import,
+            SiteTransferStatus::Completed,
+        ));
+        assert!(import.download_url.is_none());
+    }
+}
@@ -0,0 +1,94 @@
+//! Trash DTOs
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::trash::{TrashItem, TrashItemKind};
+use crate::utils::pagination::Paginated;
+
+/// Deleted content or media file in a site's trash
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TrashItemResponse {
+    /// Content ID (restore/purge via `/trash/content/{id}`) or media file ID
+    /// (via `/trash/media/{id}`)
+    pub id: Uuid,
+    pub kind: TrashItemKind,
+    #[schema(example = "blog")]
+    pub entity_type: String,
+    /// Blog, page, CV entry or legal document ID; the media file ID for media
+    pub entity_id: Uuid,
+    #[schema(example = "Hello world")]
+    pub title: Option<String>,
+    #[schema(example = "hello-world")]
+    pub slug: Option<String>,
+    /// Page route (pages only)
+    pub route: Option<String>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When the retention purge removes the item; null if automatic purging is off
+    pub purge_at: Option<DateTime<Utc>>,
+}
+
+impl TrashItemResponse {
+    /// Build a response; `retention_days` of 0 means items are kept until purged by hand
+    pub fn new(item: TrashItem, retention_days: u32) -> Self {
+        let purge_at = item
+            .deleted_at
+            .filter(|_| retention_days > 0)
+            .map(|at| at + Duration::days(i64::from(retention_days)));
+        Self {
+            id: item.id,
+            kind: item.kind(),
+            entity_type: item.entity_type,
+            entity_id: item.entity_id,
+            title: item.title,
+            slug: item.slug,
+            route: item.route,
+            deleted_at: item.deleted_at,
+            purge_at,
+        }
+    }
+}
+
+/// Paginated trash list
+pub type PaginatedTrash = Paginated<TrashItemResponse>;
+
+/// Number of items permanently deleted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TrashPurgeReport {
+    pub content: u64,
+    pub media: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(entity_type: &str, deleted_at: Option<DateTime<Utc>>) -> TrashItem {
+        TrashItem {
+            id: Uuid::new_v4(),
+            entity_type: entity_type.to_string(),
+            entity_id: Uuid::new_v4(),
+            title: None,
+            slug: None,
+            route: None,
+            deleted_at,
+        }
+    }
+
+    #[test]
+    fn test_purge_at_adds_retention() {
+        let deleted_at = Utc::now();
+        let response = TrashItemResponse::new(item("blog", Some(deleted_at)), 30);
+        assert_eq!(response.kind, TrashItemKind::Content);
+        assert_eq!(response.purge_at, Some(deleted_at + Duration::days(30)));
+    }
+
+    #[test]
+    fn test_purge_at_none_without_retention() {
+        let response = TrashItemResponse::new(item("media", Some(Utc::now())), 0);
+        assert_eq!(response.kind, TrashItemKind::Media);
+        assert!(response.purge_at.is_none());
+    }
+}
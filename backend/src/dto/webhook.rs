@@ -14,6 +14,10 @@ pub const VALID_WEBHOOK_EVENTS: &[&str] = &[
     "blog.created",
     "blog.updated",
     "blog.deleted",
+    "comment.created",
+    "comment.moderated",
+    "comment.deleted",
+    "form.submitted",
     "page.created",
     "page.updated",
     "page.deleted",
@@ -22,6 +26,8 @@ pub const VALID_WEBHOOK_EVENTS: &[&str] = &[
     "document.deleted",
     "media.created",
     "media.deleted",
+    "release.published",
+    "release.rolled_back",
 ];
 
 /// Request to create a webhook.
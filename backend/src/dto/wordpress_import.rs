@@ -0,0 +1,39 @@
+//! WordPress import DTOs
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Counts for one kind of imported record
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+pub struct WordPressImportCount {
+    #[schema(example = 12)]
+    pub created: u64,
+    /// Already present (same slug, route or file) and reused instead of created
+    #[schema(example = 1)]
+    pub existing: u64,
+    /// Could not be imported; see the warnings
+    #[schema(example = 0)]
+    pub failed: u64,
+}
+
+/// Outcome of a WordPress import
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[schema(description = "What a WordPress import wrote")]
+pub struct WordPressImportReport {
+    /// Posts imported as blogs
+    pub blogs: WordPressImportCount,
+    pub pages: WordPressImportCount,
+    pub categories: WordPressImportCount,
+    pub tags: WordPressImportCount,
+    /// Attachments stored through the media upload pipeline
+    pub media: WordPressImportCount,
+    /// Old permalinks redirected to their new paths
+    #[schema(example = 13)]
+    pub redirects: u64,
+    /// Skipped items by post type (menus, revisions, custom types) or status (trash, auto-draft)
+    pub ignored: BTreeMap<String, u64>,
+    /// Items, terms or files that could not be imported
+    pub warnings: Vec<String>,
+}
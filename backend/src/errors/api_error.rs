@@ -30,6 +30,9 @@ pub enum ApiError {
     #[error("{0}")]
     Conflict(String),
 
+    #[error("{0}")]
+    PreconditionFailed(String),
+
     #[error("{0}")]
     Database(String),
 
@@ -101,6 +104,7 @@ impl ApiError {
             ApiError::Unauthorized(_) => Status::Unauthorized,
             ApiError::Forbidden(_) => Status::Forbidden,
             ApiError::Conflict(_) => Status::Conflict,
+            ApiError::PreconditionFailed(_) => Status::PreconditionFailed,
             ApiError::Database(_) => Status::InternalServerError,
             ApiError::Internal(_) => Status::InternalServerError,
             ApiError::ServiceUnavailable(_) => Status::ServiceUnavailable,
@@ -117,6 +121,7 @@ impl ApiError {
             ApiError::Unauthorized(_) => "UNAUTHORIZED",
             ApiError::Forbidden(_) => "FORBIDDEN",
             ApiError::Conflict(_) => "CONFLICT",
+            ApiError::PreconditionFailed(_) => "PRECONDITION_FAILED",
             ApiError::Database(_) => "DATABASE_ERROR",
             ApiError::Internal(_) => "INTERNAL_ERROR",
             ApiError::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
@@ -133,6 +138,7 @@ impl ApiError {
             ApiError::Unauthorized(_) => "Unauthorized",
             ApiError::Forbidden(_) => "Forbidden",
             ApiError::Conflict(_) => "Resource Conflict",
+            ApiError::PreconditionFailed(_) => "Precondition Failed",
             ApiError::Database(_) => "Database Error",
             ApiError::Internal(_) => "Internal Server Error",
             ApiError::ServiceUnavailable(_) => "Service Unavailable",
@@ -288,6 +294,14 @@ mod tests {
         assert_eq!(error.code(), "VALIDATION_ERROR");
     }
 
+    #[test]
+    fn test_precondition_failed_error() {
+        let error = ApiError::PreconditionFailed("Blog was modified".to_string());
+        assert_eq!(error.status(), Status::PreconditionFailed);
+        assert_eq!(error.code(), "PRECONDITION_FAILED");
+        assert_eq!(error.title(), "Precondition Failed");
+    }
+
     #[test]
     fn test_problem_details_serialization() {
         let error = ApiError::NotFound("Site not found".to_string());
@@ -11,6 +11,8 @@ use sqlx::PgPool;
 use crate::errors::ApiError;
 use crate::middleware::rate_limit::{RateLimitHeaderInfo, RateLimiter, RateLimits};
 use crate::models::api_key::{ApiKey, ApiKeyPermission};
+use crate::models::content::Content;
+use crate::models::media::MediaFile;
 use crate::models::site_membership::{SiteMembership, SiteRole};
 use crate::AppState;
 
@@ -173,6 +175,48 @@ impl AuthenticatedKey {
         self.require_site_role(pool, site_id, min_role).await?;
         Ok(())
     }
+
+    /// Ensure the content exists and the caller holds `min_role` on every site
+    /// it belongs to. Returns those site IDs.
+    pub async fn authorize_content(
+        &self,
+        pool: &PgPool,
+        content_id: Uuid,
+        min_role: &SiteRole,
+    ) -> Result<Vec<Uuid>, ApiError> {
+        Content::find_by_id(pool, content_id).await?;
+        let site_ids = Content::find_site_ids(pool, content_id).await?;
+        for site_id in &site_ids {
+            self.authorize_site_action(pool, *site_id, min_role).await?;
+        }
+        Ok(site_ids)
+    }
+
+    /// Ensure the caller holds `min_role` on at least one of the sites a media
+    /// file belongs to; global media additionally needs an admin. Returns the
+    /// site access was granted through.
+    pub async fn authorize_media(
+        &self,
+        pool: &PgPool,
+        media: &MediaFile,
+        min_role: &SiteRole,
+    ) -> Result<Uuid, ApiError> {
+        if media.is_global && !self.is_admin() && !self.is_system_admin(pool).await? {
+            return Err(ApiError::Forbidden(
+                "Global media can only be changed by an admin".to_string(),
+            ));
+        }
+        let mut denied = None;
+        for site_id in MediaFile::find_site_ids(pool, media.id).await? {
+            match self.authorize_site_action(pool, site_id, min_role).await {
+                Ok(()) => return Ok(site_id),
+                Err(e) => denied = Some(e),
+            }
+        }
+        Err(denied.unwrap_or_else(|| {
+            ApiError::Forbidden("You do not have access to this media file".to_string())
+        }))
+    }
 }
 
 /// JWT claims we expect from Clerk
@@ -0,0 +1,27 @@
+//! Client guard
+//!
+//! Request guard for the caller's IP address and user agent.
+
+use rocket::request::{FromRequest, Outcome, Request};
+
+/// Client address and user agent, as far as the request reveals them
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClientInfo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(ClientInfo {
+            ip: request.client_ip().map(|ip| ip.to_string()),
+            user_agent: request
+                .headers()
+                .get_one("User-Agent")
+                .map(|ua| ua.chars().take(500).collect()),
+        })
+    }
+}
@@ -3,4 +3,6 @@
 //! Rocket request guards for extracting common request data.
 
 pub mod auth_guard;
+pub mod client_guard;
+pub mod precondition_guard;
 pub mod site_guard;
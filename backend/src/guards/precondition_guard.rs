@@ -0,0 +1,68 @@
+//! Precondition guard
+//!
+//! Request guard for the `If-Match` header used for optimistic concurrency.
+
+use rocket::request::{FromRequest, Outcome, Request};
+
+/// Entity tags from the `If-Match` header, if the request sent one
+#[derive(Debug, Clone, Default)]
+pub struct IfMatch(pub Option<String>);
+
+impl IfMatch {
+    /// Whether the request only applies to a specific representation.
+    /// A missing header and `If-Match: *` both mean "any".
+    pub fn is_conditional(&self) -> bool {
+        self.0.as_deref().is_some_and(|value| value.trim() != "*")
+    }
+
+    /// Whether `etag` satisfies the header (strong comparison, so weak tags never match)
+    pub fn matches(&self, etag: &str) -> bool {
+        match self.0.as_deref() {
+            None => true,
+            Some(value) if value.trim() == "*" => true,
+            Some(value) => value
+                .split(',')
+                .map(str::trim)
+                .any(|tag| !tag.starts_with("W/") && tag == etag),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfMatch {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(IfMatch(
+            request.headers().get_one("If-Match").map(String::from),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_or_wildcard_matches_anything() {
+        assert!(!IfMatch(None).is_conditional());
+        assert!(IfMatch(None).matches("\"3-1700000000000000\""));
+        assert!(!IfMatch(Some("*".into())).is_conditional());
+        assert!(IfMatch(Some(" * ".into())).matches("\"3-1700000000000000\""));
+    }
+
+    #[test]
+    fn test_matches_any_listed_strong_tag() {
+        let header = IfMatch(Some("\"2-1\", \"3-1700000000000000\"".into()));
+        assert!(header.is_conditional());
+        assert!(header.matches("\"3-1700000000000000\""));
+        assert!(!header.matches("\"4-1700000000000000\""));
+    }
+
+    #[test]
+    fn test_weak_tags_never_match() {
+        let header = IfMatch(Some("W/\"3-1700000000000000\"".into()));
+        assert!(!header.matches("\"3-1700000000000000\""));
+        assert!(!header.matches("W/\"3-1700000000000000\""));
+    }
+}
@@ -204,7 +204,9 @@ pub async fn revert_changes(
             let old = Blog::find_by_id(&state.db, entity_id).await?;
             let old_json = serde_json::to_value(&old)?;
             let update_req: UpdateBlogRequest = serde_json::from_value(revert_json)?;
-            let updated = Blog::update(&state.db, entity_id, update_req).await?;
+            let mut tx = state.db.begin().await?;
+            let updated = Blog::update(&mut tx, entity_id, update_req).await?;
+            tx.commit().await?;
             let new_json = serde_json::to_value(&updated)?;
             audit_service::log_action(
                 &state.db,
@@ -231,7 +233,9 @@ pub async fn revert_changes(
             let old = Page::find_by_id(&state.db, entity_id).await?;
             let old_json = serde_json::to_value(&old)?;
             let update_req: UpdatePageRequest = serde_json::from_value(revert_json)?;
-            let updated = Page::update(&state.db, entity_id, update_req).await?;
+            let mut tx = state.db.begin().await?;
+            let updated = Page::update(&mut tx, entity_id, update_req).await?;
+            tx.commit().await?;
             let new_json = serde_json::to_value(&updated)?;
             audit_service::log_action(
                 &state.db,
@@ -285,7 +289,9 @@ pub async fn revert_changes(
             let old = LegalDocument::find_by_id(&state.db, entity_id).await?;
             let old_json = serde_json::to_value(&old)?;
             let update_req: UpdateLegalDocumentRequest = serde_json::from_value(revert_json)?;
-            let updated = LegalDocument::update(&state.db, entity_id, update_req).await?;
+            let mut tx = state.db.begin().await?;
+            let updated = LegalDocument::update(&mut tx, entity_id, update_req).await?;
+            tx.commit().await?;
             let new_json = serde_json::to_value(&updated)?;
             audit_service::log_action(
                 &state.db,
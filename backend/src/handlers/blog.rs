@@ -19,23 +19,33 @@ use crate::dto::bulk::{BulkAction, BulkContentRequest, BulkContentResponse};
 use crate::dto::content::{
     CreateLocalizationRequest, LocalizationResponse, UpdateLocalizationRequest,
 };
+use crate::dto::content_block::LocalizationWithBlocksResponse;
 use crate::dto::document::BlogDocumentResponse;
+use crate::dto::redirect::validate_destination_path;
 use crate::dto::review::{ReviewAction, ReviewActionRequest, ReviewActionResponse};
 use crate::dto::taxonomy::CategoryResponse;
 use crate::errors::{ApiError, ProblemDetails};
 use crate::guards::auth_guard::ReadKey;
+use crate::guards::precondition_guard::IfMatch;
 use crate::models::audit::AuditAction;
 use crate::models::blog::Blog;
 use crate::models::content::{Content, ContentLocalization, ContentStatus};
+use crate::models::content_block::ContentBlock;
 use crate::models::document::{BlogDocument, DocumentLocalization};
+use crate::models::environment::{Environment, EnvironmentType};
 use crate::models::site::Site;
 use crate::models::site_membership::SiteRole;
 use crate::models::taxonomy::Category;
 use crate::services::{
-    audit_service, bulk_content_service::BulkContentService, content_service::ContentService,
-    notification_service, webhook_service, workflow_service,
+    audit_service,
+    bulk_content_service::BulkContentService,
+    content_service::ContentService,
+    notification_service,
+    preview_service::{self, PreviewTarget},
+    redirect_service, version_service, webhook_service, workflow_service,
 };
 use crate::utils::pagination::PaginationParams;
+use crate::utils::response::WithETag;
 use crate::AppState;
 
 /// Custom responder for RSS XML feeds
@@ -59,20 +69,23 @@ impl<'r> Responder<'r, 'static> for RssResponse {
     description = "List all blogs for a site (paginated)",
     params(
         ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("environment" = Option<String>, Query, description = "Environment to list: development, staging or production (default: the live environment)"),
         ("page" = Option<i64>, Query, description = "Page number (default 1)"),
         ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
     ),
     responses(
         (status = 200, description = "Paginated blog list", body = PaginatedBlogs),
+        (status = 422, description = "Invalid environment", body = ProblemDetails),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[get("/sites/<site_id>/blogs?<page>&<per_page>")]
+#[get("/sites/<site_id>/blogs?<environment>&<page>&<per_page>")]
 pub async fn list_blogs(
     state: &State<AppState>,
     site_id: Uuid,
+    environment: Option<String>,
     page: Option<i64>,
     per_page: Option<i64>,
     auth: ReadKey,
@@ -80,11 +93,16 @@ pub async fn list_blogs(
     auth.0
         .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
         .await?;
+    let environment = environment
+        .as_deref()
+        .map(EnvironmentType::parse)
+        .transpose()?;
+    let environment_id = Environment::resolve_id(&state.db, environment).await?;
     let params = PaginationParams::new(page, per_page);
     let (limit, offset) = params.limit_offset();
 
-    let blogs = Blog::find_all_for_site(&state.db, site_id, limit, offset).await?;
-    let total = Blog::count_for_site(&state.db, site_id).await?;
+    let blogs = Blog::find_all_for_site(&state.db, site_id, environment_id, limit, offset).await?;
+    let total = Blog::count_for_site(&state.db, site_id, environment_id).await?;
 
     let items: Vec<BlogListItem> = blogs.into_iter().map(BlogListItem::from).collect();
     let paginated = params.paginate(items, total);
@@ -171,7 +189,8 @@ pub async fn list_featured_blogs(
     description = "Get a blog post by ID",
     params(("id" = Uuid, Path, description = "Blog UUID")),
     responses(
-        (status = 200, description = "Blog details", body = BlogResponse),
+        (status = 200, description = "Blog details", body = BlogResponse,
+            headers(("ETag" = String, description = "Entity tag to send back as If-Match when updating"))),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 404, description = "Blog not found", body = ProblemDetails)
     ),
@@ -182,7 +201,7 @@ pub async fn get_blog(
     state: &State<AppState>,
     id: Uuid,
     auth: ReadKey,
-) -> Result<Json<BlogResponse>, ApiError> {
+) -> Result<WithETag<Json<BlogResponse>>, ApiError> {
     let blog = Blog::find_by_id(&state.db, id).await?;
     let site_ids = Content::find_site_ids(&state.db, blog.content_id).await?;
     for site_id in &site_ids {
@@ -190,37 +209,56 @@ pub async fn get_blog(
             .authorize_site_action(&state.db, *site_id, &SiteRole::Viewer)
             .await?;
     }
-    Ok(Json(BlogResponse::from(blog)))
+    let etag = ContentService::etag(&state.db, blog.content_id).await?;
+    Ok(WithETag::new(Json(BlogResponse::from(blog)), etag))
 }
 
 /// Get blog by slug within a site
 #[utoipa::path(
     tag = "Blogs",
     operation_id = "get_blog_by_slug",
-    description = "Get a blog post by slug within a site",
+    description = "Get a blog post by slug within a site. Read-only keys only see published posts unless a valid preview token is passed.",
     params(
         ("site_id" = Uuid, Path, description = "Site UUID"),
-        ("slug" = String, Path, description = "Blog slug")
+        ("slug" = String, Path, description = "Blog slug"),
+        ("preview_token" = Option<String>, Query, description = "Preview token granting access to an unpublished post"),
+        ("locale" = Option<String>, Query, description = "Locale code; required when the preview token is locale-scoped")
     ),
     responses(
         (status = 200, description = "Blog details", body = BlogResponse),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
-        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 403, description = "Forbidden or invalid preview token", body = ProblemDetails),
         (status = 404, description = "Blog not found", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[get("/sites/<site_id>/blogs/by-slug/<slug>")]
+#[get("/sites/<site_id>/blogs/by-slug/<slug>?<preview_token>&<locale>")]
 pub async fn get_blog_by_slug(
     state: &State<AppState>,
     site_id: Uuid,
     slug: &str,
+    preview_token: Option<&str>,
+    locale: Option<&str>,
     auth: ReadKey,
 ) -> Result<Json<BlogResponse>, ApiError> {
     auth.0
         .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
         .await?;
     let blog = Blog::find_by_slug(&state.db, site_id, slug).await?;
+    let target = PreviewTarget {
+        entity_type: "blog",
+        entity_id: blog.id,
+        content_id: blog.content_id,
+        status: &blog.status,
+    };
+    if !preview_service::authorize_view(state, &auth.0, site_id, target, preview_token, locale)
+        .await?
+    {
+        return Err(ApiError::NotFound(format!(
+            "Blog with slug '{}' not found",
+            slug
+        )));
+    }
     Ok(Json(BlogResponse::from(blog)))
 }
 
@@ -271,7 +309,11 @@ pub async fn create_blog(
         .await?;
     }
 
-    let blog = Blog::create(&state.db, req).await?;
+    let mut tx = state.db.begin().await?;
+    let blog = Blog::create(&mut tx, req).await?;
+    version_service::record_version(&mut tx, blog.content_id, "Created blog", Some(auth.0.id))
+        .await?;
+    tx.commit().await?;
     let site_id = Content::find_site_ids(&state.db, blog.content_id)
         .await?
         .into_iter()
@@ -302,14 +344,19 @@ pub async fn create_blog(
 #[utoipa::path(
     tag = "Blogs",
     operation_id = "update_blog",
-    description = "Update a blog post",
-    params(("id" = Uuid, Path, description = "Blog UUID")),
+    description = "Update a blog post. Send the `ETag` from a previous read as `If-Match` to fail with 412 instead of overwriting someone else's changes.",
+    params(
+        ("id" = Uuid, Path, description = "Blog UUID"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the blog still has this entity tag")
+    ),
     request_body(content = UpdateBlogRequest, description = "Blog update data"),
     responses(
-        (status = 200, description = "Blog updated", body = BlogResponse),
+        (status = 200, description = "Blog updated", body = BlogResponse,
+            headers(("ETag" = String, description = "Entity tag of the updated blog"))),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails),
-        (status = 404, description = "Blog not found", body = ProblemDetails)
+        (status = 404, description = "Blog not found", body = ProblemDetails),
+        (status = 412, description = "Blog was modified since the given entity tag", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
@@ -318,8 +365,9 @@ pub async fn update_blog(
     state: &State<AppState>,
     id: Uuid,
     body: Json<UpdateBlogRequest>,
+    if_match: IfMatch,
     auth: ReadKey,
-) -> Result<Json<BlogResponse>, ApiError> {
+) -> Result<WithETag<Json<BlogResponse>>, ApiError> {
     let existing = Blog::find_by_id(&state.db, id).await?;
     let site_ids = Content::find_site_ids(&state.db, existing.content_id).await?;
     for site_id in &site_ids {
@@ -352,7 +400,47 @@ pub async fn update_blog(
         }
     }
 
-    let blog = Blog::update(&state.db, id, req).await?;
+    let redirect_to = req.redirect_to.clone();
+    // Per-site paths before the change, for redirects from the old URLs
+    let old_paths = Blog::public_paths(&state.db, existing.content_id).await?;
+    let mut tx = state.db.begin().await?;
+    ContentService::check_if_match(&mut tx, existing.content_id, &if_match).await?;
+    let blog = Blog::update(&mut tx, id, req).await?;
+    version_service::record_version(&mut tx, blog.content_id, "Updated blog", Some(auth.0.id))
+        .await?;
+    Content::touch(&mut *tx, blog.content_id).await?;
+    tx.commit().await?;
+    let etag = ContentService::etag(&state.db, blog.content_id).await?;
+    let new_paths = Blog::public_paths(&state.db, blog.content_id).await?;
+    for (site_id, old_path) in &old_paths {
+        if blog.status == ContentStatus::Archived && existing.status != ContentStatus::Archived {
+            if let Some(target) = redirect_to.as_deref() {
+                redirect_service::redirect_removed(
+                    &state.db,
+                    &[*site_id],
+                    blog.content_id,
+                    old_path,
+                    target,
+                    "Blog archived",
+                )
+                .await;
+            }
+        } else if existing.status == ContentStatus::Published
+            && blog.status == ContentStatus::Published
+        {
+            if let Some((_, new_path)) = new_paths.iter().find(|(s, _)| s == site_id) {
+                redirect_service::redirect_moved(
+                    &state.db,
+                    &[*site_id],
+                    blog.content_id,
+                    old_path,
+                    new_path,
+                    "Blog slug changed",
+                )
+                .await;
+            }
+        }
+    }
     let site_id = site_ids.into_iter().next();
     audit_service::log_action(
         &state.db,
@@ -389,27 +477,32 @@ pub async fn update_blog(
             );
         }
     }
-    Ok(Json(BlogResponse::from(blog)))
+    Ok(WithETag::new(Json(BlogResponse::from(blog)), etag))
 }
 
 /// Delete a blog post (soft delete)
 #[utoipa::path(
     tag = "Blogs",
     operation_id = "delete_blog",
-    description = "Soft delete a blog post",
-    params(("id" = Uuid, Path, description = "Blog UUID")),
+    description = "Soft delete a blog post. Pass `redirect_to` to redirect the post's URL to another path or absolute URL.",
+    params(
+        ("id" = Uuid, Path, description = "Blog UUID"),
+        ("redirect_to" = Option<String>, Query, description = "Redirect the deleted post's URL here (301)")
+    ),
     responses(
         (status = 204, description = "Blog deleted"),
+        (status = 400, description = "Invalid redirect target", body = ProblemDetails),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails),
         (status = 404, description = "Blog not found", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[delete("/blogs/<id>")]
+#[delete("/blogs/<id>?<redirect_to>")]
 pub async fn delete_blog(
     state: &State<AppState>,
     id: Uuid,
+    redirect_to: Option<String>,
     auth: ReadKey,
 ) -> Result<Status, ApiError> {
     let blog = Blog::find_by_id(&state.db, id).await?;
@@ -419,8 +512,26 @@ pub async fn delete_blog(
             .authorize_site_action(&state.db, *site_id, &SiteRole::Editor)
             .await?;
     }
+    if let Some(target) = redirect_to.as_deref() {
+        validate_destination_path(target)
+            .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    }
 
+    let paths = Blog::public_paths(&state.db, blog.content_id).await?;
     Blog::soft_delete(&state.db, id).await?;
+    if let Some(target) = redirect_to.as_deref() {
+        for (site_id, path) in &paths {
+            redirect_service::redirect_removed(
+                &state.db,
+                &[*site_id],
+                blog.content_id,
+                path,
+                target,
+                "Blog deleted",
+            )
+            .await;
+        }
+    }
     let site_id = site_ids.into_iter().next();
     audit_service::log_action(
         &state.db,
@@ -472,7 +583,11 @@ pub async fn clone_blog(
             .await?;
     }
 
-    let blog = Blog::clone_blog(&state.db, id, site_ids.clone()).await?;
+    let mut tx = state.db.begin().await?;
+    let blog = Blog::clone_blog(&mut tx, id, site_ids.clone()).await?;
+    version_service::record_version(&mut tx, blog.content_id, "Cloned blog", Some(auth.0.id))
+        .await?;
+    tx.commit().await?;
     let site_id = site_ids.into_iter().next();
     let metadata = serde_json::json!({ "cloned_from": id.to_string() });
     audit_service::log_action(
@@ -497,14 +612,15 @@ pub async fn clone_blog(
     Ok((Status::Created, Json(BlogResponse::from(blog))))
 }
 
-/// Get blog detail (blog + all localizations + categories)
+/// Get blog detail (blog + all localizations with blocks + categories)
 #[utoipa::path(
     tag = "Blogs",
     operation_id = "get_blog_detail",
-    description = "Get blog with all localizations and categories",
+    description = "Get blog with all localizations (including their content blocks) and categories",
     params(("id" = Uuid, Path, description = "Blog UUID")),
     responses(
-        (status = 200, description = "Blog detail with localizations", body = BlogDetailResponse),
+        (status = 200, description = "Blog detail with localizations", body = BlogDetailResponse,
+            headers(("ETag" = String, description = "Entity tag to send back as If-Match when updating"))),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 404, description = "Blog not found", body = ProblemDetails)
     ),
@@ -515,7 +631,7 @@ pub async fn get_blog_detail(
     state: &State<AppState>,
     id: Uuid,
     auth: ReadKey,
-) -> Result<Json<BlogDetailResponse>, ApiError> {
+) -> Result<WithETag<Json<BlogDetailResponse>>, ApiError> {
     let blog = Blog::find_by_id(&state.db, id).await?;
     let site_ids = Content::find_site_ids(&state.db, blog.content_id).await?;
     for site_id in &site_ids {
@@ -525,10 +641,9 @@ pub async fn get_blog_detail(
     }
     let localizations =
         ContentLocalization::find_all_for_content(&state.db, blog.content_id).await?;
-    let loc_responses: Vec<LocalizationResponse> = localizations
-        .into_iter()
-        .map(LocalizationResponse::from)
-        .collect();
+    let localization_ids: Vec<Uuid> = localizations.iter().map(|l| l.id).collect();
+    let blocks = ContentBlock::find_for_localizations(&state.db, &localization_ids).await?;
+    let loc_responses = LocalizationWithBlocksResponse::from_parts(localizations, blocks);
     let categories = Category::find_for_content(&state.db, blog.content_id).await?;
     let cat_responses: Vec<CategoryResponse> =
         categories.into_iter().map(CategoryResponse::from).collect();
@@ -542,12 +657,16 @@ pub async fn get_blog_detail(
         doc_responses.push(BlogDocumentResponse::from_parts(detail, doc_locs));
     }
 
-    Ok(Json(BlogDetailResponse {
-        blog: BlogResponse::from(blog),
-        localizations: loc_responses,
-        categories: cat_responses,
-        documents: doc_responses,
-    }))
+    let etag = ContentService::etag(&state.db, blog.content_id).await?;
+    Ok(WithETag::new(
+        Json(BlogDetailResponse {
+            blog: BlogResponse::from(blog),
+            localizations: loc_responses,
+            categories: cat_responses,
+            documents: doc_responses,
+        }),
+        etag,
+    ))
 }
 
 /// Get blog localizations
@@ -628,8 +747,9 @@ pub async fn create_blog_localization(
         )));
     }
 
+    let mut tx = state.db.begin().await?;
     let localization = ContentLocalization::create(
-        &state.db,
+        &mut *tx,
         blog.content_id,
         req.locale_id,
         &req.title,
@@ -640,6 +760,14 @@ pub async fn create_blog_localization(
         req.meta_description.as_deref(),
     )
     .await?;
+    version_service::record_version(
+        &mut tx,
+        blog.content_id,
+        "Added localization",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
 
     Ok((
         Status::Created,
@@ -681,8 +809,9 @@ pub async fn update_blog_localization(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
+    let mut tx = state.db.begin().await?;
     let localization = ContentLocalization::update(
-        &state.db,
+        &mut *tx,
         loc_id,
         req.title.as_deref(),
         req.subtitle.as_deref(),
@@ -693,6 +822,14 @@ pub async fn update_blog_localization(
         req.translation_status.as_ref(),
     )
     .await?;
+    version_service::record_version(
+        &mut tx,
+        existing_loc.content_id,
+        "Updated localization",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
 
     Ok(Json(LocalizationResponse::from(localization)))
 }
@@ -725,7 +862,16 @@ pub async fn delete_blog_localization(
             .await?;
     }
 
-    ContentLocalization::delete(&state.db, loc_id).await?;
+    let mut tx = state.db.begin().await?;
+    ContentLocalization::delete(&mut *tx, loc_id).await?;
+    version_service::record_version(
+        &mut tx,
+        existing_loc.content_id,
+        "Deleted localization",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
     Ok(Status::NoContent)
 }
 
@@ -902,6 +1048,7 @@ pub async fn rss_feed(
     .fetch_optional(&state.db)
     .await?;
     let base_url = domain.map(|d| format!("https://{d}")).unwrap_or_default();
+    let blog_prefix = Blog::path_prefix(&state.db, site_id).await?;
 
     // Fetch last 50 published posts
     let blogs = Blog::find_published_for_site(&state.db, site_id, 50, 0).await?;
@@ -933,7 +1080,7 @@ pub async fn rss_feed(
         let link = blog
             .slug
             .as_ref()
-            .map(|s| format!("{base_url}/blog/{s}"))
+            .map(|s| format!("{base_url}{}", Blog::public_path(&blog_prefix, s)))
             .unwrap_or_default();
 
         let guid = GuidBuilder::default()
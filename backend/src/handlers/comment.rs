@@ -0,0 +1,499 @@
+//! Comment handlers
+//!
+//! Public submission and listing of reader comments on blog posts, plus the
+//! per-site moderation queue. New comments stay pending until a moderator
+//! approves them.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::bulk::{BulkAction, BulkContentResponse};
+use crate::dto::comment::{
+    BulkCommentRequest, CommentResponse, CommentSubmittedResponse, CommentThreadResponse,
+    CreateCommentRequest, PaginatedComments, UpdateCommentRequest,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::guards::client_guard::ClientInfo;
+use crate::models::audit::AuditAction;
+use crate::models::blog::{Blog, BlogWithContent};
+use crate::models::comment::{Comment, CommentStatus};
+use crate::models::content::{Content, ContentStatus};
+use crate::models::locale::Locale;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, comment_service, notification_service, webhook_service};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// Find a published blog post of a site, or 404
+async fn find_site_blog(
+    state: &State<AppState>,
+    site_id: Uuid,
+    blog_id: Uuid,
+) -> Result<BlogWithContent, ApiError> {
+    let not_found = || ApiError::NotFound(format!("Blog with ID {} not found", blog_id));
+    let blog = Blog::find_by_id(&state.db, blog_id)
+        .await
+        .map_err(|_| not_found())?;
+    let site_ids = Content::find_site_ids(&state.db, blog.content_id).await?;
+    if !site_ids.contains(&site_id) || blog.status != ContentStatus::Published {
+        return Err(not_found());
+    }
+    Ok(blog)
+}
+
+/// Load a comment and check the caller holds `role` on its site
+async fn authorize_comment(
+    state: &State<AppState>,
+    auth: &ReadKey,
+    id: Uuid,
+    role: &SiteRole,
+) -> Result<Comment, ApiError> {
+    let comment = Comment::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, comment.site_id, role)
+        .await?;
+    Ok(comment)
+}
+
+fn parse_status(status: &str) -> Result<CommentStatus, ApiError> {
+    match status.to_lowercase().as_str() {
+        "pending" => Ok(CommentStatus::Pending),
+        "approved" => Ok(CommentStatus::Approved),
+        "spam" => Ok(CommentStatus::Spam),
+        "deleted" => Ok(CommentStatus::Deleted),
+        _ => Err(ApiError::Validation(format!("Invalid status: {}", status))),
+    }
+}
+
+/// Submit a comment on a blog post
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "submit_comment",
+    description = "Submit a comment or reply on a published blog post. The comment is held for moderation. Submissions are rate-limited per client IP and rejected when the post does not allow comments.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("blog_id" = Uuid, Path, description = "Blog UUID")
+    ),
+    request_body(content = CreateCommentRequest, description = "Comment"),
+    responses(
+        (status = 201, description = "Comment received", body = CommentSubmittedResponse),
+        (status = 400, description = "Validation error or invalid parent", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Comments are disabled for this post", body = ProblemDetails),
+        (status = 404, description = "Blog not found", body = ProblemDetails),
+        (status = 429, description = "Too many submissions", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/blogs/<blog_id>/comments", data = "<body>")]
+pub async fn submit_comment(
+    state: &State<AppState>,
+    site_id: Uuid,
+    blog_id: Uuid,
+    body: Json<CreateCommentRequest>,
+    client: ClientInfo,
+    auth: ReadKey,
+) -> Result<(Status, Json<CommentSubmittedResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    comment_service::check_submission_rate(state, client.ip.as_deref()).await?;
+
+    let blog = find_site_blog(state, site_id, blog_id).await?;
+    if !blog.allow_comments {
+        return Err(ApiError::Forbidden(
+            "Comments are disabled for this blog post".to_string(),
+        ));
+    }
+
+    let mut locale_id = match req.locale.as_deref() {
+        Some(code) => Some(
+            Locale::find_by_code(&state.db, code)
+                .await
+                .map_err(|_| ApiError::BadRequest(format!("Unknown locale: {}", code)))?
+                .id,
+        ),
+        None => None,
+    };
+
+    if let Some(parent_id) = req.parent_id {
+        let parent = Comment::find_by_id(&state.db, parent_id)
+            .await
+            .ok()
+            .filter(|p| p.blog_id == blog_id && p.status == CommentStatus::Approved)
+            .ok_or_else(|| {
+                ApiError::BadRequest(
+                    "parent_id must be an approved comment on the same blog post".to_string(),
+                )
+            })?;
+        // Replies stay in the thread's language
+        locale_id = parent.locale_id;
+    }
+
+    let comment = Comment::create(
+        &state.db,
+        blog_id,
+        site_id,
+        locale_id,
+        req.parent_id,
+        &req.author_name,
+        req.author_email.as_deref(),
+        req.author_url.as_deref(),
+        &req.body,
+        client.ip.as_deref(),
+        client.user_agent.as_deref(),
+    )
+    .await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "comment",
+        comment.id,
+        Some(serde_json::json!({ "blog_id": blog_id })),
+    )
+    .await;
+    notification_service::notify_comment_submitted(
+        state.db.clone(),
+        site_id,
+        blog_id,
+        blog.slug.as_deref().unwrap_or_default(),
+        &comment.author_name,
+    );
+    webhook_service::dispatch(
+        state.db.clone(),
+        site_id,
+        "comment.created",
+        comment.id,
+        serde_json::to_value(CommentResponse::from(comment.clone())).unwrap_or_default(),
+    );
+
+    Ok((
+        Status::Created,
+        Json(CommentSubmittedResponse {
+            id: comment.id,
+            status: comment.status,
+            created_at: comment.created_at,
+        }),
+    ))
+}
+
+/// List approved comments of a blog post
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "list_blog_comments",
+    description = "List the approved comments of a published blog post as threads (oldest first), optionally for one locale.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("blog_id" = Uuid, Path, description = "Blog UUID"),
+        ("locale" = Option<String>, Query, description = "Only comments written in this locale")
+    ),
+    responses(
+        (status = 200, description = "Comment threads", body = Vec<CommentThreadResponse>),
+        (status = 400, description = "Unknown locale", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Blog not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/blogs/<blog_id>/comments?<locale>", rank = 2)]
+pub async fn list_blog_comments(
+    state: &State<AppState>,
+    site_id: Uuid,
+    blog_id: Uuid,
+    locale: Option<String>,
+    auth: ReadKey,
+) -> Result<Json<Vec<CommentThreadResponse>>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    find_site_blog(state, site_id, blog_id).await?;
+
+    let locale_id = match locale.as_deref() {
+        Some(code) => Some(
+            Locale::find_by_code(&state.db, code)
+                .await
+                .map_err(|_| ApiError::BadRequest(format!("Unknown locale: {}", code)))?
+                .id,
+        ),
+        None => None,
+    };
+
+    let comments = Comment::find_approved_for_blog(&state.db, blog_id, locale_id).await?;
+    Ok(Json(comment_service::build_threads(comments)))
+}
+
+/// Moderation queue of a site
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "list_site_comments",
+    description = "List a site's comments for moderation, newest first. Filter by status (pending, approved, spam, deleted) and blog post.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("status" = Option<String>, Query, description = "Filter by status"),
+        ("blog_id" = Option<Uuid>, Query, description = "Filter by blog UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated comments", body = PaginatedComments),
+        (status = 400, description = "Invalid status", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/comments?<status>&<blog_id>&<page>&<per_page>")]
+pub async fn list_site_comments(
+    state: &State<AppState>,
+    site_id: Uuid,
+    status: Option<String>,
+    blog_id: Option<Uuid>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedComments>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let status = status.as_deref().map(parse_status).transpose()?;
+
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let comments =
+        Comment::find_for_site(&state.db, site_id, status, blog_id, limit, offset).await?;
+    let total = Comment::count_for_site(&state.db, site_id, status, blog_id).await?;
+
+    let items: Vec<CommentResponse> = comments.into_iter().map(CommentResponse::from).collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Get a comment
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "get_comment",
+    description = "Get a comment with its moderation details",
+    params(("id" = Uuid, Path, description = "Comment UUID")),
+    responses(
+        (status = 200, description = "Comment", body = CommentResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Comment not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/comments/<id>")]
+pub async fn get_comment(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<CommentResponse>, ApiError> {
+    let comment = authorize_comment(state, &auth, id, &SiteRole::Editor).await?;
+    Ok(Json(CommentResponse::from(comment)))
+}
+
+/// Moderate a comment
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "moderate_comment",
+    description = "Approve a comment, mark it as spam, hide it as deleted, or send it back to pending",
+    params(("id" = Uuid, Path, description = "Comment UUID")),
+    request_body(content = UpdateCommentRequest, description = "New status"),
+    responses(
+        (status = 200, description = "Comment moderated", body = CommentResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Comment not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/comments/<id>", data = "<body>")]
+pub async fn moderate_comment(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpdateCommentRequest>,
+    auth: ReadKey,
+) -> Result<Json<CommentResponse>, ApiError> {
+    let req = body.into_inner();
+    let existing = authorize_comment(state, &auth, id, &SiteRole::Editor).await?;
+
+    let comment = Comment::update_status(&state.db, id, req.status, Some(auth.0.id)).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(comment.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "comment",
+        id,
+        Some(serde_json::json!({
+            "status": { "old": existing.status, "new": comment.status }
+        })),
+    )
+    .await;
+    webhook_service::dispatch(
+        state.db.clone(),
+        comment.site_id,
+        "comment.moderated",
+        id,
+        serde_json::to_value(CommentResponse::from(comment.clone())).unwrap_or_default(),
+    );
+
+    Ok(Json(CommentResponse::from(comment)))
+}
+
+/// Delete a comment permanently
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "delete_comment",
+    description = "Permanently delete a comment and all replies to it. To hide a comment but keep it, set its status to Deleted instead.",
+    params(("id" = Uuid, Path, description = "Comment UUID")),
+    responses(
+        (status = 204, description = "Comment deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Comment not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/comments/<id>")]
+pub async fn delete_comment(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let comment = authorize_comment(state, &auth, id, &SiteRole::Editor).await?;
+
+    Comment::purge(&state.db, id).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(comment.site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "comment",
+        id,
+        None,
+    )
+    .await;
+    webhook_service::dispatch(
+        state.db.clone(),
+        comment.site_id,
+        "comment.deleted",
+        id,
+        serde_json::json!({ "id": id, "blog_id": comment.blog_id }),
+    );
+
+    Ok(Status::NoContent)
+}
+
+/// Bulk moderation of a site's comments
+#[utoipa::path(
+    tag = "Comments",
+    operation_id = "bulk_comments",
+    description = "Change the status of (UpdateStatus) or permanently delete (Delete) multiple comments of a site",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = BulkCommentRequest, description = "Bulk moderation request"),
+    responses(
+        (status = 200, description = "Bulk operation results", body = BulkContentResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/comments/bulk", data = "<body>")]
+pub async fn bulk_comments(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<BulkCommentRequest>,
+    auth: ReadKey,
+) -> Result<Json<BulkContentResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+
+    let response = comment_service::bulk_moderate(
+        &state.db,
+        site_id,
+        &req.ids,
+        &req.action,
+        req.status,
+        auth.0.id,
+    )
+    .await?;
+
+    let (audit_action, event) = match req.action {
+        BulkAction::UpdateStatus => (AuditAction::Update, "comment.moderated"),
+        BulkAction::Delete => (AuditAction::Delete, "comment.deleted"),
+    };
+    for result in response.results.iter().filter(|r| r.success) {
+        audit_service::log_action(
+            &state.db,
+            Some(site_id),
+            Some(auth.0.id),
+            audit_action.clone(),
+            "comment",
+            result.id,
+            req.status.map(|s| serde_json::json!({ "status": s })),
+        )
+        .await;
+        webhook_service::dispatch(
+            state.db.clone(),
+            site_id,
+            event,
+            result.id,
+            serde_json::json!({ "id": result.id, "status": req.status }),
+        );
+    }
+
+    Ok(Json(response))
+}
+
+/// Collect comment routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        submit_comment,
+        list_blog_comments,
+        list_site_comments,
+        get_comment,
+        moderate_comment,
+        delete_comment,
+        bulk_comments
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 7, "Should have 7 comment routes");
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(parse_status("Pending").unwrap(), CommentStatus::Pending);
+        assert_eq!(parse_status("spam").unwrap(), CommentStatus::Spam);
+        assert!(parse_status("archived").is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Content block handlers
+//!
+//! Structured block editor API for content localizations (blogs, pages, ...).
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::content_block::{
+    BlockSchemaResponse, ContentBlockResponse, CreateContentBlockRequest,
+    ReorderContentBlocksRequest, UpdateContentBlockRequest,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::{AuthenticatedKey, ReadKey};
+use crate::models::content::{Content, ContentLocalization};
+use crate::models::content_block::ContentBlock;
+use crate::models::site_membership::SiteRole;
+use crate::services::{block_service, version_service};
+use crate::AppState;
+
+/// Ensure the caller holds `role` on every site of the localization's content.
+/// Returns the content ID.
+async fn authorize_localization(
+    state: &State<AppState>,
+    auth: &AuthenticatedKey,
+    localization_id: Uuid,
+    role: &SiteRole,
+) -> Result<Uuid, ApiError> {
+    let localization = ContentLocalization::find_by_id(&state.db, localization_id).await?;
+    let site_ids = Content::find_site_ids(&state.db, localization.content_id).await?;
+    for site_id in &site_ids {
+        auth.authorize_site_action(&state.db, *site_id, role)
+            .await?;
+    }
+    Ok(localization.content_id)
+}
+
+/// List block data schemas
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "list_block_schemas",
+    description = "List the JSON schema that `block_data` must satisfy for each block type",
+    responses(
+        (status = 200, description = "Block schemas", body = Vec<BlockSchemaResponse>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/blocks/schemas")]
+pub async fn list_block_schemas(_auth: ReadKey) -> Json<Vec<BlockSchemaResponse>> {
+    Json(
+        block_service::BLOCK_TYPES
+            .iter()
+            .map(|t| BlockSchemaResponse {
+                block_type: *t,
+                schema: block_service::block_schema(*t),
+            })
+            .collect(),
+    )
+}
+
+/// List blocks of a localization
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "list_content_blocks",
+    description = "List the blocks of a content localization in display order",
+    params(("localization_id" = Uuid, Path, description = "Localization UUID")),
+    responses(
+        (status = 200, description = "Content blocks", body = Vec<ContentBlockResponse>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Localization not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/localizations/<localization_id>/blocks")]
+pub async fn list_content_blocks(
+    state: &State<AppState>,
+    localization_id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<Vec<ContentBlockResponse>>, ApiError> {
+    authorize_localization(state, &auth.0, localization_id, &SiteRole::Viewer).await?;
+
+    let blocks = ContentBlock::find_for_localization(&state.db, localization_id).await?;
+    Ok(Json(
+        blocks.into_iter().map(ContentBlockResponse::from).collect(),
+    ))
+}
+
+/// Create a block
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "create_content_block",
+    description = "Add a block to a content localization. Without a position the block is appended; with one, later blocks move down.",
+    params(("localization_id" = Uuid, Path, description = "Localization UUID")),
+    request_body(content = CreateContentBlockRequest, description = "Block data"),
+    responses(
+        (status = 201, description = "Block created", body = ContentBlockResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Localization not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/localizations/<localization_id>/blocks", data = "<body>")]
+pub async fn create_content_block(
+    state: &State<AppState>,
+    localization_id: Uuid,
+    body: Json<CreateContentBlockRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<ContentBlockResponse>), ApiError> {
+    let content_id =
+        authorize_localization(state, &auth.0, localization_id, &SiteRole::Author).await?;
+
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    block_service::validate_block_data(req.block_type, &req.block_data)?;
+
+    let mut tx = state.db.begin().await?;
+    let block = ContentBlock::create(
+        &mut tx,
+        localization_id,
+        req.block_type,
+        &req.block_data,
+        req.position,
+    )
+    .await?;
+    version_service::record_version(&mut tx, content_id, "Added block", Some(auth.0.id)).await?;
+    tx.commit().await?;
+
+    Ok((Status::Created, Json(ContentBlockResponse::from(block))))
+}
+
+/// Get a block
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "get_content_block",
+    description = "Get a content block by ID",
+    params(("id" = Uuid, Path, description = "Block UUID")),
+    responses(
+        (status = 200, description = "Content block", body = ContentBlockResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Block not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/blocks/<id>")]
+pub async fn get_content_block(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<ContentBlockResponse>, ApiError> {
+    let block = ContentBlock::find_by_id(&state.db, id).await?;
+    authorize_localization(
+        state,
+        &auth.0,
+        block.content_localization_id,
+        &SiteRole::Viewer,
+    )
+    .await?;
+
+    Ok(Json(ContentBlockResponse::from(block)))
+}
+
+/// Update a block
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "update_content_block",
+    description = "Change a block's type and/or data. The resulting data is validated against the schema of the resulting type.",
+    params(("id" = Uuid, Path, description = "Block UUID")),
+    request_body(content = UpdateContentBlockRequest, description = "Block update data"),
+    responses(
+        (status = 200, description = "Block updated", body = ContentBlockResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Block not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/blocks/<id>", data = "<body>")]
+pub async fn update_content_block(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpdateContentBlockRequest>,
+    auth: ReadKey,
+) -> Result<Json<ContentBlockResponse>, ApiError> {
+    let existing = ContentBlock::find_by_id(&state.db, id).await?;
+    let content_id = authorize_localization(
+        state,
+        &auth.0,
+        existing.content_localization_id,
+        &SiteRole::Author,
+    )
+    .await?;
+
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    let block_type = req.block_type.unwrap_or(existing.block_type);
+    let block_data = req.block_data.unwrap_or(existing.block_data);
+    block_service::validate_block_data(block_type, &block_data)?;
+
+    let mut tx = state.db.begin().await?;
+    let block = ContentBlock::update(&mut *tx, id, block_type, &block_data).await?;
+    version_service::record_version(&mut tx, content_id, "Updated block", Some(auth.0.id)).await?;
+    tx.commit().await?;
+
+    Ok(Json(ContentBlockResponse::from(block)))
+}
+
+/// Batch-reorder blocks of a localization
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "reorder_content_blocks",
+    description = "Batch-reorder the blocks of a content localization",
+    params(("localization_id" = Uuid, Path, description = "Localization UUID")),
+    request_body(content = ReorderContentBlocksRequest, description = "New ordering"),
+    responses(
+        (status = 204, description = "Blocks reordered"),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Block not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/localizations/<localization_id>/blocks/reorder", data = "<body>")]
+pub async fn reorder_content_blocks(
+    state: &State<AppState>,
+    localization_id: Uuid,
+    body: Json<ReorderContentBlocksRequest>,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let content_id =
+        authorize_localization(state, &auth.0, localization_id, &SiteRole::Author).await?;
+
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    let items: Vec<(Uuid, i16)> = req
+        .items
+        .into_iter()
+        .map(|i| (i.id, i.block_order))
+        .collect();
+    let mut tx = state.db.begin().await?;
+    ContentBlock::reorder_for_localization(&mut tx, localization_id, items).await?;
+    version_service::record_version(&mut tx, content_id, "Reordered blocks", Some(auth.0.id))
+        .await?;
+    tx.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+/// Delete a block
+#[utoipa::path(
+    tag = "Content Blocks",
+    operation_id = "delete_content_block",
+    description = "Delete a content block",
+    params(("id" = Uuid, Path, description = "Block UUID")),
+    responses(
+        (status = 204, description = "Block deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Block not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/blocks/<id>")]
+pub async fn delete_content_block(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let block = ContentBlock::find_by_id(&state.db, id).await?;
+    let content_id = authorize_localization(
+        state,
+        &auth.0,
+        block.content_localization_id,
+        &SiteRole::Editor,
+    )
+    .await?;
+
+    let mut tx = state.db.begin().await?;
+    ContentBlock::delete(&mut *tx, id).await?;
+    version_service::record_version(&mut tx, content_id, "Deleted block", Some(auth.0.id)).await?;
+    tx.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+/// Collect content block routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_block_schemas,
+        list_content_blocks,
+        create_content_block,
+        get_content_block,
+        update_content_block,
+        reorder_content_blocks,
+        delete_content_block
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 7, "Should have 7 content block routes");
+    }
+}
@@ -0,0 +1,320 @@
+//! Content site handlers (cross-site syndication)
+//!
+//! Share existing content with further sites, override its slug, featured flag
+//! and position per site, and take it off a site again. Sites that created the
+//! content are owners: managing shares needs Editor access on them, and they
+//! cannot be removed here.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::content_site::{
+    ContentSiteResponse, ShareContentRequest, UpdateContentSiteRequest,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::{AuthenticatedKey, ReadKey};
+use crate::models::audit::AuditAction;
+use crate::models::blog::Blog;
+use crate::models::content::{Content, ContentStatus};
+use crate::models::content_site::ContentSite;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, redirect_service};
+use crate::AppState;
+
+/// Ensure the content exists and the caller holds `role` on every owning site
+async fn authorize_owners(
+    state: &State<AppState>,
+    auth: &AuthenticatedKey,
+    content_id: Uuid,
+    role: &SiteRole,
+) -> Result<(Content, Vec<ContentSite>), ApiError> {
+    let content = Content::find_by_id(&state.db, content_id).await?;
+    let sites = ContentSite::find_for_content(&state.db, content_id).await?;
+    for site in sites.iter().filter(|s| s.is_owner) {
+        auth.authorize_site_action(&state.db, site.site_id, role)
+            .await?;
+    }
+    Ok((content, sites))
+}
+
+/// Conflict unless `slug` is free for this kind of content on the site
+async fn ensure_slug_free(
+    state: &State<AppState>,
+    content: &Content,
+    site_id: Uuid,
+    slug: Option<&str>,
+) -> Result<(), ApiError> {
+    let Some(slug) = slug else {
+        return Ok(());
+    };
+    if ContentSite::slug_in_use(&state.db, site_id, content.entity_type_id, slug, content.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(format!(
+            "Slug '{}' is already in use on site {}; set a site_specific_slug",
+            slug, site_id
+        )));
+    }
+    Ok(())
+}
+
+/// List the sites a content item appears on
+#[utoipa::path(
+    tag = "Syndication",
+    operation_id = "list_content_sites",
+    description = "List the sites a content item appears on, owners first, with per-site slug, featured flag and position",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    responses(
+        (status = 200, description = "Site assignments", body = Vec<ContentSiteResponse>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/content/<content_id>/sites")]
+pub async fn list_content_sites(
+    state: &State<AppState>,
+    content_id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<Vec<ContentSiteResponse>>, ApiError> {
+    let (_, sites) = authorize_owners(state, &auth.0, content_id, &SiteRole::Viewer).await?;
+    Ok(Json(
+        sites.into_iter().map(ContentSiteResponse::from).collect(),
+    ))
+}
+
+/// Share a content item with another site
+#[utoipa::path(
+    tag = "Syndication",
+    operation_id = "share_content",
+    description = "Show an existing content item on another site. Needs Editor on the owning sites and Author on the target site. The slug in effect on the target site must be free there.",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    request_body(content = ShareContentRequest, description = "Target site and its settings"),
+    responses(
+        (status = 201, description = "Content shared", body = ContentSiteResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails),
+        (status = 409, description = "Already on the site, or the slug is taken there", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/content/<content_id>/sites", data = "<body>")]
+pub async fn share_content(
+    state: &State<AppState>,
+    content_id: Uuid,
+    body: Json<ShareContentRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<ContentSiteResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    let (content, sites) = authorize_owners(state, &auth.0, content_id, &SiteRole::Editor).await?;
+    auth.0
+        .authorize_site_action(&state.db, req.site_id, &SiteRole::Author)
+        .await?;
+
+    if sites.iter().any(|s| s.site_id == req.site_id) {
+        return Err(ApiError::Conflict(format!(
+            "Content is already on site {}",
+            req.site_id
+        )));
+    }
+    let slug = req
+        .site_specific_slug
+        .as_deref()
+        .or(content.slug.as_deref());
+    ensure_slug_free(state, &content, req.site_id, slug).await?;
+
+    let shared = ContentSite::create(
+        &state.db,
+        content_id,
+        req.site_id,
+        req.site_specific_slug.as_deref(),
+        req.is_featured,
+        req.display_order,
+    )
+    .await?;
+    audit_service::log_action(
+        &state.db,
+        Some(req.site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "content_site",
+        content_id,
+        Some(serde_json::json!({ "site_id": req.site_id })),
+    )
+    .await;
+
+    Ok((Status::Created, Json(ContentSiteResponse::from(shared))))
+}
+
+/// Update how a content item appears on one site
+#[utoipa::path(
+    tag = "Syndication",
+    operation_id = "update_content_site",
+    description = "Override the slug, featured flag or position of a content item on one site. Needs Editor on that site.",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("site_id" = Uuid, Path, description = "Site UUID")
+    ),
+    request_body(content = UpdateContentSiteRequest, description = "Per-site settings"),
+    responses(
+        (status = 200, description = "Settings updated", body = ContentSiteResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content is not on this site", body = ProblemDetails),
+        (status = 409, description = "Slug is taken on this site", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/content/<content_id>/sites/<site_id>", data = "<body>")]
+pub async fn update_content_site(
+    state: &State<AppState>,
+    content_id: Uuid,
+    site_id: Uuid,
+    body: Json<UpdateContentSiteRequest>,
+    auth: ReadKey,
+) -> Result<Json<ContentSiteResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let content = Content::find_by_id(&state.db, content_id).await?;
+    ContentSite::find(&state.db, content_id, site_id).await?;
+
+    if let Some(slug) = &req.site_specific_slug {
+        let slug = slug.as_deref().or(content.slug.as_deref());
+        ensure_slug_free(state, &content, site_id, slug).await?;
+    }
+
+    let old_paths = Blog::public_paths(&state.db, content_id).await?;
+    let updated = ContentSite::update(
+        &state.db,
+        content_id,
+        site_id,
+        req.site_specific_slug.as_ref().map(|s| s.as_deref()),
+        req.is_featured,
+        req.display_order,
+    )
+    .await?;
+    // A published blog keeps its old URL on this site working
+    if content.status == ContentStatus::Published {
+        let new_paths = Blog::public_paths(&state.db, content_id).await?;
+        let path_on_site = |paths: &[(Uuid, String)]| {
+            paths
+                .iter()
+                .find(|(s, _)| *s == site_id)
+                .map(|(_, p)| p.clone())
+        };
+        if let (Some(old_path), Some(new_path)) =
+            (path_on_site(&old_paths), path_on_site(&new_paths))
+        {
+            redirect_service::redirect_moved(
+                &state.db,
+                &[site_id],
+                content_id,
+                &old_path,
+                &new_path,
+                "Blog slug changed on site",
+            )
+            .await;
+        }
+    }
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "content_site",
+        content_id,
+        Some(serde_json::json!({ "site_id": site_id })),
+    )
+    .await;
+
+    Ok(Json(ContentSiteResponse::from(updated)))
+}
+
+/// Take a content item off a site
+#[utoipa::path(
+    tag = "Syndication",
+    operation_id = "unshare_content",
+    description = "Remove a shared content item from a site. Needs Editor on that site or on the owning sites. Owning sites cannot be removed; delete the content instead.",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("site_id" = Uuid, Path, description = "Site UUID")
+    ),
+    responses(
+        (status = 204, description = "Content removed from the site"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content is not on this site", body = ProblemDetails),
+        (status = 409, description = "Site owns the content", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/content/<content_id>/sites/<site_id>")]
+pub async fn unshare_content(
+    state: &State<AppState>,
+    content_id: Uuid,
+    site_id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    if auth
+        .0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await
+        .is_err()
+    {
+        authorize_owners(state, &auth.0, content_id, &SiteRole::Editor).await?;
+    }
+    let assignment = ContentSite::find(&state.db, content_id, site_id).await?;
+    if assignment.is_owner {
+        return Err(ApiError::Conflict(
+            "Cannot remove content from a site that owns it".to_string(),
+        ));
+    }
+
+    ContentSite::delete(&state.db, content_id, site_id).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "content_site",
+        content_id,
+        Some(serde_json::json!({ "site_id": site_id })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Collect content site routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_content_sites,
+        share_content,
+        update_content_site,
+        unshare_content
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 4, "Should have 4 content site routes");
+    }
+}
@@ -0,0 +1,232 @@
+//! Content version handlers
+//!
+//! Version history for blogs, pages, CV entries and legal documents,
+//! addressed by their shared content ID.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+
+use crate::dto::content_version::{
+    ContentVersionListItem, ContentVersionResponse, PaginatedContentVersions, VersionDiffResponse,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::content_version::ContentVersion;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, version_service, webhook_service};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// List versions of a content item (paginated)
+#[utoipa::path(
+    tag = "Versions",
+    operation_id = "list_content_versions",
+    description = "List recorded versions of a content item, newest first",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated version list", body = PaginatedContentVersions),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/content/<content_id>/versions?<page>&<per_page>")]
+pub async fn list_content_versions(
+    state: &State<AppState>,
+    content_id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedContentVersions>, ApiError> {
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Viewer)
+        .await?;
+
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let versions = ContentVersion::find_for_content(&state.db, content_id, limit, offset).await?;
+    let total = ContentVersion::count_for_content(&state.db, content_id).await?;
+
+    let items: Vec<ContentVersionListItem> = versions
+        .into_iter()
+        .map(ContentVersionListItem::from)
+        .collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Get a single version with its snapshot
+#[utoipa::path(
+    tag = "Versions",
+    operation_id = "get_content_version",
+    description = "Get a specific version of a content item, including its full snapshot",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("version_number" = i32, Path, description = "Version number")
+    ),
+    responses(
+        (status = 200, description = "Content version", body = ContentVersionResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Version not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/content/<content_id>/versions/<version_number>", rank = 2)]
+pub async fn get_content_version(
+    state: &State<AppState>,
+    content_id: Uuid,
+    version_number: i32,
+    auth: ReadKey,
+) -> Result<Json<ContentVersionResponse>, ApiError> {
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Viewer)
+        .await?;
+    let version = ContentVersion::find_by_number(&state.db, content_id, version_number).await?;
+    Ok(Json(ContentVersionResponse::from(version)))
+}
+
+/// Diff two versions field by field
+#[utoipa::path(
+    tag = "Versions",
+    operation_id = "diff_content_versions",
+    description = "Compare two versions of a content item field by field",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("from" = i32, Query, description = "Older version number"),
+        ("to" = i32, Query, description = "Newer version number")
+    ),
+    responses(
+        (status = 200, description = "Field-level diff", body = VersionDiffResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Version not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/content/<content_id>/versions/diff?<from>&<to>", rank = 1)]
+pub async fn diff_content_versions(
+    state: &State<AppState>,
+    content_id: Uuid,
+    from: i32,
+    to: i32,
+    auth: ReadKey,
+) -> Result<Json<VersionDiffResponse>, ApiError> {
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Viewer)
+        .await?;
+
+    let old = ContentVersion::find_by_number(&state.db, content_id, from).await?;
+    let new = ContentVersion::find_by_number(&state.db, content_id, to).await?;
+
+    Ok(Json(VersionDiffResponse {
+        content_id,
+        from_version: from,
+        to_version: to,
+        changes: version_service::diff_snapshots(&old.snapshot, &new.snapshot),
+    }))
+}
+
+/// Restore a version as a new version
+#[utoipa::path(
+    tag = "Versions",
+    operation_id = "restore_content_version",
+    description = "Restore the fields and localizations of an earlier version. The result is recorded as a new version; status, publish window and a page's route and parent are not changed.",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("version_number" = i32, Path, description = "Version number to restore")
+    ),
+    responses(
+        (status = 201, description = "Version restored", body = ContentVersionResponse),
+        (status = 400, description = "Snapshot cannot be restored", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Version not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/content/<content_id>/versions/<version_number>/restore")]
+pub async fn restore_content_version(
+    state: &State<AppState>,
+    content_id: Uuid,
+    version_number: i32,
+    auth: ReadKey,
+) -> Result<(Status, Json<ContentVersionResponse>), ApiError> {
+    let site_ids = auth
+        .0
+        .authorize_content(&state.db, content_id, &SiteRole::Editor)
+        .await?;
+
+    let version =
+        version_service::restore_version(&state.db, content_id, version_number, Some(auth.0.id))
+            .await?;
+
+    let entity_type = version.snapshot["entity_type"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let entity_id = version.snapshot["entity_id"]
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(content_id);
+
+    let site_id = site_ids.into_iter().next();
+    audit_service::log_action(
+        &state.db,
+        site_id,
+        Some(auth.0.id),
+        AuditAction::Restore,
+        &entity_type,
+        entity_id,
+        Some(serde_json::json!({
+            "content_id": content_id,
+            "restored_version": version_number,
+            "new_version": version.version_number,
+        })),
+    )
+    .await;
+    if let Some(sid) = site_id {
+        webhook_service::dispatch(
+            state.db.clone(),
+            sid,
+            &format!("{entity_type}.updated"),
+            entity_id,
+            serde_json::json!({
+                "content_id": content_id,
+                "restored_version": version_number,
+                "version_number": version.version_number,
+            }),
+        );
+    }
+
+    Ok((Status::Created, Json(ContentVersionResponse::from(version))))
+}
+
+/// Collect content version routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_content_versions,
+        get_content_version,
+        diff_content_versions,
+        restore_content_version
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 4, "Should have 4 content version routes");
+    }
+}
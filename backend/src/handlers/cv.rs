@@ -15,7 +15,7 @@ use crate::guards::auth_guard::ReadKey;
 use crate::models::audit::AuditAction;
 use crate::models::cv::{CvEntry, CvEntryType, Skill};
 use crate::models::site_membership::SiteRole;
-use crate::services::audit_service;
+use crate::services::{audit_service, version_service};
 use crate::utils::pagination::PaginationParams;
 use crate::AppState;
 
@@ -325,7 +325,13 @@ pub async fn create_cv_entry(
     }
 
     let site_id = req.site_ids.first().copied();
-    let entry = CvEntry::create(&state.db, req).await?;
+    let mut tx = state.db.begin().await?;
+    let entry = CvEntry::create(&mut tx, req).await?;
+    if let Some(content_id) = entry.content_id {
+        version_service::record_version(&mut tx, content_id, "Created CV entry", Some(auth.0.id))
+            .await?;
+    }
+    tx.commit().await?;
     audit_service::log_action(
         &state.db,
         site_id,
@@ -365,7 +371,13 @@ pub async fn update_cv_entry(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    let entry = CvEntry::update(&state.db, id, req).await?;
+    let mut tx = state.db.begin().await?;
+    let entry = CvEntry::update(&mut tx, id, req).await?;
+    if let Some(content_id) = entry.content_id {
+        version_service::record_version(&mut tx, content_id, "Updated CV entry", Some(auth.0.id))
+            .await?;
+    }
+    tx.commit().await?;
     audit_service::log_action(
         &state.db,
         None,
@@ -0,0 +1,230 @@
+//! Edit lock handlers
+//!
+//! Advisory locks that let the admin UI show who is editing a content item.
+//! Locks expire unless renewed with a heartbeat and are not enforced on writes;
+//! use `If-Match` on updates to actually prevent lost changes.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::edit_lock::{
+    AcquireEditLockRequest, EditLockResponse, EditLockStatusResponse, EDIT_LOCK_TTL_SECONDS,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::edit_lock::EditLock;
+use crate::models::site_membership::SiteRole;
+use crate::services::audit_service;
+use crate::AppState;
+
+/// Conflict error naming the editor who holds the lock
+fn locked_by(lock: &EditLock) -> ApiError {
+    let holder = lock
+        .holder_name
+        .clone()
+        .or_else(|| lock.holder_clerk_user_id.clone())
+        .unwrap_or_else(|| lock.holder_id.to_string());
+    ApiError::Conflict(format!(
+        "Content is being edited by {} until {}",
+        holder,
+        lock.expires_at.to_rfc3339()
+    ))
+}
+
+/// Get the edit lock of a content item
+#[utoipa::path(
+    tag = "Edit Locks",
+    operation_id = "get_edit_lock",
+    description = "Show whether someone is currently editing a content item. Expired locks are reported as unlocked.",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    responses(
+        (status = 200, description = "Lock state", body = EditLockStatusResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/content/<content_id>/lock")]
+pub async fn get_edit_lock(
+    state: &State<AppState>,
+    content_id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<EditLockStatusResponse>, ApiError> {
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Viewer)
+        .await?;
+
+    let lock = EditLock::find_active(&state.db, content_id)
+        .await?
+        .map(|lock| EditLockResponse::from_lock(lock, auth.0.id));
+    Ok(Json(EditLockStatusResponse {
+        locked: lock.is_some(),
+        lock,
+    }))
+}
+
+/// Acquire the edit lock of a content item
+#[utoipa::path(
+    tag = "Edit Locks",
+    operation_id = "acquire_edit_lock",
+    description = "Start editing a content item. Succeeds if the item is unlocked, the previous lock expired, or the caller already holds it. The lock expires after 120 seconds unless renewed.",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    request_body(content = AcquireEditLockRequest, description = "Holder details"),
+    responses(
+        (status = 200, description = "Lock acquired", body = EditLockResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails),
+        (status = 409, description = "Someone else is editing", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/content/<content_id>/lock", data = "<body>")]
+pub async fn acquire_edit_lock(
+    state: &State<AppState>,
+    content_id: Uuid,
+    body: Json<AcquireEditLockRequest>,
+    auth: ReadKey,
+) -> Result<Json<EditLockResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Author)
+        .await?;
+
+    match EditLock::acquire(
+        &state.db,
+        content_id,
+        auth.0.id,
+        auth.0.clerk_user_id(),
+        req.holder_name.as_deref(),
+        EDIT_LOCK_TTL_SECONDS,
+    )
+    .await?
+    {
+        Some(lock) => Ok(Json(EditLockResponse::from_lock(lock, auth.0.id))),
+        None => match EditLock::find_active(&state.db, content_id).await? {
+            Some(lock) => Err(locked_by(&lock)),
+            None => Err(ApiError::Conflict(
+                "Edit lock changed hands, try again".to_string(),
+            )),
+        },
+    }
+}
+
+/// Renew the edit lock of a content item
+#[utoipa::path(
+    tag = "Edit Locks",
+    operation_id = "heartbeat_edit_lock",
+    description = "Keep holding the edit lock for another 120 seconds. Send this periodically while the editor is open.",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    responses(
+        (status = 200, description = "Lock renewed", body = EditLockResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails),
+        (status = 409, description = "The caller does not hold the lock (anymore)", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/content/<content_id>/lock")]
+pub async fn heartbeat_edit_lock(
+    state: &State<AppState>,
+    content_id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<EditLockResponse>, ApiError> {
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Author)
+        .await?;
+
+    match EditLock::heartbeat(&state.db, content_id, auth.0.id, EDIT_LOCK_TTL_SECONDS).await? {
+        Some(lock) => Ok(Json(EditLockResponse::from_lock(lock, auth.0.id))),
+        None => match EditLock::find_active(&state.db, content_id).await? {
+            Some(lock) => Err(locked_by(&lock)),
+            None => Err(ApiError::Conflict(
+                "Edit lock expired; acquire it again".to_string(),
+            )),
+        },
+    }
+}
+
+/// Release the edit lock of a content item
+#[utoipa::path(
+    tag = "Edit Locks",
+    operation_id = "release_edit_lock",
+    description = "Stop editing a content item. Releasing someone else's unexpired lock requires the Admin role on the content's sites.",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    responses(
+        (status = 204, description = "Lock released (or there was none)"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/content/<content_id>/lock")]
+pub async fn release_edit_lock(
+    state: &State<AppState>,
+    content_id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let site_ids = auth
+        .0
+        .authorize_content(&state.db, content_id, &SiteRole::Author)
+        .await?;
+
+    if let Some(lock) = EditLock::find_active(&state.db, content_id).await? {
+        if lock.holder_id != auth.0.id {
+            for site_id in &site_ids {
+                auth.0
+                    .authorize_site_action(&state.db, *site_id, &SiteRole::Admin)
+                    .await?;
+            }
+            audit_service::log_action(
+                &state.db,
+                site_ids.first().copied(),
+                Some(auth.0.id),
+                AuditAction::Delete,
+                "edit_lock",
+                content_id,
+                Some(serde_json::json!({
+                    "holder_id": lock.holder_id,
+                    "holder_clerk_user_id": lock.holder_clerk_user_id,
+                })),
+            )
+            .await;
+        }
+    }
+
+    EditLock::release(&state.db, content_id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Collect edit lock routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        get_edit_lock,
+        acquire_edit_lock,
+        heartbeat_edit_lock,
+        release_edit_lock
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 4, "Should have 4 edit lock routes");
+    }
+}
@@ -0,0 +1,600 @@
+//! Form handlers
+//!
+//! Form definitions are managed per site by editors. The public site loads a
+//! form by slug with labels for its locale and posts submissions, which land in
+//! an inbox that editors can browse, export as CSV, and prune.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::form::{
+    CreateFormRequest, FormField, FormResponse, FormSubmissionResponse, FormSubmittedResponse,
+    PaginatedFormSubmissions, PaginatedForms, PublicFormResponse, SubmitFormRequest,
+    UpdateFormRequest,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::guards::client_guard::ClientInfo;
+use crate::models::audit::AuditAction;
+use crate::models::form::{Form, FormSubmission};
+use crate::models::locale::Locale;
+use crate::models::site_locale::SiteLocale;
+use crate::models::site_membership::SiteRole;
+use crate::models::site_settings::{SiteSetting, KEY_CONTACT_EMAIL};
+use crate::services::{audit_service, form_service, webhook_service};
+use crate::utils::pagination::PaginationParams;
+use crate::utils::response::FileDownload;
+use crate::AppState;
+
+/// Load a form and check the caller holds `role` on its site
+async fn authorize_form(
+    state: &State<AppState>,
+    auth: &ReadKey,
+    id: Uuid,
+    role: &SiteRole,
+) -> Result<Form, ApiError> {
+    let form = Form::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, form.site_id, role)
+        .await?;
+    Ok(form)
+}
+
+/// Check field definitions against the site's languages and serialize them for storage
+async fn prepare_fields(
+    state: &State<AppState>,
+    site_id: Uuid,
+    fields: &[FormField],
+) -> Result<serde_json::Value, ApiError> {
+    let locale_codes: Vec<String> = SiteLocale::find_all_for_site(&state.db, site_id)
+        .await?
+        .into_iter()
+        .map(|l| l.code)
+        .collect();
+    form_service::validate_definition(fields, &locale_codes)?;
+    Ok(serde_json::to_value(fields)?)
+}
+
+/// Find an active form of a site by slug, or 404
+async fn find_active_form(
+    state: &State<AppState>,
+    site_id: Uuid,
+    slug: &str,
+) -> Result<Form, ApiError> {
+    let form = Form::find_by_slug(&state.db, site_id, slug).await?;
+    if !form.is_active {
+        return Err(ApiError::NotFound(format!("Form '{}' not found", slug)));
+    }
+    Ok(form)
+}
+
+/// List forms of a site
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "list_forms",
+    description = "List all forms of a site (paginated)",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated form list", body = PaginatedForms),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/forms?<page>&<per_page>")]
+pub async fn list_forms(
+    state: &State<AppState>,
+    site_id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedForms>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let forms = Form::find_all_for_site(&state.db, site_id, limit, offset).await?;
+    let total = Form::count_for_site(&state.db, site_id).await?;
+
+    let items: Vec<FormResponse> = forms.into_iter().map(FormResponse::from).collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Get a form
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "get_form",
+    description = "Get a form definition with labels in all languages",
+    params(("id" = Uuid, Path, description = "Form UUID")),
+    responses(
+        (status = 200, description = "Form", body = FormResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/forms/<id>")]
+pub async fn get_form(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<FormResponse>, ApiError> {
+    let form = authorize_form(state, &auth, id, &SiteRole::Viewer).await?;
+    Ok(Json(FormResponse::from(form)))
+}
+
+/// Create a form
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "create_form",
+    description = "Create a form on a site. Field labels may only use the site's languages.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = CreateFormRequest, description = "Form definition"),
+    responses(
+        (status = 201, description = "Form created", body = FormResponse),
+        (status = 400, description = "Invalid request", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 409, description = "Slug already in use on this site", body = ProblemDetails),
+        (status = 422, description = "Invalid field definitions", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/forms", data = "<body>")]
+pub async fn create_form(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<CreateFormRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<FormResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let fields = prepare_fields(state, site_id, &req.fields).await?;
+
+    let form = Form::create(
+        &state.db,
+        site_id,
+        &req.slug,
+        &req.name,
+        &fields,
+        req.recipient_email.as_deref(),
+        req.retention_days,
+        req.is_active,
+        Some(auth.0.id),
+    )
+    .await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "form",
+        form.id,
+        Some(serde_json::json!({ "slug": form.slug })),
+    )
+    .await;
+
+    Ok((Status::Created, Json(FormResponse::from(form))))
+}
+
+/// Update a form
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "update_form",
+    description = "Update a form. `fields` replaces all field definitions; existing submissions keep their values.",
+    params(("id" = Uuid, Path, description = "Form UUID")),
+    request_body(content = UpdateFormRequest, description = "Fields to update"),
+    responses(
+        (status = 200, description = "Form updated", body = FormResponse),
+        (status = 400, description = "Invalid request", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found", body = ProblemDetails),
+        (status = 409, description = "Slug already in use on this site", body = ProblemDetails),
+        (status = 422, description = "Invalid field definitions", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/forms/<id>", data = "<body>")]
+pub async fn update_form(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpdateFormRequest>,
+    auth: ReadKey,
+) -> Result<Json<FormResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    let existing = authorize_form(state, &auth, id, &SiteRole::Editor).await?;
+    let fields = match &req.fields {
+        Some(fields) => Some(prepare_fields(state, existing.site_id, fields).await?),
+        None => None,
+    };
+
+    let form = Form::update(
+        &state.db,
+        id,
+        req.slug.as_deref(),
+        req.name.as_deref(),
+        fields.as_ref(),
+        req.recipient_email.as_ref().map(|e| e.as_deref()),
+        req.retention_days,
+        req.is_active,
+    )
+    .await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(form.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "form",
+        form.id,
+        None,
+    )
+    .await;
+
+    Ok(Json(FormResponse::from(form)))
+}
+
+/// Delete a form
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "delete_form",
+    description = "Delete a form together with all of its submissions",
+    params(("id" = Uuid, Path, description = "Form UUID")),
+    responses(
+        (status = 204, description = "Form deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/forms/<id>")]
+pub async fn delete_form(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let form = authorize_form(state, &auth, id, &SiteRole::Editor).await?;
+    Form::delete(&state.db, id).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(form.site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "form",
+        id,
+        Some(serde_json::json!({ "slug": form.slug })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Get a form for rendering on the public site
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "get_public_form",
+    description = "Get an active form by slug with labels in one locale. Falls back to the site's default language for missing labels.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("slug" = String, Path, description = "Form slug"),
+        ("locale" = Option<String>, Query, description = "Locale code for the labels")
+    ),
+    responses(
+        (status = 200, description = "Form", body = PublicFormResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found or inactive", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/forms/by-slug/<slug>?<locale>")]
+pub async fn get_public_form(
+    state: &State<AppState>,
+    site_id: Uuid,
+    slug: &str,
+    locale: Option<String>,
+    auth: ReadKey,
+) -> Result<Json<PublicFormResponse>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let form = find_active_form(state, site_id, slug).await?;
+
+    let site_locales = SiteLocale::find_all_for_site(&state.db, site_id).await?;
+    let default_locale = site_locales
+        .iter()
+        .find(|l| l.is_default)
+        .map(|l| l.code.clone());
+    let locale = locale
+        .filter(|code| site_locales.iter().any(|l| &l.code == code))
+        .or(default_locale.clone());
+
+    let fields = form_service::localize(
+        form.field_definitions(),
+        locale.as_deref(),
+        default_locale.as_deref(),
+    );
+    Ok(Json(PublicFormResponse {
+        id: form.id,
+        slug: form.slug,
+        name: form.name,
+        locale,
+        fields,
+    }))
+}
+
+/// Submit a form
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "submit_form",
+    description = "Submit values for an active form. Submissions are rate-limited per client IP. Requests with a filled-in honeypot are accepted but discarded.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("slug" = String, Path, description = "Form slug")
+    ),
+    request_body(content = SubmitFormRequest, description = "Field values"),
+    responses(
+        (status = 201, description = "Submission received", body = FormSubmittedResponse),
+        (status = 400, description = "Invalid request", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found or inactive", body = ProblemDetails),
+        (status = 422, description = "Values do not match the field rules", body = ProblemDetails),
+        (status = 429, description = "Too many submissions", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/forms/by-slug/<slug>/submissions", data = "<body>")]
+pub async fn submit_form(
+    state: &State<AppState>,
+    site_id: Uuid,
+    slug: &str,
+    body: Json<SubmitFormRequest>,
+    client: ClientInfo,
+    auth: ReadKey,
+) -> Result<(Status, Json<FormSubmittedResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    form_service::check_submission_rate(state, client.ip.as_deref()).await?;
+    let form = find_active_form(state, site_id, slug).await?;
+
+    // Bots fill in every input; pretend success so they don't adapt
+    if req.honeypot.as_deref().is_some_and(|v| !v.is_empty()) {
+        tracing::debug!(form_id = %form.id, "Discarded form submission with filled honeypot");
+        return Ok((
+            Status::Created,
+            Json(FormSubmittedResponse {
+                id: Uuid::new_v4(),
+                received_at: Utc::now(),
+            }),
+        ));
+    }
+
+    let locale_id = match req.locale.as_deref() {
+        Some(code) => Some(
+            Locale::find_by_code(&state.db, code)
+                .await
+                .map_err(|_| ApiError::BadRequest(format!("Unknown locale: {}", code)))?
+                .id,
+        ),
+        None => None,
+    };
+    let values = form_service::validate_submission(&form.field_definitions(), &req.data)?;
+
+    let submission = FormSubmission::create(
+        &state.db,
+        form.id,
+        site_id,
+        locale_id,
+        &serde_json::Value::Object(values),
+        client.ip.as_deref(),
+        client.user_agent.as_deref(),
+    )
+    .await?;
+
+    let recipient_email = match form.recipient_email.clone() {
+        Some(email) => Some(email),
+        None => SiteSetting::get_value(&state.db, site_id, KEY_CONTACT_EMAIL)
+            .await
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .filter(|email| !email.is_empty()),
+    };
+    webhook_service::dispatch(
+        state.db.clone(),
+        site_id,
+        "form.submitted",
+        submission.id,
+        serde_json::json!({
+            "form_id": form.id,
+            "form_slug": form.slug,
+            "form_name": form.name,
+            "recipient_email": recipient_email,
+            "locale": req.locale,
+            "data": submission.data,
+            "submitted_at": submission.created_at,
+        }),
+    );
+
+    Ok((
+        Status::Created,
+        Json(FormSubmittedResponse {
+            id: submission.id,
+            received_at: submission.created_at,
+        }),
+    ))
+}
+
+/// List submissions of a form
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "list_form_submissions",
+    description = "List the submissions of a form, newest first",
+    params(
+        ("id" = Uuid, Path, description = "Form UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated submissions", body = PaginatedFormSubmissions),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/forms/<id>/submissions?<page>&<per_page>")]
+pub async fn list_form_submissions(
+    state: &State<AppState>,
+    id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedFormSubmissions>, ApiError> {
+    authorize_form(state, &auth, id, &SiteRole::Editor).await?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let submissions = FormSubmission::find_for_form(&state.db, id, limit, offset).await?;
+    let total = FormSubmission::count_for_form(&state.db, id).await?;
+
+    let items: Vec<FormSubmissionResponse> = submissions
+        .into_iter()
+        .map(FormSubmissionResponse::from)
+        .collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Export submissions of a form as CSV
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "export_form_submissions",
+    description = "Download all submissions of a form as CSV, oldest first, with one column per current field",
+    params(("id" = Uuid, Path, description = "Form UUID")),
+    responses(
+        (status = 200, description = "CSV file", content_type = "text/csv", body = String),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Form not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/forms/<id>/submissions/export")]
+pub async fn export_form_submissions(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<FileDownload, ApiError> {
+    let form = authorize_form(state, &auth, id, &SiteRole::Editor).await?;
+
+    let submissions = FormSubmission::find_all_for_form(&state.db, id).await?;
+    let locale_codes: HashMap<Uuid, String> = Locale::find_all_including_inactive(&state.db)
+        .await?
+        .into_iter()
+        .map(|l| (l.id, l.code))
+        .collect();
+
+    Ok(FileDownload {
+        body: form_service::render_csv(&form.field_definitions(), &submissions, &locale_codes)?
+            .into_bytes(),
+        content_type: ContentType::CSV,
+        filename: format!("{}-submissions.csv", form.slug),
+    })
+}
+
+/// Delete a submission
+#[utoipa::path(
+    tag = "Forms",
+    operation_id = "delete_form_submission",
+    description = "Permanently delete a form submission",
+    params(("id" = Uuid, Path, description = "Submission UUID")),
+    responses(
+        (status = 204, description = "Submission deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Submission not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/form-submissions/<id>")]
+pub async fn delete_form_submission(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let submission = FormSubmission::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, submission.site_id, &SiteRole::Editor)
+        .await?;
+    FormSubmission::delete(&state.db, id).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(submission.site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "form_submission",
+        id,
+        Some(serde_json::json!({ "form_id": submission.form_id })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Collect form routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_forms,
+        get_form,
+        create_form,
+        update_form,
+        delete_form,
+        get_public_form,
+        submit_form,
+        list_form_submissions,
+        export_form_submissions,
+        delete_form_submission
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 10, "Should have 10 form routes");
+    }
+}
@@ -0,0 +1,395 @@
+//! Global section handlers
+//!
+//! Reusable sections are managed per site. Pages place them with
+//! `POST /pages/{page_id}/global-sections`; see the page handlers for that and
+//! for detaching a reference into a local copy.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::global_section::{
+    CreateGlobalSectionRequest, GlobalSectionLocalizationResponse, GlobalSectionResponse,
+    GlobalSectionUsageResponse, PaginatedGlobalSections, UpdateGlobalSectionRequest,
+};
+use crate::dto::page::UpsertSectionLocalizationRequest;
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::global_section::{GlobalSection, GlobalSectionLocalization};
+use crate::models::site_membership::SiteRole;
+use crate::services::audit_service;
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// Load a global section and check the caller holds `role` on its site
+async fn authorize_section(
+    state: &State<AppState>,
+    auth: &ReadKey,
+    id: Uuid,
+    role: &SiteRole,
+) -> Result<GlobalSection, ApiError> {
+    let section = GlobalSection::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, section.site_id, role)
+        .await?;
+    Ok(section)
+}
+
+/// List global sections of a site
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "list_global_sections",
+    description = "List the reusable sections of a site (paginated)",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated global sections", body = PaginatedGlobalSections),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/global-sections?<page>&<per_page>")]
+pub async fn list_global_sections(
+    state: &State<AppState>,
+    site_id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedGlobalSections>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let sections = GlobalSection::find_all_for_site(&state.db, site_id, limit, offset).await?;
+    let total = GlobalSection::count_for_site(&state.db, site_id).await?;
+
+    let items: Vec<GlobalSectionResponse> = sections
+        .into_iter()
+        .map(GlobalSectionResponse::from)
+        .collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Get a global section
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "get_global_section",
+    description = "Get a global section by ID",
+    params(("id" = Uuid, Path, description = "Global section UUID")),
+    responses(
+        (status = 200, description = "Global section", body = GlobalSectionResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Global section not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/global-sections/<id>")]
+pub async fn get_global_section(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<GlobalSectionResponse>, ApiError> {
+    let section = authorize_section(state, &auth, id, &SiteRole::Viewer).await?;
+    Ok(Json(GlobalSectionResponse::from(section)))
+}
+
+/// Create a global section
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "create_global_section",
+    description = "Create a reusable section on a site",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = CreateGlobalSectionRequest, description = "Section data"),
+    responses(
+        (status = 201, description = "Global section created", body = GlobalSectionResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 409, description = "Name already in use on this site", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/global-sections", data = "<body>")]
+pub async fn create_global_section(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<CreateGlobalSectionRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<GlobalSectionResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+
+    let section = GlobalSection::create(&state.db, site_id, &req, Some(auth.0.id)).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "global_section",
+        section.id,
+        Some(serde_json::json!({ "name": section.name })),
+    )
+    .await;
+
+    Ok((Status::Created, Json(GlobalSectionResponse::from(section))))
+}
+
+/// Update a global section
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "update_global_section",
+    description = "Update a global section. The change shows up on every page that references it.",
+    params(("id" = Uuid, Path, description = "Global section UUID")),
+    request_body(content = UpdateGlobalSectionRequest, description = "Fields to update"),
+    responses(
+        (status = 200, description = "Global section updated", body = GlobalSectionResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Global section not found", body = ProblemDetails),
+        (status = 409, description = "Name already in use on this site", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/global-sections/<id>", data = "<body>")]
+pub async fn update_global_section(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpdateGlobalSectionRequest>,
+    auth: ReadKey,
+) -> Result<Json<GlobalSectionResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    let existing = authorize_section(state, &auth, id, &SiteRole::Editor).await?;
+
+    let section = GlobalSection::update(&state.db, id, &req).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(existing.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "global_section",
+        id,
+        Some(serde_json::json!({ "usage_count": section.usage_count })),
+    )
+    .await;
+
+    Ok(Json(GlobalSectionResponse::from(section)))
+}
+
+/// Delete a global section
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "delete_global_section",
+    description = "Delete a global section. Fails while pages still reference it; detach or remove those sections first.",
+    params(("id" = Uuid, Path, description = "Global section UUID")),
+    responses(
+        (status = 204, description = "Global section deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Global section not found", body = ProblemDetails),
+        (status = 409, description = "Global section is still in use", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/global-sections/<id>")]
+pub async fn delete_global_section(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let section = authorize_section(state, &auth, id, &SiteRole::Editor).await?;
+    if section.usage_count > 0 {
+        return Err(ApiError::Conflict(format!(
+            "Global section is used by {} page section(s)",
+            section.usage_count
+        )));
+    }
+
+    GlobalSection::delete(&state.db, id).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(section.site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "global_section",
+        id,
+        Some(serde_json::json!({ "name": section.name })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// List where a global section is used
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "get_global_section_usage",
+    description = "List the page sections that reference a global section, by page route",
+    params(("id" = Uuid, Path, description = "Global section UUID")),
+    responses(
+        (status = 200, description = "Referencing page sections", body = Vec<GlobalSectionUsageResponse>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Global section not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/global-sections/<id>/usage")]
+pub async fn get_global_section_usage(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<Vec<GlobalSectionUsageResponse>>, ApiError> {
+    authorize_section(state, &auth, id, &SiteRole::Viewer).await?;
+    let usages = GlobalSection::find_usages(&state.db, id).await?;
+    Ok(Json(
+        usages
+            .into_iter()
+            .map(GlobalSectionUsageResponse::from)
+            .collect(),
+    ))
+}
+
+/// Get localizations of a global section
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "get_global_section_localizations",
+    description = "Get all localizations of a global section",
+    params(("id" = Uuid, Path, description = "Global section UUID")),
+    responses(
+        (status = 200, description = "Localizations", body = Vec<GlobalSectionLocalizationResponse>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Global section not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/global-sections/<id>/localizations")]
+pub async fn get_global_section_localizations(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<Vec<GlobalSectionLocalizationResponse>>, ApiError> {
+    authorize_section(state, &auth, id, &SiteRole::Viewer).await?;
+    let localizations = GlobalSectionLocalization::find_for_section(&state.db, id).await?;
+    Ok(Json(
+        localizations
+            .into_iter()
+            .map(GlobalSectionLocalizationResponse::from)
+            .collect(),
+    ))
+}
+
+/// Upsert a global section localization
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "upsert_global_section_localization",
+    description = "Create or update a localization of a global section",
+    params(("id" = Uuid, Path, description = "Global section UUID")),
+    request_body(content = UpsertSectionLocalizationRequest, description = "Localization data"),
+    responses(
+        (status = 200, description = "Localization upserted", body = GlobalSectionLocalizationResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Global section not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/global-sections/<id>/localizations", data = "<body>")]
+pub async fn upsert_global_section_localization(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpsertSectionLocalizationRequest>,
+    auth: ReadKey,
+) -> Result<Json<GlobalSectionLocalizationResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    authorize_section(state, &auth, id, &SiteRole::Editor).await?;
+
+    let localization = GlobalSectionLocalization::upsert(
+        &state.db,
+        id,
+        req.locale_id,
+        req.title.as_deref(),
+        req.text.as_deref(),
+        req.button_text.as_deref(),
+    )
+    .await?;
+    Ok(Json(GlobalSectionLocalizationResponse::from(localization)))
+}
+
+/// Delete a global section localization
+#[utoipa::path(
+    tag = "Global Sections",
+    operation_id = "delete_global_section_localization",
+    description = "Delete a localization of a global section",
+    params(("id" = Uuid, Path, description = "Localization UUID")),
+    responses(
+        (status = 204, description = "Localization deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Localization not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/global-sections/localizations/<id>")]
+pub async fn delete_global_section_localization(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let localization = GlobalSectionLocalization::find_by_id(&state.db, id).await?;
+    authorize_section(
+        state,
+        &auth,
+        localization.global_section_id,
+        &SiteRole::Editor,
+    )
+    .await?;
+    GlobalSectionLocalization::delete(&state.db, id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Collect global section routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_global_sections,
+        get_global_section,
+        create_global_section,
+        update_global_section,
+        delete_global_section,
+        get_global_section_usage,
+        get_global_section_localizations,
+        upsert_global_section_localization,
+        delete_global_section_localization
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 9, "Should have 9 global section routes");
+    }
+}
@@ -20,7 +20,7 @@ use crate::models::legal::{
     LegalDocType, LegalDocument, LegalDocumentLocalization, LegalGroup, LegalItem,
 };
 use crate::models::site_membership::SiteRole;
-use crate::services::audit_service;
+use crate::services::{audit_service, version_service};
 use crate::utils::pagination::PaginationParams;
 use crate::AppState;
 
@@ -216,7 +216,18 @@ pub async fn create_legal_document(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    let document = LegalDocument::create(&state.db, req).await?;
+    let mut tx = state.db.begin().await?;
+    let document = LegalDocument::create(&mut tx, req).await?;
+    if let Some(content_id) = document.content_id {
+        version_service::record_version(
+            &mut tx,
+            content_id,
+            "Created legal document",
+            Some(auth.0.id),
+        )
+        .await?;
+    }
+    tx.commit().await?;
     audit_service::log_action(
         &state.db,
         Some(site_id),
@@ -256,7 +267,18 @@ pub async fn update_legal_document(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    let document = LegalDocument::update(&state.db, id, req).await?;
+    let mut tx = state.db.begin().await?;
+    let document = LegalDocument::update(&mut tx, id, req).await?;
+    if let Some(content_id) = document.content_id {
+        version_service::record_version(
+            &mut tx,
+            content_id,
+            "Updated legal document",
+            Some(auth.0.id),
+        )
+        .await?;
+    }
+    tx.commit().await?;
     audit_service::log_action(
         &state.db,
         None,
@@ -0,0 +1,138 @@
+//! Markdown import/export handlers
+//!
+//! Move a site's blogs and pages to and from a zip of Markdown files with
+//! YAML front matter, for static site generators and content kept in git.
+
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::ContentType;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+
+use crate::dto::markdown_transfer::MarkdownImportReport;
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::site::Site;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, markdown_transfer_service};
+use crate::utils::response::FileDownload;
+use crate::AppState;
+
+/// Maximum size of an uploaded archive
+const MAX_IMPORT_MIB: u64 = 100;
+
+/// Export published blogs and pages as Markdown
+#[utoipa::path(
+    tag = "Markdown",
+    operation_id = "export_markdown",
+    description = "Download the site's published blogs and pages as a zip of Markdown files with YAML front matter. Blogs are written to `blog/<slug>.md` and pages to `pages/<route>.md` (`pages/index.md` for `/`). The default locale has no suffix; other localizations are sibling files such as `blog/<slug>.de.md`. Front matter holds the slug, dates, author, tag and category slugs and cover image URL. Page sections are not exported.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    responses(
+        (status = 200, description = "Markdown archive", content_type = "application/zip", body = String),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Site not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/export/markdown")]
+pub async fn export_markdown(
+    state: &State<AppState>,
+    site_id: Uuid,
+    auth: ReadKey,
+) -> Result<FileDownload, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let site = Site::find_by_id(&state.db, site_id).await?;
+
+    Ok(FileDownload {
+        body: markdown_transfer_service::export(&state.db, site_id).await?,
+        content_type: ContentType::ZIP,
+        filename: format!("{}-markdown.zip", site.slug),
+    })
+}
+
+/// Import blogs and pages from Markdown
+#[utoipa::path(
+    tag = "Markdown",
+    operation_id = "import_markdown",
+    description = "Import a zip of Markdown files with YAML front matter, laid out like an export. The `blog/` and `pages/` folders may sit below other folders. Blogs are matched by slug and pages by route: matches are updated from the files, everything else is created. Files are imported in the locale named in their front matter or file name suffix, else in the site's default locale. Tags and categories are linked by slug and created if missing. Files that cannot be imported are listed as warnings.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content_type = "application/zip", content = String, description = "Zip of Markdown files (max 100 MiB)"),
+    responses(
+        (status = 200, description = "Import report", body = MarkdownImportReport),
+        (status = 400, description = "Not a zip of Markdown files, or the site has no default locale", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Site not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/import/markdown", data = "<data>")]
+pub async fn import_markdown(
+    state: &State<AppState>,
+    site_id: Uuid,
+    data: Data<'_>,
+    auth: ReadKey,
+) -> Result<Json<MarkdownImportReport>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    Site::find_by_id(&state.db, site_id).await?;
+
+    let body = data
+        .open(MAX_IMPORT_MIB.mebibytes())
+        .into_bytes()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read archive: {}", e)))?;
+    if !body.is_complete() {
+        return Err(ApiError::BadRequest(format!(
+            "Archive exceeds {} MiB",
+            MAX_IMPORT_MIB
+        )));
+    }
+    let bytes = body.into_inner();
+    let files =
+        tokio::task::spawn_blocking(move || markdown_transfer_service::read_archive(&bytes))
+            .await
+            .map_err(|e| ApiError::Internal(format!("Archive task failed: {e}")))??;
+
+    let report = markdown_transfer_service::import(state, site_id, files, Some(auth.0.id)).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "markdown_import",
+        site_id,
+        Some(serde_json::json!({
+            "blogs_created": report.blogs.created,
+            "blogs_updated": report.blogs.updated,
+            "pages_created": report.pages.created,
+            "pages_updated": report.pages.updated,
+            "warnings": report.warnings.len(),
+        })),
+    )
+    .await;
+
+    Ok(Json(report))
+}
+
+/// Collect Markdown import/export routes
+pub fn routes() -> Vec<Route> {
+    routes![export_markdown, import_markdown]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 2, "Should have 2 Markdown routes");
+    }
+}
@@ -1,29 +1,86 @@
 //! Media handlers
 
+use std::io::Cursor;
+
 use rocket::form::Form;
 use rocket::fs::TempFile;
-use rocket::http::Status;
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Responder, Response};
 use rocket::serde::json::Json;
-use rocket::{Route, State};
-use sha2::{Digest, Sha256};
+use rocket::{Request, Route, State};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::config::Settings;
 use crate::dto::media::{
-    AddMediaMetadataRequest, MediaListItem, MediaMetadataResponse, MediaResponse,
-    MediaSearchParams, PaginatedMedia, UpdateMediaMetadataRequest, UpdateMediaRequest,
-    UploadMediaRequest, ALL_ALLOWED_MIMES,
+    AddMediaMetadataRequest, CropSourceResponse, MediaListItem, MediaMetadataResponse,
+    MediaResponse, MediaSearchParams, PaginatedMedia, TransformUrlResponse,
+    UpdateArtDirectionRequest, UpdateMediaMetadataRequest, UpdateMediaRequest, UploadMediaRequest,
 };
 use crate::errors::{ApiError, ProblemDetails};
 use crate::guards::auth_guard::ReadKey;
 use crate::models::audit::AuditAction;
-use crate::models::media::{MediaFile, MediaMetadata, MediaVariant, StorageProvider};
+use crate::models::media::{CropRect, MediaFile, MediaMetadata};
 use crate::models::site_membership::SiteRole;
 use crate::models::site_settings::SiteSetting;
-use crate::services::{audit_service, image_service};
+use crate::services::image_service;
+use crate::services::media_service::{self, MediaUpload};
+use crate::services::{audit_service, image_transform_service};
 use crate::utils::pagination::PaginationParams;
 use crate::AppState;
 
+/// Transformed image, cacheable forever since its URL encodes all options
+/// and the version of the image's art direction
+pub struct TransformedImageResponse {
+    bytes: Vec<u8>,
+    content_type: String,
+}
+
+impl<'r> Responder<'r, 'static> for TransformedImageResponse {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        let content_type =
+            ContentType::parse_flexible(&self.content_type).unwrap_or(ContentType::Binary);
+        Response::build()
+            .status(Status::Ok)
+            .header(content_type)
+            .header(Header::new(
+                "Cache-Control",
+                "public, max-age=31536000, immutable",
+            ))
+            .sized_body(self.bytes.len(), Cursor::new(self.bytes))
+            .ok()
+    }
+}
+
+/// Add signed renditions to each crop of a media response
+fn with_crop_sources(settings: &Settings, mut media: MediaResponse) -> MediaResponse {
+    for crop in &mut media.crops {
+        let rect = CropRect {
+            x: crop.x,
+            y: crop.y,
+            width: crop.width,
+            height: crop.height,
+        };
+        crop.sources = image_transform_service::crop_specs(&crop.name, rect)
+            .into_iter()
+            .map(|spec| {
+                let (width, height) = image_service::target_size(rect.width, rect.height, &spec);
+                CropSourceResponse {
+                    width,
+                    height,
+                    url: image_transform_service::signed_url(
+                        settings,
+                        media.id,
+                        image_transform_service::url_version(media.updated_at),
+                        &spec,
+                    ),
+                }
+            })
+            .collect();
+    }
+    media
+}
+
 /// List all media files for a site (paginated, with optional search & filters)
 #[utoipa::path(
     tag = "Media",
@@ -105,7 +162,10 @@ pub async fn get_media(
     _auth: ReadKey,
 ) -> Result<Json<MediaResponse>, ApiError> {
     let media = MediaFile::find_with_variants(&state.db, id).await?;
-    Ok(Json(MediaResponse::from(media)))
+    Ok(Json(with_crop_sources(
+        &state.settings,
+        MediaResponse::from(media),
+    )))
 }
 
 /// Create a media file record
@@ -226,121 +286,12 @@ pub async fn upload_media(
         .await
         .map_err(|e| ApiError::Internal(format!("Failed to read uploaded file: {e}")))?;
 
-    if file_bytes.is_empty() {
-        return Err(ApiError::BadRequest("Uploaded file is empty".to_string()));
-    }
-
-    // 3. Detect MIME type via magic bytes
-    let mime_type = infer::get(&file_bytes)
-        .map(|t| t.mime_type().to_string())
-        .or_else(|| {
-            // Fallback: use the content type provided by the client
-            form.file.content_type().map(|ct| ct.to_string())
-        })
-        .unwrap_or_else(|| "application/octet-stream".to_string());
-
-    // For text-based types that infer can't detect, check extension
+    // 3. Parse optional fields
     let original_filename = form
         .file
         .raw_name()
         .map(|n| n.dangerous_unsafe_unsanitized_raw().as_str().to_string())
         .unwrap_or_else(|| "upload".to_string());
-
-    let mime_type = if mime_type == "application/octet-stream" {
-        // Try to infer from extension
-        match original_filename
-            .rsplit('.')
-            .next()
-            .map(|e| e.to_lowercase())
-        {
-            Some(ext) if ext == "md" => "text/markdown".to_string(),
-            Some(ext) if ext == "txt" => "text/plain".to_string(),
-            Some(ext) if ext == "svg" => "image/svg+xml".to_string(),
-            _ => mime_type,
-        }
-    } else {
-        mime_type
-    };
-
-    if !ALL_ALLOWED_MIMES.contains(&mime_type.as_str()) {
-        return Err(ApiError::BadRequest(format!(
-            "File type '{}' is not allowed",
-            mime_type
-        )));
-    }
-
-    // 4. Validate file size against per-site limit
-    let file_size = file_bytes.len() as i64;
-    let max_size = SiteSetting::get_value(
-        &state.db,
-        site_ids[0],
-        crate::models::site_settings::KEY_MAX_MEDIA_FILE_SIZE,
-    )
-    .await?
-    .as_i64()
-    .unwrap_or(52_428_800);
-
-    if file_size > max_size {
-        return Err(ApiError::BadRequest(format!(
-            "File size {} exceeds the maximum of {} bytes",
-            file_size, max_size
-        )));
-    }
-
-    // 5. Compute SHA-256 checksum
-    let checksum = {
-        let mut hasher = Sha256::new();
-        hasher.update(&file_bytes);
-        let hash = hasher.finalize();
-        hash.iter()
-            .map(|b| format!("{:02x}", b))
-            .collect::<String>()
-    };
-
-    // 6. Deduplication check
-    if let Some(existing) = MediaFile::find_by_checksum(&state.db, &checksum).await? {
-        let media = MediaFile::find_with_variants(&state.db, existing.id).await?;
-        return Ok((Status::Ok, Json(MediaResponse::from(media))));
-    }
-
-    // 7. Sanitize filename and build storage path
-    let sanitized_filename = sanitize_filename(&original_filename);
-    let now = chrono::Utc::now();
-    let storage_path = format!(
-        "{}/{}/{:02}/{}",
-        site_ids[0],
-        now.format("%Y"),
-        now.format("%m"),
-        sanitized_filename,
-    );
-
-    // 8. Store original file
-    let public_url = state
-        .storage
-        .store(&storage_path, &file_bytes, &mime_type)
-        .await?;
-
-    // 9. Detect image dimensions
-    let (width, height) = if mime_type.starts_with("image/") {
-        detect_image_dimensions(&file_bytes)
-    } else {
-        (None, None)
-    };
-
-    // 10. Generate image variants
-    let extension = original_filename.rsplit('.').next().unwrap_or("bin");
-    let base_path = storage_path
-        .rsplit_once('.')
-        .map(|(b, _)| b)
-        .unwrap_or(&storage_path);
-
-    let variants = if mime_type.starts_with("image/") && !mime_type.contains("svg") {
-        image_service::generate_variants(&file_bytes, base_path, extension, &state.storage).await?
-    } else {
-        vec![]
-    };
-
-    // 11. Parse optional fields
     let folder_id = form
         .folder_id
         .as_deref()
@@ -348,121 +299,38 @@ pub async fn upload_media(
         .map(Uuid::parse_str)
         .transpose()
         .map_err(|e| ApiError::BadRequest(format!("Invalid folder_id: {e}")))?;
-    let is_global = form.is_global.unwrap_or(false);
-    let storage_provider = if state.settings.storage.provider == "s3" {
-        StorageProvider::S3
-    } else {
-        StorageProvider::Local
-    };
 
-    // 12. Insert into database
-    let media = MediaFile::create_from_upload(
-        &state.db,
-        &sanitized_filename,
-        &original_filename,
-        &mime_type,
-        file_size,
-        storage_provider,
-        &storage_path,
-        &public_url,
-        &checksum,
-        width,
-        height,
-        Some(auth.0.id),
-        is_global,
-        folder_id,
-        site_ids,
+    // 4. Detect, deduplicate, store and generate variants
+    let stored = media_service::upload(
+        state,
+        MediaUpload {
+            bytes: file_bytes,
+            original_filename,
+            content_type: form.file.content_type().map(|ct| ct.to_string()),
+            site_ids,
+            folder_id,
+            is_global: form.is_global.unwrap_or(false),
+            uploaded_by: Some(auth.0.id),
+        },
     )
     .await?;
+    if !stored.created {
+        return Ok((Status::Ok, Json(stored.media)));
+    }
 
-    // 13. Insert variants
-    let db_variants = if !variants.is_empty() {
-        MediaVariant::create_batch(&state.db, media.id, variants).await?
-    } else {
-        vec![]
-    };
-
-    // 14. Audit log
+    // 5. Audit log
     audit_service::log_action(
         &state.db,
         None,
         Some(auth.0.id),
         AuditAction::Create,
         "media",
-        media.id,
+        stored.media.id,
         None,
     )
     .await;
 
-    // 15. Build response
-    let response = MediaResponse {
-        id: media.id,
-        filename: media.filename,
-        original_filename: media.original_filename,
-        mime_type: media.mime_type,
-        file_size: media.file_size,
-        storage_provider: media.storage_provider,
-        public_url: media.public_url,
-        width: media.width,
-        height: media.height,
-        duration: media.duration,
-        is_global: media.is_global,
-        created_at: media.created_at,
-        updated_at: media.updated_at,
-        variants: db_variants
-            .into_iter()
-            .map(crate::dto::media::MediaVariantResponse::from)
-            .collect(),
-    };
-
-    Ok((Status::Created, Json(response)))
-}
-
-/// Sanitize a filename: keep only safe characters, replace spaces with hyphens
-fn sanitize_filename(name: &str) -> String {
-    let name = name
-        .chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' {
-                c
-            } else {
-                '-'
-            }
-        })
-        .collect::<String>();
-
-    // Collapse consecutive hyphens
-    let mut result = String::with_capacity(name.len());
-    let mut prev_was_hyphen = false;
-    for c in name.chars() {
-        if c == '-' {
-            if !prev_was_hyphen {
-                result.push(c);
-            }
-            prev_was_hyphen = true;
-        } else {
-            result.push(c);
-            prev_was_hyphen = false;
-        }
-    }
-
-    if result.is_empty() {
-        "upload".to_string()
-    } else {
-        result
-    }
-}
-
-/// Detect image dimensions from bytes
-fn detect_image_dimensions(bytes: &[u8]) -> (Option<i32>, Option<i32>) {
-    match image::ImageReader::new(std::io::Cursor::new(bytes))
-        .with_guessed_format()
-        .ok()
-        .and_then(|r| r.into_dimensions().ok())
-    {
-        Some((w, h)) => (Some(w as i32), Some(h as i32)),
-        None => (None, None),
-    }
+    Ok((Status::Created, Json(stored.media)))
 }
 
 /// Update media file metadata
@@ -511,43 +379,112 @@ pub async fn update_media(
     Ok(Json(MediaListItem::from(media)))
 }
 
-/// Delete media file (soft delete + storage cleanup)
+/// Set the focal point and crops of an image
 #[utoipa::path(
     tag = "Media",
-    operation_id = "delete_media",
-    description = "Soft delete a media file and remove files from storage",
+    operation_id = "update_media_art_direction",
+    description = "Replace the focal point and named crops of an image. The focal point is kept in view by `fit=cover` transformations; each crop gets signed WebP renditions in `crops[].sources`, which are rendered in the background. Previously cached transformations are discarded.",
     params(("id" = Uuid, Path, description = "Media file UUID")),
+    request_body(content = UpdateArtDirectionRequest, description = "Focal point and crops"),
     responses(
-        (status = 204, description = "Media deleted"),
+        (status = 200, description = "Media file with crops", body = MediaResponse),
+        (status = 400, description = "Validation error, not an image, or crop outside the image", body = ProblemDetails),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails),
         (status = 404, description = "Media not found", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[delete("/media/<id>")]
-pub async fn delete_media(
+#[put("/media/<id>/art-direction", data = "<body>", rank = 2)]
+pub async fn update_media_art_direction(
     state: &State<AppState>,
     id: Uuid,
+    body: Json<UpdateArtDirectionRequest>,
     auth: ReadKey,
-) -> Result<Status, ApiError> {
-    // Fetch media and variants before soft-deleting
-    let media = MediaFile::find_by_id(&state.db, id).await?;
-    let variants = MediaVariant::find_for_media(&state.db, id).await?;
+) -> Result<Json<MediaResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    // Delete variant files from storage
-    for variant in &variants {
-        if let Err(e) = state.storage.delete(&variant.storage_path).await {
-            tracing::warn!(error = %e, path = %variant.storage_path, "Failed to delete variant file from storage");
+    let existing = MediaFile::find_by_id(&state.db, id).await?;
+    let site_id = auth
+        .0
+        .authorize_media(&state.db, &existing, &SiteRole::Author)
+        .await?;
+    if !existing.mime_type.starts_with("image/") {
+        return Err(ApiError::BadRequest(format!(
+            "Media file {} is not an image",
+            id
+        )));
+    }
+    if !req.crops.is_empty() {
+        let (Some(width), Some(height)) = (existing.width, existing.height) else {
+            return Err(ApiError::BadRequest(format!(
+                "Dimensions of media file {} are unknown; crops cannot be checked",
+                id
+            )));
+        };
+        for (name, rect) in &req.crops {
+            if rect.x + rect.width > width as u32 || rect.y + rect.height > height as u32 {
+                return Err(ApiError::BadRequest(format!(
+                    "Crop '{}' exceeds the image ({}x{})",
+                    name, width, height
+                )));
+            }
         }
     }
 
-    // Delete original file from storage
-    if let Err(e) = state.storage.delete(&media.storage_path).await {
-        tracing::warn!(error = %e, path = %media.storage_path, "Failed to delete original file from storage");
-    }
+    let media = MediaFile::set_art_direction(
+        &state.db,
+        id,
+        req.focal_point.map(|p| (p.x, p.y)),
+        &req.crops,
+    )
+    .await?;
+    image_transform_service::invalidate(&state.db, state.storage.as_ref(), id).await?;
+    image_transform_service::spawn_crop_variants(state.db.clone(), state.storage.clone(), media);
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "media",
+        id,
+        Some(serde_json::json!({
+            "focal_point": req.focal_point.map(|p| [p.x, p.y]),
+            "crops": req.crops.keys().collect::<Vec<_>>(),
+        })),
+    )
+    .await;
+
+    let media = MediaFile::find_with_variants(&state.db, id).await?;
+    Ok(Json(with_crop_sources(
+        &state.settings,
+        MediaResponse::from(media),
+    )))
+}
 
-    // Soft-delete the DB record
+/// Delete media file (moves it to the trash)
+#[utoipa::path(
+    tag = "Media",
+    operation_id = "delete_media",
+    description = "Soft delete a media file. It moves to the site's trash, where it can be restored until it is purged; its files stay in storage until then.",
+    params(("id" = Uuid, Path, description = "Media file UUID")),
+    responses(
+        (status = 204, description = "Media deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Media not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/media/<id>")]
+pub async fn delete_media(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
     MediaFile::soft_delete(&state.db, id).await?;
     audit_service::log_action(
         &state.db,
@@ -676,6 +613,126 @@ pub async fn delete_media_metadata(
     Ok(Status::NoContent)
 }
 
+/// Get a signed image transformation URL
+#[utoipa::path(
+    tag = "Media",
+    operation_id = "get_media_transform_url",
+    description = "Sign an on-the-fly transformation of an image. The returned URL can be used without an API key, e.g. in an `img` tag; changing any of its parameters invalidates the signature.",
+    params(
+        ("id" = Uuid, Path, description = "Media file UUID"),
+        ("crop" = Option<String>, Query, description = "Name of a crop to start from, e.g. 16:9"),
+        ("w" = Option<u32>, Query, description = "Width in pixels (max 4096)"),
+        ("h" = Option<u32>, Query, description = "Height in pixels (max 4096)"),
+        ("fit" = Option<String>, Query, description = "contain (default), cover or fill; cover and fill need both w and h"),
+        ("format" = Option<String>, Query, description = "jpeg, png or webp (default: the source format)"),
+        ("q" = Option<u8>, Query, description = "JPEG quality 1-100 (default 80)")
+    ),
+    responses(
+        (status = 200, description = "Signed URL", body = TransformUrlResponse),
+        (status = 400, description = "Media file is not an image", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 404, description = "Media or crop not found", body = ProblemDetails),
+        (status = 422, description = "Invalid options", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/media/<id>/transform-url?<crop>&<w>&<h>&<fit>&<format>&<q>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_media_transform_url(
+    state: &State<AppState>,
+    id: Uuid,
+    crop: Option<&str>,
+    w: Option<u32>,
+    h: Option<u32>,
+    fit: Option<&str>,
+    format: Option<&str>,
+    q: Option<u8>,
+    _auth: ReadKey,
+) -> Result<Json<TransformUrlResponse>, ApiError> {
+    let spec = image_transform_service::parse_spec(crop, w, h, fit, format, q)?;
+    let media = MediaFile::find_by_id(&state.db, id).await?;
+    if !media.mime_type.starts_with("image/") {
+        return Err(ApiError::BadRequest(format!(
+            "Media file {} is not an image",
+            id
+        )));
+    }
+    if let Some(name) = crop {
+        if !media.crop_rects().contains_key(name) {
+            return Err(ApiError::NotFound(format!(
+                "Media file {} has no crop '{}'",
+                id, name
+            )));
+        }
+    }
+
+    Ok(Json(TransformUrlResponse {
+        url: image_transform_service::signed_url(
+            &state.settings,
+            id,
+            image_transform_service::url_version(media.updated_at),
+            &spec,
+        ),
+    }))
+}
+
+/// Transform an image on the fly
+#[utoipa::path(
+    tag = "Media",
+    operation_id = "transform_media",
+    description = "Resize, crop and convert an image. Needs no API key but a signature `s` from `GET /media/{id}/transform-url`. `fit=cover` keeps the image's focal point in view. Results are cached in storage and served with a one-year `Cache-Control`. Images are never enlarged.",
+    params(
+        ("id" = Uuid, Path, description = "Media file UUID"),
+        ("crop" = Option<String>, Query, description = "Name of a crop to start from"),
+        ("w" = Option<u32>, Query, description = "Width in pixels"),
+        ("h" = Option<u32>, Query, description = "Height in pixels"),
+        ("fit" = Option<String>, Query, description = "contain, cover or fill"),
+        ("format" = Option<String>, Query, description = "jpeg, png or webp"),
+        ("q" = Option<u8>, Query, description = "JPEG quality"),
+        ("v" = i64, Query, description = "Version of the image's focal point and crops"),
+        ("s" = String, Query, description = "URL signature")
+    ),
+    responses(
+        (status = 200, description = "Transformed image", content_type = "image/*"),
+        (status = 400, description = "Media file is not an image", body = ProblemDetails),
+        (status = 403, description = "Missing or invalid signature", body = ProblemDetails),
+        (status = 404, description = "Media or crop not found", body = ProblemDetails),
+        (status = 422, description = "Invalid options", body = ProblemDetails)
+    )
+)]
+#[get("/media/<id>/transform?<crop>&<w>&<h>&<fit>&<format>&<q>&<v>&<s>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn transform_media(
+    state: &State<AppState>,
+    id: Uuid,
+    crop: Option<&str>,
+    w: Option<u32>,
+    h: Option<u32>,
+    fit: Option<&str>,
+    format: Option<&str>,
+    q: Option<u8>,
+    v: Option<i64>,
+    s: Option<&str>,
+) -> Result<TransformedImageResponse, ApiError> {
+    let spec = image_transform_service::parse_spec(crop, w, h, fit, format, q)?;
+    image_transform_service::verify(
+        &state.settings,
+        id,
+        v.unwrap_or_default(),
+        &spec,
+        s.unwrap_or_default(),
+    )?;
+
+    let media = MediaFile::find_by_id(&state.db, id).await?;
+    let image =
+        image_transform_service::render(&state.db, state.storage.as_ref(), &media, &spec).await?;
+
+    Ok(TransformedImageResponse {
+        bytes: image.bytes,
+        content_type: image.content_type,
+    })
+}
+
 /// Collect media routes
 pub fn routes() -> Vec<Route> {
     routes![
@@ -684,11 +741,14 @@ pub fn routes() -> Vec<Route> {
         create_media,
         upload_media,
         update_media,
+        update_media_art_direction,
         delete_media,
         list_media_metadata,
         create_media_metadata,
         update_media_metadata,
-        delete_media_metadata
+        delete_media_metadata,
+        get_media_transform_url,
+        transform_media
     ]
 }
 
@@ -699,14 +759,6 @@ mod tests {
     #[test]
     fn test_routes_count() {
         let routes = routes();
-        assert_eq!(routes.len(), 10, "Should have 10 media routes");
-    }
-
-    #[test]
-    fn test_sanitize_filename() {
-        assert_eq!(sanitize_filename("hello world.jpg"), "hello-world.jpg");
-        assert_eq!(sanitize_filename("file (1).png"), "file-1-.png");
-        assert_eq!(sanitize_filename("safe-name_v2.webp"), "safe-name_v2.webp");
-        assert_eq!(sanitize_filename(""), "upload");
+        assert_eq!(routes.len(), 13, "Should have 13 media routes");
     }
 }
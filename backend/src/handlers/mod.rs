@@ -23,26 +23,43 @@ pub mod system;
 // Domain handlers
 pub mod audit;
 pub mod blog;
+pub mod comment;
+pub mod content_block;
+pub mod content_site;
 pub mod content_template;
+pub mod content_version;
 pub mod cv;
 pub mod document;
+pub mod edit_lock;
 pub mod environment;
+pub mod form;
+pub mod global_section;
 pub mod legal;
 pub mod locale;
+pub mod markdown_transfer;
 pub mod media;
 pub mod media_folder;
 pub mod navigation;
 pub mod navigation_menu;
 pub mod notification;
 pub mod page;
+pub mod page_template;
+pub mod preview_token;
+pub mod promotion;
 pub mod redirect;
+pub mod release;
+pub mod search;
 pub mod site;
 pub mod site_locale;
 pub mod site_membership;
 pub mod site_settings;
+pub mod site_transfer;
+pub mod sitemap;
 pub mod social;
 pub mod taxonomy;
+pub mod trash;
 pub mod webhook;
+pub mod wordpress_import;
 
 use rocket::Route;
 
@@ -59,14 +76,20 @@ pub fn routes() -> Vec<Route> {
     // Site management
     routes.extend(site::routes());
     routes.extend(site_settings::routes());
+    routes.extend(site_transfer::routes());
+    routes.extend(wordpress_import::routes());
+    routes.extend(markdown_transfer::routes());
 
     // Infrastructure
     routes.extend(environment::routes());
+    routes.extend(promotion::routes());
+    routes.extend(release::routes());
     routes.extend(locale::routes());
 
     // Media
     routes.extend(media::routes());
     routes.extend(media_folder::routes());
+    routes.extend(trash::routes());
 
     // Content
     routes.extend(blog::routes());
@@ -75,6 +98,36 @@ pub fn routes() -> Vec<Route> {
     routes.extend(cv::routes());
     routes.extend(legal::routes());
 
+    // Content blocks
+    routes.extend(content_block::routes());
+
+    // Content version history
+    routes.extend(content_version::routes());
+
+    // Draft previews
+    routes.extend(preview_token::routes());
+
+    // Edit locks
+    routes.extend(edit_lock::routes());
+
+    // Cross-site syndication
+    routes.extend(content_site::routes());
+
+    // Comments
+    routes.extend(comment::routes());
+
+    // Forms
+    routes.extend(form::routes());
+
+    // Global sections
+    routes.extend(global_section::routes());
+
+    // Search
+    routes.extend(search::routes());
+
+    // Sitemap
+    routes.extend(sitemap::routes());
+
     // Navigation & Social
     routes.extend(navigation::routes());
     routes.extend(navigation_menu::routes());
@@ -92,6 +145,9 @@ pub fn routes() -> Vec<Route> {
     // Content Templates
     routes.extend(content_template::routes());
 
+    // Page Templates
+    routes.extend(page_template::routes());
+
     // Notifications
     routes.extend(notification::routes());
 
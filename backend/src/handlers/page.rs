@@ -8,22 +8,32 @@ use validator::Validate;
 
 use crate::dto::bulk::{BulkAction, BulkContentRequest, BulkContentResponse};
 use crate::dto::page::{
-    CreatePageRequest, CreatePageSectionRequest, PageListItem, PageResponse, PageSectionResponse,
-    PaginatedPages, SectionLocalizationResponse, UpdatePageRequest, UpdatePageSectionRequest,
-    UpsertSectionLocalizationRequest,
+    AttachGlobalSectionRequest, BreadcrumbItem, CreatePageRequest, CreatePageSectionRequest,
+    MovePageRequest, MovePageResponse, PageListItem, PageResponse, PageSectionResponse,
+    PageTreeNode, PaginatedPages, SectionLocalizationResponse, UpdatePageRequest,
+    UpdatePageSectionRequest, UpsertSectionLocalizationRequest,
 };
+use crate::dto::redirect::validate_destination_path;
 use crate::dto::review::{ReviewAction, ReviewActionRequest, ReviewActionResponse};
 use crate::errors::{ApiError, ProblemDetails};
 use crate::guards::auth_guard::ReadKey;
+use crate::guards::precondition_guard::IfMatch;
 use crate::models::audit::AuditAction;
 use crate::models::content::{Content, ContentStatus};
+use crate::models::environment::{Environment, EnvironmentType};
+use crate::models::global_section::GlobalSection;
 use crate::models::page::{Page, PageSection, PageSectionLocalization};
 use crate::models::site_membership::SiteRole;
 use crate::services::{
-    audit_service, bulk_content_service::BulkContentService, content_service::ContentService,
-    notification_service, webhook_service, workflow_service,
+    audit_service,
+    bulk_content_service::BulkContentService,
+    content_service::ContentService,
+    notification_service, page_tree_service,
+    preview_service::{self, PreviewTarget},
+    redirect_service, version_service, webhook_service, workflow_service,
 };
 use crate::utils::pagination::PaginationParams;
+use crate::utils::response::WithETag;
 use crate::AppState;
 
 /// List all pages for a site (paginated)
@@ -33,20 +43,23 @@ use crate::AppState;
     description = "List all pages for a site (paginated)",
     params(
         ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("environment" = Option<String>, Query, description = "Environment to list: development, staging or production (default: the live environment)"),
         ("page" = Option<i64>, Query, description = "Page number (default 1)"),
         ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
     ),
     responses(
         (status = 200, description = "Paginated page list", body = PaginatedPages),
+        (status = 422, description = "Invalid environment", body = ProblemDetails),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[get("/sites/<site_id>/pages?<page>&<per_page>")]
+#[get("/sites/<site_id>/pages?<environment>&<page>&<per_page>")]
 pub async fn list_pages(
     state: &State<AppState>,
     site_id: Uuid,
+    environment: Option<String>,
     page: Option<i64>,
     per_page: Option<i64>,
     auth: ReadKey,
@@ -54,11 +67,16 @@ pub async fn list_pages(
     auth.0
         .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
         .await?;
+    let environment = environment
+        .as_deref()
+        .map(EnvironmentType::parse)
+        .transpose()?;
+    let environment_id = Environment::resolve_id(&state.db, environment).await?;
     let params = PaginationParams::new(page, per_page);
     let (limit, offset) = params.limit_offset();
 
-    let pages = Page::find_all_for_site(&state.db, site_id, limit, offset).await?;
-    let total = Page::count_for_site(&state.db, site_id).await?;
+    let pages = Page::find_all_for_site(&state.db, site_id, environment_id, limit, offset).await?;
+    let total = Page::count_for_site(&state.db, site_id, environment_id).await?;
 
     let items: Vec<PageListItem> = pages.into_iter().map(PageListItem::from).collect();
     let paginated = params.paginate(items, total);
@@ -73,7 +91,8 @@ pub async fn list_pages(
     description = "Get a page by ID",
     params(("id" = Uuid, Path, description = "Page UUID")),
     responses(
-        (status = 200, description = "Page details", body = PageResponse),
+        (status = 200, description = "Page details", body = PageResponse,
+            headers(("ETag" = String, description = "Entity tag to send back as If-Match when updating"))),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 404, description = "Page not found", body = ProblemDetails)
     ),
@@ -84,7 +103,7 @@ pub async fn get_page(
     state: &State<AppState>,
     id: Uuid,
     auth: ReadKey,
-) -> Result<Json<PageResponse>, ApiError> {
+) -> Result<WithETag<Json<PageResponse>>, ApiError> {
     let page = Page::find_by_id(&state.db, id).await?;
     let site_ids = Content::find_site_ids(&state.db, page.content_id).await?;
     for site_id in &site_ids {
@@ -92,31 +111,36 @@ pub async fn get_page(
             .authorize_site_action(&state.db, *site_id, &SiteRole::Viewer)
             .await?;
     }
-    Ok(Json(PageResponse::from(page)))
+    let etag = ContentService::etag(&state.db, page.content_id).await?;
+    Ok(WithETag::new(Json(PageResponse::from(page)), etag))
 }
 
 /// Get page by route within a site
 #[utoipa::path(
     tag = "Pages",
     operation_id = "get_page_by_route",
-    description = "Get a page by its route within a site",
+    description = "Get a page by its route within a site. Read-only keys only see published pages unless a valid preview token is passed.",
     params(
         ("site_id" = Uuid, Path, description = "Site UUID"),
-        ("route" = String, Path, description = "Page route")
+        ("route" = String, Path, description = "Page route"),
+        ("preview_token" = Option<String>, Query, description = "Preview token granting access to an unpublished page"),
+        ("locale" = Option<String>, Query, description = "Locale code; required when the preview token is locale-scoped")
     ),
     responses(
         (status = 200, description = "Page details", body = PageResponse),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
-        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 403, description = "Forbidden or invalid preview token", body = ProblemDetails),
         (status = 404, description = "Page not found", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[get("/sites/<site_id>/pages/by-route/<route..>")]
+#[get("/sites/<site_id>/pages/by-route/<route..>?<preview_token>&<locale>")]
 pub async fn get_page_by_route(
     state: &State<AppState>,
     site_id: Uuid,
     route: std::path::PathBuf,
+    preview_token: Option<&str>,
+    locale: Option<&str>,
     auth: ReadKey,
 ) -> Result<Json<PageResponse>, ApiError> {
     let route_str = route.to_string_lossy();
@@ -126,9 +150,105 @@ pub async fn get_page_by_route(
     // Normalize: routes are stored with leading slash in DB
     let normalized = format!("/{}", route_str);
     let page = Page::find_by_route(&state.db, site_id, &normalized).await?;
+    let target = PreviewTarget {
+        entity_type: "page",
+        entity_id: page.id,
+        content_id: page.content_id,
+        status: &page.status,
+    };
+    if !preview_service::authorize_view(state, &auth.0, site_id, target, preview_token, locale)
+        .await?
+    {
+        return Err(ApiError::NotFound(format!(
+            "Page with route '{}' not found",
+            normalized
+        )));
+    }
     Ok(Json(PageResponse::from(page)))
 }
 
+/// Get the page hierarchy of a site
+#[utoipa::path(
+    tag = "Pages",
+    operation_id = "get_page_tree",
+    description = "Get all pages of a site nested by parent page, in navigation order. Titles are resolved for `locale`, falling back to the site's default language.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("locale" = Option<String>, Query, description = "Locale code for page titles")
+    ),
+    responses(
+        (status = 200, description = "Top-level pages with nested children", body = Vec<PageTreeNode>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/pages/tree?<locale>")]
+pub async fn get_page_tree(
+    state: &State<AppState>,
+    site_id: Uuid,
+    locale: Option<&str>,
+    auth: ReadKey,
+) -> Result<Json<Vec<PageTreeNode>>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let rows = Page::find_tree_for_site(&state.db, site_id, locale).await?;
+    Ok(Json(page_tree_service::build_tree(rows)))
+}
+
+/// Get the breadcrumb trail of a page
+#[utoipa::path(
+    tag = "Pages",
+    operation_id = "get_page_breadcrumbs",
+    description = "Get the breadcrumb trail for a route, from the top-level ancestor down to the page itself, with titles in `locale`. Read-only keys only see published pages unless a valid preview token is passed.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("route" = String, Path, description = "Page route"),
+        ("preview_token" = Option<String>, Query, description = "Preview token granting access to an unpublished page"),
+        ("locale" = Option<String>, Query, description = "Locale code for page titles")
+    ),
+    responses(
+        (status = 200, description = "Breadcrumb trail", body = Vec<BreadcrumbItem>),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden or invalid preview token", body = ProblemDetails),
+        (status = 404, description = "Page not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/pages/breadcrumbs/<route..>?<preview_token>&<locale>")]
+pub async fn get_page_breadcrumbs(
+    state: &State<AppState>,
+    site_id: Uuid,
+    route: std::path::PathBuf,
+    preview_token: Option<&str>,
+    locale: Option<&str>,
+    auth: ReadKey,
+) -> Result<Json<Vec<BreadcrumbItem>>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let normalized = format!("/{}", route.to_string_lossy());
+    let page = Page::find_by_route(&state.db, site_id, &normalized).await?;
+    let target = PreviewTarget {
+        entity_type: "page",
+        entity_id: page.id,
+        content_id: page.content_id,
+        status: &page.status,
+    };
+    if !preview_service::authorize_view(state, &auth.0, site_id, target, preview_token, locale)
+        .await?
+    {
+        return Err(ApiError::NotFound(format!(
+            "Page with route '{}' not found",
+            normalized
+        )));
+    }
+
+    let rows = Page::find_tree_for_site(&state.db, site_id, locale).await?;
+    Ok(Json(page_tree_service::breadcrumbs(&rows, page.id)))
+}
+
 /// Get sections for a page
 #[utoipa::path(
     tag = "Pages",
@@ -210,7 +330,11 @@ pub async fn create_page(
         .await?;
     }
 
-    let page = Page::create(&state.db, req).await?;
+    let mut tx = state.db.begin().await?;
+    let page = Page::create(&mut tx, req).await?;
+    version_service::record_version(&mut tx, page.content_id, "Created page", Some(auth.0.id))
+        .await?;
+    tx.commit().await?;
     let site_id = Content::find_site_ids(&state.db, page.content_id)
         .await?
         .into_iter()
@@ -241,14 +365,19 @@ pub async fn create_page(
 #[utoipa::path(
     tag = "Pages",
     operation_id = "update_page",
-    description = "Update a page",
-    params(("id" = Uuid, Path, description = "Page UUID")),
+    description = "Update a page. Send the `ETag` from a previous read as `If-Match` to fail with 412 instead of overwriting someone else's changes.",
+    params(
+        ("id" = Uuid, Path, description = "Page UUID"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the page still has this entity tag")
+    ),
     request_body(content = UpdatePageRequest, description = "Page update data"),
     responses(
-        (status = 200, description = "Page updated", body = PageResponse),
+        (status = 200, description = "Page updated", body = PageResponse,
+            headers(("ETag" = String, description = "Entity tag of the updated page"))),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails),
-        (status = 404, description = "Page not found", body = ProblemDetails)
+        (status = 404, description = "Page not found", body = ProblemDetails),
+        (status = 412, description = "Page was modified since the given entity tag", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
@@ -257,8 +386,9 @@ pub async fn update_page(
     state: &State<AppState>,
     id: Uuid,
     body: Json<UpdatePageRequest>,
+    if_match: IfMatch,
     auth: ReadKey,
-) -> Result<Json<PageResponse>, ApiError> {
+) -> Result<WithETag<Json<PageResponse>>, ApiError> {
     let existing = Page::find_by_id(&state.db, id).await?;
     let site_ids = Content::find_site_ids(&state.db, existing.content_id).await?;
     for site_id in &site_ids {
@@ -271,6 +401,9 @@ pub async fn update_page(
     let req = body.into_inner();
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    if let Some(parent_id) = req.parent_page_id {
+        page_tree_service::check_parent(&state.db, id, &site_ids, parent_id).await?;
+    }
 
     // Validate status transition against editorial workflow rules
     if let Some(ref requested_status) = req.status {
@@ -291,7 +424,39 @@ pub async fn update_page(
         }
     }
 
-    let page = Page::update(&state.db, id, req).await?;
+    let redirect_to = req.redirect_to.clone();
+    let mut tx = state.db.begin().await?;
+    ContentService::check_if_match(&mut tx, existing.content_id, &if_match).await?;
+    let page = Page::update(&mut tx, id, req).await?;
+    version_service::record_version(&mut tx, page.content_id, "Updated page", Some(auth.0.id))
+        .await?;
+    Content::touch(&mut *tx, page.content_id).await?;
+    tx.commit().await?;
+    let etag = ContentService::etag(&state.db, page.content_id).await?;
+    if page.status == ContentStatus::Archived && existing.status != ContentStatus::Archived {
+        if let Some(target) = redirect_to.as_deref() {
+            redirect_service::redirect_removed(
+                &state.db,
+                &site_ids,
+                page.content_id,
+                &existing.route,
+                target,
+                "Page archived",
+            )
+            .await;
+        }
+    } else if existing.status == ContentStatus::Published && page.status == ContentStatus::Published
+    {
+        redirect_service::redirect_moved(
+            &state.db,
+            &site_ids,
+            page.content_id,
+            &existing.route,
+            &page.route,
+            "Page route changed",
+        )
+        .await;
+    }
     let site_id = site_ids.into_iter().next();
     audit_service::log_action(
         &state.db,
@@ -328,27 +493,32 @@ pub async fn update_page(
             );
         }
     }
-    Ok(Json(PageResponse::from(page)))
+    Ok(WithETag::new(Json(PageResponse::from(page)), etag))
 }
 
 /// Delete a page (soft delete)
 #[utoipa::path(
     tag = "Pages",
     operation_id = "delete_page",
-    description = "Soft delete a page",
-    params(("id" = Uuid, Path, description = "Page UUID")),
+    description = "Soft delete a page. Pass `redirect_to` to redirect the page's route to another path or absolute URL.",
+    params(
+        ("id" = Uuid, Path, description = "Page UUID"),
+        ("redirect_to" = Option<String>, Query, description = "Redirect the deleted page's route here (301)")
+    ),
     responses(
         (status = 204, description = "Page deleted"),
+        (status = 400, description = "Invalid redirect target", body = ProblemDetails),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails),
         (status = 404, description = "Page not found", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
-#[delete("/pages/<id>")]
+#[delete("/pages/<id>?<redirect_to>")]
 pub async fn delete_page(
     state: &State<AppState>,
     id: Uuid,
+    redirect_to: Option<String>,
     auth: ReadKey,
 ) -> Result<Status, ApiError> {
     let page = Page::find_by_id(&state.db, id).await?;
@@ -358,8 +528,23 @@ pub async fn delete_page(
             .authorize_site_action(&state.db, *site_id, &SiteRole::Editor)
             .await?;
     }
+    if let Some(target) = redirect_to.as_deref() {
+        validate_destination_path(target)
+            .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    }
 
     Page::soft_delete(&state.db, id).await?;
+    if let Some(target) = redirect_to.as_deref() {
+        redirect_service::redirect_removed(
+            &state.db,
+            &site_ids,
+            page.content_id,
+            &page.route,
+            target,
+            "Page deleted",
+        )
+        .await;
+    }
     let site_id = site_ids.into_iter().next();
     audit_service::log_action(
         &state.db,
@@ -411,7 +596,11 @@ pub async fn clone_page(
             .await?;
     }
 
-    let page = Page::clone_page(&state.db, id, site_ids.clone()).await?;
+    let mut tx = state.db.begin().await?;
+    let page = Page::clone_page(&mut tx, id, site_ids.clone()).await?;
+    version_service::record_version(&mut tx, page.content_id, "Cloned page", Some(auth.0.id))
+        .await?;
+    tx.commit().await?;
     let site_id = site_ids.into_iter().next();
     let metadata = serde_json::json!({ "cloned_from": id.to_string() });
     audit_service::log_action(
@@ -436,6 +625,80 @@ pub async fn clone_page(
     Ok((Status::Created, Json(PageResponse::from(page))))
 }
 
+/// Move a page to another parent
+#[utoipa::path(
+    tag = "Pages",
+    operation_id = "move_page",
+    description = "Move a page below another page or to the top level. Moving a page below itself or one of its descendants is rejected. With `rewrite_routes`, the page's route is rebased onto the new parent's route, descendants' routes get the new prefix, and old routes of published pages redirect to the new ones.",
+    params(("id" = Uuid, Path, description = "Page UUID")),
+    request_body(content = MovePageRequest, description = "New parent"),
+    responses(
+        (status = 200, description = "Page moved", body = MovePageResponse),
+        (status = 400, description = "Invalid parent or the move would create a cycle", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Page not found", body = ProblemDetails),
+        (status = 409, description = "A rewritten route is already in use", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/pages/<id>/move", data = "<body>")]
+pub async fn move_page(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<MovePageRequest>,
+    auth: ReadKey,
+) -> Result<Json<MovePageResponse>, ApiError> {
+    let existing = Page::find_by_id(&state.db, id).await?;
+    let site_ids = Content::find_site_ids(&state.db, existing.content_id).await?;
+    for site_id in &site_ids {
+        auth.0
+            .authorize_site_action(&state.db, *site_id, &SiteRole::Editor)
+            .await?;
+    }
+    let req = body.into_inner();
+
+    let (page, route_changes) = page_tree_service::move_page(
+        &state.db,
+        &existing,
+        &site_ids,
+        req.parent_page_id,
+        req.rewrite_routes,
+        Some(auth.0.id),
+    )
+    .await?;
+
+    let site_id = site_ids.into_iter().next();
+    audit_service::log_action(
+        &state.db,
+        site_id,
+        Some(auth.0.id),
+        AuditAction::Update,
+        "page",
+        id,
+        Some(serde_json::json!({
+            "from_parent_page_id": existing.parent_page_id,
+            "to_parent_page_id": page.parent_page_id,
+            "route_changes": route_changes.len(),
+        })),
+    )
+    .await;
+    if let Some(sid) = site_id {
+        webhook_service::dispatch(
+            state.db.clone(),
+            sid,
+            "page.updated",
+            id,
+            serde_json::to_value(PageResponse::from(page.clone())).unwrap_or_default(),
+        );
+    }
+
+    Ok(Json(MovePageResponse {
+        page: PageResponse::from(page),
+        route_changes,
+    }))
+}
+
 /// Create a page section
 #[utoipa::path(
     tag = "Pages",
@@ -470,7 +733,16 @@ pub async fn create_page_section(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    let section = PageSection::create(&state.db, page_id, req).await?;
+    let mut tx = state.db.begin().await?;
+    let section = PageSection::create(&mut *tx, page_id, req).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Added page section",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
     Ok((Status::Created, Json(PageSectionResponse::from(section))))
 }
 
@@ -485,7 +757,8 @@ pub async fn create_page_section(
         (status = 200, description = "Section updated", body = PageSectionResponse),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
         (status = 403, description = "Forbidden", body = ProblemDetails),
-        (status = 404, description = "Section not found", body = ProblemDetails)
+        (status = 404, description = "Section not found", body = ProblemDetails),
+        (status = 409, description = "Shared fields of a global section reference", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
@@ -508,8 +781,28 @@ pub async fn update_page_section(
     let req = body.into_inner();
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    if existing_section.global_section_id.is_some()
+        && (req.section_type.is_some()
+            || req.cover_image_id.is_some()
+            || req.call_to_action_route.is_some()
+            || req.settings.is_some())
+    {
+        return Err(ApiError::Conflict(
+            "Section references a global section; edit the global section or detach it first"
+                .to_string(),
+        ));
+    }
 
-    let section = PageSection::update(&state.db, id, req).await?;
+    let mut tx = state.db.begin().await?;
+    let section = PageSection::update(&mut tx, id, req).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Updated page section",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
     Ok(Json(PageSectionResponse::from(section)))
 }
 
@@ -542,10 +835,122 @@ pub async fn delete_page_section(
             .await?;
     }
 
-    PageSection::delete(&state.db, id).await?;
+    let mut tx = state.db.begin().await?;
+    PageSection::delete(&mut *tx, id).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Deleted page section",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
     Ok(Status::NoContent)
 }
 
+/// Place a global section on a page
+#[utoipa::path(
+    tag = "Pages",
+    operation_id = "attach_global_section",
+    description = "Add a section to a page that references a global section of one of the page's sites. Content stays shared until the section is detached.",
+    params(("page_id" = Uuid, Path, description = "Page UUID")),
+    request_body(content = AttachGlobalSectionRequest, description = "Global section and position"),
+    responses(
+        (status = 201, description = "Section created", body = PageSectionResponse),
+        (status = 400, description = "Validation error or global section of another site", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Page or global section not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/pages/<page_id>/global-sections", data = "<body>")]
+pub async fn attach_global_section(
+    state: &State<AppState>,
+    page_id: Uuid,
+    body: Json<AttachGlobalSectionRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<PageSectionResponse>), ApiError> {
+    let page = Page::find_by_id(&state.db, page_id).await?;
+    let site_ids = Content::find_site_ids(&state.db, page.content_id).await?;
+    for site_id in &site_ids {
+        auth.0
+            .authorize_site_action(&state.db, *site_id, &SiteRole::Author)
+            .await?;
+    }
+
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    let global = GlobalSection::find_by_id(&state.db, req.global_section_id).await?;
+    if !site_ids.contains(&global.site_id) {
+        return Err(ApiError::BadRequest(
+            "Global section belongs to a site the page is not part of".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+    let section =
+        PageSection::create_reference(&mut tx, page_id, global.id, req.display_order).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Added global section",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
+    Ok((Status::Created, Json(PageSectionResponse::from(section))))
+}
+
+/// Detach a page section from its global section
+#[utoipa::path(
+    tag = "Pages",
+    operation_id = "detach_page_section",
+    description = "Turn a global section reference into a local copy of its current fields and localizations. Later edits to the global section no longer apply.",
+    params(("id" = Uuid, Path, description = "Section UUID")),
+    responses(
+        (status = 200, description = "Section detached", body = PageSectionResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Section not found", body = ProblemDetails),
+        (status = 409, description = "Section does not reference a global section", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/pages/sections/<id>/detach")]
+pub async fn detach_page_section(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<PageSectionResponse>, ApiError> {
+    let existing_section = PageSection::find_by_id(&state.db, id).await?;
+    let page = Page::find_by_id(&state.db, existing_section.page_id).await?;
+    let site_ids = Content::find_site_ids(&state.db, page.content_id).await?;
+    for site_id in &site_ids {
+        auth.0
+            .authorize_site_action(&state.db, *site_id, &SiteRole::Author)
+            .await?;
+    }
+
+    let global_section_id = existing_section.global_section_id.ok_or_else(|| {
+        ApiError::Conflict("Section does not reference a global section".to_string())
+    })?;
+
+    let mut tx = state.db.begin().await?;
+    let section = PageSection::detach(&mut tx, id, global_section_id).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Detached global section",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(Json(PageSectionResponse::from(section)))
+}
+
 /// Get localizations for a section
 #[utoipa::path(
     tag = "Pages",
@@ -626,7 +1031,8 @@ pub async fn get_page_section_localizations(
         (status = 200, description = "Localization upserted", body = SectionLocalizationResponse),
         (status = 400, description = "Validation error", body = ProblemDetails),
         (status = 401, description = "Unauthorized", body = ProblemDetails),
-        (status = 403, description = "Forbidden", body = ProblemDetails)
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 409, description = "Section references a global section", body = ProblemDetails)
     ),
     security(("api_key" = []))
 )]
@@ -646,12 +1052,20 @@ pub async fn upsert_section_localization(
             .await?;
     }
 
+    if section.global_section_id.is_some() {
+        return Err(ApiError::Conflict(
+            "Section references a global section; localize the global section or detach it first"
+                .to_string(),
+        ));
+    }
+
     let req = body.into_inner();
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
+    let mut tx = state.db.begin().await?;
     let localization = PageSectionLocalization::upsert(
-        &state.db,
+        &mut *tx,
         section_id,
         req.locale_id,
         req.title.as_deref(),
@@ -659,6 +1073,14 @@ pub async fn upsert_section_localization(
         req.button_text.as_deref(),
     )
     .await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Updated section localization",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
 
     Ok(Json(SectionLocalizationResponse::from(localization)))
 }
@@ -693,7 +1115,16 @@ pub async fn delete_section_localization(
             .await?;
     }
 
-    PageSectionLocalization::delete(&state.db, id).await?;
+    let mut tx = state.db.begin().await?;
+    PageSectionLocalization::delete(&mut *tx, id).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Deleted section localization",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
     Ok(Status::NoContent)
 }
 
@@ -975,15 +1406,20 @@ pub fn routes() -> Vec<Route> {
         list_pages,
         get_page,
         get_page_by_route,
+        get_page_tree,
+        get_page_breadcrumbs,
         get_page_sections,
         create_page,
         update_page,
         delete_page,
         clone_page,
+        move_page,
         review_page,
         create_page_section,
         update_page_section,
         delete_page_section,
+        attach_global_section,
+        detach_page_section,
         get_section_localizations,
         get_page_section_localizations,
         upsert_section_localization,
@@ -999,6 +1435,6 @@ mod tests {
     #[test]
     fn test_routes_count() {
         let routes = routes();
-        assert_eq!(routes.len(), 17, "Should have 17 page routes");
+        assert_eq!(routes.len(), 22, "Should have 22 page routes");
     }
 }
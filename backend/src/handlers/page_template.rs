@@ -0,0 +1,374 @@
+//! Page template handlers
+//!
+//! Templates are managed per site. Creating a page from a template inserts the
+//! page, its sections and their placeholder localizations in one transaction.
+
+use std::collections::HashMap;
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::page::PageResponse;
+use crate::dto::page_template::{
+    CreatePageFromTemplateRequest, CreatePageTemplateRequest, PageTemplateResponse,
+    PageTemplateSection, PaginatedPageTemplates, UpdatePageTemplateRequest,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::content::ContentStatus;
+use crate::models::page::Page;
+use crate::models::page_template::PageTemplate;
+use crate::models::site_locale::SiteLocale;
+use crate::models::site_membership::SiteRole;
+use crate::services::{
+    audit_service, page_template_service, version_service, webhook_service, workflow_service,
+};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// Load a page template and check the caller holds `role` on its site
+async fn authorize_template(
+    state: &State<AppState>,
+    auth: &ReadKey,
+    id: Uuid,
+    role: &SiteRole,
+) -> Result<PageTemplate, ApiError> {
+    let template = PageTemplate::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, template.site_id, role)
+        .await?;
+    Ok(template)
+}
+
+/// Check a section layout against the site's languages
+async fn check_layout(
+    state: &State<AppState>,
+    site_id: Uuid,
+    sections: &[PageTemplateSection],
+) -> Result<(), ApiError> {
+    let locale_codes: Vec<String> = SiteLocale::find_all_for_site(&state.db, site_id)
+        .await?
+        .into_iter()
+        .map(|l| l.code)
+        .collect();
+    page_template_service::validate_layout(sections, &locale_codes)
+}
+
+/// List page templates for a site
+#[utoipa::path(
+    tag = "Page Templates",
+    operation_id = "list_page_templates",
+    description = "List all page templates for a site (paginated)",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated page template list", body = PaginatedPageTemplates),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/page-templates?<page>&<per_page>")]
+pub async fn list_page_templates(
+    state: &State<AppState>,
+    site_id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedPageTemplates>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let templates = PageTemplate::find_all_for_site(&state.db, site_id, limit, offset).await?;
+    let total = PageTemplate::count_for_site(&state.db, site_id).await?;
+
+    let items: Vec<PageTemplateResponse> = templates
+        .into_iter()
+        .map(PageTemplateResponse::from)
+        .collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Get a page template by ID
+#[utoipa::path(
+    tag = "Page Templates",
+    operation_id = "get_page_template",
+    description = "Get a page template with its section layout",
+    params(("id" = Uuid, Path, description = "Page template UUID")),
+    responses(
+        (status = 200, description = "Page template details", body = PageTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Page template not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/page-templates/<id>")]
+pub async fn get_page_template(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<PageTemplateResponse>, ApiError> {
+    let template = authorize_template(state, &auth, id, &SiteRole::Viewer).await?;
+    Ok(Json(PageTemplateResponse::from(template)))
+}
+
+/// Create a page template
+#[utoipa::path(
+    tag = "Page Templates",
+    operation_id = "create_page_template",
+    description = "Create a page template for a site",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = CreatePageTemplateRequest, description = "Page template data"),
+    responses(
+        (status = 201, description = "Page template created", body = PageTemplateResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 409, description = "Duplicate template name", body = ProblemDetails),
+        (status = 422, description = "Placeholder text in a language the site does not use", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/page-templates", data = "<body>")]
+pub async fn create_page_template(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<CreatePageTemplateRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<PageTemplateResponse>), ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Author)
+        .await?;
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    check_layout(state, site_id, &req.sections).await?;
+
+    let template = PageTemplate::create(&state.db, site_id, &req, Some(auth.0.id)).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "page_template",
+        template.id,
+        None,
+    )
+    .await;
+
+    Ok((Status::Created, Json(PageTemplateResponse::from(template))))
+}
+
+/// Update a page template
+#[utoipa::path(
+    tag = "Page Templates",
+    operation_id = "update_page_template",
+    description = "Update a page template. `sections` replaces the whole layout; pages created earlier are not changed.",
+    params(("id" = Uuid, Path, description = "Page template UUID")),
+    request_body(content = UpdatePageTemplateRequest, description = "Page template update data"),
+    responses(
+        (status = 200, description = "Page template updated", body = PageTemplateResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Page template not found", body = ProblemDetails),
+        (status = 409, description = "Duplicate template name", body = ProblemDetails),
+        (status = 422, description = "Placeholder text in a language the site does not use", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/page-templates/<id>", data = "<body>")]
+pub async fn update_page_template(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpdatePageTemplateRequest>,
+    auth: ReadKey,
+) -> Result<Json<PageTemplateResponse>, ApiError> {
+    let existing = authorize_template(state, &auth, id, &SiteRole::Author).await?;
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    if let Some(sections) = &req.sections {
+        check_layout(state, existing.site_id, sections).await?;
+    }
+
+    let template = PageTemplate::update(&state.db, id, &req).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(existing.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "page_template",
+        id,
+        None,
+    )
+    .await;
+
+    Ok(Json(PageTemplateResponse::from(template)))
+}
+
+/// Delete a page template
+#[utoipa::path(
+    tag = "Page Templates",
+    operation_id = "delete_page_template",
+    description = "Delete a page template. Pages created from it are kept.",
+    params(("id" = Uuid, Path, description = "Page template UUID")),
+    responses(
+        (status = 204, description = "Page template deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Page template not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/page-templates/<id>")]
+pub async fn delete_page_template(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let template = authorize_template(state, &auth, id, &SiteRole::Editor).await?;
+
+    PageTemplate::delete(&state.db, id).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(template.site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "page_template",
+        id,
+        None,
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Create a page from a template
+#[utoipa::path(
+    tag = "Page Templates",
+    operation_id = "create_page_from_template",
+    description = "Create a page with the template's page type, template name and sections. Placeholder text becomes the sections' localizations. Everything is created in one transaction.",
+    params(("id" = Uuid, Path, description = "Page template UUID")),
+    request_body(content = CreatePageFromTemplateRequest, description = "Page data"),
+    responses(
+        (status = 201, description = "Page created", body = PageResponse),
+        (status = 400, description = "Validation error or inactive template", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Page template not found", body = ProblemDetails),
+        (status = 409, description = "Route or slug already in use", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/page-templates/<id>/pages", data = "<body>")]
+pub async fn create_page_from_template(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<CreatePageFromTemplateRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<PageResponse>), ApiError> {
+    let template = authorize_template(state, &auth, id, &SiteRole::Author).await?;
+    if !template.is_active {
+        return Err(ApiError::BadRequest(format!(
+            "Page template '{}' is inactive",
+            template.name
+        )));
+    }
+
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    let req = page_template_service::page_request(&template, req)?;
+    for site_id in &req.site_ids {
+        auth.0
+            .authorize_site_action(&state.db, *site_id, &SiteRole::Author)
+            .await?;
+    }
+
+    // Validate initial status against editorial workflow rules
+    let role = auth
+        .0
+        .effective_site_role(&state.db, template.site_id)
+        .await?
+        .unwrap_or(SiteRole::Viewer);
+    workflow_service::validate_status_transition(
+        &state.db,
+        template.site_id,
+        &role,
+        &ContentStatus::Draft,
+        &req.status,
+    )
+    .await?;
+
+    let locale_ids: HashMap<String, Uuid> =
+        SiteLocale::find_all_for_site(&state.db, template.site_id)
+            .await?
+            .into_iter()
+            .map(|l| (l.code, l.locale_id))
+            .collect();
+    let mut tx = state.db.begin().await?;
+    let page =
+        Page::create_with_sections(&mut tx, req, &template.section_layout(), &locale_ids).await?;
+    version_service::record_version(
+        &mut tx,
+        page.content_id,
+        "Created page from template",
+        Some(auth.0.id),
+    )
+    .await?;
+    tx.commit().await?;
+    audit_service::log_action(
+        &state.db,
+        Some(template.site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "page",
+        page.id,
+        Some(serde_json::json!({ "page_template_id": id.to_string() })),
+    )
+    .await;
+    webhook_service::dispatch(
+        state.db.clone(),
+        template.site_id,
+        "page.created",
+        page.id,
+        serde_json::to_value(PageResponse::from(page.clone())).unwrap_or_default(),
+    );
+
+    Ok((Status::Created, Json(PageResponse::from(page))))
+}
+
+/// Collect page template routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_page_templates,
+        get_page_template,
+        create_page_template,
+        update_page_template,
+        delete_page_template,
+        create_page_from_template
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 6, "Should have 6 page template routes");
+    }
+}
@@ -0,0 +1,196 @@
+//! Preview token handlers
+//!
+//! Mint, list and revoke tokens that let a frontend fetch a draft or scheduled
+//! blog or page through the public by-slug / by-route endpoints.
+
+use chrono::{Duration, Utc};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::preview_token::{
+    CreatePreviewTokenRequest, CreatePreviewTokenResponse, PaginatedPreviewTokens,
+    PreviewTokenResponse, DEFAULT_EXPIRES_IN_HOURS,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::locale::Locale;
+use crate::models::preview_token::PreviewToken;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, preview_service};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// Mint a preview token
+#[utoipa::path(
+    tag = "Preview Tokens",
+    operation_id = "create_preview_token",
+    description = "Mint a signed, expiring token that lets read-only keys fetch this content before it is published. The token string is only returned once.",
+    params(("content_id" = Uuid, Path, description = "Content UUID")),
+    request_body(content = CreatePreviewTokenRequest, description = "Token scope and lifetime"),
+    responses(
+        (status = 201, description = "Token minted", body = CreatePreviewTokenResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content or locale not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/content/<content_id>/preview-tokens", data = "<body>")]
+pub async fn create_preview_token(
+    state: &State<AppState>,
+    content_id: Uuid,
+    body: Json<CreatePreviewTokenRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<CreatePreviewTokenResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    let site_ids = auth
+        .0
+        .authorize_content(&state.db, content_id, &SiteRole::Author)
+        .await?;
+    if let Some(locale_id) = req.locale_id {
+        Locale::find_by_id(&state.db, locale_id).await?;
+    }
+
+    let hours = req.expires_in_hours.unwrap_or(DEFAULT_EXPIRES_IN_HOURS);
+    let expires_at = Utc::now() + Duration::hours(hours);
+    let token = PreviewToken::create(
+        &state.db,
+        content_id,
+        req.locale_id,
+        expires_at,
+        Some(auth.0.id),
+    )
+    .await?;
+
+    audit_service::log_action(
+        &state.db,
+        site_ids.into_iter().next(),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "preview_token",
+        token.id,
+        Some(serde_json::json!({
+            "content_id": content_id,
+            "locale_id": token.locale_id,
+            "expires_at": token.expires_at,
+        })),
+    )
+    .await;
+
+    let response = CreatePreviewTokenResponse {
+        token: preview_service::sign(&state.settings, &token),
+        preview: PreviewTokenResponse::from(token),
+    };
+    Ok((Status::Created, Json(response)))
+}
+
+/// List preview tokens of a content item (paginated)
+#[utoipa::path(
+    tag = "Preview Tokens",
+    operation_id = "list_preview_tokens",
+    description = "List preview tokens minted for a content item, newest first, including revoked and expired ones",
+    params(
+        ("content_id" = Uuid, Path, description = "Content UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated preview token list", body = PaginatedPreviewTokens),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Content not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/content/<content_id>/preview-tokens?<page>&<per_page>")]
+pub async fn list_preview_tokens(
+    state: &State<AppState>,
+    content_id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedPreviewTokens>, ApiError> {
+    auth.0
+        .authorize_content(&state.db, content_id, &SiteRole::Author)
+        .await?;
+
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let tokens = PreviewToken::find_for_content(&state.db, content_id, limit, offset).await?;
+    let total = PreviewToken::count_for_content(&state.db, content_id).await?;
+
+    let items: Vec<PreviewTokenResponse> =
+        tokens.into_iter().map(PreviewTokenResponse::from).collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Revoke a preview token
+#[utoipa::path(
+    tag = "Preview Tokens",
+    operation_id = "revoke_preview_token",
+    description = "Revoke a preview token; it stops working immediately",
+    params(("id" = Uuid, Path, description = "Preview token UUID")),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Token not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/preview-tokens/<id>")]
+pub async fn revoke_preview_token(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let token = PreviewToken::find_by_id(&state.db, id).await?;
+    let site_ids = auth
+        .0
+        .authorize_content(&state.db, token.content_id, &SiteRole::Author)
+        .await?;
+
+    PreviewToken::revoke(&state.db, id).await?;
+
+    audit_service::log_action(
+        &state.db,
+        site_ids.into_iter().next(),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "preview_token",
+        id,
+        Some(serde_json::json!({ "content_id": token.content_id })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Collect preview token routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        create_preview_token,
+        list_preview_tokens,
+        revoke_preview_token
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 3, "Should have 3 preview token routes");
+    }
+}
@@ -0,0 +1,220 @@
+//! Environment promotion handlers
+//!
+//! Preview the differences between two environments of a site and promote
+//! content from one to the other.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::promotion::{
+    PaginatedPromotions, PromotionChangeset, PromotionRequest, PromotionResponse,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::environment::Environment;
+use crate::models::environment_promotion::EnvironmentPromotion;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, promotion_service};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// Preview a promotion
+#[utoipa::path(
+    tag = "Promotions",
+    operation_id = "preview_promotion",
+    description = "Compute the content, localizations, media references, navigation and taxonomy that differ between two environments of a site. Nothing is changed.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = PromotionRequest, description = "Source and target environments"),
+    responses(
+        (status = 200, description = "Changeset", body = PromotionChangeset),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Environment not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/promotions/preview", data = "<body>")]
+pub async fn preview_promotion(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<PromotionRequest>,
+    auth: ReadKey,
+) -> Result<Json<PromotionChangeset>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let source = Environment::find_by_name(&state.db, req.source).await?;
+    let target = Environment::find_by_name(&state.db, req.target).await?;
+
+    let mut conn = state.db.acquire().await?;
+    let changeset = promotion_service::build_changeset(
+        &mut conn,
+        site_id,
+        &source,
+        &target,
+        req.content_ids.as_deref(),
+    )
+    .await?;
+    Ok(Json(changeset))
+}
+
+/// Apply a promotion
+#[utoipa::path(
+    tag = "Promotions",
+    operation_id = "apply_promotion",
+    description = "Promote content from one environment to another in a single transaction. Pass the checksum from the preview to make sure the reviewed changes are the ones applied.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = PromotionRequest, description = "Source and target environments"),
+    responses(
+        (status = 201, description = "Promotion applied", body = PromotionResponse),
+        (status = 400, description = "Validation error or nothing to promote", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Environment not found", body = ProblemDetails),
+        (status = 412, description = "Changeset changed since the preview", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/promotions", data = "<body>")]
+pub async fn apply_promotion(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<PromotionRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<PromotionResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Admin)
+        .await?;
+    let source = Environment::find_by_name(&state.db, req.source).await?;
+    let target = Environment::find_by_name(&state.db, req.target).await?;
+
+    let promotion = promotion_service::apply(
+        &state.db,
+        site_id,
+        &source,
+        &target,
+        req.content_ids.as_deref(),
+        req.checksum.as_deref(),
+        Some(auth.0.id),
+    )
+    .await?;
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Publish,
+        "environment_promotion",
+        promotion.id,
+        Some(serde_json::json!({
+            "source_environment_id": source.id,
+            "target_environment_id": target.id,
+            "created": promotion.created_count,
+            "updated": promotion.updated_count,
+            "deleted": promotion.deleted_count,
+        })),
+    )
+    .await;
+
+    Ok((Status::Created, Json(PromotionResponse::from(promotion))))
+}
+
+/// List the promotions of a site
+#[utoipa::path(
+    tag = "Promotions",
+    operation_id = "list_promotions",
+    description = "List applied promotions of a site, newest first (paginated)",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated promotion history", body = PaginatedPromotions),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/promotions?<page>&<per_page>")]
+pub async fn list_promotions(
+    state: &State<AppState>,
+    site_id: Uuid,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedPromotions>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let promotions =
+        EnvironmentPromotion::find_all_for_site(&state.db, site_id, limit, offset).await?;
+    let total = EnvironmentPromotion::count_for_site(&state.db, site_id).await?;
+
+    let items: Vec<PromotionResponse> = promotions
+        .into_iter()
+        .map(PromotionResponse::from)
+        .collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Get a promotion by ID
+#[utoipa::path(
+    tag = "Promotions",
+    operation_id = "get_promotion",
+    description = "Get an applied promotion with its changeset",
+    params(("id" = Uuid, Path, description = "Promotion UUID")),
+    responses(
+        (status = 200, description = "Promotion details", body = PromotionResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Promotion not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/promotions/<id>")]
+pub async fn get_promotion(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<PromotionResponse>, ApiError> {
+    let promotion = EnvironmentPromotion::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, promotion.site_id, &SiteRole::Viewer)
+        .await?;
+    Ok(Json(PromotionResponse::from(promotion)))
+}
+
+/// Collect promotion routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        preview_promotion,
+        apply_promotion,
+        list_promotions,
+        get_promotion
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 4, "Should have 4 promotion routes");
+    }
+}
@@ -1,24 +1,30 @@
 //! Redirect handlers
 
-use rocket::http::Status;
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::{ContentType, Status};
 use rocket::serde::json::Json;
 use rocket::{Route, State};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::dto::redirect::{
-    CreateRedirectRequest, PaginatedRedirects, RedirectLookupResponse, RedirectResponse,
-    UpdateRedirectRequest,
+    validate_redirect_rule, CreateRedirectRequest, PaginatedRedirects, RedirectImportReport,
+    RedirectLookupResponse, RedirectResponse, RedirectTransferRow, UpdateRedirectRequest,
 };
 use crate::errors::{ApiError, ProblemDetails};
 use crate::guards::auth_guard::ReadKey;
 use crate::models::audit::AuditAction;
 use crate::models::redirect::Redirect;
 use crate::models::site_membership::SiteRole;
-use crate::services::audit_service;
+use crate::services::redirect_transfer_service::{self, TransferFormat};
+use crate::services::{audit_service, redirect_service};
 use crate::utils::pagination::PaginationParams;
+use crate::utils::response::FileDownload;
 use crate::AppState;
 
+/// Maximum size of an import file
+const MAX_IMPORT_MIB: u64 = 10;
+
 /// List redirects for a site (paginated)
 #[utoipa::path(
     tag = "Redirects",
@@ -116,14 +122,8 @@ pub async fn create_redirect(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    // Cross-field check
-    if req.source_path == req.destination_path {
-        return Err(ApiError::BadRequest(
-            "Source and destination paths must be different".to_string(),
-        ));
-    }
-
     let redirect = Redirect::create(&state.db, req).await?;
+    redirect_service::invalidate_patterns(site_id);
     audit_service::log_action(
         &state.db,
         Some(site_id),
@@ -171,19 +171,25 @@ pub async fn update_redirect(
     req.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
-    // Cross-field check: resolve effective values
-    let effective_source = req.source_path.as_deref().unwrap_or(&existing.source_path);
-    let effective_dest = req
-        .destination_path
-        .as_deref()
-        .unwrap_or(&existing.destination_path);
-    if effective_source == effective_dest {
-        return Err(ApiError::BadRequest(
-            "Source and destination paths must be different".to_string(),
-        ));
-    }
+    // Cross-field check: resolve effective values (410 drops the stored destination)
+    let effective_status = req.status_code.unwrap_or(existing.status_code);
+    let effective_dest = match effective_status {
+        410 => req.destination_path.as_deref(),
+        _ => req
+            .destination_path
+            .as_deref()
+            .or(existing.destination_path.as_deref()),
+    };
+    validate_redirect_rule(
+        req.match_type.unwrap_or(existing.match_type),
+        req.source_path.as_deref().unwrap_or(&existing.source_path),
+        effective_dest,
+        effective_status,
+    )
+    .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
 
     let redirect = Redirect::update(&state.db, id, req).await?;
+    redirect_service::invalidate_patterns(existing.site_id);
     audit_service::log_action(
         &state.db,
         Some(existing.site_id),
@@ -224,6 +230,7 @@ pub async fn delete_redirect(
         .await?;
 
     Redirect::delete(&state.db, id).await?;
+    redirect_service::invalidate_patterns(redirect.site_id);
     audit_service::log_action(
         &state.db,
         Some(redirect.site_id),
@@ -242,10 +249,10 @@ pub async fn delete_redirect(
 #[utoipa::path(
     tag = "Redirects",
     operation_id = "lookup_redirect",
-    description = "Lookup an active redirect by source path for a site",
+    description = "Resolve a requested path to an active redirect. Exact matches win over wildcard and regex patterns, which are tried in creation order. Each resolved lookup increments the redirect's hit counter.",
     params(
         ("site_id" = Uuid, Path, description = "Site UUID"),
-        ("path" = String, Query, description = "Source path to look up")
+        ("path" = String, Query, description = "Requested path, optionally with its URL-encoded query string")
     ),
     responses(
         (status = 200, description = "Redirect found", body = RedirectLookupResponse),
@@ -265,16 +272,175 @@ pub async fn lookup_redirect(
         .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
         .await?;
 
-    let redirect = Redirect::find_by_source_path(&state.db, site_id, &path)
+    let found = redirect_service::lookup(&state.db, site_id, &path)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("No active redirect for path '{}'", path)))?;
+    redirect_service::record_hit(&state.db, found.redirect_id).await;
 
     Ok(Json(RedirectLookupResponse {
-        destination_path: redirect.destination_path,
-        status_code: redirect.status_code,
+        destination_path: found.destination_path,
+        status_code: found.status_code,
     }))
 }
 
+/// Bulk import redirects from CSV or JSON
+#[utoipa::path(
+    tag = "Redirects",
+    operation_id = "import_redirects",
+    description = "Import redirects from a CSV file (`Content-Type: text/csv`, header row required) or a JSON array (`Content-Type: application/json`). Rows are checked for invalid values, duplicates within the file and against existing source paths, self-redirects, loops and chains. Rows with errors are skipped; chains are reported as warnings. With `dry_run=true` nothing is written and the report shows what would happen. All rows are written in one transaction.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("dry_run" = Option<bool>, Query, description = "Only check the file (default false)"),
+        ("overwrite" = Option<bool>, Query, description = "Replace redirects whose source path already exists instead of skipping them (default false)")
+    ),
+    request_body(
+        description = "Redirect rows (max 50,000 rows, 10 MiB)",
+        content(
+            (String = "text/csv"),
+            (Vec<RedirectTransferRow> = "application/json")
+        )
+    ),
+    responses(
+        (status = 200, description = "Import report", body = RedirectImportReport),
+        (status = 400, description = "Unreadable file or unsupported content type", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post(
+    "/sites/<site_id>/redirects/import?<dry_run>&<overwrite>",
+    data = "<data>"
+)]
+pub async fn import_redirects(
+    state: &State<AppState>,
+    site_id: Uuid,
+    dry_run: Option<bool>,
+    overwrite: Option<bool>,
+    content_type: Option<&ContentType>,
+    data: Data<'_>,
+    auth: ReadKey,
+) -> Result<Json<RedirectImportReport>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let dry_run = dry_run.unwrap_or(false);
+
+    let format = match content_type {
+        Some(ct) if ct.is_json() => TransferFormat::Json,
+        Some(ct) if ct.is_csv() || ct.is_text() => TransferFormat::Csv,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Send the file as text/csv or application/json".to_string(),
+            ))
+        }
+    };
+    let body = data
+        .open(MAX_IMPORT_MIB.mebibytes())
+        .into_string()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read import file: {}", e)))?;
+    if !body.is_complete() {
+        return Err(ApiError::BadRequest(format!(
+            "Import file exceeds {} MiB",
+            MAX_IMPORT_MIB
+        )));
+    }
+    let body = body.into_inner();
+
+    let rows = match format {
+        TransferFormat::Csv => redirect_transfer_service::parse_csv(&body)?,
+        TransferFormat::Json => redirect_transfer_service::parse_json(&body)?,
+    };
+    let existing = Redirect::find_all_unpaginated(&state.db, site_id).await?;
+    let plan = redirect_transfer_service::plan(
+        site_id,
+        rows,
+        &existing,
+        overwrite.unwrap_or(false),
+        dry_run,
+    );
+
+    if !dry_run && !plan.rows.is_empty() {
+        let mut tx = state.db.begin().await?;
+        for row in &plan.rows {
+            Redirect::import(&mut tx, &row.request).await?;
+        }
+        tx.commit().await?;
+        redirect_service::invalidate_patterns(site_id);
+
+        audit_service::log_action(
+            &state.db,
+            Some(site_id),
+            Some(auth.0.id),
+            AuditAction::Create,
+            "redirect_import",
+            site_id,
+            Some(serde_json::json!({
+                "created": plan.report.created,
+                "updated": plan.report.updated,
+                "skipped": plan.report.skipped,
+            })),
+        )
+        .await;
+    }
+
+    Ok(Json(plan.report))
+}
+
+/// Export all redirects of a site as CSV or JSON
+#[utoipa::path(
+    tag = "Redirects",
+    operation_id = "export_redirects",
+    description = "Download all redirects of a site in the import format, oldest first. Hit statistics and generated-redirect markers are not included.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("format" = Option<String>, Query, description = "csv (default) or json")
+    ),
+    responses(
+        (status = 200, description = "Redirect file", content(
+            (String = "text/csv"),
+            (Vec<RedirectTransferRow> = "application/json")
+        )),
+        (status = 400, description = "Unknown format", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/redirects/export?<format>")]
+pub async fn export_redirects(
+    state: &State<AppState>,
+    site_id: Uuid,
+    format: Option<String>,
+    auth: ReadKey,
+) -> Result<FileDownload, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let format = TransferFormat::parse(format.as_deref())?;
+
+    let rows: Vec<RedirectTransferRow> = Redirect::find_all_unpaginated(&state.db, site_id)
+        .await?
+        .into_iter()
+        .map(RedirectTransferRow::from)
+        .collect();
+
+    let export = match format {
+        TransferFormat::Csv => FileDownload {
+            body: redirect_transfer_service::render_csv(&rows)?.into_bytes(),
+            content_type: ContentType::CSV,
+            filename: format!("redirects-{}.csv", site_id),
+        },
+        TransferFormat::Json => FileDownload {
+            body: redirect_transfer_service::render_json(&rows)?.into_bytes(),
+            content_type: ContentType::JSON,
+            filename: format!("redirects-{}.json", site_id),
+        },
+    };
+    Ok(export)
+}
+
 /// Collect redirect routes
 pub fn routes() -> Vec<Route> {
     routes![
@@ -283,7 +449,9 @@ pub fn routes() -> Vec<Route> {
         create_redirect,
         update_redirect,
         delete_redirect,
-        lookup_redirect
+        lookup_redirect,
+        import_redirects,
+        export_redirects
     ]
 }
 
@@ -294,6 +462,6 @@ mod tests {
     #[test]
     fn test_routes_count() {
         let routes = routes();
-        assert_eq!(routes.len(), 6, "Should have 6 redirect routes");
+        assert_eq!(routes.len(), 8, "Should have 8 redirect routes");
     }
 }
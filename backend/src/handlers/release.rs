@@ -0,0 +1,530 @@
+//! Content release handlers
+//!
+//! Group content status and navigation changes into a named release, then
+//! publish it by hand or on a schedule and roll it back if needed.
+
+use chrono::Utc;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::release::{
+    AddReleaseItemRequest, CreateReleaseRequest, PaginatedReleases, ReleaseListItem,
+    ReleaseResponse, ScheduleReleaseRequest, UpdateReleaseRequest,
+};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::content_release::{ContentRelease, ContentReleaseItem, ContentReleaseStatus};
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, release_service};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+fn parse_status(status: &str) -> Result<ContentReleaseStatus, ApiError> {
+    match status.to_lowercase().as_str() {
+        "draft" => Ok(ContentReleaseStatus::Draft),
+        "scheduled" => Ok(ContentReleaseStatus::Scheduled),
+        "published" => Ok(ContentReleaseStatus::Published),
+        "rolled_back" | "rolledback" => Ok(ContentReleaseStatus::RolledBack),
+        "failed" => Ok(ContentReleaseStatus::Failed),
+        _ => Err(ApiError::Validation(format!("Invalid status: {}", status))),
+    }
+}
+
+/// Load a release with its items
+async fn release_response(
+    state: &AppState,
+    release: ContentRelease,
+) -> Result<ReleaseResponse, ApiError> {
+    let items = ContentReleaseItem::find_for_release(&state.db, release.id).await?;
+    Ok(ReleaseResponse::new(release, items))
+}
+
+/// List the releases of a site
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "list_releases",
+    description = "List the releases of a site, newest first (paginated)",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("status" = Option<String>, Query, description = "Filter by status: draft, scheduled, published, rolled_back or failed"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated release list", body = PaginatedReleases),
+        (status = 400, description = "Invalid status", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/releases?<status>&<page>&<per_page>")]
+pub async fn list_releases(
+    state: &State<AppState>,
+    site_id: Uuid,
+    status: Option<String>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedReleases>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+    let status = status.as_deref().map(parse_status).transpose()?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let releases =
+        ContentRelease::find_all_for_site(&state.db, site_id, status, limit, offset).await?;
+    let total = ContentRelease::count_for_site(&state.db, site_id, status).await?;
+
+    let items: Vec<ReleaseListItem> = releases.into_iter().map(ReleaseListItem::from).collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Create a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "create_release",
+    description = "Create an empty draft release",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content = CreateReleaseRequest, description = "Release name and description"),
+    responses(
+        (status = 201, description = "Release created", body = ReleaseResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/releases", data = "<body>")]
+pub async fn create_release(
+    state: &State<AppState>,
+    site_id: Uuid,
+    body: Json<CreateReleaseRequest>,
+    auth: ReadKey,
+) -> Result<(Status, Json<ReleaseResponse>), ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+
+    let release = ContentRelease::create(
+        &state.db,
+        site_id,
+        &req.name,
+        req.description.as_deref(),
+        Some(auth.0.id),
+    )
+    .await?;
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "content_release",
+        release.id,
+        Some(serde_json::json!({ "name": release.name })),
+    )
+    .await;
+
+    Ok((Status::Created, Json(ReleaseResponse::new(release, vec![]))))
+}
+
+/// Get a release by ID
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "get_release",
+    description = "Get a release with its items. Once published, each item also shows the state it replaced.",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    responses(
+        (status = 200, description = "Release details", body = ReleaseResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/releases/<id>")]
+pub async fn get_release(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Viewer)
+        .await?;
+    Ok(Json(release_response(state, release).await?))
+}
+
+/// Update a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "update_release",
+    description = "Rename a release or change its description",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    request_body(content = UpdateReleaseRequest, description = "Fields to change"),
+    responses(
+        (status = 200, description = "Release updated", body = ReleaseResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[put("/releases/<id>", data = "<body>")]
+pub async fn update_release(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<UpdateReleaseRequest>,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+
+    let release = ContentRelease::update(
+        &state.db,
+        id,
+        req.name.as_deref(),
+        req.description.as_deref(),
+    )
+    .await?;
+    audit_service::log_action(
+        &state.db,
+        Some(release.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "content_release",
+        id,
+        None,
+    )
+    .await;
+
+    Ok(Json(release_response(state, release).await?))
+}
+
+/// Delete a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "delete_release",
+    description = "Delete a release. Content is not touched. A published release must be rolled back first, since deleting it discards the recorded pre-release state.",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    responses(
+        (status = 204, description = "Release deleted"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails),
+        (status = 409, description = "Release is published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/releases/<id>")]
+pub async fn delete_release(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+    if release.status == ContentReleaseStatus::Published {
+        return Err(ApiError::Conflict(format!(
+            "Release '{}' is published; roll it back before deleting it",
+            release.name
+        )));
+    }
+
+    ContentRelease::delete(&state.db, id).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(release.site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "content_release",
+        id,
+        Some(serde_json::json!({ "name": release.name })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Add an item to a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "add_release_item",
+    description = "Add a content item (blog, page, CV entry or legal document) with the status it gets, or a navigation item with the visibility it gets. Adding an item that is already part of the release replaces its target. Only releases that are not yet published can be changed.",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    request_body(content = AddReleaseItemRequest, description = "Item and its target"),
+    responses(
+        (status = 200, description = "Updated release", body = ReleaseResponse),
+        (status = 400, description = "Validation error", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release, content or navigation item not found", body = ProblemDetails),
+        (status = 409, description = "Release already published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/releases/<id>/items", data = "<body>")]
+pub async fn add_release_item(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<AddReleaseItemRequest>,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let req = body.into_inner();
+    req.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+
+    release_service::add_item(&state.db, &release, &req).await?;
+    Ok(Json(release_response(state, release).await?))
+}
+
+/// Remove an item from a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "remove_release_item",
+    description = "Remove an item from a release that is not yet published",
+    params(
+        ("id" = Uuid, Path, description = "Release UUID"),
+        ("item_id" = Uuid, Path, description = "Release item UUID")
+    ),
+    responses(
+        (status = 204, description = "Item removed"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release or item not found", body = ProblemDetails),
+        (status = 409, description = "Release already published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/releases/<id>/items/<item_id>")]
+pub async fn remove_release_item(
+    state: &State<AppState>,
+    id: Uuid,
+    item_id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+    release_service::ensure_pending(&release)?;
+
+    ContentReleaseItem::delete(&state.db, id, item_id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Schedule a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "schedule_release",
+    description = "Publish the release automatically at `scheduled_at`. The publishing scheduler picks it up on its next tick after that time. Scheduling again moves the time; a failed release can be rescheduled.",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    request_body(content = ScheduleReleaseRequest, description = "Publish time"),
+    responses(
+        (status = 200, description = "Release scheduled", body = ReleaseResponse),
+        (status = 400, description = "Time is in the past", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails),
+        (status = 409, description = "Release already published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/releases/<id>/schedule", data = "<body>")]
+pub async fn schedule_release(
+    state: &State<AppState>,
+    id: Uuid,
+    body: Json<ScheduleReleaseRequest>,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let req = body.into_inner();
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+    release_service::ensure_pending(&release)?;
+    if req.scheduled_at <= Utc::now() {
+        return Err(ApiError::BadRequest(
+            "scheduled_at must be in the future; publish the release instead".to_string(),
+        ));
+    }
+
+    let release = ContentRelease::set_schedule(&state.db, id, Some(req.scheduled_at)).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(release.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "content_release",
+        id,
+        Some(serde_json::json!({ "scheduled_at": release.scheduled_at })),
+    )
+    .await;
+
+    Ok(Json(release_response(state, release).await?))
+}
+
+/// Unschedule a release
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "unschedule_release",
+    description = "Cancel the scheduled publish time and move the release back to draft",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    responses(
+        (status = 200, description = "Release is a draft again", body = ReleaseResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails),
+        (status = 409, description = "Release already published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/releases/<id>/schedule")]
+pub async fn unschedule_release(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+    release_service::ensure_pending(&release)?;
+
+    let release = ContentRelease::set_schedule(&state.db, id, None).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(release.site_id),
+        Some(auth.0.id),
+        AuditAction::Update,
+        "content_release",
+        id,
+        Some(serde_json::json!({ "scheduled_at": null })),
+    )
+    .await;
+
+    Ok(Json(release_response(state, release).await?))
+}
+
+/// Publish a release now
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "publish_release",
+    description = "Apply every item of the release in one transaction: content gets its target status and navigation items are shown or hidden. Content that becomes published gets a publish date, and a future `publish_start` or elapsed `publish_end` that would hide it is cleared. The previous state of every item is recorded for rollback. Fires a single `release.published` webhook listing all changes.",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    responses(
+        (status = 200, description = "Release published", body = ReleaseResponse),
+        (status = 400, description = "Release is empty or contains deleted content", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails),
+        (status = 409, description = "Release already published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/releases/<id>/publish")]
+pub async fn publish_release(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+
+    let (release, items) = release_service::publish(&state.db, id, Some(auth.0.id)).await?;
+    Ok(Json(ReleaseResponse::new(release, items)))
+}
+
+/// Roll a release back
+#[utoipa::path(
+    tag = "Releases",
+    operation_id = "rollback_release",
+    description = "Restore the status, publish dates and navigation visibility every item had before the release was published, in one transaction. Items edited after the release are left alone and flagged `rollback_skipped`. Fires a single `release.rolled_back` webhook.",
+    params(("id" = Uuid, Path, description = "Release UUID")),
+    responses(
+        (status = 200, description = "Release rolled back", body = ReleaseResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Release not found", body = ProblemDetails),
+        (status = 409, description = "Release is not published", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/releases/<id>/rollback")]
+pub async fn rollback_release(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    let release = ContentRelease::find_by_id(&state.db, id).await?;
+    auth.0
+        .authorize_site_action(&state.db, release.site_id, &SiteRole::Editor)
+        .await?;
+
+    let (release, items) = release_service::rollback(&state.db, id, Some(auth.0.id)).await?;
+    Ok(Json(ReleaseResponse::new(release, items)))
+}
+
+/// Collect release routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_releases,
+        create_release,
+        get_release,
+        update_release,
+        delete_release,
+        add_release_item,
+        remove_release_item,
+        schedule_release,
+        unschedule_release,
+        publish_release,
+        rollback_release
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 11, "Should have 11 release routes");
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(
+            parse_status("rolled_back").unwrap(),
+            ContentReleaseStatus::RolledBack
+        );
+        assert_eq!(
+            parse_status("Scheduled").unwrap(),
+            ContentReleaseStatus::Scheduled
+        );
+        assert!(parse_status("live").is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Full-text search handlers
+
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+
+use crate::dto::search::{PaginatedSearchResults, SearchParams, SearchResultResponse};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::locale::Locale;
+use crate::models::search::SearchHit;
+use crate::models::site_membership::SiteRole;
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+/// Search a site's content
+#[utoipa::path(
+    tag = "Search",
+    operation_id = "search_site",
+    description = "Full-text search over localized blog and page titles, subtitles, excerpts, bodies and meta fields, and document names and descriptions. Results are ranked (title matches weigh most) and include highlighted snippets. Read-only keys only see published content.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("q" = String, Query, description = "Search text (supports \"quoted phrases\", OR and -exclusions)"),
+        ("locale" = Option<String>, Query, description = "Restrict to one locale code, e.g. `de`"),
+        ("types" = Option<String>, Query, description = "Comma-separated types: blog, page, document (default all)"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Ranked search results", body = PaginatedSearchResults),
+        (status = 400, description = "Missing query or unknown type", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Locale not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/search?<q>&<locale>&<types>&<page>&<per_page>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_site(
+    state: &State<AppState>,
+    site_id: Uuid,
+    q: Option<String>,
+    locale: Option<String>,
+    types: Option<String>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedSearchResults>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+
+    let params = SearchParams { q, locale, types };
+    let query = params.query()?;
+    let entity_types = params.entity_types()?;
+    let locale_id = match params.locale.as_deref().filter(|l| !l.is_empty()) {
+        Some(code) => Some(Locale::find_by_code(&state.db, code).await?.id),
+        None => None,
+    };
+    let include_unpublished = auth.0.can_write();
+
+    let pagination = PaginationParams::new(page, per_page);
+    let (limit, offset) = pagination.limit_offset();
+
+    let hits = SearchHit::search_for_site(
+        &state.db,
+        site_id,
+        query,
+        locale_id,
+        &entity_types,
+        include_unpublished,
+        limit,
+        offset,
+    )
+    .await?;
+    let total = SearchHit::count_for_site(
+        &state.db,
+        site_id,
+        query,
+        locale_id,
+        &entity_types,
+        include_unpublished,
+    )
+    .await?;
+
+    let items: Vec<SearchResultResponse> =
+        hits.into_iter().map(SearchResultResponse::from).collect();
+    Ok(Json(pagination.paginate(items, total)))
+}
+
+/// Collect search routes
+pub fn routes() -> Vec<Route> {
+    routes![search_site]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 1, "Should have 1 search route");
+    }
+}
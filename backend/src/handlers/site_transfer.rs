@@ -0,0 +1,345 @@
+//! Site export/import handlers
+//!
+//! Export a site to a versioned archive and import an archive as a new site.
+//! Both run as background jobs whose progress can be polled.
+
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::{Header, Status};
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+
+use crate::dto::site_transfer::SiteTransferJobResponse;
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::{AuthSource, AuthenticatedKey, ReadKey};
+use crate::models::audit::AuditAction;
+use crate::models::site::Site;
+use crate::models::site_membership::SiteRole;
+use crate::models::site_transfer::{SiteTransferJob, SiteTransferKind, SiteTransferStatus};
+use crate::services::audit_service;
+use crate::services::site_transfer_service::{self, SiteImportOptions};
+use crate::utils::validation::validate_slug;
+use crate::AppState;
+
+/// Largest archive accepted by an import
+const MAX_ARCHIVE_GIB: u64 = 4;
+
+/// Archive of a completed export
+#[derive(Responder)]
+#[response(content_type = "application/zip")]
+pub struct ArchiveDownload {
+    file: Vec<u8>,
+    disposition: Header<'static>,
+}
+
+/// Exports belong to their site; an import belongs to whoever started it until its site exists
+async fn authorize_job(
+    state: &AppState,
+    job: &SiteTransferJob,
+    auth: &AuthenticatedKey,
+) -> Result<(), ApiError> {
+    match job.site_id {
+        Some(site_id) => {
+            auth.authorize_site_action(&state.db, site_id, &SiteRole::Admin)
+                .await
+        }
+        None if job.created_by == Some(auth.id) => Ok(()),
+        None => Err(ApiError::Forbidden(
+            "Only the creator can view this import".to_string(),
+        )),
+    }
+}
+
+/// Make sure an upload is a readable site archive and the requested slug is free
+async fn check_upload(
+    state: &AppState,
+    upload: &std::path::Path,
+    slug: Option<&str>,
+) -> Result<(), ApiError> {
+    let path = upload.to_path_buf();
+    tokio::task::spawn_blocking(move || site_transfer_service::read_manifest(&path))
+        .await
+        .map_err(|e| ApiError::Internal(format!("Archive task failed: {e}")))??;
+    if let Some(slug) = slug {
+        let mut conn = state.db.acquire().await?;
+        site_transfer_service::resolve_site_slug(&mut conn, Some(slug), slug).await?;
+    }
+    Ok(())
+}
+
+/// Check an upload, then keep it in storage under `key` for the import job
+async fn store_upload(
+    state: &AppState,
+    upload: &std::path::Path,
+    key: &str,
+    slug: Option<&str>,
+) -> Result<(), ApiError> {
+    check_upload(state, upload, slug).await?;
+    let data = tokio::fs::read(upload)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read archive: {e}")))?;
+    state.storage.store(key, &data, "application/zip").await?;
+    Ok(())
+}
+
+/// Start a site export
+#[utoipa::path(
+    tag = "Site Transfers",
+    operation_id = "export_site",
+    description = "Start exporting a site (settings, locales, content, taxonomy, navigation, redirects, templates and media binaries) to a zip archive. Poll the returned job and download the archive once it has completed.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    responses(
+        (status = 202, description = "Export queued", body = SiteTransferJobResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Site not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/export")]
+pub async fn export_site(
+    state: &State<AppState>,
+    site_id: Uuid,
+    auth: ReadKey,
+) -> Result<(Status, Json<SiteTransferJobResponse>), ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Admin)
+        .await?;
+    Site::find_by_id(&state.db, site_id).await?;
+
+    let job = SiteTransferJob::create(
+        &state.db,
+        SiteTransferKind::Export,
+        Some(site_id),
+        Some(auth.0.id),
+        site_transfer_service::instance_id(),
+    )
+    .await?;
+    site_transfer_service::spawn_export(state.db.clone(), state.storage.clone(), job.id, site_id);
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "site_export",
+        job.id,
+        None,
+    )
+    .await;
+
+    Ok((Status::Accepted, Json(SiteTransferJobResponse::from(job))))
+}
+
+/// Import a site archive
+#[utoipa::path(
+    tag = "Site Transfers",
+    operation_id = "import_site",
+    description = "Upload a site archive (application/zip, max 4 GiB) and import it as a new site. Every record gets a new ID; existing tags, categories, skills and identical media are reused. Without a slug the archived slug is used, with a numeric suffix if it is taken. Requires the same rights as creating a site.",
+    params(
+        ("name" = Option<String>, Query, description = "Name of the new site (default: archived name)"),
+        ("slug" = Option<String>, Query, description = "Slug of the new site; 409 if taken (default: archived slug)")
+    ),
+    request_body(content_type = "application/zip", content = String, description = "Archive produced by a site export"),
+    responses(
+        (status = 202, description = "Import queued", body = SiteTransferJobResponse),
+        (status = 400, description = "Not a site archive or unsupported version", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 409, description = "Slug already taken", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/import?<name>&<slug>", data = "<data>")]
+pub async fn import_site(
+    state: &State<AppState>,
+    name: Option<String>,
+    slug: Option<String>,
+    data: Data<'_>,
+    auth: ReadKey,
+) -> Result<(Status, Json<SiteTransferJobResponse>), ApiError> {
+    let owner_clerk_id = match &auth.0.auth_source {
+        AuthSource::ClerkJwt { clerk_user_id } => Some(clerk_user_id.clone()),
+        AuthSource::ApiKey => {
+            if !auth.0.is_admin() {
+                return Err(ApiError::Forbidden(
+                    "Admin API key required to import sites".into(),
+                ));
+            }
+            if auth.0.is_site_scoped() {
+                return Err(ApiError::Forbidden(
+                    "Site-scoped API keys cannot create new sites".into(),
+                ));
+            }
+            None
+        }
+    };
+    if let Some(ref slug) = slug {
+        validate_slug(slug)
+            .map_err(|_| ApiError::BadRequest(format!("Invalid slug '{}'", slug)))?;
+    }
+    if name
+        .as_deref()
+        .is_some_and(|n| n.trim().is_empty() || n.len() > 200)
+    {
+        return Err(ApiError::BadRequest(
+            "Name must be between 1 and 200 characters".to_string(),
+        ));
+    }
+
+    let dir = site_transfer_service::transfer_dir();
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to prepare import: {e}")))?;
+    let upload = dir.join(format!("upload-{}.zip", Uuid::new_v4()));
+    let written = data
+        .open(MAX_ARCHIVE_GIB.gibibytes())
+        .into_file(&upload)
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read archive: {}", e)))?;
+    if !written.is_complete() {
+        let _ = tokio::fs::remove_file(&upload).await;
+        return Err(ApiError::BadRequest(format!(
+            "Archive exceeds {} GiB",
+            MAX_ARCHIVE_GIB
+        )));
+    }
+
+    let key = site_transfer_service::new_archive_key();
+    let stored = store_upload(state, &upload, &key, slug.as_deref()).await;
+    let _ = tokio::fs::remove_file(&upload).await;
+    stored?;
+
+    let job = SiteTransferJob::create(
+        &state.db,
+        SiteTransferKind::Import,
+        None,
+        Some(auth.0.id),
+        site_transfer_service::instance_id(),
+    )
+    .await?;
+    SiteTransferJob::set_archive(&state.db, job.id, Some(&key)).await?;
+
+    let storage_provider = if state.settings.storage.provider == "s3" {
+        "s3"
+    } else {
+        "local"
+    };
+    site_transfer_service::spawn_import(
+        state.db.clone(),
+        state.storage.clone(),
+        job.id,
+        SiteImportOptions {
+            name,
+            slug,
+            storage_provider: storage_provider.to_string(),
+            owner_clerk_id,
+        },
+    );
+    audit_service::log_action(
+        &state.db,
+        None,
+        Some(auth.0.id),
+        AuditAction::Create,
+        "site_import",
+        job.id,
+        None,
+    )
+    .await;
+
+    Ok((Status::Accepted, Json(SiteTransferJobResponse::from(job))))
+}
+
+/// Get a site transfer job
+#[utoipa::path(
+    tag = "Site Transfers",
+    operation_id = "get_site_transfer",
+    description = "Get the status and progress of a site export or import",
+    params(("id" = Uuid, Path, description = "Job UUID")),
+    responses(
+        (status = 200, description = "Job status", body = SiteTransferJobResponse),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Job not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/site-transfers/<id>")]
+pub async fn get_site_transfer(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<SiteTransferJobResponse>, ApiError> {
+    let job = SiteTransferJob::find_by_id(&state.db, id).await?;
+    authorize_job(state, &job, &auth.0).await?;
+    Ok(Json(SiteTransferJobResponse::from(job)))
+}
+
+/// Download an export archive
+#[utoipa::path(
+    tag = "Site Transfers",
+    operation_id = "download_site_export",
+    description = "Download the archive of a completed site export",
+    params(("id" = Uuid, Path, description = "Job UUID")),
+    responses(
+        (status = 200, description = "Site archive", content_type = "application/zip", body = String),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Job or archive not found", body = ProblemDetails),
+        (status = 409, description = "Not a completed export", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/site-transfers/<id>/download")]
+pub async fn download_site_export(
+    state: &State<AppState>,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<ArchiveDownload, ApiError> {
+    let job = SiteTransferJob::find_by_id(&state.db, id).await?;
+    authorize_job(state, &job, &auth.0).await?;
+    if job.kind != SiteTransferKind::Export || job.status != SiteTransferStatus::Completed {
+        return Err(ApiError::Conflict(
+            "Only completed exports can be downloaded".to_string(),
+        ));
+    }
+
+    let unavailable = || ApiError::NotFound("Export archive is no longer available".to_string());
+    let path = job.archive_path.as_deref().ok_or_else(unavailable)?;
+    let file = state.storage.read(path).await.map_err(|_| unavailable())?;
+    let slug = job
+        .result
+        .as_ref()
+        .and_then(|r| r["site_slug"].as_str())
+        .unwrap_or("site")
+        .to_string();
+    let file_name = format!("{}-{}.zip", slug, job.created_at.format("%Y%m%d-%H%M%S"));
+
+    Ok(ArchiveDownload {
+        file,
+        disposition: Header::new(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", file_name),
+        ),
+    })
+}
+
+/// Collect site transfer routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        export_site,
+        import_site,
+        get_site_transfer,
+        download_site_export
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 4, "Should have 4 site transfer routes");
+    }
+}
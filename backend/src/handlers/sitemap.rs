@@ -0,0 +1,104 @@
+//! Sitemap handlers
+
+use std::io::Cursor;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::{Request, Route, State};
+use uuid::Uuid;
+
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::blog::Blog;
+use crate::models::site::Site;
+use crate::models::site_membership::SiteRole;
+use crate::models::sitemap::SitemapEntry;
+use crate::services::sitemap_service::{self, MAX_URLS_PER_SITEMAP};
+use crate::AppState;
+
+/// Custom responder for sitemap XML documents
+pub struct SitemapResponse(pub String);
+
+impl<'r> Responder<'r, 'static> for SitemapResponse {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .status(Status::Ok)
+            .header(ContentType::XML)
+            .header(Header::new("Cache-Control", "public, max-age=3600"))
+            .sized_body(self.0.len(), Cursor::new(self.0))
+            .ok()
+    }
+}
+
+/// XML sitemap for a site
+#[utoipa::path(
+    tag = "Sitemap",
+    operation_id = "sitemap",
+    description = "Get the XML sitemap of a site's published pages and blog posts, with one URL per localization and hreflang alternates. Content outside its publish window is left out. Sites with more than 50,000 URLs get a sitemap index; fetch each part with `page`.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("page" = Option<usize>, Query, description = "Sitemap part listed in the sitemap index (1-based)")
+    ),
+    responses(
+        (status = 200, description = "Sitemap or sitemap index XML", content_type = "application/xml"),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Site, primary domain or sitemap part not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/sitemap.xml?<page>")]
+pub async fn sitemap(
+    state: &State<AppState>,
+    site_id: Uuid,
+    page: Option<usize>,
+    auth: ReadKey,
+) -> Result<SitemapResponse, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Viewer)
+        .await?;
+
+    Site::find_by_id(&state.db, site_id).await?;
+    // Sitemap URLs must be absolute
+    let base_url = Site::primary_base_url(&state.db, site_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Site {} has no primary production domain", site_id))
+        })?;
+
+    let blog_prefix = Blog::path_prefix(&state.db, site_id).await?;
+    let entries = SitemapEntry::find_for_site(&state.db, site_id).await?;
+    let urls = sitemap_service::build_urls(&base_url, &blog_prefix, &entries);
+    let pages = sitemap_service::page_count(urls.len());
+
+    let xml = match page {
+        None if pages <= 1 => sitemap_service::render_urlset(&urls),
+        None => sitemap_service::render_index(&base_url, &urls),
+        Some(n) if n >= 1 && n <= pages => {
+            let start = (n - 1) * MAX_URLS_PER_SITEMAP;
+            let end = (start + MAX_URLS_PER_SITEMAP).min(urls.len());
+            sitemap_service::render_urlset(&urls[start..end])
+        }
+        Some(n) => {
+            return Err(ApiError::NotFound(format!("Sitemap page {} not found", n)));
+        }
+    };
+
+    Ok(SitemapResponse(xml))
+}
+
+/// Collect sitemap routes
+pub fn routes() -> Vec<Route> {
+    routes![sitemap]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 1, "Should have 1 sitemap route");
+    }
+}
@@ -0,0 +1,237 @@
+//! Trash handlers
+//!
+//! List deleted content and media of a site, restore them, or purge them for
+//! good ahead of the retention period.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+
+use crate::dto::trash::{PaginatedTrash, TrashItemResponse, TrashPurgeReport};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::site_membership::SiteRole;
+use crate::models::trash::{TrashItem, TrashItemKind};
+use crate::services::{audit_service, trash_service};
+use crate::utils::pagination::PaginationParams;
+use crate::AppState;
+
+fn parse_kind(kind: &str) -> Result<TrashItemKind, ApiError> {
+    match kind.to_lowercase().as_str() {
+        "content" => Ok(TrashItemKind::Content),
+        "media" => Ok(TrashItemKind::Media),
+        _ => Err(ApiError::Validation(format!("Invalid kind: {}", kind))),
+    }
+}
+
+/// List the trash of a site
+#[utoipa::path(
+    tag = "Trash",
+    operation_id = "list_trash",
+    description = "List deleted content and media owned by a site, most recently deleted first (paginated). `purge_at` tells when the retention purge removes an item.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("kind" = Option<String>, Query, description = "Filter by kind: content or media"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Paginated trash list", body = PaginatedTrash),
+        (status = 400, description = "Invalid kind", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[get("/sites/<site_id>/trash?<kind>&<page>&<per_page>")]
+pub async fn list_trash(
+    state: &State<AppState>,
+    site_id: Uuid,
+    kind: Option<String>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    auth: ReadKey,
+) -> Result<Json<PaginatedTrash>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let kind = kind.as_deref().map(parse_kind).transpose()?;
+    let params = PaginationParams::new(page, per_page);
+    let (limit, offset) = params.limit_offset();
+
+    let items = TrashItem::find_for_site(&state.db, site_id, kind, limit, offset).await?;
+    let total = TrashItem::count_for_site(&state.db, site_id, kind).await?;
+
+    let retention_days = state.settings.scheduler.trash_retention_days;
+    let items: Vec<TrashItemResponse> = items
+        .into_iter()
+        .map(|item| TrashItemResponse::new(item, retention_days))
+        .collect();
+    Ok(Json(params.paginate(items, total)))
+}
+
+/// Restore an item from the trash
+#[utoipa::path(
+    tag = "Trash",
+    operation_id = "restore_trash_item",
+    description = "Restore deleted content or a deleted media file. Content comes back with the status it had when it was deleted. Fails with 409 if its slug, or a page's route, has since been taken by other content.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("kind" = String, Path, description = "content or media"),
+        ("id" = Uuid, Path, description = "Content UUID or media file UUID")
+    ),
+    responses(
+        (status = 204, description = "Item restored"),
+        (status = 400, description = "Invalid kind", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Item not in the trash", body = ProblemDetails),
+        (status = 409, description = "Slug or route taken", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/trash/<kind>/<id>/restore")]
+pub async fn restore_trash_item(
+    state: &State<AppState>,
+    site_id: Uuid,
+    kind: &str,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    let item = match parse_kind(kind)? {
+        TrashItemKind::Content => trash_service::restore_content(&state.db, site_id, id).await?,
+        TrashItemKind::Media => trash_service::restore_media(&state.db, site_id, id).await?,
+    };
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Restore,
+        &item.entity_type,
+        item.entity_id,
+        None,
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Permanently delete an item from the trash
+#[utoipa::path(
+    tag = "Trash",
+    operation_id = "purge_trash_item",
+    description = "Permanently delete content or a media file from the trash. Media files are also removed from storage. This cannot be undone.",
+    params(
+        ("site_id" = Uuid, Path, description = "Site UUID"),
+        ("kind" = String, Path, description = "content or media"),
+        ("id" = Uuid, Path, description = "Content UUID or media file UUID")
+    ),
+    responses(
+        (status = 204, description = "Item purged"),
+        (status = 400, description = "Invalid kind", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Item not in the trash", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/sites/<site_id>/trash/<kind>/<id>")]
+pub async fn purge_trash_item(
+    state: &State<AppState>,
+    site_id: Uuid,
+    kind: &str,
+    id: Uuid,
+    auth: ReadKey,
+) -> Result<Status, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Admin)
+        .await?;
+    let kind = parse_kind(kind)?;
+    let item =
+        trash_service::purge_item(&state.db, state.storage.as_ref(), site_id, kind, id).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        &item.entity_type,
+        item.entity_id,
+        Some(serde_json::json!({ "purged": true })),
+    )
+    .await;
+
+    Ok(Status::NoContent)
+}
+
+/// Empty the trash of a site
+#[utoipa::path(
+    tag = "Trash",
+    operation_id = "empty_trash",
+    description = "Permanently delete everything in a site's trash, including stored media files. This cannot be undone.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    responses(
+        (status = 200, description = "Number of items purged", body = TrashPurgeReport),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[delete("/sites/<site_id>/trash")]
+pub async fn empty_trash(
+    state: &State<AppState>,
+    site_id: Uuid,
+    auth: ReadKey,
+) -> Result<Json<TrashPurgeReport>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Admin)
+        .await?;
+    let report = trash_service::empty_trash(&state.db, state.storage.as_ref(), site_id).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Delete,
+        "trash",
+        site_id,
+        Some(serde_json::json!({ "content": report.content, "media": report.media })),
+    )
+    .await;
+
+    Ok(Json(report))
+}
+
+/// Collect trash routes
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_trash,
+        restore_trash_item,
+        purge_trash_item,
+        empty_trash
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 4, "Should have 4 trash routes");
+    }
+
+    #[test]
+    fn test_parse_kind() {
+        assert_eq!(parse_kind("content").unwrap(), TrashItemKind::Content);
+        assert_eq!(parse_kind("Media").unwrap(), TrashItemKind::Media);
+        assert!(parse_kind("blog").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! WordPress import handler
+
+use rocket::data::{Data, ToByteUnit};
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use uuid::Uuid;
+
+use crate::dto::wordpress_import::WordPressImportReport;
+use crate::errors::{ApiError, ProblemDetails};
+use crate::guards::auth_guard::ReadKey;
+use crate::models::audit::AuditAction;
+use crate::models::site::Site;
+use crate::models::site_membership::SiteRole;
+use crate::services::{audit_service, wordpress_import_service};
+use crate::AppState;
+
+/// Maximum size of an export file
+const MAX_IMPORT_MIB: u64 = 100;
+
+/// Import a WordPress export (WXR) file into a site
+#[utoipa::path(
+    tag = "WordPress Import",
+    operation_id = "import_wordpress",
+    description = "Import a WordPress export file (Tools → Export, WXR). Posts become blogs, pages keep their parent hierarchy as routes, categories and tags go to the taxonomy and author display names to the blog `author` field. Attachments are fetched from their original URLs, or from the server's import source directory when one is configured, and stored through the media upload pipeline; links to them in post bodies are rewritten. Old permalinks redirect to the new paths. Blogs, pages, terms and files that already exist are reused, so the import can be repeated. Trashed items, revisions, menus and custom post types are skipped.",
+    params(("site_id" = Uuid, Path, description = "Site UUID")),
+    request_body(content_type = "application/xml", content = String, description = "WXR file (max 100 MiB)"),
+    responses(
+        (status = 200, description = "Import report", body = WordPressImportReport),
+        (status = 400, description = "Not a WXR file, or the site has no default locale", body = ProblemDetails),
+        (status = 401, description = "Unauthorized", body = ProblemDetails),
+        (status = 403, description = "Forbidden", body = ProblemDetails),
+        (status = 404, description = "Site not found", body = ProblemDetails)
+    ),
+    security(("api_key" = []))
+)]
+#[post("/sites/<site_id>/import/wordpress", data = "<data>")]
+pub async fn import_wordpress(
+    state: &State<AppState>,
+    site_id: Uuid,
+    data: Data<'_>,
+    auth: ReadKey,
+) -> Result<Json<WordPressImportReport>, ApiError> {
+    auth.0
+        .authorize_site_action(&state.db, site_id, &SiteRole::Editor)
+        .await?;
+    Site::find_by_id(&state.db, site_id).await?;
+
+    let body = data
+        .open(MAX_IMPORT_MIB.mebibytes())
+        .into_string()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read export file: {}", e)))?;
+    if !body.is_complete() {
+        return Err(ApiError::BadRequest(format!(
+            "Export file exceeds {} MiB",
+            MAX_IMPORT_MIB
+        )));
+    }
+
+    let export = wordpress_import_service::parse_wxr(&body)?;
+    let report = wordpress_import_service::import(state, site_id, &export, Some(auth.0.id)).await?;
+
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.0.id),
+        AuditAction::Create,
+        "wordpress_import",
+        site_id,
+        Some(serde_json::json!({
+            "blogs": report.blogs.created,
+            "pages": report.pages.created,
+            "media": report.media.created,
+            "redirects": report.redirects,
+            "warnings": report.warnings.len(),
+        })),
+    )
+    .await;
+
+    Ok(Json(report))
+}
+
+/// Collect WordPress import routes
+pub fn routes() -> Vec<Route> {
+    routes![import_wordpress]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_count() {
+        let routes = routes();
+        assert_eq!(routes.len(), 1, "Should have 1 WordPress import route");
+    }
+}
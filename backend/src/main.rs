@@ -20,7 +20,7 @@ use rocket::fs::FileServer;
 
 use openyapper::guards::auth_guard::ClerkJwksState;
 use openyapper::middleware::rate_limit::RateLimitHeaderInfo;
-use openyapper::services::storage;
+use openyapper::services::{scheduler_service, site_transfer_service, storage};
 use openyapper::{handlers, openapi::ApiDoc, AppState, Settings};
 
 #[launch]
@@ -202,6 +202,26 @@ async fn rocket() -> _ {
         }
     }
 
+    // Start the background publishing scheduler
+    if settings.scheduler.enabled {
+        tracing::info!(
+            "Publishing scheduler enabled (interval: {}s)",
+            settings.scheduler.interval_seconds
+        );
+        scheduler_service::spawn(
+            db_pool.clone(),
+            app_state.storage.clone(),
+            std::time::Duration::from_secs(settings.scheduler.interval_seconds.max(1)),
+            settings.scheduler.trash_retention_days,
+            settings.scheduler.avif_backfill_batch,
+        );
+    } else {
+        tracing::info!("Publishing scheduler disabled");
+    }
+
+    // Fail site transfers whose owner went away and expire old archives
+    site_transfer_service::spawn_maintenance(db_pool.clone(), app_state.storage.clone());
+
     let mut rocket_instance = rocket::custom(rocket_config).manage(app_state);
 
     if let Some(jwks_state) = clerk_jwks_state {
@@ -255,7 +275,11 @@ async fn rocket() -> _ {
                 ));
                 res.set_header(rocket::http::Header::new(
                     "Access-Control-Allow-Headers",
-                    "Content-Type, Authorization, X-API-Key, X-Site-Domain, X-Request-ID",
+                    "Content-Type, Authorization, X-API-Key, X-Site-Domain, X-Request-ID, If-Match",
+                ));
+                res.set_header(rocket::http::Header::new(
+                    "Access-Control-Expose-Headers",
+                    "ETag",
                 ));
                 res.set_header(rocket::http::Header::new(
                     "Access-Control-Max-Age",
@@ -120,6 +120,25 @@ impl RateLimiter {
         Self::check_windows(redis, &identifier, &windows).await
     }
 
+    /// Throttle public submissions (comments, forms) per client IP.
+    ///
+    /// Counted under `{namespace}:{ip}` with the given limits, so submissions do not eat
+    /// into the general per-IP budget. Skipped when Redis or the client address is
+    /// unavailable.
+    pub async fn check_submission(
+        redis: Option<&redis::aio::ConnectionManager>,
+        namespace: &str,
+        ip: Option<&str>,
+        limits: &SecurityConfig,
+    ) -> Result<(), ApiError> {
+        let (Some(redis), Some(ip)) = (redis, ip) else {
+            return Ok(());
+        };
+        let mut redis_conn = redis.clone();
+        Self::check_ip(&mut redis_conn, &format!("{}:{}", namespace, ip), limits).await?;
+        Ok(())
+    }
+
     /// Build window definitions from per-key rate limits
     fn build_key_windows(limits: &RateLimits) -> Vec<Window> {
         let mut windows = Vec::new();
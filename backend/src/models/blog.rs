@@ -2,12 +2,14 @@
 
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::dto::blog::{CreateBlogRequest, UpdateBlogRequest};
 use crate::errors::ApiError;
-use crate::models::content::{ContentLocalization, ContentStatus};
+use crate::models::content::{Content, ContentLocalization, ContentStatus};
+use crate::models::environment::Environment;
+use crate::models::site_settings::{SiteSetting, KEY_BLOG_PATH_PREFIX};
 use crate::services::content_service::ContentService;
 
 /// Blog with joined content data
@@ -57,10 +59,51 @@ pub struct BlogDetails {
 }
 
 impl Blog {
-    /// Find all blogs for a site
+    /// Path of a blog post on the public site, below the site's `blog_path_prefix`
+    pub fn public_path(prefix: &str, slug: &str) -> String {
+        format!("{}/{}", prefix.trim_end_matches('/'), slug)
+    }
+
+    /// The site's `blog_path_prefix` setting
+    pub async fn path_prefix(pool: &PgPool, site_id: Uuid) -> Result<String, ApiError> {
+        let value = SiteSetting::get_value(pool, site_id, KEY_BLOG_PATH_PREFIX).await?;
+        Ok(value.as_str().unwrap_or("/blog").to_string())
+    }
+
+    /// Public path of a blog on each of its sites, using the slug in effect on that site.
+    /// Empty if the content is not a blog or not in the live environment.
+    pub async fn public_paths(
+        pool: &PgPool,
+        content_id: Uuid,
+    ) -> Result<Vec<(Uuid, String)>, ApiError> {
+        let slugs: Vec<(Uuid, String)> = sqlx::query_as(
+            r#"
+            SELECT cs.site_id, COALESCE(cs.site_specific_slug, c.slug)::text
+            FROM content_sites cs
+            INNER JOIN contents c ON c.id = cs.content_id
+            INNER JOIN blogs b ON b.content_id = c.id
+            WHERE cs.content_id = $1 AND COALESCE(cs.site_specific_slug, c.slug) IS NOT NULL
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
+            ORDER BY cs.is_owner DESC, cs.created_at ASC
+            "#,
+        )
+        .bind(content_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut paths = Vec::with_capacity(slugs.len());
+        for (site_id, slug) in slugs {
+            let prefix = Self::path_prefix(pool, site_id).await?;
+            paths.push((site_id, Self::public_path(&prefix, &slug)));
+        }
+        Ok(paths)
+    }
+
+    /// Find all blogs for a site in an environment (default: the live one)
     pub async fn find_all_for_site(
         pool: &PgPool,
         site_id: Uuid,
+        environment_id: Option<Uuid>,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<BlogWithContent>, ApiError> {
@@ -68,20 +111,24 @@ impl Blog {
             r#"
             SELECT
                 b.id, b.content_id, b.author, b.published_date,
-                b.reading_time_minutes, b.cover_image_id, b.header_image_id, b.is_featured, b.allow_comments,
-                c.slug, c.status, c.published_at, c.publish_start, c.publish_end,
+                b.reading_time_minutes, b.cover_image_id, b.header_image_id,
+                CASE WHEN cs.is_owner THEN b.is_featured ELSE cs.is_featured END AS is_featured,
+                b.allow_comments, COALESCE(cs.site_specific_slug, c.slug) AS slug,
+                c.status, c.published_at, c.publish_start, c.publish_end,
                 b.created_at, b.updated_at
             FROM blogs b
             INNER JOIN contents c ON b.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND c.is_deleted = FALSE
-            ORDER BY b.published_date DESC
+              AND c.environment_id = COALESCE($4, (SELECT id FROM environments WHERE is_default))
+            ORDER BY cs.display_order ASC NULLS LAST, b.published_date DESC
             LIMIT $2 OFFSET $3
             "#,
         )
         .bind(site_id)
         .bind(limit)
         .bind(offset)
+        .bind(environment_id)
         .fetch_all(pool)
         .await?;
 
@@ -99,18 +146,21 @@ impl Blog {
             r#"
             SELECT
                 b.id, b.content_id, b.author, b.published_date,
-                b.reading_time_minutes, b.cover_image_id, b.header_image_id, b.is_featured, b.allow_comments,
-                c.slug, c.status, c.published_at, c.publish_start, c.publish_end,
+                b.reading_time_minutes, b.cover_image_id, b.header_image_id,
+                CASE WHEN cs.is_owner THEN b.is_featured ELSE cs.is_featured END AS is_featured,
+                b.allow_comments, COALESCE(cs.site_specific_slug, c.slug) AS slug,
+                c.status, c.published_at, c.publish_start, c.publish_end,
                 b.created_at, b.updated_at
             FROM blogs b
             INNER JOIN contents c ON b.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1
               AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
               AND c.status IN ('published', 'scheduled')
               AND (c.publish_start IS NULL OR c.publish_start <= NOW())
               AND (c.publish_end IS NULL OR c.publish_end > NOW())
-            ORDER BY b.published_date DESC
+            ORDER BY cs.display_order ASC NULLS LAST, b.published_date DESC
             LIMIT $2 OFFSET $3
             "#,
         )
@@ -124,7 +174,10 @@ impl Blog {
     }
 
     /// Find blog by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<BlogWithContent, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<BlogWithContent, ApiError> {
         let blog = sqlx::query_as::<_, BlogWithContent>(
             r#"
             SELECT
@@ -138,7 +191,7 @@ impl Blog {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Blog with ID {} not found", id)))?;
 
@@ -155,13 +208,18 @@ impl Blog {
             r#"
             SELECT
                 b.id, b.content_id, b.author, b.published_date,
-                b.reading_time_minutes, b.cover_image_id, b.header_image_id, b.is_featured, b.allow_comments,
-                c.slug, c.status, c.published_at, c.publish_start, c.publish_end,
+                b.reading_time_minutes, b.cover_image_id, b.header_image_id,
+                CASE WHEN cs.is_owner THEN b.is_featured ELSE cs.is_featured END AS is_featured,
+                b.allow_comments, COALESCE(cs.site_specific_slug, c.slug) AS slug,
+                c.status, c.published_at, c.publish_start, c.publish_end,
                 b.created_at, b.updated_at
             FROM blogs b
             INNER JOIN contents c ON b.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
-            WHERE cs.site_id = $1 AND c.slug = $2 AND c.is_deleted = FALSE
+            WHERE cs.site_id = $1
+              AND COALESCE(cs.site_specific_slug, c.slug) = $2
+              AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
             "#,
         )
         .bind(site_id)
@@ -183,19 +241,22 @@ impl Blog {
             r#"
             SELECT
                 b.id, b.content_id, b.author, b.published_date,
-                b.reading_time_minutes, b.cover_image_id, b.header_image_id, b.is_featured, b.allow_comments,
-                c.slug, c.status, c.published_at, c.publish_start, c.publish_end,
+                b.reading_time_minutes, b.cover_image_id, b.header_image_id,
+                CASE WHEN cs.is_owner THEN b.is_featured ELSE cs.is_featured END AS is_featured,
+                b.allow_comments, COALESCE(cs.site_specific_slug, c.slug) AS slug,
+                c.status, c.published_at, c.publish_start, c.publish_end,
                 b.created_at, b.updated_at
             FROM blogs b
             INNER JOIN contents c ON b.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1
               AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
               AND c.status IN ('published', 'scheduled')
-              AND b.is_featured = TRUE
+              AND CASE WHEN cs.is_owner THEN b.is_featured ELSE cs.is_featured END
               AND (c.publish_start IS NULL OR c.publish_start <= NOW())
               AND (c.publish_end IS NULL OR c.publish_end > NOW())
-            ORDER BY b.published_date DESC
+            ORDER BY cs.display_order ASC NULLS LAST, b.published_date DESC
             LIMIT $2
             "#,
         )
@@ -207,8 +268,12 @@ impl Blog {
         Ok(blogs)
     }
 
-    /// Count blogs for a site
-    pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
+    /// Count blogs for a site in an environment (default: the live one)
+    pub async fn count_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        environment_id: Option<Uuid>,
+    ) -> Result<i64, ApiError> {
         let row: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*)
@@ -216,9 +281,11 @@ impl Blog {
             INNER JOIN contents c ON b.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND c.is_deleted = FALSE
+              AND c.environment_id = COALESCE($2, (SELECT id FROM environments WHERE is_default))
             "#,
         )
         .bind(site_id)
+        .bind(environment_id)
         .fetch_one(pool)
         .await?;
 
@@ -235,6 +302,7 @@ impl Blog {
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1
               AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
               AND c.status IN ('published', 'scheduled')
               AND (c.publish_start IS NULL OR c.publish_start <= NOW())
               AND (c.publish_end IS NULL OR c.publish_end > NOW())
@@ -249,18 +317,21 @@ impl Blog {
 
     /// Create a new blog post with associated content
     pub async fn create(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         req: CreateBlogRequest,
     ) -> Result<BlogWithContent, ApiError> {
-        // Create content record (handles transaction, entity_type lookup, site associations)
-        let content_id = ContentService::create_content(
-            pool,
+        let environment_id = Environment::resolve_id(&mut *conn, req.environment.clone()).await?;
+
+        // Create content record (entity_type lookup, site associations)
+        let content_id = ContentService::insert_content(
+            &mut *conn,
             "blog",
             Some(&req.slug),
             &req.status,
             &req.site_ids,
             req.publish_start,
             req.publish_end,
+            environment_id,
         )
         .await?;
 
@@ -280,7 +351,7 @@ impl Blog {
         .bind(req.header_image_id)
         .bind(req.is_featured)
         .bind(req.allow_comments)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
         // Return the full blog with content
@@ -297,7 +368,7 @@ impl Blog {
             "#,
         )
         .bind(content_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(blog)
@@ -305,16 +376,16 @@ impl Blog {
 
     /// Update a blog post
     pub async fn update(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         id: Uuid,
         req: UpdateBlogRequest,
     ) -> Result<BlogWithContent, ApiError> {
         // Find existing blog to get content_id
-        let existing = Self::find_by_id(pool, id).await?;
+        let existing = Self::find_by_id(&mut *conn, id).await?;
 
         // Update content record
         ContentService::update_content(
-            pool,
+            &mut *conn,
             existing.content_id,
             req.slug.as_deref(),
             req.status.as_ref(),
@@ -346,32 +417,37 @@ impl Blog {
         .bind(req.header_image_id)
         .bind(req.is_featured)
         .bind(req.allow_comments)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
-        Self::find_by_id(pool, id).await
+        Self::find_by_id(&mut *conn, id).await
     }
 
     /// Clone a blog post: creates a new Draft blog copying fields and localizations.
     pub async fn clone_blog(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         source_id: Uuid,
         site_ids: Vec<Uuid>,
     ) -> Result<BlogWithContent, ApiError> {
-        let source = Self::find_by_id(pool, source_id).await?;
+        let source = Self::find_by_id(&mut *conn, source_id).await?;
 
         let base_slug = source.slug.as_deref().unwrap_or("untitled");
-        let new_slug = ContentService::generate_unique_slug(pool, base_slug, &site_ids).await?;
-
-        // Create content record as Draft, no scheduling
-        let content_id = ContentService::create_content(
-            pool,
+        let new_slug =
+            ContentService::generate_unique_slug(&mut *conn, base_slug, &site_ids).await?;
+        let environment_id = Content::find_by_id(&mut *conn, source.content_id)
+            .await?
+            .environment_id;
+
+        // Create content record as Draft in the source's environment, no scheduling
+        let content_id = ContentService::insert_content(
+            &mut *conn,
             "blog",
             Some(&new_slug),
             &ContentStatus::Draft,
             &site_ids,
             None,
             None,
+            Some(environment_id),
         )
         .await?;
 
@@ -390,15 +466,15 @@ impl Blog {
         .bind(source.cover_image_id)
         .bind(source.header_image_id)
         .bind(source.allow_comments)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
         // Copy localizations
         let localizations =
-            ContentLocalization::find_all_for_content(pool, source.content_id).await?;
+            ContentLocalization::find_all_for_content(&mut *conn, source.content_id).await?;
         for loc in &localizations {
             ContentLocalization::create(
-                pool,
+                &mut *conn,
                 content_id,
                 loc.locale_id,
                 &loc.title,
@@ -425,7 +501,7 @@ impl Blog {
             "#,
         )
         .bind(content_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(blog)
@@ -467,4 +543,11 @@ mod tests {
         assert!(json.contains("\"author\":\"John Doe\""));
         assert!(json.contains("\"is_featured\":true"));
     }
+
+    #[test]
+    fn test_public_path_uses_prefix() {
+        assert_eq!(Blog::public_path("/blog", "hello"), "/blog/hello");
+        assert_eq!(Blog::public_path("/news/", "hello"), "/news/hello");
+        assert_eq!(Blog::public_path("/", "hello"), "/hello");
+    }
 }
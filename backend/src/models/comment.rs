@@ -0,0 +1,253 @@
+//! Comment model
+//!
+//! Threaded reader comments on blog posts, moderated per site.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Moderation state of a comment
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Default, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "comment_status", rename_all = "lowercase")]
+pub enum CommentStatus {
+    /// Waiting in the moderation queue
+    #[default]
+    Pending,
+    /// Publicly visible
+    Approved,
+    Spam,
+    /// Hidden but kept for the record
+    Deleted,
+}
+
+/// Comment model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Comment {
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    pub site_id: Uuid,
+    pub locale_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub author_url: Option<String>,
+    pub body: String,
+    pub status: CommentStatus,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub moderated_by: Option<Uuid>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Comment {
+    /// Find a comment by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let comment = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, blog_id, site_id, locale_id, parent_id, author_name, author_email,
+                   author_url, body, status, ip_address, user_agent, moderated_by,
+                   moderated_at, created_at, updated_at
+            FROM comments
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Comment with ID {} not found", id)))?;
+
+        Ok(comment)
+    }
+
+    /// Approved comments of a blog post, oldest first, optionally for one locale
+    pub async fn find_approved_for_blog(
+        pool: &PgPool,
+        blog_id: Uuid,
+        locale_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, ApiError> {
+        let comments = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, blog_id, site_id, locale_id, parent_id, author_name, author_email,
+                   author_url, body, status, ip_address, user_agent, moderated_by,
+                   moderated_at, created_at, updated_at
+            FROM comments
+            WHERE blog_id = $1 AND status = 'approved'
+              AND ($2::uuid IS NULL OR locale_id = $2)
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(blog_id)
+        .bind(locale_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(comments)
+    }
+
+    /// Moderation queue of a site, newest first (paginated)
+    pub async fn find_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        status: Option<CommentStatus>,
+        blog_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let comments = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, blog_id, site_id, locale_id, parent_id, author_name, author_email,
+                   author_url, body, status, ip_address, user_agent, moderated_by,
+                   moderated_at, created_at, updated_at
+            FROM comments
+            WHERE site_id = $1
+              AND ($2::comment_status IS NULL OR status = $2)
+              AND ($3::uuid IS NULL OR blog_id = $3)
+            ORDER BY created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(site_id)
+        .bind(status)
+        .bind(blog_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(comments)
+    }
+
+    /// Count comments of a site matching the queue filters
+    pub async fn count_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        status: Option<CommentStatus>,
+        blog_id: Option<Uuid>,
+    ) -> Result<i64, ApiError> {
+        let row: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM comments
+            WHERE site_id = $1
+              AND ($2::comment_status IS NULL OR status = $2)
+              AND ($3::uuid IS NULL OR blog_id = $3)
+            "#,
+        )
+        .bind(site_id)
+        .bind(status)
+        .bind(blog_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Store a submitted comment (always starts as pending)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        blog_id: Uuid,
+        site_id: Uuid,
+        locale_id: Option<Uuid>,
+        parent_id: Option<Uuid>,
+        author_name: &str,
+        author_email: Option<&str>,
+        author_url: Option<&str>,
+        body: &str,
+        ip_address: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<Self, ApiError> {
+        let comment = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO comments (blog_id, site_id, locale_id, parent_id, author_name,
+                                  author_email, author_url, body, ip_address, user_agent)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id, blog_id, site_id, locale_id, parent_id, author_name, author_email,
+                      author_url, body, status, ip_address, user_agent, moderated_by,
+                      moderated_at, created_at, updated_at
+            "#,
+        )
+        .bind(blog_id)
+        .bind(site_id)
+        .bind(locale_id)
+        .bind(parent_id)
+        .bind(author_name)
+        .bind(author_email)
+        .bind(author_url)
+        .bind(body)
+        .bind(ip_address)
+        .bind(user_agent)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(comment)
+    }
+
+    /// Move a comment to another moderation state
+    pub async fn update_status(
+        pool: &PgPool,
+        id: Uuid,
+        status: CommentStatus,
+        moderated_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let comment = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE comments
+            SET status = $2, moderated_by = $3, moderated_at = NOW()
+            WHERE id = $1
+            RETURNING id, blog_id, site_id, locale_id, parent_id, author_name, author_email,
+                      author_url, body, status, ip_address, user_agent, moderated_by,
+                      moderated_at, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(moderated_by)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Comment with ID {} not found", id)))?;
+
+        Ok(comment)
+    }
+
+    /// Permanently remove a comment and its replies
+    pub async fn purge(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM comments WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Comment with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_status_default() {
+        assert_eq!(CommentStatus::default(), CommentStatus::Pending);
+    }
+
+    #[test]
+    fn test_comment_status_serialization() {
+        let json = serde_json::to_string(&CommentStatus::Spam).unwrap();
+        assert_eq!(json, "\"Spam\"");
+        let status: CommentStatus = serde_json::from_str("\"Approved\"").unwrap();
+        assert_eq!(status, CommentStatus::Approved);
+    }
+}
@@ -46,7 +46,7 @@ pub struct Content {
     pub published_at: Option<DateTime<Utc>>,
     pub publish_start: Option<DateTime<Utc>>,
     pub publish_end: Option<DateTime<Utc>>,
-    pub current_version: i16,
+    pub current_version: i32,
     pub is_global: bool,
     pub created_by: Option<Uuid>,
     pub updated_by: Option<Uuid>,
@@ -76,6 +76,30 @@ pub struct ContentLocalization {
 }
 
 impl Content {
+    /// Strong entity tag for optimistic concurrency.
+    ///
+    /// `current_version` moves on every recorded edit (including localizations
+    /// and blocks) and `updated_at` on every write to the content row itself.
+    pub fn etag(&self) -> String {
+        format!(
+            "\"{}-{}\"",
+            self.current_version,
+            self.updated_at.timestamp_micros()
+        )
+    }
+
+    /// Bump `updated_at` so that the entity tag changes.
+    ///
+    /// Uses the wall clock rather than the transaction start, so the new tag
+    /// differs from one read earlier in the same transaction.
+    pub async fn touch<'e>(executor: impl sqlx::PgExecutor<'e>, id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("UPDATE contents SET updated_at = clock_timestamp() WHERE id = $1")
+            .bind(id)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
     /// Find the site IDs associated with a content item (via content_sites junction table)
     pub async fn find_site_ids(pool: &PgPool, content_id: Uuid) -> Result<Vec<Uuid>, ApiError> {
         let rows: Vec<(Uuid,)> =
@@ -88,7 +112,10 @@ impl Content {
     }
 
     /// Find content by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
         let content = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, entity_type_id, environment_id, slug, status, published_at,
@@ -100,14 +127,58 @@ impl Content {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Content with ID {} not found", id)))?;
+
+        Ok(content)
+    }
+
+    /// Find content by ID and lock its row until the transaction ends
+    pub async fn find_for_update<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let content = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, entity_type_id, environment_id, slug, status, published_at,
+                   publish_start, publish_end, current_version, is_global,
+                   created_by, updated_by, is_deleted, deleted_at, deleted_by,
+                   created_at, updated_at
+            FROM contents
+            WHERE id = $1 AND is_deleted = FALSE
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Content with ID {} not found", id)))?;
 
         Ok(content)
     }
 
-    /// Find content by slug within a site
+    /// Find soft-deleted content by ID
+    pub async fn find_deleted(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let content = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, entity_type_id, environment_id, slug, status, published_at,
+                   publish_start, publish_end, current_version, is_global,
+                   created_by, updated_by, is_deleted, deleted_at, deleted_by,
+                   created_at, updated_at
+            FROM contents
+            WHERE id = $1 AND is_deleted = TRUE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Deleted content with ID {} not found", id)))?;
+
+        Ok(content)
+    }
+
+    /// Find live content by slug within a site (site-specific slugs take precedence)
     pub async fn find_by_slug(pool: &PgPool, site_id: Uuid, slug: &str) -> Result<Self, ApiError> {
         let content = sqlx::query_as::<_, Self>(
             r#"
@@ -117,7 +188,10 @@ impl Content {
                    c.created_at, c.updated_at
             FROM contents c
             INNER JOIN content_sites cs ON c.id = cs.content_id
-            WHERE cs.site_id = $1 AND c.slug = $2 AND c.is_deleted = FALSE
+            WHERE cs.site_id = $1
+              AND COALESCE(cs.site_specific_slug, c.slug) = $2
+              AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
             "#,
         )
         .bind(site_id)
@@ -161,8 +235,8 @@ impl ContentLocalization {
     }
 
     /// Find all localizations for content
-    pub async fn find_all_for_content(
-        pool: &PgPool,
+    pub async fn find_all_for_content<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
         content_id: Uuid,
     ) -> Result<Vec<Self>, ApiError> {
         let localizations = sqlx::query_as::<_, Self>(
@@ -175,14 +249,17 @@ impl ContentLocalization {
             "#,
         )
         .bind(content_id)
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(localizations)
     }
 
     /// Find localization by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
         let localization = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, content_id, locale_id, title, subtitle, excerpt, body,
@@ -193,7 +270,7 @@ impl ContentLocalization {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Localization with ID {} not found", id)))?;
 
@@ -202,8 +279,8 @@ impl ContentLocalization {
 
     /// Create a new localization
     #[allow(clippy::too_many_arguments)]
-    pub async fn create(
-        pool: &PgPool,
+    pub async fn create<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
         content_id: Uuid,
         locale_id: Uuid,
         title: &str,
@@ -231,7 +308,7 @@ impl ContentLocalization {
         .bind(body)
         .bind(meta_title)
         .bind(meta_description)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(localization)
@@ -239,8 +316,8 @@ impl ContentLocalization {
 
     /// Update a localization
     #[allow(clippy::too_many_arguments)]
-    pub async fn update(
-        pool: &PgPool,
+    pub async fn update<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
         id: Uuid,
         title: Option<&str>,
         subtitle: Option<&str>,
@@ -275,17 +352,17 @@ impl ContentLocalization {
         .bind(meta_title)
         .bind(meta_description)
         .bind(translation_status)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(localization)
     }
 
     /// Delete a localization
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+    pub async fn delete<'e>(executor: impl sqlx::PgExecutor<'e>, id: Uuid) -> Result<(), ApiError> {
         let result = sqlx::query("DELETE FROM content_localizations WHERE id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(executor)
             .await?;
 
         if result.rows_affected() == 0 {
@@ -321,4 +398,32 @@ mod tests {
         let status = TranslationStatus::default();
         assert_eq!(status, TranslationStatus::Pending);
     }
+
+    #[test]
+    fn test_etag_tracks_version_and_timestamp() {
+        let updated_at = DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap();
+        let mut content = Content {
+            id: Uuid::new_v4(),
+            entity_type_id: Uuid::new_v4(),
+            environment_id: Uuid::new_v4(),
+            slug: None,
+            status: ContentStatus::Draft,
+            published_at: None,
+            publish_start: None,
+            publish_end: None,
+            current_version: 3,
+            is_global: false,
+            created_by: None,
+            updated_by: None,
+            is_deleted: false,
+            deleted_at: None,
+            deleted_by: None,
+            created_at: updated_at,
+            updated_at,
+        };
+        assert_eq!(content.etag(), "\"3-1700000000123456\"");
+
+        content.current_version = 4;
+        assert_eq!(content.etag(), "\"4-1700000000123456\"");
+    }
 }
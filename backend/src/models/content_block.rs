@@ -0,0 +1,257 @@
+//! Content block model
+//!
+//! Structured, typed blocks belonging to a content localization.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Block type enum matching PostgreSQL
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "block_type", rename_all = "lowercase")]
+pub enum BlockType {
+    Paragraph,
+    Heading,
+    Image,
+    List,
+    Code,
+    Quote,
+    Embed,
+    Divider,
+    Table,
+}
+
+/// Content block model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContentBlock {
+    pub id: Uuid,
+    pub content_localization_id: Uuid,
+    pub block_type: BlockType,
+    pub block_order: i16,
+    pub block_data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ContentBlock {
+    /// Find all blocks for a localization, in display order
+    pub async fn find_for_localization(
+        pool: &PgPool,
+        localization_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
+        let blocks = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_localization_id, block_type, block_order, block_data,
+                   created_at, updated_at
+            FROM content_blocks
+            WHERE content_localization_id = $1
+            ORDER BY block_order, created_at
+            "#,
+        )
+        .bind(localization_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(blocks)
+    }
+
+    /// Find all blocks for several localizations, in display order
+    pub async fn find_for_localizations(
+        pool: &PgPool,
+        localization_ids: &[Uuid],
+    ) -> Result<Vec<Self>, ApiError> {
+        let blocks = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_localization_id, block_type, block_order, block_data,
+                   created_at, updated_at
+            FROM content_blocks
+            WHERE content_localization_id = ANY($1)
+            ORDER BY content_localization_id, block_order, created_at
+            "#,
+        )
+        .bind(localization_ids)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(blocks)
+    }
+
+    /// Find a block by ID
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let block = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_localization_id, block_type, block_order, block_data,
+                   created_at, updated_at
+            FROM content_blocks
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Content block with ID {} not found", id)))?;
+
+        Ok(block)
+    }
+
+    /// Create a block. Without a position the block is appended; with one,
+    /// blocks at or after that position move down by one.
+    pub async fn create(
+        conn: &mut PgConnection,
+        localization_id: Uuid,
+        block_type: BlockType,
+        block_data: &serde_json::Value,
+        position: Option<i16>,
+    ) -> Result<Self, ApiError> {
+        let mut tx = conn.begin().await?;
+
+        // Serialize concurrent inserts into the same localization
+        sqlx::query("SELECT id FROM content_localizations WHERE id = $1 FOR UPDATE")
+            .bind(localization_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| {
+                ApiError::NotFound(format!(
+                    "Localization with ID {} not found",
+                    localization_id
+                ))
+            })?;
+
+        let block_order = match position {
+            Some(position) => {
+                sqlx::query(
+                    "UPDATE content_blocks SET block_order = block_order + 1 \
+                     WHERE content_localization_id = $1 AND block_order >= $2",
+                )
+                .bind(localization_id)
+                .bind(position)
+                .execute(&mut *tx)
+                .await?;
+                position
+            }
+            None => {
+                sqlx::query_scalar(
+                    "SELECT COALESCE(MAX(block_order) + 1, 0)::SMALLINT FROM content_blocks \
+                     WHERE content_localization_id = $1",
+                )
+                .bind(localization_id)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+
+        let block = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO content_blocks (content_localization_id, block_type, block_order, block_data)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, content_localization_id, block_type, block_order, block_data,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(localization_id)
+        .bind(block_type)
+        .bind(block_order)
+        .bind(block_data)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(block)
+    }
+
+    /// Replace a block's type and data
+    pub async fn update<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+        block_type: BlockType,
+        block_data: &serde_json::Value,
+    ) -> Result<Self, ApiError> {
+        let block = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE content_blocks
+            SET block_type = $2, block_data = $3
+            WHERE id = $1
+            RETURNING id, content_localization_id, block_type, block_order, block_data,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(block_type)
+        .bind(block_data)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Content block with ID {} not found", id)))?;
+
+        Ok(block)
+    }
+
+    /// Batch-reorder blocks of a localization within a single transaction
+    pub async fn reorder_for_localization(
+        conn: &mut PgConnection,
+        localization_id: Uuid,
+        items: Vec<(Uuid, i16)>,
+    ) -> Result<(), ApiError> {
+        let mut tx = conn.begin().await?;
+
+        for (id, block_order) in &items {
+            let result = sqlx::query(
+                "UPDATE content_blocks SET block_order = $1 WHERE id = $2 AND content_localization_id = $3",
+            )
+            .bind(block_order)
+            .bind(id)
+            .bind(localization_id)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(ApiError::NotFound(format!(
+                    "Content block with ID {} not found for localization {}",
+                    id, localization_id
+                )));
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete a block
+    pub async fn delete<'e>(executor: impl sqlx::PgExecutor<'e>, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM content_blocks WHERE id = $1")
+            .bind(id)
+            .execute(executor)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Content block with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_type_serialization() {
+        assert_eq!(
+            serde_json::to_string(&BlockType::Paragraph).unwrap(),
+            "\"Paragraph\""
+        );
+        let parsed: BlockType = serde_json::from_str("\"Divider\"").unwrap();
+        assert_eq!(parsed, BlockType::Divider);
+    }
+}
@@ -0,0 +1,448 @@
+//! Content release model
+//!
+//! Named sets of content status and navigation changes that are published
+//! together. Each item keeps the state it replaced so that a published release
+//! can be rolled back.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+use crate::models::content::ContentStatus;
+
+/// Lifecycle state of a release
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "content_release_status", rename_all = "snake_case")]
+pub enum ContentReleaseStatus {
+    /// Being assembled
+    Draft,
+    /// Published by the scheduler at `scheduled_at`
+    Scheduled,
+    Published,
+    /// Published, then reverted to the pre-release state
+    RolledBack,
+    /// The scheduler could not apply it; see `error`
+    Failed,
+}
+
+impl ContentReleaseStatus {
+    /// Whether items can still be added, removed or the release published
+    pub fn is_pending(self) -> bool {
+        matches!(self, Self::Draft | Self::Scheduled | Self::Failed)
+    }
+}
+
+/// Content release model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContentRelease {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: ContentReleaseStatus,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub published_by: Option<Uuid>,
+    pub rolled_back_at: Option<DateTime<Utc>>,
+    pub rolled_back_by: Option<Uuid>,
+    pub error: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Item of a release, with the entity it points at
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContentReleaseItem {
+    pub id: Uuid,
+    pub release_id: Uuid,
+    pub content_id: Option<Uuid>,
+    pub navigation_item_id: Option<Uuid>,
+    pub target_status: Option<ContentStatus>,
+    pub target_active: Option<bool>,
+    pub previous_status: Option<ContentStatus>,
+    pub previous_published_at: Option<DateTime<Utc>>,
+    pub previous_publish_start: Option<DateTime<Utc>>,
+    pub previous_publish_end: Option<DateTime<Utc>>,
+    pub previous_active: Option<bool>,
+    /// Content version right after the release was published
+    pub published_version: Option<i32>,
+    /// Set when a rollback left the item alone because it changed after the release
+    pub rollback_skipped: bool,
+    /// Entity type name of the content (`blog`, `page`, ...)
+    pub entity_type: Option<String>,
+    /// Blog, page, CV entry or legal document ID of the content
+    pub entity_id: Option<Uuid>,
+    pub slug: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+const RELEASE_COLUMNS: &str = r#"
+    id, site_id, name, description, status, scheduled_at, published_at, published_by,
+    rolled_back_at, rolled_back_by, error, created_by, created_at, updated_at
+"#;
+
+impl ContentRelease {
+    /// Find a release by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            "SELECT {RELEASE_COLUMNS} FROM content_releases WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Release with ID {} not found", id)))?;
+
+        Ok(release)
+    }
+
+    /// Find a release by ID and lock it for the rest of the transaction
+    pub async fn find_for_update<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            "SELECT {RELEASE_COLUMNS} FROM content_releases WHERE id = $1 FOR UPDATE"
+        ))
+        .bind(id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Release with ID {} not found", id)))?;
+
+        Ok(release)
+    }
+
+    /// Find the releases of a site, newest first (paginated)
+    pub async fn find_all_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        status: Option<ContentReleaseStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let releases = sqlx::query_as::<_, Self>(&format!(
+            r#"
+            SELECT {RELEASE_COLUMNS}
+            FROM content_releases
+            WHERE site_id = $1 AND ($2::content_release_status IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#
+        ))
+        .bind(site_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(releases)
+    }
+
+    /// Count the releases of a site
+    pub async fn count_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        status: Option<ContentReleaseStatus>,
+    ) -> Result<i64, ApiError> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM content_releases
+            WHERE site_id = $1 AND ($2::content_release_status IS NULL OR status = $2)
+            "#,
+        )
+        .bind(site_id)
+        .bind(status)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// IDs of scheduled releases whose time has come, oldest first
+    pub async fn find_due_ids<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+    ) -> Result<Vec<Uuid>, ApiError> {
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM content_releases
+            WHERE status = 'scheduled' AND scheduled_at <= NOW()
+            ORDER BY scheduled_at, created_at
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Create a draft release
+    pub async fn create(
+        pool: &PgPool,
+        site_id: Uuid,
+        name: &str,
+        description: Option<&str>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            r#"
+            INSERT INTO content_releases (site_id, name, description, created_by)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {RELEASE_COLUMNS}
+            "#
+        ))
+        .bind(site_id)
+        .bind(name)
+        .bind(description)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(release)
+    }
+
+    /// Update name and description (COALESCE keeps fields that are not passed)
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            r#"
+            UPDATE content_releases
+            SET name = COALESCE($2, name),
+                description = COALESCE($3, description),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {RELEASE_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(name)
+        .bind(description)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Release with ID {} not found", id)))?;
+
+        Ok(release)
+    }
+
+    /// Schedule a release, or move it back to draft when `scheduled_at` is `None`
+    pub async fn set_schedule(
+        pool: &PgPool,
+        id: Uuid,
+        scheduled_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            r#"
+            UPDATE content_releases
+            SET status = CASE WHEN $2::timestamptz IS NULL THEN 'draft' ELSE 'scheduled' END::content_release_status,
+                scheduled_at = $2,
+                error = NULL,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {RELEASE_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(scheduled_at)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Release with ID {} not found", id)))?;
+
+        Ok(release)
+    }
+
+    /// Mark a release as published
+    pub async fn mark_published<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+        published_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            r#"
+            UPDATE content_releases
+            SET status = 'published', published_at = NOW(), published_by = $2,
+                error = NULL, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {RELEASE_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(published_by)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(release)
+    }
+
+    /// Mark a release as rolled back
+    pub async fn mark_rolled_back<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+        rolled_back_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let release = sqlx::query_as::<_, Self>(&format!(
+            r#"
+            UPDATE content_releases
+            SET status = 'rolled_back', rolled_back_at = NOW(), rolled_back_by = $2,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {RELEASE_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(rolled_back_by)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(release)
+    }
+
+    /// Mark a scheduled release as failed
+    pub async fn mark_failed<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+        error: &str,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE content_releases
+            SET status = 'failed', error = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete a release (items cascade)
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM content_releases WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Release with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl ContentReleaseItem {
+    /// Find the items of a release, content first, in the order they were added
+    pub async fn find_for_release<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        release_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
+        let items = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT ri.id, ri.release_id, ri.content_id, ri.navigation_item_id,
+                   ri.target_status, ri.target_active, ri.previous_status,
+                   ri.previous_published_at, ri.previous_publish_start,
+                   ri.previous_publish_end, ri.previous_active,
+                   ri.published_version, ri.rollback_skipped,
+                   et.name AS entity_type,
+                   COALESCE(b.id, p.id, cv.id, ld.id) AS entity_id,
+                   c.slug, ri.created_at
+            FROM content_release_items ri
+            LEFT JOIN contents c ON c.id = ri.content_id
+            LEFT JOIN entity_types et ON et.id = c.entity_type_id
+            LEFT JOIN blogs b ON b.content_id = c.id
+            LEFT JOIN pages p ON p.content_id = c.id
+            LEFT JOIN cv_entries cv ON cv.content_id = c.id
+            LEFT JOIN legal_documents ld ON ld.content_id = c.id
+            WHERE ri.release_id = $1
+            ORDER BY ri.content_id IS NULL, ri.created_at, ri.id
+            "#,
+        )
+        .bind(release_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Add or replace the target status of a content item in a release
+    pub async fn upsert_content(
+        pool: &PgPool,
+        release_id: Uuid,
+        content_id: Uuid,
+        target_status: &ContentStatus,
+    ) -> Result<Uuid, ApiError> {
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO content_release_items (release_id, content_id, target_status)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (release_id, content_id)
+            DO UPDATE SET target_status = EXCLUDED.target_status
+            RETURNING id
+            "#,
+        )
+        .bind(release_id)
+        .bind(content_id)
+        .bind(target_status)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Add or replace the target visibility of a navigation item in a release
+    pub async fn upsert_navigation_item(
+        pool: &PgPool,
+        release_id: Uuid,
+        navigation_item_id: Uuid,
+        target_active: bool,
+    ) -> Result<Uuid, ApiError> {
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO content_release_items (release_id, navigation_item_id, target_active)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (release_id, navigation_item_id)
+            DO UPDATE SET target_active = EXCLUDED.target_active
+            RETURNING id
+            "#,
+        )
+        .bind(release_id)
+        .bind(navigation_item_id)
+        .bind(target_active)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Remove an item from a release
+    pub async fn delete(pool: &PgPool, release_id: Uuid, id: Uuid) -> Result<(), ApiError> {
+        let result =
+            sqlx::query("DELETE FROM content_release_items WHERE id = $1 AND release_id = $2")
+                .bind(id)
+                .bind(release_id)
+                .execute(pool)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Release item with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+}
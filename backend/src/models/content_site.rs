@@ -0,0 +1,189 @@
+//! Content site model
+//!
+//! Rows of the `content_sites` junction: which sites a content item appears on,
+//! and per-site overrides for slug, featured flag and ordering. Sites that
+//! created the content are owners; sites it was shared with later are not.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Content site assignment (database row plus the slug in effect on the site)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContentSite {
+    pub content_id: Uuid,
+    pub site_id: Uuid,
+    pub is_owner: bool,
+    pub is_featured: bool,
+    pub display_order: Option<i16>,
+    pub site_specific_slug: Option<String>,
+    /// `site_specific_slug` if set, else the content's own slug
+    pub slug: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ContentSite {
+    /// All site assignments of a content item, owners first
+    pub async fn find_for_content(pool: &PgPool, content_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let rows = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT cs.content_id, cs.site_id, cs.is_owner, cs.is_featured, cs.display_order,
+                   cs.site_specific_slug::text AS site_specific_slug,
+                   COALESCE(cs.site_specific_slug, c.slug)::text AS slug, cs.created_at
+            FROM content_sites cs
+            INNER JOIN contents c ON c.id = cs.content_id
+            WHERE cs.content_id = $1
+            ORDER BY cs.is_owner DESC, cs.created_at ASC
+            "#,
+        )
+        .bind(content_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Find the assignment of a content item to one site
+    pub async fn find(pool: &PgPool, content_id: Uuid, site_id: Uuid) -> Result<Self, ApiError> {
+        let row = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT cs.content_id, cs.site_id, cs.is_owner, cs.is_featured, cs.display_order,
+                   cs.site_specific_slug::text AS site_specific_slug,
+                   COALESCE(cs.site_specific_slug, c.slug)::text AS slug, cs.created_at
+            FROM content_sites cs
+            INNER JOIN contents c ON c.id = cs.content_id
+            WHERE cs.content_id = $1 AND cs.site_id = $2
+            "#,
+        )
+        .bind(content_id)
+        .bind(site_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Content {} is not shared with site {}",
+                content_id, site_id
+            ))
+        })?;
+
+        Ok(row)
+    }
+
+    /// Share a content item with another site (as a non-owner)
+    pub async fn create(
+        pool: &PgPool,
+        content_id: Uuid,
+        site_id: Uuid,
+        site_specific_slug: Option<&str>,
+        is_featured: bool,
+        display_order: Option<i16>,
+    ) -> Result<Self, ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO content_sites (content_id, site_id, is_owner, is_featured,
+                                       display_order, site_specific_slug)
+            VALUES ($1, $2, FALSE, $3, $4, $5)
+            "#,
+        )
+        .bind(content_id)
+        .bind(site_id)
+        .bind(is_featured)
+        .bind(display_order)
+        .bind(site_specific_slug)
+        .execute(pool)
+        .await?;
+
+        Self::find(pool, content_id, site_id).await
+    }
+
+    /// Update per-site settings; `None` keeps a value, `Some(None)` clears it
+    pub async fn update(
+        pool: &PgPool,
+        content_id: Uuid,
+        site_id: Uuid,
+        site_specific_slug: Option<Option<&str>>,
+        is_featured: Option<bool>,
+        display_order: Option<Option<i16>>,
+    ) -> Result<Self, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE content_sites
+            SET site_specific_slug = CASE WHEN $3 THEN $4 ELSE site_specific_slug END,
+                is_featured = COALESCE($5, is_featured),
+                display_order = CASE WHEN $6 THEN $7 ELSE display_order END
+            WHERE content_id = $1 AND site_id = $2
+            "#,
+        )
+        .bind(content_id)
+        .bind(site_id)
+        .bind(site_specific_slug.is_some())
+        .bind(site_specific_slug.flatten())
+        .bind(is_featured)
+        .bind(display_order.is_some())
+        .bind(display_order.flatten())
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Content {} is not shared with site {}",
+                content_id, site_id
+            )));
+        }
+
+        Self::find(pool, content_id, site_id).await
+    }
+
+    /// Remove a content item from a site
+    pub async fn delete(pool: &PgPool, content_id: Uuid, site_id: Uuid) -> Result<(), ApiError> {
+        let result =
+            sqlx::query("DELETE FROM content_sites WHERE content_id = $1 AND site_id = $2")
+                .bind(content_id)
+                .bind(site_id)
+                .execute(pool)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Content {} is not shared with site {}",
+                content_id, site_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Whether another live content item of the same type, in the same environment as
+    /// `exclude_content_id`, already uses `slug` on a site
+    pub async fn slug_in_use(
+        pool: &PgPool,
+        site_id: Uuid,
+        entity_type_id: Uuid,
+        slug: &str,
+        exclude_content_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM contents c
+                INNER JOIN content_sites cs ON c.id = cs.content_id
+                WHERE cs.site_id = $1 AND c.entity_type_id = $2
+                  AND COALESCE(cs.site_specific_slug, c.slug) = $3::citext
+                  AND c.id <> $4 AND c.is_deleted = FALSE
+                  AND c.environment_id = (SELECT environment_id FROM contents WHERE id = $4)
+            )
+            "#,
+        )
+        .bind(site_id)
+        .bind(entity_type_id)
+        .bind(slug)
+        .bind(exclude_content_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(exists)
+    }
+}
@@ -0,0 +1,167 @@
+//! Content version model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Full snapshot of a content item at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContentVersion {
+    pub id: Uuid,
+    pub content_id: Uuid,
+    pub version_number: i32,
+    pub snapshot: serde_json::Value,
+    pub change_summary: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ContentVersion {
+    /// List versions for a content item, newest first (paginated)
+    pub async fn find_for_content(
+        pool: &PgPool,
+        content_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let versions = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_id, version_number, snapshot, change_summary, created_by, created_at
+            FROM content_versions
+            WHERE content_id = $1
+            ORDER BY version_number DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(content_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(versions)
+    }
+
+    /// Count versions for a content item
+    pub async fn count_for_content(pool: &PgPool, content_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM content_versions WHERE content_id = $1")
+                .bind(content_id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(row.0)
+    }
+
+    /// Find a specific version of a content item
+    pub async fn find_by_number(
+        pool: &PgPool,
+        content_id: Uuid,
+        version_number: i32,
+    ) -> Result<Self, ApiError> {
+        let version = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_id, version_number, snapshot, change_summary, created_by, created_at
+            FROM content_versions
+            WHERE content_id = $1 AND version_number = $2
+            "#,
+        )
+        .bind(content_id)
+        .bind(version_number)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Version {} not found for content {}",
+                version_number, content_id
+            ))
+        })?;
+
+        Ok(version)
+    }
+
+    /// Insert the next version for a content item and bump `contents.current_version`.
+    ///
+    /// Must run inside a transaction: the content row is locked so concurrent
+    /// writers cannot allocate the same version number.
+    pub async fn create_next(
+        conn: &mut PgConnection,
+        content_id: Uuid,
+        snapshot: &serde_json::Value,
+        change_summary: Option<&str>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let current_version: i32 =
+            sqlx::query_scalar("SELECT current_version FROM contents WHERE id = $1 FOR UPDATE")
+                .bind(content_id)
+                .fetch_optional(&mut *conn)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!("Content with ID {} not found", content_id))
+                })?;
+
+        let latest: Option<i32> = sqlx::query_scalar(
+            "SELECT MAX(version_number) FROM content_versions WHERE content_id = $1",
+        )
+        .bind(content_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let version_number = next_version_number(latest, current_version);
+
+        let version = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO content_versions (content_id, version_number, snapshot, change_summary, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, content_id, version_number, snapshot, change_summary, created_by, created_at
+            "#,
+        )
+        .bind(content_id)
+        .bind(version_number)
+        .bind(snapshot)
+        .bind(change_summary)
+        .bind(created_by)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        sqlx::query("UPDATE contents SET current_version = $2 WHERE id = $1")
+            .bind(content_id)
+            .bind(version_number)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(version)
+    }
+}
+
+/// Pick the number for the next version.
+///
+/// Content created before versioning existed has `current_version = 1` but no
+/// rows in `content_versions`; its first recorded snapshot becomes version 1.
+fn next_version_number(latest: Option<i32>, current_version: i32) -> i32 {
+    match latest {
+        Some(n) => n + 1,
+        None => current_version.max(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_version_number_first_snapshot() {
+        assert_eq!(next_version_number(None, 1), 1);
+        assert_eq!(next_version_number(None, 0), 1);
+    }
+
+    #[test]
+    fn test_next_version_number_increments() {
+        assert_eq!(next_version_number(Some(1), 1), 2);
+        assert_eq!(next_version_number(Some(7), 7), 8);
+        assert_eq!(next_version_number(Some(32767), 32767), 32768);
+    }
+}
@@ -2,7 +2,7 @@
 
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::dto::cv::{
@@ -245,7 +245,7 @@ impl CvEntry {
     ) -> Result<i64, ApiError> {
         let row: (i64,) = if let Some(ref et) = entry_type {
             sqlx::query_as(
-                "SELECT COUNT(*) FROM cv_entries e INNER JOIN contents c ON e.content_id = c.id INNER JOIN content_sites cs ON c.id = cs.content_id WHERE cs.site_id = $1 AND e.entry_type = $2 AND c.is_deleted = FALSE"
+                "SELECT COUNT(*) FROM cv_entries e INNER JOIN contents c ON e.content_id = c.id INNER JOIN content_sites cs ON c.id = cs.content_id WHERE cs.site_id = $1 AND e.entry_type = $2 AND c.is_deleted = FALSE AND c.environment_id = (SELECT id FROM environments WHERE is_default)"
             )
             .bind(site_id)
             .bind(et)
@@ -253,7 +253,7 @@ impl CvEntry {
             .await?
         } else {
             sqlx::query_as(
-                "SELECT COUNT(*) FROM cv_entries e INNER JOIN contents c ON e.content_id = c.id INNER JOIN content_sites cs ON c.id = cs.content_id WHERE cs.site_id = $1 AND c.is_deleted = FALSE"
+                "SELECT COUNT(*) FROM cv_entries e INNER JOIN contents c ON e.content_id = c.id INNER JOIN content_sites cs ON c.id = cs.content_id WHERE cs.site_id = $1 AND c.is_deleted = FALSE AND c.environment_id = (SELECT id FROM environments WHERE is_default)"
             )
             .bind(site_id)
             .fetch_one(pool)
@@ -280,6 +280,7 @@ impl CvEntry {
                 INNER JOIN contents c ON e.content_id = c.id
                 INNER JOIN content_sites cs ON c.id = cs.content_id
                 WHERE cs.site_id = $1 AND e.entry_type = $2 AND c.is_deleted = FALSE
+                  AND c.environment_id = (SELECT id FROM environments WHERE is_default)
                 ORDER BY e.display_order ASC, e.start_date DESC
                 LIMIT $3 OFFSET $4
                 "#,
@@ -300,6 +301,7 @@ impl CvEntry {
                 INNER JOIN contents c ON e.content_id = c.id
                 INNER JOIN content_sites cs ON c.id = cs.content_id
                 WHERE cs.site_id = $1 AND c.is_deleted = FALSE
+                  AND c.environment_id = (SELECT id FROM environments WHERE is_default)
                 ORDER BY e.entry_type ASC, e.display_order ASC, e.start_date DESC
                 LIMIT $2 OFFSET $3
                 "#,
@@ -315,7 +317,10 @@ impl CvEntry {
     }
 
     /// Find CV entry by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
         let entry = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, content_id, company, company_url, company_logo_id,
@@ -326,7 +331,7 @@ impl CvEntry {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("CV entry with ID {} not found", id)))?;
 
@@ -334,15 +339,19 @@ impl CvEntry {
     }
 
     /// Create a CV entry with associated content
-    pub async fn create(pool: &PgPool, req: CreateCvEntryRequest) -> Result<Self, ApiError> {
-        let content_id = ContentService::create_content(
-            pool,
+    pub async fn create(
+        conn: &mut PgConnection,
+        req: CreateCvEntryRequest,
+    ) -> Result<Self, ApiError> {
+        let content_id = ContentService::insert_content(
+            &mut *conn,
             "cv_entry",
             None,
             &req.status,
             &req.site_ids,
             None,
             None,
+            None,
         )
         .await?;
 
@@ -366,7 +375,7 @@ impl CvEntry {
         .bind(req.is_current)
         .bind(&req.entry_type)
         .bind(req.display_order)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(entry)
@@ -374,15 +383,22 @@ impl CvEntry {
 
     /// Update a CV entry
     pub async fn update(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         id: Uuid,
         req: UpdateCvEntryRequest,
     ) -> Result<Self, ApiError> {
-        let existing = Self::find_by_id(pool, id).await?;
+        let existing = Self::find_by_id(&mut *conn, id).await?;
 
         if let Some(content_id) = existing.content_id {
-            ContentService::update_content(pool, content_id, None, req.status.as_ref(), None, None)
-                .await?;
+            ContentService::update_content(
+                &mut *conn,
+                content_id,
+                None,
+                req.status.as_ref(),
+                None,
+                None,
+            )
+            .await?;
         }
 
         let entry = sqlx::query_as::<_, Self>(
@@ -414,7 +430,7 @@ impl CvEntry {
         .bind(req.is_current)
         .bind(&req.entry_type)
         .bind(req.display_order)
-        .fetch_optional(pool)
+        .fetch_optional(&mut *conn)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("CV entry with ID {} not found", id)))?;
 
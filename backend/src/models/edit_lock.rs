@@ -0,0 +1,157 @@
+//! Edit lock model
+//!
+//! Advisory, auto-expiring locks that show who is currently editing a content item.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Advisory lock on one content item, held until released or `expires_at` passes
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EditLock {
+    pub content_id: Uuid,
+    pub holder_id: Uuid,
+    pub holder_clerk_user_id: Option<String>,
+    pub holder_name: Option<String>,
+    pub acquired_at: DateTime<Utc>,
+    pub heartbeat_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl EditLock {
+    /// Whether the lock has not expired yet
+    pub fn is_active(&self) -> bool {
+        self.expires_at > Utc::now()
+    }
+
+    /// Find the unexpired lock on a content item, if any
+    pub async fn find_active(pool: &PgPool, content_id: Uuid) -> Result<Option<Self>, ApiError> {
+        let lock = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT content_id, holder_id, holder_clerk_user_id, holder_name,
+                   acquired_at, heartbeat_at, expires_at
+            FROM content_edit_locks
+            WHERE content_id = $1 AND expires_at > NOW()
+            "#,
+        )
+        .bind(content_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(lock)
+    }
+
+    /// Take the lock, or extend it when the caller already holds it.
+    ///
+    /// Returns `None` when someone else holds an unexpired lock.
+    pub async fn acquire(
+        pool: &PgPool,
+        content_id: Uuid,
+        holder_id: Uuid,
+        holder_clerk_user_id: Option<&str>,
+        holder_name: Option<&str>,
+        ttl_seconds: i64,
+    ) -> Result<Option<Self>, ApiError> {
+        let lock = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO content_edit_locks
+                (content_id, holder_id, holder_clerk_user_id, holder_name, expires_at)
+            VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5))
+            ON CONFLICT (content_id) DO UPDATE
+            SET holder_id = EXCLUDED.holder_id,
+                holder_clerk_user_id = EXCLUDED.holder_clerk_user_id,
+                holder_name = CASE
+                    WHEN content_edit_locks.holder_id = EXCLUDED.holder_id
+                    THEN COALESCE(EXCLUDED.holder_name, content_edit_locks.holder_name)
+                    ELSE EXCLUDED.holder_name
+                END,
+                acquired_at = CASE
+                    WHEN content_edit_locks.holder_id = EXCLUDED.holder_id
+                         AND content_edit_locks.expires_at > NOW()
+                    THEN content_edit_locks.acquired_at
+                    ELSE NOW()
+                END,
+                heartbeat_at = NOW(),
+                expires_at = EXCLUDED.expires_at
+            WHERE content_edit_locks.holder_id = EXCLUDED.holder_id
+               OR content_edit_locks.expires_at <= NOW()
+            RETURNING content_id, holder_id, holder_clerk_user_id, holder_name,
+                      acquired_at, heartbeat_at, expires_at
+            "#,
+        )
+        .bind(content_id)
+        .bind(holder_id)
+        .bind(holder_clerk_user_id)
+        .bind(holder_name)
+        .bind(ttl_seconds as f64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(lock)
+    }
+
+    /// Extend an unexpired lock held by `holder_id`.
+    ///
+    /// Returns `None` when the caller does not hold the lock (anymore).
+    pub async fn heartbeat(
+        pool: &PgPool,
+        content_id: Uuid,
+        holder_id: Uuid,
+        ttl_seconds: i64,
+    ) -> Result<Option<Self>, ApiError> {
+        let lock = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE content_edit_locks
+            SET heartbeat_at = NOW(),
+                expires_at = NOW() + make_interval(secs => $3)
+            WHERE content_id = $1 AND holder_id = $2 AND expires_at > NOW()
+            RETURNING content_id, holder_id, holder_clerk_user_id, holder_name,
+                      acquired_at, heartbeat_at, expires_at
+            "#,
+        )
+        .bind(content_id)
+        .bind(holder_id)
+        .bind(ttl_seconds as f64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(lock)
+    }
+
+    /// Remove the lock on a content item (expired or not)
+    pub async fn release(pool: &PgPool, content_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("DELETE FROM content_edit_locks WHERE content_id = $1")
+            .bind(content_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn lock(expires_at: DateTime<Utc>) -> EditLock {
+        EditLock {
+            content_id: Uuid::new_v4(),
+            holder_id: Uuid::new_v4(),
+            holder_clerk_user_id: Some("user_abc123".to_string()),
+            holder_name: Some("Jane Editor".to_string()),
+            acquired_at: Utc::now(),
+            heartbeat_at: Utc::now(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_is_active() {
+        assert!(lock(Utc::now() + Duration::seconds(60)).is_active());
+        assert!(!lock(Utc::now() - Duration::seconds(1)).is_active());
+    }
+}
@@ -16,6 +16,21 @@ pub enum EnvironmentType {
     Production,
 }
 
+impl EnvironmentType {
+    /// Parse an environment name as used in query parameters (case-insensitive)
+    pub fn parse(name: &str) -> Result<Self, ApiError> {
+        match name.to_lowercase().as_str() {
+            "development" => Ok(Self::Development),
+            "staging" => Ok(Self::Staging),
+            "production" => Ok(Self::Production),
+            _ => Err(ApiError::Validation(format!(
+                "Invalid environment: {}",
+                name
+            ))),
+        }
+    }
+}
+
 /// Environment model
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Environment {
@@ -78,7 +93,10 @@ impl Environment {
     }
 
     /// Find environment by name
-    pub async fn find_by_name(pool: &PgPool, name: EnvironmentType) -> Result<Self, ApiError> {
+    pub async fn find_by_name<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        name: EnvironmentType,
+    ) -> Result<Self, ApiError> {
         let environment = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, name, display_name, is_default, created_at, updated_at
@@ -87,12 +105,23 @@ impl Environment {
             "#,
         )
         .bind(&name)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Environment {:?} not found", name)))?;
 
         Ok(environment)
     }
+
+    /// ID of the named environment; `None` (meaning the default environment) without a name
+    pub async fn resolve_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        name: Option<EnvironmentType>,
+    ) -> Result<Option<Uuid>, ApiError> {
+        match name {
+            Some(name) => Ok(Some(Self::find_by_name(executor, name).await?.id)),
+            None => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -111,4 +140,17 @@ mod tests {
         let env: EnvironmentType = serde_json::from_str("\"Development\"").unwrap();
         assert_eq!(env, EnvironmentType::Development);
     }
+
+    #[test]
+    fn test_environment_type_parse() {
+        assert_eq!(
+            EnvironmentType::parse("staging").unwrap(),
+            EnvironmentType::Staging
+        );
+        assert_eq!(
+            EnvironmentType::parse("Production").unwrap(),
+            EnvironmentType::Production
+        );
+        assert!(EnvironmentType::parse("qa").is_err());
+    }
 }
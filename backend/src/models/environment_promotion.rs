@@ -0,0 +1,118 @@
+//! Environment promotion model
+//!
+//! History of content promoted between environments of a site. Each row keeps
+//! the changeset that was applied.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Environment promotion model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EnvironmentPromotion {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub source_environment_id: Uuid,
+    pub target_environment_id: Uuid,
+    pub changeset: serde_json::Value,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub deleted_count: i32,
+    pub promoted_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EnvironmentPromotion {
+    /// Find a promotion by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let promotion = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, source_environment_id, target_environment_id, changeset,
+                   created_count, updated_count, deleted_count, promoted_by, created_at
+            FROM environment_promotions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Promotion with ID {} not found", id)))?;
+
+        Ok(promotion)
+    }
+
+    /// Find the promotions of a site, newest first (paginated)
+    pub async fn find_all_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let promotions = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, source_environment_id, target_environment_id, changeset,
+                   created_count, updated_count, deleted_count, promoted_by, created_at
+            FROM environment_promotions
+            WHERE site_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(site_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(promotions)
+    }
+
+    /// Count the promotions of a site
+    pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM environment_promotions WHERE site_id = $1")
+                .bind(site_id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(row.0)
+    }
+
+    /// Record an applied promotion
+    pub async fn create<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        site_id: Uuid,
+        source_environment_id: Uuid,
+        target_environment_id: Uuid,
+        changeset: &serde_json::Value,
+        counts: (i32, i32, i32),
+        promoted_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let (created, updated, deleted) = counts;
+        let promotion = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO environment_promotions (site_id, source_environment_id,
+                                                target_environment_id, changeset, created_count,
+                                                updated_count, deleted_count, promoted_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, site_id, source_environment_id, target_environment_id, changeset,
+                      created_count, updated_count, deleted_count, promoted_by, created_at
+            "#,
+        )
+        .bind(site_id)
+        .bind(source_environment_id)
+        .bind(target_environment_id)
+        .bind(changeset)
+        .bind(created)
+        .bind(updated)
+        .bind(deleted)
+        .bind(promoted_by)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(promotion)
+    }
+}
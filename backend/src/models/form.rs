@@ -0,0 +1,380 @@
+//! Form model
+//!
+//! Site-scoped form definitions (e.g. contact forms) and their stored submissions.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::form::FormField;
+use crate::errors::ApiError;
+
+/// Form model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Form {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub fields: serde_json::Value,
+    pub recipient_email: Option<String>,
+    pub retention_days: Option<i32>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Form submission model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FormSubmission {
+    pub id: Uuid,
+    pub form_id: Uuid,
+    pub site_id: Uuid,
+    pub locale_id: Option<Uuid>,
+    pub data: serde_json::Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Form {
+    /// Parsed field definitions (definitions are validated before they are stored)
+    pub fn field_definitions(&self) -> Vec<FormField> {
+        serde_json::from_value(self.fields.clone()).unwrap_or_default()
+    }
+
+    /// Find a form by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let form = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, slug, name, fields, recipient_email, retention_days,
+                   is_active, created_by, created_at, updated_at
+            FROM forms
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Form with ID {} not found", id)))?;
+
+        Ok(form)
+    }
+
+    /// Find a form by slug within a site
+    pub async fn find_by_slug(pool: &PgPool, site_id: Uuid, slug: &str) -> Result<Self, ApiError> {
+        let form = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, slug, name, fields, recipient_email, retention_days,
+                   is_active, created_by, created_at, updated_at
+            FROM forms
+            WHERE site_id = $1 AND slug = $2
+            "#,
+        )
+        .bind(site_id)
+        .bind(slug)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Form '{}' not found", slug)))?;
+
+        Ok(form)
+    }
+
+    /// Find all forms of a site (paginated)
+    pub async fn find_all_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let forms = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, slug, name, fields, recipient_email, retention_days,
+                   is_active, created_by, created_at, updated_at
+            FROM forms
+            WHERE site_id = $1
+            ORDER BY name ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(site_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(forms)
+    }
+
+    /// Count forms of a site
+    pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM forms WHERE site_id = $1")
+            .bind(site_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(row.0)
+    }
+
+    /// Create a form
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        site_id: Uuid,
+        slug: &str,
+        name: &str,
+        fields: &serde_json::Value,
+        recipient_email: Option<&str>,
+        retention_days: Option<i32>,
+        is_active: bool,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let form = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO forms (site_id, slug, name, fields, recipient_email, retention_days,
+                               is_active, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, site_id, slug, name, fields, recipient_email, retention_days,
+                      is_active, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(site_id)
+        .bind(slug)
+        .bind(name)
+        .bind(fields)
+        .bind(recipient_email)
+        .bind(retention_days)
+        .bind(is_active)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(form)
+    }
+
+    /// Update a form; `None` leaves a column unchanged, `Some(None)` clears it
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        slug: Option<&str>,
+        name: Option<&str>,
+        fields: Option<&serde_json::Value>,
+        recipient_email: Option<Option<&str>>,
+        retention_days: Option<Option<i32>>,
+        is_active: Option<bool>,
+    ) -> Result<Self, ApiError> {
+        let form = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE forms
+            SET slug = COALESCE($2, slug),
+                name = COALESCE($3, name),
+                fields = COALESCE($4, fields),
+                recipient_email = CASE WHEN $5 THEN $6 ELSE recipient_email END,
+                retention_days = CASE WHEN $7 THEN $8 ELSE retention_days END,
+                is_active = COALESCE($9, is_active)
+            WHERE id = $1
+            RETURNING id, site_id, slug, name, fields, recipient_email, retention_days,
+                      is_active, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(slug)
+        .bind(name)
+        .bind(fields)
+        .bind(recipient_email.is_some())
+        .bind(recipient_email.flatten())
+        .bind(retention_days.is_some())
+        .bind(retention_days.flatten())
+        .bind(is_active)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Form with ID {} not found", id)))?;
+
+        Ok(form)
+    }
+
+    /// Delete a form and all of its submissions
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM forms WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!("Form with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+}
+
+impl FormSubmission {
+    /// Find a submission by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let submission = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, form_id, site_id, locale_id, data, ip_address, user_agent, created_at
+            FROM form_submissions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Form submission with ID {} not found", id)))?;
+
+        Ok(submission)
+    }
+
+    /// Submissions of a form, newest first (paginated)
+    pub async fn find_for_form(
+        pool: &PgPool,
+        form_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let submissions = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, form_id, site_id, locale_id, data, ip_address, user_agent, created_at
+            FROM form_submissions
+            WHERE form_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(form_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(submissions)
+    }
+
+    /// All submissions of a form, oldest first (for export)
+    pub async fn find_all_for_form(pool: &PgPool, form_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let submissions = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, form_id, site_id, locale_id, data, ip_address, user_agent, created_at
+            FROM form_submissions
+            WHERE form_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(form_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(submissions)
+    }
+
+    /// Count submissions of a form
+    pub async fn count_for_form(pool: &PgPool, form_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM form_submissions WHERE form_id = $1")
+                .bind(form_id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(row.0)
+    }
+
+    /// Store a submission
+    pub async fn create(
+        pool: &PgPool,
+        form_id: Uuid,
+        site_id: Uuid,
+        locale_id: Option<Uuid>,
+        data: &serde_json::Value,
+        ip_address: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<Self, ApiError> {
+        let submission = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO form_submissions (form_id, site_id, locale_id, data, ip_address, user_agent)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, form_id, site_id, locale_id, data, ip_address, user_agent, created_at
+            "#,
+        )
+        .bind(form_id)
+        .bind(site_id)
+        .bind(locale_id)
+        .bind(data)
+        .bind(ip_address)
+        .bind(user_agent)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(submission)
+    }
+
+    /// Delete a submission
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM form_submissions WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Form submission with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Delete submissions older than their form's `retention_days`.
+    /// Returns the number of deleted submissions.
+    pub async fn purge_expired<'e, E>(executor: E) -> Result<u64, ApiError>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM form_submissions s
+            USING forms f
+            WHERE s.form_id = f.id
+              AND f.retention_days IS NOT NULL
+              AND s.created_at < NOW() - make_interval(days => f.retention_days)
+            "#,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_definitions_tolerate_bad_json() {
+        let mut form = Form {
+            id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            slug: "contact".to_string(),
+            name: "Contact".to_string(),
+            fields: serde_json::json!([{"name": "email", "field_type": "Email", "required": true}]),
+            recipient_email: None,
+            retention_days: None,
+            is_active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let fields = form.field_definitions();
+        assert_eq!(fields.len(), 1);
+        assert!(fields[0].required);
+        assert!(fields[0].labels.is_empty());
+
+        form.fields = serde_json::json!({"not": "a list"});
+        assert!(form.field_definitions().is_empty());
+    }
+}
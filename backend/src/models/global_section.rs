@@ -0,0 +1,333 @@
+//! Global section model
+//!
+//! Site-level sections that pages reference from `page_sections.global_section_id`,
+//! so one edit applies to every page that uses them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::global_section::{CreateGlobalSectionRequest, UpdateGlobalSectionRequest};
+use crate::errors::ApiError;
+use crate::models::content::ContentStatus;
+use crate::models::page::SectionType;
+
+/// Global section model (database row plus the number of live references)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GlobalSection {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub name: String,
+    pub section_type: SectionType,
+    pub cover_image_id: Option<Uuid>,
+    pub call_to_action_route: Option<String>,
+    pub settings: Option<serde_json::Value>,
+    pub created_by: Option<Uuid>,
+    pub usage_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Global section localization model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GlobalSectionLocalization {
+    pub id: Uuid,
+    pub global_section_id: Uuid,
+    pub locale_id: Uuid,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub button_text: Option<String>,
+}
+
+/// A page section referencing a global section
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GlobalSectionUsage {
+    pub page_section_id: Uuid,
+    pub page_id: Uuid,
+    pub route: String,
+    pub slug: Option<String>,
+    pub status: ContentStatus,
+    pub display_order: i16,
+}
+
+impl GlobalSection {
+    /// Find a global section by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let section = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT gs.id, gs.site_id, gs.name, gs.section_type, gs.cover_image_id,
+                   gs.call_to_action_route, gs.settings, gs.created_by,
+                   (SELECT COUNT(*)
+                    FROM page_sections ps
+                    INNER JOIN pages p ON p.id = ps.page_id
+                    INNER JOIN contents c ON c.id = p.content_id
+                    WHERE ps.global_section_id = gs.id AND c.is_deleted = FALSE) AS usage_count,
+                   gs.created_at, gs.updated_at
+            FROM global_sections gs
+            WHERE gs.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Global section with ID {} not found", id)))?;
+
+        Ok(section)
+    }
+
+    /// Find all global sections of a site (paginated)
+    pub async fn find_all_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let sections = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT gs.id, gs.site_id, gs.name, gs.section_type, gs.cover_image_id,
+                   gs.call_to_action_route, gs.settings, gs.created_by,
+                   (SELECT COUNT(*)
+                    FROM page_sections ps
+                    INNER JOIN pages p ON p.id = ps.page_id
+                    INNER JOIN contents c ON c.id = p.content_id
+                    WHERE ps.global_section_id = gs.id AND c.is_deleted = FALSE) AS usage_count,
+                   gs.created_at, gs.updated_at
+            FROM global_sections gs
+            WHERE gs.site_id = $1
+            ORDER BY gs.name ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(site_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sections)
+    }
+
+    /// Count global sections of a site
+    pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM global_sections WHERE site_id = $1")
+            .bind(site_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(row.0)
+    }
+
+    /// Create a global section
+    pub async fn create(
+        pool: &PgPool,
+        site_id: Uuid,
+        req: &CreateGlobalSectionRequest,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let (id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO global_sections (site_id, name, section_type, cover_image_id,
+                                         call_to_action_route, settings, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(site_id)
+        .bind(&req.name)
+        .bind(&req.section_type)
+        .bind(req.cover_image_id)
+        .bind(&req.call_to_action_route)
+        .bind(&req.settings)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await?;
+
+        Self::find_by_id(pool, id).await
+    }
+
+    /// Update a global section
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        req: &UpdateGlobalSectionRequest,
+    ) -> Result<Self, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE global_sections
+            SET name = COALESCE($2, name),
+                section_type = COALESCE($3, section_type),
+                cover_image_id = COALESCE($4, cover_image_id),
+                call_to_action_route = COALESCE($5, call_to_action_route),
+                settings = COALESCE($6, settings)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&req.name)
+        .bind(&req.section_type)
+        .bind(req.cover_image_id)
+        .bind(&req.call_to_action_route)
+        .bind(&req.settings)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Global section with ID {} not found",
+                id
+            )));
+        }
+
+        Self::find_by_id(pool, id).await
+    }
+
+    /// Delete a global section (references on deleted pages go with it).
+    ///
+    /// Fails with a conflict if a live page section still references it, including one
+    /// added after the caller's usage check.
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            r#"
+            DELETE FROM page_sections ps
+            USING pages p, contents c
+            WHERE ps.global_section_id = $1 AND ps.page_id = p.id
+              AND p.content_id = c.id AND c.is_deleted = TRUE
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        let result = sqlx::query("DELETE FROM global_sections WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23503") => {
+                    ApiError::Conflict("Global section is still used by page sections".into())
+                }
+                e => ApiError::from(e),
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Global section with ID {} not found",
+                id
+            )));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Page sections on non-deleted pages that reference a global section
+    pub async fn find_usages(pool: &PgPool, id: Uuid) -> Result<Vec<GlobalSectionUsage>, ApiError> {
+        let usages = sqlx::query_as::<_, GlobalSectionUsage>(
+            r#"
+            SELECT ps.id AS page_section_id, p.id AS page_id, p.route, c.slug, c.status,
+                   ps.display_order
+            FROM page_sections ps
+            INNER JOIN pages p ON p.id = ps.page_id
+            INNER JOIN contents c ON c.id = p.content_id
+            WHERE ps.global_section_id = $1 AND c.is_deleted = FALSE
+            ORDER BY p.route ASC, ps.display_order ASC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(usages)
+    }
+}
+
+impl GlobalSectionLocalization {
+    /// Find all localizations of a global section
+    pub async fn find_for_section(
+        pool: &PgPool,
+        global_section_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
+        let localizations = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, global_section_id, locale_id, title, text, button_text
+            FROM global_section_localizations
+            WHERE global_section_id = $1
+            "#,
+        )
+        .bind(global_section_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(localizations)
+    }
+
+    /// Find a localization by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let localization = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, global_section_id, locale_id, title, text, button_text
+            FROM global_section_localizations
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Global section localization with ID {} not found",
+                id
+            ))
+        })?;
+
+        Ok(localization)
+    }
+
+    /// Upsert a localization (INSERT ON CONFLICT UPDATE)
+    pub async fn upsert(
+        pool: &PgPool,
+        global_section_id: Uuid,
+        locale_id: Uuid,
+        title: Option<&str>,
+        text: Option<&str>,
+        button_text: Option<&str>,
+    ) -> Result<Self, ApiError> {
+        let localization = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO global_section_localizations (global_section_id, locale_id, title, text, button_text)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (global_section_id, locale_id)
+            DO UPDATE SET title = $3, text = $4, button_text = $5
+            RETURNING id, global_section_id, locale_id, title, text, button_text
+            "#,
+        )
+        .bind(global_section_id)
+        .bind(locale_id)
+        .bind(title)
+        .bind(text)
+        .bind(button_text)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(localization)
+    }
+
+    /// Delete a localization (hard delete)
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM global_section_localizations WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Global section localization with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::dto::legal::{
@@ -95,7 +95,7 @@ impl LegalDocument {
     /// Count legal documents for a site
     pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
         let row: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM legal_documents ld INNER JOIN contents c ON ld.content_id = c.id INNER JOIN content_sites cs ON c.id = cs.content_id WHERE cs.site_id = $1 AND c.is_deleted = FALSE"
+            "SELECT COUNT(*) FROM legal_documents ld INNER JOIN contents c ON ld.content_id = c.id INNER JOIN content_sites cs ON c.id = cs.content_id WHERE cs.site_id = $1 AND c.is_deleted = FALSE AND c.environment_id = (SELECT id FROM environments WHERE is_default)"
         )
         .bind(site_id)
         .fetch_one(pool)
@@ -118,6 +118,7 @@ impl LegalDocument {
             INNER JOIN contents c ON ld.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
             ORDER BY ld.document_type ASC
             LIMIT $2 OFFSET $3
             "#,
@@ -132,7 +133,10 @@ impl LegalDocument {
     }
 
     /// Find legal document by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
         let document = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, content_id, cookie_name, document_type, created_at, updated_at
@@ -141,7 +145,7 @@ impl LegalDocument {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Legal document with ID {} not found", id)))?;
 
@@ -162,6 +166,7 @@ impl LegalDocument {
             INNER JOIN contents c ON ld.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND ld.document_type = $2 AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
             "#,
         )
         .bind(site_id)
@@ -173,7 +178,7 @@ impl LegalDocument {
         Ok(document)
     }
 
-    /// Find legal document by content slug for a site
+    /// Find legal document by slug for a site (site-specific slugs take precedence)
     pub async fn find_by_slug_for_site(
         pool: &PgPool,
         site_id: Uuid,
@@ -186,7 +191,10 @@ impl LegalDocument {
             FROM legal_documents ld
             INNER JOIN contents c ON ld.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
-            WHERE cs.site_id = $1 AND c.slug = $2 AND c.is_deleted = FALSE
+            WHERE cs.site_id = $1
+              AND COALESCE(cs.site_specific_slug, c.slug) = $2
+              AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
             "#,
         )
         .bind(site_id)
@@ -201,15 +209,19 @@ impl LegalDocument {
     }
 
     /// Create a legal document with associated content
-    pub async fn create(pool: &PgPool, req: CreateLegalDocumentRequest) -> Result<Self, ApiError> {
-        let content_id = ContentService::create_content(
-            pool,
+    pub async fn create(
+        conn: &mut PgConnection,
+        req: CreateLegalDocumentRequest,
+    ) -> Result<Self, ApiError> {
+        let content_id = ContentService::insert_content(
+            &mut *conn,
             "legal",
             None,
             &req.status,
             &req.site_ids,
             None,
             None,
+            None,
         )
         .await?;
 
@@ -223,7 +235,7 @@ impl LegalDocument {
         .bind(content_id)
         .bind(&req.cookie_name)
         .bind(&req.document_type)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(document)
@@ -231,15 +243,22 @@ impl LegalDocument {
 
     /// Update a legal document
     pub async fn update(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         id: Uuid,
         req: UpdateLegalDocumentRequest,
     ) -> Result<Self, ApiError> {
-        let existing = Self::find_by_id(pool, id).await?;
+        let existing = Self::find_by_id(&mut *conn, id).await?;
 
         if let Some(content_id) = existing.content_id {
-            ContentService::update_content(pool, content_id, None, req.status.as_ref(), None, None)
-                .await?;
+            ContentService::update_content(
+                &mut *conn,
+                content_id,
+                None,
+                req.status.as_ref(),
+                None,
+                None,
+            )
+            .await?;
         }
 
         let document = sqlx::query_as::<_, Self>(
@@ -255,7 +274,7 @@ impl LegalDocument {
         .bind(id)
         .bind(&req.cookie_name)
         .bind(&req.document_type)
-        .fetch_optional(pool)
+        .fetch_optional(&mut *conn)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Legal document with ID {} not found", id)))?;
 
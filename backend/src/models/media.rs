@@ -1,5 +1,7 @@
 //! Media model
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
@@ -56,11 +58,30 @@ pub struct MediaFile {
     pub environment_id: Option<Uuid>,
     pub is_global: bool,
     pub folder_id: Option<Uuid>,
+    /// Focal point as a fraction of the width (NULL = center)
+    pub focal_x: Option<f64>,
+    /// Focal point as a fraction of the height (NULL = center)
+    pub focal_y: Option<f64>,
+    /// Named crop rectangles, e.g. `{"16:9": {"x": 0, "y": 120, "width": 1600, "height": 900}}`
+    pub crops: serde_json::Value,
     pub is_deleted: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Crop rectangle in source image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CropRect {
+    #[schema(example = 0)]
+    pub x: u32,
+    #[schema(example = 120)]
+    pub y: u32,
+    #[schema(example = 1600)]
+    pub width: u32,
+    #[schema(example = 900)]
+    pub height: u32,
+}
+
 /// Media variant model
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct MediaVariant {
@@ -75,6 +96,21 @@ pub struct MediaVariant {
     pub created_at: DateTime<Utc>,
 }
 
+/// Cached on-the-fly transformation of an image
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MediaTransform {
+    pub id: Uuid,
+    pub media_file_id: Uuid,
+    /// Normalized transformation options, e.g. `w400_h300_cover_q80.webp`
+    pub transform_key: String,
+    pub content_type: String,
+    pub width: i16,
+    pub height: i16,
+    pub file_size: i32,
+    pub storage_path: String,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Media metadata (localized)
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct MediaMetadata {
@@ -102,12 +138,25 @@ pub struct MediaWithVariants {
     pub height: Option<i16>,
     pub duration: Option<i32>,
     pub is_global: bool,
+    pub focal_x: Option<f64>,
+    pub focal_y: Option<f64>,
+    pub crops: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub variants: Vec<MediaVariant>,
 }
 
 impl MediaFile {
+    /// Focal point as fractions of width and height, if set
+    pub fn focal_point(&self) -> Option<(f64, f64)> {
+        self.focal_x.zip(self.focal_y)
+    }
+
+    /// Parsed crop rectangles (crops are validated before they are stored)
+    pub fn crop_rects(&self) -> BTreeMap<String, CropRect> {
+        serde_json::from_value(self.crops.clone()).unwrap_or_default()
+    }
+
     /// Find all media files for a site
     pub async fn find_all_for_site(
         pool: &PgPool,
@@ -120,7 +169,8 @@ impl MediaFile {
             SELECT m.id, m.filename, m.original_filename, m.mime_type, m.file_size,
                    m.storage_provider, m.storage_path, m.public_url, m.checksum,
                    m.width, m.height, m.duration, m.uploaded_by, m.environment_id,
-                   m.is_global, m.folder_id, m.is_deleted, m.created_at, m.updated_at
+                   m.is_global, m.folder_id, m.focal_x, m.focal_y, m.crops, m.is_deleted,
+                   m.created_at, m.updated_at
             FROM media_files m
             INNER JOIN media_sites ms ON m.id = ms.media_file_id
             WHERE ms.site_id = $1 AND m.is_deleted = FALSE
@@ -144,7 +194,8 @@ impl MediaFile {
             SELECT id, filename, original_filename, mime_type, file_size,
                    storage_provider, storage_path, public_url, checksum,
                    width, height, duration, uploaded_by, environment_id,
-                   is_global, folder_id, is_deleted, created_at, updated_at
+                   is_global, folder_id, focal_x, focal_y, crops, is_deleted,
+                   created_at, updated_at
             FROM media_files
             WHERE id = $1 AND is_deleted = FALSE
             "#,
@@ -177,6 +228,9 @@ impl MediaFile {
             height: media.height,
             duration: media.duration,
             is_global: media.is_global,
+            focal_x: media.focal_x,
+            focal_y: media.focal_y,
+            crops: media.crops,
             created_at: media.created_at,
             updated_at: media.updated_at,
             variants,
@@ -190,7 +244,8 @@ impl MediaFile {
             SELECT id, filename, original_filename, mime_type, file_size,
                    storage_provider, storage_path, public_url, checksum,
                    width, height, duration, uploaded_by, environment_id,
-                   is_global, folder_id, is_deleted, created_at, updated_at
+                   is_global, folder_id, focal_x, focal_y, crops, is_deleted,
+                   created_at, updated_at
             FROM media_files
             WHERE checksum = $1 AND is_deleted = FALSE
             "#,
@@ -202,6 +257,33 @@ impl MediaFile {
         Ok(media)
     }
 
+    /// Find a site's media file by its public URL
+    pub async fn find_by_public_url(
+        pool: &PgPool,
+        site_id: Uuid,
+        public_url: &str,
+    ) -> Result<Option<Self>, ApiError> {
+        let media = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT m.id, m.filename, m.original_filename, m.mime_type, m.file_size,
+                   m.storage_provider, m.storage_path, m.public_url, m.checksum,
+                   m.width, m.height, m.duration, m.uploaded_by, m.environment_id,
+                   m.is_global, m.folder_id, m.focal_x, m.focal_y, m.crops, m.is_deleted,
+                   m.created_at, m.updated_at
+            FROM media_files m
+            INNER JOIN media_sites ms ON m.id = ms.media_file_id
+            WHERE ms.site_id = $1 AND m.public_url = $2 AND m.is_deleted = FALSE
+            LIMIT 1
+            "#,
+        )
+        .bind(site_id)
+        .bind(public_url)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(media)
+    }
+
     /// Search media files for a site with optional filters.
     /// Uses `QueryBuilder` because the combination of 3 optional filters
     /// (search text, MIME category, folder) would require 8 static queries.
@@ -216,7 +298,8 @@ impl MediaFile {
             "SELECT DISTINCT m.id, m.filename, m.original_filename, m.mime_type, m.file_size, \
              m.storage_provider, m.storage_path, m.public_url, m.checksum, \
              m.width, m.height, m.duration, m.uploaded_by, m.environment_id, \
-             m.is_global, m.folder_id, m.is_deleted, m.created_at, m.updated_at \
+             m.is_global, m.folder_id, m.focal_x, m.focal_y, m.crops, m.is_deleted, \
+             m.created_at, m.updated_at \
              FROM media_files m \
              INNER JOIN media_sites ms ON m.id = ms.media_file_id",
         );
@@ -344,7 +427,8 @@ impl MediaFile {
             RETURNING id, filename, original_filename, mime_type, file_size,
                       storage_provider, storage_path, public_url, checksum,
                       width, height, duration, uploaded_by, environment_id,
-                      is_global, folder_id, is_deleted, created_at, updated_at
+                      is_global, folder_id, focal_x, focal_y, crops, is_deleted,
+                      created_at, updated_at
             "#,
         )
         .bind(&req.filename)
@@ -394,7 +478,8 @@ impl MediaFile {
             RETURNING id, filename, original_filename, mime_type, file_size,
                       storage_provider, storage_path, public_url, checksum,
                       width, height, duration, uploaded_by, environment_id,
-                      is_global, folder_id, is_deleted, created_at, updated_at
+                      is_global, folder_id, focal_x, focal_y, crops, is_deleted,
+                      created_at, updated_at
             "#,
         )
         .bind(id)
@@ -409,6 +494,38 @@ impl MediaFile {
         Ok(media)
     }
 
+    /// Replace the focal point and crops of a media file
+    pub async fn set_art_direction(
+        pool: &PgPool,
+        id: Uuid,
+        focal_point: Option<(f64, f64)>,
+        crops: &BTreeMap<String, CropRect>,
+    ) -> Result<Self, ApiError> {
+        let crops = serde_json::to_value(crops)
+            .map_err(|e| ApiError::Internal(format!("Failed to serialize crops: {e}")))?;
+        let media = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE media_files
+            SET focal_x = $2, focal_y = $3, crops = $4, updated_at = NOW()
+            WHERE id = $1 AND is_deleted = FALSE
+            RETURNING id, filename, original_filename, mime_type, file_size,
+                      storage_provider, storage_path, public_url, checksum,
+                      width, height, duration, uploaded_by, environment_id,
+                      is_global, folder_id, focal_x, focal_y, crops, is_deleted,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(focal_point.map(|(x, _)| x))
+        .bind(focal_point.map(|(_, y)| y))
+        .bind(crops)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Media file with ID {} not found", id)))?;
+
+        Ok(media)
+    }
+
     /// Create a new media file from an actual upload (server-side detection)
     #[allow(clippy::too_many_arguments)]
     pub async fn create_from_upload(
@@ -439,7 +556,8 @@ impl MediaFile {
             RETURNING id, filename, original_filename, mime_type, file_size,
                       storage_provider, storage_path, public_url, checksum,
                       width, height, duration, uploaded_by, environment_id,
-                      is_global, folder_id, is_deleted, created_at, updated_at
+                      is_global, folder_id, focal_x, focal_y, crops, is_deleted,
+                      created_at, updated_at
             "#,
         )
         .bind(filename)
@@ -470,13 +588,30 @@ impl MediaFile {
         Ok(media)
     }
 
+    /// Link an existing media file to another site (no-op if already linked)
+    pub async fn add_to_site(pool: &PgPool, id: Uuid, site_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO media_sites (media_file_id, site_id, is_owner)
+            VALUES ($1, $2, FALSE)
+            ON CONFLICT (media_file_id, site_id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(site_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Soft delete media file
     pub async fn soft_delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
         let result = sqlx::query(
             r#"
             UPDATE media_files
-            SET is_deleted = TRUE, updated_at = NOW()
-            WHERE id = $1
+            SET is_deleted = TRUE, deleted_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND is_deleted = FALSE
             "#,
         )
         .bind(id)
@@ -492,9 +627,128 @@ impl MediaFile {
 
         Ok(())
     }
+
+    /// Take a media file out of the trash
+    pub async fn restore(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE media_files
+            SET is_deleted = FALSE, deleted_at = NULL, updated_at = NOW()
+            WHERE id = $1 AND is_deleted = TRUE
+            "#,
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Deleted media file with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Sites a media file belongs to, the owning site first
+    pub async fn find_site_ids(pool: &PgPool, id: Uuid) -> Result<Vec<Uuid>, ApiError> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT site_id FROM media_sites WHERE media_file_id = $1 ORDER BY is_owner DESC, site_id",
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Site that owns a media file, if any
+    pub async fn find_owner_site(pool: &PgPool, id: Uuid) -> Result<Option<Uuid>, ApiError> {
+        let site_id = sqlx::query_scalar(
+            "SELECT site_id FROM media_sites WHERE media_file_id = $1 AND is_owner = TRUE LIMIT 1",
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(site_id)
+    }
+
+    /// Raster images that have no AVIF variant yet, newest first. SVG and AVIF
+    /// originals are skipped since they cannot be decoded, and so are images
+    /// whose AVIF variant already failed.
+    pub async fn find_images_without_avif(
+        pool: &PgPool,
+        limit: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let media = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT m.id, m.filename, m.original_filename, m.mime_type, m.file_size,
+                   m.storage_provider, m.storage_path, m.public_url, m.checksum,
+                   m.width, m.height, m.duration, m.uploaded_by, m.environment_id,
+                   m.is_global, m.folder_id, m.focal_x, m.focal_y, m.crops, m.is_deleted,
+                   m.created_at, m.updated_at
+            FROM media_files m
+            WHERE m.is_deleted = FALSE
+              AND m.mime_type LIKE 'image/%'
+              AND m.mime_type NOT IN ('image/svg+xml', 'image/avif')
+              AND m.avif_failed_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM media_variants v
+                  WHERE v.media_file_id = m.id AND v.variant_name = $1
+              )
+            ORDER BY m.created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(MediaVariantType::Avif)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(media)
+    }
+
+    /// Record that the AVIF variant of a media file could not be generated
+    pub async fn mark_avif_failed(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("UPDATE media_files SET avif_failed_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
 }
 
 impl MediaVariant {
+    /// Insert a generated variant unless the media file already has one of its type
+    pub async fn create_if_missing(
+        pool: &PgPool,
+        media_file_id: Uuid,
+        v: &crate::services::image_service::GeneratedVariant,
+    ) -> Result<Option<Self>, ApiError> {
+        let variant = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO media_variants (media_file_id, variant_name, width, height, file_size, storage_path, public_url)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (media_file_id, variant_name) DO NOTHING
+            RETURNING id, media_file_id, variant_name, width, height, file_size, storage_path, public_url, created_at
+            "#,
+        )
+        .bind(media_file_id)
+        .bind(&v.variant_type)
+        .bind(v.width as i16)
+        .bind(v.height as i16)
+        .bind(v.file_size as i32)
+        .bind(&v.storage_path)
+        .bind(&v.public_url)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(variant)
+    }
+
     /// Batch-insert generated variants for a media file
     pub async fn create_batch(
         pool: &PgPool,
@@ -546,6 +800,78 @@ impl MediaVariant {
     }
 }
 
+impl MediaTransform {
+    /// Find a cached transformation of a media file
+    pub async fn find(
+        pool: &PgPool,
+        media_file_id: Uuid,
+        transform_key: &str,
+    ) -> Result<Option<Self>, ApiError> {
+        let transform = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, media_file_id, transform_key, content_type, width, height,
+                   file_size, storage_path, created_at
+            FROM media_transforms
+            WHERE media_file_id = $1 AND transform_key = $2
+            "#,
+        )
+        .bind(media_file_id)
+        .bind(transform_key)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(transform)
+    }
+
+    /// Record a stored transformation; a concurrent request may have recorded it first
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        media_file_id: Uuid,
+        transform_key: &str,
+        content_type: &str,
+        width: u32,
+        height: u32,
+        file_size: usize,
+        storage_path: &str,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO media_transforms
+                (media_file_id, transform_key, content_type, width, height, file_size, storage_path)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (media_file_id, transform_key) DO NOTHING
+            "#,
+        )
+        .bind(media_file_id)
+        .bind(transform_key)
+        .bind(content_type)
+        .bind(width as i16)
+        .bind(height as i16)
+        .bind(file_size as i32)
+        .bind(storage_path)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Forget all cached transformations of a media file, returning their storage paths
+    pub async fn delete_for_media(
+        pool: &PgPool,
+        media_file_id: Uuid,
+    ) -> Result<Vec<String>, ApiError> {
+        let paths = sqlx::query_scalar(
+            "DELETE FROM media_transforms WHERE media_file_id = $1 RETURNING storage_path",
+        )
+        .bind(media_file_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(paths)
+    }
+}
+
 impl MediaMetadata {
     /// Find metadata for a media file and locale
     pub async fn find_for_media_locale(
@@ -5,11 +5,20 @@
 pub mod api_key;
 pub mod audit;
 pub mod blog;
+pub mod comment;
 pub mod content;
+pub mod content_block;
+pub mod content_release;
+pub mod content_site;
 pub mod content_template;
+pub mod content_version;
 pub mod cv;
 pub mod document;
+pub mod edit_lock;
 pub mod environment;
+pub mod environment_promotion;
+pub mod form;
+pub mod global_section;
 pub mod legal;
 pub mod locale;
 pub mod media;
@@ -18,13 +27,19 @@ pub mod navigation;
 pub mod navigation_menu;
 pub mod notification;
 pub mod page;
+pub mod page_template;
+pub mod preview_token;
 pub mod redirect;
+pub mod search;
 pub mod site;
 pub mod site_locale;
 pub mod site_membership;
 pub mod site_settings;
+pub mod site_transfer;
+pub mod sitemap;
 pub mod social;
 pub mod taxonomy;
+pub mod trash;
 pub mod webhook;
 
 // Re-export commonly used models
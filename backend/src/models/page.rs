@@ -1,15 +1,19 @@
 //! Page model
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{Connection, PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::dto::page::{
     CreatePageRequest, CreatePageSectionRequest, UpdatePageRequest, UpdatePageSectionRequest,
 };
+use crate::dto::page_template::PageTemplateSection;
 use crate::errors::ApiError;
-use crate::models::content::ContentStatus;
+use crate::models::content::{Content, ContentStatus};
+use crate::models::environment::Environment;
 use crate::services::content_service::ContentService;
 
 /// Page type enum matching PostgreSQL
@@ -86,15 +90,32 @@ pub struct PageSection {
     pub cover_image_id: Option<Uuid>,
     pub call_to_action_route: Option<String>,
     pub settings: Option<serde_json::Value>,
+    /// Global section this section references; shared fields come from there
+    pub global_section_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Page of a site's hierarchy with its title resolved for one locale
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PageTreeRow {
+    pub id: Uuid,
+    pub parent_page_id: Option<Uuid>,
+    pub route: String,
+    pub slug: Option<String>,
+    pub title: Option<String>,
+    pub page_type: PageType,
+    pub status: ContentStatus,
+    pub is_in_navigation: bool,
+    pub navigation_order: Option<i16>,
+}
+
 impl Page {
-    /// Find all pages for a site
+    /// Find all pages for a site in an environment (default: the live one)
     pub async fn find_all_for_site(
         pool: &PgPool,
         site_id: Uuid,
+        environment_id: Option<Uuid>,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<PageWithContent>, ApiError> {
@@ -109,6 +130,7 @@ impl Page {
             INNER JOIN contents c ON p.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND c.is_deleted = FALSE
+              AND c.environment_id = COALESCE($4, (SELECT id FROM environments WHERE is_default))
             ORDER BY p.route ASC
             LIMIT $2 OFFSET $3
             "#,
@@ -116,6 +138,7 @@ impl Page {
         .bind(site_id)
         .bind(limit)
         .bind(offset)
+        .bind(environment_id)
         .fetch_all(pool)
         .await?;
 
@@ -123,7 +146,10 @@ impl Page {
     }
 
     /// Find page by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<PageWithContent, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<PageWithContent, ApiError> {
         let page = sqlx::query_as::<_, PageWithContent>(
             r#"
             SELECT
@@ -137,7 +163,7 @@ impl Page {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Page with ID {} not found", id)))?;
 
@@ -161,6 +187,7 @@ impl Page {
             INNER JOIN contents c ON p.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND p.route = $2 AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
             "#,
         )
         .bind(site_id)
@@ -172,8 +199,12 @@ impl Page {
         Ok(page)
     }
 
-    /// Count pages for a site
-    pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
+    /// Count pages for a site in an environment (default: the live one)
+    pub async fn count_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        environment_id: Option<Uuid>,
+    ) -> Result<i64, ApiError> {
         let row: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*)
@@ -181,36 +212,216 @@ impl Page {
             INNER JOIN contents c ON p.content_id = c.id
             INNER JOIN content_sites cs ON c.id = cs.content_id
             WHERE cs.site_id = $1 AND c.is_deleted = FALSE
+              AND c.environment_id = COALESCE($2, (SELECT id FROM environments WHERE is_default))
             "#,
         )
         .bind(site_id)
+        .bind(environment_id)
         .fetch_one(pool)
         .await?;
 
         Ok(row.0)
     }
 
+    /// All live pages of a site for building the hierarchy, in navigation order.
+    ///
+    /// Titles come from the localization in `locale`, falling back to the site's
+    /// default language and then to any localization.
+    pub async fn find_tree_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        locale: Option<&str>,
+    ) -> Result<Vec<PageTreeRow>, ApiError> {
+        let rows = sqlx::query_as::<_, PageTreeRow>(
+            r#"
+            SELECT
+                p.id, p.parent_page_id, p.route, c.slug, t.title, p.page_type, c.status,
+                p.is_in_navigation, p.navigation_order
+            FROM pages p
+            INNER JOIN contents c ON p.content_id = c.id
+            INNER JOIN content_sites cs ON c.id = cs.content_id
+            LEFT JOIN LATERAL (
+                SELECT cl.title
+                FROM content_localizations cl
+                INNER JOIN locales l ON l.id = cl.locale_id
+                LEFT JOIN site_locales sl ON sl.locale_id = cl.locale_id AND sl.site_id = $1
+                WHERE cl.content_id = c.id
+                ORDER BY COALESCE(l.code = $2, FALSE) DESC,
+                         COALESCE(sl.is_default, FALSE) DESC,
+                         l.code ASC
+                LIMIT 1
+            ) t ON TRUE
+            WHERE cs.site_id = $1 AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
+            ORDER BY p.navigation_order ASC NULLS LAST, p.route ASC
+            "#,
+        )
+        .bind(site_id)
+        .bind(locale)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// IDs of a page and its ancestors, starting with the page itself
+    pub async fn ancestor_ids<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Vec<Uuid>, ApiError> {
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT id, parent_page_id, 1 AS depth FROM pages WHERE id = $1
+                UNION ALL
+                SELECT p.id, p.parent_page_id, chain.depth + 1
+                FROM pages p
+                INNER JOIN chain ON p.id = chain.parent_page_id
+                WHERE chain.depth < 100
+            )
+            SELECT id FROM chain ORDER BY depth
+            "#,
+        )
+        .bind(id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// All pages below a page, at any depth
+    pub async fn find_descendants(
+        pool: &PgPool,
+        id: Uuid,
+    ) -> Result<Vec<PageWithContent>, ApiError> {
+        let pages = sqlx::query_as::<_, PageWithContent>(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id, 1 AS depth FROM pages WHERE parent_page_id = $1
+                UNION
+                SELECT p.id, tree.depth + 1
+                FROM pages p
+                INNER JOIN tree ON p.parent_page_id = tree.id
+                WHERE tree.depth < 100
+            )
+            SELECT
+                p.id, p.content_id, p.route, p.page_type,
+                p.template, p.is_in_navigation, p.navigation_order, p.parent_page_id,
+                c.slug, c.status, c.published_at, c.publish_start, c.publish_end,
+                p.created_at, p.updated_at
+            FROM pages p
+            INNER JOIN contents c ON p.content_id = c.id
+            WHERE p.id IN (SELECT id FROM tree) AND p.id <> $1 AND c.is_deleted = FALSE
+            ORDER BY p.route ASC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(pages)
+    }
+
+    /// Whether a route is taken on any of the sites by a page not in `exclude`,
+    /// within the environment of the `exclude` pages
+    pub async fn route_in_use<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        site_ids: &[Uuid],
+        route: &str,
+        exclude: &[Uuid],
+    ) -> Result<bool, ApiError> {
+        let in_use: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM pages p
+                INNER JOIN contents c ON p.content_id = c.id
+                INNER JOIN content_sites cs ON c.id = cs.content_id
+                WHERE p.route = $1 AND cs.site_id = ANY($2) AND c.is_deleted = FALSE
+                  AND NOT (p.id = ANY($3))
+                  AND c.environment_id IN (
+                      SELECT ec.environment_id FROM pages ep
+                      INNER JOIN contents ec ON ec.id = ep.content_id
+                      WHERE ep.id = ANY($3)
+                  )
+            )
+            "#,
+        )
+        .bind(route)
+        .bind(site_ids)
+        .bind(exclude)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(in_use)
+    }
+
+    /// Attach a page to a new parent (`None` for top level) and apply route
+    /// changes to it and its descendants in one transaction
+    pub async fn move_to(
+        conn: &mut PgConnection,
+        id: Uuid,
+        parent_page_id: Option<Uuid>,
+        routes: &[(Uuid, String)],
+    ) -> Result<(), ApiError> {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("UPDATE pages SET parent_page_id = $2, updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(parent_page_id)
+            .execute(&mut *tx)
+            .await?;
+        for (page_id, route) in routes {
+            sqlx::query("UPDATE pages SET route = $2, updated_at = NOW() WHERE id = $1")
+                .bind(page_id)
+                .bind(route)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Create a new page with associated content
     pub async fn create(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         req: CreatePageRequest,
     ) -> Result<PageWithContent, ApiError> {
-        let content_id = ContentService::create_content(
-            pool,
+        Self::create_with_sections(&mut *conn, req, &[], &HashMap::new()).await
+    }
+
+    /// Create a page together with a section layout in one transaction.
+    ///
+    /// Sections get `display_order` from their position. Placeholders become
+    /// section localizations; codes missing from `locale_ids` are skipped.
+    pub async fn create_with_sections(
+        conn: &mut PgConnection,
+        req: CreatePageRequest,
+        sections: &[PageTemplateSection],
+        locale_ids: &HashMap<String, Uuid>,
+    ) -> Result<PageWithContent, ApiError> {
+        let environment_id = Environment::resolve_id(&mut *conn, req.environment.clone()).await?;
+        let mut tx = conn.begin().await?;
+
+        let content_id = ContentService::insert_content(
+            &mut tx,
             "page",
             Some(&req.slug),
             &req.status,
             &req.site_ids,
             req.publish_start,
             req.publish_end,
+            environment_id,
         )
         .await?;
 
-        sqlx::query(
+        let (page_id,): (Uuid,) = sqlx::query_as(
             r#"
             INSERT INTO pages (content_id, route, page_type, template,
                              is_in_navigation, navigation_order, parent_page_id)
             VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
             "#,
         )
         .bind(content_id)
@@ -220,38 +431,62 @@ impl Page {
         .bind(req.is_in_navigation)
         .bind(req.navigation_order)
         .bind(req.parent_page_id)
-        .execute(pool)
+        .fetch_one(&mut *tx)
         .await?;
 
-        let page = sqlx::query_as::<_, PageWithContent>(
-            r#"
-            SELECT
-                p.id, p.content_id, p.route, p.page_type,
-                p.template, p.is_in_navigation, p.navigation_order, p.parent_page_id,
-                c.slug, c.status, c.published_at, c.publish_start, c.publish_end,
-                p.created_at, p.updated_at
-            FROM pages p
-            INNER JOIN contents c ON p.content_id = c.id
-            WHERE p.content_id = $1
-            "#,
-        )
-        .bind(content_id)
-        .fetch_one(pool)
-        .await?;
+        for (position, section) in sections.iter().enumerate() {
+            let (section_id,): (Uuid,) = sqlx::query_as(
+                r#"
+                INSERT INTO page_sections (page_id, section_type, display_order,
+                                          cover_image_id, call_to_action_route, settings)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id
+                "#,
+            )
+            .bind(page_id)
+            .bind(&section.section_type)
+            .bind(position as i16)
+            .bind(section.cover_image_id)
+            .bind(&section.call_to_action_route)
+            .bind(&section.settings)
+            .fetch_one(&mut *tx)
+            .await?;
 
-        Ok(page)
+            for (code, placeholder) in &section.placeholders {
+                let Some(locale_id) = locale_ids.get(code) else {
+                    continue;
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO page_section_localizations (page_section_id, locale_id, title, text, button_text)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                )
+                .bind(section_id)
+                .bind(locale_id)
+                .bind(&placeholder.title)
+                .bind(&placeholder.text)
+                .bind(&placeholder.button_text)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Self::find_by_id(&mut *conn, page_id).await
     }
 
     /// Update a page
     pub async fn update(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         id: Uuid,
         req: UpdatePageRequest,
     ) -> Result<PageWithContent, ApiError> {
-        let existing = Self::find_by_id(pool, id).await?;
+        let existing = Self::find_by_id(&mut *conn, id).await?;
 
         ContentService::update_content(
-            pool,
+            &mut *conn,
             existing.content_id,
             req.slug.as_deref(),
             req.status.as_ref(),
@@ -280,34 +515,39 @@ impl Page {
         .bind(req.is_in_navigation)
         .bind(req.navigation_order)
         .bind(req.parent_page_id)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
-        Self::find_by_id(pool, id).await
+        Self::find_by_id(&mut *conn, id).await
     }
 
     /// Clone a page: creates a new Draft page copying fields, localizations, sections, and section localizations.
     pub async fn clone_page(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         source_id: Uuid,
         site_ids: Vec<Uuid>,
     ) -> Result<PageWithContent, ApiError> {
-        let source = Self::find_by_id(pool, source_id).await?;
+        let source = Self::find_by_id(&mut *conn, source_id).await?;
 
         let base_slug = source.slug.as_deref().unwrap_or("untitled");
-        let new_slug = ContentService::generate_unique_slug(pool, base_slug, &site_ids).await?;
+        let new_slug =
+            ContentService::generate_unique_slug(&mut *conn, base_slug, &site_ids).await?;
         let new_route =
-            ContentService::generate_unique_route(pool, &source.route, &site_ids).await?;
+            ContentService::generate_unique_route(&mut *conn, &source.route, &site_ids).await?;
+        let environment_id = Content::find_by_id(&mut *conn, source.content_id)
+            .await?
+            .environment_id;
 
-        // Create content record as Draft, no scheduling
-        let content_id = ContentService::create_content(
-            pool,
+        // Create content record as Draft in the source's environment, no scheduling
+        let content_id = ContentService::insert_content(
+            &mut *conn,
             "page",
             Some(&new_slug),
             &ContentStatus::Draft,
             &site_ids,
             None,
             None,
+            Some(environment_id),
         )
         .await?;
 
@@ -325,7 +565,7 @@ impl Page {
         .bind(&source.template)
         .bind(source.navigation_order)
         .bind(source.parent_page_id)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
         // Get the new page
@@ -342,18 +582,18 @@ impl Page {
             "#,
         )
         .bind(content_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         // Copy content localizations
         let localizations = crate::models::content::ContentLocalization::find_all_for_content(
-            pool,
+            &mut *conn,
             source.content_id,
         )
         .await?;
         for loc in &localizations {
             crate::models::content::ContentLocalization::create(
-                pool,
+                &mut *conn,
                 content_id,
                 loc.locale_id,
                 &loc.title,
@@ -367,15 +607,16 @@ impl Page {
         }
 
         // Copy page sections and their localizations
-        let source_sections = PageSection::find_for_page(pool, source_id).await?;
+        let source_sections = PageSection::find_for_page(&mut *conn, source_id).await?;
         for section in &source_sections {
             let new_section = sqlx::query_as::<_, PageSection>(
                 r#"
                 INSERT INTO page_sections (page_id, section_type, display_order,
-                                          cover_image_id, call_to_action_route, settings)
-                VALUES ($1, $2, $3, $4, $5, $6)
+                                          cover_image_id, call_to_action_route, settings,
+                                          global_section_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
                 RETURNING id, page_id, section_type, display_order, cover_image_id,
-                          call_to_action_route, settings, created_at, updated_at
+                          call_to_action_route, settings, global_section_id, created_at, updated_at
                 "#,
             )
             .bind(new_page.id)
@@ -384,14 +625,19 @@ impl Page {
             .bind(section.cover_image_id)
             .bind(&section.call_to_action_route)
             .bind(&section.settings)
-            .fetch_one(pool)
+            .bind(section.global_section_id)
+            .fetch_one(&mut *conn)
             .await?;
 
-            // Copy section localizations
-            let section_locs = PageSectionLocalization::find_for_section(pool, section.id).await?;
+            // References keep pointing at the shared section; copy local localizations only
+            if section.global_section_id.is_some() {
+                continue;
+            }
+            let section_locs =
+                PageSectionLocalization::find_for_section(&mut *conn, section.id).await?;
             for sloc in &section_locs {
                 PageSectionLocalization::upsert(
-                    pool,
+                    &mut *conn,
                     new_section.id,
                     sloc.locale_id,
                     sloc.title.as_deref(),
@@ -413,36 +659,58 @@ impl Page {
 }
 
 impl PageSection {
-    /// Find sections for a page
-    pub async fn find_for_page(pool: &PgPool, page_id: Uuid) -> Result<Vec<Self>, ApiError> {
+    /// Find sections for a page, with shared fields of referenced global sections resolved
+    pub async fn find_for_page<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        page_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
         let sections = sqlx::query_as::<_, Self>(
             r#"
-            SELECT id, page_id, section_type, display_order, cover_image_id,
-                   call_to_action_route, settings, created_at, updated_at
-            FROM page_sections
-            WHERE page_id = $1
-            ORDER BY display_order ASC
+            SELECT ps.id, ps.page_id,
+                   COALESCE(gs.section_type, ps.section_type) AS section_type,
+                   ps.display_order,
+                   CASE WHEN gs.id IS NULL THEN ps.cover_image_id ELSE gs.cover_image_id END
+                       AS cover_image_id,
+                   CASE WHEN gs.id IS NULL THEN ps.call_to_action_route ELSE gs.call_to_action_route END
+                       AS call_to_action_route,
+                   CASE WHEN gs.id IS NULL THEN ps.settings ELSE gs.settings END AS settings,
+                   ps.global_section_id, ps.created_at, ps.updated_at
+            FROM page_sections ps
+            LEFT JOIN global_sections gs ON gs.id = ps.global_section_id
+            WHERE ps.page_id = $1
+            ORDER BY ps.display_order ASC
             "#,
         )
         .bind(page_id)
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(sections)
     }
 
-    /// Find section by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+    /// Find section by ID, with shared fields of a referenced global section resolved
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
         let section = sqlx::query_as::<_, Self>(
             r#"
-            SELECT id, page_id, section_type, display_order, cover_image_id,
-                   call_to_action_route, settings, created_at, updated_at
-            FROM page_sections
-            WHERE id = $1
+            SELECT ps.id, ps.page_id,
+                   COALESCE(gs.section_type, ps.section_type) AS section_type,
+                   ps.display_order,
+                   CASE WHEN gs.id IS NULL THEN ps.cover_image_id ELSE gs.cover_image_id END
+                       AS cover_image_id,
+                   CASE WHEN gs.id IS NULL THEN ps.call_to_action_route ELSE gs.call_to_action_route END
+                       AS call_to_action_route,
+                   CASE WHEN gs.id IS NULL THEN ps.settings ELSE gs.settings END AS settings,
+                   ps.global_section_id, ps.created_at, ps.updated_at
+            FROM page_sections ps
+            LEFT JOIN global_sections gs ON gs.id = ps.global_section_id
+            WHERE ps.id = $1
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Page section with ID {} not found", id)))?;
 
@@ -450,8 +718,8 @@ impl PageSection {
     }
 
     /// Create a new page section
-    pub async fn create(
-        pool: &PgPool,
+    pub async fn create<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
         page_id: Uuid,
         req: CreatePageSectionRequest,
     ) -> Result<Self, ApiError> {
@@ -461,7 +729,7 @@ impl PageSection {
                                       cover_image_id, call_to_action_route, settings)
             VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING id, page_id, section_type, display_order, cover_image_id,
-                      call_to_action_route, settings, created_at, updated_at
+                      call_to_action_route, settings, global_section_id, created_at, updated_at
             "#,
         )
         .bind(page_id)
@@ -470,7 +738,7 @@ impl PageSection {
         .bind(req.cover_image_id)
         .bind(&req.call_to_action_route)
         .bind(&req.settings)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(section)
@@ -478,11 +746,11 @@ impl PageSection {
 
     /// Update a page section
     pub async fn update(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         id: Uuid,
         req: UpdatePageSectionRequest,
     ) -> Result<Self, ApiError> {
-        let section = sqlx::query_as::<_, Self>(
+        let result = sqlx::query(
             r#"
             UPDATE page_sections
             SET section_type = COALESCE($2, section_type),
@@ -492,8 +760,6 @@ impl PageSection {
                 settings = COALESCE($6, settings),
                 updated_at = NOW()
             WHERE id = $1
-            RETURNING id, page_id, section_type, display_order, cover_image_id,
-                      call_to_action_route, settings, created_at, updated_at
             "#,
         )
         .bind(id)
@@ -502,18 +768,100 @@ impl PageSection {
         .bind(req.cover_image_id)
         .bind(&req.call_to_action_route)
         .bind(&req.settings)
-        .fetch_optional(pool)
+        .execute(&mut *conn)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Page section with ID {} not found",
+                id
+            )));
+        }
+
+        Self::find_by_id(&mut *conn, id).await
+    }
+
+    /// Add a reference to a global section to a page
+    pub async fn create_reference(
+        conn: &mut PgConnection,
+        page_id: Uuid,
+        global_section_id: Uuid,
+        display_order: i16,
+    ) -> Result<Self, ApiError> {
+        let (id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO page_sections (page_id, section_type, display_order, global_section_id)
+            SELECT $1, section_type, $2, id FROM global_sections WHERE id = $3
+            RETURNING id
+            "#,
+        )
+        .bind(page_id)
+        .bind(display_order)
+        .bind(global_section_id)
+        .fetch_optional(&mut *conn)
         .await?
-        .ok_or_else(|| ApiError::NotFound(format!("Page section with ID {} not found", id)))?;
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Global section with ID {} not found",
+                global_section_id
+            ))
+        })?;
 
-        Ok(section)
+        Self::find_by_id(&mut *conn, id).await
+    }
+
+    /// Turn a reference into a local section by copying the global section's
+    /// fields and localizations into it
+    pub async fn detach(
+        conn: &mut PgConnection,
+        id: Uuid,
+        global_section_id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO page_section_localizations (page_section_id, locale_id, title, text, button_text)
+            SELECT $1, locale_id, title, text, button_text
+            FROM global_section_localizations
+            WHERE global_section_id = $2
+            ON CONFLICT (page_section_id, locale_id)
+            DO UPDATE SET title = EXCLUDED.title, text = EXCLUDED.text,
+                          button_text = EXCLUDED.button_text
+            "#,
+        )
+        .bind(id)
+        .bind(global_section_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE page_sections ps
+            SET section_type = gs.section_type,
+                cover_image_id = gs.cover_image_id,
+                call_to_action_route = gs.call_to_action_route,
+                settings = gs.settings,
+                global_section_id = NULL,
+                updated_at = NOW()
+            FROM global_sections gs
+            WHERE ps.id = $1 AND gs.id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(global_section_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Self::find_by_id(&mut *conn, id).await
     }
 
     /// Delete a page section (hard delete)
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+    pub async fn delete<'e>(executor: impl sqlx::PgExecutor<'e>, id: Uuid) -> Result<(), ApiError> {
         let result = sqlx::query("DELETE FROM page_sections WHERE id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(executor)
             .await?;
 
         if result.rows_affected() == 0 {
@@ -539,29 +887,44 @@ pub struct PageSectionLocalization {
 }
 
 impl PageSectionLocalization {
-    /// Find all localizations for a section
-    pub async fn find_for_section(pool: &PgPool, section_id: Uuid) -> Result<Vec<Self>, ApiError> {
+    /// Find all localizations for a section; references return those of their global section
+    pub async fn find_for_section<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        section_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
         let localizations = sqlx::query_as::<_, Self>(
             r#"
-            SELECT id, page_section_id, locale_id, title, text, button_text
-            FROM page_section_localizations
-            WHERE page_section_id = $1
+            SELECT psl.id, psl.page_section_id, psl.locale_id, psl.title, psl.text, psl.button_text
+            FROM page_section_localizations psl
+            INNER JOIN page_sections ps ON psl.page_section_id = ps.id
+            WHERE ps.id = $1 AND ps.global_section_id IS NULL
+            UNION ALL
+            SELECT gsl.id, ps.id, gsl.locale_id, gsl.title, gsl.text, gsl.button_text
+            FROM global_section_localizations gsl
+            INNER JOIN page_sections ps ON gsl.global_section_id = ps.global_section_id
+            WHERE ps.id = $1
             "#,
         )
         .bind(section_id)
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(localizations)
     }
 
-    /// Find all section localizations for a page (via JOIN on page_sections)
+    /// Find all section localizations for a page (via JOIN on page_sections),
+    /// including those of referenced global sections
     pub async fn find_all_for_page(pool: &PgPool, page_id: Uuid) -> Result<Vec<Self>, ApiError> {
         let localizations = sqlx::query_as::<_, Self>(
             r#"
             SELECT psl.id, psl.page_section_id, psl.locale_id, psl.title, psl.text, psl.button_text
             FROM page_section_localizations psl
             INNER JOIN page_sections ps ON psl.page_section_id = ps.id
+            WHERE ps.page_id = $1 AND ps.global_section_id IS NULL
+            UNION ALL
+            SELECT gsl.id, ps.id, gsl.locale_id, gsl.title, gsl.text, gsl.button_text
+            FROM global_section_localizations gsl
+            INNER JOIN page_sections ps ON gsl.global_section_id = ps.global_section_id
             WHERE ps.page_id = $1
             "#,
         )
@@ -573,8 +936,8 @@ impl PageSectionLocalization {
     }
 
     /// Upsert a section localization (INSERT ON CONFLICT UPDATE)
-    pub async fn upsert(
-        pool: &PgPool,
+    pub async fn upsert<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
         section_id: Uuid,
         locale_id: Uuid,
         title: Option<&str>,
@@ -595,14 +958,17 @@ impl PageSectionLocalization {
         .bind(title)
         .bind(text)
         .bind(button_text)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(localization)
     }
 
     /// Find a localization by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+    pub async fn find_by_id<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
         let localization = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, page_section_id, locale_id, title, text, button_text
@@ -611,7 +977,7 @@ impl PageSectionLocalization {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| {
             ApiError::NotFound(format!(
@@ -624,10 +990,10 @@ impl PageSectionLocalization {
     }
 
     /// Delete a localization (hard delete)
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+    pub async fn delete<'e>(executor: impl sqlx::PgExecutor<'e>, id: Uuid) -> Result<(), ApiError> {
         let result = sqlx::query("DELETE FROM page_section_localizations WHERE id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(executor)
             .await?;
 
         if result.rows_affected() == 0 {
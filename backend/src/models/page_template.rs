@@ -0,0 +1,215 @@
+//! Page template model
+//!
+//! Site-scoped blueprints for new pages: a page type, a template name and an
+//! ordered section layout with default settings and placeholder text.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::page_template::{
+    CreatePageTemplateRequest, PageTemplateSection, UpdatePageTemplateRequest,
+};
+use crate::errors::ApiError;
+use crate::models::page::PageType;
+
+/// Page template model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PageTemplate {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub page_type: PageType,
+    pub template: Option<String>,
+    pub sections: serde_json::Value,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PageTemplate {
+    /// Parsed section layout (layouts are validated before they are stored)
+    pub fn section_layout(&self) -> Vec<PageTemplateSection> {
+        serde_json::from_value(self.sections.clone()).unwrap_or_default()
+    }
+
+    /// Find a page template by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let template = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, name, description, page_type, template, sections,
+                   is_active, created_by, created_at, updated_at
+            FROM page_templates
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Page template with ID {} not found", id)))?;
+
+        Ok(template)
+    }
+
+    /// Find all page templates of a site (paginated)
+    pub async fn find_all_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let templates = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, name, description, page_type, template, sections,
+                   is_active, created_by, created_at, updated_at
+            FROM page_templates
+            WHERE site_id = $1
+            ORDER BY name ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(site_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    /// Count page templates of a site
+    pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM page_templates WHERE site_id = $1")
+            .bind(site_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(row.0)
+    }
+
+    /// Create a page template
+    pub async fn create(
+        pool: &PgPool,
+        site_id: Uuid,
+        req: &CreatePageTemplateRequest,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let template = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO page_templates (site_id, name, description, page_type, template,
+                                        sections, is_active, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, site_id, name, description, page_type, template, sections,
+                      is_active, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(site_id)
+        .bind(&req.name)
+        .bind(&req.description)
+        .bind(&req.page_type)
+        .bind(&req.template)
+        .bind(serde_json::to_value(&req.sections)?)
+        .bind(req.is_active)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// Update a page template (partial update with COALESCE; `sections` replaces the layout)
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        req: &UpdatePageTemplateRequest,
+    ) -> Result<Self, ApiError> {
+        let sections = req
+            .sections
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+        let template = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE page_templates
+            SET name = COALESCE($2, name),
+                description = COALESCE($3, description),
+                page_type = COALESCE($4, page_type),
+                template = COALESCE($5, template),
+                sections = COALESCE($6, sections),
+                is_active = COALESCE($7, is_active)
+            WHERE id = $1
+            RETURNING id, site_id, name, description, page_type, template, sections,
+                      is_active, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(&req.name)
+        .bind(&req.description)
+        .bind(&req.page_type)
+        .bind(&req.template)
+        .bind(sections)
+        .bind(req.is_active)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Page template with ID {} not found", id)))?;
+
+        Ok(template)
+    }
+
+    /// Delete a page template (hard delete; pages created from it are kept)
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM page_templates WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Page template with ID {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::page::SectionType;
+
+    #[test]
+    fn test_section_layout_parses_stored_json() {
+        let mut template = PageTemplate {
+            id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            name: "Landing page".to_string(),
+            description: None,
+            page_type: PageType::Landing,
+            template: Some("landing".to_string()),
+            sections: serde_json::json!([
+                {"section_type": "Hero", "placeholders": {"en": {"title": "Headline"}}},
+                {"section_type": "Cta", "call_to_action_route": "/contact"}
+            ]),
+            is_active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let layout = template.section_layout();
+        assert_eq!(layout.len(), 2);
+        assert_eq!(layout[0].section_type, SectionType::Hero);
+        assert_eq!(
+            layout[0].placeholders["en"].title.as_deref(),
+            Some("Headline")
+        );
+        assert_eq!(layout[1].call_to_action_route.as_deref(), Some("/contact"));
+
+        template.sections = serde_json::json!({"not": "a list"});
+        assert!(template.section_layout().is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+//! Preview token model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Expiring grant to view one content item (optionally one locale) before it is published
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PreviewToken {
+    pub id: Uuid,
+    pub content_id: Uuid,
+    pub locale_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub use_count: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PreviewToken {
+    /// Whether the token is neither revoked nor expired
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    /// List tokens for a content item, newest first (paginated)
+    pub async fn find_for_content(
+        pool: &PgPool,
+        content_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let tokens = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_id, locale_id, expires_at, revoked_at, last_used_at,
+                   use_count, created_by, created_at
+            FROM preview_tokens
+            WHERE content_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(content_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tokens)
+    }
+
+    /// Count tokens for a content item
+    pub async fn count_for_content(pool: &PgPool, content_id: Uuid) -> Result<i64, ApiError> {
+        let row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM preview_tokens WHERE content_id = $1")
+                .bind(content_id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(row.0)
+    }
+
+    /// Find a token by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let token = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, content_id, locale_id, expires_at, revoked_at, last_used_at,
+                   use_count, created_by, created_at
+            FROM preview_tokens
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Preview token with ID {} not found", id)))?;
+
+        Ok(token)
+    }
+
+    /// Create a token
+    pub async fn create(
+        pool: &PgPool,
+        content_id: Uuid,
+        locale_id: Option<Uuid>,
+        expires_at: DateTime<Utc>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, ApiError> {
+        let token = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO preview_tokens (content_id, locale_id, expires_at, created_by)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, content_id, locale_id, expires_at, revoked_at, last_used_at,
+                      use_count, created_by, created_at
+            "#,
+        )
+        .bind(content_id)
+        .bind(locale_id)
+        .bind(expires_at)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Revoke a token (revoking twice keeps the original timestamp)
+    pub async fn revoke(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let token = sqlx::query_as::<_, Self>(
+            r#"
+            UPDATE preview_tokens
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1
+            RETURNING id, content_id, locale_id, expires_at, revoked_at, last_used_at,
+                      use_count, created_by, created_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Preview token with ID {} not found", id)))?;
+
+        Ok(token)
+    }
+
+    /// Record a successful use
+    pub async fn record_use(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            "UPDATE preview_tokens SET use_count = use_count + 1, last_used_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn token(expires_at: DateTime<Utc>, revoked_at: Option<DateTime<Utc>>) -> PreviewToken {
+        PreviewToken {
+            id: Uuid::new_v4(),
+            content_id: Uuid::new_v4(),
+            locale_id: None,
+            expires_at,
+            revoked_at,
+            last_used_at: None,
+            use_count: 0,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_is_active() {
+        assert!(token(Utc::now() + Duration::hours(1), None).is_active());
+    }
+
+    #[test]
+    fn test_expired_is_inactive() {
+        assert!(!token(Utc::now() - Duration::seconds(1), None).is_active());
+    }
+
+    #[test]
+    fn test_revoked_is_inactive() {
+        assert!(!token(Utc::now() + Duration::hours(1), Some(Utc::now())).is_active());
+    }
+}
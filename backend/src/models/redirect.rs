@@ -1,23 +1,85 @@
 //! Redirect model
 
 use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::dto::redirect::{CreateRedirectRequest, UpdateRedirectRequest};
 use crate::errors::ApiError;
 
+/// Compiled regex size limit, keeps user-supplied patterns cheap to build
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// How `source_path` is matched against a requested path
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Default, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "redirect_match_type", rename_all = "lowercase")]
+pub enum RedirectMatchType {
+    /// The path must equal `source_path`
+    #[default]
+    Exact,
+    /// `*` matches any characters; each `*` is a capture group (`$1`, `$2`, ...)
+    Wildcard,
+    /// `source_path` is a regular expression matched against the whole path
+    Regex,
+}
+
+impl RedirectMatchType {
+    /// Compile `source` into an anchored regex (`None` for exact matches)
+    pub fn compile(&self, source: &str) -> Result<Option<Regex>, regex::Error> {
+        let pattern = match self {
+            Self::Exact => return Ok(None),
+            Self::Wildcard => source
+                .split('*')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join("(.*)"),
+            Self::Regex => source.to_string(),
+        };
+        RegexBuilder::new(&format!("^(?:{})$", pattern))
+            .size_limit(PATTERN_SIZE_LIMIT)
+            .build()
+            .map(Some)
+    }
+}
+
+/// What happens to the query string of a requested path
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Default, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "redirect_query_handling", rename_all = "lowercase")]
+pub enum RedirectQueryHandling {
+    /// Match on the path only and drop the query string
+    #[default]
+    Ignore,
+    /// Match on the path only and append the query string to the destination
+    Preserve,
+    /// Match on path and query string; `source_path` includes the query
+    Exact,
+}
+
 /// URL redirect model
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Redirect {
     pub id: Uuid,
     pub site_id: Uuid,
     pub source_path: String,
-    pub destination_path: String,
+    /// `None` for 410 Gone
+    pub destination_path: Option<String>,
     pub status_code: i16,
     pub is_active: bool,
     pub description: Option<String>,
+    /// Created automatically on a slug/route change or removal with fallback
+    pub is_auto_generated: bool,
+    /// Content whose URL change produced this redirect
+    pub content_id: Option<Uuid>,
+    pub match_type: RedirectMatchType,
+    pub query_handling: RedirectQueryHandling,
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -33,7 +95,8 @@ impl Redirect {
         let redirects = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, site_id, source_path, destination_path, status_code,
-                   is_active, description, created_at, updated_at
+                   is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
             FROM redirects
             WHERE site_id = $1
             ORDER BY created_at DESC
@@ -49,6 +112,25 @@ impl Redirect {
         Ok(redirects)
     }
 
+    /// All redirects for a site, oldest first (for export and import checks)
+    pub async fn find_all_unpaginated(pool: &PgPool, site_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let redirects = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, source_path, destination_path, status_code,
+                   is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
+            FROM redirects
+            WHERE site_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(site_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(redirects)
+    }
+
     /// Count redirects for a site
     pub async fn count_for_site(pool: &PgPool, site_id: Uuid) -> Result<i64, ApiError> {
         let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM redirects WHERE site_id = $1")
@@ -63,7 +145,8 @@ impl Redirect {
         let redirect = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, site_id, source_path, destination_path, status_code,
-                   is_active, description, created_at, updated_at
+                   is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
             FROM redirects
             WHERE id = $1
             "#,
@@ -76,36 +159,82 @@ impl Redirect {
         Ok(redirect)
     }
 
-    /// Lookup an active redirect by source path for a site
-    pub async fn find_by_source_path(
+    /// Find the active exact-match redirect for a requested path.
+    ///
+    /// `path` is the requested path without its query string, `path_with_query`
+    /// the full request; rules with exact query handling match the latter and win.
+    pub async fn find_exact(
         pool: &PgPool,
         site_id: Uuid,
-        source_path: &str,
+        path: &str,
+        path_with_query: &str,
     ) -> Result<Option<Self>, ApiError> {
         let redirect = sqlx::query_as::<_, Self>(
             r#"
             SELECT id, site_id, source_path, destination_path, status_code,
-                   is_active, description, created_at, updated_at
+                   is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
             FROM redirects
-            WHERE site_id = $1 AND source_path = $2 AND is_active = TRUE
+            WHERE site_id = $1 AND is_active = TRUE AND match_type = 'exact'
+              AND source_path = CASE WHEN query_handling = 'exact' THEN $3 ELSE $2 END
+            ORDER BY (query_handling = 'exact') DESC
+            LIMIT 1
             "#,
         )
         .bind(site_id)
-        .bind(source_path)
+        .bind(path)
+        .bind(path_with_query)
         .fetch_optional(pool)
         .await?;
 
         Ok(redirect)
     }
 
+    /// Active wildcard and regex redirects for a site, in evaluation order: wildcards before
+    /// regexes, wildcards with more literal characters first, then oldest first
+    pub async fn find_active_patterns(pool: &PgPool, site_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let redirects = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, site_id, source_path, destination_path, status_code,
+                   is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
+            FROM redirects
+            WHERE site_id = $1 AND is_active = TRUE AND match_type <> 'exact'
+            ORDER BY match_type = 'regex',
+                     CASE WHEN match_type = 'wildcard'
+                          THEN length(replace(source_path, '*', '')) END DESC NULLS LAST,
+                     created_at ASC, id ASC
+            "#,
+        )
+        .bind(site_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(redirects)
+    }
+
+    /// Count a lookup that resolved to this redirect
+    pub async fn record_hit(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            "UPDATE redirects SET hit_count = hit_count + 1, last_hit_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Create a new redirect
     pub async fn create(pool: &PgPool, req: CreateRedirectRequest) -> Result<Self, ApiError> {
         let redirect = sqlx::query_as::<_, Self>(
             r#"
-            INSERT INTO redirects (site_id, source_path, destination_path, status_code, is_active, description)
-            VALUES ($1, $2, $3, $4, COALESCE($5, TRUE), $6)
+            INSERT INTO redirects (site_id, source_path, destination_path, status_code, is_active,
+                                   description, match_type, query_handling)
+            VALUES ($1, $2, $3, $4, COALESCE($5, TRUE), $6, $7, $8)
             RETURNING id, site_id, source_path, destination_path, status_code,
-                      is_active, description, created_at, updated_at
+                      is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
             "#,
         )
         .bind(req.site_id)
@@ -114,12 +243,53 @@ impl Redirect {
         .bind(req.status_code)
         .bind(req.is_active)
         .bind(&req.description)
+        .bind(req.match_type.unwrap_or_default())
+        .bind(req.query_handling.unwrap_or_default())
         .fetch_one(pool)
         .await?;
 
         Ok(redirect)
     }
 
+    /// Insert an imported redirect, replacing any existing one with the same source path
+    pub async fn import(
+        conn: &mut PgConnection,
+        req: &CreateRedirectRequest,
+    ) -> Result<Self, ApiError> {
+        let redirect = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO redirects (site_id, source_path, destination_path, status_code, is_active,
+                                   description, match_type, query_handling)
+            VALUES ($1, $2, $3, $4, COALESCE($5, TRUE), $6, $7, $8)
+            ON CONFLICT (site_id, source_path) DO UPDATE
+            SET destination_path = EXCLUDED.destination_path,
+                status_code = EXCLUDED.status_code,
+                is_active = EXCLUDED.is_active,
+                description = EXCLUDED.description,
+                match_type = EXCLUDED.match_type,
+                query_handling = EXCLUDED.query_handling,
+                is_auto_generated = FALSE,
+                content_id = NULL,
+                updated_at = NOW()
+            RETURNING id, site_id, source_path, destination_path, status_code,
+                      is_active, description, is_auto_generated, content_id, match_type,
+                      query_handling, hit_count, last_hit_at, created_at, updated_at
+            "#,
+        )
+        .bind(req.site_id)
+        .bind(&req.source_path)
+        .bind(&req.destination_path)
+        .bind(req.status_code)
+        .bind(req.is_active)
+        .bind(&req.description)
+        .bind(req.match_type.unwrap_or_default())
+        .bind(req.query_handling.unwrap_or_default())
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(redirect)
+    }
+
     /// Update a redirect (partial update with COALESCE)
     pub async fn update(
         pool: &PgPool,
@@ -130,14 +300,18 @@ impl Redirect {
             r#"
             UPDATE redirects
             SET source_path = COALESCE($2, source_path),
-                destination_path = COALESCE($3, destination_path),
+                destination_path = CASE WHEN COALESCE($4, status_code) = 410 THEN NULL
+                                        ELSE COALESCE($3, destination_path) END,
                 status_code = COALESCE($4, status_code),
                 is_active = COALESCE($5, is_active),
                 description = COALESCE($6, description),
+                match_type = COALESCE($7, match_type),
+                query_handling = COALESCE($8, query_handling),
                 updated_at = NOW()
             WHERE id = $1
             RETURNING id, site_id, source_path, destination_path, status_code,
-                      is_active, description, created_at, updated_at
+                      is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
             "#,
         )
         .bind(id)
@@ -146,6 +320,8 @@ impl Redirect {
         .bind(req.status_code)
         .bind(req.is_active)
         .bind(&req.description)
+        .bind(req.match_type)
+        .bind(req.query_handling)
         .fetch_optional(pool)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Redirect with ID {} not found", id)))?;
@@ -153,6 +329,140 @@ impl Redirect {
         Ok(redirect)
     }
 
+    /// Make `path` servable again by dropping generated redirects away from it.
+    ///
+    /// Manual redirects are left alone; returns `true` if an active one still claims the path.
+    pub async fn release_source(
+        conn: &mut PgConnection,
+        site_id: Uuid,
+        path: &str,
+    ) -> Result<bool, ApiError> {
+        sqlx::query(
+            r#"
+            DELETE FROM redirects
+            WHERE site_id = $1 AND source_path = $2 AND match_type = 'exact'
+              AND is_auto_generated = TRUE
+            "#,
+        )
+        .bind(site_id)
+        .bind(path)
+        .execute(&mut *conn)
+        .await?;
+
+        let manual: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM redirects
+                WHERE site_id = $1 AND source_path = $2 AND match_type = 'exact' AND is_active = TRUE
+            )
+            "#,
+        )
+        .bind(site_id)
+        .bind(path)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(manual)
+    }
+
+    /// Follow active redirects from `path` to its final destination (stops on loops)
+    pub async fn resolve_destination(
+        conn: &mut PgConnection,
+        site_id: Uuid,
+        path: &str,
+    ) -> Result<String, ApiError> {
+        const MAX_HOPS: usize = 10;
+
+        let mut current = path.to_string();
+        let mut seen = vec![current.clone()];
+        for _ in 0..MAX_HOPS {
+            let next: Option<String> = sqlx::query_scalar(
+                r#"
+                SELECT destination_path FROM redirects
+                WHERE site_id = $1 AND source_path = $2 AND is_active = TRUE
+                  AND match_type = 'exact' AND destination_path IS NOT NULL
+                "#,
+            )
+            .bind(site_id)
+            .bind(&current)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            match next {
+                Some(next) if !seen.contains(&next) => {
+                    seen.push(next.clone());
+                    current = next;
+                }
+                _ => break,
+            }
+        }
+        Ok(current)
+    }
+
+    /// Point every redirect that ends at `old_destination` straight at `new_destination`
+    pub async fn retarget(
+        conn: &mut PgConnection,
+        site_id: Uuid,
+        old_destination: &str,
+        new_destination: &str,
+    ) -> Result<u64, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE redirects SET destination_path = $3, updated_at = NOW()
+            WHERE site_id = $1 AND destination_path = $2 AND source_path <> $3
+            "#,
+        )
+        .bind(site_id)
+        .bind(old_destination)
+        .bind(new_destination)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Create or replace the generated 301 redirect for `source_path`.
+    ///
+    /// A manual redirect with the same source is never overwritten; `None` is returned instead.
+    pub async fn upsert_generated(
+        conn: &mut PgConnection,
+        site_id: Uuid,
+        source_path: &str,
+        destination_path: &str,
+        description: &str,
+        content_id: Uuid,
+    ) -> Result<Option<Self>, ApiError> {
+        let redirect = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO redirects (site_id, source_path, destination_path, status_code, is_active,
+                                   description, is_auto_generated, content_id)
+            VALUES ($1, $2, $3, 301, TRUE, $4, TRUE, $5)
+            ON CONFLICT (site_id, source_path) DO UPDATE
+            SET destination_path = EXCLUDED.destination_path,
+                status_code = 301,
+                match_type = 'exact',
+                is_active = TRUE,
+                description = EXCLUDED.description,
+                is_auto_generated = TRUE,
+                content_id = EXCLUDED.content_id,
+                updated_at = NOW()
+            WHERE redirects.is_auto_generated
+            RETURNING id, site_id, source_path, destination_path, status_code,
+                      is_active, description, is_auto_generated, content_id, match_type,
+                   query_handling, hit_count, last_hit_at, created_at, updated_at
+            "#,
+        )
+        .bind(site_id)
+        .bind(source_path)
+        .bind(destination_path)
+        .bind(description)
+        .bind(content_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(redirect)
+    }
+
     /// Delete a redirect (hard delete)
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
         let result = sqlx::query("DELETE FROM redirects WHERE id = $1")
@@ -181,10 +491,16 @@ mod tests {
             id: Uuid::new_v4(),
             site_id: Uuid::new_v4(),
             source_path: "/old-page".to_string(),
-            destination_path: "/new-page".to_string(),
+            destination_path: Some("/new-page".to_string()),
             status_code: 301,
             is_active: true,
             description: Some("Moved permanently".to_string()),
+            is_auto_generated: false,
+            content_id: None,
+            match_type: RedirectMatchType::Exact,
+            query_handling: RedirectQueryHandling::Ignore,
+            hit_count: 0,
+            last_hit_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -192,5 +508,34 @@ mod tests {
         let json = serde_json::to_string(&redirect).unwrap();
         assert!(json.contains("\"source_path\":\"/old-page\""));
         assert!(json.contains("\"status_code\":301"));
+        assert!(json.contains("\"match_type\":\"Exact\""));
+    }
+
+    #[test]
+    fn test_compile_wildcard_escapes_and_captures() {
+        let re = RedirectMatchType::Wildcard
+            .compile("/old.blog/*/page-*")
+            .unwrap()
+            .unwrap();
+        let caps = re.captures("/old.blog/2024/page-3").unwrap();
+        assert_eq!(&caps[1], "2024");
+        assert_eq!(&caps[2], "3");
+        assert!(!re.is_match("/oldXblog/2024/page-3"));
+        assert!(!re.is_match("/prefix/old.blog/2024/page-3"));
+    }
+
+    #[test]
+    fn test_compile_regex_is_anchored() {
+        let re = RedirectMatchType::Regex
+            .compile(r"/posts/(\d+)")
+            .unwrap()
+            .unwrap();
+        assert!(re.is_match("/posts/42"));
+        assert!(!re.is_match("/posts/42/comments"));
+        assert!(RedirectMatchType::Regex.compile("/posts/(").is_err());
+        assert!(RedirectMatchType::Exact
+            .compile("/posts")
+            .unwrap()
+            .is_none());
     }
 }
@@ -0,0 +1,198 @@
+//! Full-text search model
+//!
+//! Searches content localizations (blogs, pages) and document localizations
+//! using the weighted `search_vector` columns. Each locale's query is parsed
+//! with the text search configuration of that locale, so stemming matches the
+//! language the content was written in.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+use crate::models::content::ContentStatus;
+
+/// Shared CTEs: one parsed query per locale, and every matching localization.
+///
+/// Parameters: `$1` site, `$2` query text, `$3` locale (nullable),
+/// `$4` entity types, `$5` include unpublished content. Without `$5` only
+/// live content is returned, using the same publish window as the blog lists.
+const SEARCH_HITS_CTE: &str = r#"
+    WITH queries AS (
+        SELECT l.id AS locale_id, l.code AS locale_code,
+               search_config_for_locale(l.code) AS config,
+               websearch_to_tsquery(search_config_for_locale(l.code), $2) AS query
+        FROM locales l
+        WHERE $3::uuid IS NULL OR l.id = $3
+    ),
+    hits AS (
+        SELECT et.name AS entity_type, COALESCE(b.id, p.id) AS entity_id,
+               c.id AS content_id, COALESCE(cs.site_specific_slug, c.slug) AS slug,
+               p.route, c.status,
+               q.locale_id, q.locale_code, cl.title,
+               CONCAT_WS(' ', cl.excerpt, cl.body) AS snippet_source,
+               ts_rank_cd(cl.search_vector, q.query) AS rank,
+               q.config, q.query
+        FROM content_localizations cl
+        INNER JOIN queries q ON q.locale_id = cl.locale_id
+        INNER JOIN contents c ON c.id = cl.content_id
+        INNER JOIN entity_types et ON et.id = c.entity_type_id
+        INNER JOIN content_sites cs ON cs.content_id = c.id AND cs.site_id = $1
+        LEFT JOIN blogs b ON b.content_id = c.id
+        LEFT JOIN pages p ON p.content_id = c.id
+        WHERE cl.search_vector @@ q.query
+          AND et.name = ANY($4)
+          AND et.name IN ('blog', 'page')
+          AND c.is_deleted = FALSE
+          AND c.environment_id = (SELECT id FROM environments WHERE is_default)
+          AND ($5 OR (
+                c.status IN ('published', 'scheduled')
+                AND (c.publish_start IS NULL OR c.publish_start <= NOW())
+                AND (c.publish_end IS NULL OR c.publish_end > NOW())
+          ))
+        UNION ALL
+        SELECT 'document', d.id,
+               NULL, NULL, NULL, NULL,
+               q.locale_id, q.locale_code, dl.name,
+               COALESCE(dl.description, ''),
+               ts_rank_cd(dl.search_vector, q.query),
+               q.config, q.query
+        FROM document_localizations dl
+        INNER JOIN queries q ON q.locale_id = dl.locale_id
+        INNER JOIN documents d ON d.id = dl.document_id
+        WHERE dl.search_vector @@ q.query
+          AND d.site_id = $1
+          AND 'document' = ANY($4)
+    )
+"#;
+
+/// Highlight delimiters passed to `ts_headline`. Private-use code points that
+/// cannot collide with real text; they are swapped for `<mark>` tags after the
+/// text has been HTML-escaped.
+const HIGHLIGHT_START: char = '\u{E000}';
+const HIGHLIGHT_STOP: char = '\u{E001}';
+
+/// HTML-escape a `ts_headline` result and turn its delimiters into `<mark>` tags
+fn render_highlight(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len() + 16);
+    for c in raw.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<mark>"),
+            HIGHLIGHT_STOP => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            _ => html.push(c),
+        }
+    }
+    html
+}
+
+/// A single ranked search hit with highlighted title and snippet
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SearchHit {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub content_id: Option<Uuid>,
+    pub slug: Option<String>,
+    pub route: Option<String>,
+    pub status: Option<ContentStatus>,
+    pub locale_id: Uuid,
+    pub locale_code: String,
+    pub title: String,
+    pub title_highlight: String,
+    pub snippet: String,
+    pub rank: f32,
+}
+
+impl SearchHit {
+    /// Search a site, best matches first (paginated)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        query: &str,
+        locale_id: Option<Uuid>,
+        entity_types: &[String],
+        include_unpublished: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        // Highlighting is expensive, so it only runs on the requested page
+        let sql = format!(
+            r#"{SEARCH_HITS_CTE}
+            SELECT entity_type, entity_id, content_id, slug, route, status,
+                   locale_id, locale_code, title,
+                   ts_headline(config, title, query,
+                       'HighlightAll=true, StartSel={HIGHLIGHT_START}, StopSel={HIGHLIGHT_STOP}') AS title_highlight,
+                   ts_headline(config, snippet_source, query,
+                       'StartSel={HIGHLIGHT_START}, StopSel={HIGHLIGHT_STOP}, MaxWords=35, MinWords=15, MaxFragments=2, FragmentDelimiter=" … "') AS snippet,
+                   rank
+            FROM (
+                SELECT * FROM hits
+                ORDER BY rank DESC, title, entity_id
+                LIMIT $6 OFFSET $7
+            ) page
+            ORDER BY rank DESC, title, entity_id
+            "#
+        );
+
+        let mut hits = sqlx::query_as::<_, Self>(&sql)
+            .bind(site_id)
+            .bind(query)
+            .bind(locale_id)
+            .bind(entity_types)
+            .bind(include_unpublished)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        // Titles and bodies are raw text; escape them so only our <mark> tags are HTML
+        for hit in &mut hits {
+            hit.title_highlight = render_highlight(&hit.title_highlight);
+            hit.snippet = render_highlight(&hit.snippet);
+        }
+
+        Ok(hits)
+    }
+
+    /// Count search hits with the same filters as `search_for_site`
+    pub async fn count_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        query: &str,
+        locale_id: Option<Uuid>,
+        entity_types: &[String],
+        include_unpublished: bool,
+    ) -> Result<i64, ApiError> {
+        let sql = format!("{SEARCH_HITS_CTE} SELECT COUNT(*) FROM hits");
+
+        let row: (i64,) = sqlx::query_as(&sql)
+            .bind(site_id)
+            .bind(query)
+            .bind(locale_id)
+            .bind(entity_types)
+            .bind(include_unpublished)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(row.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_highlight_escapes_text_and_keeps_marks() {
+        let raw = format!("<b>{HIGHLIGHT_START}Run{HIGHLIGHT_STOP}</b> & \"walk\"");
+        assert_eq!(
+            render_highlight(&raw),
+            "&lt;b&gt;<mark>Run</mark>&lt;/b&gt; &amp; &quot;walk&quot;"
+        );
+    }
+}
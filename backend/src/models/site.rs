@@ -100,6 +100,30 @@ impl Site {
         Ok(site)
     }
 
+    /// Base URL (`https://domain`) of the site's primary production domain, if one is configured
+    pub async fn primary_base_url(
+        pool: &PgPool,
+        site_id: Uuid,
+    ) -> Result<Option<String>, ApiError> {
+        let row: Option<(String, bool)> = sqlx::query_as(
+            r#"
+            SELECT domain::text, ssl_enabled
+            FROM site_domains
+            WHERE site_id = $1 AND is_primary = TRUE AND is_active = TRUE
+              AND environment = 'production'
+            LIMIT 1
+            "#,
+        )
+        .bind(site_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|(domain, ssl)| {
+            let scheme = if ssl { "https" } else { "http" };
+            format!("{scheme}://{domain}")
+        }))
+    }
+
     /// Create a new site
     pub async fn create(
         pool: &PgPool,
@@ -37,6 +37,19 @@ pub struct SiteLocaleWithDetails {
 }
 
 impl SiteLocale {
+    /// Prefix a public path with a locale `url_prefix` (`/about` → `/de/about`, `/` → `/de/`)
+    pub fn localize_path(url_prefix: Option<&str>, path: &str) -> String {
+        let path = format!("/{}", path.trim_start_matches('/'));
+        match url_prefix
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty())
+        {
+            Some(prefix) if path == "/" => format!("/{prefix}/"),
+            Some(prefix) => format!("/{prefix}{path}"),
+            None => path,
+        }
+    }
+
     /// Find all locales for a site with locale details
     pub async fn find_all_for_site(
         pool: &PgPool,
@@ -320,6 +333,18 @@ impl SiteLocale {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_localize_path() {
+        assert_eq!(SiteLocale::localize_path(None, "/about"), "/about");
+        assert_eq!(SiteLocale::localize_path(Some(""), "about"), "/about");
+        assert_eq!(SiteLocale::localize_path(Some("de"), "/about"), "/de/about");
+        assert_eq!(
+            SiteLocale::localize_path(Some("/de/"), "/about"),
+            "/de/about"
+        );
+        assert_eq!(SiteLocale::localize_path(Some("de"), "/"), "/de/");
+    }
+
     #[test]
     fn test_site_locale_struct_fields() {
         let now = Utc::now();
@@ -17,6 +17,9 @@ pub const KEY_CONTACT_EMAIL: &str = "contact_email";
 pub const KEY_POSTS_PER_PAGE: &str = "posts_per_page";
 pub const KEY_EDITORIAL_WORKFLOW_ENABLED: &str = "editorial_workflow_enabled";
 pub const KEY_PREVIEW_TEMPLATES: &str = "preview_templates";
+pub const KEY_AVIF_QUALITY: &str = "avif_quality";
+pub const KEY_AVIF_SPEED: &str = "avif_speed";
+pub const KEY_BLOG_PATH_PREFIX: &str = "blog_path_prefix";
 
 /// Returns the known defaults as a HashMap.
 pub fn defaults() -> HashMap<String, serde_json::Value> {
@@ -38,6 +41,9 @@ pub fn defaults() -> HashMap<String, serde_json::Value> {
         serde_json::json!(false),
     );
     m.insert(KEY_PREVIEW_TEMPLATES.into(), serde_json::json!([]));
+    m.insert(KEY_AVIF_QUALITY.into(), serde_json::json!(60));
+    m.insert(KEY_AVIF_SPEED.into(), serde_json::json!(8));
+    m.insert(KEY_BLOG_PATH_PREFIX.into(), serde_json::json!("/blog"));
     m
 }
 
@@ -147,7 +153,7 @@ mod tests {
     #[test]
     fn test_defaults_contains_all_keys() {
         let d = defaults();
-        assert_eq!(d.len(), 8);
+        assert_eq!(d.len(), 11);
         assert!(d.contains_key(KEY_MAX_DOCUMENT_FILE_SIZE));
         assert!(d.contains_key(KEY_MAX_MEDIA_FILE_SIZE));
         assert!(d.contains_key(KEY_ANALYTICS_ENABLED));
@@ -156,6 +162,9 @@ mod tests {
         assert!(d.contains_key(KEY_POSTS_PER_PAGE));
         assert!(d.contains_key(KEY_EDITORIAL_WORKFLOW_ENABLED));
         assert!(d.contains_key(KEY_PREVIEW_TEMPLATES));
+        assert!(d.contains_key(KEY_AVIF_QUALITY));
+        assert!(d.contains_key(KEY_AVIF_SPEED));
+        assert!(d.contains_key(KEY_BLOG_PATH_PREFIX));
     }
 
     #[test]
@@ -169,6 +178,9 @@ mod tests {
         assert_eq!(d[KEY_POSTS_PER_PAGE], serde_json::json!(10));
         assert_eq!(d[KEY_EDITORIAL_WORKFLOW_ENABLED], serde_json::json!(false));
         assert_eq!(d[KEY_PREVIEW_TEMPLATES], serde_json::json!([]));
+        assert_eq!(d[KEY_AVIF_QUALITY], serde_json::json!(60));
+        assert_eq!(d[KEY_AVIF_SPEED], serde_json::json!(8));
+        assert_eq!(d[KEY_BLOG_PATH_PREFIX], serde_json::json!("/blog"));
     }
 
     #[test]
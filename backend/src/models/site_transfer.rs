@@ -0,0 +1,272 @@
+//! Site transfer job model
+//!
+//! Background jobs that export a site to an archive or import an archive as a
+//! new site, with progress reporting.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Direction of a site transfer
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "site_transfer_kind", rename_all = "lowercase")]
+pub enum SiteTransferKind {
+    Export,
+    Import,
+}
+
+/// Lifecycle state of a site transfer job
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "site_transfer_status", rename_all = "lowercase")]
+pub enum SiteTransferStatus {
+    /// Accepted, not started yet
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Site transfer job model (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SiteTransferJob {
+    pub id: Uuid,
+    pub kind: SiteTransferKind,
+    pub site_id: Option<Uuid>,
+    pub status: SiteTransferStatus,
+    pub stage: Option<String>,
+    pub completed_steps: i32,
+    pub total_steps: i32,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    /// Storage backend key of the export archive or the uploaded import archive
+    pub archive_path: Option<String>,
+    /// Server process running the job
+    pub owner_instance: Option<Uuid>,
+    /// Last sign of life from the owner while the job is queued or running
+    pub heartbeat_at: DateTime<Utc>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl SiteTransferJob {
+    /// Find a job by ID
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Self, ApiError> {
+        let job = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, kind, site_id, status, stage, completed_steps, total_steps, result,
+                   error, archive_path, owner_instance, heartbeat_at, created_by, created_at,
+                   started_at, finished_at
+            FROM site_transfer_jobs
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Site transfer with ID {} not found", id)))?;
+
+        Ok(job)
+    }
+
+    /// Queue a new job run by `owner_instance`
+    pub async fn create(
+        pool: &PgPool,
+        kind: SiteTransferKind,
+        site_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+        owner_instance: Uuid,
+    ) -> Result<Self, ApiError> {
+        let job = sqlx::query_as::<_, Self>(
+            r#"
+            INSERT INTO site_transfer_jobs (kind, site_id, created_by, owner_instance)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, kind, site_id, status, stage, completed_steps, total_steps, result,
+                      error, archive_path, owner_instance, heartbeat_at, created_by, created_at,
+                      started_at, finished_at
+            "#,
+        )
+        .bind(kind)
+        .bind(site_id)
+        .bind(created_by)
+        .bind(owner_instance)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// Mark a job as running
+    pub async fn start(pool: &PgPool, id: Uuid, total_steps: i32) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE site_transfer_jobs
+            SET status = 'running', total_steps = $2, started_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(total_steps)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record progress of a running job
+    pub async fn update_progress(
+        pool: &PgPool,
+        id: Uuid,
+        stage: &str,
+        completed_steps: i32,
+    ) -> Result<(), ApiError> {
+        sqlx::query("UPDATE site_transfer_jobs SET stage = $2, completed_steps = $3 WHERE id = $1")
+            .bind(id)
+            .bind(stage)
+            .bind(completed_steps)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Refresh the heartbeat of a queued or running job
+    pub async fn heartbeat(pool: &PgPool, id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE site_transfer_jobs SET heartbeat_at = NOW()
+            WHERE id = $1 AND status IN ('queued', 'running')
+            "#,
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record or clear the storage key of the job's archive
+    pub async fn set_archive(
+        pool: &PgPool,
+        id: Uuid,
+        archive_path: Option<&str>,
+    ) -> Result<(), ApiError> {
+        sqlx::query("UPDATE site_transfer_jobs SET archive_path = $2 WHERE id = $1")
+            .bind(id)
+            .bind(archive_path)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Attach the site created by an import
+    pub async fn set_site(pool: &PgPool, id: Uuid, site_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("UPDATE site_transfer_jobs SET site_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(site_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mark a job as completed with its result
+    pub async fn complete(
+        pool: &PgPool,
+        id: Uuid,
+        result: &serde_json::Value,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE site_transfer_jobs
+            SET status = 'completed', stage = NULL, completed_steps = total_steps,
+                result = $2, finished_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(result)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark queued or running jobs without a heartbeat for `stale_after` as failed.
+    /// Returns the failed jobs with the instance that owned them.
+    pub async fn fail_stale(
+        pool: &PgPool,
+        stale_after: Duration,
+    ) -> Result<Vec<(Uuid, Option<Uuid>)>, ApiError> {
+        let failed = sqlx::query_as(
+            r#"
+            UPDATE site_transfer_jobs
+            SET status = 'failed', error = 'Interrupted: the server running it stopped',
+                finished_at = NOW()
+            WHERE status IN ('queued', 'running')
+              AND heartbeat_at < NOW() - make_interval(secs => $1)
+            RETURNING id, owner_instance
+            "#,
+        )
+        .bind(stale_after.as_secs_f64())
+        .fetch_all(pool)
+        .await?;
+
+        Ok(failed)
+    }
+
+    /// Detach the archives of jobs finished more than `max_age` ago and return
+    /// their storage keys, so that exactly one caller deletes each of them
+    pub async fn take_expired_archives(
+        pool: &PgPool,
+        max_age: Duration,
+    ) -> Result<Vec<String>, ApiError> {
+        let paths = sqlx::query_scalar(
+            r#"
+            WITH expired AS (
+                SELECT id, archive_path FROM site_transfer_jobs
+                WHERE archive_path IS NOT NULL
+                  AND finished_at < NOW() - make_interval(secs => $1)
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE site_transfer_jobs j SET archive_path = NULL
+            FROM expired e
+            WHERE j.id = e.id
+            RETURNING e.archive_path
+            "#,
+        )
+        .bind(max_age.as_secs_f64())
+        .fetch_all(pool)
+        .await?;
+
+        Ok(paths)
+    }
+
+    /// Mark a job as failed
+    pub async fn fail(pool: &PgPool, id: Uuid, error: &str) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE site_transfer_jobs
+            SET status = 'failed', error = $2, finished_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
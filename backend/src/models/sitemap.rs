@@ -0,0 +1,64 @@
+//! Sitemap model
+//!
+//! One row per localization of a live page or blog post, in an active locale
+//! of the site.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// A localized, currently published page or blog post
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SitemapEntry {
+    pub content_id: Uuid,
+    /// `page` or `blog`
+    pub entity_type: String,
+    /// Page route (pages only)
+    pub route: Option<String>,
+    pub slug: Option<String>,
+    pub locale_code: String,
+    pub url_prefix: Option<String>,
+    pub is_default_locale: bool,
+    pub lastmod: DateTime<Utc>,
+}
+
+impl SitemapEntry {
+    /// Published pages and blogs inside their publish window, grouped by content
+    pub async fn find_for_site(pool: &PgPool, site_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let entries = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT c.id AS content_id, et.name::text AS entity_type, p.route,
+                   COALESCE(cs.site_specific_slug, c.slug) AS slug,
+                   l.code AS locale_code, sl.url_prefix, sl.is_default AS is_default_locale,
+                   GREATEST(c.updated_at, cl.updated_at,
+                            COALESCE(p.updated_at, b.updated_at, c.updated_at)) AS lastmod
+            FROM contents c
+            INNER JOIN entity_types et ON et.id = c.entity_type_id
+            INNER JOIN content_sites cs ON cs.content_id = c.id AND cs.site_id = $1
+            INNER JOIN content_localizations cl ON cl.content_id = c.id
+            INNER JOIN site_locales sl
+                ON sl.site_id = $1 AND sl.locale_id = cl.locale_id AND sl.is_active = TRUE
+            INNER JOIN locales l ON l.id = cl.locale_id
+            LEFT JOIN pages p ON p.content_id = c.id
+            LEFT JOIN blogs b ON b.content_id = c.id
+            WHERE et.name IN ('page', 'blog')
+              AND c.is_deleted = FALSE
+              AND c.environment_id = (SELECT id FROM environments WHERE is_default)
+              AND c.status = 'published'
+              AND (c.publish_start IS NULL OR c.publish_start <= NOW())
+              AND (c.publish_end IS NULL OR c.publish_end > NOW())
+              AND (p.id IS NOT NULL OR b.id IS NOT NULL)
+            ORDER BY et.name DESC, COALESCE(p.route, cs.site_specific_slug, c.slug), c.id,
+                     sl.is_default DESC, l.code
+            "#,
+        )
+        .bind(site_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
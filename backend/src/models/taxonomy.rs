@@ -181,6 +181,82 @@ impl Tag {
         Ok(tag)
     }
 
+    /// Link an existing tag to a site (no-op if already linked)
+    pub async fn add_to_site(pool: &PgPool, tag_id: Uuid, site_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO tag_sites (tag_id, site_id, is_owner)
+            VALUES ($1, $2, FALSE)
+            ON CONFLICT (tag_id, site_id) DO NOTHING
+            "#,
+        )
+        .bind(tag_id)
+        .bind(site_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Add a localized name unless the tag already has one for the locale
+    pub async fn add_localization(
+        pool: &PgPool,
+        tag_id: Uuid,
+        locale_id: Uuid,
+        name: &str,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO tag_localizations (tag_id, locale_id, name)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (tag_id, locale_id) DO NOTHING
+            "#,
+        )
+        .bind(tag_id)
+        .bind(locale_id)
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Assign a tag to content (no-op if already assigned)
+    pub async fn assign_to_content(
+        pool: &PgPool,
+        content_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO content_tags (content_id, tag_id)
+            VALUES ($1, $2)
+            ON CONFLICT (content_id, tag_id) DO NOTHING
+            "#,
+        )
+        .bind(content_id)
+        .bind(tag_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a tag from content (no-op if not assigned)
+    pub async fn remove_from_content(
+        pool: &PgPool,
+        content_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), ApiError> {
+        sqlx::query("DELETE FROM content_tags WHERE content_id = $1 AND tag_id = $2")
+            .bind(content_id)
+            .bind(tag_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
     /// Update a tag
     pub async fn update(pool: &PgPool, id: Uuid, req: &UpdateTagRequest) -> Result<Self, ApiError> {
         let tag = sqlx::query_as::<_, Self>(
@@ -355,6 +431,73 @@ impl Category {
         Ok(category)
     }
 
+    /// Find a category by slug under a parent (`None` for top level)
+    pub async fn find_by_slug(
+        pool: &PgPool,
+        parent_id: Option<Uuid>,
+        slug: &str,
+    ) -> Result<Option<Self>, ApiError> {
+        let category = sqlx::query_as::<_, Self>(
+            r#"
+            SELECT id, parent_id, slug, is_global, is_active, created_at
+            FROM categories
+            WHERE parent_id IS NOT DISTINCT FROM $1 AND slug = $2
+            "#,
+        )
+        .bind(parent_id)
+        .bind(slug)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(category)
+    }
+
+    /// Link an existing category to a site (no-op if already linked)
+    pub async fn add_to_site(
+        pool: &PgPool,
+        category_id: Uuid,
+        site_id: Uuid,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO category_sites (category_id, site_id, is_owner)
+            VALUES ($1, $2, FALSE)
+            ON CONFLICT (category_id, site_id) DO NOTHING
+            "#,
+        )
+        .bind(category_id)
+        .bind(site_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Add a localized name unless the category already has one for the locale
+    pub async fn add_localization(
+        pool: &PgPool,
+        category_id: Uuid,
+        locale_id: Uuid,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO category_localizations (category_id, locale_id, name, description)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (category_id, locale_id) DO NOTHING
+            "#,
+        )
+        .bind(category_id)
+        .bind(locale_id)
+        .bind(name)
+        .bind(description)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Update a category
     pub async fn update(
         pool: &PgPool,
@@ -459,6 +602,7 @@ impl Category {
             INNER JOIN category_sites cs ON c.id = cs.category_id
             LEFT JOIN content_categories cc ON c.id = cc.category_id
             LEFT JOIN contents co ON cc.content_id = co.id
+                AND co.environment_id = (SELECT id FROM environments WHERE is_default)
             LEFT JOIN blogs b ON b.content_id = co.id
             WHERE cs.site_id = $1 AND c.is_active = TRUE
             GROUP BY c.id, c.parent_id, c.slug, c.is_global, c.is_active, c.created_at
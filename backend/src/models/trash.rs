@@ -0,0 +1,229 @@
+//! Trash model
+//!
+//! Soft-deleted content and media owned by a site, and the queries that remove
+//! them for good. Content is listed by its content ID and media by its file ID.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// What a trash entry is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub enum TrashItemKind {
+    /// Blog, page, CV entry or legal document
+    Content,
+    Media,
+}
+
+impl TrashItemKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Content => "content",
+            Self::Media => "media",
+        }
+    }
+}
+
+/// Trash entry (database row)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TrashItem {
+    /// Content ID or media file ID
+    pub id: Uuid,
+    /// `blog`, `page`, `cv_entry`, `legal_document` or `media`
+    pub entity_type: String,
+    /// Blog, page, CV entry, legal document or media file ID
+    pub entity_id: Uuid,
+    /// First localized title, or the original file name
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub route: Option<String>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl TrashItem {
+    /// Kind of entry
+    pub fn kind(&self) -> TrashItemKind {
+        if self.entity_type == "media" {
+            TrashItemKind::Media
+        } else {
+            TrashItemKind::Content
+        }
+    }
+}
+
+/// Deleted content and media owned by a site, optionally of one kind.
+/// Binds `$1` = site ID and `$2` = kind (`content`, `media` or NULL).
+const TRASH_QUERY: &str = r#"
+    SELECT c.id, et.name::text AS entity_type,
+           COALESCE(b.id, p.id, cv.id, ld.id, c.id) AS entity_id,
+           (SELECT cl.title FROM content_localizations cl
+            WHERE cl.content_id = c.id ORDER BY cl.created_at LIMIT 1) AS title,
+           COALESCE(cs.site_specific_slug, c.slug)::text AS slug,
+           p.route, c.deleted_at
+    FROM contents c
+    INNER JOIN content_sites cs ON cs.content_id = c.id AND cs.site_id = $1 AND cs.is_owner = TRUE
+    INNER JOIN entity_types et ON et.id = c.entity_type_id
+    LEFT JOIN blogs b ON b.content_id = c.id
+    LEFT JOIN pages p ON p.content_id = c.id
+    LEFT JOIN cv_entries cv ON cv.content_id = c.id
+    LEFT JOIN legal_documents ld ON ld.content_id = c.id
+    WHERE c.is_deleted = TRUE AND ($2::text IS NULL OR $2 = 'content')
+    UNION ALL
+    SELECT m.id, 'media' AS entity_type, m.id AS entity_id, m.original_filename AS title,
+           NULL AS slug, NULL AS route, m.deleted_at
+    FROM media_files m
+    INNER JOIN media_sites ms ON ms.media_file_id = m.id AND ms.site_id = $1 AND ms.is_owner = TRUE
+    WHERE m.is_deleted = TRUE AND ($2::text IS NULL OR $2 = 'media')
+"#;
+
+impl TrashItem {
+    /// Find the trash of a site, most recently deleted first (paginated)
+    pub async fn find_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        kind: Option<TrashItemKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, ApiError> {
+        let items = sqlx::query_as::<_, Self>(&format!(
+            "{TRASH_QUERY} ORDER BY deleted_at DESC NULLS LAST, id LIMIT $3 OFFSET $4"
+        ))
+        .bind(site_id)
+        .bind(kind.map(TrashItemKind::as_str))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Count the trash of a site
+    pub async fn count_for_site(
+        pool: &PgPool,
+        site_id: Uuid,
+        kind: Option<TrashItemKind>,
+    ) -> Result<i64, ApiError> {
+        let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM ({TRASH_QUERY}) t"))
+            .bind(site_id)
+            .bind(kind.map(TrashItemKind::as_str))
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Find one entry in the trash of a site
+    pub async fn find(
+        pool: &PgPool,
+        site_id: Uuid,
+        kind: TrashItemKind,
+        id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let item =
+            sqlx::query_as::<_, Self>(&format!("SELECT * FROM ({TRASH_QUERY}) t WHERE id = $3"))
+                .bind(site_id)
+                .bind(kind.as_str())
+                .bind(id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!(
+                        "Deleted item with ID {} not found in the trash",
+                        id
+                    ))
+                })?;
+
+        Ok(item)
+    }
+
+    /// IDs of deleted content and media files whose deletion is older than `retention_days`
+    pub async fn find_expired(
+        pool: &PgPool,
+        retention_days: u32,
+    ) -> Result<(Vec<Uuid>, Vec<Uuid>), ApiError> {
+        let content_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM contents
+            WHERE is_deleted = TRUE AND deleted_at < NOW() - make_interval(days => $1)
+            "#,
+        )
+        .bind(retention_days as i32)
+        .fetch_all(pool)
+        .await?;
+
+        let media_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM media_files
+            WHERE is_deleted = TRUE AND deleted_at < NOW() - make_interval(days => $1)
+            "#,
+        )
+        .bind(retention_days as i32)
+        .fetch_all(pool)
+        .await?;
+
+        Ok((content_ids, media_ids))
+    }
+
+    /// Hard-delete soft-deleted content; dependent rows cascade
+    pub async fn purge_content(pool: &PgPool, ids: &[Uuid]) -> Result<u64, ApiError> {
+        let result = sqlx::query("DELETE FROM contents WHERE id = ANY($1) AND is_deleted = TRUE")
+            .bind(ids)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Hard-delete soft-deleted media files with their variants and cached
+    /// transformations.
+    ///
+    /// Returns the number of files deleted and the storage paths that are no
+    /// longer used by any remaining media file or variant.
+    pub async fn purge_media(pool: &PgPool, ids: &[Uuid]) -> Result<(u64, Vec<String>), ApiError> {
+        let mut tx = pool.begin().await?;
+
+        let paths: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT storage_path FROM media_files WHERE id = ANY($1) AND is_deleted = TRUE
+            UNION
+            SELECT v.storage_path FROM media_variants v
+            INNER JOIN media_files m ON m.id = v.media_file_id
+            WHERE m.id = ANY($1) AND m.is_deleted = TRUE
+            UNION
+            SELECT t.storage_path FROM media_transforms t
+            INNER JOIN media_files m ON m.id = t.media_file_id
+            WHERE m.id = ANY($1) AND m.is_deleted = TRUE
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let result =
+            sqlx::query("DELETE FROM media_files WHERE id = ANY($1) AND is_deleted = TRUE")
+                .bind(ids)
+                .execute(&mut *tx)
+                .await?;
+
+        // A later upload with the same name may have reused a path
+        let in_use: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT storage_path FROM media_files WHERE storage_path = ANY($1)
+            UNION
+            SELECT storage_path FROM media_variants WHERE storage_path = ANY($1)
+            "#,
+        )
+        .bind(&paths)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let paths = paths.into_iter().filter(|p| !in_use.contains(p)).collect();
+        Ok((result.rows_affected(), paths))
+    }
+}
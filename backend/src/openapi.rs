@@ -28,20 +28,37 @@ use utoipa::{Modify, OpenApi};
         (name = "CV", description = "CV/Resume entries and skills"),
         (name = "Legal", description = "Legal documents and consent management"),
         (name = "Media", description = "Media file management"),
+        (name = "Trash", description = "Restore or purge deleted content and media"),
         (name = "Navigation", description = "Navigation structure management"),
         (name = "Social Links", description = "Social media links"),
         (name = "Taxonomy", description = "Tags and categories"),
         (name = "Environments", description = "Environment configuration"),
+        (name = "Promotions", description = "Promote content between environments"),
+        (name = "Releases", description = "Publish sets of content and navigation changes together"),
         (name = "Locales", description = "Locale/language management"),
         (name = "Site Locales", description = "Per-site language/locale management"),
         (name = "Site Members", description = "Site membership management"),
         (name = "Users", description = "User management"),
         (name = "Audit", description = "Audit logs and change history"),
         (name = "Site Settings", description = "Per-site settings management"),
+        (name = "Site Transfers", description = "Site export and import archives"),
+        (name = "WordPress Import", description = "Import WordPress export (WXR) files"),
+        (name = "Markdown", description = "Export and import blogs and pages as Markdown files with YAML front matter"),
         (name = "Notifications", description = "In-app notification management"),
         (name = "Webhooks", description = "Webhook subscription management"),
         (name = "Redirects", description = "URL redirect management"),
         (name = "Content Templates", description = "Content template management"),
+        (name = "Page Templates", description = "Page templates with predefined section layouts"),
+        (name = "Syndication", description = "Share content across sites with per-site slugs"),
+        (name = "Content Blocks", description = "Structured block editor for content localizations"),
+        (name = "Versions", description = "Content version history"),
+        (name = "Search", description = "Full-text content search"),
+        (name = "Preview Tokens", description = "Shareable draft preview tokens"),
+        (name = "Edit Locks", description = "Advisory locks showing who is editing content"),
+        (name = "Comments", description = "Threaded reader comments on blog posts and their moderation"),
+        (name = "Forms", description = "Form definitions, public submissions and the submissions inbox"),
+        (name = "Global Sections", description = "Reusable sections shared across pages"),
+        (name = "Sitemap", description = "XML sitemaps with hreflang alternates"),
         (name = "API Keys", description = "API key management (requires master key)")
     ),
     paths(
@@ -63,6 +80,16 @@ use utoipa::{Modify, OpenApi};
         // Site Settings
         crate::handlers::site_settings::get_site_settings,
         crate::handlers::site_settings::update_site_settings,
+        // Site Transfers
+        crate::handlers::site_transfer::export_site,
+        crate::handlers::site_transfer::import_site,
+        crate::handlers::site_transfer::get_site_transfer,
+        crate::handlers::site_transfer::download_site_export,
+        // WordPress Import
+        crate::handlers::wordpress_import::import_wordpress,
+        // Markdown
+        crate::handlers::markdown_transfer::export_markdown,
+        crate::handlers::markdown_transfer::import_markdown,
         // Blogs
         crate::handlers::blog::list_blogs,
         crate::handlers::blog::list_published_blogs,
@@ -85,6 +112,8 @@ use utoipa::{Modify, OpenApi};
         crate::handlers::page::list_pages,
         crate::handlers::page::get_page,
         crate::handlers::page::get_page_by_route,
+        crate::handlers::page::get_page_tree,
+        crate::handlers::page::get_page_breadcrumbs,
         crate::handlers::page::get_page_sections,
         crate::handlers::page::create_page,
         crate::handlers::page::update_page,
@@ -92,11 +121,14 @@ use utoipa::{Modify, OpenApi};
         crate::handlers::page::create_page_section,
         crate::handlers::page::update_page_section,
         crate::handlers::page::delete_page_section,
+        crate::handlers::page::attach_global_section,
+        crate::handlers::page::detach_page_section,
         crate::handlers::page::get_section_localizations,
         crate::handlers::page::get_page_section_localizations,
         crate::handlers::page::upsert_section_localization,
         crate::handlers::page::delete_section_localization,
         crate::handlers::page::clone_page,
+        crate::handlers::page::move_page,
         crate::handlers::page::review_page,
         crate::handlers::page::bulk_pages,
         // CV
@@ -133,7 +165,15 @@ use utoipa::{Modify, OpenApi};
         crate::handlers::media::create_media,
         crate::handlers::media::upload_media,
         crate::handlers::media::update_media,
+        crate::handlers::media::update_media_art_direction,
         crate::handlers::media::delete_media,
+        crate::handlers::media::get_media_transform_url,
+        crate::handlers::media::transform_media,
+        // Trash
+        crate::handlers::trash::list_trash,
+        crate::handlers::trash::restore_trash_item,
+        crate::handlers::trash::purge_trash_item,
+        crate::handlers::trash::empty_trash,
         // Navigation
         crate::handlers::navigation::list_navigation,
         crate::handlers::navigation::list_menu_items,
@@ -184,6 +224,23 @@ use utoipa::{Modify, OpenApi};
         crate::handlers::environment::list_environments,
         crate::handlers::environment::get_environment,
         crate::handlers::environment::get_default_environment,
+        // Promotions
+        crate::handlers::promotion::preview_promotion,
+        crate::handlers::promotion::apply_promotion,
+        crate::handlers::promotion::list_promotions,
+        crate::handlers::promotion::get_promotion,
+        // Releases
+        crate::handlers::release::list_releases,
+        crate::handlers::release::create_release,
+        crate::handlers::release::get_release,
+        crate::handlers::release::update_release,
+        crate::handlers::release::delete_release,
+        crate::handlers::release::add_release_item,
+        crate::handlers::release::remove_release_item,
+        crate::handlers::release::schedule_release,
+        crate::handlers::release::unschedule_release,
+        crate::handlers::release::publish_release,
+        crate::handlers::release::rollback_release,
         // Locales
         crate::handlers::locale::list_locales,
         crate::handlers::locale::get_locale,
@@ -243,12 +300,81 @@ use utoipa::{Modify, OpenApi};
         crate::handlers::redirect::update_redirect,
         crate::handlers::redirect::delete_redirect,
         crate::handlers::redirect::lookup_redirect,
+        crate::handlers::redirect::import_redirects,
+        crate::handlers::redirect::export_redirects,
         // Content Templates
         crate::handlers::content_template::list_content_templates,
         crate::handlers::content_template::get_content_template,
         crate::handlers::content_template::create_content_template,
         crate::handlers::content_template::update_content_template,
         crate::handlers::content_template::delete_content_template,
+        // Page Templates
+        crate::handlers::page_template::list_page_templates,
+        crate::handlers::page_template::get_page_template,
+        crate::handlers::page_template::create_page_template,
+        crate::handlers::page_template::update_page_template,
+        crate::handlers::page_template::delete_page_template,
+        crate::handlers::page_template::create_page_from_template,
+        // Syndication
+        crate::handlers::content_site::list_content_sites,
+        crate::handlers::content_site::share_content,
+        crate::handlers::content_site::update_content_site,
+        crate::handlers::content_site::unshare_content,
+        // Content Blocks
+        crate::handlers::content_block::list_block_schemas,
+        crate::handlers::content_block::list_content_blocks,
+        crate::handlers::content_block::create_content_block,
+        crate::handlers::content_block::get_content_block,
+        crate::handlers::content_block::update_content_block,
+        crate::handlers::content_block::reorder_content_blocks,
+        crate::handlers::content_block::delete_content_block,
+        // Content Versions
+        crate::handlers::content_version::list_content_versions,
+        crate::handlers::content_version::get_content_version,
+        crate::handlers::content_version::diff_content_versions,
+        crate::handlers::content_version::restore_content_version,
+        // Search
+        crate::handlers::search::search_site,
+        // Preview Tokens
+        crate::handlers::preview_token::create_preview_token,
+        crate::handlers::preview_token::list_preview_tokens,
+        crate::handlers::preview_token::revoke_preview_token,
+        // Edit Locks
+        crate::handlers::edit_lock::get_edit_lock,
+        crate::handlers::edit_lock::acquire_edit_lock,
+        crate::handlers::edit_lock::heartbeat_edit_lock,
+        crate::handlers::edit_lock::release_edit_lock,
+        // Comments
+        crate::handlers::comment::submit_comment,
+        crate::handlers::comment::list_blog_comments,
+        crate::handlers::comment::list_site_comments,
+        crate::handlers::comment::get_comment,
+        crate::handlers::comment::moderate_comment,
+        crate::handlers::comment::delete_comment,
+        crate::handlers::comment::bulk_comments,
+        // Forms
+        crate::handlers::form::list_forms,
+        crate::handlers::form::get_form,
+        crate::handlers::form::create_form,
+        crate::handlers::form::update_form,
+        crate::handlers::form::delete_form,
+        crate::handlers::form::get_public_form,
+        crate::handlers::form::submit_form,
+        crate::handlers::form::list_form_submissions,
+        crate::handlers::form::export_form_submissions,
+        crate::handlers::form::delete_form_submission,
+        // Global Sections
+        crate::handlers::global_section::list_global_sections,
+        crate::handlers::global_section::get_global_section,
+        crate::handlers::global_section::create_global_section,
+        crate::handlers::global_section::update_global_section,
+        crate::handlers::global_section::delete_global_section,
+        crate::handlers::global_section::get_global_section_usage,
+        crate::handlers::global_section::get_global_section_localizations,
+        crate::handlers::global_section::upsert_global_section_localization,
+        crate::handlers::global_section::delete_global_section_localization,
+        // Sitemap
+        crate::handlers::sitemap::sitemap,
         // Config
         crate::handlers::config::get_config,
     ),
@@ -281,6 +407,7 @@ use utoipa::{Modify, OpenApi};
         // Model enums
         crate::models::content::ContentStatus,
         crate::models::content::TranslationStatus,
+        crate::models::content_block::BlockType,
         crate::models::api_key::ApiKeyPermission,
         crate::models::api_key::ApiKeyStatus,
         crate::models::cv::CvEntryType,
@@ -293,6 +420,10 @@ use utoipa::{Modify, OpenApi};
         crate::models::environment::EnvironmentType,
         crate::models::locale::TextDirection,
         crate::models::audit::AuditAction,
+        crate::models::redirect::RedirectMatchType,
+        crate::models::redirect::RedirectQueryHandling,
+        crate::models::comment::CommentStatus,
+        crate::dto::form::FormFieldType,
         // Site DTOs
         crate::dto::site::CreateSiteRequest,
         crate::dto::site::UpdateSiteRequest,
@@ -301,6 +432,17 @@ use utoipa::{Modify, OpenApi};
         crate::dto::site_settings::SiteSettingsResponse,
         crate::dto::site_settings::UpdateSiteSettingsRequest,
         crate::dto::site_settings::PreviewTemplate,
+        // Site Transfer DTOs
+        crate::dto::site_transfer::SiteTransferReport,
+        crate::dto::site_transfer::SiteTransferJobResponse,
+        crate::models::site_transfer::SiteTransferKind,
+        crate::models::site_transfer::SiteTransferStatus,
+        // WordPress Import DTOs
+        crate::dto::wordpress_import::WordPressImportReport,
+        crate::dto::wordpress_import::WordPressImportCount,
+        // Markdown DTOs
+        crate::dto::markdown_transfer::MarkdownImportReport,
+        crate::dto::markdown_transfer::MarkdownImportCount,
         // Bulk DTOs
         crate::dto::bulk::BulkAction,
         crate::dto::bulk::BulkContentRequest,
@@ -326,6 +468,12 @@ use utoipa::{Modify, OpenApi};
         crate::dto::page::PageSectionResponse,
         crate::dto::page::SectionLocalizationResponse,
         crate::dto::page::UpsertSectionLocalizationRequest,
+        crate::dto::page::PageTreeNode,
+        crate::dto::page::BreadcrumbItem,
+        crate::dto::page::MovePageRequest,
+        crate::dto::page::AttachGlobalSectionRequest,
+        crate::dto::page::MovePageResponse,
+        crate::dto::page::PageRouteChange,
         // CV DTOs
         crate::dto::cv::CreateSkillRequest,
         crate::dto::cv::UpdateSkillRequest,
@@ -350,10 +498,21 @@ use utoipa::{Modify, OpenApi};
         // Media DTOs
         crate::dto::media::UploadMediaRequest,
         crate::dto::media::UpdateMediaRequest,
+        crate::dto::media::FocalPoint,
+        crate::models::media::CropRect,
+        crate::dto::media::UpdateArtDirectionRequest,
         crate::dto::media::AddMediaMetadataRequest,
         crate::dto::media::MediaListItem,
         crate::dto::media::MediaVariantResponse,
+        crate::dto::media::CropSourceResponse,
+        crate::dto::media::MediaCropResponse,
         crate::dto::media::MediaResponse,
+        crate::dto::media::TransformUrlResponse,
+        // Trash DTOs
+        crate::models::trash::TrashItemKind,
+        crate::dto::trash::TrashItemResponse,
+        crate::dto::trash::PaginatedTrash,
+        crate::dto::trash::TrashPurgeReport,
         // Navigation DTOs
         crate::dto::navigation::CreateNavigationItemRequest,
         crate::dto::navigation::UpdateNavigationItemRequest,
@@ -391,6 +550,24 @@ use utoipa::{Modify, OpenApi};
         crate::dto::environment::CreateEnvironmentRequest,
         crate::dto::environment::UpdateEnvironmentRequest,
         crate::dto::environment::EnvironmentResponse,
+        // Promotion DTOs
+        crate::dto::promotion::PromotionRequest,
+        crate::dto::promotion::PromotionAction,
+        crate::dto::promotion::PromotionArea,
+        crate::dto::promotion::PromotionItem,
+        crate::dto::promotion::PromotionChangeset,
+        crate::dto::promotion::PromotionResponse,
+        crate::dto::promotion::PaginatedPromotions,
+        // Release DTOs
+        crate::models::content_release::ContentReleaseStatus,
+        crate::dto::release::CreateReleaseRequest,
+        crate::dto::release::UpdateReleaseRequest,
+        crate::dto::release::ScheduleReleaseRequest,
+        crate::dto::release::AddReleaseItemRequest,
+        crate::dto::release::ReleaseItemResponse,
+        crate::dto::release::ReleaseResponse,
+        crate::dto::release::ReleaseListItem,
+        crate::dto::release::PaginatedReleases,
         // Locale DTOs
         crate::dto::locale::CreateLocaleRequest,
         crate::dto::locale::UpdateLocaleRequest,
@@ -437,11 +614,81 @@ use utoipa::{Modify, OpenApi};
         crate::dto::redirect::RedirectResponse,
         crate::dto::redirect::RedirectLookupResponse,
         crate::dto::redirect::PaginatedRedirects,
+        crate::dto::redirect::RedirectTransferRow,
+        crate::dto::redirect::RedirectImportIssueKind,
+        crate::dto::redirect::RedirectImportSeverity,
+        crate::dto::redirect::RedirectImportIssue,
+        crate::dto::redirect::RedirectImportReport,
         // Content Template DTOs
         crate::dto::content_template::CreateContentTemplateRequest,
         crate::dto::content_template::UpdateContentTemplateRequest,
         crate::dto::content_template::ContentTemplateResponse,
         crate::dto::content_template::PaginatedContentTemplates,
+        // Page Template DTOs
+        crate::dto::page_template::SectionPlaceholder,
+        crate::dto::page_template::PageTemplateSection,
+        crate::dto::page_template::CreatePageTemplateRequest,
+        crate::dto::page_template::UpdatePageTemplateRequest,
+        crate::dto::page_template::CreatePageFromTemplateRequest,
+        crate::dto::page_template::PageTemplateResponse,
+        crate::dto::page_template::PaginatedPageTemplates,
+        // Syndication DTOs
+        crate::dto::content_site::ShareContentRequest,
+        crate::dto::content_site::UpdateContentSiteRequest,
+        crate::dto::content_site::ContentSiteResponse,
+        // Content Block DTOs
+        crate::dto::content_block::CreateContentBlockRequest,
+        crate::dto::content_block::UpdateContentBlockRequest,
+        crate::dto::content_block::ReorderContentBlockItem,
+        crate::dto::content_block::ReorderContentBlocksRequest,
+        crate::dto::content_block::ContentBlockResponse,
+        crate::dto::content_block::LocalizationWithBlocksResponse,
+        crate::dto::content_block::BlockSchemaResponse,
+        // Content Version DTOs
+        crate::dto::content_version::ContentVersionListItem,
+        crate::dto::content_version::ContentVersionResponse,
+        crate::dto::content_version::VersionFieldChange,
+        crate::dto::content_version::VersionDiffResponse,
+        crate::dto::content_version::PaginatedContentVersions,
+        // Search DTOs
+        crate::dto::search::SearchResultResponse,
+        crate::dto::search::PaginatedSearchResults,
+        // Preview Token DTOs
+        crate::dto::preview_token::CreatePreviewTokenRequest,
+        crate::dto::preview_token::CreatePreviewTokenResponse,
+        crate::dto::preview_token::PreviewTokenResponse,
+        crate::dto::preview_token::PaginatedPreviewTokens,
+        // Edit Lock DTOs
+        crate::dto::edit_lock::AcquireEditLockRequest,
+        crate::dto::edit_lock::EditLockResponse,
+        crate::dto::edit_lock::EditLockStatusResponse,
+        // Comment DTOs
+        crate::dto::comment::CreateCommentRequest,
+        crate::dto::comment::UpdateCommentRequest,
+        crate::dto::comment::BulkCommentRequest,
+        crate::dto::comment::CommentResponse,
+        crate::dto::comment::CommentThreadResponse,
+        crate::dto::comment::CommentSubmittedResponse,
+        crate::dto::comment::PaginatedComments,
+        // Form DTOs
+        crate::dto::form::FormField,
+        crate::dto::form::CreateFormRequest,
+        crate::dto::form::UpdateFormRequest,
+        crate::dto::form::FormResponse,
+        crate::dto::form::LocalizedFormField,
+        crate::dto::form::PublicFormResponse,
+        crate::dto::form::SubmitFormRequest,
+        crate::dto::form::FormSubmittedResponse,
+        crate::dto::form::FormSubmissionResponse,
+        crate::dto::form::PaginatedForms,
+        crate::dto::form::PaginatedFormSubmissions,
+        // Global Section DTOs
+        crate::dto::global_section::CreateGlobalSectionRequest,
+        crate::dto::global_section::UpdateGlobalSectionRequest,
+        crate::dto::global_section::GlobalSectionResponse,
+        crate::dto::global_section::GlobalSectionLocalizationResponse,
+        crate::dto::global_section::GlobalSectionUsageResponse,
+        crate::dto::global_section::PaginatedGlobalSections,
         // Notification DTOs
         crate::dto::notification::NotificationResponse,
         crate::dto::notification::UnreadCountResponse,
@@ -0,0 +1,265 @@
+//! Content block service
+//!
+//! JSON schemas for each block type and validation of `block_data` against them.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::errors::ApiError;
+use crate::models::content_block::BlockType;
+use crate::utils::validation::{contains_dangerous_content, validate_json_depth};
+
+/// Every block type, in the order they are listed by the schema endpoint
+pub const BLOCK_TYPES: [BlockType; 9] = [
+    BlockType::Paragraph,
+    BlockType::Heading,
+    BlockType::Image,
+    BlockType::List,
+    BlockType::Code,
+    BlockType::Quote,
+    BlockType::Embed,
+    BlockType::Divider,
+    BlockType::Table,
+];
+
+/// Maximum nesting depth of `block_data` (tables are the deepest at 3)
+const MAX_BLOCK_DEPTH: usize = 4;
+
+lazy_static::lazy_static! {
+    static ref VALIDATORS: HashMap<BlockType, jsonschema::Validator> = BLOCK_TYPES
+        .iter()
+        .map(|t| {
+            let validator = jsonschema::options()
+                .should_validate_formats(true)
+                .build(&block_schema(*t))
+                .expect("built-in block schema must compile");
+            (*t, validator)
+        })
+        .collect();
+}
+
+/// JSON schema for the `block_data` of a block type
+pub fn block_schema(block_type: BlockType) -> Value {
+    let (properties, required): (Value, &[&str]) = match block_type {
+        BlockType::Paragraph => (
+            json!({ "text": { "type": "string", "maxLength": 20000 } }),
+            &["text"],
+        ),
+        BlockType::Heading => (
+            json!({
+                "text": { "type": "string", "minLength": 1, "maxLength": 500 },
+                "level": { "type": "integer", "minimum": 1, "maximum": 6 },
+                "anchor": { "type": "string", "pattern": "^[a-z0-9][a-z0-9-]*$", "maxLength": 100 }
+            }),
+            &["text", "level"],
+        ),
+        BlockType::Image => (
+            json!({
+                "media_id": { "type": "string", "format": "uuid" },
+                "alt": { "type": "string", "maxLength": 500 },
+                "caption": { "type": "string", "maxLength": 1000 }
+            }),
+            &["media_id"],
+        ),
+        BlockType::List => (
+            json!({
+                "style": { "enum": ["ordered", "unordered"] },
+                "items": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": 500,
+                    "items": { "type": "string", "maxLength": 2000 }
+                }
+            }),
+            &["style", "items"],
+        ),
+        BlockType::Code => (
+            json!({
+                "code": { "type": "string", "maxLength": 100000 },
+                "language": { "type": "string", "pattern": "^[A-Za-z0-9+#._-]*$", "maxLength": 50 }
+            }),
+            &["code"],
+        ),
+        BlockType::Quote => (
+            json!({
+                "text": { "type": "string", "minLength": 1, "maxLength": 5000 },
+                "attribution": { "type": "string", "maxLength": 500 }
+            }),
+            &["text"],
+        ),
+        BlockType::Embed => (
+            json!({
+                "url": { "type": "string", "format": "uri", "pattern": "^https?://", "maxLength": 2000 },
+                "provider": { "type": "string", "maxLength": 100 },
+                "caption": { "type": "string", "maxLength": 1000 }
+            }),
+            &["url"],
+        ),
+        BlockType::Divider => (json!({}), &[]),
+        BlockType::Table => (
+            json!({
+                "header": { "type": "boolean" },
+                "caption": { "type": "string", "maxLength": 1000 },
+                "rows": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": 1000,
+                    "items": {
+                        "type": "array",
+                        "maxItems": 50,
+                        "items": { "type": "string", "maxLength": 5000 }
+                    }
+                }
+            }),
+            &["rows"],
+        ),
+    };
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false
+    })
+}
+
+/// Validate `block_data` against the schema of its block type
+pub fn validate_block_data(block_type: BlockType, data: &Value) -> Result<(), ApiError> {
+    validate_json_depth(data, MAX_BLOCK_DEPTH)
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    let errors: Vec<String> = VALIDATORS[&block_type]
+        .iter_errors(data)
+        .map(|e| {
+            let path = e.instance_path.to_string();
+            if path.is_empty() {
+                e.to_string()
+            } else {
+                format!("{}: {}", path, e)
+            }
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid {:?} block data: {}",
+            block_type,
+            errors.join("; ")
+        )));
+    }
+
+    // Code blocks hold source text verbatim; everything else is rendered as content
+    if block_type != BlockType::Code && contains_dangerous_strings(data) {
+        return Err(ApiError::BadRequest(
+            "Block data contains potentially dangerous HTML or JavaScript".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn contains_dangerous_strings(value: &Value) -> bool {
+    match value {
+        Value::String(s) => contains_dangerous_content(s),
+        Value::Array(arr) => arr.iter().any(contains_dangerous_strings),
+        Value::Object(obj) => obj.values().any(contains_dangerous_strings),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_schema_compiles() {
+        for block_type in BLOCK_TYPES {
+            assert!(VALIDATORS.contains_key(&block_type));
+        }
+    }
+
+    #[test]
+    fn test_valid_blocks() {
+        let cases = [
+            (BlockType::Paragraph, json!({ "text": "Hello" })),
+            (BlockType::Heading, json!({ "text": "Intro", "level": 2 })),
+            (
+                BlockType::Image,
+                json!({ "media_id": "550e8400-e29b-41d4-a716-446655440000", "alt": "A cat" }),
+            ),
+            (
+                BlockType::List,
+                json!({ "style": "ordered", "items": ["one", "two"] }),
+            ),
+            (
+                BlockType::Code,
+                json!({ "code": "<script>alert(1)</script>", "language": "html" }),
+            ),
+            (
+                BlockType::Quote,
+                json!({ "text": "To be", "attribution": "Hamlet" }),
+            ),
+            (
+                BlockType::Embed,
+                json!({ "url": "https://www.youtube.com/watch?v=abc" }),
+            ),
+            (BlockType::Divider, json!({})),
+            (
+                BlockType::Table,
+                json!({ "header": true, "rows": [["a", "b"], ["1", "2"]] }),
+            ),
+        ];
+        for (block_type, data) in cases {
+            assert!(
+                validate_block_data(block_type, &data).is_ok(),
+                "{:?} should accept {}",
+                block_type,
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let err = validate_block_data(BlockType::Heading, &json!({ "text": "No level" }))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("level"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_out_of_range_value() {
+        let err = validate_block_data(BlockType::Heading, &json!({ "text": "x", "level": 7 }))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("/level"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_unknown_property_rejected() {
+        assert!(validate_block_data(BlockType::Divider, &json!({ "style": "dashed" })).is_err());
+    }
+
+    #[test]
+    fn test_invalid_formats_rejected() {
+        assert!(
+            validate_block_data(BlockType::Image, &json!({ "media_id": "not-a-uuid" })).is_err()
+        );
+        assert!(
+            validate_block_data(BlockType::Embed, &json!({ "url": "javascript:alert(1)" }))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_dangerous_content_rejected_outside_code() {
+        let data = json!({ "text": "<script>alert(1)</script>" });
+        assert!(validate_block_data(BlockType::Paragraph, &data).is_err());
+    }
+
+    #[test]
+    fn test_non_object_rejected() {
+        assert!(validate_block_data(BlockType::Paragraph, &json!("just a string")).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Comment service
+//!
+//! Rate limiting of public submissions, reply threading for the public list,
+//! and bulk moderation of a site's comments.
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::bulk::{BulkAction, BulkContentResponse, BulkItemResult};
+use crate::dto::comment::CommentThreadResponse;
+use crate::errors::ApiError;
+use crate::middleware::rate_limit::RateLimiter;
+use crate::models::comment::{Comment, CommentStatus};
+use crate::AppState;
+
+/// Throttle comment submissions per client IP with `SecurityConfig::comment_limits`
+pub async fn check_submission_rate(state: &AppState, ip: Option<&str>) -> Result<(), ApiError> {
+    RateLimiter::check_submission(
+        state.redis.as_ref(),
+        "comment",
+        ip,
+        &state.settings.security.comment_limits(),
+    )
+    .await
+}
+
+/// Nest approved comments under their parents, keeping creation order.
+///
+/// Replies whose parent is not in `comments` (e.g. a parent that is no longer
+/// approved) are dropped along with their own replies.
+pub fn build_threads(comments: Vec<Comment>) -> Vec<CommentThreadResponse> {
+    let mut roots = Vec::new();
+    let mut children: HashMap<Uuid, Vec<CommentThreadResponse>> = HashMap::new();
+    for comment in comments {
+        match comment.parent_id {
+            None => roots.push(CommentThreadResponse::from(comment)),
+            Some(parent_id) => children
+                .entry(parent_id)
+                .or_default()
+                .push(CommentThreadResponse::from(comment)),
+        }
+    }
+
+    fn attach(
+        mut node: CommentThreadResponse,
+        children: &mut HashMap<Uuid, Vec<CommentThreadResponse>>,
+    ) -> CommentThreadResponse {
+        node.replies = children
+            .remove(&node.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|reply| attach(reply, children))
+            .collect();
+        node
+    }
+
+    roots
+        .into_iter()
+        .map(|root| attach(root, &mut children))
+        .collect()
+}
+
+/// Apply a bulk moderation action to comments of one site.
+/// Each item is processed independently; failures don't affect other items.
+pub async fn bulk_moderate(
+    pool: &PgPool,
+    site_id: Uuid,
+    ids: &[Uuid],
+    action: &BulkAction,
+    status: Option<CommentStatus>,
+    moderated_by: Uuid,
+) -> Result<BulkContentResponse, ApiError> {
+    if matches!(action, BulkAction::UpdateStatus) && status.is_none() {
+        return Err(ApiError::BadRequest(
+            "status is required for UpdateStatus action".to_string(),
+        ));
+    }
+
+    let mut results = Vec::with_capacity(ids.len());
+    for &id in ids {
+        let outcome = match Comment::find_by_id(pool, id).await {
+            Ok(comment) if comment.site_id != site_id => Err(ApiError::NotFound(format!(
+                "Comment with ID {} not found",
+                id
+            ))),
+            Ok(_) => match (action, status) {
+                (BulkAction::UpdateStatus, Some(status)) => {
+                    Comment::update_status(pool, id, status, Some(moderated_by))
+                        .await
+                        .map(|_| ())
+                }
+                _ => Comment::purge(pool, id).await,
+            },
+            Err(e) => Err(e),
+        };
+        results.push(BulkItemResult {
+            id,
+            success: outcome.is_ok(),
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    Ok(BulkContentResponse {
+        total: ids.len(),
+        succeeded,
+        failed: ids.len() - succeeded,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn comment(id: Uuid, parent_id: Option<Uuid>, minutes: i64) -> Comment {
+        let at = Utc::now() + Duration::minutes(minutes);
+        Comment {
+            id,
+            blog_id: Uuid::nil(),
+            site_id: Uuid::nil(),
+            locale_id: None,
+            parent_id,
+            author_name: "Reader".to_string(),
+            author_email: None,
+            author_url: None,
+            body: "Hello".to_string(),
+            status: CommentStatus::Approved,
+            ip_address: None,
+            user_agent: None,
+            moderated_by: None,
+            moderated_at: None,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn test_build_threads_nests_replies_in_order() {
+        let (a, b, a1, a2, a1x) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let threads = build_threads(vec![
+            comment(a, None, 0),
+            comment(a1, Some(a), 1),
+            comment(b, None, 2),
+            comment(a1x, Some(a1), 3),
+            comment(a2, Some(a), 4),
+        ]);
+
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].id, a);
+        assert_eq!(threads[1].id, b);
+        let replies: Vec<Uuid> = threads[0].replies.iter().map(|r| r.id).collect();
+        assert_eq!(replies, vec![a1, a2]);
+        assert_eq!(threads[0].replies[0].replies[0].id, a1x);
+        assert!(threads[1].replies.is_empty());
+    }
+
+    #[test]
+    fn test_build_threads_drops_orphans() {
+        let root = Uuid::new_v4();
+        let threads = build_threads(vec![
+            comment(root, None, 0),
+            comment(Uuid::new_v4(), Some(Uuid::new_v4()), 1),
+        ]);
+        assert_eq!(threads.len(), 1);
+        assert!(threads[0].replies.is_empty());
+    }
+}
@@ -1,18 +1,21 @@
 //! Content service — shared logic for content-based entities (Blog, Page, Legal, CV)
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::errors::ApiError;
-use crate::models::content::ContentStatus;
+use crate::guards::precondition_guard::IfMatch;
+use crate::models::content::{Content, ContentStatus};
 
 pub struct ContentService;
 
 impl ContentService {
     /// Create a new content record with site associations.
     /// Uses a transaction so the caller can wrap this with their own entity insert.
+    /// The record goes into `environment_id`, or the default environment when `None`.
     /// Returns the content_id.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_content(
         pool: &PgPool,
         entity_type_name: &str,
@@ -21,6 +24,37 @@ impl ContentService {
         site_ids: &[Uuid],
         publish_start: Option<DateTime<Utc>>,
         publish_end: Option<DateTime<Utc>>,
+        environment_id: Option<Uuid>,
+    ) -> Result<Uuid, ApiError> {
+        let mut tx = pool.begin().await?;
+        let content_id = Self::insert_content(
+            &mut tx,
+            entity_type_name,
+            slug,
+            status,
+            site_ids,
+            publish_start,
+            publish_end,
+            environment_id,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(content_id)
+    }
+
+    /// Same as [`Self::create_content`], on a connection the caller manages, so the
+    /// content record can be part of a larger transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn insert_content(
+        conn: &mut PgConnection,
+        entity_type_name: &str,
+        slug: Option<&str>,
+        status: &ContentStatus,
+        site_ids: &[Uuid],
+        publish_start: Option<DateTime<Utc>>,
+        publish_end: Option<DateTime<Utc>>,
+        environment_id: Option<Uuid>,
     ) -> Result<Uuid, ApiError> {
         // Validate scheduling window
         if let (Some(start), Some(end)) = (publish_start, publish_end) {
@@ -42,26 +76,28 @@ impl ContentService {
             status.clone()
         };
 
-        let mut tx = pool.begin().await?;
-
         // Look up entity_type_id
         let entity_type_id: Uuid =
             sqlx::query_scalar("SELECT id FROM entity_types WHERE name = $1")
                 .bind(entity_type_name)
-                .fetch_optional(&mut *tx)
+                .fetch_optional(&mut *conn)
                 .await?
                 .ok_or_else(|| {
                     ApiError::BadRequest(format!("Unknown entity type: {}", entity_type_name))
                 })?;
 
-        // Get default environment
-        let environment_id: Uuid =
-            sqlx::query_scalar("SELECT id FROM environments WHERE is_default = TRUE LIMIT 1")
-                .fetch_optional(&mut *tx)
-                .await?
-                .ok_or_else(|| {
-                    ApiError::BadRequest("No default environment configured".to_string())
-                })?;
+        // Fall back to the default environment
+        let environment_id: Uuid = match environment_id {
+            Some(id) => id,
+            None => {
+                sqlx::query_scalar("SELECT id FROM environments WHERE is_default = TRUE LIMIT 1")
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .ok_or_else(|| {
+                        ApiError::BadRequest("No default environment configured".to_string())
+                    })?
+            }
+        };
 
         // Determine published_at
         let published_at = if effective_status == ContentStatus::Published {
@@ -85,7 +121,7 @@ impl ContentService {
         .bind(published_at)
         .bind(publish_start)
         .bind(publish_end)
-        .fetch_one(&mut *tx)
+        .fetch_one(&mut *conn)
         .await?;
 
         // Insert content_sites associations
@@ -93,20 +129,46 @@ impl ContentService {
             sqlx::query("INSERT INTO content_sites (content_id, site_id) VALUES ($1, $2)")
                 .bind(content_id)
                 .bind(site_id)
-                .execute(&mut *tx)
+                .execute(&mut *conn)
                 .await?;
         }
 
-        tx.commit().await?;
-
         Ok(content_id)
     }
 
+    /// Current entity tag of a content item
+    pub async fn etag(pool: &PgPool, content_id: Uuid) -> Result<String, ApiError> {
+        Ok(Content::find_by_id(pool, content_id).await?.etag())
+    }
+
+    /// Enforce an `If-Match` precondition before a write.
+    ///
+    /// Runs on the write's transaction and locks the content row, so a
+    /// concurrent writer holding the same entity tag waits for this change and
+    /// then fails the check. The caller calls [`Content::touch`] after the
+    /// update succeeds.
+    pub async fn check_if_match(
+        conn: &mut PgConnection,
+        content_id: Uuid,
+        if_match: &IfMatch,
+    ) -> Result<(), ApiError> {
+        if !if_match.is_conditional() {
+            return Ok(());
+        }
+        let content = Content::find_for_update(conn, content_id).await?;
+        if !if_match.matches(&content.etag()) {
+            return Err(ApiError::PreconditionFailed(
+                "Content was modified since it was loaded; reload it and retry".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Update an existing content record (slug, status, scheduling).
     /// Auto-sets published_at when status becomes Published.
     /// Auto-sets status to Scheduled when publish_start is in the future.
-    pub async fn update_content(
-        pool: &PgPool,
+    pub async fn update_content<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
         content_id: Uuid,
         slug: Option<&str>,
         status: Option<&ContentStatus>,
@@ -162,7 +224,7 @@ impl ContentService {
             .bind(&effective_status)
             .bind(publish_start)
             .bind(publish_end)
-            .execute(pool)
+            .execute(executor)
             .await?;
         } else {
             sqlx::query(
@@ -181,7 +243,7 @@ impl ContentService {
             .bind(&effective_status)
             .bind(publish_start)
             .bind(publish_end)
-            .execute(pool)
+            .execute(executor)
             .await?;
         }
 
@@ -191,7 +253,7 @@ impl ContentService {
     /// Generate a unique slug for cloned content.
     /// Tries `"{base}-copy"`, then `"{base}-copy-2"` through `"{base}-copy-99"`.
     pub async fn generate_unique_slug(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         base_slug: &str,
         site_ids: &[Uuid],
     ) -> Result<String, ApiError> {
@@ -212,13 +274,15 @@ impl ContentService {
                 SELECT EXISTS(
                     SELECT 1 FROM contents c
                     INNER JOIN content_sites cs ON c.id = cs.content_id
-                    WHERE c.slug = $1 AND cs.site_id = ANY($2) AND c.is_deleted = FALSE
+                    WHERE COALESCE(cs.site_specific_slug, c.slug) = $1
+                      AND cs.site_id = ANY($2)
+                      AND c.is_deleted = FALSE
                 )
                 "#,
             )
             .bind(candidate)
             .bind(site_ids)
-            .fetch_one(pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             if !exists {
@@ -235,7 +299,7 @@ impl ContentService {
     /// Generate a unique route for cloned pages.
     /// Same logic as slug but checks the pages table.
     pub async fn generate_unique_route(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         base_route: &str,
         site_ids: &[Uuid],
     ) -> Result<String, ApiError> {
@@ -262,7 +326,7 @@ impl ContentService {
             )
             .bind(candidate)
             .bind(site_ids)
-            .fetch_one(pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             if !exists {
@@ -298,4 +362,27 @@ impl ContentService {
 
         Ok(())
     }
+
+    /// Take a soft-deleted content record out of the trash.
+    pub async fn restore_content(pool: &PgPool, content_id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE contents
+            SET is_deleted = FALSE, deleted_at = NULL, deleted_by = NULL, updated_at = NOW()
+            WHERE id = $1 AND is_deleted = TRUE
+            "#,
+        )
+        .bind(content_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Deleted content with ID {} not found",
+                content_id
+            )));
+        }
+
+        Ok(())
+    }
 }
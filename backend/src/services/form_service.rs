@@ -0,0 +1,418 @@
+//! Form service
+//!
+//! Checks form definitions against the site's languages, validates public
+//! submissions against the field rules, resolves labels for a locale, and
+//! renders the submissions inbox as CSV.
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use validator::{ValidateEmail, ValidateUrl};
+
+use crate::dto::form::{FormField, FormFieldType, LocalizedFormField};
+use crate::errors::ApiError;
+use crate::middleware::rate_limit::RateLimiter;
+use crate::models::form::FormSubmission;
+use crate::AppState;
+
+/// Longest accepted text value when a field sets no `max_length`
+const DEFAULT_MAX_LENGTH: usize = 10_000;
+
+/// Throttle form submissions per client IP with `SecurityConfig::form_limits`
+pub async fn check_submission_rate(state: &AppState, ip: Option<&str>) -> Result<(), ApiError> {
+    RateLimiter::check_submission(
+        state.redis.as_ref(),
+        "form",
+        ip,
+        &state.settings.security.form_limits(),
+    )
+    .await
+}
+
+/// Check rules that span fields or need the site's locale codes
+pub fn validate_definition(fields: &[FormField], locale_codes: &[String]) -> Result<(), ApiError> {
+    let mut names = HashSet::new();
+    for field in fields {
+        if !names.insert(field.name.as_str()) {
+            return Err(ApiError::Validation(format!(
+                "Duplicate field name '{}'",
+                field.name
+            )));
+        }
+        if let Some(code) = field.labels.keys().find(|c| !locale_codes.contains(c)) {
+            return Err(ApiError::Validation(format!(
+                "Field '{}' has a label for '{}', which is not a language of this site",
+                field.name, code
+            )));
+        }
+        if field.field_type == FormFieldType::Select && field.options.is_empty() {
+            return Err(ApiError::Validation(format!(
+                "Select field '{}' needs at least one option",
+                field.name
+            )));
+        }
+        if let (Some(min), Some(max)) = (field.min_length, field.max_length) {
+            if min > max {
+                return Err(ApiError::Validation(format!(
+                    "Field '{}' has min_length greater than max_length",
+                    field.name
+                )));
+            }
+        }
+        if let Some(pattern) = &field.pattern {
+            Regex::new(pattern).map_err(|e| {
+                ApiError::Validation(format!(
+                    "Field '{}' has an invalid pattern: {}",
+                    field.name, e
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Check one value against its field definition
+fn check_value(field: &FormField, value: &Value) -> Result<(), String> {
+    if let FormFieldType::Checkbox = field.field_type {
+        return match value {
+            Value::Bool(true) => Ok(()),
+            Value::Bool(false) if !field.required => Ok(()),
+            Value::Bool(false) => Err("must be checked".to_string()),
+            _ => Err("must be true or false".to_string()),
+        };
+    }
+    if let FormFieldType::Number = field.field_type {
+        return match value {
+            Value::Number(_) => Ok(()),
+            _ => Err("must be a number".to_string()),
+        };
+    }
+
+    let Value::String(text) = value else {
+        return Err("must be text".to_string());
+    };
+    let length = text.chars().count();
+    if let Some(min) = field.min_length {
+        if length < min as usize {
+            return Err(format!("must be at least {} characters", min));
+        }
+    }
+    let max = field.max_length.map_or(DEFAULT_MAX_LENGTH, |m| m as usize);
+    if length > max {
+        return Err(format!("cannot exceed {} characters", max));
+    }
+    match field.field_type {
+        FormFieldType::Email if !text.validate_email() => {
+            return Err("must be a valid email address".to_string());
+        }
+        FormFieldType::Url if !text.validate_url() => {
+            return Err("must be a valid URL".to_string());
+        }
+        FormFieldType::Select if !field.options.contains(text) => {
+            return Err("is not one of the options".to_string());
+        }
+        _ => {}
+    }
+    if let Some(pattern) = &field.pattern {
+        if !Regex::new(pattern).is_ok_and(|re| re.is_match(text)) {
+            return Err("has an invalid format".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Whether a submitted value counts as "not filled in"
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Validate submitted values and return them with blank optional values removed.
+///
+/// Unknown keys are rejected so that the inbox only ever holds defined fields.
+pub fn validate_submission(
+    fields: &[FormField],
+    data: &Map<String, Value>,
+) -> Result<Map<String, Value>, ApiError> {
+    if let Some(key) = data.keys().find(|k| !fields.iter().any(|f| &f.name == *k)) {
+        return Err(ApiError::Validation(format!("Unknown field '{}'", key)));
+    }
+
+    let mut errors = Vec::new();
+    let mut values = Map::new();
+    for field in fields {
+        match data.get(&field.name) {
+            Some(value) if !is_blank(value) => match check_value(field, value) {
+                Ok(()) => {
+                    values.insert(field.name.clone(), value.clone());
+                }
+                Err(message) => errors.push(format!("{} {}", field.name, message)),
+            },
+            _ if field.required => errors.push(format!("{} is required", field.name)),
+            _ => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(ApiError::Validation(errors.join("; ")))
+    }
+}
+
+/// Resolve field labels for `locale`, falling back to the site's default locale,
+/// then to any label, then to the field name.
+pub fn localize(
+    fields: Vec<FormField>,
+    locale: Option<&str>,
+    default_locale: Option<&str>,
+) -> Vec<LocalizedFormField> {
+    fields
+        .into_iter()
+        .map(|field| {
+            let label = locale
+                .and_then(|code| field.labels.get(code))
+                .or_else(|| default_locale.and_then(|code| field.labels.get(code)))
+                .or_else(|| field.labels.values().next())
+                .cloned()
+                .unwrap_or_else(|| field.name.clone());
+            LocalizedFormField {
+                name: field.name,
+                field_type: field.field_type,
+                required: field.required,
+                label,
+                options: field.options,
+                min_length: field.min_length,
+                max_length: field.max_length,
+                pattern: field.pattern,
+            }
+        })
+        .collect()
+}
+
+/// Render a value as a CSV cell, defusing spreadsheet formulas
+fn csv_cell(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    if text.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", text)
+    } else {
+        text
+    }
+}
+
+/// Render submissions as CSV: id, submitted_at, locale, then one column per field
+pub fn render_csv(
+    fields: &[FormField],
+    submissions: &[FormSubmission],
+    locale_codes: &HashMap<Uuid, String>,
+) -> Result<String, ApiError> {
+    let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+    let write_error = |e: csv::Error| ApiError::Internal(format!("Failed to write CSV: {}", e));
+
+    let mut header = vec!["id", "submitted_at", "locale"];
+    header.extend(fields.iter().map(|f| f.name.as_str()));
+    writer.write_record(&header).map_err(write_error)?;
+
+    for submission in submissions {
+        let mut record = vec![
+            submission.id.to_string(),
+            submission.created_at.to_rfc3339(),
+            submission
+                .locale_id
+                .and_then(|id| locale_codes.get(&id).cloned())
+                .unwrap_or_default(),
+        ];
+        record.extend(
+            fields
+                .iter()
+                .map(|f| csv_cell(submission.data.get(&f.name))),
+        );
+        writer.write_record(&record).map_err(write_error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| ApiError::Internal(format!("Failed to write CSV: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| ApiError::Internal(format!("Invalid CSV output: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::BTreeMap;
+
+    fn field(name: &str, field_type: FormFieldType, required: bool) -> FormField {
+        FormField {
+            name: name.to_string(),
+            field_type,
+            required,
+            labels: BTreeMap::new(),
+            options: vec![],
+            min_length: None,
+            max_length: None,
+            pattern: None,
+        }
+    }
+
+    fn contact_fields() -> Vec<FormField> {
+        let mut topic = field("topic", FormFieldType::Select, false);
+        topic.options = vec!["sales".to_string(), "support".to_string()];
+        let mut message = field("message", FormFieldType::Textarea, true);
+        message.min_length = Some(5);
+        vec![
+            field("email", FormFieldType::Email, true),
+            message,
+            topic,
+            field("consent", FormFieldType::Checkbox, true),
+        ]
+    }
+
+    fn data(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_validate_definition() {
+        let codes = vec!["en".to_string(), "de".to_string()];
+        let mut fields = contact_fields();
+        assert!(validate_definition(&fields, &codes).is_ok());
+
+        fields[0]
+            .labels
+            .insert("fr".to_string(), "Courriel".to_string());
+        assert!(validate_definition(&fields, &codes).is_err());
+
+        let mut fields = contact_fields();
+        fields.push(field("email", FormFieldType::Text, false));
+        assert!(validate_definition(&fields, &codes).is_err());
+
+        let mut fields = contact_fields();
+        fields[2].options.clear();
+        assert!(validate_definition(&fields, &codes).is_err());
+
+        let mut fields = contact_fields();
+        fields[1].pattern = Some("([".to_string());
+        assert!(validate_definition(&fields, &codes).is_err());
+    }
+
+    #[test]
+    fn test_validate_submission_accepts_valid_data() {
+        let values = validate_submission(
+            &contact_fields(),
+            &data(serde_json::json!({
+                "email": "jane@example.com",
+                "message": "Hello there",
+                "topic": "",
+                "consent": true
+            })),
+        )
+        .unwrap();
+        assert_eq!(values.len(), 3);
+        assert!(!values.contains_key("topic"));
+    }
+
+    #[test]
+    fn test_validate_submission_reports_every_problem() {
+        let err = validate_submission(
+            &contact_fields(),
+            &data(serde_json::json!({
+                "email": "not-an-email",
+                "message": "Hi",
+                "topic": "other",
+                "consent": false
+            })),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("email must be a valid email address"));
+        assert!(err.contains("message must be at least 5 characters"));
+        assert!(err.contains("topic is not one of the options"));
+        assert!(err.contains("consent must be checked"));
+
+        let err = validate_submission(&contact_fields(), &data(serde_json::json!({})))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("email is required"));
+
+        assert!(validate_submission(
+            &contact_fields(),
+            &data(serde_json::json!({"email": "a@b.co", "message": "Hello", "consent": true, "extra": 1}))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_pattern_and_default_max_length() {
+        let mut phone = field("phone", FormFieldType::Phone, true);
+        phone.pattern = Some(r"^\+?[0-9 ]+$".to_string());
+        let fields = vec![phone];
+        assert!(
+            validate_submission(&fields, &data(serde_json::json!({"phone": "+43 1 234"}))).is_ok()
+        );
+        assert!(
+            validate_submission(&fields, &data(serde_json::json!({"phone": "call me"}))).is_err()
+        );
+
+        let fields = vec![field("note", FormFieldType::Text, true)];
+        let long = "a".repeat(DEFAULT_MAX_LENGTH + 1);
+        assert!(validate_submission(&fields, &data(serde_json::json!({"note": long}))).is_err());
+    }
+
+    #[test]
+    fn test_localize_fallbacks() {
+        let mut email = field("email", FormFieldType::Email, true);
+        email.labels.insert("en".to_string(), "Email".to_string());
+        email.labels.insert("de".to_string(), "E-Mail".to_string());
+        let mut name = field("name", FormFieldType::Text, true);
+        name.labels.insert("de".to_string(), "Name".to_string());
+        let fields = vec![email, name, field("plain", FormFieldType::Text, false)];
+
+        let localized = localize(fields.clone(), Some("de"), Some("en"));
+        assert_eq!(localized[0].label, "E-Mail");
+        let localized = localize(fields.clone(), Some("fr"), Some("en"));
+        assert_eq!(localized[0].label, "Email");
+        assert_eq!(localized[1].label, "Name");
+        assert_eq!(localized[2].label, "plain");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let fields = contact_fields();
+        let locale_id = Uuid::new_v4();
+        let submission = FormSubmission {
+            id: Uuid::new_v4(),
+            form_id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            locale_id: Some(locale_id),
+            data: serde_json::json!({
+                "email": "jane@example.com",
+                "message": "=HYPERLINK(\"x\"), hi",
+                "consent": true
+            }),
+            ip_address: None,
+            user_agent: None,
+            created_at: Utc::now(),
+        };
+        let codes = HashMap::from([(locale_id, "en".to_string())]);
+        let csv = render_csv(&fields, &[submission], &codes).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "id,submitted_at,locale,email,message,topic,consent"
+        );
+        let row = lines.next().unwrap();
+        assert!(row.contains(",en,jane@example.com,"));
+        assert!(row.contains("\"'=HYPERLINK(\"\"x\"\"), hi\""));
+        assert!(row.ends_with(",,true"));
+    }
+}
@@ -1,15 +1,19 @@
 //! Image variant generation service
 //!
-//! Generates thumbnail, small, medium, large, and webp variants for uploaded images.
+//! Generates thumbnail, small, medium, large, and webp variants for uploaded images,
+//! encodes the (slower) AVIF variant, and applies on-the-fly transformations
+//! (resize, crop, convert).
 
 use std::io::Cursor;
 use std::sync::Arc;
 
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
 use image::imageops::FilterType;
 use image::{DynamicImage, ImageFormat, ImageReader};
 
 use crate::errors::ApiError;
-use crate::models::media::MediaVariantType;
+use crate::models::media::{CropRect, MediaVariantType};
 use crate::services::storage::StorageBackend;
 
 /// Specification for a single image variant
@@ -129,6 +133,92 @@ pub async fn generate_variants(
     Ok(results)
 }
 
+/// Largest width of the AVIF variant
+pub const AVIF_MAX_WIDTH: u32 = 1200;
+
+/// AVIF encoder settings, configurable per site
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvifOptions {
+    /// 1 – 100
+    pub quality: u8,
+    /// 1 (smallest files) – 10 (fastest)
+    pub speed: u8,
+}
+
+impl Default for AvifOptions {
+    fn default() -> Self {
+        Self {
+            quality: 60,
+            speed: 8,
+        }
+    }
+}
+
+/// Generate the AVIF variant of an image, at most `AVIF_MAX_WIDTH` wide.
+///
+/// Encoding runs on the blocking thread pool since it takes far longer than
+/// the other variants. Returns `Ok(None)` if the bytes cannot be decoded as an
+/// image.
+pub async fn generate_avif_variant(
+    original_bytes: Vec<u8>,
+    base_path: &str,
+    storage: &dyn StorageBackend,
+    options: AvifOptions,
+) -> Result<Option<GeneratedVariant>, ApiError> {
+    let encoded = tokio::task::spawn_blocking(move || {
+        let img = match ImageReader::new(Cursor::new(original_bytes))
+            .with_guessed_format()
+            .map_err(|e| ApiError::Internal(format!("Image format detection failed: {e}")))?
+            .decode()
+        {
+            Ok(img) => img,
+            Err(_) => return Ok(None),
+        };
+        let img = if img.width() > AVIF_MAX_WIDTH {
+            resize_image(&img, AVIF_MAX_WIDTH)
+        } else {
+            img
+        };
+        let bytes = encode_avif(&img, options)?;
+        Ok::<_, ApiError>(Some((bytes, img.width(), img.height())))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(format!("AVIF encoding task failed: {e}")))??;
+
+    let Some((bytes, width, height)) = encoded else {
+        return Ok(None);
+    };
+    let storage_path = format!("{}_avif.avif", base_path);
+    let public_url = storage.store(&storage_path, &bytes, "image/avif").await?;
+
+    Ok(Some(GeneratedVariant {
+        variant_type: MediaVariantType::Avif,
+        width,
+        height,
+        file_size: bytes.len(),
+        storage_path,
+        public_url,
+    }))
+}
+
+/// Encode as AVIF (8-bit RGB, or RGBA if the image has an alpha channel)
+fn encode_avif(img: &DynamicImage, options: AvifOptions) -> Result<Vec<u8>, ApiError> {
+    let img = if img.color().has_alpha() {
+        DynamicImage::ImageRgba8(img.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(img.to_rgb8())
+    };
+    let mut buf = Vec::new();
+    let encoder = AvifEncoder::new_with_speed_quality(
+        &mut buf,
+        options.speed.clamp(1, 10),
+        options.quality.clamp(1, 100),
+    );
+    img.write_with_encoder(encoder)
+        .map_err(|e| ApiError::Internal(format!("AVIF encoding failed: {e}")))?;
+    Ok(buf)
+}
+
 /// Resize an image to fit within max_width, preserving aspect ratio (only downscale)
 fn resize_image(img: &DynamicImage, max_width: u32) -> DynamicImage {
     let (w, h) = (img.width(), img.height());
@@ -164,3 +254,421 @@ fn encode_original_format(
 
     Ok((buf, ext, ct))
 }
+
+/// Largest width or height a transformation may request
+pub const MAX_TRANSFORM_DIMENSION: u32 = 4096;
+
+/// JPEG quality used when a transformation does not set one
+pub const DEFAULT_TRANSFORM_QUALITY: u8 = 80;
+
+/// How a transformed image fills the requested width and height
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransformFit {
+    /// Scale to fit inside the box, keeping the aspect ratio
+    #[default]
+    Contain,
+    /// Scale to cover the box, then crop the overflow around the focal point
+    Cover,
+    /// Stretch to the box exactly
+    Fill,
+}
+
+impl TransformFit {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "contain" => Some(Self::Contain),
+            "cover" => Some(Self::Cover),
+            "fill" => Some(Self::Fill),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Contain => "contain",
+            Self::Cover => "cover",
+            Self::Fill => "fill",
+        }
+    }
+}
+
+/// Output format of a transformation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformFormat {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl TransformFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "png" => Some(Self::Png),
+            "webp" => Some(Self::Webp),
+            _ => None,
+        }
+    }
+
+    /// Format to keep when none is requested; other sources become PNG
+    pub fn for_mime_type(mime_type: &str) -> Self {
+        match mime_type {
+            "image/jpeg" => Self::Jpeg,
+            "image/webp" => Self::Webp,
+            _ => Self::Png,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Webp => "image/webp",
+        }
+    }
+}
+
+/// Options of an on-the-fly transformation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformSpec {
+    /// Named crop of the media file to start from
+    pub crop: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fit: TransformFit,
+    /// `None` keeps the source format
+    pub format: Option<TransformFormat>,
+    /// JPEG quality (1-100); PNG and WebP are encoded losslessly
+    pub quality: u8,
+}
+
+impl Default for TransformSpec {
+    fn default() -> Self {
+        Self {
+            crop: None,
+            width: None,
+            height: None,
+            fit: TransformFit::default(),
+            format: None,
+            quality: DEFAULT_TRANSFORM_QUALITY,
+        }
+    }
+}
+
+impl TransformSpec {
+    /// Normalized form of the options, e.g. `c16.9_w400_h300_cover_q80_webp`.
+    /// Equal options always give the same key.
+    pub fn key(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(crop) = &self.crop {
+            // Crop names cannot contain dots, so this stays unambiguous
+            parts.push(format!("c{}", crop.replace(':', ".")));
+        }
+        if let Some(w) = self.width {
+            parts.push(format!("w{w}"));
+        }
+        if let Some(h) = self.height {
+            parts.push(format!("h{h}"));
+        }
+        parts.push(self.fit.as_str().to_string());
+        parts.push(format!("q{}", self.quality));
+        parts.push(
+            self.format
+                .map(|f| f.extension())
+                .unwrap_or("auto")
+                .to_string(),
+        );
+        parts.join("_")
+    }
+}
+
+/// Result of a transformation
+pub struct TransformedImage {
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Resize, crop and encode an image. Never enlarges the source.
+///
+/// `crop` is cut out first and then treated as the source. `focal_point`
+/// (fractions of the source width and height) decides what `cover` keeps.
+pub fn transform(
+    original_bytes: &[u8],
+    spec: &TransformSpec,
+    format: TransformFormat,
+    focal_point: Option<(f64, f64)>,
+    crop: Option<CropRect>,
+) -> Result<TransformedImage, ApiError> {
+    let mut img = ImageReader::new(Cursor::new(original_bytes))
+        .with_guessed_format()
+        .map_err(|e| ApiError::Internal(format!("Image format detection failed: {e}")))?
+        .decode()
+        .map_err(|_| ApiError::BadRequest("Media file is not a decodable image".to_string()))?;
+
+    let mut focal_point = focal_point;
+    if let Some(rect) = crop {
+        let (full_w, full_h) = (img.width() as f64, img.height() as f64);
+        img = img.crop_imm(rect.x, rect.y, rect.width, rect.height);
+        // Express the focal point relative to the cropped area
+        focal_point = focal_point.map(|(fx, fy)| {
+            (
+                ((fx * full_w - rect.x as f64) / img.width() as f64).clamp(0.0, 1.0),
+                ((fy * full_h - rect.y as f64) / img.height() as f64).clamp(0.0, 1.0),
+            )
+        });
+    }
+
+    let (orig_w, orig_h) = (img.width(), img.height());
+    let (w, h) = target_size(orig_w, orig_h, spec);
+    let out = if (w, h) == (orig_w, orig_h) {
+        img
+    } else if spec.fit == TransformFit::Cover && spec.width.is_some() && spec.height.is_some() {
+        cover(&img, w, h, focal_point.unwrap_or((0.5, 0.5)))
+    } else {
+        img.resize_exact(w, h, FilterType::Lanczos3)
+    };
+
+    let mut buf = Vec::new();
+    match format {
+        TransformFormat::Jpeg => {
+            let rgb = DynamicImage::ImageRgb8(out.to_rgb8());
+            JpegEncoder::new_with_quality(&mut buf, spec.quality)
+                .encode_image(&rgb)
+                .map_err(|e| ApiError::Internal(format!("JPEG encoding failed: {e}")))?;
+        }
+        TransformFormat::Png => {
+            out.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+                .map_err(|e| ApiError::Internal(format!("PNG encoding failed: {e}")))?;
+        }
+        TransformFormat::Webp => buf = encode_webp(&out)?,
+    }
+
+    Ok(TransformedImage {
+        bytes: buf,
+        width: out.width(),
+        height: out.height(),
+    })
+}
+
+/// Scale an image to cover `w` x `h` and crop it there, keeping the focal
+/// point as close to the center as the edges allow
+fn cover(img: &DynamicImage, w: u32, h: u32, (fx, fy): (f64, f64)) -> DynamicImage {
+    let scale = (w as f64 / img.width() as f64).max(h as f64 / img.height() as f64);
+    let scaled_w = ((img.width() as f64 * scale).round() as u32).max(w);
+    let scaled_h = ((img.height() as f64 * scale).round() as u32).max(h);
+    let offset = |focus: f64, scaled: u32, len: u32| {
+        (focus * scaled as f64 - len as f64 / 2.0)
+            .round()
+            .clamp(0.0, (scaled - len) as f64) as u32
+    };
+
+    img.resize_exact(scaled_w, scaled_h, FilterType::Lanczos3)
+        .crop_imm(offset(fx, scaled_w, w), offset(fy, scaled_h, h), w, h)
+}
+
+/// Widths of the resized variants, smallest first
+pub fn variant_widths() -> Vec<u32> {
+    VARIANTS
+        .iter()
+        .filter(|spec| !spec.force_webp)
+        .map(|spec| spec.max_width)
+        .collect()
+}
+
+/// Output dimensions for a transformation of an `orig_w` x `orig_h` image
+pub fn target_size(orig_w: u32, orig_h: u32, spec: &TransformSpec) -> (u32, u32) {
+    let scaled = |len: u32, factor: f64| ((len as f64 * factor).round() as u32).max(1);
+    match (spec.width, spec.height) {
+        (None, None) => (orig_w, orig_h),
+        (Some(w), None) => {
+            let factor = (w as f64 / orig_w as f64).min(1.0);
+            (scaled(orig_w, factor), scaled(orig_h, factor))
+        }
+        (None, Some(h)) => {
+            let factor = (h as f64 / orig_h as f64).min(1.0);
+            (scaled(orig_w, factor), scaled(orig_h, factor))
+        }
+        (Some(w), Some(h)) => match spec.fit {
+            TransformFit::Contain => {
+                let factor = (w as f64 / orig_w as f64)
+                    .min(h as f64 / orig_h as f64)
+                    .min(1.0);
+                (scaled(orig_w, factor), scaled(orig_h, factor))
+            }
+            // Keep the requested aspect ratio, shrinking the box if it exceeds the source
+            TransformFit::Cover | TransformFit::Fill => {
+                let factor = (orig_w as f64 / w as f64)
+                    .min(orig_h as f64 / h as f64)
+                    .min(1.0);
+                (scaled(w, factor), scaled(h, factor))
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(width: Option<u32>, height: Option<u32>, fit: TransformFit) -> TransformSpec {
+        TransformSpec {
+            width,
+            height,
+            fit,
+            ..TransformSpec::default()
+        }
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        DynamicImage::new_rgb8(width, height)
+            .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_target_size_keeps_aspect_ratio() {
+        let s = spec(Some(400), None, TransformFit::Contain);
+        assert_eq!(target_size(1600, 1200, &s), (400, 300));
+        let s = spec(Some(400), Some(400), TransformFit::Contain);
+        assert_eq!(target_size(1600, 1200, &s), (400, 300));
+    }
+
+    #[test]
+    fn test_target_size_cover_uses_box() {
+        let s = spec(Some(400), Some(400), TransformFit::Cover);
+        assert_eq!(target_size(1600, 1200, &s), (400, 400));
+    }
+
+    #[test]
+    fn test_encode_avif() {
+        let img = image::load_from_memory(&png(64, 32)).unwrap();
+        let bytes = encode_avif(&img, AvifOptions::default()).unwrap();
+        // ISO-BMFF file type box with the AVIF brand
+        assert_eq!(&bytes[4..12], b"ftypavif");
+    }
+
+    #[test]
+    fn test_variant_widths() {
+        assert_eq!(variant_widths(), vec![200, 400, 800, 1200]);
+    }
+
+    #[test]
+    fn test_target_size_never_enlarges() {
+        let s = spec(Some(3200), None, TransformFit::Contain);
+        assert_eq!(target_size(1600, 1200, &s), (1600, 1200));
+        let s = spec(Some(2400), Some(2400), TransformFit::Cover);
+        assert_eq!(target_size(1600, 1200, &s), (1200, 1200));
+    }
+
+    #[test]
+    fn test_spec_key_is_normalized() {
+        let s = TransformSpec {
+            crop: Some("16:9".to_string()),
+            width: Some(400),
+            height: Some(300),
+            fit: TransformFit::Cover,
+            format: Some(TransformFormat::Webp),
+            quality: 75,
+        };
+        assert_eq!(s.key(), "c16.9_w400_h300_cover_q75_webp");
+        assert_eq!(TransformSpec::default().key(), "contain_q80_auto");
+    }
+
+    #[test]
+    fn test_transform_crops_and_converts() {
+        let s = TransformSpec {
+            format: Some(TransformFormat::Jpeg),
+            ..spec(Some(50), Some(50), TransformFit::Cover)
+        };
+        let out = transform(&png(200, 100), &s, TransformFormat::Jpeg, None, None).unwrap();
+        assert_eq!((out.width, out.height), (50, 50));
+        assert_eq!(&out.bytes[..3], &[0xFF, 0xD8, 0xFF]);
+    }
+
+    /// 200x100 image, black on the left half and white on the right
+    fn split_png() -> Vec<u8> {
+        let img = image::RgbImage::from_fn(200, 100, |x, _| {
+            if x < 100 {
+                image::Rgb([0, 0, 0])
+            } else {
+                image::Rgb([255, 255, 255])
+            }
+        });
+        let mut buf = Vec::new();
+        DynamicImage::ImageRgb8(img)
+            .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+            .unwrap();
+        buf
+    }
+
+    fn center_pixel(bytes: &[u8]) -> u8 {
+        let img = image::load_from_memory(bytes).unwrap().to_rgb8();
+        img.get_pixel(img.width() / 2, img.height() / 2)[0]
+    }
+
+    #[test]
+    fn test_cover_keeps_focal_point() {
+        let s = spec(Some(50), Some(50), TransformFit::Cover);
+        let right = transform(
+            &split_png(),
+            &s,
+            TransformFormat::Png,
+            Some((0.9, 0.5)),
+            None,
+        )
+        .unwrap();
+        assert_eq!(center_pixel(&right.bytes), 255);
+        let left = transform(
+            &split_png(),
+            &s,
+            TransformFormat::Png,
+            Some((0.1, 0.5)),
+            None,
+        )
+        .unwrap();
+        assert_eq!(center_pixel(&left.bytes), 0);
+    }
+
+    #[test]
+    fn test_transform_applies_crop() {
+        let rect = CropRect {
+            x: 100,
+            y: 0,
+            width: 100,
+            height: 100,
+        };
+        let out = transform(
+            &split_png(),
+            &TransformSpec::default(),
+            TransformFormat::Png,
+            None,
+            Some(rect),
+        )
+        .unwrap();
+        assert_eq!((out.width, out.height), (100, 100));
+        assert_eq!(center_pixel(&out.bytes), 255);
+    }
+
+    #[test]
+    fn test_transform_rejects_non_images() {
+        let result = transform(
+            b"not an image",
+            &TransformSpec::default(),
+            TransformFormat::Png,
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,361 @@
+//! On-the-fly image transformation service
+//!
+//! `/media/{id}/transform` URLs carry a signature: an HMAC-SHA256 over the
+//! media ID and the normalized options. Only URLs handed out by the API are
+//! served, so the endpoint cannot be used to render arbitrary sizes. Each
+//! result is stored in the storage backend and recorded in `media_transforms`,
+//! and later requests read it back instead of decoding the original again.
+//!
+//! Named crops of a media file are served the same way (`crop=16:9`), and a
+//! set of widths per crop is rendered ahead of time when the crops change.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Settings;
+use crate::errors::ApiError;
+use crate::models::media::{CropRect, MediaFile, MediaTransform};
+use crate::services::image_service::{
+    self, TransformFit, TransformFormat, TransformSpec, MAX_TRANSFORM_DIMENSION,
+};
+use crate::services::storage::StorageBackend;
+use crate::services::webhook_service::compute_hmac_sha256;
+use crate::utils::signing::{constant_time_eq, secret_or_fallback};
+
+fn signing_secret(settings: &Settings) -> &str {
+    secret_or_fallback(&settings.security.image_url_secret)
+}
+
+fn sign_with(secret: &str, media_id: Uuid, version: i64, spec: &TransformSpec) -> String {
+    compute_hmac_sha256(secret, &format!("{}:{}:{}", media_id, version, spec.key()))
+}
+
+/// Version of a media file's art direction carried in its transformation
+/// URLs, so that changing the focal point or crops yields new URLs instead of
+/// ones that caches already hold with the old output
+pub fn url_version(updated_at: DateTime<Utc>) -> i64 {
+    updated_at.timestamp_micros()
+}
+
+/// Build transformation options from the `crop`, `w`, `h`, `fit`, `format`
+/// and `q` query parameters
+pub fn parse_spec(
+    crop: Option<&str>,
+    width: Option<u32>,
+    height: Option<u32>,
+    fit: Option<&str>,
+    format: Option<&str>,
+    quality: Option<u8>,
+) -> Result<TransformSpec, ApiError> {
+    for (name, value) in [("w", width), ("h", height)] {
+        if value.is_some_and(|v| v == 0 || v > MAX_TRANSFORM_DIMENSION) {
+            return Err(ApiError::Validation(format!(
+                "{} must be between 1 and {}",
+                name, MAX_TRANSFORM_DIMENSION
+            )));
+        }
+    }
+    let fit = match fit {
+        Some(value) => TransformFit::parse(value)
+            .ok_or_else(|| ApiError::Validation(format!("Invalid fit: {}", value)))?,
+        None => TransformFit::default(),
+    };
+    if fit != TransformFit::Contain && (width.is_none() || height.is_none()) {
+        return Err(ApiError::Validation(format!(
+            "fit={} needs both w and h",
+            fit.as_str()
+        )));
+    }
+    let format = format
+        .map(|value| {
+            TransformFormat::parse(value)
+                .ok_or_else(|| ApiError::Validation(format!("Invalid format: {}", value)))
+        })
+        .transpose()?;
+    let quality = quality.unwrap_or(image_service::DEFAULT_TRANSFORM_QUALITY);
+    if !(1..=100).contains(&quality) {
+        return Err(ApiError::Validation(
+            "q must be between 1 and 100".to_string(),
+        ));
+    }
+
+    Ok(TransformSpec {
+        crop: crop.map(str::to_string),
+        width,
+        height,
+        fit,
+        format,
+        quality,
+    })
+}
+
+/// Signed path of a transformation, relative to the server root
+pub fn signed_url(
+    settings: &Settings,
+    media_id: Uuid,
+    version: i64,
+    spec: &TransformSpec,
+) -> String {
+    let mut query = Vec::new();
+    if let Some(crop) = &spec.crop {
+        query.push(format!("crop={crop}"));
+    }
+    if let Some(w) = spec.width {
+        query.push(format!("w={w}"));
+    }
+    if let Some(h) = spec.height {
+        query.push(format!("h={h}"));
+    }
+    if spec.fit != TransformFit::Contain {
+        query.push(format!("fit={}", spec.fit.as_str()));
+    }
+    if let Some(format) = spec.format {
+        query.push(format!("format={}", format.extension()));
+    }
+    if spec.quality != image_service::DEFAULT_TRANSFORM_QUALITY {
+        query.push(format!("q={}", spec.quality));
+    }
+    query.push(format!("v={version}"));
+    query.push(format!(
+        "s={}",
+        sign_with(signing_secret(settings), media_id, version, spec)
+    ));
+
+    format!("/api/v1/media/{}/transform?{}", media_id, query.join("&"))
+}
+
+/// Check the signature of a transformation URL
+pub fn verify(
+    settings: &Settings,
+    media_id: Uuid,
+    version: i64,
+    spec: &TransformSpec,
+    signature: &str,
+) -> Result<(), ApiError> {
+    let expected = sign_with(signing_secret(settings), media_id, version, spec);
+    if constant_time_eq(&expected, signature) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(
+            "Invalid image URL signature".to_string(),
+        ))
+    }
+}
+
+/// Transformed image ready to be served
+pub struct RenderedImage {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Return the transformed image, rendering and caching it on first use
+pub async fn render(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    media: &MediaFile,
+    spec: &TransformSpec,
+) -> Result<RenderedImage, ApiError> {
+    if !media.mime_type.starts_with("image/") {
+        return Err(ApiError::BadRequest(format!(
+            "Media file {} is not an image",
+            media.id
+        )));
+    }
+    let crop = match &spec.crop {
+        Some(name) => Some(*media.crop_rects().get(name).ok_or_else(|| {
+            ApiError::NotFound(format!("Media file {} has no crop '{}'", media.id, name))
+        })?),
+        None => None,
+    };
+
+    let key = spec.key();
+    if let Some(cached) = MediaTransform::find(pool, media.id, &key).await? {
+        match storage.read(&cached.storage_path).await {
+            Ok(bytes) => {
+                return Ok(RenderedImage {
+                    bytes,
+                    content_type: cached.content_type,
+                })
+            }
+            // Render again and overwrite the missing file
+            Err(e) => {
+                tracing::warn!(error = %e, path = %cached.storage_path, "Cached image transformation is unreadable")
+            }
+        }
+    }
+
+    let original = storage.read(&media.storage_path).await?;
+    let format = spec
+        .format
+        .unwrap_or_else(|| TransformFormat::for_mime_type(&media.mime_type));
+    let focal_point = media.focal_point();
+    let render_spec = spec.clone();
+    // Decoding and resizing are CPU-bound; keep them off the async workers
+    let out = tokio::task::spawn_blocking(move || {
+        image_service::transform(&original, &render_spec, format, focal_point, crop)
+    })
+    .await
+    .map_err(|e| ApiError::Internal(format!("Image transformation task failed: {e}")))??;
+
+    let storage_path = format!("transforms/{}/{}.{}", media.id, key, format.extension());
+    storage
+        .store(&storage_path, &out.bytes, format.content_type())
+        .await?;
+    MediaTransform::create(
+        pool,
+        media.id,
+        &key,
+        format.content_type(),
+        out.width,
+        out.height,
+        out.bytes.len(),
+        &storage_path,
+    )
+    .await?;
+
+    Ok(RenderedImage {
+        bytes: out.bytes,
+        content_type: format.content_type().to_string(),
+    })
+}
+
+/// Transformations offered for a crop: WebP at each variant width below the
+/// crop's own width, plus the crop at full size
+pub fn crop_specs(name: &str, rect: CropRect) -> Vec<TransformSpec> {
+    let full_width = rect.width.min(MAX_TRANSFORM_DIMENSION);
+    let mut widths: Vec<u32> = image_service::variant_widths()
+        .into_iter()
+        .filter(|w| *w < full_width)
+        .collect();
+    widths.push(full_width);
+
+    widths
+        .into_iter()
+        .map(|w| TransformSpec {
+            crop: Some(name.to_string()),
+            width: Some(w),
+            format: Some(TransformFormat::Webp),
+            ..TransformSpec::default()
+        })
+        .collect()
+}
+
+/// Drop the cached transformations of a media file and their stored files,
+/// e.g. after its focal point or crops changed
+pub async fn invalidate(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    media_id: Uuid,
+) -> Result<(), ApiError> {
+    for path in MediaTransform::delete_for_media(pool, media_id).await? {
+        if let Err(e) = storage.delete(&path).await {
+            tracing::warn!(error = %e, path = %path, "Failed to delete cached image transformation");
+        }
+    }
+    Ok(())
+}
+
+/// Render the crop transformations of a media file in the background
+pub fn spawn_crop_variants(pool: PgPool, storage: Arc<dyn StorageBackend>, media: MediaFile) {
+    tokio::spawn(async move {
+        for (name, rect) in media.crop_rects() {
+            for spec in crop_specs(&name, rect) {
+                if let Err(e) = render(&pool, storage.as_ref(), &media, &spec).await {
+                    tracing::warn!(error = %e, media_id = %media.id, crop = %name, "Failed to render crop variant");
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_covers_options() {
+        let id = Uuid::new_v4();
+        let spec = parse_spec(
+            None,
+            Some(400),
+            Some(300),
+            Some("cover"),
+            Some("webp"),
+            None,
+        )
+        .unwrap();
+        let signature = sign_with("secret", id, 1, &spec);
+
+        assert_eq!(signature, sign_with("secret", id, 1, &spec));
+        let wider = TransformSpec {
+            width: Some(800),
+            ..spec.clone()
+        };
+        assert_ne!(signature, sign_with("secret", id, 1, &wider));
+        assert_ne!(signature, sign_with("secret", Uuid::new_v4(), 1, &spec));
+        assert_ne!(signature, sign_with("other", id, 1, &spec));
+        assert_ne!(signature, sign_with("secret", id, 2, &spec));
+        let cropped = TransformSpec {
+            crop: Some("1:1".to_string()),
+            ..spec.clone()
+        };
+        assert_ne!(signature, sign_with("secret", id, 1, &cropped));
+    }
+
+    #[test]
+    fn test_signed_url_round_trip() {
+        let settings = Settings::default();
+        let id = Uuid::new_v4();
+        let spec = parse_spec(None, Some(400), None, None, None, Some(60)).unwrap();
+        let url = signed_url(&settings, id, 7, &spec);
+
+        assert!(url.starts_with(&format!("/api/v1/media/{}/transform?w=400&q=60&v=7&s=", id)));
+        let signature = url.rsplit_once("s=").unwrap().1;
+        assert!(verify(&settings, id, 7, &spec, signature).is_ok());
+        assert!(verify(&settings, id, 8, &spec, signature).is_err());
+        assert!(verify(&settings, id, 7, &spec, "forged").is_err());
+
+        let spec = parse_spec(Some("16:9"), Some(800), None, None, None, None).unwrap();
+        let url = signed_url(&settings, id, 7, &spec);
+        assert!(url.contains("?crop=16:9&w=800&v=7&s="));
+    }
+
+    #[test]
+    fn test_crop_specs() {
+        let rect = CropRect {
+            x: 0,
+            y: 0,
+            width: 900,
+            height: 900,
+        };
+        let widths: Vec<Option<u32>> = crop_specs("1:1", rect).iter().map(|s| s.width).collect();
+        assert_eq!(widths, vec![Some(200), Some(400), Some(800), Some(900)]);
+
+        let small = CropRect { width: 150, ..rect };
+        let specs = crop_specs("1:1", small);
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].key(), "c1.1_w150_contain_q80_webp");
+    }
+
+    #[test]
+    fn test_parse_spec_validation() {
+        assert!(parse_spec(None, Some(0), None, None, None, None).is_err());
+        assert!(parse_spec(
+            None,
+            Some(MAX_TRANSFORM_DIMENSION + 1),
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+        assert!(parse_spec(None, Some(400), None, Some("cover"), None, None).is_err());
+        assert!(parse_spec(None, None, None, Some("stretch"), None, None).is_err());
+        assert!(parse_spec(None, None, None, None, Some("tiff"), None).is_err());
+        assert!(parse_spec(None, None, None, None, None, Some(0)).is_err());
+        assert!(parse_spec(None, None, None, None, Some("JPG"), Some(100)).is_ok());
+    }
+}
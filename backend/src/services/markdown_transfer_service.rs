@@ -0,0 +1,1063 @@
+//! Markdown import and export
+//!
+//! Bridges a site's blogs and pages and a tree of Markdown files with YAML
+//! front matter, the way static site generators keep content in git:
+//!
+//! ```text
+//! blog/hello-world.md       default locale
+//! blog/hello-world.de.md    other locales, one sibling file each
+//! pages/index.md            route `/`
+//! pages/about/team.md       route `/about/team`
+//! ```
+//!
+//! Exports contain published content only. Imports match blogs by slug and
+//! pages by route and update them, so the same tree can be imported again.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read, Write};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::blog::{CreateBlogRequest, UpdateBlogRequest};
+use crate::dto::content::CreateLocalizationRequest;
+use crate::dto::markdown_transfer::MarkdownImportReport;
+use crate::dto::page::{CreatePageRequest, UpdatePageRequest};
+use crate::dto::taxonomy::{CreateCategoryRequest, CreateTagRequest};
+use crate::errors::ApiError;
+use crate::models::blog::Blog;
+use crate::models::content::{ContentLocalization, ContentStatus};
+use crate::models::locale::Locale;
+use crate::models::media::MediaFile;
+use crate::models::page::{Page, PageWithContent};
+use crate::models::site_locale::SiteLocale;
+use crate::models::taxonomy::{Category, Tag};
+use crate::services::version_service;
+use crate::services::wordpress_import_service::slugify;
+use crate::AppState;
+
+/// Folder of blog files in the tree
+const BLOG_DIR: &str = "blog";
+
+/// Folder of page files in the tree
+const PAGES_DIR: &str = "pages";
+
+/// Largest total size of the Markdown files in an uploaded archive
+const MAX_EXTRACTED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Rows fetched per query while exporting
+const EXPORT_BATCH: i64 = 200;
+
+/// Change summary of versions written by an import
+const VERSION_SUMMARY: &str = "Imported from Markdown";
+
+/// Kind of content a file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContentKind {
+    Blog,
+    Page,
+}
+
+/// YAML front matter of a Markdown file.
+///
+/// Blog-only fields are ignored on pages and the other way around; unknown
+/// keys are ignored so files can carry generator-specific settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrontMatter {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    /// Page route; taken from the file path when missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+    /// Locale code; taken from the file name suffix, else the site's default locale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Blog publication date (`2024-05-01` or an RFC 3339 timestamp)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Last change; written on export, ignored on import
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    /// Tag slugs; names are accepted on import and slugified
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Category slugs, primary first; names are accepted on import and slugified
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    /// Public URL of a media file of the site
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub featured: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_time: Option<i16>,
+    /// `true` imports as Draft, `false` publishes; missing keeps the current status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Whether the page appears in navigation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navigation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navigation_order: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_description: Option<String>,
+}
+
+// ============================================
+// Files
+// ============================================
+
+/// Write a Markdown file: front matter between `---` lines, then the body
+pub fn render_file(front_matter: &FrontMatter, body: &str) -> Result<String, ApiError> {
+    let yaml = serde_norway::to_string(front_matter)
+        .map_err(|e| ApiError::Internal(format!("Failed to write front matter: {e}")))?;
+    let body = body.trim_end();
+    if body.is_empty() {
+        Ok(format!("---\n{yaml}---\n"))
+    } else {
+        Ok(format!("---\n{yaml}---\n\n{body}\n"))
+    }
+}
+
+/// Split a Markdown file into its front matter and body
+pub fn parse_file(raw: &str) -> Result<(FrontMatter, String), String> {
+    let raw = raw.trim_start_matches('\u{feff}');
+    let rest = raw
+        .strip_prefix("---\n")
+        .or_else(|| raw.strip_prefix("---\r\n"))
+        .ok_or("no front matter (the file must start with ---)")?;
+
+    let mut offset = 0;
+    let mut split = None;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            split = Some((&rest[..offset], &rest[offset + line.len()..]));
+            break;
+        }
+        offset += line.len();
+    }
+    let (yaml, body) = split.ok_or("front matter is not closed with ---")?;
+
+    let front_matter = if yaml.trim().is_empty() {
+        FrontMatter::default()
+    } else {
+        serde_norway::from_str(yaml).map_err(|e| format!("invalid front matter: {e}"))?
+    };
+    let body = body
+        .strip_prefix('\n')
+        .or_else(|| body.strip_prefix("\r\n"))
+        .unwrap_or(body)
+        .trim_end();
+    Ok((front_matter, body.to_string()))
+}
+
+/// Path of a file in the tree; `locale` is `None` for the default locale
+pub fn file_path(kind: ContentKind, key: &str, locale: Option<&str>) -> String {
+    let suffix = locale.map(|l| format!(".{l}")).unwrap_or_default();
+    match kind {
+        ContentKind::Blog => format!("{BLOG_DIR}/{key}{suffix}.md"),
+        ContentKind::Page if key == "/" => format!("{PAGES_DIR}/index{suffix}.md"),
+        ContentKind::Page => format!("{PAGES_DIR}{key}{suffix}.md"),
+    }
+}
+
+/// Kind, key (blog slug or page route) and locale suffix of a file in the tree.
+///
+/// The tree may sit below other folders (e.g. `src/content/blog/`), and
+/// `blog/<slug>/index.md` and `pages/<route>/index.md` are accepted as well.
+pub fn parse_path(path: &str) -> Option<(ContentKind, String, Option<String>)> {
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    if parts.iter().any(|p| p.starts_with('.') || *p == "__MACOSX") {
+        return None;
+    }
+    let start = parts
+        .iter()
+        .position(|p| *p == BLOG_DIR || *p == PAGES_DIR)?;
+    let (file, dirs) = parts[start + 1..].split_last()?;
+    let stem = file
+        .strip_suffix(".md")
+        .or_else(|| file.strip_suffix(".markdown"))?;
+    let (stem, locale) = match stem.split_once('.') {
+        Some((stem, locale)) => (stem, Some(locale.to_string())),
+        None => (stem, None),
+    };
+
+    if parts[start] == BLOG_DIR {
+        let slug = match (stem, dirs.last()) {
+            ("index", Some(dir)) => *dir,
+            _ => stem,
+        };
+        (!slug.is_empty()).then(|| (ContentKind::Blog, slug.to_string(), locale))
+    } else {
+        let mut segments = dirs.to_vec();
+        if stem != "index" {
+            segments.push(stem);
+        }
+        Some((
+            ContentKind::Page,
+            format!("/{}", segments.join("/")),
+            locale,
+        ))
+    }
+}
+
+/// Parse a front matter date: a plain date or a timestamp
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|d| d.date_naive())
+        })
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|d| d.date())
+        })
+}
+
+// ============================================
+// Export
+// ============================================
+
+/// Whether a page is visible on the public site right now
+fn is_live(page: &PageWithContent, now: DateTime<Utc>) -> bool {
+    matches!(
+        page.status,
+        ContentStatus::Published | ContentStatus::Scheduled
+    ) && page.publish_start.is_none_or(|start| start <= now)
+        && page.publish_end.is_none_or(|end| end > now)
+}
+
+/// Public URL of a media file, if it still exists
+async fn media_url(pool: &PgPool, id: Option<Uuid>) -> Result<Option<String>, ApiError> {
+    let Some(id) = id else {
+        return Ok(None);
+    };
+    match MediaFile::find_by_id(pool, id).await {
+        Ok(media) => Ok(media.public_url),
+        Err(ApiError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Export a site's published blogs and pages as a zip of Markdown files
+pub async fn export(pool: &PgPool, site_id: Uuid) -> Result<Vec<u8>, ApiError> {
+    let default_locale = SiteLocale::find_all_for_site(pool, site_id)
+        .await?
+        .into_iter()
+        .find(|l| l.is_default)
+        .map(|l| l.locale_id);
+    let codes: HashMap<Uuid, String> = Locale::find_all_including_inactive(pool)
+        .await?
+        .into_iter()
+        .map(|l| (l.id, l.code))
+        .collect();
+    let suffix = |locale_id: Uuid| {
+        (Some(locale_id) != default_locale)
+            .then(|| codes.get(&locale_id).cloned())
+            .flatten()
+    };
+
+    let mut files = Vec::new();
+
+    let mut blogs = Vec::new();
+    loop {
+        let batch =
+            Blog::find_published_for_site(pool, site_id, EXPORT_BATCH, blogs.len() as i64).await?;
+        let done = (batch.len() as i64) < EXPORT_BATCH;
+        blogs.extend(batch);
+        if done {
+            break;
+        }
+    }
+    for blog in blogs {
+        let Some(slug) = blog.slug.clone() else {
+            continue;
+        };
+        let tags = Tag::find_for_content(pool, blog.content_id).await?;
+        let categories = Category::find_for_content(pool, blog.content_id).await?;
+        let shared = FrontMatter {
+            slug: Some(slug.clone()),
+            date: Some(blog.published_date.to_string()),
+            author: Some(blog.author.clone()),
+            tags: tags.into_iter().map(|t| t.slug).collect(),
+            categories: categories.into_iter().map(|c| c.slug).collect(),
+            cover_image: media_url(pool, blog.cover_image_id).await?,
+            header_image: media_url(pool, blog.header_image_id).await?,
+            featured: Some(blog.is_featured),
+            reading_time: blog.reading_time_minutes,
+            ..FrontMatter::default()
+        };
+        for loc in ContentLocalization::find_all_for_content(pool, blog.content_id).await? {
+            let front_matter = FrontMatter {
+                title: loc.title,
+                subtitle: loc.subtitle,
+                locale: codes.get(&loc.locale_id).cloned(),
+                updated: Some(blog.updated_at.max(loc.updated_at)),
+                excerpt: loc.excerpt,
+                meta_title: loc.meta_title,
+                meta_description: loc.meta_description,
+                ..shared.clone()
+            };
+            let path = file_path(ContentKind::Blog, &slug, suffix(loc.locale_id).as_deref());
+            let body = loc.body.unwrap_or_default();
+            files.push((path, render_file(&front_matter, &body)?));
+        }
+    }
+
+    let now = Utc::now();
+    let mut pages = Vec::new();
+    loop {
+        let batch =
+            Page::find_all_for_site(pool, site_id, None, EXPORT_BATCH, pages.len() as i64).await?;
+        let done = (batch.len() as i64) < EXPORT_BATCH;
+        pages.extend(batch);
+        if done {
+            break;
+        }
+    }
+    for page in pages.into_iter().filter(|p| is_live(p, now)) {
+        let shared = FrontMatter {
+            slug: page.slug.clone(),
+            route: Some(page.route.clone()),
+            template: page.template.clone(),
+            navigation: Some(page.is_in_navigation),
+            navigation_order: page.navigation_order,
+            ..FrontMatter::default()
+        };
+        for loc in ContentLocalization::find_all_for_content(pool, page.content_id).await? {
+            let front_matter = FrontMatter {
+                title: loc.title,
+                subtitle: loc.subtitle,
+                locale: codes.get(&loc.locale_id).cloned(),
+                updated: Some(page.updated_at.max(loc.updated_at)),
+                excerpt: loc.excerpt,
+                meta_title: loc.meta_title,
+                meta_description: loc.meta_description,
+                ..shared.clone()
+            };
+            let path = file_path(
+                ContentKind::Page,
+                &page.route,
+                suffix(loc.locale_id).as_deref(),
+            );
+            let body = loc.body.unwrap_or_default();
+            files.push((path, render_file(&front_matter, &body)?));
+        }
+    }
+
+    write_archive(&files)
+}
+
+/// Zip the rendered files
+fn write_archive(files: &[(String, String)]) -> Result<Vec<u8>, ApiError> {
+    let zip_error =
+        |e: zip::result::ZipError| ApiError::Internal(format!("Failed to write archive: {e}"));
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (path, contents) in files {
+        zip.start_file(path.as_str(), options).map_err(zip_error)?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| ApiError::Internal(format!("Failed to write archive: {e}")))?;
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+// ============================================
+// Import
+// ============================================
+
+/// Read the Markdown files of an uploaded zip that belong to the tree
+pub fn read_archive(bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, ApiError> {
+    let invalid = |e: String| ApiError::BadRequest(format!("Not a zip archive: {e}"));
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| invalid(e.to_string()))?;
+
+    let mut files = Vec::new();
+    let mut read = 0u64;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| invalid(e.to_string()))?;
+        if entry.is_dir() || parse_path(entry.name()).is_none() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut contents = Vec::new();
+        (&mut entry)
+            .take(MAX_EXTRACTED_BYTES - read + 1)
+            .read_to_end(&mut contents)
+            .map_err(|e| invalid(e.to_string()))?;
+        read += contents.len() as u64;
+        if read > MAX_EXTRACTED_BYTES {
+            return Err(ApiError::BadRequest(format!(
+                "Markdown files exceed {} MiB",
+                MAX_EXTRACTED_BYTES / (1024 * 1024)
+            )));
+        }
+        files.push((name, contents));
+    }
+
+    if files.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "The archive contains no Markdown files in a {BLOG_DIR}/ or {PAGES_DIR}/ folder"
+        )));
+    }
+    Ok(files)
+}
+
+/// One parsed file of a blog or page
+struct Document {
+    path: String,
+    locale_id: Uuid,
+    front_matter: FrontMatter,
+    body: String,
+}
+
+struct Importer<'a> {
+    state: &'a AppState,
+    site_id: Uuid,
+    default_locale_id: Uuid,
+    user_id: Option<Uuid>,
+    report: MarkdownImportReport,
+    /// Tag slug -> tag ID
+    tag_ids: HashMap<String, Uuid>,
+    /// Category slug -> category ID
+    category_ids: HashMap<String, Uuid>,
+}
+
+/// Import Markdown files into a site.
+///
+/// Files that cannot be imported are reported as warnings; database errors
+/// abort the import, which can then be run again.
+pub async fn import(
+    state: &AppState,
+    site_id: Uuid,
+    files: Vec<(String, Vec<u8>)>,
+    user_id: Option<Uuid>,
+) -> Result<MarkdownImportReport, ApiError> {
+    let pool = &state.db;
+    let locales = SiteLocale::find_all_for_site(pool, site_id).await?;
+    let default_locale_id = locales
+        .iter()
+        .find(|l| l.is_default)
+        .map(|l| l.locale_id)
+        .ok_or_else(|| {
+            ApiError::BadRequest("The site needs a default locale before importing".to_string())
+        })?;
+    let locale_ids: HashMap<String, Uuid> = locales
+        .iter()
+        .map(|l| (l.code.to_lowercase(), l.locale_id))
+        .collect();
+
+    let mut importer = Importer {
+        state,
+        site_id,
+        default_locale_id,
+        user_id,
+        report: MarkdownImportReport::default(),
+        tag_ids: HashMap::new(),
+        category_ids: Category::find_all_for_site(pool, site_id)
+            .await?
+            .into_iter()
+            .map(|c| (c.slug, c.id))
+            .collect(),
+    };
+
+    // Group sibling files; parent routes sort before their children
+    let mut groups: BTreeMap<(ContentKind, String), Vec<Document>> = BTreeMap::new();
+    for (path, bytes) in files {
+        let Some((kind, key, suffix)) = parse_path(&path) else {
+            continue;
+        };
+        let Ok(raw) = String::from_utf8(bytes) else {
+            importer.warn(format!("{path}: not UTF-8 text"));
+            continue;
+        };
+        let (front_matter, body) = match parse_file(&raw) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                importer.warn(format!("{path}: {e}"));
+                continue;
+            }
+        };
+        let locale_id = match front_matter.locale.clone().or(suffix) {
+            Some(code) => match locale_ids.get(&code.to_lowercase()) {
+                Some(&id) => id,
+                None => {
+                    importer.warn(format!(
+                        "{path}: locale '{code}' is not enabled on the site"
+                    ));
+                    continue;
+                }
+            },
+            None => default_locale_id,
+        };
+        let documents = groups.entry((kind, key)).or_default();
+        if documents.iter().any(|d| d.locale_id == locale_id) {
+            importer.warn(format!("{path}: another file has the same locale; skipped"));
+            continue;
+        }
+        documents.push(Document {
+            path,
+            locale_id,
+            front_matter,
+            body,
+        });
+    }
+
+    for ((kind, key), documents) in groups {
+        match kind {
+            ContentKind::Blog => importer.import_blog(&key, &documents).await?,
+            ContentKind::Page => importer.import_page(&key, &documents).await?,
+        }
+    }
+
+    Ok(importer.report)
+}
+
+impl Importer<'_> {
+    fn warn(&mut self, warning: String) {
+        self.report.warnings.push(warning);
+    }
+
+    /// The file in the default locale, else the first one
+    fn primary<'d>(&self, documents: &'d [Document]) -> &'d Document {
+        documents
+            .iter()
+            .find(|d| d.locale_id == self.default_locale_id)
+            .unwrap_or(&documents[0])
+    }
+
+    /// Localizations of all files, checked like ones sent to the API
+    fn localizations(documents: &[Document]) -> Result<Vec<CreateLocalizationRequest>, String> {
+        documents
+            .iter()
+            .map(|d| {
+                let fm = &d.front_matter;
+                let req = CreateLocalizationRequest {
+                    locale_id: d.locale_id,
+                    title: fm.title.trim().to_string(),
+                    subtitle: fm.subtitle.clone(),
+                    excerpt: fm.excerpt.clone(),
+                    body: (!d.body.is_empty()).then(|| d.body.clone()),
+                    meta_title: fm.meta_title.clone(),
+                    meta_description: fm.meta_description.clone(),
+                };
+                req.validate().map_err(|e| format!("{}: {}", d.path, e))?;
+                Ok(req)
+            })
+            .collect()
+    }
+
+    /// Create or overwrite the localization of each file
+    async fn write_localizations(
+        &mut self,
+        conn: &mut PgConnection,
+        content_id: Uuid,
+        localizations: Vec<CreateLocalizationRequest>,
+    ) -> Result<(), ApiError> {
+        let existing = ContentLocalization::find_all_for_content(&mut *conn, content_id).await?;
+        for req in localizations {
+            match existing.iter().find(|l| l.locale_id == req.locale_id) {
+                Some(current) => {
+                    ContentLocalization::update(
+                        &mut *conn,
+                        current.id,
+                        Some(&req.title),
+                        req.subtitle.as_deref(),
+                        req.excerpt.as_deref(),
+                        req.body.as_deref(),
+                        req.meta_title.as_deref(),
+                        req.meta_description.as_deref(),
+                        None,
+                    )
+                    .await?;
+                }
+                None => {
+                    ContentLocalization::create(
+                        &mut *conn,
+                        content_id,
+                        req.locale_id,
+                        &req.title,
+                        req.subtitle.as_deref(),
+                        req.excerpt.as_deref(),
+                        req.body.as_deref(),
+                        req.meta_title.as_deref(),
+                        req.meta_description.as_deref(),
+                    )
+                    .await?;
+                }
+            }
+            self.report.localizations += 1;
+        }
+        Ok(())
+    }
+
+    /// Media file of the site with the given public URL
+    async fn media_id(&mut self, url: Option<&str>, path: &str) -> Result<Option<Uuid>, ApiError> {
+        let Some(url) = url.map(str::trim).filter(|u| !u.is_empty()) else {
+            return Ok(None);
+        };
+        match MediaFile::find_by_public_url(&self.state.db, self.site_id, url).await? {
+            Some(media) => Ok(Some(media.id)),
+            None => {
+                self.warn(format!("{path}: image '{url}' is not in the site's media"));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Status set by the `draft` flag, if any
+    fn status(front_matter: &FrontMatter) -> Option<ContentStatus> {
+        front_matter.draft.map(|draft| {
+            if draft {
+                ContentStatus::Draft
+            } else {
+                ContentStatus::Published
+            }
+        })
+    }
+
+    async fn import_blog(&mut self, key: &str, documents: &[Document]) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+        let primary = self.primary(documents);
+        let fm = &primary.front_matter;
+        let path = primary.path.clone();
+
+        let slug = fm.slug.clone().unwrap_or_else(|| key.to_string());
+        let published_date = fm.date.as_deref().and_then(parse_date);
+        if published_date.is_none() {
+            if let Some(date) = &fm.date {
+                self.warn(format!("{path}: unreadable date '{date}'"));
+            }
+        }
+        let cover_image_id = self.media_id(fm.cover_image.as_deref(), &path).await?;
+        let header_image_id = self.media_id(fm.header_image.as_deref(), &path).await?;
+
+        let existing = match Blog::find_by_slug(pool, self.site_id, &slug).await {
+            Ok(existing) => Some(existing),
+            Err(ApiError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let checked = Self::localizations(documents);
+
+        // The content, its localizations and its version are written together
+        let mut tx = pool.begin().await?;
+        let content_id = match existing {
+            Some(existing) => {
+                let req = UpdateBlogRequest {
+                    slug: None,
+                    author: fm.author.clone(),
+                    published_date,
+                    reading_time_minutes: fm.reading_time,
+                    cover_image_id,
+                    header_image_id,
+                    is_featured: fm.featured,
+                    allow_comments: None,
+                    status: Self::status(fm),
+                    publish_start: existing.publish_start,
+                    publish_end: existing.publish_end,
+                    redirect_to: None,
+                };
+                let localizations = match req
+                    .validate()
+                    .map_err(|e| format!("{path}: {e}"))
+                    .and(checked)
+                {
+                    Ok(localizations) => localizations,
+                    Err(e) => {
+                        self.report.blogs.failed += 1;
+                        self.warn(e);
+                        return Ok(());
+                    }
+                };
+                Blog::update(&mut tx, existing.id, req).await?;
+                self.write_localizations(&mut tx, existing.content_id, localizations)
+                    .await?;
+                self.report.blogs.updated += 1;
+                existing.content_id
+            }
+            None => {
+                let req = CreateBlogRequest {
+                    slug,
+                    author: fm.author.clone().unwrap_or_else(|| "Unknown".to_string()),
+                    published_date: published_date.unwrap_or_else(|| Utc::now().date_naive()),
+                    reading_time_minutes: fm.reading_time,
+                    cover_image_id,
+                    header_image_id,
+                    is_featured: fm.featured.unwrap_or(false),
+                    allow_comments: true,
+                    status: Self::status(fm).unwrap_or(ContentStatus::Published),
+                    publish_start: None,
+                    publish_end: None,
+                    site_ids: vec![self.site_id],
+                    environment: None,
+                };
+                let localizations = match req
+                    .validate()
+                    .map_err(|e| format!("{path}: {e}"))
+                    .and(checked)
+                {
+                    Ok(localizations) => localizations,
+                    Err(e) => {
+                        self.report.blogs.failed += 1;
+                        self.warn(e);
+                        return Ok(());
+                    }
+                };
+                let blog = Blog::create(&mut tx, req).await?;
+                self.write_localizations(&mut tx, blog.content_id, localizations)
+                    .await?;
+                self.report.blogs.created += 1;
+                blog.content_id
+            }
+        };
+
+        version_service::record_version(&mut tx, content_id, VERSION_SUMMARY, self.user_id).await?;
+        tx.commit().await?;
+
+        self.sync_terms(content_id, &primary.front_matter, &path)
+            .await?;
+        Ok(())
+    }
+
+    async fn import_page(&mut self, key: &str, documents: &[Document]) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+        let primary = self.primary(documents);
+        let fm = &primary.front_matter;
+        let path = primary.path.clone();
+
+        let route = fm.route.clone().unwrap_or_else(|| key.to_string());
+        let slug = fm.slug.clone().unwrap_or_else(|| {
+            match route.rsplit('/').next().filter(|s| !s.is_empty()) {
+                Some(segment) => segment.to_string(),
+                None => "home".to_string(),
+            }
+        });
+        // Parents are imported first, so an existing parent route is found here
+        let parent_page_id = match route.rsplit_once('/') {
+            Some((parent, _)) if !parent.is_empty() => {
+                match Page::find_by_route(pool, self.site_id, parent).await {
+                    Ok(parent) => Some(parent.id),
+                    Err(ApiError::NotFound(_)) => None,
+                    Err(e) => return Err(e),
+                }
+            }
+            _ => None,
+        };
+
+        let existing = match Page::find_by_route(pool, self.site_id, &route).await {
+            Ok(existing) => Some(existing),
+            Err(ApiError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let checked = Self::localizations(documents);
+
+        let mut tx = pool.begin().await?;
+        let content_id = match existing {
+            Some(existing) => {
+                let req = UpdatePageRequest {
+                    route: None,
+                    slug: fm.slug.clone(),
+                    page_type: None,
+                    template: fm.template.clone(),
+                    is_in_navigation: fm.navigation,
+                    navigation_order: fm.navigation_order,
+                    parent_page_id,
+                    status: Self::status(fm),
+                    publish_start: existing.publish_start,
+                    publish_end: existing.publish_end,
+                    redirect_to: None,
+                };
+                let localizations = match req
+                    .validate()
+                    .map_err(|e| format!("{path}: {e}"))
+                    .and(checked)
+                {
+                    Ok(localizations) => localizations,
+                    Err(e) => {
+                        self.report.pages.failed += 1;
+                        self.warn(e);
+                        return Ok(());
+                    }
+                };
+                Page::update(&mut tx, existing.id, req).await?;
+                self.write_localizations(&mut tx, existing.content_id, localizations)
+                    .await?;
+                self.report.pages.updated += 1;
+                existing.content_id
+            }
+            None => {
+                let req = CreatePageRequest {
+                    route,
+                    slug,
+                    page_type: Default::default(),
+                    template: fm.template.clone(),
+                    is_in_navigation: fm.navigation.unwrap_or(false),
+                    navigation_order: fm.navigation_order,
+                    parent_page_id,
+                    status: Self::status(fm).unwrap_or(ContentStatus::Published),
+                    publish_start: None,
+                    publish_end: None,
+                    site_ids: vec![self.site_id],
+                    environment: None,
+                };
+                let localizations = match req
+                    .validate()
+                    .map_err(|e| format!("{path}: {e}"))
+                    .and(checked)
+                {
+                    Ok(localizations) => localizations,
+                    Err(e) => {
+                        self.report.pages.failed += 1;
+                        self.warn(e);
+                        return Ok(());
+                    }
+                };
+                let page = Page::create(&mut tx, req).await?;
+                self.write_localizations(&mut tx, page.content_id, localizations)
+                    .await?;
+                self.report.pages.created += 1;
+                page.content_id
+            }
+        };
+        version_service::record_version(&mut tx, content_id, VERSION_SUMMARY, self.user_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Make the content's tags and categories match the front matter
+    async fn sync_terms(
+        &mut self,
+        content_id: Uuid,
+        front_matter: &FrontMatter,
+        path: &str,
+    ) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+
+        let mut tag_ids = Vec::new();
+        for value in &front_matter.tags {
+            match self.tag_id(value).await? {
+                Some(id) if !tag_ids.contains(&id) => tag_ids.push(id),
+                Some(_) => {}
+                None => self.warn(format!("{path}: unusable tag '{value}'")),
+            }
+        }
+        for tag in Tag::find_for_content(pool, content_id).await? {
+            if !tag_ids.contains(&tag.id) {
+                Tag::remove_from_content(pool, content_id, tag.id).await?;
+            }
+        }
+        for &id in &tag_ids {
+            Tag::assign_to_content(pool, content_id, id).await?;
+        }
+
+        let mut category_ids = Vec::new();
+        for value in &front_matter.categories {
+            match self.category_id(value).await? {
+                Some(id) if !category_ids.contains(&id) => category_ids.push(id),
+                Some(_) => {}
+                None => self.warn(format!("{path}: unusable category '{value}'")),
+            }
+        }
+        for category in Category::find_for_content(pool, content_id).await? {
+            if !category_ids.contains(&category.id) {
+                Category::remove_from_content(pool, content_id, category.id).await?;
+            }
+        }
+        for (i, &id) in category_ids.iter().enumerate() {
+            Category::assign_to_content(pool, content_id, id, i == 0).await?;
+        }
+        Ok(())
+    }
+
+    /// Tag for a slug or name, linked to the site and created if missing
+    async fn tag_id(&mut self, value: &str) -> Result<Option<Uuid>, ApiError> {
+        let slug = slugify(value);
+        if slug.is_empty() {
+            return Ok(None);
+        }
+        if let Some(&id) = self.tag_ids.get(&slug) {
+            return Ok(Some(id));
+        }
+        let state = self.state;
+        let pool = &state.db;
+        let id = match Tag::find_by_slug(pool, &slug).await {
+            Ok(existing) => {
+                Tag::add_to_site(pool, existing.id, self.site_id).await?;
+                existing.id
+            }
+            Err(ApiError::NotFound(_)) => {
+                let req = CreateTagRequest {
+                    slug: slug.clone(),
+                    is_global: false,
+                    site_id: Some(self.site_id),
+                };
+                let tag = Tag::create(pool, &req).await?;
+                Tag::add_localization(pool, tag.id, self.default_locale_id, value.trim()).await?;
+                tag.id
+            }
+            Err(e) => return Err(e),
+        };
+        self.tag_ids.insert(slug, id);
+        Ok(Some(id))
+    }
+
+    /// Category of the site for a slug or name; a top-level one is created if missing
+    async fn category_id(&mut self, value: &str) -> Result<Option<Uuid>, ApiError> {
+        let slug = slugify(value);
+        if slug.is_empty() {
+            return Ok(None);
+        }
+        if let Some(&id) = self.category_ids.get(&slug) {
+            return Ok(Some(id));
+        }
+        let state = self.state;
+        let pool = &state.db;
+        let id = match Category::find_by_slug(pool, None, &slug).await? {
+            Some(existing) => {
+                Category::add_to_site(pool, existing.id, self.site_id).await?;
+                existing.id
+            }
+            None => {
+                let req = CreateCategoryRequest {
+                    parent_id: None,
+                    slug: slug.clone(),
+                    is_global: false,
+                    site_id: Some(self.site_id),
+                };
+                let category = Category::create(pool, &req).await?;
+                Category::add_localization(
+                    pool,
+                    category.id,
+                    self.default_locale_id,
+                    value.trim(),
+                    None,
+                )
+                .await?;
+                category.id
+            }
+        };
+        self.category_ids.insert(slug, id);
+        Ok(Some(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_parse_file_round_trip() {
+        let front_matter = FrontMatter {
+            title: "Hello: World".to_string(),
+            slug: Some("hello-world".to_string()),
+            locale: Some("en".to_string()),
+            date: Some("2024-05-01".to_string()),
+            tags: vec!["rust".to_string(), "web".to_string()],
+            featured: Some(true),
+            ..FrontMatter::default()
+        };
+        let rendered = render_file(&front_matter, "# Hello\n\n---\n\nWorld\n\n").unwrap();
+        assert!(rendered.starts_with("---\ntitle: 'Hello: World'\n"));
+        assert!(!rendered.contains("subtitle"));
+        assert!(rendered.ends_with("---\n\n# Hello\n\n---\n\nWorld\n"));
+
+        let (parsed, body) = parse_file(&rendered).unwrap();
+        assert_eq!(parsed, front_matter);
+        assert_eq!(body, "# Hello\n\n---\n\nWorld");
+
+        let (_, body) = parse_file(&render_file(&front_matter, "").unwrap()).unwrap();
+        assert_eq!(body, "");
+    }
+
+    #[test]
+    fn test_parse_file_accepts_generator_files() {
+        let raw = "\u{feff}---\r\ntitle: Post\r\ndate: 2024-05-01T09:30:00Z\r\nlayout: post\r\n---\r\nBody\r\n";
+        let (fm, body) = parse_file(raw).unwrap();
+        assert_eq!(fm.title, "Post");
+        assert_eq!(
+            fm.date.as_deref().and_then(parse_date),
+            NaiveDate::from_ymd_opt(2024, 5, 1)
+        );
+        assert_eq!(body, "Body");
+
+        assert!(parse_file("# No front matter").is_err());
+        assert!(parse_file("---\ntitle: Open\n").is_err());
+        assert!(parse_file("---\ntags: [unclosed\n---\n").is_err());
+        assert_eq!(
+            parse_date("2024-05-01 09:30:00"),
+            NaiveDate::from_ymd_opt(2024, 5, 1)
+        );
+        assert_eq!(parse_date("May 1st"), None);
+    }
+
+    #[test]
+    fn test_file_paths() {
+        assert_eq!(
+            file_path(ContentKind::Blog, "hello-world", None),
+            "blog/hello-world.md"
+        );
+        assert_eq!(
+            file_path(ContentKind::Blog, "hello-world", Some("de")),
+            "blog/hello-world.de.md"
+        );
+        assert_eq!(file_path(ContentKind::Page, "/", None), "pages/index.md");
+        assert_eq!(
+            file_path(ContentKind::Page, "/about/team", Some("de-AT")),
+            "pages/about/team.de-AT.md"
+        );
+
+        for path in [
+            "blog/hello-world.md",
+            "blog/hello-world.de.md",
+            "pages/index.md",
+            "pages/about/team.de-AT.md",
+        ] {
+            let (kind, key, locale) = parse_path(path).unwrap();
+            assert_eq!(file_path(kind, &key, locale.as_deref()), path);
+        }
+    }
+
+    #[test]
+    fn test_parse_path_layouts() {
+        assert_eq!(
+            parse_path("my-site-main/src/content/blog/hello/index.md"),
+            Some((ContentKind::Blog, "hello".to_string(), None))
+        );
+        assert_eq!(
+            parse_path("pages/about/index.fr.markdown"),
+            Some((
+                ContentKind::Page,
+                "/about".to_string(),
+                Some("fr".to_string())
+            ))
+        );
+        assert_eq!(parse_path("blog/notes.txt"), None);
+        assert_eq!(parse_path("README.md"), None);
+        assert_eq!(parse_path("__MACOSX/blog/._hello.md"), None);
+        assert_eq!(parse_path("blog/.draft.md"), None);
+    }
+}
@@ -0,0 +1,395 @@
+//! Media upload pipeline
+//!
+//! Detects the MIME type, enforces the per-site size limit, deduplicates by
+//! checksum, stores the original, generates image variants and records the
+//! media file. Shared by the upload endpoint and importers.
+//!
+//! The AVIF variant is encoded in the background after the upload returns,
+//! and the scheduler backfills it for images uploaded before AVIF support.
+
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::media::{MediaResponse, MediaVariantResponse, ALL_ALLOWED_MIMES};
+use crate::errors::ApiError;
+use crate::models::media::{MediaFile, MediaVariant, StorageProvider};
+use crate::models::site_settings::{
+    SiteSetting, KEY_AVIF_QUALITY, KEY_AVIF_SPEED, KEY_MAX_MEDIA_FILE_SIZE,
+};
+use crate::services::image_service::{self, AvifOptions};
+use crate::services::storage::StorageBackend;
+use crate::AppState;
+
+/// A file to run through the upload pipeline
+#[derive(Debug, Clone)]
+pub struct MediaUpload {
+    pub bytes: Vec<u8>,
+    /// Name of the file as supplied by the client or source
+    pub original_filename: String,
+    /// Content type claimed by the client, used when sniffing fails
+    pub content_type: Option<String>,
+    /// Sites to link the file to; the first one decides the size limit and storage path
+    pub site_ids: Vec<Uuid>,
+    pub folder_id: Option<Uuid>,
+    pub is_global: bool,
+    pub uploaded_by: Option<Uuid>,
+}
+
+/// Result of an upload
+#[derive(Debug, Clone)]
+pub struct StoredMedia {
+    pub media: MediaResponse,
+    /// False when an identical file already existed and was returned instead
+    pub created: bool,
+}
+
+/// Run a file through the upload pipeline.
+///
+/// Identical files (same SHA-256) are not stored twice; the existing media
+/// file is returned with `created = false`.
+pub async fn upload(state: &AppState, upload: MediaUpload) -> Result<StoredMedia, ApiError> {
+    let site_id = *upload
+        .site_ids
+        .first()
+        .ok_or_else(|| ApiError::BadRequest("At least one site ID is required".to_string()))?;
+
+    if upload.bytes.is_empty() {
+        return Err(ApiError::BadRequest("Uploaded file is empty".to_string()));
+    }
+
+    // Detect MIME type via magic bytes
+    let mime_type = detect_mime_type(
+        &upload.bytes,
+        upload.content_type.as_deref(),
+        &upload.original_filename,
+    );
+    if !ALL_ALLOWED_MIMES.contains(&mime_type.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "File type '{}' is not allowed",
+            mime_type
+        )));
+    }
+
+    // Validate file size against per-site limit
+    let file_size = upload.bytes.len() as i64;
+    let max_size = SiteSetting::get_value(&state.db, site_id, KEY_MAX_MEDIA_FILE_SIZE)
+        .await?
+        .as_i64()
+        .unwrap_or(52_428_800);
+
+    if file_size > max_size {
+        return Err(ApiError::BadRequest(format!(
+            "File size {} exceeds the maximum of {} bytes",
+            file_size, max_size
+        )));
+    }
+
+    // Deduplication check
+    let checksum = sha256_hex(&upload.bytes);
+    if let Some(existing) = MediaFile::find_by_checksum(&state.db, &checksum).await? {
+        let media = MediaFile::find_with_variants(&state.db, existing.id).await?;
+        return Ok(StoredMedia {
+            media: MediaResponse::from(media),
+            created: false,
+        });
+    }
+
+    // Sanitize filename and build storage path
+    let sanitized_filename = sanitize_filename(&upload.original_filename);
+    let now = chrono::Utc::now();
+    let storage_path = format!(
+        "{}/{}/{:02}/{}",
+        site_id,
+        now.format("%Y"),
+        now.format("%m"),
+        sanitized_filename,
+    );
+
+    // Store original file
+    let public_url = state
+        .storage
+        .store(&storage_path, &upload.bytes, &mime_type)
+        .await?;
+
+    let (width, height) = if mime_type.starts_with("image/") {
+        detect_image_dimensions(&upload.bytes)
+    } else {
+        (None, None)
+    };
+
+    // Generate image variants
+    let extension = upload.original_filename.rsplit('.').next().unwrap_or("bin");
+    let base_path = storage_path
+        .rsplit_once('.')
+        .map(|(b, _)| b)
+        .unwrap_or(&storage_path);
+
+    let variants = if mime_type.starts_with("image/") && !mime_type.contains("svg") {
+        image_service::generate_variants(&upload.bytes, base_path, extension, &state.storage)
+            .await?
+    } else {
+        vec![]
+    };
+
+    let storage_provider = if state.settings.storage.provider == "s3" {
+        StorageProvider::S3
+    } else {
+        StorageProvider::Local
+    };
+
+    let media = MediaFile::create_from_upload(
+        &state.db,
+        &sanitized_filename,
+        &upload.original_filename,
+        &mime_type,
+        file_size,
+        storage_provider,
+        &storage_path,
+        &public_url,
+        &checksum,
+        width,
+        height,
+        upload.uploaded_by,
+        upload.is_global,
+        upload.folder_id,
+        upload.site_ids,
+    )
+    .await?;
+
+    let db_variants = if !variants.is_empty() {
+        MediaVariant::create_batch(&state.db, media.id, variants).await?
+    } else {
+        vec![]
+    };
+    if !db_variants.is_empty() {
+        spawn_avif_variant(state.db.clone(), state.storage.clone(), media.clone());
+    }
+
+    let response = MediaResponse {
+        id: media.id,
+        filename: media.filename,
+        original_filename: media.original_filename,
+        mime_type: media.mime_type,
+        file_size: media.file_size,
+        storage_provider: media.storage_provider,
+        public_url: media.public_url,
+        width: media.width,
+        height: media.height,
+        duration: media.duration,
+        is_global: media.is_global,
+        created_at: media.created_at,
+        updated_at: media.updated_at,
+        variants: db_variants
+            .into_iter()
+            .map(MediaVariantResponse::from)
+            .collect(),
+        focal_point: None,
+        crops: Vec::new(),
+    };
+
+    Ok(StoredMedia {
+        media: response,
+        created: true,
+    })
+}
+
+/// AVIF encoder settings of a site
+pub async fn avif_options(pool: &PgPool, site_id: Uuid) -> Result<AvifOptions, ApiError> {
+    let defaults = AvifOptions::default();
+    let quality = SiteSetting::get_value(pool, site_id, KEY_AVIF_QUALITY)
+        .await?
+        .as_u64()
+        .map_or(defaults.quality, |v| v.clamp(1, 100) as u8);
+    let speed = SiteSetting::get_value(pool, site_id, KEY_AVIF_SPEED)
+        .await?
+        .as_u64()
+        .map_or(defaults.speed, |v| v.clamp(1, 10) as u8);
+
+    Ok(AvifOptions { quality, speed })
+}
+
+/// Encode and record the AVIF variant of an image, using its owning site's
+/// settings. Returns false if the file is not a decodable image.
+pub async fn generate_avif_variant(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    media: &MediaFile,
+) -> Result<bool, ApiError> {
+    let options = match MediaFile::find_owner_site(pool, media.id).await? {
+        Some(site_id) => avif_options(pool, site_id).await?,
+        None => AvifOptions::default(),
+    };
+    let original = storage.read(&media.storage_path).await?;
+    let base_path = media
+        .storage_path
+        .rsplit_once('.')
+        .map(|(b, _)| b)
+        .unwrap_or(&media.storage_path);
+
+    let Some(variant) =
+        image_service::generate_avif_variant(original, base_path, storage, options).await?
+    else {
+        return Ok(false);
+    };
+    MediaVariant::create_if_missing(pool, media.id, &variant).await?;
+    Ok(true)
+}
+
+/// Generate the AVIF variant of a new upload in the background
+fn spawn_avif_variant(pool: PgPool, storage: Arc<dyn StorageBackend>, media: MediaFile) {
+    tokio::spawn(async move {
+        if let Err(e) = generate_avif_variant(&pool, storage.as_ref(), &media).await {
+            tracing::warn!(error = %e, media_id = %media.id, "Failed to generate AVIF variant");
+        }
+    });
+}
+
+/// Outcome of one AVIF backfill batch
+#[derive(Debug, Default)]
+pub struct AvifBackfillReport {
+    pub generated: u64,
+    /// Images that could not be read or decoded; they are marked and not
+    /// picked up by later batches
+    pub failed: Vec<Uuid>,
+}
+
+/// Generate the AVIF variant for up to `batch` images that lack one, newest
+/// first
+pub async fn backfill_avif_variants(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    batch: u32,
+) -> Result<AvifBackfillReport, ApiError> {
+    let media = MediaFile::find_images_without_avif(pool, batch as i64).await?;
+
+    let mut report = AvifBackfillReport::default();
+    for media in media {
+        match generate_avif_variant(pool, storage, &media).await {
+            Ok(true) => report.generated += 1,
+            Ok(false) => report.failed.push(media.id),
+            Err(e) => {
+                tracing::warn!(error = %e, media_id = %media.id, "Failed to backfill AVIF variant");
+                report.failed.push(media.id);
+            }
+        }
+    }
+    for id in &report.failed {
+        MediaFile::mark_avif_failed(pool, *id).await?;
+    }
+
+    Ok(report)
+}
+
+/// Sniff the MIME type, falling back to the claimed type and then the extension
+fn detect_mime_type(bytes: &[u8], content_type: Option<&str>, filename: &str) -> String {
+    let mime_type = infer::get(bytes)
+        .map(|t| t.mime_type().to_string())
+        .or_else(|| content_type.map(str::to_string))
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+
+    // For text-based types that infer can't detect, check extension
+    if mime_type == "application/octet-stream" {
+        match filename.rsplit('.').next().map(|e| e.to_lowercase()) {
+            Some(ext) if ext == "md" => "text/markdown".to_string(),
+            Some(ext) if ext == "txt" => "text/plain".to_string(),
+            Some(ext) if ext == "svg" => "image/svg+xml".to_string(),
+            _ => mime_type,
+        }
+    } else {
+        mime_type
+    }
+}
+
+/// Hex-encoded SHA-256 of a file
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    let hash = hasher.finalize();
+    hash.iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()
+}
+
+/// Sanitize a filename: keep only safe characters, replace spaces with hyphens
+fn sanitize_filename(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+
+    // Collapse consecutive hyphens
+    let mut result = String::with_capacity(name.len());
+    let mut prev_was_hyphen = false;
+    for c in name.chars() {
+        if c == '-' {
+            if !prev_was_hyphen {
+                result.push(c);
+            }
+            prev_was_hyphen = true;
+        } else {
+            result.push(c);
+            prev_was_hyphen = false;
+        }
+    }
+
+    if result.is_empty() {
+        "upload".to_string()
+    } else {
+        result
+    }
+}
+
+/// Detect image dimensions from bytes
+fn detect_image_dimensions(bytes: &[u8]) -> (Option<i32>, Option<i32>) {
+    match image::ImageReader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()
+        .and_then(|r| r.into_dimensions().ok())
+    {
+        Some((w, h)) => (Some(w as i32), Some(h as i32)),
+        None => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("hello world.jpg"), "hello-world.jpg");
+        assert_eq!(sanitize_filename("file (1).png"), "file-1-.png");
+        assert_eq!(sanitize_filename("safe-name_v2.webp"), "safe-name_v2.webp");
+        assert_eq!(sanitize_filename(""), "upload");
+    }
+
+    #[test]
+    fn test_detect_mime_type_fallbacks() {
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0];
+        assert_eq!(
+            detect_mime_type(&png, Some("text/plain"), "x.txt"),
+            "image/png"
+        );
+        assert_eq!(
+            detect_mime_type(b"# Title", Some("text/markdown"), "notes"),
+            "text/markdown"
+        );
+        assert_eq!(
+            detect_mime_type(b"# Title", None, "README.md"),
+            "text/markdown"
+        );
+        assert_eq!(
+            detect_mime_type(b"plain", None, "blob"),
+            "application/octet-stream"
+        );
+    }
+}
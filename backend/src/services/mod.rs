@@ -3,11 +3,30 @@
 //! This module contains service layer implementations.
 
 pub mod audit_service;
+pub mod block_service;
 pub mod bulk_content_service;
 pub mod clerk_service;
+pub mod comment_service;
 pub mod content_service;
+pub mod form_service;
 pub mod image_service;
+pub mod image_transform_service;
+pub mod markdown_transfer_service;
+pub mod media_service;
 pub mod notification_service;
+pub mod page_template_service;
+pub mod page_tree_service;
+pub mod preview_service;
+pub mod promotion_service;
+pub mod redirect_service;
+pub mod redirect_transfer_service;
+pub mod release_service;
+pub mod scheduler_service;
+pub mod site_transfer_service;
+pub mod sitemap_service;
 pub mod storage;
+pub mod trash_service;
+pub mod version_service;
 pub mod webhook_service;
+pub mod wordpress_import_service;
 pub mod workflow_service;
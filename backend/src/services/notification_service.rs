@@ -116,6 +116,24 @@ pub fn notify_changes_requested(
     });
 }
 
+/// Notify reviewers that a reader comment awaits moderation (fire-and-forget).
+pub fn notify_comment_submitted(
+    pool: PgPool,
+    site_id: Uuid,
+    blog_id: Uuid,
+    slug: &str,
+    author_name: &str,
+) {
+    let slug = slug.to_string();
+    let author_name = author_name.to_string();
+    tokio::spawn(async move {
+        let result = notify_comment_inner(&pool, site_id, blog_id, &slug, &author_name).await;
+        if let Err(e) = result {
+            tracing::warn!("Notification dispatch (comment) failed: {e}");
+        }
+    });
+}
+
 async fn notify_submitted_inner(
     pool: &PgPool,
     site_id: Uuid,
@@ -152,6 +170,34 @@ async fn notify_submitted_inner(
     Ok(())
 }
 
+async fn notify_comment_inner(
+    pool: &PgPool,
+    site_id: Uuid,
+    blog_id: Uuid,
+    slug: &str,
+    author_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let reviewer_ids = find_reviewer_clerk_ids(pool, site_id).await?;
+    let title = format!("New comment on blog '{}' awaits moderation", slug);
+    let message = format!("{} left a comment", author_name);
+
+    for clerk_id in reviewer_ids {
+        let _ = Notification::create(
+            pool,
+            site_id,
+            &clerk_id,
+            None,
+            "comment_submitted",
+            "blog",
+            blog_id,
+            &title,
+            Some(&message),
+        )
+        .await;
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn notify_review_result_inner(
     pool: &PgPool,
@@ -0,0 +1,144 @@
+//! Page template service
+//!
+//! Checks section layouts against the site's languages and turns a template
+//! plus a create request into the page and sections to insert.
+
+use uuid::Uuid;
+
+use crate::dto::page::CreatePageRequest;
+use crate::dto::page_template::{CreatePageFromTemplateRequest, PageTemplateSection};
+use crate::errors::ApiError;
+use crate::models::page_template::PageTemplate;
+
+/// Check that placeholder text only uses languages of the site
+pub fn validate_layout(
+    sections: &[PageTemplateSection],
+    locale_codes: &[String],
+) -> Result<(), ApiError> {
+    for (position, section) in sections.iter().enumerate() {
+        if let Some(code) = section
+            .placeholders
+            .keys()
+            .find(|c| !locale_codes.contains(c))
+        {
+            return Err(ApiError::Validation(format!(
+                "Section {} has placeholder text for '{}', which is not a language of this site",
+                position + 1,
+                code
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Page creation request for a page made from `template`.
+///
+/// The page takes the template's page type and template name. Without explicit
+/// sites it lands on the template's site; explicit sites must include it.
+pub fn page_request(
+    template: &PageTemplate,
+    req: CreatePageFromTemplateRequest,
+) -> Result<CreatePageRequest, ApiError> {
+    let site_ids: Vec<Uuid> = match req.site_ids {
+        Some(ids) if !ids.is_empty() => ids,
+        _ => vec![template.site_id],
+    };
+    if !site_ids.contains(&template.site_id) {
+        return Err(ApiError::BadRequest(
+            "site_ids must include the template's site".to_string(),
+        ));
+    }
+
+    Ok(CreatePageRequest {
+        route: req.route,
+        slug: req.slug,
+        page_type: template.page_type.clone(),
+        template: template.template.clone(),
+        is_in_navigation: req.is_in_navigation,
+        navigation_order: req.navigation_order,
+        parent_page_id: req.parent_page_id,
+        status: req.status,
+        publish_start: req.publish_start,
+        publish_end: req.publish_end,
+        site_ids,
+        environment: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::dto::page_template::SectionPlaceholder;
+    use crate::models::content::ContentStatus;
+    use crate::models::page::{PageType, SectionType};
+
+    fn template(site_id: Uuid) -> PageTemplate {
+        PageTemplate {
+            id: Uuid::new_v4(),
+            site_id,
+            name: "Landing page".to_string(),
+            description: None,
+            page_type: PageType::Landing,
+            template: Some("landing".to_string()),
+            sections: serde_json::json!([]),
+            is_active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn request(site_ids: Option<Vec<Uuid>>) -> CreatePageFromTemplateRequest {
+        CreatePageFromTemplateRequest {
+            route: "/spring-sale".to_string(),
+            slug: "spring-sale".to_string(),
+            is_in_navigation: false,
+            navigation_order: None,
+            parent_page_id: None,
+            status: ContentStatus::Draft,
+            publish_start: None,
+            publish_end: None,
+            site_ids,
+        }
+    }
+
+    #[test]
+    fn test_validate_layout() {
+        let codes = vec!["en".to_string(), "de".to_string()];
+        let mut section = PageTemplateSection {
+            section_type: SectionType::Hero,
+            cover_image_id: None,
+            call_to_action_route: None,
+            settings: None,
+            placeholders: BTreeMap::from([("de".to_string(), SectionPlaceholder::default())]),
+        };
+        assert!(validate_layout(std::slice::from_ref(&section), &codes).is_ok());
+
+        section
+            .placeholders
+            .insert("fr".to_string(), SectionPlaceholder::default());
+        let err = validate_layout(&[section], &codes).unwrap_err();
+        assert!(matches!(err, ApiError::Validation(msg) if msg.contains("'fr'")));
+    }
+
+    #[test]
+    fn test_page_request_uses_template_fields_and_site() {
+        let site_id = Uuid::new_v4();
+        let template = template(site_id);
+
+        let req = page_request(&template, request(None)).unwrap();
+        assert_eq!(req.page_type, PageType::Landing);
+        assert_eq!(req.template.as_deref(), Some("landing"));
+        assert_eq!(req.site_ids, vec![site_id]);
+
+        let other = Uuid::new_v4();
+        let req = page_request(&template, request(Some(vec![site_id, other]))).unwrap();
+        assert_eq!(req.site_ids.len(), 2);
+
+        assert!(page_request(&template, request(Some(vec![other]))).is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! Page tree service
+//!
+//! Builds the page hierarchy of a site from `parent_page_id`, resolves
+//! breadcrumb trails, and moves pages between parents. A move is refused when
+//! it would create a cycle, and can rebase the route prefix of the page and all
+//! of its descendants, redirecting the old routes of published pages.
+
+use std::collections::{HashMap, HashSet};
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::page::{BreadcrumbItem, PageRouteChange, PageTreeNode};
+use crate::errors::ApiError;
+use crate::models::content::{Content, ContentStatus};
+use crate::models::page::{Page, PageTreeRow, PageWithContent};
+use crate::services::{redirect_service, version_service};
+use crate::utils::validation::validate_route;
+
+/// Nest pages under their parents, keeping the order of `rows`.
+///
+/// Pages whose parent is not part of the site (or is deleted) become roots.
+/// Pages caught in a parent cycle are attached at the top level as well, so
+/// nothing goes missing.
+pub fn build_tree(rows: Vec<PageTreeRow>) -> Vec<PageTreeNode> {
+    let ids: HashSet<Uuid> = rows.iter().map(|r| r.id).collect();
+    let mut roots = Vec::new();
+    let mut children: HashMap<Uuid, Vec<PageTreeNode>> = HashMap::new();
+    for row in rows {
+        match row.parent_page_id.filter(|p| ids.contains(p)) {
+            None => roots.push(PageTreeNode::from(row)),
+            Some(parent_id) => children
+                .entry(parent_id)
+                .or_default()
+                .push(PageTreeNode::from(row)),
+        }
+    }
+
+    fn attach(
+        mut node: PageTreeNode,
+        children: &mut HashMap<Uuid, Vec<PageTreeNode>>,
+    ) -> PageTreeNode {
+        node.children = children
+            .remove(&node.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| attach(child, children))
+            .collect();
+        node
+    }
+
+    let mut tree: Vec<PageTreeNode> = roots
+        .into_iter()
+        .map(|root| attach(root, &mut children))
+        .collect();
+
+    // Whatever is left only hangs off itself; break each cycle at one page
+    while let Some(&parent_id) = children.keys().next() {
+        let orphans = children.remove(&parent_id).unwrap_or_default();
+        for mut orphan in orphans {
+            orphan.parent_page_id = None;
+            tree.push(attach(orphan, &mut children));
+        }
+    }
+    tree
+}
+
+/// Breadcrumb trail from the top-level ancestor down to `page_id`
+pub fn breadcrumbs(rows: &[PageTreeRow], page_id: Uuid) -> Vec<BreadcrumbItem> {
+    let by_id: HashMap<Uuid, &PageTreeRow> = rows.iter().map(|r| (r.id, r)).collect();
+    let mut trail = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = by_id.get(&page_id).copied();
+    while let Some(row) = current {
+        if !seen.insert(row.id) {
+            break;
+        }
+        trail.push(BreadcrumbItem::from(row));
+        current = row.parent_page_id.and_then(|p| by_id.get(&p).copied());
+    }
+    trail.reverse();
+    trail
+}
+
+/// Route of a page placed below `parent_route`, keeping its last segment
+/// (`/about/team` under `/company` → `/company/team`)
+fn child_route(parent_route: Option<&str>, route: &str) -> String {
+    let segment = route.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+    if segment.is_empty() {
+        return route.to_string();
+    }
+    match parent_route.map(|p| p.trim_end_matches('/')) {
+        Some(parent) if !parent.is_empty() => format!("{}/{}", parent, segment),
+        _ => format!("/{}", segment),
+    }
+}
+
+/// Swap the `old_prefix` of a descendant's route for `new_prefix`; `None` when
+/// the route does not live under the old prefix
+fn replace_prefix(route: &str, old_prefix: &str, new_prefix: &str) -> Option<String> {
+    let rest = route.strip_prefix(old_prefix.trim_end_matches('/'))?;
+    rest.starts_with('/')
+        .then(|| format!("{}{}", new_prefix.trim_end_matches('/'), rest))
+}
+
+/// Check that `parent_id` may become the parent of `page_id`: it exists, serves
+/// every site of the page, and is not the page itself or one of its descendants.
+pub async fn check_parent(
+    pool: &PgPool,
+    page_id: Uuid,
+    site_ids: &[Uuid],
+    parent_id: Uuid,
+) -> Result<PageWithContent, ApiError> {
+    if parent_id == page_id {
+        return Err(ApiError::BadRequest(
+            "A page cannot be its own parent".to_string(),
+        ));
+    }
+    let parent = Page::find_by_id(pool, parent_id)
+        .await
+        .map_err(|_| ApiError::BadRequest(format!("Parent page {} not found", parent_id)))?;
+
+    let parent_sites = Content::find_site_ids(pool, parent.content_id).await?;
+    if !site_ids.iter().all(|s| parent_sites.contains(s)) {
+        return Err(ApiError::BadRequest(
+            "Parent page must belong to the same sites as the page".to_string(),
+        ));
+    }
+    if Page::ancestor_ids(pool, parent_id)
+        .await?
+        .contains(&page_id)
+    {
+        return Err(ApiError::BadRequest(
+            "Cannot move a page below one of its own descendants".to_string(),
+        ));
+    }
+    Ok(parent)
+}
+
+/// Move a page to a new parent (`None` for top level).
+///
+/// With `rewrite_routes`, the page's route is rebased onto the parent's route and
+/// descendants under the old route get the new prefix. Fails with 409 when a new
+/// route is already taken. The move and the page's new version (attributed to
+/// `created_by`) are written in one transaction. Old routes of published pages
+/// redirect to the new ones.
+pub async fn move_page(
+    pool: &PgPool,
+    page: &PageWithContent,
+    site_ids: &[Uuid],
+    parent_id: Option<Uuid>,
+    rewrite_routes: bool,
+    created_by: Option<Uuid>,
+) -> Result<(PageWithContent, Vec<PageRouteChange>), ApiError> {
+    let parent = match parent_id {
+        Some(parent_id) => Some(check_parent(pool, page.id, site_ids, parent_id).await?),
+        None => None,
+    };
+
+    let mut moved: Vec<(Uuid, Uuid, bool, PageRouteChange)> = Vec::new();
+    if rewrite_routes {
+        let new_route = child_route(parent.as_ref().map(|p| p.route.as_str()), &page.route);
+        if new_route != page.route {
+            for descendant in Page::find_descendants(pool, page.id).await? {
+                if let Some(route) = replace_prefix(&descendant.route, &page.route, &new_route) {
+                    moved.push((
+                        descendant.id,
+                        descendant.content_id,
+                        descendant.status == ContentStatus::Published,
+                        PageRouteChange {
+                            page_id: descendant.id,
+                            old_route: descendant.route,
+                            new_route: route,
+                        },
+                    ));
+                }
+            }
+            moved.insert(
+                0,
+                (
+                    page.id,
+                    page.content_id,
+                    page.status == ContentStatus::Published,
+                    PageRouteChange {
+                        page_id: page.id,
+                        old_route: page.route.clone(),
+                        new_route,
+                    },
+                ),
+            );
+        }
+    }
+
+    let moved_ids: Vec<Uuid> = moved.iter().map(|(id, ..)| *id).collect();
+    for (_, _, _, change) in &moved {
+        validate_route(&change.new_route).map_err(|e| {
+            ApiError::BadRequest(format!("Invalid route '{}': {}", change.new_route, e))
+        })?;
+        if Page::route_in_use(pool, site_ids, &change.new_route, &moved_ids).await? {
+            return Err(ApiError::Conflict(format!(
+                "Route '{}' is already in use",
+                change.new_route
+            )));
+        }
+    }
+
+    let routes: Vec<(Uuid, String)> = moved
+        .iter()
+        .map(|(id, _, _, change)| (*id, change.new_route.clone()))
+        .collect();
+    let mut tx = pool.begin().await?;
+    Page::move_to(&mut tx, page.id, parent_id, &routes).await?;
+    version_service::record_version(&mut tx, page.content_id, "Moved page", created_by).await?;
+    tx.commit().await?;
+
+    for (_, content_id, published, change) in &moved {
+        if !published {
+            continue;
+        }
+        let page_sites = Content::find_site_ids(pool, *content_id).await?;
+        redirect_service::redirect_moved(
+            pool,
+            &page_sites,
+            *content_id,
+            &change.old_route,
+            &change.new_route,
+            "Page moved",
+        )
+        .await;
+    }
+
+    let page = Page::find_by_id(pool, page.id).await?;
+    let changes = moved.into_iter().map(|(.., change)| change).collect();
+    Ok((page, changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::page::PageType;
+
+    fn row(id: Uuid, parent: Option<Uuid>, route: &str, title: Option<&str>) -> PageTreeRow {
+        PageTreeRow {
+            id,
+            parent_page_id: parent,
+            route: route.to_string(),
+            slug: None,
+            title: title.map(String::from),
+            page_type: PageType::Static,
+            status: ContentStatus::Published,
+            is_in_navigation: true,
+            navigation_order: None,
+        }
+    }
+
+    #[test]
+    fn test_build_tree_nests_children() {
+        let (about, team, contact) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let tree = build_tree(vec![
+            row(about, None, "/about", Some("About")),
+            row(team, Some(about), "/about/team", None),
+            row(contact, Some(Uuid::new_v4()), "/contact", None),
+        ]);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].id, about);
+        assert_eq!(tree[0].children.len(), 1);
+        assert_eq!(tree[0].children[0].title, "/about/team");
+        // Parent outside the site: shown at the top level
+        assert_eq!(tree[1].id, contact);
+    }
+
+    #[test]
+    fn test_build_tree_keeps_pages_in_cycles() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let tree = build_tree(vec![
+            row(a, Some(b), "/a", None),
+            row(b, Some(a), "/b", None),
+        ]);
+        assert_eq!(tree.len(), 1);
+        assert!(tree[0].parent_page_id.is_none());
+        assert_eq!(tree[0].children.len(), 1);
+        assert!(tree[0].children[0].children.is_empty());
+    }
+
+    #[test]
+    fn test_breadcrumbs_from_root() {
+        let (about, team, jane) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            row(about, None, "/about", Some("About us")),
+            row(team, Some(about), "/about/team", Some("Team")),
+            row(jane, Some(team), "/about/team/jane", Some("Jane")),
+        ];
+        let trail = breadcrumbs(&rows, jane);
+        let titles: Vec<&str> = trail.iter().map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["About us", "Team", "Jane"]);
+        assert!(breadcrumbs(&rows, Uuid::new_v4()).is_empty());
+    }
+
+    #[test]
+    fn test_child_route() {
+        assert_eq!(
+            child_route(Some("/company"), "/about/team"),
+            "/company/team"
+        );
+        assert_eq!(child_route(Some("/"), "/about/team"), "/team");
+        assert_eq!(child_route(None, "/about/team/"), "/team");
+        assert_eq!(child_route(Some("/company"), "/"), "/");
+    }
+
+    #[test]
+    fn test_replace_prefix() {
+        assert_eq!(
+            replace_prefix("/about/team/jane", "/about/team", "/company/team"),
+            Some("/company/team/jane".to_string())
+        );
+        assert_eq!(replace_prefix("/about/teams", "/about/team", "/x"), None);
+        assert_eq!(replace_prefix("/elsewhere", "/about", "/x"), None);
+    }
+}
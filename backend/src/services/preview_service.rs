@@ -0,0 +1,200 @@
+//! Draft preview service
+//!
+//! Preview tokens have the form `oyp_<token id>.<signature>`. The signature is
+//! an HMAC-SHA256 over the token's ID, content, locale scope and expiry, so a
+//! token cannot be re-scoped or extended without the signing secret. The
+//! database row only exists so tokens can be listed, revoked and counted.
+
+use uuid::Uuid;
+
+use crate::config::Settings;
+use crate::errors::ApiError;
+use crate::guards::auth_guard::AuthenticatedKey;
+use crate::models::audit::AuditAction;
+use crate::models::content::ContentStatus;
+use crate::models::locale::Locale;
+use crate::models::preview_token::PreviewToken;
+use crate::services::audit_service;
+use crate::services::webhook_service::compute_hmac_sha256;
+use crate::utils::signing::{constant_time_eq, secret_or_fallback};
+use crate::AppState;
+
+/// Prefix of every preview token
+pub const TOKEN_PREFIX: &str = "oyp_";
+
+/// Content item a public lookup resolved to
+pub struct PreviewTarget<'a> {
+    pub entity_type: &'a str,
+    pub entity_id: Uuid,
+    pub content_id: Uuid,
+    pub status: &'a ContentStatus,
+}
+
+fn signing_secret(settings: &Settings) -> &str {
+    secret_or_fallback(&settings.security.preview_token_secret)
+}
+
+fn signing_payload(token: &PreviewToken) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        token.id,
+        token.content_id,
+        token
+            .locale_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "*".to_string()),
+        token.expires_at.timestamp()
+    )
+}
+
+fn sign_with(secret: &str, token: &PreviewToken) -> String {
+    format!(
+        "{}{}.{}",
+        TOKEN_PREFIX,
+        token.id.simple(),
+        compute_hmac_sha256(secret, &signing_payload(token))
+    )
+}
+
+/// Split a token into its ID and signature
+fn parse_token(raw: &str) -> Option<(Uuid, &str)> {
+    let (id, signature) = raw.strip_prefix(TOKEN_PREFIX)?.split_once('.')?;
+    Some((Uuid::parse_str(id).ok()?, signature))
+}
+
+/// Produce the shareable token string for a stored token
+pub fn sign(settings: &Settings, token: &PreviewToken) -> String {
+    sign_with(signing_secret(settings), token)
+}
+
+/// Resolve a token string to an active token for `content_id`
+async fn verify(state: &AppState, raw: &str, content_id: Uuid) -> Result<PreviewToken, ApiError> {
+    let invalid = || ApiError::Forbidden("Invalid preview token".to_string());
+
+    let (id, signature) = parse_token(raw).ok_or_else(invalid)?;
+    let token = match PreviewToken::find_by_id(&state.db, id).await {
+        Ok(token) => token,
+        Err(ApiError::NotFound(_)) => return Err(invalid()),
+        Err(e) => return Err(e),
+    };
+    let expected = sign_with(signing_secret(&state.settings), &token);
+    let (_, expected_signature) = parse_token(&expected).ok_or_else(invalid)?;
+    if !constant_time_eq(signature, expected_signature) {
+        return Err(invalid());
+    }
+    if !token.is_active() {
+        return Err(ApiError::Forbidden(
+            "Preview token has expired or been revoked".to_string(),
+        ));
+    }
+    if token.content_id != content_id {
+        return Err(ApiError::Forbidden(
+            "Preview token is not valid for this content".to_string(),
+        ));
+    }
+    Ok(token)
+}
+
+/// Decide whether a public lookup may return `target`.
+///
+/// Without a preview token, read-only keys only see published content. With a
+/// token, the token must be valid for the content (and for `locale` when it is
+/// locale-scoped); each use is counted and written to the audit log.
+pub async fn authorize_view(
+    state: &AppState,
+    auth: &AuthenticatedKey,
+    site_id: Uuid,
+    target: PreviewTarget<'_>,
+    preview_token: Option<&str>,
+    locale: Option<&str>,
+) -> Result<bool, ApiError> {
+    let Some(raw) = preview_token.filter(|t| !t.is_empty()) else {
+        return Ok(*target.status == ContentStatus::Published || auth.can_write());
+    };
+
+    let token = verify(state, raw, target.content_id).await?;
+    if let Some(scoped_locale) = token.locale_id {
+        let requested = match locale.filter(|l| !l.is_empty()) {
+            Some(code) => Some(Locale::find_by_code(&state.db, code).await?.id),
+            None => None,
+        };
+        if requested != Some(scoped_locale) {
+            return Err(ApiError::Forbidden(
+                "Preview token is scoped to another locale".to_string(),
+            ));
+        }
+    }
+
+    PreviewToken::record_use(&state.db, token.id).await?;
+    audit_service::log_action(
+        &state.db,
+        Some(site_id),
+        Some(auth.id),
+        AuditAction::Read,
+        target.entity_type,
+        target.entity_id,
+        Some(serde_json::json!({
+            "preview_token_id": token.id,
+            "content_id": token.content_id,
+            "locale_id": token.locale_id,
+            "status": target.status,
+        })),
+    )
+    .await;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn token() -> PreviewToken {
+        PreviewToken {
+            id: Uuid::new_v4(),
+            content_id: Uuid::new_v4(),
+            locale_id: None,
+            expires_at: Utc::now() + Duration::hours(1),
+            revoked_at: None,
+            last_used_at: None,
+            use_count: 0,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_signed_token_round_trips() {
+        let t = token();
+        let raw = sign_with("secret", &t);
+        assert!(raw.starts_with(TOKEN_PREFIX));
+        let (id, signature) = parse_token(&raw).unwrap();
+        assert_eq!(id, t.id);
+        assert_eq!(signature.len(), 64);
+    }
+
+    #[test]
+    fn test_signature_binds_scope_and_expiry() {
+        let t = token();
+        let raw = sign_with("secret", &t);
+
+        let mut rescoped = t.clone();
+        rescoped.locale_id = Some(Uuid::new_v4());
+        assert_ne!(raw, sign_with("secret", &rescoped));
+
+        let mut extended = t.clone();
+        extended.expires_at += Duration::days(1);
+        assert_ne!(raw, sign_with("secret", &extended));
+
+        assert_ne!(raw, sign_with("other-secret", &t));
+    }
+
+    #[test]
+    fn test_parse_token_rejects_malformed() {
+        assert!(parse_token("").is_none());
+        assert!(parse_token("oyp_not-a-uuid.abc").is_none());
+        assert!(parse_token(&format!("dk_{}.abc", Uuid::new_v4().simple())).is_none());
+        assert!(parse_token(&format!("oyp_{}", Uuid::new_v4().simple())).is_none());
+    }
+}
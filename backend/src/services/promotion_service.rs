@@ -0,0 +1,491 @@
+//! Environment promotion service
+//!
+//! Copies of a content item in different environments share a `lineage_id`.
+//! A promotion compares every item a site owns in the source environment with
+//! its copy in the target environment and brings the target in line: missing
+//! copies are created, differing ones overwritten, and copies whose source was
+//! deleted are deleted too.
+//!
+//! Besides the versioned snapshot (content, localizations, blocks, sections,
+//! media references) a changeset covers tags and categories and, when the
+//! target is the default (live) environment, navigation items: menus are not
+//! environment-scoped, so items linking to a promoted page are moved to its
+//! live copy.
+
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::dto::content_version::VersionFieldChange;
+use crate::dto::promotion::{PromotionAction, PromotionArea, PromotionChangeset, PromotionItem};
+use crate::errors::ApiError;
+use crate::models::content_version::ContentVersion;
+use crate::models::environment::Environment;
+use crate::models::environment_promotion::EnvironmentPromotion;
+use crate::services::version_service;
+
+/// A source item paired with its copy in the target environment
+#[derive(Debug, sqlx::FromRow)]
+struct Pair {
+    source_id: Uuid,
+    source_deleted: bool,
+    entity_type: String,
+    slug: Option<String>,
+    target_id: Option<Uuid>,
+}
+
+/// Which part of a content item a diff field belongs to
+fn area_for(field: &str) -> PromotionArea {
+    let section = field.split('.').next().unwrap_or_default();
+    let last = field.rsplit('.').next().unwrap_or_default();
+    if section == "taxonomy" {
+        PromotionArea::Taxonomy
+    } else if section == "navigation" {
+        PromotionArea::Navigation
+    } else if last.ends_with("image_id") || last.ends_with("logo_id") {
+        PromotionArea::Media
+    } else if section.ends_with("localizations") || section == "blocks" {
+        PromotionArea::Localizations
+    } else {
+        PromotionArea::Content
+    }
+}
+
+fn areas_for(changes: &[VersionFieldChange]) -> Vec<PromotionArea> {
+    let mut areas: Vec<PromotionArea> = changes.iter().map(|c| area_for(&c.field)).collect();
+    areas.sort();
+    areas.dedup();
+    areas
+}
+
+/// Checksum of a changeset, so that an apply can insist on the previewed one
+pub fn checksum(items: &[PromotionItem]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(items).unwrap_or_default());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Snapshot a content item in its environment-independent form, including
+/// taxonomy and (when `navigation_of` is given) the navigation items linking
+/// to any of those pages.
+async fn portable(
+    conn: &mut PgConnection,
+    content_id: Uuid,
+    navigation_of: Option<&[Uuid]>,
+) -> Result<serde_json::Value, ApiError> {
+    let snapshot = version_service::build_snapshot(conn, content_id).await?;
+    let mut portable = version_service::portable_snapshot(&snapshot)?;
+
+    // Parent pages live in the same environment; compare them by lineage
+    if let Some(parent) = portable["entity"]
+        .get("parent_page_id")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<Uuid>().ok())
+    {
+        let lineage: Option<Uuid> = sqlx::query_scalar(
+            "SELECT c.lineage_id FROM pages p INNER JOIN contents c ON c.id = p.content_id WHERE p.id = $1",
+        )
+        .bind(parent)
+        .fetch_optional(&mut *conn)
+        .await?;
+        portable["entity"]["parent_page_id"] = serde_json::json!(lineage);
+    }
+
+    let tags: Vec<(String,)> = sqlx::query_as(
+        "SELECT tag_id::text FROM content_tags WHERE content_id = $1 ORDER BY tag_id",
+    )
+    .bind(content_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let categories: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT category_id::text, is_primary FROM content_categories WHERE content_id = $1 ORDER BY category_id",
+    )
+    .bind(content_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    portable["taxonomy"] = serde_json::json!({
+        "tags": tags.into_iter().map(|(id,)| (id, serde_json::json!(true))).collect::<serde_json::Map<_, _>>(),
+        "categories": categories.into_iter().map(|(id, primary)| (id, serde_json::json!(primary))).collect::<serde_json::Map<_, _>>(),
+    });
+
+    if let Some(content_ids) = navigation_of {
+        let items: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT ni.id::text FROM navigation_items ni
+            INNER JOIN pages p ON p.id = ni.page_id
+            WHERE p.content_id = ANY($1)
+            ORDER BY ni.id
+            "#,
+        )
+        .bind(content_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+        portable["navigation"] = items
+            .into_iter()
+            .map(|(id,)| (id, serde_json::json!(true)))
+            .collect::<serde_json::Map<_, _>>()
+            .into();
+    }
+
+    Ok(portable)
+}
+
+async fn find_pairs(
+    conn: &mut PgConnection,
+    site_id: Uuid,
+    source: &Environment,
+    target: &Environment,
+    content_ids: Option<&[Uuid]>,
+) -> Result<Vec<Pair>, ApiError> {
+    let pairs = sqlx::query_as::<_, Pair>(
+        r#"
+        SELECT s.id AS source_id, s.is_deleted AS source_deleted, et.name AS entity_type,
+               s.slug::text AS slug, t.id AS target_id
+        FROM contents s
+        INNER JOIN entity_types et ON et.id = s.entity_type_id
+        INNER JOIN content_sites cs ON cs.content_id = s.id AND cs.site_id = $1 AND cs.is_owner
+        LEFT JOIN contents t
+            ON t.lineage_id = s.lineage_id AND t.environment_id = $3 AND t.is_deleted = FALSE
+        WHERE s.environment_id = $2
+          AND et.name = ANY($4)
+          AND ($5::uuid[] IS NULL OR s.id = ANY($5))
+          AND (s.is_deleted = FALSE OR t.id IS NOT NULL)
+          -- a lineage has at most one live copy per environment, but may have
+          -- several deleted ones
+          AND (s.is_deleted = FALSE OR NOT EXISTS (
+              SELECT 1 FROM contents l
+              WHERE l.lineage_id = s.lineage_id AND l.environment_id = $2 AND l.is_deleted = FALSE
+          ))
+        ORDER BY et.name, s.created_at, s.id
+        "#,
+    )
+    .bind(site_id)
+    .bind(source.id)
+    .bind(target.id)
+    .bind(version_service::versioned_entity_types())
+    .bind(content_ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Keep one deleted source per lineage
+    let mut seen = std::collections::HashSet::new();
+    Ok(pairs
+        .into_iter()
+        .filter(|p| !p.source_deleted || seen.insert(p.target_id))
+        .collect())
+}
+
+/// Compute the changes that promoting `source` to `target` would make
+pub async fn build_changeset(
+    conn: &mut PgConnection,
+    site_id: Uuid,
+    source: &Environment,
+    target: &Environment,
+    content_ids: Option<&[Uuid]>,
+) -> Result<PromotionChangeset, ApiError> {
+    if source.id == target.id {
+        return Err(ApiError::BadRequest(
+            "Source and target environments must differ".to_string(),
+        ));
+    }
+
+    let mut items = Vec::new();
+    for pair in find_pairs(conn, site_id, source, target, content_ids).await? {
+        let (action, changes) = match (pair.source_deleted, pair.target_id) {
+            (true, _) => (PromotionAction::Delete, Vec::new()),
+            (false, target_id) => {
+                let pages = target
+                    .is_default
+                    .then(|| {
+                        pair.target_id
+                            .into_iter()
+                            .chain([pair.source_id])
+                            .collect::<Vec<_>>()
+                    })
+                    .filter(|_| pair.entity_type == "page");
+                let new = portable(conn, pair.source_id, pages.as_deref()).await?;
+                match target_id {
+                    None => (
+                        PromotionAction::Create,
+                        version_service::diff_snapshots(&serde_json::json!({}), &new),
+                    ),
+                    Some(target_id) => {
+                        let target_pages = pages.as_ref().map(|_| vec![target_id]);
+                        let old = portable(conn, target_id, target_pages.as_deref()).await?;
+                        let changes = version_service::diff_snapshots(&old, &new);
+                        if changes.is_empty() {
+                            continue;
+                        }
+                        (PromotionAction::Update, changes)
+                    }
+                }
+            }
+        };
+        items.push(PromotionItem {
+            source_content_id: pair.source_id,
+            target_content_id: pair.target_id,
+            entity_type: pair.entity_type,
+            slug: pair.slug,
+            action,
+            areas: areas_for(&changes),
+            changes,
+        });
+    }
+
+    Ok(PromotionChangeset {
+        site_id,
+        source_environment_id: source.id,
+        target_environment_id: target.id,
+        checksum: checksum(&items),
+        items,
+    })
+}
+
+/// Copy tags and categories from one content item to another
+async fn copy_taxonomy(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    target_id: Uuid,
+) -> Result<(), ApiError> {
+    sqlx::query("DELETE FROM content_tags WHERE content_id = $1")
+        .bind(target_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "INSERT INTO content_tags (content_id, tag_id) SELECT $2, tag_id FROM content_tags WHERE content_id = $1",
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query("DELETE FROM content_categories WHERE content_id = $1")
+        .bind(target_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO content_categories (content_id, category_id, is_primary)
+        SELECT $2, category_id, is_primary FROM content_categories WHERE content_id = $1
+        "#,
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Point a promoted page at the copy of its parent in the target environment
+async fn link_parent_page(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    target_id: Uuid,
+    target_environment_id: Uuid,
+) -> Result<(), ApiError> {
+    let parent: Option<(Option<Uuid>, Option<Uuid>)> = sqlx::query_as(
+        r#"
+        SELECT pc.lineage_id,
+               (SELECT tp.id FROM contents tc
+                INNER JOIN pages tp ON tp.content_id = tc.id
+                WHERE tc.lineage_id = pc.lineage_id
+                  AND tc.environment_id = $2 AND tc.is_deleted = FALSE)
+        FROM pages p
+        INNER JOIN pages pp ON pp.id = p.parent_page_id
+        INNER JOIN contents pc ON pc.id = pp.content_id
+        WHERE p.content_id = $1
+        "#,
+    )
+    .bind(source_id)
+    .bind(target_environment_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let parent_page_id = match parent {
+        None => None,
+        Some((_, Some(target_parent))) => Some(target_parent),
+        Some((lineage, None)) => {
+            return Err(ApiError::BadRequest(format!(
+                "The parent page of content {} is not in the target environment; promote {} first",
+                source_id,
+                lineage.map(|l| l.to_string()).unwrap_or_default()
+            )))
+        }
+    };
+    sqlx::query("UPDATE pages SET parent_page_id = $2 WHERE content_id = $1")
+        .bind(target_id)
+        .bind(parent_page_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Compute and apply a promotion in one transaction, recording it in the history.
+///
+/// With `expected_checksum`, the promotion fails with `412 Precondition Failed`
+/// if the changeset no longer matches the one that was previewed.
+pub async fn apply(
+    pool: &PgPool,
+    site_id: Uuid,
+    source: &Environment,
+    target: &Environment,
+    content_ids: Option<&[Uuid]>,
+    expected_checksum: Option<&str>,
+    promoted_by: Option<Uuid>,
+) -> Result<EnvironmentPromotion, ApiError> {
+    let mut tx = pool.begin().await?;
+    let changeset = build_changeset(&mut tx, site_id, source, target, content_ids).await?;
+    if let Some(expected) = expected_checksum {
+        if expected != changeset.checksum {
+            return Err(ApiError::PreconditionFailed(
+                "The changeset has changed since it was previewed".to_string(),
+            ));
+        }
+    }
+    if changeset.items.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Nothing to promote: the environments are in sync".to_string(),
+        ));
+    }
+
+    let summary = format!("Promoted from {}", source.display_name);
+    let mut promoted = Vec::new();
+    let (mut created, mut updated, mut deleted) = (0, 0, 0);
+    for item in &changeset.items {
+        let target_id = match (item.action, item.target_content_id) {
+            (PromotionAction::Delete, Some(target_id)) => {
+                sqlx::query(
+                    "UPDATE contents SET is_deleted = TRUE, deleted_at = NOW(), updated_at = NOW() WHERE id = $1",
+                )
+                .bind(target_id)
+                .execute(&mut *tx)
+                .await?;
+                deleted += 1;
+                continue;
+            }
+            (_, Some(target_id)) => {
+                updated += 1;
+                target_id
+            }
+            (_, None) => {
+                let target_id: Uuid = sqlx::query_scalar(
+                    r#"
+                    INSERT INTO contents (entity_type_id, environment_id, slug, status, lineage_id)
+                    SELECT entity_type_id, $2, slug, status, lineage_id FROM contents WHERE id = $1
+                    RETURNING id
+                    "#,
+                )
+                .bind(item.source_content_id)
+                .bind(target.id)
+                .fetch_one(&mut *tx)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO content_sites (content_id, site_id, is_owner) VALUES ($1, $2, TRUE)",
+                )
+                .bind(target_id)
+                .bind(site_id)
+                .execute(&mut *tx)
+                .await?;
+                created += 1;
+                target_id
+            }
+        };
+
+        let snapshot = version_service::build_snapshot(&mut tx, item.source_content_id).await?;
+        version_service::copy_snapshot(&mut tx, target_id, &snapshot, &["parent_page_id"]).await?;
+        copy_taxonomy(&mut tx, item.source_content_id, target_id).await?;
+        promoted.push((item, target_id));
+    }
+
+    // Parents may be created later in the same promotion, so link them last
+    for (item, target_id) in &promoted {
+        if item.entity_type != "page" {
+            continue;
+        }
+        link_parent_page(&mut tx, item.source_content_id, *target_id, target.id).await?;
+        if target.is_default {
+            sqlx::query(
+                r#"
+                UPDATE navigation_items SET page_id = tp.id, updated_at = NOW()
+                FROM pages sp, pages tp
+                WHERE navigation_items.page_id = sp.id
+                  AND sp.content_id = $1 AND tp.content_id = $2
+                "#,
+            )
+            .bind(item.source_content_id)
+            .bind(target_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    for (_, target_id) in &promoted {
+        let snapshot = version_service::build_snapshot(&mut tx, *target_id).await?;
+        ContentVersion::create_next(&mut tx, *target_id, &snapshot, Some(&summary), promoted_by)
+            .await?;
+    }
+
+    let record = EnvironmentPromotion::create(
+        &mut *tx,
+        site_id,
+        source.id,
+        target.id,
+        &serde_json::to_value(&changeset.items)?,
+        (created, updated, deleted),
+        promoted_by,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_for_fields() {
+        assert_eq!(area_for("content.slug"), PromotionArea::Content);
+        assert_eq!(area_for("entity.author"), PromotionArea::Content);
+        assert_eq!(area_for("entity.cover_image_id"), PromotionArea::Media);
+        assert_eq!(area_for("sections.0.cover_image_id"), PromotionArea::Media);
+        assert_eq!(
+            area_for("localizations.6b1c.title"),
+            PromotionArea::Localizations
+        );
+        assert_eq!(
+            area_for("blocks.6b1c.0.block_data.text"),
+            PromotionArea::Localizations
+        );
+        assert_eq!(
+            area_for("section_localizations.0.6b1c.title"),
+            PromotionArea::Localizations
+        );
+        assert_eq!(area_for("taxonomy.tags.9f2e"), PromotionArea::Taxonomy);
+        assert_eq!(area_for("navigation.41aa"), PromotionArea::Navigation);
+    }
+
+    #[test]
+    fn test_checksum_tracks_changes() {
+        let item = PromotionItem {
+            source_content_id: Uuid::nil(),
+            target_content_id: None,
+            entity_type: "blog".to_string(),
+            slug: Some("hello".to_string()),
+            action: PromotionAction::Create,
+            areas: vec![PromotionArea::Content],
+            changes: vec![],
+        };
+        let mut other = item.clone();
+        other.slug = Some("hello-world".to_string());
+
+        let again = item.clone();
+        assert_eq!(checksum(std::slice::from_ref(&item)), checksum(&[again]));
+        assert_ne!(checksum(&[item]), checksum(&[other]));
+        assert_eq!(checksum(&[]).len(), 64);
+    }
+}
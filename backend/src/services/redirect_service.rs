@@ -0,0 +1,452 @@
+//! Redirect service
+//!
+//! Resolves requested paths against a site's redirects: exact matches first,
+//! then wildcard and regex patterns in priority order, with capture groups
+//! substituted into the destination. Compiled patterns are cached per site and
+//! dropped whenever the site's redirects change.
+//!
+//! Also keeps old URLs working when a blog slug or page route changes, and when
+//! content is deleted or archived with a fallback. Redirects are generated for
+//! the bare path and for every locale `url_prefix` of each site, and existing
+//! chains are collapsed so visitors never take more than one hop.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+use crate::models::redirect::{Redirect, RedirectQueryHandling};
+use crate::models::site_locale::SiteLocale;
+
+/// How long compiled patterns are reused. Writes on this instance invalidate
+/// immediately; the TTL bounds staleness after writes on other instances.
+const PATTERN_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// A site's active pattern redirects, compiled, in priority order
+struct SitePatterns {
+    loaded_at: Instant,
+    patterns: Vec<(Redirect, Regex)>,
+}
+
+lazy_static::lazy_static! {
+    static ref PATTERN_CACHE: RwLock<HashMap<Uuid, Arc<SitePatterns>>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Drop a site's compiled patterns; call after any write to its redirects
+pub fn invalidate_patterns(site_id: Uuid) {
+    PATTERN_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&site_id);
+}
+
+/// The site's compiled patterns, from the cache or freshly loaded
+async fn site_patterns(pool: &PgPool, site_id: Uuid) -> Result<Arc<SitePatterns>, ApiError> {
+    let cached = PATTERN_CACHE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&site_id)
+        .filter(|entry| entry.loaded_at.elapsed() < PATTERN_CACHE_TTL)
+        .cloned();
+    if let Some(entry) = cached {
+        return Ok(entry);
+    }
+
+    let loaded_at = Instant::now();
+    let mut patterns = Vec::new();
+    for redirect in Redirect::find_active_patterns(pool, site_id).await? {
+        match redirect.match_type.compile(&redirect.source_path) {
+            Ok(Some(pattern)) => patterns.push((redirect, pattern)),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Skipping redirect {} with invalid pattern: {e}",
+                redirect.id
+            ),
+        }
+    }
+
+    let entry = Arc::new(SitePatterns {
+        loaded_at,
+        patterns,
+    });
+    PATTERN_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(site_id, entry.clone());
+    Ok(entry)
+}
+
+/// A redirect that a requested path resolved to
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedirectMatch {
+    pub redirect_id: Uuid,
+    /// Destination with captures substituted; `None` for 410 Gone
+    pub destination_path: Option<String>,
+    pub status_code: i16,
+}
+
+/// Append a query string to a destination that may already carry one
+fn append_query(destination: &str, query: &str) -> String {
+    let separator = if destination.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", destination, separator, query)
+}
+
+/// Match one redirect against a requested path (split from its query string).
+///
+/// `pattern` is the compiled `source_path` of wildcard and regex redirects.
+fn apply(
+    redirect: &Redirect,
+    pattern: Option<&Regex>,
+    path: &str,
+    query: Option<&str>,
+) -> Option<RedirectMatch> {
+    let subject = match (redirect.query_handling, query) {
+        (RedirectQueryHandling::Exact, Some(query)) => format!("{}?{}", path, query),
+        _ => path.to_string(),
+    };
+
+    let destination = match pattern {
+        None => {
+            if redirect.source_path != subject {
+                return None;
+            }
+            redirect.destination_path.clone()
+        }
+        Some(pattern) => {
+            let captures = pattern.captures(&subject)?;
+            redirect.destination_path.as_deref().map(|template| {
+                let mut expanded = String::new();
+                captures.expand(template, &mut expanded);
+                expanded
+            })
+        }
+    };
+
+    let destination = match (redirect.query_handling, query, destination) {
+        (RedirectQueryHandling::Preserve, Some(query), Some(destination)) => {
+            Some(append_query(&destination, query))
+        }
+        (_, _, destination) => destination,
+    };
+
+    Some(RedirectMatch {
+        redirect_id: redirect.id,
+        destination_path: destination,
+        status_code: redirect.status_code,
+    })
+}
+
+/// Resolve a requested path (optionally with `?query`) to an active redirect
+pub async fn lookup(
+    pool: &PgPool,
+    site_id: Uuid,
+    requested: &str,
+) -> Result<Option<RedirectMatch>, ApiError> {
+    let (path, query) = match requested.split_once('?') {
+        Some((path, query)) => (path, Some(query).filter(|q| !q.is_empty())),
+        None => (requested, None),
+    };
+    let path_with_query = match query {
+        Some(query) => format!("{}?{}", path, query),
+        None => path.to_string(),
+    };
+
+    if let Some(redirect) = Redirect::find_exact(pool, site_id, path, &path_with_query).await? {
+        if let Some(found) = apply(&redirect, None, path, query) {
+            return Ok(Some(found));
+        }
+    }
+    let site = site_patterns(pool, site_id).await?;
+    for (redirect, pattern) in &site.patterns {
+        if let Some(found) = apply(redirect, Some(pattern), path, query) {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
+
+/// Count a resolved lookup. Logs errors but never fails the request.
+pub async fn record_hit(pool: &PgPool, redirect_id: Uuid) {
+    if let Err(e) = Redirect::record_hit(pool, redirect_id).await {
+        tracing::warn!("Failed to record hit for redirect {redirect_id}: {e}");
+    }
+}
+
+/// Distinct path prefixes in use on a site: none, plus each active locale prefix
+async fn locale_prefixes(pool: &PgPool, site_id: Uuid) -> Result<Vec<Option<String>>, ApiError> {
+    let mut prefixes: Vec<Option<String>> = vec![None];
+    for locale in SiteLocale::find_all_for_site(pool, site_id).await? {
+        let prefix = locale
+            .url_prefix
+            .map(|p| p.trim_matches('/').to_string())
+            .filter(|p| !p.is_empty());
+        if locale.is_active && prefix.is_some() && !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+    Ok(prefixes)
+}
+
+/// Apply a locale prefix to a destination; absolute URLs are left alone
+fn localize_destination(prefix: Option<&str>, destination: &str) -> String {
+    if destination.starts_with('/') {
+        SiteLocale::localize_path(prefix, destination)
+    } else {
+        destination.to_string()
+    }
+}
+
+async fn try_redirect(
+    pool: &PgPool,
+    site_ids: &[Uuid],
+    content_id: Uuid,
+    old_path: &str,
+    new_path: &str,
+    description: &str,
+    target_is_live: bool,
+) -> Result<(), ApiError> {
+    for site_id in site_ids {
+        let prefixes = locale_prefixes(pool, *site_id).await?;
+
+        let mut tx = pool.begin().await?;
+        for prefix in &prefixes {
+            let source = SiteLocale::localize_path(prefix.as_deref(), old_path);
+            let mut destination = localize_destination(prefix.as_deref(), new_path);
+
+            if target_is_live {
+                // The new path serves content now, so generated redirects away from it go
+                if Redirect::release_source(&mut tx, *site_id, &destination).await? {
+                    tracing::warn!(
+                        "Manual redirect on {destination} in site {site_id} hides moved content"
+                    );
+                }
+            } else {
+                // A fallback may itself be redirected; skip straight to the end of that chain
+                destination =
+                    Redirect::resolve_destination(&mut tx, *site_id, &destination).await?;
+            }
+            if source == destination {
+                continue;
+            }
+
+            Redirect::retarget(&mut tx, *site_id, &source, &destination).await?;
+            let generated = Redirect::upsert_generated(
+                &mut tx,
+                *site_id,
+                &source,
+                &destination,
+                description,
+                content_id,
+            )
+            .await?;
+            if generated.is_none() {
+                tracing::warn!(
+                    "Kept manual redirect on {source} in site {site_id} instead of redirecting to {destination}"
+                );
+            }
+        }
+        tx.commit().await?;
+        invalidate_patterns(*site_id);
+    }
+    Ok(())
+}
+
+/// Redirect a published URL that moved (slug or route change) to its new path.
+///
+/// Logs errors but never fails the request.
+pub async fn redirect_moved(
+    pool: &PgPool,
+    site_ids: &[Uuid],
+    content_id: Uuid,
+    old_path: &str,
+    new_path: &str,
+    description: &str,
+) {
+    if old_path == new_path {
+        return;
+    }
+    if let Err(e) = try_redirect(
+        pool,
+        site_ids,
+        content_id,
+        old_path,
+        new_path,
+        description,
+        true,
+    )
+    .await
+    {
+        tracing::warn!("Failed to create redirect {old_path} -> {new_path}: {e}");
+    }
+}
+
+/// Redirect the URL of deleted or archived content to a chosen fallback.
+///
+/// Logs errors but never fails the request.
+pub async fn redirect_removed(
+    pool: &PgPool,
+    site_ids: &[Uuid],
+    content_id: Uuid,
+    old_path: &str,
+    fallback: &str,
+    description: &str,
+) {
+    if let Err(e) = try_redirect(
+        pool,
+        site_ids,
+        content_id,
+        old_path,
+        fallback,
+        description,
+        false,
+    )
+    .await
+    {
+        tracing::warn!("Failed to create redirect {old_path} -> {fallback}: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::redirect::RedirectMatchType;
+    use chrono::Utc;
+
+    fn redirect(
+        match_type: RedirectMatchType,
+        query_handling: RedirectQueryHandling,
+        source: &str,
+        destination: Option<&str>,
+        status_code: i16,
+    ) -> Redirect {
+        Redirect {
+            id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            source_path: source.to_string(),
+            destination_path: destination.map(String::from),
+            status_code,
+            is_active: true,
+            description: None,
+            is_auto_generated: false,
+            content_id: None,
+            match_type,
+            query_handling,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Compile the redirect's pattern the way `lookup` does, then apply it
+    fn check(r: &Redirect, path: &str, query: Option<&str>) -> Option<RedirectMatch> {
+        let pattern = r.match_type.compile(&r.source_path).unwrap();
+        apply(r, pattern.as_ref(), path, query)
+    }
+
+    #[test]
+    fn test_apply_exact() {
+        let r = redirect(
+            RedirectMatchType::Exact,
+            RedirectQueryHandling::Ignore,
+            "/old",
+            Some("/new"),
+            301,
+        );
+        let found = check(&r, "/old", Some("utm=x")).unwrap();
+        assert_eq!(found.destination_path.as_deref(), Some("/new"));
+        assert_eq!(found.status_code, 301);
+        assert!(check(&r, "/older", None).is_none());
+    }
+
+    #[test]
+    fn test_apply_wildcard_substitutes_captures() {
+        let r = redirect(
+            RedirectMatchType::Wildcard,
+            RedirectQueryHandling::Ignore,
+            "/blog/*/*",
+            Some("/news/${2}-$1"),
+            308,
+        );
+        let found = check(&r, "/blog/2024/hello", None).unwrap();
+        assert_eq!(found.destination_path.as_deref(), Some("/news/hello-2024"));
+        assert_eq!(found.status_code, 308);
+        assert!(check(&r, "/blog/hello", None).is_none());
+    }
+
+    #[test]
+    fn test_apply_regex_with_named_group() {
+        let r = redirect(
+            RedirectMatchType::Regex,
+            RedirectQueryHandling::Ignore,
+            r"/posts/(?P<id>\d+)",
+            Some("/blog/post-$id"),
+            301,
+        );
+        let found = check(&r, "/posts/42", None).unwrap();
+        assert_eq!(found.destination_path.as_deref(), Some("/blog/post-42"));
+        assert!(check(&r, "/posts/abc", None).is_none());
+    }
+
+    #[test]
+    fn test_apply_query_handling() {
+        let preserve = redirect(
+            RedirectMatchType::Exact,
+            RedirectQueryHandling::Preserve,
+            "/old",
+            Some("/new?ref=old"),
+            302,
+        );
+        let found = check(&preserve, "/old", Some("page=2")).unwrap();
+        assert_eq!(
+            found.destination_path.as_deref(),
+            Some("/new?ref=old&page=2")
+        );
+
+        let exact = redirect(
+            RedirectMatchType::Exact,
+            RedirectQueryHandling::Exact,
+            "/search?q=shoes",
+            Some("/shoes"),
+            301,
+        );
+        assert!(check(&exact, "/search", Some("q=shoes")).is_some());
+        assert!(check(&exact, "/search", Some("q=hats")).is_none());
+        assert!(check(&exact, "/search", None).is_none());
+    }
+
+    #[test]
+    fn test_apply_gone_has_no_destination() {
+        let r = redirect(
+            RedirectMatchType::Wildcard,
+            RedirectQueryHandling::Preserve,
+            "/retired/*",
+            None,
+            410,
+        );
+        let found = check(&r, "/retired/thing", Some("a=1")).unwrap();
+        assert_eq!(found.destination_path, None);
+        assert_eq!(found.status_code, 410);
+    }
+
+    #[test]
+    fn test_append_query() {
+        assert_eq!(append_query("/new", "a=1"), "/new?a=1");
+        assert_eq!(append_query("/new?b=2", "a=1"), "/new?b=2&a=1");
+    }
+
+    #[test]
+    fn test_localize_destination() {
+        assert_eq!(localize_destination(Some("de"), "/news"), "/de/news");
+        assert_eq!(localize_destination(None, "/news"), "/news");
+        assert_eq!(
+            localize_destination(Some("de"), "https://example.com/news"),
+            "https://example.com/news"
+        );
+    }
+}
@@ -0,0 +1,539 @@
+//! Redirect import and export
+//!
+//! Parses CSV and JSON redirect files, checks them against the site's existing
+//! redirects (duplicates, self-redirects, loops and chains) and renders exports
+//! in the same formats, so a file exported from one site imports into another.
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::redirect::{
+    CreateRedirectRequest, RedirectImportIssue, RedirectImportIssueKind, RedirectImportReport,
+    RedirectImportSeverity, RedirectTransferRow,
+};
+use crate::errors::ApiError;
+use crate::models::redirect::{Redirect, RedirectMatchType};
+
+/// Maximum number of rows in one import
+pub const MAX_IMPORT_ROWS: usize = 50_000;
+
+/// CSV column order for exports
+const CSV_HEADER: [&str; 7] = [
+    "source_path",
+    "destination_path",
+    "status_code",
+    "match_type",
+    "query_handling",
+    "is_active",
+    "description",
+];
+
+/// File format of an import or export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferFormat {
+    Csv,
+    Json,
+}
+
+impl TransferFormat {
+    /// Parse a `format` query value (`csv` or `json`, default `csv`)
+    pub fn parse(value: Option<&str>) -> Result<Self, ApiError> {
+        match value.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("") | Some("csv") => Ok(Self::Csv),
+            Some("json") => Ok(Self::Json),
+            Some(other) => Err(ApiError::BadRequest(format!(
+                "Unknown format '{}'; use csv or json",
+                other
+            ))),
+        }
+    }
+}
+
+/// A parsed import row, or the reason it could not be parsed
+pub type ParsedRow = Result<RedirectTransferRow, String>;
+
+fn check_row_count(count: usize) -> Result<(), ApiError> {
+    if count > MAX_IMPORT_ROWS {
+        return Err(ApiError::BadRequest(format!(
+            "Import has {} rows; the maximum is {}",
+            count, MAX_IMPORT_ROWS
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a CSV file with a header row naming the columns
+pub fn parse_csv(input: &str) -> Result<Vec<ParsedRow>, ApiError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| ApiError::BadRequest(format!("Invalid CSV header: {}", e)))?;
+    if !headers.iter().any(|h| h == "source_path") {
+        return Err(ApiError::BadRequest(
+            "CSV header must include a source_path column".to_string(),
+        ));
+    }
+
+    let rows: Vec<ParsedRow> = reader
+        .deserialize::<RedirectTransferRow>()
+        .map(|row| row.map_err(|e| format!("Invalid CSV row: {}", e)))
+        .collect();
+    check_row_count(rows.len())?;
+    Ok(rows)
+}
+
+/// Parse a JSON array of redirect rows
+pub fn parse_json(input: &str) -> Result<Vec<ParsedRow>, ApiError> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(input)
+        .map_err(|e| ApiError::BadRequest(format!("Expected a JSON array of redirects: {}", e)))?;
+    check_row_count(values.len())?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            serde_json::from_value::<RedirectTransferRow>(value)
+                .map_err(|e| format!("Invalid row: {}", e))
+        })
+        .collect())
+}
+
+/// Render rows as CSV with a header
+pub fn render_csv(rows: &[RedirectTransferRow]) -> Result<String, ApiError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    let write_error = |e: csv::Error| ApiError::Internal(format!("Failed to write CSV: {}", e));
+
+    writer.write_record(CSV_HEADER).map_err(write_error)?;
+    for row in rows {
+        writer.serialize(row).map_err(write_error)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| ApiError::Internal(format!("Failed to write CSV: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| ApiError::Internal(format!("Invalid CSV output: {}", e)))
+}
+
+/// Render rows as a JSON array
+pub fn render_json(rows: &[RedirectTransferRow]) -> Result<String, ApiError> {
+    serde_json::to_string_pretty(rows)
+        .map_err(|e| ApiError::Internal(format!("Failed to write JSON: {}", e)))
+}
+
+/// A row that passed all checks
+#[derive(Debug, Clone)]
+pub struct PlannedRow {
+    pub request: CreateRedirectRequest,
+    /// An existing redirect with the same source path is replaced
+    pub replaces_existing: bool,
+}
+
+/// Rows to write plus the report describing them
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub rows: Vec<PlannedRow>,
+    pub report: RedirectImportReport,
+}
+
+fn issue(
+    row: usize,
+    source_path: Option<&str>,
+    kind: RedirectImportIssueKind,
+    severity: RedirectImportSeverity,
+    message: String,
+) -> RedirectImportIssue {
+    RedirectImportIssue {
+        row,
+        source_path: source_path.map(String::from),
+        kind,
+        severity,
+        message,
+    }
+}
+
+/// Exact, active, local `source -> destination` edges that visitors would follow
+fn local_edge(
+    match_type: RedirectMatchType,
+    is_active: bool,
+    destination: Option<&str>,
+) -> Option<&str> {
+    if match_type != RedirectMatchType::Exact || !is_active {
+        return None;
+    }
+    destination.filter(|d| d.starts_with('/'))
+}
+
+/// Check parsed rows against each other and the site's existing redirects.
+///
+/// Rows with errors are left out of the plan; warnings are reported but the
+/// row is still imported. Existing source paths are errors unless `overwrite`.
+pub fn plan(
+    site_id: Uuid,
+    rows: Vec<ParsedRow>,
+    existing: &[Redirect],
+    overwrite: bool,
+    dry_run: bool,
+) -> ImportPlan {
+    let total_rows = rows.len();
+    let existing_sources: HashSet<&str> = existing.iter().map(|r| r.source_path.as_str()).collect();
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    let mut accepted: Vec<(usize, PlannedRow)> = Vec::new();
+
+    for (index, parsed) in rows.into_iter().enumerate() {
+        let row = index + 1;
+        let transfer = match parsed {
+            Ok(transfer) => transfer,
+            Err(message) => {
+                issues.push(issue(
+                    row,
+                    None,
+                    RedirectImportIssueKind::Invalid,
+                    RedirectImportSeverity::Error,
+                    message,
+                ));
+                continue;
+            }
+        };
+        let request = transfer.to_create_request(site_id);
+        let source = request.source_path.as_str();
+
+        if !seen.insert(request.source_path.clone()) {
+            issues.push(issue(
+                row,
+                Some(source),
+                RedirectImportIssueKind::DuplicateInFile,
+                RedirectImportSeverity::Error,
+                format!("Source path {} appears earlier in the file", source),
+            ));
+            continue;
+        }
+        if request.destination_path.as_deref() == Some(source) {
+            issues.push(issue(
+                row,
+                Some(source),
+                RedirectImportIssueKind::SelfRedirect,
+                RedirectImportSeverity::Error,
+                format!("{} redirects to itself", source),
+            ));
+            continue;
+        }
+        if let Err(e) = request.validate() {
+            issues.push(issue(
+                row,
+                Some(source),
+                RedirectImportIssueKind::Invalid,
+                RedirectImportSeverity::Error,
+                format!("Validation error: {}", e),
+            ));
+            continue;
+        }
+
+        let replaces_existing = existing_sources.contains(source);
+        if replaces_existing {
+            if !overwrite {
+                issues.push(issue(
+                    row,
+                    Some(source),
+                    RedirectImportIssueKind::Duplicate,
+                    RedirectImportSeverity::Error,
+                    format!("A redirect for {} already exists on this site", source),
+                ));
+                continue;
+            }
+            issues.push(issue(
+                row,
+                Some(source),
+                RedirectImportIssueKind::Duplicate,
+                RedirectImportSeverity::Warning,
+                format!("Replaces the existing redirect for {}", source),
+            ));
+        }
+
+        accepted.push((
+            row,
+            PlannedRow {
+                request,
+                replaces_existing,
+            },
+        ));
+    }
+
+    // The redirect graph after the import: imported rows win over the rows they replace
+    let mut graph: HashMap<&str, &str> = HashMap::new();
+    for redirect in existing {
+        if let Some(destination) = local_edge(
+            redirect.match_type,
+            redirect.is_active,
+            redirect.destination_path.as_deref(),
+        ) {
+            graph.insert(&redirect.source_path, destination);
+        }
+    }
+    for (_, planned) in &accepted {
+        let request = &planned.request;
+        graph.remove(request.source_path.as_str());
+        if let Some(destination) = local_edge(
+            request.match_type.unwrap_or_default(),
+            request.is_active.unwrap_or(true),
+            request.destination_path.as_deref(),
+        ) {
+            graph.insert(&request.source_path, destination);
+        }
+    }
+
+    let mut looping = HashSet::new();
+    for (row, planned) in &accepted {
+        let source = planned.request.source_path.as_str();
+        let Some(first) = graph.get(source).copied() else {
+            continue;
+        };
+        let mut visited = HashSet::from([source]);
+        let mut current = first;
+        let mut hops = 0;
+        let mut is_loop = false;
+        while let Some(next) = graph.get(current).copied() {
+            if !visited.insert(current) {
+                is_loop = true;
+                break;
+            }
+            current = next;
+            hops += 1;
+        }
+        if is_loop {
+            looping.insert(*row);
+            issues.push(issue(
+                *row,
+                Some(source),
+                RedirectImportIssueKind::Loop,
+                RedirectImportSeverity::Error,
+                format!("Following {} leads into a redirect loop", source),
+            ));
+        } else if hops > 0 {
+            issues.push(issue(
+                *row,
+                Some(source),
+                RedirectImportIssueKind::Chain,
+                RedirectImportSeverity::Warning,
+                format!(
+                    "{} reaches {} only after {} more redirect(s)",
+                    source, current, hops
+                ),
+            ));
+        }
+    }
+
+    let rows: Vec<PlannedRow> = accepted
+        .into_iter()
+        .filter(|(row, _)| !looping.contains(row))
+        .map(|(_, planned)| planned)
+        .collect();
+    issues.sort_by_key(|i| i.row);
+
+    let updated = rows.iter().filter(|r| r.replaces_existing).count();
+    let report = RedirectImportReport {
+        dry_run,
+        total_rows,
+        created: rows.len() - updated,
+        updated,
+        skipped: total_rows - rows.len(),
+        issues,
+    };
+    ImportPlan { rows, report }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::redirect::RedirectQueryHandling;
+    use chrono::Utc;
+
+    fn existing(source: &str, destination: &str) -> Redirect {
+        Redirect {
+            id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            source_path: source.to_string(),
+            destination_path: Some(destination.to_string()),
+            status_code: 301,
+            is_active: true,
+            description: None,
+            is_auto_generated: false,
+            content_id: None,
+            match_type: RedirectMatchType::Exact,
+            query_handling: RedirectQueryHandling::Ignore,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn kinds(plan: &ImportPlan) -> Vec<(usize, RedirectImportIssueKind)> {
+        plan.report.issues.iter().map(|i| (i.row, i.kind)).collect()
+    }
+
+    #[test]
+    fn test_parse_csv_with_optional_columns() {
+        let rows = parse_csv(
+            "source_path,destination_path,status_code,match_type\n\
+             /old,/new,,\n\
+             /gone,,410,\n\
+             /blog/*,/news/$1,308,Wildcard\n\
+             /bad,/x,abc,\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 4);
+        let first = rows[0].as_ref().unwrap();
+        assert_eq!(first.destination_path.as_deref(), Some("/new"));
+        assert_eq!(first.status_code, None);
+        assert_eq!(rows[1].as_ref().unwrap().destination_path, None);
+        assert_eq!(
+            rows[2].as_ref().unwrap().match_type,
+            Some(RedirectMatchType::Wildcard)
+        );
+        assert!(rows[3].is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_requires_source_column() {
+        assert!(parse_csv("from,to\n/a,/b\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let rows =
+            parse_json(r#"[{"source_path": "/a", "destination_path": "/b"}, {"x": 1}]"#).unwrap();
+        assert!(rows[0].is_ok());
+        assert!(rows[1].is_err());
+        assert!(parse_json(r#"{"source_path": "/a"}"#).is_err());
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let rows: Vec<RedirectTransferRow> = vec![
+            existing("/a", "/b").into(),
+            RedirectTransferRow {
+                source_path: "/gone".to_string(),
+                destination_path: None,
+                status_code: Some(410),
+                match_type: Some(RedirectMatchType::Exact),
+                query_handling: Some(RedirectQueryHandling::Preserve),
+                is_active: Some(true),
+                description: Some("Retired, \"for good\"".to_string()),
+            },
+        ];
+        let csv = render_csv(&rows).unwrap();
+        assert!(csv.starts_with("source_path,destination_path,status_code,"));
+        let parsed = parse_csv(&csv).unwrap();
+        assert_eq!(parsed.len(), 2);
+        let gone = parsed[1].as_ref().unwrap();
+        assert_eq!(gone.destination_path, None);
+        assert_eq!(gone.status_code, Some(410));
+        assert_eq!(gone.query_handling, Some(RedirectQueryHandling::Preserve));
+        assert_eq!(gone.description.as_deref(), Some("Retired, \"for good\""));
+    }
+
+    #[test]
+    fn test_plan_flags_duplicates_self_redirects_and_invalid_rows() {
+        let rows = parse_csv(
+            "source_path,destination_path\n\
+             /taken,/x\n\
+             /same,/same\n\
+             /new,/y\n\
+             /new,/z\n\
+             no-slash,/y\n",
+        )
+        .unwrap();
+        let plan = plan(
+            Uuid::new_v4(),
+            rows,
+            &[existing("/taken", "/t")],
+            false,
+            true,
+        );
+        assert_eq!(
+            kinds(&plan),
+            vec![
+                (1, RedirectImportIssueKind::Duplicate),
+                (2, RedirectImportIssueKind::SelfRedirect),
+                (4, RedirectImportIssueKind::DuplicateInFile),
+                (5, RedirectImportIssueKind::Invalid),
+            ]
+        );
+        assert_eq!(plan.rows.len(), 1);
+        assert_eq!(plan.report.created, 1);
+        assert_eq!(plan.report.skipped, 4);
+        assert!(plan.report.dry_run);
+    }
+
+    #[test]
+    fn test_plan_overwrite_replaces_existing() {
+        let rows = parse_csv("source_path,destination_path\n/taken,/x\n").unwrap();
+        let plan = plan(
+            Uuid::new_v4(),
+            rows,
+            &[existing("/taken", "/t")],
+            true,
+            false,
+        );
+        assert_eq!(plan.report.updated, 1);
+        assert_eq!(plan.report.created, 0);
+        assert_eq!(
+            plan.report.issues[0].severity,
+            RedirectImportSeverity::Warning
+        );
+        assert!(plan.rows[0].replaces_existing);
+    }
+
+    #[test]
+    fn test_plan_detects_loops_and_chains() {
+        let rows = parse_csv(
+            "source_path,destination_path\n\
+             /a,/b\n\
+             /b,/a\n\
+             /c,/d\n\
+             /x,https://example.com/x\n",
+        )
+        .unwrap();
+        // Existing /d -> /e makes /c a chain
+        let plan = plan(Uuid::new_v4(), rows, &[existing("/d", "/e")], false, true);
+        assert_eq!(
+            kinds(&plan),
+            vec![
+                (1, RedirectImportIssueKind::Loop),
+                (2, RedirectImportIssueKind::Loop),
+                (3, RedirectImportIssueKind::Chain),
+            ]
+        );
+        assert_eq!(plan.rows.len(), 2);
+        assert!(plan.report.issues[2].message.contains("/e"));
+    }
+
+    #[test]
+    fn test_plan_loop_through_existing_redirects() {
+        let rows = parse_csv("source_path,destination_path\n/a,/b\n").unwrap();
+        let plan = plan(
+            Uuid::new_v4(),
+            rows,
+            &[existing("/b", "/c"), existing("/c", "/a")],
+            false,
+            true,
+        );
+        assert_eq!(kinds(&plan), vec![(1, RedirectImportIssueKind::Loop)]);
+        assert!(plan.rows.is_empty());
+    }
+
+    #[test]
+    fn test_transfer_format_parse() {
+        assert_eq!(TransferFormat::parse(None).unwrap(), TransferFormat::Csv);
+        assert_eq!(
+            TransferFormat::parse(Some("JSON")).unwrap(),
+            TransferFormat::Json
+        );
+        assert!(TransferFormat::parse(Some("xml")).is_err());
+    }
+}
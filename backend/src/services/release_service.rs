@@ -0,0 +1,568 @@
+//! Content release service
+//!
+//! Publishing a release applies all of its items in one transaction: content
+//! gets its target status and navigation items are shown or hidden. The state
+//! each item replaced is stored on the item, so rolling back restores it in
+//! one transaction as well. Either way a single webhook describes the whole
+//! release instead of one event per item.
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::dto::release::AddReleaseItemRequest;
+use crate::errors::ApiError;
+use crate::models::audit::AuditAction;
+use crate::models::content_release::{ContentRelease, ContentReleaseItem, ContentReleaseStatus};
+use crate::services::{audit_service, version_service, webhook_service};
+
+/// What happened to a release, for audit and webhooks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseEvent {
+    Published,
+    RolledBack,
+}
+
+impl ReleaseEvent {
+    /// Webhook event name
+    pub fn event_name(self) -> &'static str {
+        match self {
+            Self::Published => "release.published",
+            Self::RolledBack => "release.rolled_back",
+        }
+    }
+
+    fn audit_action(self) -> AuditAction {
+        match self {
+            Self::Published => AuditAction::Publish,
+            Self::RolledBack => AuditAction::Restore,
+        }
+    }
+}
+
+/// Fail unless the release can still be changed and published
+pub fn ensure_pending(release: &ContentRelease) -> Result<(), ApiError> {
+    if release.status.is_pending() {
+        Ok(())
+    } else {
+        Err(ApiError::Conflict(format!(
+            "Release '{}' has already been {}",
+            release.name,
+            match release.status {
+                ContentReleaseStatus::RolledBack => "rolled back",
+                _ => "published",
+            }
+        )))
+    }
+}
+
+/// Add a content or navigation item to a pending release, or change its target
+pub async fn add_item(
+    pool: &PgPool,
+    release: &ContentRelease,
+    req: &AddReleaseItemRequest,
+) -> Result<Uuid, ApiError> {
+    ensure_pending(release)?;
+
+    if let (Some(content_id), Some(status)) = (req.content_id, &req.target_status) {
+        let in_site: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM content_sites cs
+                INNER JOIN contents c ON c.id = cs.content_id
+                WHERE cs.content_id = $1 AND cs.site_id = $2 AND c.is_deleted = FALSE
+            )
+            "#,
+        )
+        .bind(content_id)
+        .bind(release.site_id)
+        .fetch_one(pool)
+        .await?;
+        if !in_site {
+            return Err(ApiError::NotFound(format!(
+                "Content with ID {} not found in this site",
+                content_id
+            )));
+        }
+        return ContentReleaseItem::upsert_content(pool, release.id, content_id, status).await;
+    }
+
+    if let (Some(item_id), Some(active)) = (req.navigation_item_id, req.target_active) {
+        let in_site: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM navigation_items WHERE id = $1 AND site_id = $2)",
+        )
+        .bind(item_id)
+        .bind(release.site_id)
+        .fetch_one(pool)
+        .await?;
+        if !in_site {
+            return Err(ApiError::NotFound(format!(
+                "Navigation item with ID {} not found in this site",
+                item_id
+            )));
+        }
+        return ContentReleaseItem::upsert_navigation_item(pool, release.id, item_id, active).await;
+    }
+
+    Err(ApiError::BadRequest(
+        "Pass either content_id with target_status, or navigation_item_id with target_active"
+            .to_string(),
+    ))
+}
+
+/// Publish a release by hand: apply it in one transaction, then notify
+pub async fn publish(
+    pool: &PgPool,
+    release_id: Uuid,
+    published_by: Option<Uuid>,
+) -> Result<(ContentRelease, Vec<ContentReleaseItem>), ApiError> {
+    let mut tx = pool.begin().await?;
+    let (release, items) = apply(&mut tx, release_id, published_by).await?;
+    tx.commit().await?;
+
+    notify(
+        pool,
+        &release,
+        &items,
+        ReleaseEvent::Published,
+        published_by,
+    )
+    .await;
+    Ok((release, items))
+}
+
+/// Apply a pending release on an open transaction.
+///
+/// Records the current state of every item, then sets content statuses and
+/// navigation visibility. Content that becomes published gets a publish date
+/// and loses a publish window that would hide it. Every content item gets a new
+/// version, which the item remembers so that [`rollback`] can detect later
+/// edits. The caller commits and then calls [`notify`].
+pub async fn apply(
+    conn: &mut PgConnection,
+    release_id: Uuid,
+    published_by: Option<Uuid>,
+) -> Result<(ContentRelease, Vec<ContentReleaseItem>), ApiError> {
+    let release = ContentRelease::find_for_update(&mut *conn, release_id).await?;
+    ensure_pending(&release)?;
+
+    let items = ContentReleaseItem::find_for_release(&mut *conn, release_id).await?;
+    if items.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "Release '{}' has no items",
+            release.name
+        )));
+    }
+
+    let deleted: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT c.id FROM content_release_items ri
+        INNER JOIN contents c ON c.id = ri.content_id
+        WHERE ri.release_id = $1 AND c.is_deleted = TRUE
+        "#,
+    )
+    .bind(release_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    if let Some(content_id) = deleted.first() {
+        return Err(ApiError::BadRequest(format!(
+            "Content with ID {} has been deleted; remove it from the release",
+            content_id
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE content_release_items ri
+        SET previous_status = c.status,
+            previous_published_at = c.published_at,
+            previous_publish_start = c.publish_start,
+            previous_publish_end = c.publish_end
+        FROM contents c
+        WHERE ri.content_id = c.id AND ri.release_id = $1
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE content_release_items ri
+        SET previous_active = ni.is_active
+        FROM navigation_items ni
+        WHERE ri.navigation_item_id = ni.id AND ri.release_id = $1
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE contents c
+        SET status = ri.target_status,
+            published_at = CASE WHEN ri.target_status = 'published'
+                                THEN COALESCE(c.published_at, NOW()) ELSE c.published_at END,
+            publish_start = CASE WHEN ri.target_status = 'published' AND c.publish_start > NOW()
+                                 THEN NULL ELSE c.publish_start END,
+            publish_end = CASE WHEN ri.target_status = 'published' AND c.publish_end <= NOW()
+                               THEN NULL ELSE c.publish_end END,
+            updated_at = NOW()
+        FROM content_release_items ri
+        WHERE ri.content_id = c.id AND ri.release_id = $1
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE navigation_items ni
+        SET is_active = ri.target_active, updated_at = NOW()
+        FROM content_release_items ri
+        WHERE ri.navigation_item_id = ni.id AND ri.release_id = $1
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let release = ContentRelease::mark_published(&mut *conn, release_id, published_by).await?;
+    record_versions(
+        &mut *conn,
+        &release,
+        &items,
+        ReleaseEvent::Published,
+        published_by,
+    )
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE content_release_items ri
+        SET published_version = c.current_version
+        FROM contents c
+        WHERE ri.content_id = c.id AND ri.release_id = $1
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let items = ContentReleaseItem::find_for_release(&mut *conn, release_id).await?;
+    Ok((release, items))
+}
+
+/// Roll a published release back to the state recorded when it was published.
+///
+/// Items changed after the release are skipped and flagged `rollback_skipped`:
+/// content with a newer version than the one the release published, and
+/// navigation items whose visibility was changed again.
+pub async fn rollback(
+    pool: &PgPool,
+    release_id: Uuid,
+    rolled_back_by: Option<Uuid>,
+) -> Result<(ContentRelease, Vec<ContentReleaseItem>), ApiError> {
+    let mut tx = pool.begin().await?;
+    let release = ContentRelease::find_for_update(&mut *tx, release_id).await?;
+    if release.status != ContentReleaseStatus::Published {
+        return Err(ApiError::Conflict(format!(
+            "Only published releases can be rolled back; '{}' is {:?}",
+            release.name, release.status
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE content_release_items ri
+        SET rollback_skipped = TRUE
+        FROM contents c
+        WHERE ri.content_id = c.id AND ri.release_id = $1
+          AND c.current_version IS DISTINCT FROM ri.published_version
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE content_release_items ri
+        SET rollback_skipped = TRUE
+        FROM navigation_items ni
+        WHERE ri.navigation_item_id = ni.id AND ri.release_id = $1
+          AND ni.is_active IS DISTINCT FROM ri.target_active
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE contents c
+        SET status = ri.previous_status,
+            published_at = ri.previous_published_at,
+            publish_start = ri.previous_publish_start,
+            publish_end = ri.previous_publish_end,
+            updated_at = NOW()
+        FROM content_release_items ri
+        WHERE ri.content_id = c.id AND ri.release_id = $1 AND ri.previous_status IS NOT NULL
+          AND NOT ri.rollback_skipped
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE navigation_items ni
+        SET is_active = ri.previous_active, updated_at = NOW()
+        FROM content_release_items ri
+        WHERE ri.navigation_item_id = ni.id AND ri.release_id = $1
+          AND ri.previous_active IS NOT NULL AND NOT ri.rollback_skipped
+        "#,
+    )
+    .bind(release_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let release = ContentRelease::mark_rolled_back(&mut *tx, release_id, rolled_back_by).await?;
+    let items = ContentReleaseItem::find_for_release(&mut *tx, release_id).await?;
+    record_versions(
+        &mut tx,
+        &release,
+        &items,
+        ReleaseEvent::RolledBack,
+        rolled_back_by,
+    )
+    .await?;
+    tx.commit().await?;
+
+    notify(
+        pool,
+        &release,
+        &items,
+        ReleaseEvent::RolledBack,
+        rolled_back_by,
+    )
+    .await;
+    Ok((release, items))
+}
+
+/// Record a version of every content item a release changed
+async fn record_versions(
+    conn: &mut PgConnection,
+    release: &ContentRelease,
+    items: &[ContentReleaseItem],
+    event: ReleaseEvent,
+    user_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let summary = match event {
+        ReleaseEvent::Published => format!("Published with release \"{}\"", release.name),
+        ReleaseEvent::RolledBack => format!("Rolled back release \"{}\"", release.name),
+    };
+    for content_id in items
+        .iter()
+        .filter(|i| !i.rollback_skipped)
+        .filter_map(|i| i.content_id)
+    {
+        version_service::record_version(&mut *conn, content_id, &summary, user_id).await?;
+    }
+    Ok(())
+}
+
+/// Write the audit entry and fire the webhook for a release
+pub async fn notify(
+    pool: &PgPool,
+    release: &ContentRelease,
+    items: &[ContentReleaseItem],
+    event: ReleaseEvent,
+    user_id: Option<Uuid>,
+) {
+    let payload = release_payload(release, items, event);
+    audit_service::log_action(
+        pool,
+        Some(release.site_id),
+        user_id,
+        event.audit_action(),
+        "content_release",
+        release.id,
+        Some(payload.clone()),
+    )
+    .await;
+
+    webhook_service::dispatch(
+        pool.clone(),
+        release.site_id,
+        event.event_name(),
+        release.id,
+        payload,
+    );
+}
+
+/// Build the audit/webhook payload: the release and every change it made.
+///
+/// A rollback lists the items it skipped under `skipped_items`.
+fn release_payload(
+    release: &ContentRelease,
+    items: &[ContentReleaseItem],
+    event: ReleaseEvent,
+) -> serde_json::Value {
+    let (changed, skipped): (Vec<_>, Vec<_>) = items
+        .iter()
+        .partition(|item| event == ReleaseEvent::Published || !item.rollback_skipped);
+    let items: Vec<serde_json::Value> = changed
+        .into_iter()
+        .map(|item| match item.content_id {
+            Some(content_id) => {
+                let (status, previous) = match event {
+                    ReleaseEvent::Published => (&item.target_status, &item.previous_status),
+                    ReleaseEvent::RolledBack => (&item.previous_status, &item.target_status),
+                };
+                serde_json::json!({
+                    "content_id": content_id,
+                    "entity_type": item.entity_type,
+                    "entity_id": item.entity_id,
+                    "slug": item.slug,
+                    "status": status,
+                    "previous_status": previous,
+                })
+            }
+            None => {
+                let (active, previous) = match event {
+                    ReleaseEvent::Published => (item.target_active, item.previous_active),
+                    ReleaseEvent::RolledBack => (item.previous_active, item.target_active),
+                };
+                serde_json::json!({
+                    "navigation_item_id": item.navigation_item_id,
+                    "is_active": active,
+                    "previous_is_active": previous,
+                })
+            }
+        })
+        .collect();
+
+    let mut payload = serde_json::json!({
+        "release_id": release.id,
+        "name": release.name,
+        "scheduled": release.scheduled_at.is_some() && release.published_by.is_none(),
+        "published_at": release.published_at,
+        "rolled_back_at": release.rolled_back_at,
+        "items": items,
+    });
+    if event == ReleaseEvent::RolledBack {
+        payload["skipped_items"] = skipped
+            .into_iter()
+            .map(|item| match item.content_id {
+                Some(content_id) => serde_json::json!({
+                    "content_id": content_id,
+                    "entity_type": item.entity_type,
+                    "entity_id": item.entity_id,
+                    "slug": item.slug,
+                }),
+                None => serde_json::json!({ "navigation_item_id": item.navigation_item_id }),
+            })
+            .collect();
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::content::ContentStatus;
+    use chrono::Utc;
+
+    fn sample_release(status: ContentReleaseStatus) -> ContentRelease {
+        ContentRelease {
+            id: Uuid::new_v4(),
+            site_id: Uuid::new_v4(),
+            name: "Spring campaign".to_string(),
+            description: None,
+            status,
+            scheduled_at: None,
+            published_at: Some(Utc::now()),
+            published_by: Some(Uuid::new_v4()),
+            rolled_back_at: None,
+            rolled_back_by: None,
+            error: None,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn sample_item(release_id: Uuid) -> ContentReleaseItem {
+        ContentReleaseItem {
+            id: Uuid::new_v4(),
+            release_id,
+            content_id: Some(Uuid::new_v4()),
+            navigation_item_id: None,
+            target_status: Some(ContentStatus::Published),
+            target_active: None,
+            previous_status: Some(ContentStatus::Draft),
+            previous_published_at: None,
+            previous_publish_start: None,
+            previous_publish_end: None,
+            previous_active: None,
+            published_version: Some(2),
+            rollback_skipped: false,
+            entity_type: Some("blog".to_string()),
+            entity_id: Some(Uuid::new_v4()),
+            slug: Some("spring-launch".to_string()),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_event_names() {
+        assert_eq!(ReleaseEvent::Published.event_name(), "release.published");
+        assert_eq!(ReleaseEvent::RolledBack.event_name(), "release.rolled_back");
+    }
+
+    #[test]
+    fn test_ensure_pending() {
+        assert!(ensure_pending(&sample_release(ContentReleaseStatus::Draft)).is_ok());
+        assert!(ensure_pending(&sample_release(ContentReleaseStatus::Scheduled)).is_ok());
+        assert!(ensure_pending(&sample_release(ContentReleaseStatus::Failed)).is_ok());
+        assert!(ensure_pending(&sample_release(ContentReleaseStatus::Published)).is_err());
+        assert!(ensure_pending(&sample_release(ContentReleaseStatus::RolledBack)).is_err());
+    }
+
+    #[test]
+    fn test_release_payload() {
+        let release = sample_release(ContentReleaseStatus::Published);
+        let mut nav = sample_item(release.id);
+        nav.content_id = None;
+        nav.navigation_item_id = Some(Uuid::new_v4());
+        nav.target_status = None;
+        nav.previous_status = None;
+        nav.target_active = Some(true);
+        nav.previous_active = Some(false);
+        let items = vec![sample_item(release.id), nav];
+
+        let payload = release_payload(&release, &items, ReleaseEvent::Published);
+        assert_eq!(payload["name"], "Spring campaign");
+        assert_eq!(payload["scheduled"], false);
+        assert_eq!(payload["items"][0]["status"], "Published");
+        assert_eq!(payload["items"][0]["previous_status"], "Draft");
+        assert_eq!(payload["items"][1]["is_active"], true);
+
+        assert!(payload.get("skipped_items").is_none());
+
+        let payload = release_payload(&release, &items, ReleaseEvent::RolledBack);
+        assert_eq!(payload["items"][0]["status"], "Draft");
+        assert_eq!(payload["items"][1]["is_active"], false);
+        assert_eq!(payload["skipped_items"], serde_json::json!([]));
+
+        let mut items = items;
+        items[0].rollback_skipped = true;
+        let payload = release_payload(&release, &items, ReleaseEvent::RolledBack);
+        assert_eq!(payload["items"].as_array().unwrap().len(), 1);
+        assert_eq!(payload["items"][0]["is_active"], false);
+        assert_eq!(payload["skipped_items"][0]["slug"], "spring-launch");
+    }
+}
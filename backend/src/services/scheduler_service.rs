@@ -0,0 +1,458 @@
+//! Publishing scheduler
+//!
+//! Background task that honours `publish_start` / `publish_end` on content:
+//! scheduled content is promoted to published once its start time passes, and
+//! published content is archived once its end time passes. Each tick runs
+//! under a transaction-scoped Postgres advisory lock so that only one replica
+//! performs the transitions at a time. The same tick publishes content
+//! releases whose scheduled time has passed and deletes form submissions that
+//! have outlived their form's retention period. After a tick that held the
+//! lock, content and media that have been in the trash longer than the trash
+//! retention period are purged under an advisory lock of their own. A
+//! separate task gives a batch of older images their AVIF variant on the same
+//! interval, under another advisory lock, so slow encodes never delay a tick.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{Acquire, PgPool};
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+use crate::models::audit::AuditAction;
+use crate::models::content_release::{ContentRelease, ContentReleaseItem};
+use crate::models::form::FormSubmission;
+use crate::services::release_service::{self, ReleaseEvent};
+use crate::services::storage::StorageBackend;
+use crate::services::{audit_service, media_service, trash_service, webhook_service};
+
+/// Advisory lock key shared by all replicas running the scheduler.
+const SCHEDULER_LOCK_KEY: i64 = 0x6f79_5f73_6368_6564;
+
+/// Advisory lock key held by the replica purging the trash.
+const TRASH_PURGE_LOCK_KEY: i64 = 0x6f79_5f74_7261_7368;
+
+/// Advisory lock key held by the replica backfilling AVIF variants.
+const AVIF_BACKFILL_LOCK_KEY: i64 = 0x6f79_5f61_7669_6600;
+
+/// A single content transition performed by the scheduler, one row per site.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ScheduledTransition {
+    pub content_id: Uuid,
+    pub entity_id: Uuid,
+    pub entity_type: String,
+    pub slug: Option<String>,
+    pub site_id: Uuid,
+    pub timezone: String,
+    pub publish_start: Option<DateTime<Utc>>,
+    pub publish_end: Option<DateTime<Utc>>,
+}
+
+/// Which way a transition went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    Published,
+    Unpublished,
+}
+
+impl TransitionKind {
+    /// Webhook event name for an entity type, e.g. `blog.published`.
+    pub fn event_name(self, entity_type: &str) -> String {
+        match self {
+            Self::Published => format!("{entity_type}.published"),
+            Self::Unpublished => format!("{entity_type}.unpublished"),
+        }
+    }
+
+    fn audit_action(self) -> AuditAction {
+        match self {
+            Self::Published => AuditAction::Publish,
+            Self::Unpublished => AuditAction::Unpublish,
+        }
+    }
+}
+
+/// Outcome of a single scheduler tick.
+#[derive(Debug, Default)]
+pub struct TickReport {
+    /// False when another replica held the lock and this tick did nothing.
+    pub lock_acquired: bool,
+    pub published: Vec<ScheduledTransition>,
+    pub unpublished: Vec<ScheduledTransition>,
+    /// Scheduled releases published by this tick, with their items.
+    pub releases: Vec<(ContentRelease, Vec<ContentReleaseItem>)>,
+    /// Scheduled releases that could not be applied and were marked as failed.
+    pub failed_releases: Vec<Uuid>,
+    /// Form submissions deleted because their form's retention period elapsed.
+    pub purged_submissions: u64,
+}
+
+/// Spawn the scheduler loop on the Tokio runtime.
+///
+/// `trash_retention_days` of 0 disables the trash purge, and
+/// `avif_backfill_batch` of 0 the AVIF backfill.
+pub fn spawn(
+    pool: PgPool,
+    storage: Arc<dyn StorageBackend>,
+    interval: Duration,
+    trash_retention_days: u32,
+    avif_backfill_batch: u32,
+) {
+    if avif_backfill_batch > 0 {
+        spawn_avif_backfill(pool.clone(), storage.clone(), interval, avif_backfill_batch);
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match run_tick(&pool).await {
+                Ok(report) => {
+                    if !report.published.is_empty() || !report.unpublished.is_empty() {
+                        tracing::info!(
+                            published = report.published.len(),
+                            unpublished = report.unpublished.len(),
+                            "Scheduler tick applied content transitions"
+                        );
+                    }
+                    if !report.releases.is_empty() || !report.failed_releases.is_empty() {
+                        tracing::info!(
+                            published = report.releases.len(),
+                            failed = report.failed_releases.len(),
+                            "Scheduler tick applied scheduled releases"
+                        );
+                    }
+                    if report.purged_submissions > 0 {
+                        tracing::info!(
+                            purged = report.purged_submissions,
+                            "Scheduler tick purged expired form submissions"
+                        );
+                    }
+                    if report.lock_acquired && trash_retention_days > 0 {
+                        let purge = with_session_lock(&pool, TRASH_PURGE_LOCK_KEY, || {
+                            trash_service::purge_expired(
+                                &pool,
+                                storage.as_ref(),
+                                trash_retention_days,
+                            )
+                        });
+                        match purge.await {
+                            Ok(Some(purged)) if purged.content > 0 || purged.media > 0 => {
+                                tracing::info!(
+                                    content = purged.content,
+                                    media = purged.media,
+                                    "Scheduler purged expired trash"
+                                );
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("Trash purge failed: {e}"),
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Scheduler tick failed: {e}"),
+            }
+        }
+    });
+}
+
+/// Spawn the AVIF backfill loop, which encodes up to `batch` images per run.
+fn spawn_avif_backfill(
+    pool: PgPool,
+    storage: Arc<dyn StorageBackend>,
+    interval: Duration,
+    batch: u32,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let backfill = with_session_lock(&pool, AVIF_BACKFILL_LOCK_KEY, || {
+                media_service::backfill_avif_variants(&pool, storage.as_ref(), batch)
+            });
+            match backfill.await {
+                Ok(Some(report)) if report.generated > 0 || !report.failed.is_empty() => {
+                    tracing::info!(
+                        generated = report.generated,
+                        failed = report.failed.len(),
+                        "Scheduler backfilled AVIF variants"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("AVIF backfill failed: {e}"),
+            }
+        }
+    });
+}
+
+/// Run `job` while holding the session-level advisory lock `key` on a
+/// connection of its own, so that it is not limited to one transaction and
+/// another replica cannot run it at the same time. Returns `None` without
+/// running `job` when another replica holds the lock.
+async fn with_session_lock<T, F, Fut>(
+    pool: &PgPool,
+    key: i64,
+    job: F,
+) -> Result<Option<T>, ApiError>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ApiError>>,
+{
+    let mut conn = pool.acquire().await?;
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+        .bind(key)
+        .fetch_one(&mut *conn)
+        .await?;
+    if !locked {
+        return Ok(None);
+    }
+
+    let outcome = job().await;
+    let unlocked = sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(key)
+        .execute(&mut *conn)
+        .await;
+    if unlocked.is_err() {
+        // Closing the session releases the lock
+        let _ = conn.close().await;
+    }
+    outcome.map(Some)
+}
+
+/// Run one scheduler tick: apply due transitions, then audit and notify.
+pub async fn run_tick(pool: &PgPool) -> Result<TickReport, ApiError> {
+    let mut tx = pool.begin().await?;
+
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_xact_lock($1)")
+        .bind(SCHEDULER_LOCK_KEY)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if !locked {
+        tx.rollback().await?;
+        return Ok(TickReport::default());
+    }
+
+    // Expire first so content whose whole window has elapsed is never promoted.
+    let unpublished = sqlx::query_as::<_, ScheduledTransition>(
+        r#"
+        WITH updated AS (
+            UPDATE contents
+            SET status = 'archived', updated_at = NOW()
+            WHERE status = 'published'
+              AND is_deleted = FALSE
+              AND publish_end IS NOT NULL
+              AND publish_end <= NOW()
+            RETURNING id, entity_type_id, slug, publish_start, publish_end
+        )
+        SELECT u.id AS content_id,
+               COALESCE(b.id, p.id, cv.id, ld.id, u.id) AS entity_id,
+               et.name AS entity_type, u.slug, s.id AS site_id, s.timezone,
+               u.publish_start, u.publish_end
+        FROM updated u
+        INNER JOIN entity_types et ON et.id = u.entity_type_id
+        INNER JOIN content_sites cs ON cs.content_id = u.id
+        INNER JOIN sites s ON s.id = cs.site_id
+        LEFT JOIN blogs b ON b.content_id = u.id
+        LEFT JOIN pages p ON p.content_id = u.id
+        LEFT JOIN cv_entries cv ON cv.content_id = u.id
+        LEFT JOIN legal_documents ld ON ld.content_id = u.id
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let published = sqlx::query_as::<_, ScheduledTransition>(
+        r#"
+        WITH updated AS (
+            UPDATE contents
+            SET status = 'published',
+                published_at = COALESCE(published_at, NOW()),
+                updated_at = NOW()
+            WHERE status = 'scheduled'
+              AND is_deleted = FALSE
+              AND publish_start IS NOT NULL
+              AND publish_start <= NOW()
+              AND (publish_end IS NULL OR publish_end > NOW())
+            RETURNING id, entity_type_id, slug, publish_start, publish_end
+        )
+        SELECT u.id AS content_id,
+               COALESCE(b.id, p.id, cv.id, ld.id, u.id) AS entity_id,
+               et.name AS entity_type, u.slug, s.id AS site_id, s.timezone,
+               u.publish_start, u.publish_end
+        FROM updated u
+        INNER JOIN entity_types et ON et.id = u.entity_type_id
+        INNER JOIN content_sites cs ON cs.content_id = u.id
+        INNER JOIN sites s ON s.id = cs.site_id
+        LEFT JOIN blogs b ON b.content_id = u.id
+        LEFT JOIN pages p ON p.content_id = u.id
+        LEFT JOIN cv_entries cv ON cv.content_id = u.id
+        LEFT JOIN legal_documents ld ON ld.content_id = u.id
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Each release runs in a savepoint, so one that fails leaves the rest of the tick intact.
+    let mut releases = Vec::new();
+    let mut failed_releases = Vec::new();
+    for release_id in ContentRelease::find_due_ids(&mut *tx).await? {
+        let mut savepoint = (&mut *tx).begin().await?;
+        match release_service::apply(&mut savepoint, release_id, None).await {
+            Ok(applied) => {
+                savepoint.commit().await?;
+                releases.push(applied);
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                tracing::warn!(%release_id, "Scheduled release failed: {e}");
+                ContentRelease::mark_failed(&mut *tx, release_id, &e.to_string()).await?;
+                failed_releases.push(release_id);
+            }
+        }
+    }
+
+    let purged_submissions = FormSubmission::purge_expired(&mut *tx).await?;
+
+    tx.commit().await?;
+
+    for transition in &unpublished {
+        notify(pool, transition, TransitionKind::Unpublished).await;
+    }
+    for transition in &published {
+        notify(pool, transition, TransitionKind::Published).await;
+    }
+    for (release, items) in &releases {
+        release_service::notify(pool, release, items, ReleaseEvent::Published, None).await;
+    }
+
+    Ok(TickReport {
+        lock_acquired: true,
+        published,
+        unpublished,
+        releases,
+        failed_releases,
+        purged_submissions,
+    })
+}
+
+/// Write the audit entry and fire the webhook for one transition.
+async fn notify(pool: &PgPool, transition: &ScheduledTransition, kind: TransitionKind) {
+    let payload = transition_payload(transition, kind);
+
+    audit_service::log_action(
+        pool,
+        Some(transition.site_id),
+        None,
+        kind.audit_action(),
+        &transition.entity_type,
+        transition.entity_id,
+        Some(payload.clone()),
+    )
+    .await;
+
+    webhook_service::dispatch(
+        pool.clone(),
+        transition.site_id,
+        &kind.event_name(&transition.entity_type),
+        transition.entity_id,
+        payload,
+    );
+}
+
+/// Build the audit/webhook payload, reporting times in the site's timezone.
+fn transition_payload(transition: &ScheduledTransition, kind: TransitionKind) -> serde_json::Value {
+    let status = match kind {
+        TransitionKind::Published => "Published",
+        TransitionKind::Unpublished => "Archived",
+    };
+    serde_json::json!({
+        "scheduled": true,
+        "content_id": transition.content_id,
+        "slug": transition.slug,
+        "status": status,
+        "timezone": transition.timezone,
+        "publish_start": transition.publish_start,
+        "publish_end": transition.publish_end,
+        "publish_start_local": transition
+            .publish_start
+            .map(|t| format_in_timezone(t, &transition.timezone)),
+        "publish_end_local": transition
+            .publish_end
+            .map(|t| format_in_timezone(t, &transition.timezone)),
+    })
+}
+
+/// Format a UTC timestamp as RFC 3339 in the given IANA timezone.
+/// Unknown timezone names fall back to UTC.
+pub fn format_in_timezone(ts: DateTime<Utc>, timezone: &str) -> String {
+    match timezone.parse::<chrono_tz::Tz>() {
+        Ok(tz) => ts.with_timezone(&tz).to_rfc3339(),
+        Err(_) => ts.to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample_transition() -> ScheduledTransition {
+        ScheduledTransition {
+            content_id: Uuid::new_v4(),
+            entity_id: Uuid::new_v4(),
+            entity_type: "blog".to_string(),
+            slug: Some("hello-world".to_string()),
+            site_id: Uuid::new_v4(),
+            timezone: "Europe/Vienna".to_string(),
+            publish_start: Some(Utc.with_ymd_and_hms(2025, 1, 15, 8, 0, 0).unwrap()),
+            publish_end: None,
+        }
+    }
+
+    #[test]
+    fn test_event_names() {
+        assert_eq!(
+            TransitionKind::Published.event_name("blog"),
+            "blog.published"
+        );
+        assert_eq!(
+            TransitionKind::Unpublished.event_name("page"),
+            "page.unpublished"
+        );
+    }
+
+    #[test]
+    fn test_format_in_timezone() {
+        let ts = Utc.with_ymd_and_hms(2025, 1, 15, 8, 0, 0).unwrap();
+        assert_eq!(
+            format_in_timezone(ts, "Europe/Vienna"),
+            "2025-01-15T09:00:00+01:00"
+        );
+        assert_eq!(format_in_timezone(ts, "UTC"), "2025-01-15T08:00:00+00:00");
+    }
+
+    #[test]
+    fn test_format_in_timezone_unknown_falls_back_to_utc() {
+        let ts = Utc.with_ymd_and_hms(2025, 6, 1, 12, 30, 0).unwrap();
+        assert_eq!(
+            format_in_timezone(ts, "Mars/Olympus_Mons"),
+            "2025-06-01T12:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_transition_payload() {
+        let t = sample_transition();
+        let payload = transition_payload(&t, TransitionKind::Published);
+        assert_eq!(payload["status"], "Published");
+        assert_eq!(payload["timezone"], "Europe/Vienna");
+        assert_eq!(payload["publish_start_local"], "2025-01-15T09:00:00+01:00");
+        assert!(payload["publish_end_local"].is_null());
+
+        let payload = transition_payload(&t, TransitionKind::Unpublished);
+        assert_eq!(payload["status"], "Archived");
+    }
+}
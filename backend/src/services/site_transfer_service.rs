@@ -0,0 +1,1439 @@
+//! Site transfer service
+//!
+//! Exports a site to a versioned zip archive and imports such an archive as a
+//! new site. The archive holds a manifest, one JSON file of rows per table,
+//! the global lookups the rows point at (locales, entity types, environments)
+//! and the media binaries read from the storage backend.
+//!
+//! An import gives every row a fresh UUID and rewrites every reference to it,
+//! including IDs embedded in JSON columns. Tags, categories, skills and media
+//! that already exist are reused instead of copied.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::dto::site_transfer::SiteTransferReport;
+use crate::errors::ApiError;
+use crate::models::site_membership::{SiteMembership, SiteRole};
+use crate::models::site_transfer::SiteTransferJob;
+use crate::services::storage::StorageBackend;
+
+/// Identifies an OpenYapper site archive
+pub const ARCHIVE_FORMAT: &str = "openyapper-site-archive";
+
+/// Current archive version; imports accept this version and older
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Upper bound for the unpacked size of an imported archive
+const MAX_EXTRACTED_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+/// Rows per INSERT statement during an import
+const INSERT_BATCH: usize = 500;
+
+/// Media binaries between two progress updates
+const MEDIA_PROGRESS_EVERY: usize = 10;
+
+/// How long export archives and local leftovers are kept after a job finished
+pub const ARCHIVE_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Time between two sweeps for expired archives
+const ARCHIVE_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Time between two heartbeats of a running job, and between two checks for
+/// jobs whose owner went away
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A queued or running job without a heartbeat for this long has lost its owner
+const JOB_STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+const MANIFEST_FILE: &str = "manifest.json";
+const LOOKUPS_FILE: &str = "lookups.json";
+const DATA_DIR: &str = "data";
+const MEDIA_DIR: &str = "media";
+
+/// Content owned by the site (`$1`)
+const SITE_CONTENT: &str = "SELECT cs.content_id FROM content_sites cs \
+     JOIN contents c ON c.id = cs.content_id \
+     WHERE cs.site_id = $1 AND cs.is_owner AND NOT c.is_deleted";
+
+/// Media linked to the site
+const SITE_MEDIA: &str = "SELECT ms.media_file_id FROM media_sites ms \
+     JOIN media_files m ON m.id = ms.media_file_id \
+     WHERE ms.site_id = $1 AND NOT m.is_deleted";
+
+/// Tags linked to the site or its content
+const SITE_TAGS: &str = "SELECT tag_id FROM tag_sites WHERE site_id = $1 \
+     UNION SELECT tag_id FROM content_tags WHERE content_id IN ({content})";
+
+/// Categories linked to the site or its content, with their ancestors
+const SITE_CATEGORIES: &str = "WITH RECURSIVE tree AS ( \
+     SELECT category_id AS id FROM category_sites WHERE site_id = $1 \
+     UNION SELECT category_id FROM content_categories WHERE content_id IN ({content}) \
+     UNION SELECT c.parent_id FROM categories c JOIN tree ON c.id = tree.id \
+     WHERE c.parent_id IS NOT NULL) SELECT id FROM tree";
+
+/// Skills linked to the site or its CV entries
+const SITE_SKILLS: &str = "SELECT skill_id FROM skill_sites WHERE site_id = $1 \
+     UNION SELECT skill_id FROM cv_entry_skills WHERE cv_entry_id IN \
+     (SELECT id FROM cv_entries WHERE content_id IN ({content}))";
+
+/// A table in the archive
+struct TableSpec {
+    name: &'static str,
+    /// Rows of the site: condition on `t`, with `$1` bound to the site ID
+    filter: &'static str,
+    /// Reuse an existing row `t` matching the archived row `r` instead of copying it
+    reuse: Option<&'static str>,
+    /// Column pointing at a possibly reused row; rows of reused owners are not copied
+    owner: Option<&'static str>,
+    /// Self-reference; parents are inserted before their children
+    parent: Option<&'static str>,
+}
+
+const fn table(name: &'static str, filter: &'static str) -> TableSpec {
+    TableSpec {
+        name,
+        filter,
+        reuse: None,
+        owner: None,
+        parent: None,
+    }
+}
+
+/// Tables in insert order: every table comes after the tables it references
+const TABLES: &[TableSpec] = &[
+    table("sites", "t.id = $1"),
+    table("site_settings", "t.site_id = $1"),
+    table("site_locales", "t.site_id = $1"),
+    TableSpec {
+        parent: Some("parent_id"),
+        ..table("media_folders", "t.site_id = $1")
+    },
+    TableSpec {
+        reuse: Some("t.checksum = r.checksum AND NOT t.is_deleted"),
+        ..table("media_files", "t.id IN ({media})")
+    },
+    table(
+        "media_sites",
+        "t.site_id = $1 AND t.media_file_id IN ({media})",
+    ),
+    TableSpec {
+        owner: Some("media_file_id"),
+        ..table("media_variants", "t.media_file_id IN ({media})")
+    },
+    TableSpec {
+        owner: Some("media_file_id"),
+        ..table("media_metadata", "t.media_file_id IN ({media})")
+    },
+    TableSpec {
+        reuse: Some("t.slug = r.slug"),
+        ..table("tags", "t.id IN ({tags})")
+    },
+    TableSpec {
+        owner: Some("tag_id"),
+        ..table("tag_localizations", "t.tag_id IN ({tags})")
+    },
+    table("tag_sites", "t.site_id = $1"),
+    TableSpec {
+        reuse: Some("t.slug = r.slug AND t.parent_id IS NOT DISTINCT FROM r.parent_id"),
+        parent: Some("parent_id"),
+        ..table("categories", "t.id IN ({categories})")
+    },
+    TableSpec {
+        owner: Some("category_id"),
+        ..table("category_localizations", "t.category_id IN ({categories})")
+    },
+    table("category_sites", "t.site_id = $1"),
+    TableSpec {
+        reuse: Some("t.slug = r.slug"),
+        ..table("skills", "t.id IN ({skills})")
+    },
+    TableSpec {
+        owner: Some("skill_id"),
+        ..table("skill_localizations", "t.skill_id IN ({skills})")
+    },
+    table("skill_sites", "t.site_id = $1"),
+    table("contents", "t.id IN ({content})"),
+    table(
+        "content_sites",
+        "t.site_id = $1 AND t.content_id IN ({content})",
+    ),
+    table("content_localizations", "t.content_id IN ({content})"),
+    table(
+        "content_blocks",
+        "t.content_localization_id IN \
+         (SELECT id FROM content_localizations WHERE content_id IN ({content}))",
+    ),
+    table("content_tags", "t.content_id IN ({content})"),
+    table("content_categories", "t.content_id IN ({content})"),
+    table("blogs", "t.content_id IN ({content})"),
+    table(
+        "blog_links",
+        "t.blog_id IN (SELECT id FROM blogs WHERE content_id IN ({content}))",
+    ),
+    table(
+        "blog_photos",
+        "t.blog_id IN (SELECT id FROM blogs WHERE content_id IN ({content}))",
+    ),
+    table(
+        "blog_attachments",
+        "t.blog_id IN (SELECT id FROM blogs WHERE content_id IN ({content}))",
+    ),
+    table("global_sections", "t.site_id = $1"),
+    table(
+        "global_section_localizations",
+        "t.global_section_id IN (SELECT id FROM global_sections WHERE site_id = $1)",
+    ),
+    TableSpec {
+        parent: Some("parent_page_id"),
+        ..table("pages", "t.content_id IN ({content})")
+    },
+    table(
+        "page_sections",
+        "t.page_id IN (SELECT id FROM pages WHERE content_id IN ({content}))",
+    ),
+    table(
+        "page_section_localizations",
+        "t.page_section_id IN (SELECT ps.id FROM page_sections ps \
+         JOIN pages p ON p.id = ps.page_id WHERE p.content_id IN ({content}))",
+    ),
+    table("cv_entries", "t.content_id IN ({content})"),
+    table(
+        "cv_entry_localizations",
+        "t.cv_entry_id IN (SELECT id FROM cv_entries WHERE content_id IN ({content}))",
+    ),
+    table(
+        "cv_entry_skills",
+        "t.cv_entry_id IN (SELECT id FROM cv_entries WHERE content_id IN ({content}))",
+    ),
+    table("legal_documents", "t.content_id IN ({content})"),
+    table(
+        "legal_document_localizations",
+        "t.legal_document_id IN (SELECT id FROM legal_documents WHERE content_id IN ({content}))",
+    ),
+    table(
+        "legal_groups",
+        "t.legal_document_id IN (SELECT id FROM legal_documents WHERE content_id IN ({content}))",
+    ),
+    table(
+        "legal_group_localizations",
+        "t.legal_group_id IN (SELECT g.id FROM legal_groups g \
+         JOIN legal_documents d ON d.id = g.legal_document_id WHERE d.content_id IN ({content}))",
+    ),
+    table(
+        "legal_items",
+        "t.legal_group_id IN (SELECT g.id FROM legal_groups g \
+         JOIN legal_documents d ON d.id = g.legal_document_id WHERE d.content_id IN ({content}))",
+    ),
+    table(
+        "legal_item_localizations",
+        "t.legal_item_id IN (SELECT i.id FROM legal_items i \
+         JOIN legal_groups g ON g.id = i.legal_group_id \
+         JOIN legal_documents d ON d.id = g.legal_document_id WHERE d.content_id IN ({content}))",
+    ),
+    table("navigation_menus", "t.site_id = $1"),
+    table(
+        "navigation_menu_localizations",
+        "t.navigation_menu_id IN (SELECT id FROM navigation_menus WHERE site_id = $1)",
+    ),
+    TableSpec {
+        parent: Some("parent_id"),
+        ..table("navigation_items", "t.site_id = $1")
+    },
+    table(
+        "navigation_item_localizations",
+        "t.navigation_item_id IN (SELECT id FROM navigation_items WHERE site_id = $1)",
+    ),
+    table("social_links", "t.site_id = $1"),
+    table("redirects", "t.site_id = $1"),
+    table("content_templates", "t.site_id = $1"),
+    table("page_templates", "t.site_id = $1"),
+];
+
+/// Describes the contents of an archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub site_id: Uuid,
+    pub site_name: String,
+    pub site_slug: String,
+    /// Rows per table
+    pub tables: BTreeMap<String, u64>,
+    /// Media binaries under `media/`
+    pub media_files: u64,
+}
+
+/// Global rows the archived site refers to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ArchiveLookups {
+    locales: Vec<Value>,
+    entity_types: Vec<Value>,
+    environments: Vec<Value>,
+}
+
+/// How to create the imported site
+#[derive(Debug, Clone)]
+pub struct SiteImportOptions {
+    /// Name of the new site; defaults to the archived name
+    pub name: Option<String>,
+    /// Slug of the new site; defaults to the archived slug with a numeric suffix if taken
+    pub slug: Option<String>,
+    /// Storage provider recorded on imported media (`local` or `s3`)
+    pub storage_provider: String,
+    /// Clerk user who becomes owner of the new site
+    pub owner_clerk_id: Option<String>,
+}
+
+lazy_static::lazy_static! {
+    /// Identifies this server process as the owner of the jobs it runs
+    static ref INSTANCE_ID: Uuid = Uuid::new_v4();
+}
+
+/// ID recorded as `owner_instance` on the jobs this process runs
+pub fn instance_id() -> Uuid {
+    *INSTANCE_ID
+}
+
+/// Local scratch directory for uploads and the working folders of running jobs.
+/// Archives that outlive a job are kept in the storage backend.
+pub fn transfer_dir() -> PathBuf {
+    std::env::temp_dir().join("openyapper-transfers")
+}
+
+/// New storage key for an archive. It is random rather than derived from the
+/// job ID because local storage is served publicly.
+pub fn new_archive_key() -> String {
+    format!("site-transfers/{}.zip", Uuid::new_v4())
+}
+
+/// Fail jobs whose owner stopped sending heartbeats, delete expired archives
+/// from storage and clear expired local leftovers.
+///
+/// Every replica runs this; a job is only failed once its heartbeat is older
+/// than `JOB_STALE_AFTER`, so jobs running on other replicas are left alone.
+pub fn spawn_maintenance(pool: PgPool, storage: Arc<dyn StorageBackend>) {
+    let jobs_pool = pool.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match SiteTransferJob::fail_stale(&jobs_pool, JOB_STALE_AFTER).await {
+                Ok(failed) => {
+                    for (job_id, owner) in failed {
+                        tracing::warn!(job_id = %job_id, owner = ?owner, "Marked interrupted site transfer as failed");
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to mark interrupted site transfers: {e}"),
+            }
+        }
+    });
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(ARCHIVE_CLEANUP_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match SiteTransferJob::take_expired_archives(&pool, ARCHIVE_RETENTION).await {
+                Ok(paths) => {
+                    for path in paths {
+                        if let Err(e) = storage.delete(&path).await {
+                            tracing::warn!(path = %path, "Failed to delete expired archive: {e}");
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to look up expired site transfer archives: {e}"),
+            }
+            let removed = tokio::task::spawn_blocking(|| {
+                remove_expired_archives(&transfer_dir(), ARCHIVE_RETENTION)
+            })
+            .await
+            .unwrap_or_default();
+            if removed > 0 {
+                tracing::info!(removed, "Removed expired site transfer leftovers");
+            }
+        }
+    });
+}
+
+/// Remove the entries of `dir` last modified more than `max_age` ago.
+/// Returns how many were removed.
+pub fn remove_expired_archives(dir: &Path, max_age: Duration) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= max_age);
+        if !expired {
+            continue;
+        }
+        let path = entry.path();
+        let outcome = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        match outcome {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "Failed to remove expired archive: {e}")
+            }
+        }
+    }
+    removed
+}
+
+/// Run an export in the background
+pub fn spawn_export(pool: PgPool, storage: Arc<dyn StorageBackend>, job_id: Uuid, site_id: Uuid) {
+    tokio::spawn(async move {
+        let outcome = with_heartbeat(
+            &pool,
+            job_id,
+            export_site(&pool, storage.as_ref(), job_id, site_id),
+        )
+        .await;
+        finish_job(&pool, job_id, outcome).await;
+    });
+}
+
+/// Run an import of an uploaded archive in the background
+pub fn spawn_import(
+    pool: PgPool,
+    storage: Arc<dyn StorageBackend>,
+    job_id: Uuid,
+    options: SiteImportOptions,
+) {
+    tokio::spawn(async move {
+        let outcome = with_heartbeat(
+            &pool,
+            job_id,
+            import_site(&pool, storage.as_ref(), job_id, &options),
+        )
+        .await;
+        if let Ok(SiteTransferJob {
+            archive_path: Some(path),
+            ..
+        }) = SiteTransferJob::find_by_id(&pool, job_id).await
+        {
+            let _ = storage.delete(&path).await;
+            let _ = SiteTransferJob::set_archive(&pool, job_id, None).await;
+        }
+        finish_job(&pool, job_id, outcome).await;
+    });
+}
+
+/// Drive a job while refreshing its heartbeat every `HEARTBEAT_INTERVAL`
+async fn with_heartbeat<T>(
+    pool: &PgPool,
+    job_id: Uuid,
+    job: impl std::future::Future<Output = T>,
+) -> T {
+    tokio::pin!(job);
+    let mut ticker = tokio::time::interval(HEARTBEAT_INTERVAL);
+    loop {
+        tokio::select! {
+            outcome = &mut job => return outcome,
+            _ = ticker.tick() => {
+                if let Err(e) = SiteTransferJob::heartbeat(pool, job_id).await {
+                    tracing::warn!(job_id = %job_id, "Failed to record site transfer heartbeat: {e}");
+                }
+            }
+        }
+    }
+}
+
+async fn finish_job(pool: &PgPool, job_id: Uuid, outcome: Result<SiteTransferReport, ApiError>) {
+    let _ = tokio::fs::remove_dir_all(work_dir(job_id)).await;
+    let _ = tokio::fs::remove_file(local_archive(job_id)).await;
+    let recorded = match outcome {
+        Ok(report) => match serde_json::to_value(&report) {
+            Ok(value) => SiteTransferJob::complete(pool, job_id, &value).await,
+            Err(e) => SiteTransferJob::fail(pool, job_id, &e.to_string()).await,
+        },
+        Err(e) => {
+            tracing::warn!(job_id = %job_id, error = %e, "Site transfer failed");
+            SiteTransferJob::fail(pool, job_id, &e.to_string()).await
+        }
+    };
+    if let Err(e) = recorded {
+        tracing::error!(job_id = %job_id, error = %e, "Failed to record site transfer outcome");
+    }
+}
+
+fn work_dir(job_id: Uuid) -> PathBuf {
+    transfer_dir().join(job_id.to_string())
+}
+
+/// Local copy of a job's archive while it is written or extracted
+fn local_archive(job_id: Uuid) -> PathBuf {
+    transfer_dir().join(format!("{}.zip", job_id))
+}
+
+fn io_error(e: std::io::Error) -> ApiError {
+    ApiError::Internal(format!("Archive I/O failed: {e}"))
+}
+
+/// Substitute the shared subqueries of a table filter
+fn expand_filter(filter: &str) -> String {
+    filter
+        .replace("{tags}", SITE_TAGS)
+        .replace("{categories}", SITE_CATEGORIES)
+        .replace("{skills}", SITE_SKILLS)
+        .replace("{content}", SITE_CONTENT)
+        .replace("{media}", SITE_MEDIA)
+}
+
+/// Storage paths are relative; refuse anything that could escape a directory
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Export a site to an archive in storage, recorded on the job
+pub async fn export_site(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    job_id: Uuid,
+    site_id: Uuid,
+) -> Result<SiteTransferReport, ApiError> {
+    let work = work_dir(job_id);
+    let _ = tokio::fs::remove_dir_all(&work).await;
+    tokio::fs::create_dir_all(work.join(DATA_DIR))
+        .await
+        .map_err(io_error)?;
+
+    let media_paths: Vec<String> = sqlx::query_scalar(&expand_filter(
+        "SELECT storage_path FROM media_files WHERE id IN ({media}) \
+         UNION SELECT storage_path FROM media_variants WHERE media_file_id IN ({media})",
+    ))
+    .bind(site_id)
+    .fetch_all(pool)
+    .await?;
+
+    let total_steps = (TABLES.len() + media_paths.len() + 1) as i32;
+    SiteTransferJob::start(pool, job_id, total_steps).await?;
+    let mut completed = 0;
+    let mut report = SiteTransferReport::default();
+
+    let mut site_row = Value::Null;
+    for spec in TABLES {
+        let sql = format!(
+            "SELECT to_jsonb(t) - 'search_vector' FROM {} t WHERE {}",
+            spec.name,
+            expand_filter(spec.filter)
+        );
+        let rows: Vec<Value> = sqlx::query_scalar(&sql)
+            .bind(site_id)
+            .fetch_all(pool)
+            .await?;
+        if spec.name == "sites" {
+            site_row = rows
+                .first()
+                .cloned()
+                .ok_or_else(|| ApiError::NotFound(format!("Site {} not found", site_id)))?;
+        }
+        report.rows.insert(spec.name.to_string(), rows.len() as u64);
+        let path = work.join(DATA_DIR).join(format!("{}.json", spec.name));
+        tokio::fs::write(path, serde_json::to_vec(&rows)?)
+            .await
+            .map_err(io_error)?;
+
+        completed += 1;
+        SiteTransferJob::update_progress(pool, job_id, spec.name, completed).await?;
+    }
+
+    for (i, storage_path) in media_paths.iter().enumerate() {
+        if !is_safe_relative(storage_path) {
+            report
+                .warnings
+                .push(format!("Skipped media with unsafe path '{}'", storage_path));
+        } else {
+            match storage.read(storage_path).await {
+                Ok(data) => {
+                    let target = work.join(MEDIA_DIR).join(storage_path);
+                    if let Some(parent) = target.parent() {
+                        tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+                    }
+                    tokio::fs::write(target, data).await.map_err(io_error)?;
+                    report.media_files += 1;
+                }
+                Err(e) => report
+                    .warnings
+                    .push(format!("Media file '{}' not archived: {}", storage_path, e)),
+            }
+        }
+
+        completed += 1;
+        if (i + 1).is_multiple_of(MEDIA_PROGRESS_EVERY) || i + 1 == media_paths.len() {
+            SiteTransferJob::update_progress(pool, job_id, MEDIA_DIR, completed).await?;
+        }
+    }
+
+    let lookups = ArchiveLookups {
+        locales: sqlx::query_scalar("SELECT to_jsonb(l) FROM locales l")
+            .fetch_all(pool)
+            .await?,
+        entity_types: sqlx::query_scalar(
+            "SELECT jsonb_build_object('id', id, 'name', name) FROM entity_types",
+        )
+        .fetch_all(pool)
+        .await?,
+        environments: sqlx::query_scalar(
+            "SELECT jsonb_build_object('id', id, 'name', name) FROM environments",
+        )
+        .fetch_all(pool)
+        .await?,
+    };
+    tokio::fs::write(work.join(LOOKUPS_FILE), serde_json::to_vec(&lookups)?)
+        .await
+        .map_err(io_error)?;
+
+    let site_slug = site_row["slug"].as_str().unwrap_or_default().to_string();
+    let manifest = ArchiveManifest {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        exported_at: Utc::now(),
+        site_id,
+        site_name: site_row["name"].as_str().unwrap_or_default().to_string(),
+        site_slug: site_slug.clone(),
+        tables: report.rows.clone(),
+        media_files: report.media_files,
+    };
+    tokio::fs::write(
+        work.join(MANIFEST_FILE),
+        serde_json::to_vec_pretty(&manifest)?,
+    )
+    .await
+    .map_err(io_error)?;
+
+    SiteTransferJob::update_progress(pool, job_id, "archive", completed).await?;
+    let archive = local_archive(job_id);
+    let target = archive.clone();
+    let archive_bytes = tokio::task::spawn_blocking(move || write_archive(&work, &target))
+        .await
+        .map_err(|e| ApiError::Internal(format!("Archive task failed: {e}")))??;
+
+    let data = tokio::fs::read(&archive).await.map_err(io_error)?;
+    let _ = tokio::fs::remove_file(&archive).await;
+    let key = new_archive_key();
+    storage.store(&key, &data, "application/zip").await?;
+    SiteTransferJob::set_archive(pool, job_id, Some(&key)).await?;
+
+    report.archive_bytes = Some(archive_bytes);
+    report.site_slug = Some(site_slug);
+    Ok(report)
+}
+
+/// Zip a working folder; media is stored as is, data files are deflated
+fn write_archive(work: &Path, archive: &Path) -> Result<u64, ApiError> {
+    let file = std::fs::File::create(archive).map_err(io_error)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let deflated = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+    let stored = deflated.compression_method(zip::CompressionMethod::Stored);
+
+    let mut pending = vec![work.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let name = path
+                .strip_prefix(work)
+                .map_err(|e| ApiError::Internal(e.to_string()))?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let options = if name.starts_with(MEDIA_DIR) {
+                stored
+            } else {
+                deflated
+            };
+            zip.start_file(name, options).map_err(zip_error)?;
+            let mut source = std::fs::File::open(&path).map_err(io_error)?;
+            std::io::copy(&mut source, &mut zip).map_err(io_error)?;
+        }
+    }
+    let mut file = zip.finish().map_err(zip_error)?;
+    file.flush().map_err(io_error)?;
+
+    Ok(file.metadata().map_err(io_error)?.len())
+}
+
+fn zip_error(e: zip::result::ZipError) -> ApiError {
+    ApiError::Internal(format!("Failed to write archive: {e}"))
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// Read and check the manifest of an uploaded archive
+pub fn read_manifest(archive: &Path) -> Result<ArchiveManifest, ApiError> {
+    let invalid = |e: String| ApiError::BadRequest(format!("Not a site archive: {e}"));
+    let file = std::fs::File::open(archive).map_err(io_error)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| invalid(e.to_string()))?;
+    let mut entry = zip
+        .by_name(MANIFEST_FILE)
+        .map_err(|e| invalid(e.to_string()))?;
+    let mut raw = String::new();
+    entry
+        .read_to_string(&mut raw)
+        .map_err(|e| invalid(e.to_string()))?;
+    let manifest: ArchiveManifest =
+        serde_json::from_str(&raw).map_err(|e| invalid(e.to_string()))?;
+
+    if manifest.format != ARCHIVE_FORMAT {
+        return Err(invalid(format!("unknown format '{}'", manifest.format)));
+    }
+    if manifest.version > ARCHIVE_VERSION {
+        return Err(ApiError::BadRequest(format!(
+            "Archive version {} is newer than the supported version {}",
+            manifest.version, ARCHIVE_VERSION
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Unpack an archive, refusing entries outside the target folder
+fn extract_archive(archive: &Path, target: &Path) -> Result<(), ApiError> {
+    let invalid = |e: String| ApiError::BadRequest(format!("Corrupt site archive: {e}"));
+    let file = std::fs::File::open(archive).map_err(io_error)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| invalid(e.to_string()))?;
+
+    let mut written = 0u64;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| invalid(e.to_string()))?;
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(invalid(format!("unsafe entry '{}'", entry.name())));
+        };
+        let path = target.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path).map_err(io_error)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut out = std::fs::File::create(&path).map_err(io_error)?;
+        let budget = MAX_EXTRACTED_BYTES - written;
+        written += std::io::copy(&mut (&mut entry).take(budget + 1), &mut out).map_err(io_error)?;
+        if written > MAX_EXTRACTED_BYTES {
+            return Err(ApiError::BadRequest(format!(
+                "Archive unpacks to more than {} GiB",
+                MAX_EXTRACTED_BYTES / (1024 * 1024 * 1024)
+            )));
+        }
+    }
+    Ok(())
+}
+
+async fn slug_taken(conn: &mut PgConnection, slug: &str) -> Result<bool, ApiError> {
+    let taken = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM sites WHERE slug = $1)")
+        .bind(slug)
+        .fetch_one(conn)
+        .await?;
+    Ok(taken)
+}
+
+/// Find a free site slug: the requested one, or the archived one with a numeric suffix
+pub async fn resolve_site_slug(
+    conn: &mut PgConnection,
+    requested: Option<&str>,
+    archived: &str,
+) -> Result<String, ApiError> {
+    if let Some(slug) = requested {
+        if slug_taken(conn, slug).await? {
+            return Err(ApiError::Conflict(format!(
+                "A site with slug '{}' already exists",
+                slug
+            )));
+        }
+        return Ok(slug.to_string());
+    }
+
+    let mut candidate = archived.to_string();
+    let mut suffix = 2;
+    while slug_taken(conn, &candidate).await? {
+        candidate = format!("{}-{}", archived, suffix);
+        suffix += 1;
+    }
+    Ok(candidate)
+}
+
+/// Foreign key columns of a table with whether they accept NULL
+async fn foreign_keys(pool: &PgPool) -> Result<HashMap<String, Vec<(String, bool)>>, ApiError> {
+    let rows: Vec<(String, String, bool)> = sqlx::query_as(
+        r#"
+        SELECT kcu.table_name::text, kcu.column_name::text, c.is_nullable = 'YES'
+        FROM information_schema.table_constraints tc
+        JOIN information_schema.key_column_usage kcu
+          ON kcu.constraint_name = tc.constraint_name AND kcu.table_schema = tc.table_schema
+        JOIN information_schema.columns c
+          ON c.table_schema = kcu.table_schema AND c.table_name = kcu.table_name
+         AND c.column_name = kcu.column_name
+        WHERE tc.constraint_type = 'FOREIGN KEY' AND tc.table_schema = current_schema()
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut keys: HashMap<String, Vec<(String, bool)>> = HashMap::new();
+    for (table, column, nullable) in rows {
+        keys.entry(table).or_default().push((column, nullable));
+    }
+    Ok(keys)
+}
+
+/// Columns of a table an import may write
+async fn writable_columns(pool: &PgPool, table: &str) -> Result<Vec<String>, ApiError> {
+    let columns = sqlx::query_scalar(
+        r#"
+        SELECT column_name::text FROM information_schema.columns
+        WHERE table_schema = current_schema() AND table_name = $1
+          AND is_generated = 'NEVER' AND data_type <> 'tsvector'
+        ORDER BY ordinal_position
+        "#,
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+    Ok(columns)
+}
+
+/// Replace every string that is a key of `replacements`, at any depth
+fn remap_value(value: &mut Value, replacements: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(new) = replacements.get(s.as_str()) {
+                *s = new.clone();
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| remap_value(v, replacements)),
+        Value::Object(map) => map.values_mut().for_each(|v| remap_value(v, replacements)),
+        _ => {}
+    }
+}
+
+/// Order rows so that each row comes after the row its `column` points at
+fn parents_first(rows: Vec<Value>, column: &str) -> Vec<Value> {
+    let ids: HashSet<String> = rows
+        .iter()
+        .filter_map(|r| r["id"].as_str().map(str::to_string))
+        .collect();
+    let mut placed: HashSet<String> = HashSet::new();
+    let mut ordered = Vec::with_capacity(rows.len());
+    let mut pending = rows;
+
+    while !pending.is_empty() {
+        let before = pending.len();
+        let (ready, rest): (Vec<Value>, Vec<Value>) =
+            pending.into_iter().partition(|r| match r[column].as_str() {
+                Some(parent) if ids.contains(parent) => placed.contains(parent),
+                _ => true,
+            });
+        placed.extend(
+            ready
+                .iter()
+                .filter_map(|r| r["id"].as_str().map(str::to_string)),
+        );
+        ordered.extend(ready);
+        pending = rest;
+        if pending.len() == before {
+            // Cycle: keep the remaining rows in archive order
+            ordered.append(&mut pending);
+        }
+    }
+    ordered
+}
+
+/// Move a media path under the new site's folder
+fn rebase_storage_path(path: &str, old_site_id: &str, new_site_id: Uuid) -> String {
+    let relative = path
+        .strip_prefix(old_site_id)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(path);
+    format!("{}/{}", new_site_id, relative)
+}
+
+async fn read_rows(work: &Path, file: &str) -> Result<Vec<Value>, ApiError> {
+    match tokio::fs::read(work.join(file)).await {
+        Ok(raw) => Ok(serde_json::from_slice(&raw)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(io_error(e)),
+    }
+}
+
+/// Insert rows with `jsonb_populate_recordset`, skipping rows that violate a unique constraint
+async fn insert_rows(
+    conn: &mut PgConnection,
+    table: &str,
+    columns: &[String],
+    rows: &[Value],
+) -> Result<u64, ApiError> {
+    let present: HashSet<&str> = rows
+        .iter()
+        .filter_map(Value::as_object)
+        .flat_map(|r| r.keys().map(String::as_str))
+        .collect();
+    let list = columns
+        .iter()
+        .filter(|c| present.contains(c.as_str()))
+        .map(|c| format!("\"{}\"", c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if list.is_empty() {
+        return Ok(0);
+    }
+    let sql = format!(
+        "INSERT INTO {table} ({list}) SELECT {list} \
+         FROM jsonb_populate_recordset(NULL::{table}, $1) ON CONFLICT DO NOTHING"
+    );
+
+    let mut inserted = 0;
+    for batch in rows.chunks(INSERT_BATCH) {
+        inserted += sqlx::query(&sql)
+            .bind(Value::Array(batch.to_vec()))
+            .execute(&mut *conn)
+            .await?
+            .rows_affected();
+    }
+    Ok(inserted)
+}
+
+/// ID bookkeeping of a running import
+struct ImportState {
+    /// Old ID or media URL to its replacement
+    replacements: HashMap<String, String>,
+    /// IDs that exist in the target database after the rows processed so far
+    known: HashSet<String>,
+}
+
+impl ImportState {
+    fn map(&mut self, old: &str, new: String) {
+        self.known.insert(new.clone());
+        self.replacements.insert(old.to_string(), new);
+    }
+}
+
+/// Map archived locales, entity types and environments to the local rows
+async fn map_lookups(
+    conn: &mut PgConnection,
+    pool: &PgPool,
+    lookups: &ArchiveLookups,
+    state: &mut ImportState,
+) -> Result<(), ApiError> {
+    let locale_columns = writable_columns(pool, "locales").await?;
+    for locale in &lookups.locales {
+        let (Some(old), Some(code)) = (locale["id"].as_str(), locale["code"].as_str()) else {
+            continue;
+        };
+        let existing: Option<Uuid> = sqlx::query_scalar("SELECT id FROM locales WHERE code = $1")
+            .bind(code)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let id = Uuid::new_v4();
+                let mut row = locale.clone();
+                row["id"] = Value::String(id.to_string());
+                insert_rows(conn, "locales", &locale_columns, &[row]).await?;
+                id
+            }
+        };
+        state.map(old, id.to_string());
+    }
+
+    for (table, rows) in [
+        ("entity_types", &lookups.entity_types),
+        ("environments", &lookups.environments),
+    ] {
+        for row in rows {
+            let (Some(old), Some(name)) = (row["id"].as_str(), row["name"].as_str()) else {
+                continue;
+            };
+            let id: Uuid =
+                sqlx::query_scalar(&format!("SELECT id FROM {table} WHERE name::text = $1"))
+                    .bind(name)
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .ok_or_else(|| {
+                        ApiError::BadRequest(format!(
+                            "Archive refers to unknown {} '{}'",
+                            table.trim_end_matches('s').replace('_', " "),
+                            name
+                        ))
+                    })?;
+            state.map(old, id.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Import the archive recorded on the job as a new site
+pub async fn import_site(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    job_id: Uuid,
+    options: &SiteImportOptions,
+) -> Result<SiteTransferReport, ApiError> {
+    let work = work_dir(job_id);
+    let _ = tokio::fs::remove_dir_all(&work).await;
+    {
+        let key = SiteTransferJob::find_by_id(pool, job_id)
+            .await?
+            .archive_path
+            .ok_or_else(|| ApiError::NotFound("Import archive not found".to_string()))?;
+        let archive = local_archive(job_id);
+        tokio::fs::create_dir_all(transfer_dir())
+            .await
+            .map_err(io_error)?;
+        tokio::fs::write(&archive, storage.read(&key).await?)
+            .await
+            .map_err(io_error)?;
+        let target = work.clone();
+        let extracted = {
+            let archive = archive.clone();
+            tokio::task::spawn_blocking(move || extract_archive(&archive, &target))
+                .await
+                .map_err(|e| ApiError::Internal(format!("Archive task failed: {e}")))
+        };
+        let _ = tokio::fs::remove_file(&archive).await;
+        extracted??;
+    }
+    let manifest: ArchiveManifest = serde_json::from_slice(
+        &tokio::fs::read(work.join(MANIFEST_FILE))
+            .await
+            .map_err(io_error)?,
+    )?;
+    let lookups: ArchiveLookups = match tokio::fs::read(work.join(LOOKUPS_FILE)).await {
+        Ok(raw) => serde_json::from_slice(&raw)?,
+        Err(_) => ArchiveLookups::default(),
+    };
+
+    let media_rows: u64 = ["media_files", "media_variants"]
+        .iter()
+        .filter_map(|t| manifest.tables.get(*t))
+        .sum();
+    let total_steps = (TABLES.len() as u64 + media_rows) as i32;
+    SiteTransferJob::start(pool, job_id, total_steps).await?;
+
+    let mut stored_paths = Vec::new();
+    let outcome = import_rows(
+        pool,
+        storage,
+        job_id,
+        options,
+        &work,
+        &manifest,
+        &lookups,
+        &mut stored_paths,
+    )
+    .await;
+    if outcome.is_err() {
+        // Nothing was committed; drop the binaries written so far
+        for path in &stored_paths {
+            let _ = storage.delete(path).await;
+        }
+    }
+    outcome
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn import_rows(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    job_id: Uuid,
+    options: &SiteImportOptions,
+    work: &Path,
+    manifest: &ArchiveManifest,
+    lookups: &ArchiveLookups,
+    stored_paths: &mut Vec<String>,
+) -> Result<SiteTransferReport, ApiError> {
+    let mut state = ImportState {
+        replacements: HashMap::new(),
+        known: HashSet::new(),
+    };
+
+    // Fresh IDs for every archived row, so references in any table resolve
+    let mut tables = Vec::with_capacity(TABLES.len());
+    for spec in TABLES {
+        let rows = read_rows(work, &format!("{}/{}.json", DATA_DIR, spec.name)).await?;
+        for row in &rows {
+            for key in ["id", "lineage_id"] {
+                if let Some(old) = row[key].as_str() {
+                    if !state.replacements.contains_key(old) {
+                        state
+                            .replacements
+                            .insert(old.to_string(), Uuid::new_v4().to_string());
+                    }
+                }
+            }
+        }
+        tables.push((spec, rows));
+    }
+
+    let old_site_id = manifest.site_id.to_string();
+    let site_id: Uuid = state
+        .replacements
+        .get(&old_site_id)
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| ApiError::BadRequest("Archive contains no site".to_string()))?;
+
+    // Upload binaries before the row transaction, so it does not stay open
+    // (and hold its locks) while storage is slow
+    let mut report = SiteTransferReport::default();
+    let mut completed = 0;
+    let mut unclaimed = upload_media_binaries(
+        pool,
+        storage,
+        job_id,
+        work,
+        &tables,
+        &old_site_id,
+        site_id,
+        &mut report,
+        stored_paths,
+        &mut completed,
+    )
+    .await?;
+
+    let mut tx = pool.begin().await?;
+    map_lookups(&mut tx, pool, lookups, &mut state).await?;
+    let slug = resolve_site_slug(&mut tx, options.slug.as_deref(), &manifest.site_slug).await?;
+    report.site_slug = Some(slug.clone());
+
+    let foreign_keys = foreign_keys(pool).await?;
+    let mut reused_ids: HashSet<String> = HashSet::new();
+
+    for (spec, rows) in tables {
+        let rows = match spec.parent {
+            Some(column) => parents_first(rows, column),
+            None => rows,
+        };
+        let columns = writable_columns(pool, spec.name).await?;
+        let keys = foreign_keys.get(spec.name).cloned().unwrap_or_default();
+        let mut accepted = Vec::with_capacity(rows.len());
+        let mut dropped = 0;
+        let mut reused = 0;
+
+        for mut row in rows {
+            if let Some(owner) = spec.owner {
+                if row[owner].as_str().is_some_and(|o| reused_ids.contains(o)) {
+                    continue;
+                }
+            }
+            let old_id = row["id"].as_str().map(str::to_string);
+            remap_value(&mut row, &state.replacements);
+
+            if spec.name == "sites" {
+                row["slug"] = Value::String(slug.clone());
+                if let Some(name) = &options.name {
+                    row["name"] = Value::String(name.clone());
+                }
+                row["is_deleted"] = Value::Bool(false);
+            }
+
+            if let (Some(condition), Some(old_id)) = (spec.reuse, old_id.as_deref()) {
+                let existing: Option<Uuid> = sqlx::query_scalar(&format!(
+                    "SELECT t.id FROM {table} t, jsonb_populate_record(NULL::{table}, $1) r \
+                     WHERE {condition} LIMIT 1",
+                    table = spec.name
+                ))
+                .bind(&row)
+                .fetch_optional(&mut *tx)
+                .await?;
+                if let Some(existing) = existing {
+                    state.map(old_id, existing.to_string());
+                    reused_ids.insert(old_id.to_string());
+                    reused += 1;
+                    continue;
+                }
+            }
+
+            let mut missing_required = false;
+            for (column, nullable) in &keys {
+                let Some(value) = row[column.as_str()].as_str() else {
+                    continue;
+                };
+                if state.known.contains(value) {
+                    continue;
+                }
+                if *nullable {
+                    row[column.as_str()] = Value::Null;
+                } else {
+                    missing_required = true;
+                }
+            }
+            if missing_required {
+                dropped += 1;
+                continue;
+            }
+
+            if matches!(spec.name, "media_files" | "media_variants") {
+                if let Some(path) =
+                    rebase_media_row(storage, &mut row, &old_site_id, site_id, &mut state)
+                {
+                    unclaimed.remove(&path);
+                }
+                if spec.name == "media_files" {
+                    row["storage_provider"] = Value::String(options.storage_provider.clone());
+                }
+            }
+
+            if let Some(id) = row["id"].as_str() {
+                state.known.insert(id.to_string());
+            }
+            accepted.push(row);
+        }
+
+        let inserted = insert_rows(&mut tx, spec.name, &columns, &accepted).await?;
+        report.rows.insert(spec.name.to_string(), inserted);
+        if reused > 0 {
+            report.reused.insert(spec.name.to_string(), reused);
+        }
+        if dropped > 0 {
+            report.warnings.push(format!(
+                "{} row(s) of {} skipped because they refer to data missing from the archive",
+                dropped, spec.name
+            ));
+        }
+
+        completed += 1;
+        SiteTransferJob::update_progress(pool, job_id, spec.name, completed).await?;
+    }
+
+    // Logo and favicon may point at media that moved with the import
+    sqlx::query(
+        r#"
+        UPDATE sites
+        SET logo_url = COALESCE(($2::jsonb) ->> logo_url, logo_url),
+            favicon_url = COALESCE(($2::jsonb) ->> favicon_url, favicon_url)
+        WHERE id = $1
+        "#,
+    )
+    .bind(site_id)
+    .bind(serde_json::to_value(&state.replacements)?)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // Binaries of media rows that were skipped
+    for path in &unclaimed {
+        let _ = storage.delete(path).await;
+    }
+
+    if let Some(clerk_user_id) = &options.owner_clerk_id {
+        SiteMembership::create(pool, clerk_user_id, site_id, &SiteRole::Owner, None).await?;
+    }
+    SiteTransferJob::set_site(pool, job_id, site_id).await?;
+
+    Ok(report)
+}
+
+/// Copy the binaries of the archived media rows to their new storage paths.
+///
+/// Returns the paths written; [`rebase_media_row`] claims them as the rows are
+/// accepted.
+#[allow(clippy::too_many_arguments)]
+async fn upload_media_binaries(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    job_id: Uuid,
+    work: &Path,
+    tables: &[(&TableSpec, Vec<Value>)],
+    old_site_id: &str,
+    site_id: Uuid,
+    report: &mut SiteTransferReport,
+    stored_paths: &mut Vec<String>,
+    completed: &mut i32,
+) -> Result<HashSet<String>, ApiError> {
+    let mut uploaded = HashSet::new();
+    for (spec, rows) in tables {
+        if !matches!(spec.name, "media_files" | "media_variants") {
+            continue;
+        }
+        for row in rows {
+            if let Some(path) =
+                import_media_binary(storage, work, row, old_site_id, site_id, report).await?
+            {
+                stored_paths.push(path.clone());
+                uploaded.insert(path);
+            }
+            *completed += 1;
+            if (*completed as usize).is_multiple_of(MEDIA_PROGRESS_EVERY) {
+                SiteTransferJob::update_progress(pool, job_id, spec.name, *completed).await?;
+            }
+        }
+    }
+    Ok(uploaded)
+}
+
+/// Copy the binary of an archived media row to its new storage path
+async fn import_media_binary(
+    storage: &dyn StorageBackend,
+    work: &Path,
+    row: &Value,
+    old_site_id: &str,
+    site_id: Uuid,
+    report: &mut SiteTransferReport,
+) -> Result<Option<String>, ApiError> {
+    let Some(old_path) = row["storage_path"].as_str() else {
+        return Ok(None);
+    };
+    if !is_safe_relative(old_path) {
+        report
+            .warnings
+            .push(format!("Media file '{}' has an unsafe path", old_path));
+        return Ok(None);
+    }
+    let data = match tokio::fs::read(work.join(MEDIA_DIR).join(old_path)).await {
+        Ok(data) => data,
+        Err(_) => {
+            report.warnings.push(format!(
+                "Media file '{}' is missing from the archive",
+                old_path
+            ));
+            return Ok(None);
+        }
+    };
+    let mime_type = row["mime_type"]
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| infer::get(&data).map(|t| t.mime_type().to_string()))
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let new_path = rebase_storage_path(old_path, old_site_id, site_id);
+    storage.store(&new_path, &data, &mime_type).await?;
+    report.media_files += 1;
+
+    Ok(Some(new_path))
+}
+
+/// Point a media row at its new storage path; returns that path
+fn rebase_media_row(
+    storage: &dyn StorageBackend,
+    row: &mut Value,
+    old_site_id: &str,
+    site_id: Uuid,
+    state: &mut ImportState,
+) -> Option<String> {
+    let old_path = row["storage_path"].as_str()?;
+    let new_path = rebase_storage_path(old_path, old_site_id, site_id);
+    if let Some(old_url) = row["public_url"].as_str() {
+        state
+            .replacements
+            .insert(old_url.to_string(), storage.public_url(&new_path));
+    }
+    row["storage_path"] = Value::String(new_path.clone());
+    row["public_url"] = Value::String(storage.public_url(&new_path));
+    Some(new_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filters_expand_all_placeholders() {
+        for spec in TABLES {
+            let filter = expand_filter(spec.filter);
+            assert!(!filter.contains('{'), "{} filter not expanded", spec.name);
+        }
+        let names: HashSet<&str> = TABLES.iter().map(|t| t.name).collect();
+        assert_eq!(names.len(), TABLES.len(), "Duplicate table in archive");
+    }
+
+    #[test]
+    fn test_remap_value_replaces_nested_ids() {
+        let old = Uuid::new_v4().to_string();
+        let new = Uuid::new_v4().to_string();
+        let replacements = HashMap::from([(old.clone(), new.clone())]);
+        let mut value = json!({
+            "id": old,
+            "data": { "items": [old, "keep"] },
+            "other": "untouched"
+        });
+        remap_value(&mut value, &replacements);
+        assert_eq!(value["id"], json!(new));
+        assert_eq!(value["data"]["items"], json!([new, "keep"]));
+        assert_eq!(value["other"], json!("untouched"));
+    }
+
+    #[test]
+    fn test_parents_first_orders_children_after_parents() {
+        let rows = vec![
+            json!({ "id": "c", "parent_id": "b" }),
+            json!({ "id": "b", "parent_id": "a" }),
+            json!({ "id": "a", "parent_id": null }),
+            json!({ "id": "x", "parent_id": "outside" }),
+        ];
+        let ordered: Vec<String> = parents_first(rows, "parent_id")
+            .iter()
+            .map(|r| r["id"].as_str().unwrap().to_string())
+            .collect();
+        let pos = |id: &str| ordered.iter().position(|o| o == id).unwrap();
+        assert!(pos("a") < pos("b"));
+        assert!(pos("b") < pos("c"));
+        assert_eq!(ordered.len(), 4);
+    }
+
+    #[test]
+    fn test_rebase_storage_path() {
+        let new_site = Uuid::new_v4();
+        assert_eq!(
+            rebase_storage_path("old-site/2024/01/photo.jpg", "old-site", new_site),
+            format!("{}/2024/01/photo.jpg", new_site)
+        );
+        assert_eq!(
+            rebase_storage_path("other/2024/01/photo.jpg", "old-site", new_site),
+            format!("{}/other/2024/01/photo.jpg", new_site)
+        );
+        assert!(!is_safe_relative("../etc/passwd"));
+        assert!(is_safe_relative("site/2024/01/photo.jpg"));
+    }
+
+    #[test]
+    fn test_remove_expired_archives() {
+        let dir = std::env::temp_dir().join(format!("openyapper-expiry-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("job")).unwrap();
+        std::fs::write(dir.join("job.zip"), b"zip").unwrap();
+
+        assert_eq!(remove_expired_archives(&dir, ARCHIVE_RETENTION), 0);
+        assert!(dir.join("job.zip").exists());
+
+        assert_eq!(remove_expired_archives(&dir, Duration::ZERO), 2);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        assert_eq!(
+            remove_expired_archives(&dir.join("missing"), Duration::ZERO),
+            0
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
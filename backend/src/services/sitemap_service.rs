@@ -0,0 +1,297 @@
+//! Sitemap rendering
+//!
+//! Turns sitemap entries into `<urlset>` documents (with `hreflang`
+//! alternates) and, for sites above the per-file URL limit, a
+//! `<sitemapindex>` pointing at numbered chunks.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::models::blog::Blog;
+use crate::models::site_locale::SiteLocale;
+use crate::models::sitemap::SitemapEntry;
+
+/// Maximum URLs per sitemap file (sitemaps.org protocol limit)
+pub const MAX_URLS_PER_SITEMAP: usize = 50_000;
+
+/// A single `<url>` element
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: DateTime<Utc>,
+    /// `(hreflang, href)` pairs, including the URL itself and `x-default`
+    pub alternates: Vec<(String, String)>,
+}
+
+/// Escape text for use in XML element content and attribute values
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn format_lastmod(lastmod: &DateTime<Utc>) -> String {
+    lastmod.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Absolute URL of one localization: base URL + locale prefix + path
+fn entry_url(base_url: &str, blog_prefix: &str, entry: &SitemapEntry) -> Option<String> {
+    let path = match entry.entity_type.as_str() {
+        "page" => entry.route.clone()?,
+        _ => Blog::public_path(blog_prefix, entry.slug.as_deref()?),
+    };
+    let path = SiteLocale::localize_path(entry.url_prefix.as_deref(), &path);
+    Some(format!("{}{}", base_url.trim_end_matches('/'), path))
+}
+
+/// Build one URL per localization, each listing every localization of the same content
+pub fn build_urls(base_url: &str, blog_prefix: &str, entries: &[SitemapEntry]) -> Vec<SitemapUrl> {
+    let mut urls = Vec::with_capacity(entries.len());
+    let mut start = 0;
+    while start < entries.len() {
+        let content_id: Uuid = entries[start].content_id;
+        let end = entries[start..]
+            .iter()
+            .position(|e| e.content_id != content_id)
+            .map_or(entries.len(), |offset| start + offset);
+        let group = &entries[start..end];
+        start = end;
+
+        let located: Vec<(&SitemapEntry, String)> = group
+            .iter()
+            .filter_map(|e| entry_url(base_url, blog_prefix, e).map(|url| (e, url)))
+            .collect();
+
+        let mut alternates: Vec<(String, String)> = located
+            .iter()
+            .map(|(e, url)| (e.locale_code.clone(), url.clone()))
+            .collect();
+        if let Some((_, url)) = located.iter().find(|(e, _)| e.is_default_locale) {
+            alternates.push(("x-default".to_string(), url.clone()));
+        }
+
+        for (entry, url) in &located {
+            urls.push(SitemapUrl {
+                loc: url.clone(),
+                lastmod: entry.lastmod,
+                alternates: alternates.clone(),
+            });
+        }
+    }
+    urls
+}
+
+/// Render a `<urlset>` document
+pub fn render_urlset(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" \
+         xmlns:xhtml=\"http://www.w3.org/1999/xhtml\">\n",
+    );
+    for url in urls {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", xml_escape(&url.loc)));
+        xml.push_str(&format!(
+            "    <lastmod>{}</lastmod>\n",
+            format_lastmod(&url.lastmod)
+        ));
+        for (hreflang, href) in &url.alternates {
+            xml.push_str(&format!(
+                "    <xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\"/>\n",
+                xml_escape(hreflang),
+                xml_escape(href)
+            ));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Render a `<sitemapindex>` with one `sitemap.xml?page=N` entry per chunk of `urls`
+pub fn render_index(base_url: &str, urls: &[SitemapUrl]) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for (index, chunk) in urls.chunks(MAX_URLS_PER_SITEMAP).enumerate() {
+        let loc = format!("{}/sitemap.xml?page={}", base_url, index + 1);
+        xml.push_str("  <sitemap>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", xml_escape(&loc)));
+        if let Some(lastmod) = chunk.iter().map(|u| u.lastmod).max() {
+            xml.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                format_lastmod(&lastmod)
+            ));
+        }
+        xml.push_str("  </sitemap>\n");
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+/// Number of sitemap files needed for `url_count` URLs
+pub fn page_count(url_count: usize) -> usize {
+    url_count.div_ceil(MAX_URLS_PER_SITEMAP)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(
+        content_id: Uuid,
+        entity_type: &str,
+        path: &str,
+        locale: &str,
+        prefix: Option<&str>,
+        is_default: bool,
+    ) -> SitemapEntry {
+        SitemapEntry {
+            content_id,
+            entity_type: entity_type.to_string(),
+            route: (entity_type == "page").then(|| path.to_string()),
+            slug: (entity_type == "blog").then(|| path.to_string()),
+            locale_code: locale.to_string(),
+            url_prefix: prefix.map(String::from),
+            is_default_locale: is_default,
+            lastmod: Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_entry_url_with_and_without_prefix() {
+        let id = Uuid::new_v4();
+        let base = "https://example.com/";
+        assert_eq!(
+            entry_url(
+                base,
+                "/blog",
+                &entry(id, "page", "/about", "en", None, true)
+            )
+            .unwrap(),
+            "https://example.com/about"
+        );
+        assert_eq!(
+            entry_url(
+                base,
+                "/blog",
+                &entry(id, "page", "/about", "de", Some("/de/"), false)
+            )
+            .unwrap(),
+            "https://example.com/de/about"
+        );
+        assert_eq!(
+            entry_url(
+                base,
+                "/blog",
+                &entry(id, "page", "/", "de", Some("de"), false)
+            )
+            .unwrap(),
+            "https://example.com/de/"
+        );
+        assert_eq!(
+            entry_url(
+                base,
+                "/blog",
+                &entry(id, "blog", "hello", "en", Some(""), true)
+            )
+            .unwrap(),
+            "https://example.com/blog/hello"
+        );
+        assert_eq!(
+            entry_url(
+                base,
+                "/news",
+                &entry(id, "blog", "hello", "de", Some("de"), false)
+            )
+            .unwrap(),
+            "https://example.com/de/news/hello"
+        );
+    }
+
+    #[test]
+    fn test_build_urls_groups_alternates_by_content() {
+        let page = Uuid::new_v4();
+        let blog = Uuid::new_v4();
+        let entries = vec![
+            entry(page, "page", "/about", "en", None, true),
+            entry(page, "page", "/about", "de", Some("de"), false),
+            entry(blog, "blog", "solo", "de", Some("de"), false),
+        ];
+        let urls = build_urls("https://example.com", "/blog", &entries);
+        assert_eq!(urls.len(), 3);
+
+        assert_eq!(urls[0].loc, "https://example.com/about");
+        assert_eq!(
+            urls[0].alternates,
+            vec![
+                ("en".to_string(), "https://example.com/about".to_string()),
+                ("de".to_string(), "https://example.com/de/about".to_string()),
+                (
+                    "x-default".to_string(),
+                    "https://example.com/about".to_string()
+                ),
+            ]
+        );
+        assert_eq!(urls[1].alternates, urls[0].alternates);
+
+        // No default-locale localization, so no x-default
+        assert_eq!(
+            urls[2].alternates,
+            vec![(
+                "de".to_string(),
+                "https://example.com/de/blog/solo".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_render_urlset_escapes_and_formats() {
+        let urls = vec![SitemapUrl {
+            loc: "https://example.com/a&b".to_string(),
+            lastmod: Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap(),
+            alternates: vec![("en".to_string(), "https://example.com/a&b".to_string())],
+        }];
+        let xml = render_urlset(&urls);
+        assert!(xml.contains("<loc>https://example.com/a&amp;b</loc>"));
+        assert!(xml.contains("<lastmod>2025-03-01T12:00:00Z</lastmod>"));
+        assert!(xml.contains(
+            "<xhtml:link rel=\"alternate\" hreflang=\"en\" href=\"https://example.com/a&amp;b\"/>"
+        ));
+        assert!(xml.contains("xmlns:xhtml=\"http://www.w3.org/1999/xhtml\""));
+    }
+
+    #[test]
+    fn test_render_index_chunks() {
+        let url = SitemapUrl {
+            loc: "https://example.com/x".to_string(),
+            lastmod: Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap(),
+            alternates: vec![],
+        };
+        let urls = vec![url; MAX_URLS_PER_SITEMAP + 1];
+        let xml = render_index("https://example.com", &urls);
+        assert!(xml.contains("<loc>https://example.com/sitemap.xml?page=1</loc>"));
+        assert!(xml.contains("<loc>https://example.com/sitemap.xml?page=2</loc>"));
+        assert!(!xml.contains("page=3"));
+        assert_eq!(page_count(urls.len()), 2);
+    }
+
+    #[test]
+    fn test_page_count() {
+        assert_eq!(page_count(0), 0);
+        assert_eq!(page_count(1), 1);
+        assert_eq!(page_count(MAX_URLS_PER_SITEMAP), 1);
+    }
+}
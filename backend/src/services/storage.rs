@@ -26,6 +26,9 @@ pub trait StorageBackend: Send + Sync {
     /// Store file data at the given path, returning the public URL
     async fn store(&self, path: &str, data: &[u8], content_type: &str) -> Result<String, ApiError>;
 
+    /// Read the file at the given path
+    async fn read(&self, path: &str) -> Result<Vec<u8>, ApiError>;
+
     /// Delete the file at the given path
     async fn delete(&self, path: &str) -> Result<(), ApiError>;
 
@@ -82,6 +85,13 @@ impl StorageBackend for LocalStorage {
         Ok(self.public_url(path))
     }
 
+    async fn read(&self, path: &str) -> Result<Vec<u8>, ApiError> {
+        let full_path = format!("{}/{}", self.upload_dir, path);
+        tokio::fs::read(&full_path)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to read file: {e}")))
+    }
+
     async fn delete(&self, path: &str) -> Result<(), ApiError> {
         let full_path = format!("{}/{}", self.upload_dir, path);
         if tokio::fs::metadata(&full_path).await.is_ok() {
@@ -207,6 +217,25 @@ impl StorageBackend for S3Storage {
         Ok(self.public_url(path))
     }
 
+    async fn read(&self, path: &str) -> Result<Vec<u8>, ApiError> {
+        let key = self.full_key(path);
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+            .map_err(|e| ApiError::Internal(format!("S3 GetObject failed: {e}")))?;
+        let data = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| ApiError::Internal(format!("S3 GetObject body failed: {e}")))?;
+
+        Ok(data.into_bytes().to_vec())
+    }
+
     async fn delete(&self, path: &str) -> Result<(), ApiError> {
         let key = self.full_key(path);
         self.client
@@ -0,0 +1,147 @@
+//! Trash service
+//!
+//! Deleting content or media only marks it as deleted; it then sits in the
+//! owning site's trash. Restoring checks that its slug (and route, for pages)
+//! was not taken in the meantime. Purging deletes the rows for good, and for
+//! media also the stored files. The scheduler purges everything whose
+//! retention period has elapsed.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::trash::TrashPurgeReport;
+use crate::errors::ApiError;
+use crate::models::content::Content;
+use crate::models::content_site::ContentSite;
+use crate::models::media::MediaFile;
+use crate::models::page::Page;
+use crate::models::trash::{TrashItem, TrashItemKind};
+use crate::services::content_service::ContentService;
+use crate::services::storage::StorageBackend;
+
+/// Restore deleted content owned by a site.
+///
+/// Fails with a conflict if another item now uses its slug on one of its
+/// sites, or, for pages, its route.
+pub async fn restore_content(
+    pool: &PgPool,
+    site_id: Uuid,
+    content_id: Uuid,
+) -> Result<TrashItem, ApiError> {
+    let item = TrashItem::find(pool, site_id, TrashItemKind::Content, content_id).await?;
+    let content = Content::find_deleted(pool, content_id).await?;
+    let sites = ContentSite::find_for_content(pool, content_id).await?;
+
+    for site in &sites {
+        let Some(slug) = site.slug.as_deref() else {
+            continue;
+        };
+        if ContentSite::slug_in_use(pool, site.site_id, content.entity_type_id, slug, content_id)
+            .await?
+        {
+            return Err(ApiError::Conflict(format!(
+                "Slug '{}' is already in use on site {}; change the other item's slug before restoring",
+                slug, site.site_id
+            )));
+        }
+    }
+
+    if let Some(route) = item.route.as_deref() {
+        let site_ids: Vec<Uuid> = sites.iter().map(|s| s.site_id).collect();
+        if Page::route_in_use(pool, &site_ids, route, &[item.entity_id]).await? {
+            return Err(ApiError::Conflict(format!(
+                "Route '{}' is already in use; change the other page's route before restoring",
+                route
+            )));
+        }
+    }
+
+    ContentService::restore_content(pool, content_id).await?;
+    Ok(item)
+}
+
+/// Restore a deleted media file owned by a site
+pub async fn restore_media(
+    pool: &PgPool,
+    site_id: Uuid,
+    media_id: Uuid,
+) -> Result<TrashItem, ApiError> {
+    let item = TrashItem::find(pool, site_id, TrashItemKind::Media, media_id).await?;
+    MediaFile::restore(pool, media_id).await?;
+    Ok(item)
+}
+
+/// Permanently delete one item from a site's trash
+pub async fn purge_item(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    site_id: Uuid,
+    kind: TrashItemKind,
+    id: Uuid,
+) -> Result<TrashItem, ApiError> {
+    let item = TrashItem::find(pool, site_id, kind, id).await?;
+    match kind {
+        TrashItemKind::Content => {
+            TrashItem::purge_content(pool, &[id]).await?;
+        }
+        TrashItemKind::Media => {
+            purge_media(pool, storage, &[id]).await?;
+        }
+    }
+    Ok(item)
+}
+
+/// Permanently delete everything in a site's trash
+pub async fn empty_trash(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    site_id: Uuid,
+) -> Result<TrashPurgeReport, ApiError> {
+    let items = TrashItem::find_for_site(pool, site_id, None, i64::MAX, 0).await?;
+    let (media, content): (Vec<TrashItem>, Vec<TrashItem>) = items
+        .into_iter()
+        .partition(|item| item.kind() == TrashItemKind::Media);
+
+    let content_ids: Vec<Uuid> = content.iter().map(|item| item.id).collect();
+    let media_ids: Vec<Uuid> = media.iter().map(|item| item.id).collect();
+
+    Ok(TrashPurgeReport {
+        content: TrashItem::purge_content(pool, &content_ids).await?,
+        media: purge_media(pool, storage, &media_ids).await?,
+    })
+}
+
+/// Permanently delete content and media that have been in the trash for more
+/// than `retention_days`
+pub async fn purge_expired(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    retention_days: u32,
+) -> Result<TrashPurgeReport, ApiError> {
+    let (content_ids, media_ids) = TrashItem::find_expired(pool, retention_days).await?;
+
+    Ok(TrashPurgeReport {
+        content: TrashItem::purge_content(pool, &content_ids).await?,
+        media: purge_media(pool, storage, &media_ids).await?,
+    })
+}
+
+/// Delete media rows, then the stored files nothing else refers to
+async fn purge_media(
+    pool: &PgPool,
+    storage: &dyn StorageBackend,
+    ids: &[Uuid],
+) -> Result<u64, ApiError> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let (purged, paths) = TrashItem::purge_media(pool, ids).await?;
+    for path in &paths {
+        if let Err(e) = storage.delete(path).await {
+            tracing::warn!(error = %e, path = %path, "Failed to delete purged media file from storage");
+        }
+    }
+
+    Ok(purged)
+}
@@ -0,0 +1,878 @@
+//! Content version service
+//!
+//! Records full snapshots of versionable content (blog, page, CV entry, legal
+//! document) into `content_versions`, restores snapshots, and diffs them.
+//!
+//! Snapshots store rows in their database shape (`to_jsonb`) so that they can
+//! be written back with `jsonb_populate_record(set)` without per-entity code.
+//! Each entity type is described by an [`EntitySpec`] listing its table, the
+//! columns a restore may overwrite, and the child tables (localizations,
+//! content blocks, sections) captured alongside it.
+
+use std::collections::BTreeMap;
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::dto::content_version::VersionFieldChange;
+use crate::errors::ApiError;
+use crate::models::content_version::ContentVersion;
+
+/// Which row a child table hangs off.
+#[derive(Debug, Clone, Copy)]
+enum Owner {
+    /// The `contents` row
+    Content,
+    /// The entity row (e.g. `pages.id`)
+    Entity,
+    /// Rows of another child list in the same snapshot
+    Child(&'static str),
+}
+
+/// A child table captured in a snapshot.
+#[derive(Debug)]
+struct ChildSpec {
+    /// Key of the row list in the snapshot
+    key: &'static str,
+    table: &'static str,
+    owner_column: &'static str,
+    owner: Owner,
+    /// Columns written back on restore (besides `id` and `owner_column`)
+    columns: &'static [&'static str],
+    order_by: &'static str,
+}
+
+/// A versionable entity type.
+#[derive(Debug)]
+struct EntitySpec {
+    entity_type: &'static str,
+    table: &'static str,
+    /// Columns copied by promotions and written back on restore
+    columns: &'static [&'static str],
+    /// Columns of `columns` that a restore leaves alone, because changing them
+    /// must go through a path that adds redirects or checks the page tree
+    not_restored: &'static [&'static str],
+    children: &'static [ChildSpec],
+}
+
+const CONTENT_LOCALIZATIONS: ChildSpec = ChildSpec {
+    key: "localizations",
+    table: "content_localizations",
+    owner_column: "content_id",
+    owner: Owner::Content,
+    columns: &[
+        "locale_id",
+        "title",
+        "subtitle",
+        "excerpt",
+        "body",
+        "meta_title",
+        "meta_description",
+        "translation_status",
+        "translated_by",
+    ],
+    order_by: "created_at",
+};
+
+const CONTENT_BLOCKS: ChildSpec = ChildSpec {
+    key: "blocks",
+    table: "content_blocks",
+    owner_column: "content_localization_id",
+    owner: Owner::Child("localizations"),
+    columns: &["block_type", "block_order", "block_data"],
+    order_by: "block_order",
+};
+
+const ENTITY_SPECS: &[EntitySpec] = &[
+    EntitySpec {
+        entity_type: "blog",
+        table: "blogs",
+        columns: &[
+            "author",
+            "published_date",
+            "reading_time_minutes",
+            "cover_image_id",
+            "header_image_id",
+            "is_featured",
+            "allow_comments",
+        ],
+        not_restored: &[],
+        children: &[CONTENT_LOCALIZATIONS, CONTENT_BLOCKS],
+    },
+    EntitySpec {
+        entity_type: "page",
+        table: "pages",
+        columns: &[
+            "route",
+            "page_type",
+            "template",
+            "is_in_navigation",
+            "navigation_order",
+            "parent_page_id",
+        ],
+        not_restored: &["route", "parent_page_id"],
+        children: &[
+            CONTENT_LOCALIZATIONS,
+            CONTENT_BLOCKS,
+            ChildSpec {
+                key: "sections",
+                table: "page_sections",
+                owner_column: "page_id",
+                owner: Owner::Entity,
+                columns: &[
+                    "section_type",
+                    "display_order",
+                    "cover_image_id",
+                    "call_to_action_route",
+                    "settings",
+                    "global_section_id",
+                ],
+                order_by: "display_order",
+            },
+            ChildSpec {
+                key: "section_localizations",
+                table: "page_section_localizations",
+                owner_column: "page_section_id",
+                owner: Owner::Child("sections"),
+                columns: &["locale_id", "title", "text", "button_text"],
+                order_by: "id",
+            },
+        ],
+    },
+    EntitySpec {
+        entity_type: "cv_entry",
+        table: "cv_entries",
+        columns: &[
+            "company",
+            "company_url",
+            "company_logo_id",
+            "location",
+            "start_date",
+            "end_date",
+            "is_current",
+            "entry_type",
+            "display_order",
+        ],
+        not_restored: &[],
+        children: &[
+            CONTENT_LOCALIZATIONS,
+            CONTENT_BLOCKS,
+            ChildSpec {
+                key: "entry_localizations",
+                table: "cv_entry_localizations",
+                owner_column: "cv_entry_id",
+                owner: Owner::Entity,
+                columns: &["locale_id", "position", "description", "achievements"],
+                order_by: "id",
+            },
+        ],
+    },
+    EntitySpec {
+        entity_type: "legal_document",
+        table: "legal_documents",
+        columns: &["cookie_name", "document_type"],
+        not_restored: &[],
+        children: &[
+            CONTENT_LOCALIZATIONS,
+            CONTENT_BLOCKS,
+            ChildSpec {
+                key: "document_localizations",
+                table: "legal_document_localizations",
+                owner_column: "legal_document_id",
+                owner: Owner::Entity,
+                columns: &["locale_id", "title", "intro"],
+                order_by: "id",
+            },
+        ],
+    },
+];
+
+/// Fields left out of diffs: identifiers, ownership links and timestamps.
+const DIFF_IGNORED_FIELDS: &[&str] = &[
+    "id",
+    "content_id",
+    "entity_id",
+    "page_id",
+    "page_section_id",
+    "content_localization_id",
+    "cv_entry_id",
+    "legal_document_id",
+    "created_at",
+    "updated_at",
+];
+
+fn spec_for(entity_type: &str) -> Result<&'static EntitySpec, ApiError> {
+    ENTITY_SPECS
+        .iter()
+        .find(|s| s.entity_type == entity_type)
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Versioning is not supported for entity type '{}'",
+                entity_type
+            ))
+        })
+}
+
+/// Collect the `id` of every row in a snapshot list.
+fn row_ids(rows: &serde_json::Value) -> Vec<Uuid> {
+    rows.as_array()
+        .map(|rows| {
+            rows.iter()
+                .filter_map(|r| r.get("id").and_then(|v| v.as_str()))
+                .filter_map(|s| s.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn owner_ids(
+    owner: Owner,
+    content_id: Uuid,
+    entity_id: Uuid,
+    lists: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<Uuid> {
+    match owner {
+        Owner::Content => vec![content_id],
+        Owner::Entity => vec![entity_id],
+        Owner::Child(key) => lists.get(key).map(row_ids).unwrap_or_default(),
+    }
+}
+
+/// Build a full snapshot of a content item, including localizations.
+pub async fn build_snapshot(
+    conn: &mut PgConnection,
+    content_id: Uuid,
+) -> Result<serde_json::Value, ApiError> {
+    let (entity_type, content): (String, serde_json::Value) = sqlx::query_as(
+        r#"
+        SELECT et.name,
+               jsonb_build_object(
+                   'slug', c.slug,
+                   'status', c.status,
+                   'publish_start', c.publish_start,
+                   'publish_end', c.publish_end
+               )
+        FROM contents c
+        INNER JOIN entity_types et ON et.id = c.entity_type_id
+        WHERE c.id = $1 AND c.is_deleted = FALSE
+        "#,
+    )
+    .bind(content_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Content with ID {} not found", content_id)))?;
+
+    let spec = spec_for(&entity_type)?;
+
+    let entity: serde_json::Value = sqlx::query_scalar(&format!(
+        "SELECT to_jsonb(t) FROM {} t WHERE t.content_id = $1",
+        spec.table
+    ))
+    .bind(content_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "No {} found for content {}",
+            spec.entity_type, content_id
+        ))
+    })?;
+
+    let entity_id: Uuid = entity
+        .get("id")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| ApiError::Internal("Entity row has no id".to_string()))?;
+
+    // Derived columns (the full-text search vector) are rebuilt by triggers
+    let mut lists = serde_json::Map::new();
+    for child in spec.children {
+        let ids = owner_ids(child.owner, content_id, entity_id, &lists);
+        let rows: serde_json::Value = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(jsonb_agg(to_jsonb(t) - 'search_vector' ORDER BY t.{order}), '[]'::jsonb) \
+             FROM {table} t WHERE t.{owner} = ANY($1)",
+            order = child.order_by,
+            table = child.table,
+            owner = child.owner_column,
+        ))
+        .bind(&ids)
+        .fetch_one(&mut *conn)
+        .await?;
+        lists.insert(child.key.to_string(), rows);
+    }
+
+    let mut snapshot = serde_json::json!({
+        "entity_type": spec.entity_type,
+        "content_id": content_id,
+        "entity_id": entity_id,
+        "content": content,
+        "entity": entity,
+    });
+    if let Some(obj) = snapshot.as_object_mut() {
+        obj.extend(lists);
+    }
+
+    Ok(snapshot)
+}
+
+/// Write a snapshot back onto the live rows.
+///
+/// Restores the slug, the entity columns and every child list. Workflow state
+/// (status and publish window) is left untouched so that restoring an old
+/// version never unpublishes or reschedules content by accident, and so are
+/// the page's route and parent, which only change through an update or a move.
+async fn apply_snapshot(
+    conn: &mut PgConnection,
+    content_id: Uuid,
+    snapshot: &serde_json::Value,
+) -> Result<(), ApiError> {
+    let entity_type = snapshot
+        .get("entity_type")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ApiError::BadRequest("Snapshot has no entity_type".to_string()))?;
+    let spec = spec_for(entity_type)?;
+
+    let current_type: String = sqlx::query_scalar(
+        r#"
+        SELECT et.name FROM contents c
+        INNER JOIN entity_types et ON et.id = c.entity_type_id
+        WHERE c.id = $1 AND c.is_deleted = FALSE
+        "#,
+    )
+    .bind(content_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Content with ID {} not found", content_id)))?;
+
+    if current_type != spec.entity_type {
+        return Err(ApiError::BadRequest(format!(
+            "Snapshot is for a {} but content {} is a {}",
+            spec.entity_type, content_id, current_type
+        )));
+    }
+
+    let slug = snapshot
+        .get("content")
+        .and_then(|c| c.get("slug"))
+        .and_then(|s| s.as_str());
+    sqlx::query("UPDATE contents SET slug = $2 WHERE id = $1")
+        .bind(content_id)
+        .bind(slug)
+        .execute(&mut *conn)
+        .await?;
+
+    let entity = snapshot.get("entity").cloned().unwrap_or_default();
+    let restored: Vec<&str> = spec
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| !spec.not_restored.contains(c))
+        .collect();
+    let columns = restored.join(", ");
+    let source_columns = restored
+        .iter()
+        .map(|c| format!("r.{c}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let entity_id: Uuid = sqlx::query_scalar(&format!(
+        "UPDATE {table} t SET ({columns}) = \
+         (SELECT {source_columns} FROM jsonb_populate_record(NULL::{table}, $2) r) \
+         WHERE t.content_id = $1 RETURNING t.id",
+        table = spec.table,
+    ))
+    .bind(content_id)
+    .bind(&entity)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "No {} found for content {}",
+            spec.entity_type, content_id
+        ))
+    })?;
+
+    let lists = snapshot.as_object().cloned().unwrap_or_default();
+    for child in spec.children {
+        let rows = lists
+            .get(child.key)
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!([]));
+        let owners = owner_ids(child.owner, content_id, entity_id, &lists);
+        let keep = row_ids(&rows);
+
+        // Rows that did not exist in the snapshot are removed first so that a
+        // row recreated for the same locale cannot collide with the restored one.
+        sqlx::query(&format!(
+            "DELETE FROM {table} WHERE {owner} = ANY($1) AND NOT (id = ANY($2))",
+            table = child.table,
+            owner = child.owner_column,
+        ))
+        .bind(&owners)
+        .bind(&keep)
+        .execute(&mut *conn)
+        .await?;
+
+        let insert_columns = std::iter::once("id")
+            .chain(std::iter::once(child.owner_column))
+            .chain(child.columns.iter().copied())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let updates = child
+            .columns
+            .iter()
+            .map(|c| format!("{c} = EXCLUDED.{c}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        sqlx::query(&format!(
+            "INSERT INTO {table} ({insert_columns}) \
+             SELECT {insert_columns} FROM jsonb_populate_recordset(NULL::{table}, $1) \
+             ON CONFLICT (id) DO UPDATE SET {updates}",
+            table = child.table,
+        ))
+        .bind(&rows)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Snapshot a content item and store it as its next version.
+///
+/// Runs on the caller's connection so the version is written in the same
+/// transaction as the change it records.
+pub async fn record_version(
+    conn: &mut PgConnection,
+    content_id: Uuid,
+    change_summary: &str,
+    created_by: Option<Uuid>,
+) -> Result<ContentVersion, ApiError> {
+    let snapshot = build_snapshot(&mut *conn, content_id).await?;
+    ContentVersion::create_next(
+        conn,
+        content_id,
+        &snapshot,
+        Some(change_summary),
+        created_by,
+    )
+    .await
+}
+
+/// Restore a stored version and record the result as a new version.
+pub async fn restore_version(
+    pool: &PgPool,
+    content_id: Uuid,
+    version_number: i32,
+    created_by: Option<Uuid>,
+) -> Result<ContentVersion, ApiError> {
+    let source = ContentVersion::find_by_number(pool, content_id, version_number).await?;
+
+    let mut tx = pool.begin().await?;
+    apply_snapshot(&mut tx, content_id, &source.snapshot).await?;
+    let snapshot = build_snapshot(&mut tx, content_id).await?;
+    let summary = format!("Restored from version {}", version_number);
+    let version =
+        ContentVersion::create_next(&mut tx, content_id, &snapshot, Some(&summary), created_by)
+            .await?;
+    tx.commit().await?;
+
+    Ok(version)
+}
+
+/// Entity types that snapshots (and therefore promotions) support.
+pub fn versioned_entity_types() -> Vec<&'static str> {
+    ENTITY_SPECS.iter().map(|s| s.entity_type).collect()
+}
+
+/// Environment-independent form of a snapshot, for comparing two copies of
+/// the same content item.
+///
+/// Row ids are dropped and every list becomes an object: localization rows
+/// are keyed by locale, other rows by their position under their owner (so a
+/// page's second section is `sections.1`, its German text `section_localizations.1.<locale_id>`).
+pub fn portable_snapshot(snapshot: &serde_json::Value) -> Result<serde_json::Value, ApiError> {
+    let entity_type = snapshot
+        .get("entity_type")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ApiError::BadRequest("Snapshot has no entity_type".to_string()))?;
+    let spec = spec_for(entity_type)?;
+
+    let pick = |row: &serde_json::Value, columns: &[&str]| {
+        let fields = columns
+            .iter()
+            .filter(|c| **c != "locale_id")
+            .map(|c| (c.to_string(), row.get(*c).cloned().unwrap_or_default()))
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::Value::Object(fields)
+    };
+
+    let mut portable = serde_json::json!({
+        "entity_type": spec.entity_type,
+        "content": snapshot.get("content").cloned().unwrap_or_default(),
+        "entity": pick(&snapshot.get("entity").cloned().unwrap_or_default(), spec.columns),
+    });
+
+    let mut keys: BTreeMap<String, String> = BTreeMap::new();
+    for child in spec.children {
+        let mut out = serde_json::Map::new();
+        let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+        let rows = snapshot
+            .get(child.key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for row in &rows {
+            let field = |name: &str| row.get(name).and_then(|v| v.as_str()).map(str::to_string);
+            let parent = match child.owner {
+                Owner::Child(_) => field(child.owner_column).and_then(|id| keys.get(&id).cloned()),
+                _ => None,
+            };
+            let own = field("locale_id").unwrap_or_else(|| {
+                let position = positions
+                    .entry(parent.clone().unwrap_or_default())
+                    .or_default();
+                *position += 1;
+                (*position - 1).to_string()
+            });
+            let key = match parent {
+                Some(parent) => format!("{parent}.{own}"),
+                None => own,
+            };
+            if let Some(id) = field("id") {
+                keys.insert(id, key.clone());
+            }
+            out.insert(key, pick(row, child.columns));
+        }
+        portable[child.key] = serde_json::Value::Object(out);
+    }
+
+    Ok(portable)
+}
+
+/// Overwrite a content item with a snapshot taken from a different item.
+///
+/// Unlike a restore, the status and publish window are copied, the entity row
+/// is created if missing, and every child row is recreated with a fresh id.
+/// Entity columns listed in `keep` are left as they are on the target.
+pub async fn copy_snapshot(
+    conn: &mut PgConnection,
+    target_content_id: Uuid,
+    snapshot: &serde_json::Value,
+    keep: &[&str],
+) -> Result<(), ApiError> {
+    let entity_type = snapshot
+        .get("entity_type")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ApiError::BadRequest("Snapshot has no entity_type".to_string()))?;
+    let spec = spec_for(entity_type)?;
+
+    let content = snapshot.get("content").cloned().unwrap_or_default();
+    sqlx::query(
+        r#"
+        UPDATE contents
+        SET slug = r.slug,
+            status = r.status,
+            publish_start = r.publish_start,
+            publish_end = r.publish_end,
+            published_at = CASE
+                WHEN r.status = 'published' THEN COALESCE(contents.published_at, NOW())
+                ELSE contents.published_at
+            END
+        FROM jsonb_populate_record(NULL::contents, $2) r
+        WHERE contents.id = $1
+        "#,
+    )
+    .bind(target_content_id)
+    .bind(&content)
+    .execute(&mut *conn)
+    .await?;
+
+    let entity = snapshot.get("entity").cloned().unwrap_or_default();
+    let copied: Vec<&str> = spec
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| !keep.contains(c))
+        .collect();
+    let columns = copied.join(", ");
+    let source_columns = copied
+        .iter()
+        .map(|c| format!("r.{c}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let updated: Option<Uuid> = sqlx::query_scalar(&format!(
+        "UPDATE {table} t SET ({columns}) = \
+         (SELECT {source_columns} FROM jsonb_populate_record(NULL::{table}, $2) r) \
+         WHERE t.content_id = $1 RETURNING t.id",
+        table = spec.table,
+    ))
+    .bind(target_content_id)
+    .bind(&entity)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let entity_id = match updated {
+        Some(id) => id,
+        None => {
+            sqlx::query_scalar(&format!(
+                "INSERT INTO {table} (content_id, {columns}) \
+                 SELECT $1, {source_columns} FROM jsonb_populate_record(NULL::{table}, $2) r \
+                 RETURNING id",
+                table = spec.table,
+            ))
+            .bind(target_content_id)
+            .bind(&entity)
+            .fetch_one(&mut *conn)
+            .await?
+        }
+    };
+
+    // Nested rows go with their owners (ON DELETE CASCADE)
+    for child in spec.children {
+        let owner = match child.owner {
+            Owner::Content => target_content_id,
+            Owner::Entity => entity_id,
+            Owner::Child(_) => continue,
+        };
+        sqlx::query(&format!(
+            "DELETE FROM {table} WHERE {owner_column} = $1",
+            table = child.table,
+            owner_column = child.owner_column,
+        ))
+        .bind(owner)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let mut new_ids: BTreeMap<String, Uuid> = BTreeMap::new();
+    for child in spec.children {
+        let mut rows = snapshot
+            .get(child.key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for row in rows.iter_mut() {
+            let owner = match child.owner {
+                Owner::Content => Some(target_content_id),
+                Owner::Entity => Some(entity_id),
+                Owner::Child(_) => row
+                    .get(child.owner_column)
+                    .and_then(|v| v.as_str())
+                    .and_then(|id| new_ids.get(id))
+                    .copied(),
+            };
+            let id = Uuid::new_v4();
+            if let Some(old) = row.get("id").and_then(|v| v.as_str()) {
+                new_ids.insert(old.to_string(), id);
+            }
+            row["id"] = serde_json::json!(id);
+            row[child.owner_column] = serde_json::json!(owner);
+        }
+
+        let insert_columns = std::iter::once("id")
+            .chain(std::iter::once(child.owner_column))
+            .chain(child.columns.iter().copied())
+            .collect::<Vec<_>>()
+            .join(", ");
+        sqlx::query(&format!(
+            "INSERT INTO {table} ({insert_columns}) \
+             SELECT {insert_columns} FROM jsonb_populate_recordset(NULL::{table}, $1)",
+            table = child.table,
+        ))
+        .bind(serde_json::Value::Array(rows))
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Identify a row inside a snapshot list so diffs line up across versions.
+/// Localization rows are keyed by locale (and section, for page sections).
+fn row_key(row: &serde_json::Value) -> Option<String> {
+    let field = |name: &str| row.get(name).and_then(|v| v.as_str());
+    match (field("locale_id"), field("page_section_id"), field("id")) {
+        (Some(locale), Some(section), _) => Some(format!("{section}.{locale}")),
+        (Some(locale), None, _) => Some(locale.to_string()),
+        (None, _, Some(id)) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut BTreeMap<String, serde_json::Value>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, val) in map {
+                if DIFF_IGNORED_FIELDS.contains(&key.as_str()) || key == "entity_type" {
+                    continue;
+                }
+                flatten(&join(key), val, out);
+            }
+        }
+        serde_json::Value::Array(items)
+            if !items.is_empty() && items.iter().all(|i| row_key(i).is_some()) =>
+        {
+            for item in items {
+                if let Some(key) = row_key(item) {
+                    flatten(&join(&key), item, out);
+                }
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Diff two snapshots field by field.
+///
+/// Field names are dotted paths such as `entity.author` or
+/// `localizations.<locale_id>.title`. Fields present on only one side are
+/// reported with `null` on the other.
+pub fn diff_snapshots(old: &serde_json::Value, new: &serde_json::Value) -> Vec<VersionFieldChange> {
+    let mut old_fields = BTreeMap::new();
+    let mut new_fields = BTreeMap::new();
+    flatten("", old, &mut old_fields);
+    flatten("", new, &mut new_fields);
+
+    let mut fields: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = old_fields.get(field).cloned().unwrap_or_default();
+            let new_value = new_fields.get(field).cloned().unwrap_or_default();
+            (old_value != new_value).then(|| VersionFieldChange {
+                field: field.clone(),
+                old_value,
+                new_value,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_spec_for_known_types() {
+        for t in ["blog", "page", "cv_entry", "legal_document"] {
+            assert_eq!(spec_for(t).unwrap().entity_type, t);
+        }
+        assert!(spec_for("social_link").is_err());
+    }
+
+    #[test]
+    fn test_specs_never_restore_identity_columns() {
+        for spec in ENTITY_SPECS {
+            assert!(!spec.columns.contains(&"id"));
+            assert!(!spec.columns.contains(&"content_id"));
+            for child in spec.children {
+                assert!(!child.columns.contains(&"id"));
+                assert!(!child.columns.contains(&child.owner_column));
+            }
+        }
+    }
+
+    #[test]
+    fn test_specs_skip_only_known_columns_on_restore() {
+        for spec in ENTITY_SPECS {
+            for column in spec.not_restored {
+                assert!(spec.columns.contains(column));
+            }
+        }
+        let page = spec_for("page").unwrap();
+        assert_eq!(page.not_restored, &["route", "parent_page_id"]);
+    }
+
+    #[test]
+    fn test_diff_entity_and_content_fields() {
+        let old = json!({
+            "entity_type": "blog",
+            "content": { "slug": "hello", "status": "draft" },
+            "entity": { "id": "a", "author": "Jane", "updated_at": "2025-01-01" },
+            "localizations": []
+        });
+        let new = json!({
+            "entity_type": "blog",
+            "content": { "slug": "hello-world", "status": "draft" },
+            "entity": { "id": "a", "author": "Jane", "updated_at": "2025-02-01" },
+            "localizations": []
+        });
+
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "content.slug");
+        assert_eq!(changes[0].old_value, json!("hello"));
+        assert_eq!(changes[0].new_value, json!("hello-world"));
+    }
+
+    #[test]
+    fn test_diff_localizations_keyed_by_locale() {
+        let old = json!({
+            "localizations": [
+                { "id": "l1", "locale_id": "en", "title": "Hello" },
+                { "id": "l2", "locale_id": "de", "title": "Hallo" }
+            ]
+        });
+        // Order changed, German localization recreated with a new id, English title edited
+        let new = json!({
+            "localizations": [
+                { "id": "l3", "locale_id": "de", "title": "Hallo" },
+                { "id": "l1", "locale_id": "en", "title": "Hello there" }
+            ]
+        });
+
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "localizations.en.title");
+    }
+
+    #[test]
+    fn test_diff_added_and_removed_rows() {
+        let old = json!({ "sections": [{ "id": "s1", "section_type": "hero" }] });
+        let new = json!({ "sections": [{ "id": "s2", "section_type": "cta" }] });
+
+        let changes = diff_snapshots(&old, &new);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["sections.s1.section_type", "sections.s2.section_type"]
+        );
+        assert_eq!(changes[0].new_value, serde_json::Value::Null);
+        assert_eq!(changes[1].old_value, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_diff_section_localizations_keyed_by_section_and_locale() {
+        let old = json!({ "section_localizations": [
+            { "id": "x", "page_section_id": "s1", "locale_id": "en", "title": "A" },
+            { "id": "y", "page_section_id": "s2", "locale_id": "en", "title": "B" }
+        ]});
+        let new = json!({ "section_localizations": [
+            { "id": "x", "page_section_id": "s1", "locale_id": "en", "title": "A" },
+            { "id": "y", "page_section_id": "s2", "locale_id": "en", "title": "C" }
+        ]});
+
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "section_localizations.s2.en.title");
+    }
+
+    #[test]
+    fn test_diff_identical_snapshots_is_empty() {
+        let snap = json!({ "entity": { "author": "Jane" }, "localizations": [] });
+        assert!(diff_snapshots(&snap, &snap).is_empty());
+    }
+}
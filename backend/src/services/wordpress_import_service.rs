@@ -0,0 +1,1285 @@
+//! WordPress import
+//!
+//! Reads WordPress export files (WXR, an RSS dialect) and maps them onto a
+//! site: posts become blogs, pages keep their parent hierarchy, categories and
+//! tags go to the taxonomy and authors to the blog `author` field. Attachments
+//! run through the media upload pipeline, links to them in post bodies are
+//! rewritten, and old permalinks redirect to the new paths.
+//!
+//! Blogs, pages, terms and files that already exist on the site are reused, so
+//! an interrupted import can simply be run again.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use lazy_static::lazy_static;
+use quick_xml::encoding::Decoder;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::{Captures, Regex};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::blog::CreateBlogRequest;
+use crate::dto::content::CreateLocalizationRequest;
+use crate::dto::media::AddMediaMetadataRequest;
+use crate::dto::page::CreatePageRequest;
+use crate::dto::taxonomy::{CreateCategoryRequest, CreateTagRequest};
+use crate::dto::wordpress_import::WordPressImportReport;
+use crate::errors::ApiError;
+use crate::models::blog::Blog;
+use crate::models::content::{ContentLocalization, ContentStatus};
+use crate::models::media::{MediaFile, MediaMetadata};
+use crate::models::page::Page;
+use crate::models::site_locale::SiteLocale;
+use crate::models::taxonomy::{Category, Tag};
+use crate::services::media_service::{self, MediaUpload};
+use crate::services::redirect_service;
+use crate::AppState;
+
+/// Largest attachment downloaded from the source site
+const MAX_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Redirects followed per attachment download
+const MAX_REDIRECTS: usize = 5;
+
+/// Description of generated redirects
+const REDIRECT_DESCRIPTION: &str = "Imported from WordPress";
+
+lazy_static! {
+    /// Links into the WordPress uploads folder; captures the path below it
+    static ref UPLOADS_URL: Regex =
+        Regex::new(r#"(?:https?:)?(?://[^/\s"'<>]+)?/wp-content/uploads/([^\s"'<>()?#]+)"#)
+            .unwrap();
+
+    /// Size suffix WordPress adds to resized images, e.g. `photo-300x200.jpg`
+    static ref SIZE_SUFFIX: Regex = Regex::new(r"-\d+x\d+(\.[A-Za-z0-9]+)$").unwrap();
+
+    static ref HTML_TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+
+    static ref HTML_ENTITY: Regex =
+        Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|amp|lt|gt|quot|apos|nbsp);").unwrap();
+}
+
+// ============================================
+// WXR parsing
+// ============================================
+
+/// A category or tag declared in the export
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WxrTerm {
+    /// Slug as exported (may be percent-encoded)
+    pub slug: String,
+    pub name: String,
+    /// Slug of the parent category
+    pub parent: Option<String>,
+    pub description: Option<String>,
+}
+
+/// A term assigned to an item (`<category domain="post_tag" nicename="...">`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WxrItemTerm {
+    /// `category` or `post_tag`; other taxonomies are ignored
+    pub domain: String,
+    pub slug: String,
+    pub name: String,
+}
+
+/// A post, page, attachment or other item
+#[derive(Debug, Clone, Default)]
+pub struct WxrItem {
+    pub post_id: i64,
+    pub post_type: String,
+    pub title: String,
+    /// Permalink on the source site
+    pub link: Option<String>,
+    /// Author login
+    pub creator: Option<String>,
+    pub content: String,
+    pub excerpt: String,
+    pub post_date: Option<NaiveDateTime>,
+    pub post_date_gmt: Option<NaiveDateTime>,
+    pub slug: String,
+    pub status: String,
+    /// Parent post ID, 0 for none
+    pub parent_id: i64,
+    pub menu_order: i32,
+    pub comment_status: Option<String>,
+    pub is_sticky: bool,
+    pub attachment_url: Option<String>,
+    pub terms: Vec<WxrItemTerm>,
+    pub meta: HashMap<String, String>,
+}
+
+impl WxrItem {
+    fn meta(&self, key: &str) -> Option<&str> {
+        self.meta
+            .get(key)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// A parsed WordPress export
+#[derive(Debug, Clone, Default)]
+pub struct WxrExport {
+    /// Author logins mapped to display names
+    pub authors: HashMap<String, String>,
+    pub categories: Vec<WxrTerm>,
+    pub tags: Vec<WxrTerm>,
+    pub items: Vec<WxrItem>,
+}
+
+fn element_name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.name().as_ref()).into_owned()
+}
+
+fn attribute(e: &BytesStart, name: &str, decoder: Decoder) -> String {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| {
+            a.decode_and_unescape_value(decoder)
+                .ok()
+                .map(|v| v.into_owned())
+        })
+        .unwrap_or_default()
+}
+
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M:%S").ok()
+}
+
+/// Decode the HTML entities WordPress leaves in titles and term names
+fn decode_entities(value: &str) -> String {
+    HTML_ENTITY
+        .replace_all(value, |caps: &Captures| {
+            let entity = &caps[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => match entity[1..].strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity[1..].parse().ok(),
+                }
+                .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn set_item_field(item: &mut WxrItem, field: &str, value: String) {
+    match field {
+        "title" => item.title = decode_entities(value.trim()),
+        "link" => item.link = non_empty(value),
+        "dc:creator" => item.creator = non_empty(value),
+        "content:encoded" => item.content = value,
+        "excerpt:encoded" => item.excerpt = value.trim().to_string(),
+        "wp:post_id" => item.post_id = value.trim().parse().unwrap_or_default(),
+        "wp:post_date" => item.post_date = parse_date(&value),
+        "wp:post_date_gmt" => item.post_date_gmt = parse_date(&value),
+        "wp:post_name" => item.slug = value.trim().to_string(),
+        "wp:status" => item.status = value.trim().to_string(),
+        "wp:post_parent" => item.parent_id = value.trim().parse().unwrap_or_default(),
+        "wp:menu_order" => item.menu_order = value.trim().parse().unwrap_or_default(),
+        "wp:post_type" => item.post_type = value.trim().to_string(),
+        "wp:comment_status" => item.comment_status = non_empty(value),
+        "wp:is_sticky" => item.is_sticky = value.trim() == "1",
+        "wp:attachment_url" => item.attachment_url = non_empty(value),
+        _ => {}
+    }
+}
+
+fn set_term_field(term: &mut WxrTerm, field: &str, value: String) {
+    match field {
+        "wp:category_nicename" | "wp:tag_slug" => term.slug = value.trim().to_string(),
+        "wp:cat_name" | "wp:tag_name" => term.name = decode_entities(value.trim()),
+        "wp:category_parent" => term.parent = non_empty(value),
+        "wp:category_description" | "wp:tag_description" => term.description = non_empty(value),
+        _ => {}
+    }
+}
+
+/// Parse a WordPress export (WXR) file
+pub fn parse_wxr(input: &str) -> Result<WxrExport, ApiError> {
+    let mut reader = Reader::from_str(input);
+    let mut export = WxrExport::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut is_wxr = false;
+
+    let mut item = WxrItem::default();
+    let mut term = WxrTerm::default();
+    let mut author = (String::new(), String::new());
+    let mut item_term = (String::new(), String::new());
+    let mut meta = (String::new(), String::new());
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = element_name(&e);
+                text.clear();
+                match (path.last().map(String::as_str), name.as_str()) {
+                    (Some("channel"), "item") => item = WxrItem::default(),
+                    (Some("channel"), "wp:category" | "wp:tag") => term = WxrTerm::default(),
+                    (Some("channel"), "wp:author") => author = Default::default(),
+                    (Some("item"), "category") => {
+                        let decoder = reader.decoder();
+                        item_term = (
+                            attribute(&e, "domain", decoder),
+                            attribute(&e, "nicename", decoder),
+                        )
+                    }
+                    (Some("item"), "wp:postmeta") => meta = Default::default(),
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Ok(Event::Text(t)) => match t.unescape() {
+                Ok(value) => text.push_str(&value),
+                Err(_) => text.push_str(&String::from_utf8_lossy(&t)),
+            },
+            Ok(Event::CData(c)) => text.push_str(&String::from_utf8_lossy(&c)),
+            Ok(Event::End(_)) => {
+                let name = path.pop().unwrap_or_default();
+                let value = std::mem::take(&mut text);
+                match (path.last().map(String::as_str), name.as_str()) {
+                    (Some("channel"), "wp:wxr_version") => is_wxr = true,
+                    (Some("channel"), "item") => export.items.push(std::mem::take(&mut item)),
+                    (Some("channel"), "wp:category") => {
+                        export.categories.push(std::mem::take(&mut term))
+                    }
+                    (Some("channel"), "wp:tag") => export.tags.push(std::mem::take(&mut term)),
+                    (Some("channel"), "wp:author") => {
+                        let (login, display_name) = std::mem::take(&mut author);
+                        if !login.is_empty() {
+                            export.authors.insert(login, display_name);
+                        }
+                    }
+                    (Some("wp:author"), "wp:author_login") => author.0 = value.trim().to_string(),
+                    (Some("wp:author"), "wp:author_display_name") => {
+                        author.1 = value.trim().to_string()
+                    }
+                    (Some("wp:category" | "wp:tag"), field) => {
+                        set_term_field(&mut term, field, value)
+                    }
+                    (Some("item"), "category") => {
+                        let (domain, slug) = std::mem::take(&mut item_term);
+                        if !slug.is_empty() {
+                            item.terms.push(WxrItemTerm {
+                                domain,
+                                slug,
+                                name: decode_entities(value.trim()),
+                            });
+                        }
+                    }
+                    (Some("item"), "wp:postmeta") => {
+                        let (key, value) = std::mem::take(&mut meta);
+                        item.meta.insert(key, value);
+                    }
+                    (Some("wp:postmeta"), "wp:meta_key") => meta.0 = value.trim().to_string(),
+                    (Some("wp:postmeta"), "wp:meta_value") => meta.1 = value,
+                    (Some("item"), field) => set_item_field(&mut item, field, value),
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(ApiError::BadRequest(format!(
+                    "Invalid WXR file at byte {}: {}",
+                    reader.error_position(),
+                    e
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    if !is_wxr {
+        return Err(ApiError::BadRequest(
+            "Not a WordPress export file (missing wp:wxr_version)".to_string(),
+        ));
+    }
+    Ok(export)
+}
+
+// ============================================
+// Mapping helpers
+// ============================================
+
+/// Decode `%xx` escapes (WordPress percent-encodes non-ASCII slugs)
+fn percent_decode(value: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push(high * 16 + low);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Turn a WordPress slug or title into a valid slug; empty if nothing is left
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::new();
+    for c in percent_decode(value).to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(100);
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Slug for an item: its post name, else its title, else type and ID
+fn item_slug(item: &WxrItem) -> String {
+    [slugify(&item.slug), slugify(&item.title)]
+        .into_iter()
+        .find(|s| !s.is_empty())
+        .unwrap_or_else(|| format!("{}-{}", slugify(&item.post_type), item.post_id))
+}
+
+/// Status and publish start for an item, or `None` if it should not be imported
+fn item_status(item: &WxrItem) -> Option<(ContentStatus, Option<DateTime<Utc>>)> {
+    match item.status.as_str() {
+        "publish" => Some((ContentStatus::Published, None)),
+        "future" => Some((
+            ContentStatus::Scheduled,
+            item.post_date_gmt.or(item.post_date).map(|d| d.and_utc()),
+        )),
+        "draft" | "pending" | "private" => Some((ContentStatus::Draft, None)),
+        _ => None,
+    }
+}
+
+/// Path of a URL, without host, query or fragment
+fn url_path(url: &str) -> &str {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let path = rest.find('/').map(|i| &rest[i..]).unwrap_or("/");
+    path.split(['?', '#']).next().unwrap_or("/")
+}
+
+/// Path of a file below `wp-content/uploads`, the key used to rewrite links
+fn uploads_key(url: &str) -> Option<String> {
+    UPLOADS_URL
+        .captures(url)
+        .map(|caps| percent_decode(&caps[1]))
+}
+
+/// Point links to WordPress uploads at the imported media; resized copies
+/// (`photo-300x200.jpg`) point at the original
+pub fn rewrite_media_links(html: &str, uploads: &HashMap<String, String>) -> String {
+    UPLOADS_URL
+        .replace_all(html, |caps: &Captures| {
+            let key = percent_decode(&caps[1]);
+            uploads
+                .get(&key)
+                .or_else(|| uploads.get(SIZE_SUFFIX.replace(&key, "$1").as_ref()))
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Estimated reading time at 200 words per minute
+fn reading_time_minutes(html: &str) -> i16 {
+    let words = HTML_TAG.replace_all(html, " ").split_whitespace().count();
+    words.div_ceil(200).clamp(1, 999) as i16
+}
+
+/// Cut a string to at most `max` characters
+fn truncate(value: &str, max: usize) -> String {
+    value.chars().take(max).collect()
+}
+
+/// Order records so parents come before their children; cycles are cut
+fn parents_first<T, K>(
+    records: Vec<&T>,
+    key: impl Fn(&T) -> K,
+    parent: impl Fn(&T) -> Option<K>,
+) -> Vec<&T>
+where
+    K: Eq + Hash + Clone,
+{
+    let by_key: HashMap<K, &T> = records.iter().map(|r| (key(r), *r)).collect();
+    let depth = |record: &T| {
+        let mut seen = HashSet::new();
+        let mut current = record;
+        while let Some(next) = parent(current).and_then(|p| by_key.get(&p).copied()) {
+            if !seen.insert(key(next)) {
+                break;
+            }
+            current = next;
+        }
+        seen.len()
+    };
+    let mut ordered = records.clone();
+    ordered.sort_by_cached_key(|r| depth(r));
+    ordered
+}
+
+/// Whether an address is reachable on the public internet. Downloads from
+/// loopback, private, link-local and other special-purpose ranges are refused,
+/// so an export file cannot make the server fetch from its own network.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)) // shared address space
+                || (a == 192 && b == 0 && c == 0) // IETF protocol assignments
+                || (a == 198 && (18..20).contains(&b)) // benchmarking
+                || a >= 240) // reserved
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00 // unique local
+                || (first & 0xffc0) == 0xfe80 // link-local
+                || (first == 0x2001 && ip.segments()[1] == 0x0db8)) // documentation
+        }
+    }
+}
+
+/// Resolve the host of a download URL, refusing hosts with any non-public address
+async fn public_addrs(url: &reqwest::Url) -> Result<Vec<SocketAddr>, String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("not an http(s) URL".to_string());
+    }
+    let host = url.host_str().ok_or("URL has no host")?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|e| format!("cannot resolve {host}: {e}"))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("cannot resolve {host}"));
+    }
+    if addrs.iter().any(|a| !is_public_ip(a.ip())) {
+        return Err(format!("{host} is not a public address"));
+    }
+    Ok(addrs)
+}
+
+/// Where attachment files are read from
+enum AttachmentSource {
+    /// Local copy of the source site; URL paths resolve inside it
+    Directory(PathBuf),
+    /// The source site itself, over public HTTP(S)
+    Http,
+}
+
+impl AttachmentSource {
+    fn new(state: &AppState) -> Self {
+        match state.settings.storage.import_source_dir.as_deref() {
+            Some(dir) if !dir.is_empty() => Self::Directory(PathBuf::from(dir)),
+            _ => Self::Http,
+        }
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Directory(dir) => {
+                let mut file = dir.clone();
+                for segment in url_path(url).split('/').filter(|s| !s.is_empty()) {
+                    let segment = percent_decode(segment);
+                    if segment == "." || segment == ".." || segment.contains(['/', '\\']) {
+                        return Err("invalid path".to_string());
+                    }
+                    file.push(segment);
+                }
+                tokio::fs::read(&file).await.map_err(|_| {
+                    format!("{} not found in the import source directory", url_path(url))
+                })
+            }
+            Self::Http => {
+                let mut url = reqwest::Url::parse(url).map_err(|_| "invalid URL".to_string())?;
+                for _ in 0..=MAX_REDIRECTS {
+                    // Connect only to the checked addresses, and check every redirect hop
+                    let addrs = public_addrs(&url).await?;
+                    let client = reqwest::Client::builder()
+                        .timeout(Duration::from_secs(60))
+                        .redirect(reqwest::redirect::Policy::none())
+                        .resolve_to_addrs(url.host_str().unwrap_or_default(), &addrs)
+                        .build()
+                        .map_err(|e| format!("download failed: {e}"))?;
+                    let response = client
+                        .get(url.clone())
+                        .send()
+                        .await
+                        .map_err(|e| format!("download failed: {e}"))?;
+                    if response.status().is_redirection() {
+                        let location = response
+                            .headers()
+                            .get(reqwest::header::LOCATION)
+                            .and_then(|l| l.to_str().ok())
+                            .ok_or("redirect without a location")?;
+                        url = url
+                            .join(location)
+                            .map_err(|_| "invalid redirect location".to_string())?;
+                        continue;
+                    }
+                    return read_capped(response).await;
+                }
+                Err("too many redirects".to_string())
+            }
+        }
+    }
+}
+
+/// Read a response body, giving up once it exceeds `MAX_DOWNLOAD_BYTES`
+async fn read_capped(response: reqwest::Response) -> Result<Vec<u8>, String> {
+    let mut response = response
+        .error_for_status()
+        .map_err(|e| format!("download failed: {e}"))?;
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_DOWNLOAD_BYTES)
+    {
+        return Err("file is too large".to_string());
+    }
+    let mut data = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("download failed: {e}"))?
+    {
+        if (data.len() + chunk.len()) as u64 > MAX_DOWNLOAD_BYTES {
+            return Err("file is too large".to_string());
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+// ============================================
+// Import
+// ============================================
+
+struct Importer<'a> {
+    state: &'a AppState,
+    site_id: Uuid,
+    locale_id: Uuid,
+    user_id: Option<Uuid>,
+    report: WordPressImportReport,
+    /// Exported category slug -> category ID
+    category_ids: HashMap<String, Uuid>,
+    /// Exported tag slug -> tag ID
+    tag_ids: HashMap<String, Uuid>,
+    /// Attachment post ID -> media ID
+    media_ids: HashMap<i64, Uuid>,
+    /// Path below `wp-content/uploads` -> public URL of the imported media
+    uploads: HashMap<String, String>,
+}
+
+/// Import a parsed export into a site.
+///
+/// Items that cannot be imported are reported as warnings; database errors
+/// abort the import, which can then be run again.
+pub async fn import(
+    state: &AppState,
+    site_id: Uuid,
+    export: &WxrExport,
+    user_id: Option<Uuid>,
+) -> Result<WordPressImportReport, ApiError> {
+    let locale_id = SiteLocale::find_all_for_site(&state.db, site_id)
+        .await?
+        .into_iter()
+        .find(|l| l.is_default)
+        .map(|l| l.locale_id)
+        .ok_or_else(|| {
+            ApiError::BadRequest("The site needs a default locale before importing".to_string())
+        })?;
+
+    let mut importer = Importer {
+        state,
+        site_id,
+        locale_id,
+        user_id,
+        report: WordPressImportReport::default(),
+        category_ids: HashMap::new(),
+        tag_ids: HashMap::new(),
+        media_ids: HashMap::new(),
+        uploads: HashMap::new(),
+    };
+
+    importer.import_terms(export).await?;
+    importer.import_media(export).await?;
+    importer.import_blogs(export).await?;
+    importer.import_pages(export).await?;
+    for item in &export.items {
+        if !matches!(item.post_type.as_str(), "post" | "page" | "attachment") {
+            *importer
+                .report
+                .ignored
+                .entry(item.post_type.clone())
+                .or_default() += 1;
+        }
+    }
+
+    Ok(importer.report)
+}
+
+impl Importer<'_> {
+    fn warn(&mut self, warning: String) {
+        self.report.warnings.push(warning);
+    }
+
+    fn ignore(&mut self, item: &WxrItem) {
+        *self.report.ignored.entry(item.status.clone()).or_default() += 1;
+    }
+
+    /// Declared terms plus terms only referenced by items
+    fn collect_terms(export: &WxrExport, domain: &str, declared: &[WxrTerm]) -> Vec<WxrTerm> {
+        let mut terms = declared.to_vec();
+        let mut seen: HashSet<String> = terms.iter().map(|t| t.slug.clone()).collect();
+        for term in export.items.iter().flat_map(|i| &i.terms) {
+            if term.domain == domain && seen.insert(term.slug.clone()) {
+                terms.push(WxrTerm {
+                    slug: term.slug.clone(),
+                    name: term.name.clone(),
+                    ..WxrTerm::default()
+                });
+            }
+        }
+        terms
+    }
+
+    async fn import_terms(&mut self, export: &WxrExport) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+
+        let categories = Self::collect_terms(export, "category", &export.categories);
+        let ordered = parents_first(
+            categories.iter().collect(),
+            |t| t.slug.clone(),
+            |t| t.parent.clone(),
+        );
+        for term in ordered {
+            let slug = [slugify(&term.slug), slugify(&term.name)]
+                .into_iter()
+                .find(|s| !s.is_empty());
+            let Some(slug) = slug else {
+                self.report.categories.failed += 1;
+                self.warn(format!("Category '{}': no usable slug", term.name));
+                continue;
+            };
+            let parent_id = term
+                .parent
+                .as_ref()
+                .and_then(|p| self.category_ids.get(p))
+                .copied();
+
+            let id = match Category::find_by_slug(pool, parent_id, &slug).await? {
+                Some(existing) => {
+                    Category::add_to_site(pool, existing.id, self.site_id).await?;
+                    self.report.categories.existing += 1;
+                    existing.id
+                }
+                None => {
+                    let req = CreateCategoryRequest {
+                        parent_id,
+                        slug,
+                        is_global: false,
+                        site_id: Some(self.site_id),
+                    };
+                    self.report.categories.created += 1;
+                    Category::create(pool, &req).await?.id
+                }
+            };
+            let name = if term.name.is_empty() {
+                &term.slug
+            } else {
+                &term.name
+            };
+            Category::add_localization(pool, id, self.locale_id, name, term.description.as_deref())
+                .await?;
+            self.category_ids.insert(term.slug.clone(), id);
+        }
+
+        for term in Self::collect_terms(export, "post_tag", &export.tags) {
+            let slug = [slugify(&term.slug), slugify(&term.name)]
+                .into_iter()
+                .find(|s| !s.is_empty());
+            let Some(slug) = slug else {
+                self.report.tags.failed += 1;
+                self.warn(format!("Tag '{}': no usable slug", term.name));
+                continue;
+            };
+
+            let id = match Tag::find_by_slug(pool, &slug).await {
+                Ok(existing) => {
+                    Tag::add_to_site(pool, existing.id, self.site_id).await?;
+                    self.report.tags.existing += 1;
+                    existing.id
+                }
+                Err(ApiError::NotFound(_)) => {
+                    let req = CreateTagRequest {
+                        slug,
+                        is_global: false,
+                        site_id: Some(self.site_id),
+                    };
+                    self.report.tags.created += 1;
+                    Tag::create(pool, &req).await?.id
+                }
+                Err(e) => return Err(e),
+            };
+            let name = if term.name.is_empty() {
+                &term.slug
+            } else {
+                &term.name
+            };
+            Tag::add_localization(pool, id, self.locale_id, name).await?;
+            self.tag_ids.insert(term.slug.clone(), id);
+        }
+
+        Ok(())
+    }
+
+    async fn import_media(&mut self, export: &WxrExport) -> Result<(), ApiError> {
+        let source = AttachmentSource::new(self.state);
+
+        for item in export.items.iter().filter(|i| i.post_type == "attachment") {
+            let Some(url) = item.attachment_url.as_deref() else {
+                self.report.media.failed += 1;
+                self.warn(format!("Attachment {}: no attachment URL", item.post_id));
+                continue;
+            };
+
+            let bytes = match source.fetch(url).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    self.report.media.failed += 1;
+                    self.warn(format!("Attachment {} ({}): {}", item.post_id, url, e));
+                    continue;
+                }
+            };
+            let original_filename = url_path(url)
+                .rsplit('/')
+                .next()
+                .map(percent_decode)
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("attachment-{}", item.post_id));
+
+            let upload = MediaUpload {
+                bytes,
+                original_filename,
+                content_type: None,
+                site_ids: vec![self.site_id],
+                folder_id: None,
+                is_global: false,
+                uploaded_by: self.user_id,
+            };
+            let stored = match media_service::upload(self.state, upload).await {
+                Ok(stored) => stored,
+                Err(e @ (ApiError::BadRequest(_) | ApiError::Validation(_))) => {
+                    self.report.media.failed += 1;
+                    self.warn(format!("Attachment {} ({}): {}", item.post_id, url, e));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            if stored.created {
+                self.report.media.created += 1;
+                self.add_media_metadata(stored.media.id, item).await?;
+            } else {
+                MediaFile::add_to_site(&self.state.db, stored.media.id, self.site_id).await?;
+                self.report.media.existing += 1;
+            }
+            self.media_ids.insert(item.post_id, stored.media.id);
+            if let (Some(key), Some(public_url)) = (uploads_key(url), stored.media.public_url) {
+                self.uploads.insert(key, public_url);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Alt text, caption and title of an attachment in the default locale
+    async fn add_media_metadata(&mut self, media_id: Uuid, item: &WxrItem) -> Result<(), ApiError> {
+        let req = AddMediaMetadataRequest {
+            locale_id: self.locale_id,
+            alt_text: item
+                .meta("_wp_attachment_image_alt")
+                .map(|v| truncate(v, 500)),
+            caption: non_empty(truncate(&item.excerpt, 1000)),
+            title: non_empty(truncate(&item.title, 200)),
+        };
+        if req.alt_text.is_some() || req.caption.is_some() || req.title.is_some() {
+            MediaMetadata::create(&self.state.db, media_id, req).await?;
+        }
+        Ok(())
+    }
+
+    /// Localization in the default locale, checked like one sent to the API
+    fn localization(&self, item: &WxrItem) -> Result<CreateLocalizationRequest, String> {
+        let title = if item.title.is_empty() {
+            "Untitled".to_string()
+        } else {
+            truncate(&item.title, 500)
+        };
+        let req = CreateLocalizationRequest {
+            locale_id: self.locale_id,
+            title,
+            subtitle: None,
+            excerpt: non_empty(truncate(&item.excerpt, 2000)),
+            body: non_empty(rewrite_media_links(&item.content, &self.uploads)),
+            meta_title: item.meta("_yoast_wpseo_title").map(|v| truncate(v, 200)),
+            meta_description: item.meta("_yoast_wpseo_metadesc").map(|v| truncate(v, 500)),
+        };
+        req.validate().map_err(|e| e.to_string())?;
+        Ok(req)
+    }
+
+    /// Store the localization and taxonomy of newly created content
+    async fn finish_content(
+        &self,
+        content_id: Uuid,
+        localization: CreateLocalizationRequest,
+        item: &WxrItem,
+    ) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+        ContentLocalization::create(
+            pool,
+            content_id,
+            localization.locale_id,
+            &localization.title,
+            None,
+            localization.excerpt.as_deref(),
+            localization.body.as_deref(),
+            localization.meta_title.as_deref(),
+            localization.meta_description.as_deref(),
+        )
+        .await?;
+
+        let mut primary = true;
+        for term in &item.terms {
+            match term.domain.as_str() {
+                "category" => {
+                    if let Some(&id) = self.category_ids.get(&term.slug) {
+                        Category::assign_to_content(pool, content_id, id, primary).await?;
+                        primary = false;
+                    }
+                }
+                "post_tag" => {
+                    if let Some(&id) = self.tag_ids.get(&term.slug) {
+                        Tag::assign_to_content(pool, content_id, id).await?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Redirect the old permalink (with and without trailing slash) to the new path
+    async fn redirect(&mut self, item: &WxrItem, content_id: Uuid, new_path: &str) {
+        let Some(link) = item.link.as_deref() else {
+            return;
+        };
+        // Plain permalinks (`/?p=123`) carry the post in the query string
+        if link.contains('?') {
+            return;
+        }
+        let old_path = url_path(link);
+        let trimmed = old_path.trim_end_matches('/');
+        let mut sources = vec![old_path];
+        if !trimmed.is_empty() && trimmed != old_path {
+            sources.push(trimmed);
+        }
+        for source in sources {
+            if source == new_path || source == "/" {
+                continue;
+            }
+            redirect_service::redirect_moved(
+                &self.state.db,
+                &[self.site_id],
+                content_id,
+                source,
+                new_path,
+                REDIRECT_DESCRIPTION,
+            )
+            .await;
+            self.report.redirects += 1;
+        }
+    }
+
+    async fn import_blogs(&mut self, export: &WxrExport) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+        let blog_prefix = Blog::path_prefix(pool, self.site_id).await?;
+
+        for item in export.items.iter().filter(|i| i.post_type == "post") {
+            let Some((status, publish_start)) = item_status(item) else {
+                self.ignore(item);
+                continue;
+            };
+            let slug = item_slug(item);
+            let path = Blog::public_path(&blog_prefix, &slug);
+
+            match Blog::find_by_slug(pool, self.site_id, &slug).await {
+                Ok(existing) => {
+                    self.report.blogs.existing += 1;
+                    self.redirect(item, existing.content_id, &path).await;
+                    continue;
+                }
+                Err(ApiError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+
+            let author = item
+                .creator
+                .as_ref()
+                .map(|login| {
+                    export
+                        .authors
+                        .get(login)
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or(login)
+                })
+                .map(|name| truncate(name, 200))
+                .unwrap_or_else(|| "Unknown".to_string());
+            let req = CreateBlogRequest {
+                slug,
+                author,
+                published_date: item
+                    .post_date
+                    .map(|d| d.date())
+                    .unwrap_or_else(|| Utc::now().date_naive()),
+                reading_time_minutes: Some(reading_time_minutes(&item.content)),
+                cover_image_id: item
+                    .meta("_thumbnail_id")
+                    .and_then(|id| id.parse().ok())
+                    .and_then(|id: i64| self.media_ids.get(&id).copied()),
+                header_image_id: None,
+                is_featured: item.is_sticky,
+                allow_comments: item.comment_status.as_deref() != Some("closed"),
+                status,
+                publish_start,
+                publish_end: None,
+                site_ids: vec![self.site_id],
+                environment: None,
+            };
+            let checked = req
+                .validate()
+                .map_err(|e| e.to_string())
+                .and_then(|_| self.localization(item));
+            let localization = match checked {
+                Ok(localization) => localization,
+                Err(e) => {
+                    self.report.blogs.failed += 1;
+                    self.warn(format!("Post {} '{}': {}", item.post_id, item.title, e));
+                    continue;
+                }
+            };
+
+            let mut tx = pool.begin().await?;
+            let blog = Blog::create(&mut tx, req).await?;
+            tx.commit().await?;
+            self.finish_content(blog.content_id, localization, item)
+                .await?;
+            self.report.blogs.created += 1;
+            self.redirect(item, blog.content_id, &path).await;
+        }
+
+        Ok(())
+    }
+
+    async fn import_pages(&mut self, export: &WxrExport) -> Result<(), ApiError> {
+        let state = self.state;
+        let pool = &state.db;
+
+        let mut pages = Vec::new();
+        for item in export.items.iter().filter(|i| i.post_type == "page") {
+            match item_status(item) {
+                Some(status) => pages.push((item, status)),
+                None => self.ignore(item),
+            }
+        }
+        let pages = parents_first(
+            pages.iter().collect(),
+            |p| p.0.post_id,
+            |p| Some(p.0.parent_id),
+        );
+
+        // Page post ID -> (page ID, route)
+        let mut imported: HashMap<i64, (Uuid, String)> = HashMap::new();
+        for &&(item, (ref status, publish_start)) in &pages {
+            let status = status.clone();
+            let parent = imported.get(&item.parent_id).cloned();
+            if item.parent_id != 0 && parent.is_none() {
+                self.warn(format!(
+                    "Page {} '{}': parent {} was not imported; placed at the top level",
+                    item.post_id, item.title, item.parent_id
+                ));
+            }
+            let slug = item_slug(item);
+            let route = match &parent {
+                Some((_, parent_route)) => format!("{}/{}", parent_route, slug),
+                None => format!("/{}", slug),
+            };
+
+            match Page::find_by_route(pool, self.site_id, &route).await {
+                Ok(existing) => {
+                    self.report.pages.existing += 1;
+                    imported.insert(item.post_id, (existing.id, route.clone()));
+                    self.redirect(item, existing.content_id, &route).await;
+                    continue;
+                }
+                Err(ApiError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+
+            let req = CreatePageRequest {
+                route: route.clone(),
+                slug,
+                page_type: Default::default(),
+                template: None,
+                is_in_navigation: false,
+                navigation_order: (item.menu_order > 0).then(|| item.menu_order.min(9999) as i16),
+                parent_page_id: parent.map(|(id, _)| id),
+                status,
+                publish_start,
+                publish_end: None,
+                site_ids: vec![self.site_id],
+                environment: None,
+            };
+            let checked = req
+                .validate()
+                .map_err(|e| e.to_string())
+                .and_then(|_| self.localization(item));
+            let localization = match checked {
+                Ok(localization) => localization,
+                Err(e) => {
+                    self.report.pages.failed += 1;
+                    self.warn(format!("Page {} '{}': {}", item.post_id, item.title, e));
+                    continue;
+                }
+            };
+
+            let mut tx = pool.begin().await?;
+            let page = Page::create(&mut tx, req).await?;
+            tx.commit().await?;
+            self.finish_content(page.content_id, localization, item)
+                .await?;
+            self.report.pages.created += 1;
+            imported.insert(item.post_id, (page.id, route.clone()));
+            self.redirect(item, page.content_id, &route).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WXR: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>Old Blog</title>
+    <link>https://old.example.com</link>
+    <wp:wxr_version>1.2</wp:wxr_version>
+    <wp:author>
+        <wp:author_id>1</wp:author_id>
+        <wp:author_login><![CDATA[jdoe]]></wp:author_login>
+        <wp:author_display_name><![CDATA[Jane Doe]]></wp:author_display_name>
+    </wp:author>
+    <wp:category>
+        <wp:term_id>2</wp:term_id>
+        <wp:category_nicename><![CDATA[news]]></wp:category_nicename>
+        <wp:category_parent><![CDATA[]]></wp:category_parent>
+        <wp:cat_name><![CDATA[News &amp; Events]]></wp:cat_name>
+    </wp:category>
+    <wp:tag>
+        <wp:tag_slug><![CDATA[rust]]></wp:tag_slug>
+        <wp:tag_name><![CDATA[Rust]]></wp:tag_name>
+    </wp:tag>
+    <item>
+        <title>Hello &amp;amp; welcome &#8211; again</title>
+        <link>https://old.example.com/2024/05/hello-world/</link>
+        <dc:creator><![CDATA[jdoe]]></dc:creator>
+        <content:encoded><![CDATA[<p>Hi <img src="https://old.example.com/wp-content/uploads/2024/05/cat-300x200.jpg"></p>]]></content:encoded>
+        <excerpt:encoded><![CDATA[]]></excerpt:encoded>
+        <wp:post_id>10</wp:post_id>
+        <wp:post_date><![CDATA[2024-05-01 09:30:00]]></wp:post_date>
+        <wp:post_name><![CDATA[hello-world]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_parent>0</wp:post_parent>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <category domain="category" nicename="news"><![CDATA[News & Events]]></category>
+        <category domain="post_tag" nicename="rust"><![CDATA[Rust]]></category>
+        <wp:postmeta>
+            <wp:meta_key><![CDATA[_thumbnail_id]]></wp:meta_key>
+            <wp:meta_value><![CDATA[11]]></wp:meta_value>
+        </wp:postmeta>
+        <wp:comment>
+            <wp:comment_id>1</wp:comment_id>
+            <wp:comment_content><![CDATA[Nice]]></wp:comment_content>
+        </wp:comment>
+    </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse_wxr() {
+        let export = parse_wxr(WXR).unwrap();
+        assert_eq!(export.authors.get("jdoe").unwrap(), "Jane Doe");
+        assert_eq!(export.categories.len(), 1);
+        assert_eq!(export.categories[0].name, "News & Events");
+        assert_eq!(export.categories[0].parent, None);
+        assert_eq!(export.tags[0].slug, "rust");
+
+        assert_eq!(export.items.len(), 1);
+        let item = &export.items[0];
+        assert_eq!(item.post_id, 10);
+        assert_eq!(item.post_type, "post");
+        assert_eq!(item.title, "Hello & welcome \u{2013} again");
+        assert_eq!(item.creator.as_deref(), Some("jdoe"));
+        assert_eq!(item.slug, "hello-world");
+        assert_eq!(item.status, "publish");
+        assert_eq!(
+            item.post_date.unwrap().to_string(),
+            "2024-05-01 09:30:00".to_string()
+        );
+        assert_eq!(item.terms.len(), 2);
+        assert_eq!(item.terms[1].domain, "post_tag");
+        assert_eq!(item.meta("_thumbnail_id"), Some("11"));
+        assert!(item.content.contains("cat-300x200.jpg"));
+    }
+
+    #[test]
+    fn test_parse_rejects_other_feeds() {
+        let rss = r#"<rss version="2.0"><channel><title>x</title></channel></rss>"#;
+        assert!(matches!(parse_wxr(rss), Err(ApiError::BadRequest(_))));
+        let broken = "<rss><channel><wp:wxr_version>1.2</channel></rss>";
+        assert!(matches!(parse_wxr(broken), Err(ApiError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("hello-world"), "hello-world");
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("caf%c3%a9-au-lait"), "caf-au-lait");
+        assert_eq!(slugify("%e6%97%a5%e6%9c%ac"), "");
+        assert_eq!(slugify(&"a".repeat(150)).len(), 100);
+    }
+
+    #[test]
+    fn test_url_path() {
+        assert_eq!(
+            url_path("https://old.example.com/2024/05/hello/"),
+            "/2024/05/hello/"
+        );
+        assert_eq!(url_path("https://old.example.com"), "/");
+        assert_eq!(url_path("/about/#team"), "/about/");
+    }
+
+    #[test]
+    fn test_rewrite_media_links() {
+        let mut uploads = HashMap::new();
+        uploads.insert(
+            "2024/05/cat.jpg".to_string(),
+            "/uploads/s/cat.jpg".to_string(),
+        );
+        let html = r#"<img src="https://old.example.com/wp-content/uploads/2024/05/cat-300x200.jpg"> <a href="http://old.example.com/wp-content/uploads/2024/05/cat.jpg">x</a> <img src="/wp-content/uploads/2024/05/dog.jpg">"#;
+        assert_eq!(
+            rewrite_media_links(html, &uploads),
+            r#"<img src="/uploads/s/cat.jpg"> <a href="/uploads/s/cat.jpg">x</a> <img src="/wp-content/uploads/2024/05/dog.jpg">"#
+        );
+    }
+
+    #[test]
+    fn test_parents_first() {
+        let items: Vec<(i64, i64)> = vec![(3, 2), (2, 1), (1, 0), (4, 0), (5, 6), (6, 5)];
+        let ordered = parents_first(items.iter().collect(), |i| i.0, |i| Some(i.1));
+        let position = |id| ordered.iter().position(|i| i.0 == id).unwrap();
+        assert!(position(1) < position(2));
+        assert!(position(2) < position(3));
+        assert_eq!(ordered.len(), 6);
+    }
+
+    #[test]
+    fn test_item_status_and_reading_time() {
+        let mut item = WxrItem {
+            status: "future".to_string(),
+            post_date_gmt: parse_date("2030-01-01 08:00:00"),
+            ..WxrItem::default()
+        };
+        let (status, start) = item_status(&item).unwrap();
+        assert_eq!(status, ContentStatus::Scheduled);
+        assert_eq!(start.unwrap().to_rfc3339(), "2030-01-01T08:00:00+00:00");
+        item.status = "trash".to_string();
+        assert!(item_status(&item).is_none());
+
+        assert_eq!(reading_time_minutes("<p>short</p>"), 1);
+        assert_eq!(reading_time_minutes(&"word ".repeat(401)), 3);
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "2606:2800:220:1::1"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.5",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+}
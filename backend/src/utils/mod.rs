@@ -3,4 +3,5 @@
 pub mod pagination;
 pub mod query_params;
 pub mod response;
+pub mod signing;
 pub mod validation;
@@ -1,5 +1,10 @@
 //! Response utilities
 
+use std::io::Cursor;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
 use serde::Serialize;
 
 /// Standard API response wrapper
@@ -34,3 +39,46 @@ impl Default for EmptyResponse {
         Self { success: true }
     }
 }
+
+/// File download (e.g. a CSV export) served as an attachment
+#[derive(Debug)]
+pub struct FileDownload {
+    pub body: Vec<u8>,
+    pub content_type: ContentType,
+    pub filename: String,
+}
+
+impl<'r> Responder<'r, 'static> for FileDownload {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .status(Status::Ok)
+            .header(self.content_type)
+            .header(Header::new(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", self.filename),
+            ))
+            .sized_body(self.body.len(), Cursor::new(self.body))
+            .ok()
+    }
+}
+
+/// Wraps a responder and sets its `ETag` header
+#[derive(Debug)]
+pub struct WithETag<R> {
+    pub inner: R,
+    pub etag: String,
+}
+
+impl<R> WithETag<R> {
+    pub fn new(inner: R, etag: String) -> Self {
+        Self { inner, etag }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for WithETag<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        Response::build_from(self.inner.respond_to(req)?)
+            .header(Header::new("ETag", self.etag))
+            .ok()
+    }
+}
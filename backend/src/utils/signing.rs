@@ -0,0 +1,54 @@
+//! Helpers shared by the services that sign URLs and tokens
+
+use uuid::Uuid;
+
+lazy_static::lazy_static! {
+    /// Used in development when a signing secret is unset; signatures do not
+    /// survive a restart.
+    static ref FALLBACK_SECRET: String = {
+        tracing::warn!("A signing secret is not set; signed URLs and tokens are invalidated on restart");
+        format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+    };
+}
+
+/// Return `configured`, or a random per-process secret when it is empty
+///
+/// Outside development, startup fails when a signing secret is missing (see
+/// [`crate::config::SecurityConfig::missing_signing_secrets`]), so the fallback
+/// is only ever used locally.
+pub fn secret_or_fallback(configured: &str) -> &str {
+    if configured.is_empty() {
+        FALLBACK_SECRET.as_str()
+    } else {
+        configured
+    }
+}
+
+/// Compare two strings without short-circuiting on the first difference
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_or_fallback() {
+        assert_eq!(secret_or_fallback("configured"), "configured");
+        let fallback = secret_or_fallback("");
+        assert_eq!(fallback.len(), 64);
+        assert_eq!(secret_or_fallback(""), fallback);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("abc", "abc"));
+        assert!(!constant_time_eq("abc", "abd"));
+        assert!(!constant_time_eq("abc", "abcd"));
+    }
+}
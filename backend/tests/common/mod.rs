@@ -7,6 +7,7 @@
 
 use std::sync::Arc;
 
+use rocket::http::{ContentType, Header, Status};
 use rocket::local::asynchronous::Client;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
@@ -70,6 +71,8 @@ pub fn test_app_state(pool: PgPool, temp_dir: &TempDir) -> AppState {
             provider: "local".to_string(),
             local_upload_dir: upload_dir.clone(),
             local_base_url: "/uploads".to_string(),
+            // Importers read source-site files from here instead of downloading them
+            import_source_dir: Some(format!("{}/import-source", upload_dir)),
             ..StorageConfig::default()
         },
         ..Settings::default()
@@ -165,6 +168,45 @@ pub async fn create_test_api_key(
     result.plaintext_key
 }
 
+/// Create a draft blog through the API. Returns the created blog JSON.
+pub async fn create_test_blog(
+    ctx: &TestContext,
+    key: &str,
+    site_id: Uuid,
+    slug: &str,
+) -> serde_json::Value {
+    create_test_blog_with_status(ctx, key, site_id, slug, "Draft").await
+}
+
+/// Create a blog with the given status through the API. Returns the created blog JSON.
+pub async fn create_test_blog_with_status(
+    ctx: &TestContext,
+    key: &str,
+    site_id: Uuid,
+    slug: &str,
+    status: &str,
+) -> serde_json::Value {
+    let response = ctx
+        .client
+        .post("/api/v1/blogs")
+        .header(Header::new("X-API-Key", key.to_string()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "slug": slug,
+                "author": "Test Author",
+                "published_date": "2025-01-15",
+                "site_ids": [site_id],
+                "status": status
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created, "create blog {slug}");
+    response.into_json().await.expect("valid JSON")
+}
+
 /// Insert a test notification directly via the model layer.
 pub async fn create_test_notification(
     pool: &PgPool,
@@ -222,13 +264,18 @@ pub async fn cleanup_test_data(pool: &PgPool) {
             legal_group_localizations, legal_groups,
             legal_document_localizations, legal_documents,
             page_section_localizations, page_sections, pages,
+            global_section_localizations, global_sections, page_templates,
+            environment_promotions, site_transfer_jobs,
+            content_release_items, content_releases,
             cv_entry_skills, cv_entry_localizations, cv_entries,
             skill_localizations, skill_sites, skills,
             blog_documents, document_localizations, documents, document_folders,
-            blog_photos, blog_links, blog_attachments, blogs,
+            form_submissions, forms,
+            comments, blog_photos, blog_links, blog_attachments, blogs,
+            preview_tokens, content_edit_locks,
             content_blocks, content_localizations, content_versions,
             content_sites, contents,
-            media_metadata, media_variants, media_sites, media_files,
+            media_metadata, media_transforms, media_variants, media_sites, media_files,
             media_folders,
             api_key_ip_rules, api_key_usage_daily, api_key_usage, api_keys,
             system_admins, site_memberships,
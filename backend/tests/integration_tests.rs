@@ -19,11 +19,11 @@
 mod common;
 
 use common::{
-    cleanup_test_data, create_test_api_key, create_test_notification, create_test_site,
-    create_test_webhook, test_context,
+    cleanup_test_data, create_test_api_key, create_test_blog, create_test_blog_with_status,
+    create_test_notification, create_test_site, create_test_webhook, test_context,
 };
 use openyapper::models::api_key::ApiKeyPermission;
-use rocket::http::{Header, Status};
+use rocket::http::{ContentType, Header, Status};
 use serial_test::serial;
 
 // =========================================================================
@@ -1173,3 +1173,4976 @@ async fn test_site_members_read_key_cannot_add() {
         "Read key should not be able to add site members"
     );
 }
+
+// =========================================================================
+// 16. Publishing scheduler
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_scheduler_publishes_and_expires_content() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    // A blog published with a future publish_start becomes Scheduled
+    let create_body = serde_json::json!({
+        "slug": "scheduled-blog",
+        "author": "Test Author",
+        "published_date": "2025-01-15",
+        "site_ids": [site_id],
+        "status": "Published",
+        "publish_start": "2099-01-01T00:00:00Z"
+    });
+    let response = ctx
+        .client
+        .post("/api/v1/blogs")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(create_body.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let blog: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(blog["status"], "Scheduled");
+    let scheduled_id: uuid::Uuid = blog["id"].as_str().unwrap().parse().unwrap();
+
+    // A published blog whose window is about to end
+    let blog =
+        create_test_blog_with_status(&ctx, &write_key, site_id, "expiring-blog", "Published").await;
+    let expiring_id: uuid::Uuid = blog["id"].as_str().unwrap().parse().unwrap();
+
+    // Move both windows into the past
+    sqlx::query(
+        "UPDATE contents SET publish_start = NOW() - INTERVAL '1 minute' \
+         WHERE id = (SELECT content_id FROM blogs WHERE id = $1)",
+    )
+    .bind(scheduled_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "UPDATE contents SET publish_start = NOW() - INTERVAL '2 days', \
+         publish_end = NOW() - INTERVAL '1 minute' \
+         WHERE id = (SELECT content_id FROM blogs WHERE id = $1)",
+    )
+    .bind(expiring_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let report = openyapper::services::scheduler_service::run_tick(&ctx.pool)
+        .await
+        .expect("scheduler tick");
+    assert!(report.lock_acquired);
+    assert_eq!(report.published.len(), 1);
+    assert_eq!(report.published[0].entity_id, scheduled_id);
+    assert_eq!(report.published[0].entity_type, "blog");
+    assert_eq!(report.unpublished.len(), 1);
+    assert_eq!(report.unpublished[0].entity_id, expiring_id);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}", scheduled_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    let fetched: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(fetched["status"], "Published");
+    assert!(!fetched["published_at"].is_null());
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}", expiring_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    let fetched: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(fetched["status"], "Archived");
+
+    let actions: Vec<(String,)> = sqlx::query_as(
+        "SELECT action::text FROM audit_logs WHERE entity_id = ANY($1) AND user_id IS NULL ORDER BY action",
+    )
+    .bind(vec![scheduled_id, expiring_id])
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    let actions: Vec<String> = actions.into_iter().map(|(a,)| a).collect();
+    assert_eq!(actions, vec!["publish", "unpublish"]);
+
+    // A second tick has nothing left to do
+    let report = openyapper::services::scheduler_service::run_tick(&ctx.pool)
+        .await
+        .expect("scheduler tick");
+    assert!(report.published.is_empty());
+    assert!(report.unpublished.is_empty());
+}
+
+// =========================================================================
+// 17. Content versions
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_content_versions_list_diff_restore() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    let create_body = serde_json::json!({
+        "slug": "versioned-blog",
+        "author": "First Author",
+        "published_date": "2025-01-15",
+        "site_ids": [site_id]
+    });
+    let response = ctx
+        .client
+        .post("/api/v1/blogs")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(create_body.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let blog: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let blog_id = blog["id"].as_str().unwrap().to_string();
+    let content_id = blog["content_id"].as_str().unwrap().to_string();
+
+    // Version 2: author changed
+    let response = ctx
+        .client
+        .put(format!("/api/v1/blogs/{}", blog_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(serde_json::json!({ "author": "Second Author" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Version 3: English localization added
+    let (locale_id,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM locales WHERE code = 'en'")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    let response = ctx
+        .client
+        .post(format!("/api/v1/blogs/{}/localizations", blog_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(serde_json::json!({ "locale_id": locale_id, "title": "Hello" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+
+    // --- List ---
+    let response = ctx
+        .client
+        .get(format!("/api/v1/content/{}/versions", content_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let list: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let versions = list["data"].as_array().expect("data array");
+    let numbers: Vec<i64> = versions
+        .iter()
+        .map(|v| v["version_number"].as_i64().unwrap())
+        .collect();
+    assert_eq!(numbers, vec![3, 2, 1]);
+
+    // --- Diff ---
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/content/{}/versions/diff?from=1&to=3",
+            content_id
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let diff: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let changes = diff["changes"].as_array().expect("changes array");
+    let author = changes
+        .iter()
+        .find(|c| c["field"] == "entity.author")
+        .expect("author change");
+    assert_eq!(author["old_value"], "First Author");
+    assert_eq!(author["new_value"], "Second Author");
+    assert!(changes
+        .iter()
+        .any(|c| c["field"] == format!("localizations.{}.title", locale_id)));
+
+    // --- Restore version 1 ---
+    let response = ctx
+        .client
+        .post(format!("/api/v1/content/{}/versions/1/restore", content_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let restored: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(restored["version_number"], 4);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}/detail", blog_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    let detail: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(detail["author"], "First Author");
+    assert!(detail["localizations"].as_array().unwrap().is_empty());
+
+    let (current_version,): (i32,) =
+        sqlx::query_as("SELECT current_version FROM contents WHERE id = $1::uuid")
+            .bind(&content_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(current_version, 4);
+
+    // Unknown version → 404
+    let response = ctx
+        .client
+        .get(format!("/api/v1/content/{}/versions/99", content_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+// =========================================================================
+// 18. Content blocks
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_content_blocks_crud_reorder_and_detail() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    let blog = create_test_blog(&ctx, &write_key, site_id, "block-blog").await;
+    let blog_id = blog["id"].as_str().unwrap().to_string();
+
+    let (locale_id,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM locales WHERE code = 'en'")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    let response = ctx
+        .client
+        .post(format!("/api/v1/blogs/{}/localizations", blog_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(serde_json::json!({ "locale_id": locale_id, "title": "Hello" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let loc: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let loc_id = loc["id"].as_str().unwrap().to_string();
+
+    // --- Create: appended paragraph, then a heading inserted before it ---
+    let response = ctx
+        .client
+        .post(format!("/api/v1/localizations/{}/blocks", loc_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(
+            serde_json::json!({ "block_type": "Paragraph", "block_data": { "text": "Body" } })
+                .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let paragraph: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(paragraph["block_order"], 0);
+    let paragraph_id = paragraph["id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/localizations/{}/blocks", loc_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(
+            serde_json::json!({
+                "block_type": "Heading",
+                "block_data": { "text": "Title", "level": 2 },
+                "position": 0
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let heading: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let heading_id = heading["id"].as_str().unwrap().to_string();
+
+    // --- Schema validation ---
+    let response = ctx
+        .client
+        .post(format!("/api/v1/localizations/{}/blocks", loc_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(
+            serde_json::json!({ "block_type": "Heading", "block_data": { "text": "x", "level": 9 } })
+                .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = ctx
+        .client
+        .put(format!("/api/v1/blocks/{}", paragraph_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(serde_json::json!({ "block_type": "Divider" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(
+        response.status(),
+        Status::BadRequest,
+        "Paragraph data is not valid divider data"
+    );
+
+    // --- Update ---
+    let response = ctx
+        .client
+        .put(format!("/api/v1/blocks/{}", paragraph_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(serde_json::json!({ "block_data": { "text": "Edited body" } }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // --- List reflects insertion order ---
+    let response = ctx
+        .client
+        .get(format!("/api/v1/localizations/{}/blocks", loc_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let blocks: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let ids: Vec<&str> = blocks
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![heading_id.as_str(), paragraph_id.as_str()]);
+
+    // --- Reorder ---
+    let response = ctx
+        .client
+        .post(format!("/api/v1/localizations/{}/blocks/reorder", loc_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(
+            serde_json::json!({ "items": [
+                { "id": paragraph_id, "block_order": 0 },
+                { "id": heading_id, "block_order": 1 }
+            ] })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    // --- Blocks inline in blog detail ---
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}/detail", blog_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let detail: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let inline = detail["localizations"][0]["blocks"].as_array().unwrap();
+    assert_eq!(inline.len(), 2);
+    assert_eq!(inline[0]["block_type"], "Paragraph");
+    assert_eq!(inline[0]["block_data"]["text"], "Edited body");
+    assert_eq!(inline[1]["block_type"], "Heading");
+
+    // --- Delete ---
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/blocks/{}", heading_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blocks/{}", heading_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Schemas are published for every block type
+    let response = ctx
+        .client
+        .get("/api/v1/blocks/schemas")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let schemas: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(schemas.as_array().unwrap().len(), 9);
+}
+
+// =========================================================================
+// 19. Full-text search
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_site_search_ranks_highlights_and_filters() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+
+    let locale = |code: &'static str| {
+        let pool = ctx.pool.clone();
+        async move {
+            let (id,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM locales WHERE code = $1")
+                .bind(code)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            id
+        }
+    };
+    let en = locale("en").await;
+    let de = locale("de").await;
+
+    // Published blog (en + de) and a draft blog (en)
+    let mut blog_ids = Vec::new();
+    for (slug, status) in [("published-post", "Published"), ("draft-post", "Draft")] {
+        let blog = create_test_blog_with_status(&ctx, &write_key, site_id, slug, status).await;
+        blog_ids.push(blog["id"].as_str().unwrap().to_string());
+    }
+
+    let localizations = [
+        (
+            &blog_ids[0],
+            en,
+            "Running a marathon",
+            "Training plans for runners who want to finish strong.",
+        ),
+        (
+            &blog_ids[0],
+            de,
+            "Marathon laufen",
+            "Viele Läufer trainieren in den Bergen.",
+        ),
+        (
+            &blog_ids[1],
+            en,
+            "Draft about <b>runs</b>",
+            "Unpublished thoughts on running.",
+        ),
+    ];
+    for (blog_id, locale_id, title, body) in localizations {
+        let response = ctx
+            .client
+            .post(format!("/api/v1/blogs/{}/localizations", blog_id))
+            .header(Header::new("X-API-Key", write_key.clone()))
+            .header(Header::new("Content-Type", "application/json"))
+            .body(
+                serde_json::json!({ "locale_id": locale_id, "title": title, "body": body })
+                    .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Created);
+    }
+
+    // A document in the same site
+    let (document_id,): (uuid::Uuid,) = sqlx::query_as(
+        "INSERT INTO documents (site_id, url, document_type) \
+         VALUES ($1, 'https://example.com/plan.pdf', 'pdf') RETURNING id",
+    )
+    .bind(site_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_localizations (document_id, locale_id, name, description) \
+         VALUES ($1, $2, 'Marathon training plan', 'Printable weekly running schedule')",
+    )
+    .bind(document_id)
+    .bind(en)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let search = |key: String, query: String| {
+        let client = &ctx.client;
+        async move {
+            let response = client
+                .get(format!("/api/v1/sites/{}/search?{}", site_id, query))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            let status = response.status();
+            let body: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, body)
+        }
+    };
+
+    // English stemming: "run" matches "Running", "runners", "runs"; the draft is visible to writers
+    let (status, body) = search(write_key.clone(), "q=run&locale=en".to_string()).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["meta"]["total_items"], 3);
+    let results = body["data"].as_array().unwrap();
+    // Title matches outrank the document, which only matches in its description
+    assert_eq!(results[0]["entity_type"], "blog");
+    assert_eq!(results[1]["entity_type"], "blog");
+    assert_eq!(results[2]["entity_type"], "document");
+    assert!(results
+        .iter()
+        .any(|r| r["title_highlight"] == "<mark>Running</mark> a marathon"));
+    assert!(results[2]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>running</mark>"));
+    // Markup in the source text is escaped; only the highlight tags are HTML
+    assert!(results
+        .iter()
+        .any(|r| r["title_highlight"] == "Draft about &lt;b&gt;<mark>runs</mark>&lt;/b&gt;"));
+
+    // Read-only keys only see published content
+    let (status, body) = search(read_key.clone(), "q=run&locale=en".to_string()).await;
+    assert_eq!(status, Status::Ok);
+    let results = body["data"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results
+        .iter()
+        .all(|r| r["status"].is_null() || r["status"] == "Published"));
+
+    // German stemming: "Läufer" in the body
+    let (_, body) = search(read_key.clone(), "q=l%C3%A4ufer&locale=de".to_string()).await;
+    assert_eq!(body["meta"]["total_items"], 1);
+    assert_eq!(body["data"][0]["locale_code"], "de");
+    assert!(body["data"][0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>Läufer</mark>"));
+
+    // Type filter
+    let (_, body) = search(read_key.clone(), "q=marathon&types=document".to_string()).await;
+    assert_eq!(body["meta"]["total_items"], 1);
+    assert_eq!(body["data"][0]["entity_id"], document_id.to_string());
+
+    // Validation
+    let (status, _) = search(read_key.clone(), "q=".to_string()).await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = search(read_key.clone(), "q=run&types=media".to_string()).await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = search(read_key.clone(), "q=run&locale=xx".to_string()).await;
+    assert_eq!(status, Status::NotFound);
+
+    // Published content outside its publish window is hidden from read-only keys
+    sqlx::query(
+        "UPDATE contents SET publish_end = NOW() - INTERVAL '1 day' WHERE slug = 'published-post'",
+    )
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let (_, body) = search(read_key, "q=run&locale=en".to_string()).await;
+    assert_eq!(body["meta"]["total_items"], 1);
+    assert_eq!(body["data"][0]["entity_type"], "document");
+    let (_, body) = search(write_key, "q=run&locale=en".to_string()).await;
+    assert_eq!(body["meta"]["total_items"], 3);
+}
+
+// =========================================================================
+// 20. Preview tokens
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_preview_tokens_unlock_drafts_by_slug_and_route() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+
+    let (de,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM locales WHERE code = 'de'")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+
+    // A draft blog and a scheduled page
+    let blog = create_test_blog(&ctx, &write_key, site_id, "secret-draft").await;
+    let blog_content_id = blog["content_id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .post("/api/v1/pages")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body(
+            serde_json::json!({
+                "route": "/coming-soon",
+                "slug": "coming-soon",
+                "page_type": "Static",
+                "site_ids": [site_id],
+                "status": "Scheduled",
+                "publish_start": "2099-01-01T00:00:00Z"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let page: serde_json::Value = response.into_json().await.expect("valid JSON");
+    let page_content_id = page["content_id"].as_str().unwrap().to_string();
+
+    let fetch = |key: String, path: String| {
+        let client = &ctx.client;
+        async move {
+            client
+                .get(format!("/api/v1/sites/{}/{}", site_id, path))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await
+                .status()
+        }
+    };
+    let mint = |content_id: String, body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .post(format!("/api/v1/content/{}/preview-tokens", content_id))
+                .header(Header::new("X-API-Key", key))
+                .header(Header::new("Content-Type", "application/json"))
+                .body(body.to_string())
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Created);
+            let token: serde_json::Value = response.into_json().await.expect("valid JSON");
+            token
+        }
+    };
+
+    // Without a token, read-only keys cannot see unpublished content; writers can
+    let blog_path = "blogs/by-slug/secret-draft".to_string();
+    let page_path = "pages/by-route/coming-soon".to_string();
+    assert_eq!(
+        fetch(read_key.clone(), blog_path.clone()).await,
+        Status::NotFound
+    );
+    assert_eq!(
+        fetch(read_key.clone(), page_path.clone()).await,
+        Status::NotFound
+    );
+    assert_eq!(
+        fetch(write_key.clone(), blog_path.clone()).await,
+        Status::Ok
+    );
+
+    // Read-only keys cannot mint tokens
+    let response = ctx
+        .client
+        .post(format!(
+            "/api/v1/content/{}/preview-tokens",
+            blog_content_id
+        ))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .header(Header::new("Content-Type", "application/json"))
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let blog_token = mint(blog_content_id.clone(), serde_json::json!({})).await;
+    let raw = blog_token["token"].as_str().unwrap().to_string();
+    assert!(raw.starts_with("oyp_"));
+    assert_eq!(blog_token["is_active"], true);
+
+    assert_eq!(
+        fetch(
+            read_key.clone(),
+            format!("{}?preview_token={}", blog_path, raw)
+        )
+        .await,
+        Status::Ok
+    );
+    // A token for one content item does not unlock another
+    assert_eq!(
+        fetch(
+            read_key.clone(),
+            format!("{}?preview_token={}", page_path, raw)
+        )
+        .await,
+        Status::Forbidden
+    );
+    // Tampered signatures are rejected
+    let last = if raw.ends_with('0') { '1' } else { '0' };
+    let tampered = format!("{}{}", &raw[..raw.len() - 1], last);
+    assert_eq!(
+        fetch(
+            read_key.clone(),
+            format!("{}?preview_token={}", blog_path, tampered)
+        )
+        .await,
+        Status::Forbidden
+    );
+
+    // Locale-scoped token for the page
+    let page_token = mint(
+        page_content_id.clone(),
+        serde_json::json!({ "locale_id": de, "expires_in_hours": 1 }),
+    )
+    .await;
+    let page_raw = page_token["token"].as_str().unwrap().to_string();
+    assert_eq!(page_token["locale_id"], de.to_string());
+    assert_eq!(
+        fetch(
+            read_key.clone(),
+            format!("{}?preview_token={}", page_path, page_raw)
+        )
+        .await,
+        Status::Forbidden
+    );
+    assert_eq!(
+        fetch(
+            read_key.clone(),
+            format!("{}?preview_token={}&locale=en", page_path, page_raw)
+        )
+        .await,
+        Status::Forbidden
+    );
+    assert_eq!(
+        fetch(
+            read_key.clone(),
+            format!("{}?preview_token={}&locale=de", page_path, page_raw)
+        )
+        .await,
+        Status::Ok
+    );
+
+    // Listing shows usage without exposing the token
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/content/{}/preview-tokens",
+            blog_content_id
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let list: serde_json::Value = response.into_json().await.expect("valid JSON");
+    assert_eq!(list["meta"]["total_items"], 1);
+    assert_eq!(list["data"][0]["use_count"], 1);
+    assert!(list["data"][0]["last_used_at"].is_string());
+    assert!(list["data"][0].get("token").is_none());
+
+    // Every use is audited
+    let (uses,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM audit_logs WHERE action = 'read' AND metadata ? 'preview_token_id'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(uses, 2);
+
+    // Revoked tokens stop working immediately
+    let response = ctx
+        .client
+        .delete(format!(
+            "/api/v1/preview-tokens/{}",
+            blog_token["id"].as_str().unwrap()
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(
+        fetch(read_key, format!("{}?preview_token={}", blog_path, raw)).await,
+        Status::Forbidden
+    );
+}
+
+// =========================================================================
+// 21. Sitemap
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_sitemap_lists_live_localized_urls_with_hreflang() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+
+    // Without a primary domain there is no sitemap
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/sitemap.xml", site_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    sqlx::query(
+        "INSERT INTO site_domains (site_id, domain, is_primary) VALUES ($1, 'sitemap-test.example', TRUE)",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    // en is the default locale without a prefix; de lives under /de
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default, url_prefix) \
+         SELECT $1, id, code = 'en', CASE WHEN code = 'de' THEN 'de' END \
+         FROM locales WHERE code IN ('en', 'de')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let create = |path: &'static str, body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .post(path)
+                .header(Header::new("X-API-Key", key))
+                .header(Header::new("Content-Type", "application/json"))
+                .body(body.to_string())
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Created);
+            let created: serde_json::Value = response.into_json().await.expect("valid JSON");
+            created["content_id"]
+                .as_str()
+                .unwrap()
+                .parse::<uuid::Uuid>()
+                .unwrap()
+        }
+    };
+
+    let about = create(
+        "/api/v1/pages",
+        serde_json::json!({
+            "route": "/about", "slug": "about", "site_ids": [site_id], "status": "Published"
+        }),
+    )
+    .await;
+    let post = create(
+        "/api/v1/blogs",
+        serde_json::json!({
+            "slug": "hello-world", "author": "Test Author", "published_date": "2025-01-15",
+            "site_ids": [site_id], "status": "Published"
+        }),
+    )
+    .await;
+    let draft = create(
+        "/api/v1/pages",
+        serde_json::json!({
+            "route": "/draft", "slug": "draft", "site_ids": [site_id], "status": "Draft"
+        }),
+    )
+    .await;
+    let expired = create(
+        "/api/v1/pages",
+        serde_json::json!({
+            "route": "/expired", "slug": "expired", "site_ids": [site_id], "status": "Published"
+        }),
+    )
+    .await;
+    sqlx::query("UPDATE contents SET publish_end = NOW() - INTERVAL '1 day' WHERE id = $1")
+        .bind(expired)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    for (content_id, codes) in [
+        (about, vec!["en", "de"]),
+        (post, vec!["de"]),
+        (draft, vec!["en"]),
+        (expired, vec!["en"]),
+    ] {
+        for code in codes {
+            sqlx::query(
+                "INSERT INTO content_localizations (content_id, locale_id, title) \
+                 SELECT $1, id, 'Title' FROM locales WHERE code = $2",
+            )
+            .bind(content_id)
+            .bind(code)
+            .execute(&ctx.pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/sitemap.xml", site_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::XML));
+    let xml = response.into_string().await.unwrap();
+
+    assert!(xml.contains("<urlset"));
+    assert_eq!(xml.matches("<url>").count(), 3);
+    assert!(xml.contains("<loc>https://sitemap-test.example/about</loc>"));
+    assert!(xml.contains("<loc>https://sitemap-test.example/de/about</loc>"));
+    assert!(xml.contains("<loc>https://sitemap-test.example/de/blog/hello-world</loc>"));
+    assert!(xml.contains(
+        "<xhtml:link rel=\"alternate\" hreflang=\"de\" href=\"https://sitemap-test.example/de/about\"/>"
+    ));
+    assert!(xml.contains(
+        "<xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"https://sitemap-test.example/about\"/>"
+    ));
+    assert!(xml.contains("<lastmod>"));
+    assert!(!xml.contains("/draft"));
+    assert!(!xml.contains("/expired"));
+
+    // A small site fits in one file, so only part 1 exists
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/sitemap.xml?page=2", site_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Blog paths follow the site's blog_path_prefix, in the sitemap and the RSS feed
+    sqlx::query(
+        "INSERT INTO site_settings (site_id, setting_key, setting_value) \
+         VALUES ($1, 'blog_path_prefix', '\"/news\"')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/sitemap.xml", site_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    let xml = response.into_string().await.unwrap();
+    assert!(xml.contains("<loc>https://sitemap-test.example/de/news/hello-world</loc>"));
+    assert!(!xml.contains("/blog/"));
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/feed.rss", site_id))
+        .header(Header::new("X-API-Key", read_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let rss = response.into_string().await.unwrap();
+    assert!(rss.contains("<link>https://sitemap-test.example/news/hello-world</link>"));
+}
+
+// =========================================================================
+// 22. Automatic redirects
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_automatic_redirects_on_slug_route_change_and_delete() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default, url_prefix) \
+         SELECT $1, id, code = 'en', CASE WHEN code = 'de' THEN 'de' END \
+         FROM locales WHERE code IN ('en', 'de')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let send = |method: &'static str, path: String, body: Option<serde_json::Value>| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let request = match method {
+                "POST" => client.post(path),
+                "PUT" => client.put(path),
+                "DELETE" => client.delete(path),
+                _ => client.get(path),
+            }
+            .header(Header::new("X-API-Key", key))
+            .header(ContentType::JSON);
+            let request = match body {
+                Some(body) => request.body(body.to_string()),
+                None => request,
+            };
+            let response = request.dispatch().await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+    let lookup = |path: &'static str| {
+        send(
+            "GET",
+            format!("/api/v1/sites/{}/redirects/lookup?path={}", site_id, path),
+            None,
+        )
+    };
+
+    // Slug change on a published post redirects the old URL, with and without locale prefix
+    let blog = create_test_blog_with_status(&ctx, &write_key, site_id, "first", "Published").await;
+    let blog_id = blog["id"].as_str().unwrap().to_string();
+
+    let (status, _) = send(
+        "PUT",
+        format!("/api/v1/blogs/{}", blog_id),
+        Some(serde_json::json!({ "slug": "second" })),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, found) = lookup("/blog/first").await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(found["destination_path"], "/blog/second");
+    assert_eq!(found["status_code"], 301);
+    let (_, found) = lookup("/de/blog/first").await;
+    assert_eq!(found["destination_path"], "/de/blog/second");
+
+    // A second change collapses the chain instead of adding a hop
+    send(
+        "PUT",
+        format!("/api/v1/blogs/{}", blog_id),
+        Some(serde_json::json!({ "slug": "third" })),
+    )
+    .await;
+    let (_, found) = lookup("/blog/first").await;
+    assert_eq!(found["destination_path"], "/blog/third");
+    let (_, found) = lookup("/blog/second").await;
+    assert_eq!(found["destination_path"], "/blog/third");
+
+    // Going back to an earlier slug makes it servable again
+    send(
+        "PUT",
+        format!("/api/v1/blogs/{}", blog_id),
+        Some(serde_json::json!({ "slug": "first" })),
+    )
+    .await;
+    let (status, _) = lookup("/blog/first").await;
+    assert_eq!(status, Status::NotFound);
+    let (_, found) = lookup("/blog/third").await;
+    assert_eq!(found["destination_path"], "/blog/first");
+
+    let (_, list) = send(
+        "GET",
+        format!("/api/v1/sites/{}/redirects?per_page=100", site_id),
+        None,
+    )
+    .await;
+    let redirects = list["data"].as_array().unwrap();
+    assert!(!redirects.is_empty());
+    assert!(redirects
+        .iter()
+        .all(|r| r["is_auto_generated"] == true && r["content_id"] == blog["content_id"]));
+
+    // Drafts have no public URL, so renaming them creates nothing
+    let (_, draft) = send(
+        "POST",
+        "/api/v1/pages".to_string(),
+        Some(serde_json::json!({
+            "route": "/draft", "slug": "draft", "site_ids": [site_id], "status": "Draft"
+        })),
+    )
+    .await;
+    send(
+        "PUT",
+        format!("/api/v1/pages/{}", draft["id"].as_str().unwrap()),
+        Some(serde_json::json!({ "route": "/draft-renamed" })),
+    )
+    .await;
+    let (status, _) = lookup("/draft").await;
+    assert_eq!(status, Status::NotFound);
+
+    // Route change retargets manual redirects that pointed at the old route
+    let (_, page) = send(
+        "POST",
+        "/api/v1/pages".to_string(),
+        Some(serde_json::json!({
+            "route": "/old", "slug": "old", "site_ids": [site_id], "status": "Published"
+        })),
+    )
+    .await;
+    let page_id = page["id"].as_str().unwrap().to_string();
+    let (status, manual) = send(
+        "POST",
+        format!("/api/v1/sites/{}/redirects", site_id),
+        Some(serde_json::json!({
+            "source_path": "/legacy", "destination_path": "/old", "status_code": 302,
+            "site_id": site_id
+        })),
+    )
+    .await;
+    assert_eq!(status, Status::Created);
+    assert_eq!(manual["is_auto_generated"], false);
+
+    send(
+        "PUT",
+        format!("/api/v1/pages/{}", page_id),
+        Some(serde_json::json!({ "route": "/new" })),
+    )
+    .await;
+    let (_, found) = lookup("/old").await;
+    assert_eq!(found["destination_path"], "/new");
+    let (_, found) = lookup("/legacy").await;
+    assert_eq!(found["destination_path"], "/new");
+    assert_eq!(found["status_code"], 302);
+
+    // Deleting with a fallback redirects the removed route and everything pointing at it
+    let (status, _) = send(
+        "DELETE",
+        format!("/api/v1/pages/{}?redirect_to=not-a-path", page_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send(
+        "DELETE",
+        format!("/api/v1/pages/{}?redirect_to=/", page_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, Status::NoContent);
+    for path in ["/new", "/old", "/legacy"] {
+        let (_, found) = lookup(path).await;
+        assert_eq!(found["destination_path"], "/", "{path}");
+    }
+    let (_, found) = lookup("/de/new").await;
+    assert_eq!(found["destination_path"], "/de/");
+
+    // Archiving with redirect_to follows the fallback's own redirects
+    send(
+        "PUT",
+        format!("/api/v1/blogs/{}", blog_id),
+        Some(serde_json::json!({ "status": "Archived", "redirect_to": "/legacy" })),
+    )
+    .await;
+    let (_, found) = lookup("/blog/first").await;
+    assert_eq!(found["destination_path"], "/");
+    let (_, found) = lookup("/blog/third").await;
+    assert_eq!(found["destination_path"], "/");
+}
+
+#[rocket::async_test]
+#[serial]
+async fn test_automatic_redirects_keep_manual_redirects() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    let send = |method: &'static str, path: String, body: Option<serde_json::Value>| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let request = match method {
+                "POST" => client.post(path),
+                "PUT" => client.put(path),
+                _ => client.get(path),
+            }
+            .header(Header::new("X-API-Key", key))
+            .header(ContentType::JSON);
+            let request = match body {
+                Some(body) => request.body(body.to_string()),
+                None => request,
+            };
+            let response = request.dispatch().await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+    let lookup = |path: &'static str| {
+        send(
+            "GET",
+            format!("/api/v1/sites/{}/redirects/lookup?path={}", site_id, path),
+            None,
+        )
+    };
+
+    let blog = create_test_blog_with_status(&ctx, &write_key, site_id, "alpha", "Published").await;
+    let blog_id = blog["id"].as_str().unwrap().to_string();
+
+    // Manual redirects on the old path and on a later target path
+    for (source, destination) in [("/blog/alpha", "/campaign"), ("/blog/gamma", "/promo")] {
+        let (status, _) = send(
+            "POST",
+            format!("/api/v1/sites/{}/redirects", site_id),
+            Some(serde_json::json!({
+                "source_path": source, "destination_path": destination, "status_code": 302,
+                "site_id": site_id
+            })),
+        )
+        .await;
+        assert_eq!(status, Status::Created);
+    }
+
+    // The slug change does not overwrite the manual redirect on the old path
+    let (status, _) = send(
+        "PUT",
+        format!("/api/v1/blogs/{}", blog_id),
+        Some(serde_json::json!({ "slug": "beta" })),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (_, found) = lookup("/blog/alpha").await;
+    assert_eq!(found["destination_path"], "/campaign");
+    assert_eq!(found["status_code"], 302);
+
+    // Moving onto a manually redirected path leaves that redirect active
+    send(
+        "PUT",
+        format!("/api/v1/blogs/{}", blog_id),
+        Some(serde_json::json!({ "slug": "gamma" })),
+    )
+    .await;
+    let (_, found) = lookup("/blog/gamma").await;
+    assert_eq!(found["destination_path"], "/promo");
+    let (_, found) = lookup("/blog/beta").await;
+    assert_eq!(found["destination_path"], "/blog/gamma");
+
+    let (_, list) = send(
+        "GET",
+        format!("/api/v1/sites/{}/redirects?per_page=100", site_id),
+        None,
+    )
+    .await;
+    let manual: Vec<&serde_json::Value> = list["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|r| r["is_auto_generated"] == false)
+        .collect();
+    assert_eq!(manual.len(), 2);
+    assert!(manual
+        .iter()
+        .all(|r| r["is_active"] == true && r["content_id"].is_null()));
+}
+
+// =========================================================================
+// 23. Pattern redirects and hit statistics
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_pattern_redirects_status_codes_and_hits() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    let create = |body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .post(format!("/api/v1/sites/{}/redirects", site_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+    let lookup = |path: &'static str| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .get(format!(
+                    "/api/v1/sites/{}/redirects/lookup?path={}",
+                    site_id, path
+                ))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+
+    let (status, wildcard) = create(serde_json::json!({
+        "source_path": "/old-blog/*", "destination_path": "/blog/$1",
+        "status_code": 308, "match_type": "Wildcard", "site_id": site_id
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+    assert_eq!(wildcard["match_type"], "Wildcard");
+    assert_eq!(wildcard["hit_count"], 0);
+
+    let (status, _) = create(serde_json::json!({
+        "source_path": r"/products/(\d+)/(?P<slug>[a-z-]+)",
+        "destination_path": "/shop/${slug}?id=$1",
+        "status_code": 307, "match_type": "Regex", "query_handling": "Preserve",
+        "site_id": site_id
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+
+    let (status, gone) = create(serde_json::json!({
+        "source_path": "/discontinued", "status_code": 410, "site_id": site_id
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+    assert!(gone["destination_path"].is_null());
+
+    let (status, _) = create(serde_json::json!({
+        "source_path": "/search?q=shoes", "destination_path": "/shoes",
+        "status_code": 301, "query_handling": "Exact", "site_id": site_id
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+
+    // An exact rule beats a pattern that also matches
+    let (status, _) = create(serde_json::json!({
+        "source_path": "/old-blog/special", "destination_path": "/special",
+        "status_code": 301, "site_id": site_id
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+
+    // Invalid combinations are rejected
+    for body in [
+        serde_json::json!({
+            "source_path": "/x", "destination_path": "/y", "status_code": 410, "site_id": site_id
+        }),
+        serde_json::json!({ "source_path": "/x", "status_code": 301, "site_id": site_id }),
+        serde_json::json!({
+            "source_path": "/x/(", "destination_path": "/y", "status_code": 301,
+            "match_type": "Regex", "site_id": site_id
+        }),
+        serde_json::json!({
+            "source_path": "/x", "destination_path": "/y", "status_code": 303, "site_id": site_id
+        }),
+    ] {
+        let (status, _) = create(body).await;
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    let (status, found) = lookup("/old-blog/2024/hello").await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(found["destination_path"], "/blog/2024/hello");
+    assert_eq!(found["status_code"], 308);
+
+    let (_, found) = lookup("/old-blog/special").await;
+    assert_eq!(found["destination_path"], "/special");
+
+    let (_, found) = lookup("/products/7/red-shoes%3Futm_source%3Dmail").await;
+    assert_eq!(
+        found["destination_path"],
+        "/shop/red-shoes?id=7&utm_source=mail"
+    );
+    assert_eq!(found["status_code"], 307);
+
+    let (status, found) = lookup("/discontinued%3Fref%3Dold").await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(found["status_code"], 410);
+    assert!(found["destination_path"].is_null());
+
+    let (_, found) = lookup("/search%3Fq%3Dshoes").await;
+    assert_eq!(found["destination_path"], "/shoes");
+    let (status, _) = lookup("/search%3Fq%3Dhats").await;
+    assert_eq!(status, Status::NotFound);
+    let (status, _) = lookup("/products/x/red").await;
+    assert_eq!(status, Status::NotFound);
+
+    // Hits are counted without touching updated_at
+    lookup("/old-blog/again").await;
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/redirects/{}",
+            wildcard["id"].as_str().unwrap()
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    let after: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(after["hit_count"], 2);
+    assert!(after["last_hit_at"].is_string());
+    assert_eq!(after["updated_at"], wildcard["updated_at"]);
+
+    // A more specific wildcard wins even though it is newer, as soon as it is created
+    let (status, _) = create(serde_json::json!({
+        "source_path": "/old-blog/2024/*", "destination_path": "/archive/2024/$1",
+        "status_code": 301, "match_type": "Wildcard", "site_id": site_id
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+    let (_, found) = lookup("/old-blog/2024/hello").await;
+    assert_eq!(found["destination_path"], "/archive/2024/hello");
+    let (_, found) = lookup("/old-blog/2023/hello").await;
+    assert_eq!(found["destination_path"], "/blog/2023/hello");
+
+    // Switching to 410 clears the destination; switching back requires one
+    let response = ctx
+        .client
+        .put(format!(
+            "/api/v1/redirects/{}",
+            wildcard["id"].as_str().unwrap()
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "status_code": 410 }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let updated: serde_json::Value = response.into_json().await.unwrap();
+    assert!(updated["destination_path"].is_null());
+    // Updates replace the cached pattern straight away
+    let (_, found) = lookup("/old-blog/2023/hello").await;
+    assert_eq!(found["status_code"], 410);
+
+    let response = ctx
+        .client
+        .put(format!(
+            "/api/v1/redirects/{}",
+            wildcard["id"].as_str().unwrap()
+        ))
+        .header(Header::new("X-API-Key", write_key))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "status_code": 301 }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// =========================================================================
+// 24. Redirect import and export
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_redirect_import_dry_run_apply_and_export() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let other_site_id = create_test_site(&ctx.pool).await;
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+    let other_key = create_test_api_key(&ctx.pool, other_site_id, ApiKeyPermission::Admin).await;
+
+    sqlx::query(
+        "INSERT INTO redirects (site_id, source_path, destination_path) VALUES ($1, '/existing', '/somewhere')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let csv = "source_path,destination_path,status_code,match_type\n\
+               /a,/b,,\n\
+               /b,/c,301,\n\
+               /loop-1,/loop-2,,\n\
+               /loop-2,/loop-1,,\n\
+               /existing,/elsewhere,,\n\
+               /self,/self,,\n\
+               /gone,,410,\n\
+               /old/*,/new/$1,308,Wildcard\n";
+
+    let import =
+        |site: uuid::Uuid, key: String, query: &'static str, ct: ContentType, body: String| {
+            let client = &ctx.client;
+            async move {
+                let response = client
+                    .post(format!("/api/v1/sites/{}/redirects/import{}", site, query))
+                    .header(Header::new("X-API-Key", key))
+                    .header(ct)
+                    .body(body)
+                    .dispatch()
+                    .await;
+                let status = response.status();
+                let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+                (status, json)
+            }
+        };
+    let count = || async {
+        let (n,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM redirects WHERE site_id = $1")
+            .bind(site_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+        n
+    };
+
+    // Dry run reports everything and writes nothing
+    let (status, report) = import(
+        site_id,
+        admin_key.clone(),
+        "?dry_run=true",
+        ContentType::CSV,
+        csv.to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["total_rows"], 8);
+    assert_eq!(report["created"], 4);
+    assert_eq!(report["skipped"], 4);
+    let issues: Vec<(u64, String)> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| {
+            (
+                i["row"].as_u64().unwrap(),
+                i["kind"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            (1, "Chain".to_string()),
+            (3, "Loop".to_string()),
+            (4, "Loop".to_string()),
+            (5, "Duplicate".to_string()),
+            (6, "SelfRedirect".to_string()),
+        ]
+    );
+    assert_eq!(count().await, 1);
+
+    // Unsupported content type
+    let (status, _) = import(
+        site_id,
+        admin_key.clone(),
+        "",
+        ContentType::Binary,
+        csv.to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    // Real import, replacing the existing redirect
+    let (status, report) = import(
+        site_id,
+        admin_key.clone(),
+        "?overwrite=true",
+        ContentType::CSV,
+        csv.to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(report["dry_run"], false);
+    assert_eq!(report["created"], 4);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(count().await, 5);
+    let (destination,): (Option<String>,) = sqlx::query_as(
+        "SELECT destination_path FROM redirects WHERE site_id = $1 AND source_path = '/existing'",
+    )
+    .bind(site_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(destination.as_deref(), Some("/elsewhere"));
+
+    // Export as CSV and load it into another site
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/redirects/export", site_id))
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CSV));
+    assert!(response
+        .headers()
+        .get_one("Content-Disposition")
+        .unwrap()
+        .contains(".csv"));
+    let exported = response.into_string().await.unwrap();
+    assert_eq!(exported.lines().count(), 6);
+
+    let (status, report) = import(
+        other_site_id,
+        other_key.clone(),
+        "",
+        ContentType::CSV,
+        exported,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(report["created"], 5);
+    assert_eq!(report["skipped"], 0);
+
+    // JSON export round-trips through JSON import
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/redirects/export?format=json",
+            site_id
+        ))
+        .header(Header::new("X-API-Key", admin_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    let exported: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 5);
+    assert!(exported
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|r| r["source_path"] == "/gone" && r["destination_path"].is_null()));
+
+    let (status, report) = import(
+        other_site_id,
+        other_key,
+        "?dry_run=true",
+        ContentType::JSON,
+        exported.to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    // Everything already exists on the other site now
+    assert_eq!(report["created"], 0);
+    assert_eq!(report["skipped"], 5);
+}
+
+// =========================================================================
+// 25. Optimistic concurrency and edit locks
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_if_match_and_edit_locks() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let alice_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let bob_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+
+    let response = ctx
+        .client
+        .post("/api/v1/blogs")
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "slug": "concurrent-edits",
+                "author": "Alice",
+                "published_date": "2025-01-15",
+                "site_ids": [site_id],
+                "status": "Draft"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let blog: serde_json::Value = response.into_json().await.unwrap();
+    let blog_id = blog["id"].as_str().unwrap().to_string();
+    let content_id = blog["content_id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}", blog_id))
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").unwrap().to_string();
+
+    let update = |key: String, if_match: Option<String>, author: &'static str| {
+        let client = &ctx.client;
+        let blog_id = blog_id.clone();
+        async move {
+            let mut request = client
+                .put(format!("/api/v1/blogs/{}", blog_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(serde_json::json!({ "author": author }).to_string());
+            if let Some(tag) = if_match {
+                request = request.header(Header::new("If-Match", tag));
+            }
+            let response = request.dispatch().await;
+            let etag = response.headers().get_one("ETag").map(String::from);
+            (response.status(), etag)
+        }
+    };
+
+    // Alice saves first with the tag she read, Bob's save with the same tag is rejected
+    let (status, new_etag) = update(alice_key.clone(), Some(etag.clone()), "Alice").await;
+    assert_eq!(status, Status::Ok);
+    let new_etag = new_etag.unwrap();
+    assert_ne!(new_etag, etag);
+    let (status, _) = update(bob_key.clone(), Some(etag.clone()), "Bob").await;
+    assert_eq!(status, Status::PreconditionFailed);
+
+    // An update that fails after the check leaves the entity tag as it was
+    let response = ctx
+        .client
+        .put(format!("/api/v1/blogs/{}", blog_id))
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .header(Header::new("If-Match", new_etag.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "publish_start": "2030-01-02T00:00:00Z",
+                "publish_end": "2030-01-01T00:00:00Z"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}/detail", blog_id))
+        .header(Header::new("X-API-Key", bob_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.headers().get_one("ETag"), Some(new_etag.as_str()));
+    let detail: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(detail["author"], "Alice");
+
+    // Wildcard and missing If-Match stay unconditional
+    let (status, _) = update(bob_key.clone(), Some("*".to_string()), "Bob").await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = update(bob_key.clone(), None, "Bob").await;
+    assert_eq!(status, Status::Ok);
+
+    // --- Edit locks ---
+    let lock_url = format!("/api/v1/content/{}/lock", content_id);
+    let response = ctx
+        .client
+        .post(lock_url.clone())
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .header(ContentType::JSON)
+        .body(r#"{"holder_name":"Alice"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let lock: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(lock["held_by_caller"], true);
+
+    let response = ctx
+        .client
+        .post(lock_url.clone())
+        .header(Header::new("X-API-Key", bob_key.clone()))
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = ctx
+        .client
+        .get(lock_url.clone())
+        .header(Header::new("X-API-Key", bob_key.clone()))
+        .dispatch()
+        .await;
+    let state: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(state["locked"], true);
+    assert_eq!(state["lock"]["holder_name"], "Alice");
+    assert_eq!(state["lock"]["held_by_caller"], false);
+
+    let response = ctx
+        .client
+        .put(lock_url.clone())
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = ctx
+        .client
+        .put(lock_url.clone())
+        .header(Header::new("X-API-Key", bob_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    // Only admins may break someone else's lock
+    let response = ctx
+        .client
+        .delete(lock_url.clone())
+        .header(Header::new("X-API-Key", bob_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+    let response = ctx
+        .client
+        .delete(lock_url.clone())
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    // Expired locks can be taken over
+    let response = ctx
+        .client
+        .post(lock_url.clone())
+        .header(Header::new("X-API-Key", bob_key.clone()))
+        .header(ContentType::JSON)
+        .body(r#"{"holder_name":"Bob"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    sqlx::query("UPDATE content_edit_locks SET expires_at = NOW() - INTERVAL '1 second'")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let response = ctx
+        .client
+        .get(lock_url.clone())
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .dispatch()
+        .await;
+    let state: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(state["locked"], false);
+    let response = ctx
+        .client
+        .post(lock_url.clone())
+        .header(Header::new("X-API-Key", alice_key.clone()))
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let lock: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(lock["held_by_caller"], true);
+    assert!(lock["holder_name"].is_null());
+
+    // Releasing your own lock needs no admin rights
+    let response = ctx
+        .client
+        .delete(lock_url)
+        .header(Header::new("X-API-Key", alice_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+}
+
+// =========================================================================
+// 26. Blog comments and moderation
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_comment_submission_and_moderation() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let editor_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    let create_blog = |slug: &'static str, allow_comments: bool| {
+        let client = &ctx.client;
+        let key = editor_key.clone();
+        async move {
+            let response = client
+                .post("/api/v1/blogs")
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(
+                    serde_json::json!({
+                        "slug": slug,
+                        "author": "Editor",
+                        "published_date": "2025-01-15",
+                        "site_ids": [site_id],
+                        "status": "Published",
+                        "allow_comments": allow_comments
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Created);
+            let blog: serde_json::Value = response.into_json().await.unwrap();
+            blog["id"].as_str().unwrap().to_string()
+        }
+    };
+    let open_blog = create_blog("open-for-comments", true).await;
+    let closed_blog = create_blog("closed-for-comments", false).await;
+
+    let submit = |blog_id: String, body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = read_key.clone();
+        async move {
+            let response = client
+                .post(format!(
+                    "/api/v1/sites/{}/blogs/{}/comments",
+                    site_id, blog_id
+                ))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+    let list_public = || {
+        let client = &ctx.client;
+        let key = read_key.clone();
+        let blog_id = open_blog.clone();
+        async move {
+            let response = client
+                .get(format!(
+                    "/api/v1/sites/{}/blogs/{}/comments",
+                    site_id, blog_id
+                ))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+            response.into_json::<serde_json::Value>().await.unwrap()
+        }
+    };
+
+    // Submissions start pending and are hidden from the public list
+    let (status, submitted) = submit(
+        open_blog.clone(),
+        serde_json::json!({
+            "author_name": "Jane Reader",
+            "author_email": "jane@example.com",
+            "locale": "en",
+            "body": "Great post!"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Created);
+    assert_eq!(submitted["status"], "Pending");
+    let root_id = submitted["id"].as_str().unwrap().to_string();
+    assert_eq!(list_public().await.as_array().unwrap().len(), 0);
+
+    let (status, _) = submit(
+        closed_blog,
+        serde_json::json!({ "author_name": "Jane Reader", "body": "Hello?" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    // Replies to unapproved comments are rejected
+    let (status, _) = submit(
+        open_blog.clone(),
+        serde_json::json!({ "parent_id": root_id, "author_name": "Joe", "body": "Agreed" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    // Readers cannot moderate
+    let response = ctx
+        .client
+        .put(format!("/api/v1/comments/{}", root_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .header(ContentType::JSON)
+        .body(r#"{"status":"Approved"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = ctx
+        .client
+        .put(format!("/api/v1/comments/{}", root_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(r#"{"status":"Approved"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let moderated: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(moderated["status"], "Approved");
+    assert!(!moderated["moderated_by"].is_null());
+
+    // Replies inherit the thread's locale and nest under their parent once approved
+    let (status, reply) = submit(
+        open_blog.clone(),
+        serde_json::json!({ "parent_id": root_id, "author_name": "Joe", "body": "Agreed" }),
+    )
+    .await;
+    assert_eq!(status, Status::Created);
+    let reply_id = reply["id"].as_str().unwrap().to_string();
+    let (_, spam) = submit(
+        open_blog.clone(),
+        serde_json::json!({ "author_name": "Spammer", "body": "Buy now" }),
+    )
+    .await;
+    let spam_id = spam["id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/comments?status=pending", site_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let queue: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(queue["meta"]["total_items"], 2);
+
+    let bulk = |body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = editor_key.clone();
+        async move {
+            let response = client
+                .post(format!("/api/v1/sites/{}/comments/bulk", site_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+            response.into_json::<serde_json::Value>().await.unwrap()
+        }
+    };
+    let result = bulk(serde_json::json!({
+        "ids": [reply_id, spam_id],
+        "action": "UpdateStatus",
+        "status": "Approved"
+    }))
+    .await;
+    assert_eq!(result["succeeded"], 2);
+    let result = bulk(serde_json::json!({
+        "ids": [spam_id, uuid::Uuid::new_v4()],
+        "action": "UpdateStatus",
+        "status": "Spam"
+    }))
+    .await;
+    assert_eq!(result["succeeded"], 1);
+    assert_eq!(result["failed"], 1);
+
+    let threads = list_public().await;
+    let threads = threads.as_array().unwrap();
+    assert_eq!(threads.len(), 1);
+    assert_eq!(threads[0]["id"], root_id.as_str());
+    assert_eq!(threads[0]["replies"][0]["id"], reply_id.as_str());
+    assert_eq!(
+        threads[0]["replies"][0]["locale_id"],
+        threads[0]["locale_id"]
+    );
+    assert!(threads[0].get("author_email").is_none());
+
+    // Deleting the root removes the whole thread
+    let result = bulk(serde_json::json!({ "ids": [root_id], "action": "Delete" })).await;
+    assert_eq!(result["succeeded"], 1);
+    assert_eq!(list_public().await.as_array().unwrap().len(), 0);
+    let response = ctx
+        .client
+        .get(format!("/api/v1/comments/{}", reply_id))
+        .header(Header::new("X-API-Key", editor_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+// =========================================================================
+// 27. Forms and submissions
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_form_submissions_inbox_and_retention() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let editor_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default, url_prefix) \
+         SELECT $1, id, code = 'en', CASE WHEN code = 'de' THEN 'de' END \
+         FROM locales WHERE code IN ('en', 'de')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let form_body = |email_labels: serde_json::Value| {
+        serde_json::json!({
+            "slug": "contact",
+            "name": "Contact",
+            "retention_days": 30,
+            "fields": [
+                { "name": "email", "field_type": "Email", "required": true, "labels": email_labels },
+                { "name": "message", "field_type": "Textarea", "required": true, "max_length": 20,
+                  "labels": { "en": "Message" } }
+            ]
+        })
+    };
+
+    // Labels may only use the site's languages
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/forms", site_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(form_body(serde_json::json!({ "en": "Email", "fr": "Courriel" })).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/forms", site_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(form_body(serde_json::json!({ "en": "Email", "de": "E-Mail" })).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let form: serde_json::Value = response.into_json().await.unwrap();
+    let form_id = form["id"].as_str().unwrap().to_string();
+
+    // Public definition resolves labels, falling back to the default locale
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/forms/by-slug/contact?locale=de",
+            site_id
+        ))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let public: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(public["locale"], "de");
+    assert_eq!(public["fields"][0]["label"], "E-Mail");
+    assert_eq!(public["fields"][1]["label"], "Message");
+
+    let submit = |body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = read_key.clone();
+        async move {
+            client
+                .post(format!(
+                    "/api/v1/sites/{}/forms/by-slug/contact/submissions",
+                    site_id
+                ))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await
+                .status()
+        }
+    };
+    let status = submit(serde_json::json!({
+        "locale": "de",
+        "data": { "email": "jane@example.com", "message": "=Hallo" }
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+    let status = submit(serde_json::json!({
+        "data": { "email": "not-an-email", "message": "Hi" }
+    }))
+    .await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    let status = submit(serde_json::json!({
+        "data": { "email": "jane@example.com", "message": "Hi", "extra": "x" }
+    }))
+    .await;
+    assert_eq!(status, Status::UnprocessableEntity);
+
+    // Honeypot hits look successful but are not stored
+    let status = submit(serde_json::json!({
+        "data": { "email": "bot@example.com", "message": "Buy now" },
+        "honeypot": "http://spam.example.com"
+    }))
+    .await;
+    assert_eq!(status, Status::Created);
+
+    // The inbox is for editors only
+    let response = ctx
+        .client
+        .get(format!("/api/v1/forms/{}/submissions", form_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/forms/{}/submissions", form_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let inbox: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(inbox["meta"]["total_items"], 1);
+    assert_eq!(inbox["data"][0]["data"]["email"], "jane@example.com");
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/forms/{}/submissions/export", form_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CSV));
+    let csv = response.into_string().await.unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("id,submitted_at,locale,email,message"));
+    let row = lines.next().unwrap();
+    assert!(row.contains(",de,jane@example.com,'=Hallo"));
+
+    // Submissions older than the retention period are purged by the scheduler
+    sqlx::query(
+        "UPDATE form_submissions SET created_at = NOW() - INTERVAL '31 days' WHERE form_id = $1",
+    )
+    .bind(uuid::Uuid::parse_str(&form_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let report = openyapper::services::scheduler_service::run_tick(&ctx.pool)
+        .await
+        .expect("scheduler tick");
+    assert_eq!(report.purged_submissions, 1);
+
+    // Inactive forms disappear from the public site
+    let response = ctx
+        .client
+        .put(format!("/api/v1/forms/{}", form_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(r#"{"is_active":false,"retention_days":null}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let updated: serde_json::Value = response.into_json().await.unwrap();
+    assert!(updated["retention_days"].is_null());
+    let status = submit(serde_json::json!({
+        "data": { "email": "jane@example.com", "message": "Hi" }
+    }))
+    .await;
+    assert_eq!(status, Status::NotFound);
+}
+
+// =========================================================================
+// 28. Page hierarchy
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_page_tree_breadcrumbs_and_move() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let editor_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default, url_prefix) \
+         SELECT $1, id, code = 'en', CASE WHEN code = 'de' THEN 'de' END \
+         FROM locales WHERE code IN ('en', 'de')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let create = |route: &'static str, parent: Option<String>, title_en: &'static str| {
+        let client = &ctx.client;
+        let pool = &ctx.pool;
+        let key = editor_key.clone();
+        async move {
+            let response = client
+                .post("/api/v1/pages")
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(
+                    serde_json::json!({
+                        "route": route,
+                        "slug": route.rsplit('/').next().unwrap(),
+                        "parent_page_id": parent,
+                        "site_ids": [site_id],
+                        "status": "Published"
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Created);
+            let page: serde_json::Value = response.into_json().await.unwrap();
+            sqlx::query(
+                "INSERT INTO content_localizations (content_id, locale_id, title) \
+                 SELECT $1, id, $2 FROM locales WHERE code = 'en'",
+            )
+            .bind(
+                page["content_id"]
+                    .as_str()
+                    .unwrap()
+                    .parse::<uuid::Uuid>()
+                    .unwrap(),
+            )
+            .bind(title_en)
+            .execute(pool)
+            .await
+            .unwrap();
+            (
+                page["id"].as_str().unwrap().to_string(),
+                page["content_id"].as_str().unwrap().to_string(),
+            )
+        }
+    };
+    let (about, about_content) = create("/about", None, "About us").await;
+    let (team, _) = create("/about/team", Some(about.clone()), "Team").await;
+    let (jane, _) = create("/about/team/jane", Some(team.clone()), "Jane").await;
+    let (company, _) = create("/company", None, "Company").await;
+    sqlx::query(
+        "INSERT INTO content_localizations (content_id, locale_id, title) \
+         SELECT $1, id, 'Über uns' FROM locales WHERE code = 'de'",
+    )
+    .bind(about_content.parse::<uuid::Uuid>().unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/pages/tree?locale=de", site_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let tree: serde_json::Value = response.into_json().await.unwrap();
+    let roots = tree.as_array().unwrap();
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots[0]["title"], "Über uns");
+    assert_eq!(roots[0]["children"][0]["title"], "Team");
+    assert_eq!(roots[0]["children"][0]["children"][0]["id"], jane);
+
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/pages/breadcrumbs/about/team/jane",
+            site_id
+        ))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let trail: serde_json::Value = response.into_json().await.unwrap();
+    let titles: Vec<&str> = trail
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["About us", "Team", "Jane"]);
+
+    let move_page = |id: String, body: serde_json::Value| {
+        let client = &ctx.client;
+        let key = editor_key.clone();
+        async move {
+            let response = client
+                .post(format!("/api/v1/pages/{}/move", id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+
+    // Cycles are rejected, also through a plain update
+    let (status, _) = move_page(about.clone(), serde_json::json!({ "parent_page_id": jane })).await;
+    assert_eq!(status, Status::BadRequest);
+    let response = ctx
+        .client
+        .put(format!("/api/v1/pages/{}", about))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "parent_page_id": about }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Moving the team below /company rewrites the subtree and redirects old routes
+    let (status, moved) = move_page(
+        team.clone(),
+        serde_json::json!({ "parent_page_id": company, "rewrite_routes": true }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(moved["page"]["route"], "/company/team");
+    assert_eq!(moved["page"]["parent_page_id"], company);
+    assert_eq!(moved["route_changes"].as_array().unwrap().len(), 2);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/pages/{}", jane))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    let page: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(page["route"], "/company/team/jane");
+
+    let (destination,): (String,) = sqlx::query_as(
+        "SELECT destination_path FROM redirects \
+         WHERE site_id = $1 AND source_path = '/about/team/jane'",
+    )
+    .bind(site_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(destination, "/company/team/jane");
+
+    // A rewritten route may not collide with an existing page
+    create("/team", None, "Other team").await;
+    let (status, _) = move_page(
+        team,
+        serde_json::json!({ "parent_page_id": null, "rewrite_routes": true }),
+    )
+    .await;
+    assert_eq!(status, Status::Conflict);
+}
+
+// =========================================================================
+// 29. Global sections
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_global_section_reuse_and_detach() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let editor_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let (locale_id,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM locales WHERE code = 'en'")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/global-sections", site_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "name": "Footer CTA",
+                "section_type": "Cta",
+                "call_to_action_route": "/contact"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let global: serde_json::Value = response.into_json().await.unwrap();
+    let global_id = global["id"].as_str().unwrap().to_string();
+    assert_eq!(global["usage_count"], 0);
+
+    let response = ctx
+        .client
+        .put(format!(
+            "/api/v1/global-sections/{}/localizations",
+            global_id
+        ))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "locale_id": locale_id, "title": "Talk to us" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Place the section on two pages
+    let (mut page_ids, mut section_ids) = (Vec::new(), Vec::new());
+    for route in ["/pricing", "/features"] {
+        let response = ctx
+            .client
+            .post("/api/v1/pages")
+            .header(Header::new("X-API-Key", editor_key.clone()))
+            .header(ContentType::JSON)
+            .body(
+                serde_json::json!({
+                    "route": route,
+                    "slug": &route[1..],
+                    "site_ids": [site_id]
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Created);
+        let page: serde_json::Value = response.into_json().await.unwrap();
+        let page_id = page["id"].as_str().unwrap().to_string();
+
+        let response = ctx
+            .client
+            .post(format!("/api/v1/pages/{}/global-sections", page_id))
+            .header(Header::new("X-API-Key", editor_key.clone()))
+            .header(ContentType::JSON)
+            .body(
+                serde_json::json!({ "global_section_id": global_id, "display_order": 9 })
+                    .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Created);
+        let section: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(section["global_section_id"], global_id.as_str());
+        section_ids.push(section["id"].as_str().unwrap().to_string());
+        page_ids.push(page_id);
+    }
+
+    // One edit shows up on every page
+    let response = ctx
+        .client
+        .put(format!("/api/v1/global-sections/{}", global_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "call_to_action_route": "/demo" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let updated: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(updated["usage_count"], 2);
+
+    for section_id in &section_ids {
+        let response = ctx
+            .client
+            .get(format!(
+                "/api/v1/pages/sections/{}/localizations",
+                section_id
+            ))
+            .header(Header::new("X-API-Key", read_key.clone()))
+            .dispatch()
+            .await;
+        let localizations: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(localizations[0]["title"], "Talk to us");
+    }
+    for page_id in &page_ids {
+        let response = ctx
+            .client
+            .get(format!("/api/v1/pages/{}/sections", page_id))
+            .header(Header::new("X-API-Key", read_key.clone()))
+            .dispatch()
+            .await;
+        let sections: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(sections[0]["call_to_action_route"], "/demo");
+    }
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/global-sections/{}/usage", global_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    let usage: serde_json::Value = response.into_json().await.unwrap();
+    let routes: Vec<&str> = usage
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["route"].as_str().unwrap())
+        .collect();
+    assert_eq!(routes, vec!["/features", "/pricing"]);
+
+    // Shared fields are not editable through a reference, and the section cannot go while used
+    let response = ctx
+        .client
+        .put(format!("/api/v1/pages/sections/{}", section_ids[0]))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "call_to_action_route": "/local" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/global-sections/{}", global_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+    // The database refuses as well, so a reference added after the usage check survives
+    let err = openyapper::models::global_section::GlobalSection::delete(
+        &ctx.pool,
+        global_id.parse().unwrap(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, openyapper::errors::ApiError::Conflict(_)));
+
+    // Detaching leaves a local copy that no longer follows the global section
+    let response = ctx
+        .client
+        .post(format!("/api/v1/pages/sections/{}/detach", section_ids[0]))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let detached: serde_json::Value = response.into_json().await.unwrap();
+    assert!(detached["global_section_id"].is_null());
+    assert_eq!(detached["call_to_action_route"], "/demo");
+
+    let response = ctx
+        .client
+        .put(format!(
+            "/api/v1/global-sections/{}/localizations",
+            global_id
+        ))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "locale_id": locale_id, "title": "Get in touch" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/pages/sections/{}/localizations",
+            section_ids[0]
+        ))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    let localizations: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(localizations[0]["title"], "Talk to us");
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/global-sections/{}", global_id))
+        .header(Header::new("X-API-Key", read_key))
+        .dispatch()
+        .await;
+    let global: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(global["usage_count"], 1);
+}
+
+// =========================================================================
+// 30. Page templates
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_create_page_from_template() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let editor_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default) \
+         SELECT $1, id, code = 'en' FROM locales WHERE code IN ('en', 'de')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let layout = serde_json::json!([
+        {
+            "section_type": "Hero",
+            "settings": { "layout": "split" },
+            "placeholders": {
+                "en": { "title": "Your headline" },
+                "de": { "title": "Ihre Überschrift" }
+            }
+        },
+        {
+            "section_type": "Cta",
+            "call_to_action_route": "/contact",
+            "placeholders": { "en": { "button_text": "Get in touch" } }
+        }
+    ]);
+
+    // Placeholders must use the site's languages
+    let mut invalid = layout.clone();
+    invalid[1]["placeholders"]["fr"] = serde_json::json!({ "title": "Titre" });
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/page-templates", site_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "name": "Landing page", "sections": invalid }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/page-templates", site_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "name": "Landing page",
+                "page_type": "Landing",
+                "template": "landing",
+                "sections": layout
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let template: serde_json::Value = response.into_json().await.unwrap();
+    let template_id = template["id"].as_str().unwrap().to_string();
+    assert_eq!(template["sections"].as_array().unwrap().len(), 2);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/page-templates/{}/pages", template_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "route": "/spring-sale", "slug": "spring-sale" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let page: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(page["page_type"], "Landing");
+    assert_eq!(page["template"], "landing");
+    let page_id = page["id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/pages/{}/sections", page_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    let sections: serde_json::Value = response.into_json().await.unwrap();
+    let sections = sections.as_array().unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0]["section_type"], "Hero");
+    assert_eq!(sections[0]["settings"]["layout"], "split");
+    assert_eq!(sections[1]["display_order"], 1);
+    assert_eq!(sections[1]["call_to_action_route"], "/contact");
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/pages/{}/sections/localizations", page_id))
+        .header(Header::new("X-API-Key", read_key))
+        .dispatch()
+        .await;
+    let localizations: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(localizations.as_array().unwrap().len(), 3);
+
+    // A failing section rolls back the whole page
+    let mut broken = layout.clone();
+    broken[1]["cover_image_id"] = serde_json::json!(uuid::Uuid::new_v4());
+    let response = ctx
+        .client
+        .put(format!("/api/v1/page-templates/{}", template_id))
+        .header(Header::new("X-API-Key", editor_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "sections": broken }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = ctx
+        .client
+        .post(format!("/api/v1/page-templates/{}/pages", template_id))
+        .header(Header::new("X-API-Key", editor_key))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "route": "/summer-sale", "slug": "summer-sale" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM contents WHERE slug = 'summer-sale'")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(count, 0);
+}
+
+// =========================================================================
+// 31. Cross-site syndication
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_share_content_with_site_specific_slug() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_a = create_test_site(&ctx.pool).await;
+    let site_b = create_test_site(&ctx.pool).await;
+    let key_a = create_test_api_key(&ctx.pool, site_a, ApiKeyPermission::Write).await;
+    let key_b = create_test_api_key(&ctx.pool, site_b, ApiKeyPermission::Write).await;
+
+    let mut blog_ids = Vec::new();
+    for (site_id, slug, key) in [
+        (site_a, "shared-post", &key_a),
+        (site_b, "local-post", &key_b),
+    ] {
+        let blog = create_test_blog(&ctx, key, site_id, slug).await;
+        blog_ids.push(blog["content_id"].as_str().unwrap().to_string());
+    }
+    let content_id = blog_ids[0].clone();
+
+    // A key scoped to site A has no access to site B
+    let response = ctx
+        .client
+        .post(format!("/api/v1/content/{}/sites", content_id))
+        .header(Header::new("X-API-Key", key_a.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "site_id": site_b }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    openyapper::models::content_site::ContentSite::create(
+        &ctx.pool,
+        content_id.parse().unwrap(),
+        site_b,
+        Some("syndicated-post"),
+        true,
+        Some(1),
+    )
+    .await
+    .unwrap();
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/content/{}/sites", content_id))
+        .header(Header::new("X-API-Key", key_a.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let sites: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(sites.as_array().unwrap().len(), 2);
+    assert_eq!(sites[0]["is_owner"], true);
+    assert_eq!(sites[1]["slug"], "syndicated-post");
+
+    // by-slug resolves the override on site B only
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/blogs/by-slug/syndicated-post",
+            site_b
+        ))
+        .header(Header::new("X-API-Key", key_b.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let blog: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(blog["slug"], "syndicated-post");
+    assert_eq!(blog["is_featured"], true);
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/blogs/by-slug/shared-post",
+            site_b
+        ))
+        .header(Header::new("X-API-Key", key_b.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Overrides must not collide with the target site's own content
+    let response = ctx
+        .client
+        .put(format!("/api/v1/content/{}/sites/{}", content_id, site_b))
+        .header(Header::new("X-API-Key", key_b.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "site_specific_slug": "local-post" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = ctx
+        .client
+        .put(format!("/api/v1/content/{}/sites/{}", content_id, site_b))
+        .header(Header::new("X-API-Key", key_b.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "site_specific_slug": null, "display_order": null }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let shared: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(shared["slug"], "shared-post");
+    assert!(shared["display_order"].is_null());
+    assert_eq!(shared["is_featured"], true);
+
+    // Owning sites stay; shared sites can be removed
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/content/{}/sites/{}", content_id, site_a))
+        .header(Header::new("X-API-Key", key_a))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/content/{}/sites/{}", content_id, site_b))
+        .header(Header::new("X-API-Key", key_b.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/blogs/by-slug/shared-post",
+            site_b
+        ))
+        .header(Header::new("X-API-Key", key_b))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[rocket::async_test]
+#[serial]
+async fn test_site_specific_slug_change_redirects_on_that_site() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_a = create_test_site(&ctx.pool).await;
+    let site_b = create_test_site(&ctx.pool).await;
+    let key_a = create_test_api_key(&ctx.pool, site_a, ApiKeyPermission::Write).await;
+    let key_b = create_test_api_key(&ctx.pool, site_b, ApiKeyPermission::Write).await;
+
+    let blog = create_test_blog_with_status(&ctx, &key_a, site_a, "shared-post", "Published").await;
+    let content_id = blog["content_id"].as_str().unwrap().to_string();
+    openyapper::models::content_site::ContentSite::create(
+        &ctx.pool,
+        content_id.parse().unwrap(),
+        site_b,
+        Some("syndicated"),
+        false,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let set_slug = |slug: Option<&'static str>| {
+        let client = &ctx.client;
+        let key = key_b.clone();
+        let path = format!("/api/v1/content/{}/sites/{}", content_id, site_b);
+        async move {
+            let response = client
+                .put(path)
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(serde_json::json!({ "site_specific_slug": slug }).to_string())
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+    };
+    let lookup = |site_id: uuid::Uuid, key: String, path: &'static str| {
+        let client = &ctx.client;
+        async move {
+            let response = client
+                .get(format!(
+                    "/api/v1/sites/{}/redirects/lookup?path={}",
+                    site_id, path
+                ))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            let status = response.status();
+            let json: serde_json::Value = response.into_json().await.unwrap_or_default();
+            (status, json)
+        }
+    };
+
+    // Renaming on site B redirects B's old URL, built from B's slug
+    set_slug(Some("renamed")).await;
+    let (status, found) = lookup(site_b, key_b.clone(), "/blog/syndicated").await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(found["destination_path"], "/blog/renamed");
+    let (status, _) = lookup(site_a, key_a.clone(), "/blog/syndicated").await;
+    assert_eq!(status, Status::NotFound);
+    let (status, _) = lookup(site_a, key_a, "/blog/shared-post").await;
+    assert_eq!(status, Status::NotFound);
+
+    // Clearing the override falls back to the content's own slug
+    set_slug(None).await;
+    let (_, found) = lookup(site_b, key_b.clone(), "/blog/renamed").await;
+    assert_eq!(found["destination_path"], "/blog/shared-post");
+    let (_, found) = lookup(site_b, key_b, "/blog/syndicated").await;
+    assert_eq!(found["destination_path"], "/blog/shared-post");
+}
+
+// =========================================================================
+// 32. Environment promotion
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_promote_content_between_environments() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+
+    // Author the post in staging
+    let response = ctx
+        .client
+        .post("/api/v1/blogs")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "slug": "promoted-post",
+                "author": "Test Author",
+                "published_date": "2025-01-15",
+                "site_ids": [site_id],
+                "status": "Published",
+                "environment": "Staging"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let staging_blog: serde_json::Value = response.into_json().await.unwrap();
+    let staging_id = staging_blog["id"].as_str().unwrap().to_string();
+
+    let get = |path: String| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .get(path)
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            let status = response.status();
+            (status, response.into_json::<serde_json::Value>().await)
+        }
+    };
+    let published = || get(format!("/api/v1/sites/{}/blogs/published", site_id));
+    let by_slug = || {
+        get(format!(
+            "/api/v1/sites/{}/blogs/by-slug/promoted-post",
+            site_id
+        ))
+    };
+
+    // Staging content stays off the live site
+    let (_, list) = published().await;
+    assert_eq!(list.unwrap()["meta"]["total_items"], 0);
+    let (status, _) = by_slug().await;
+    assert_eq!(status, Status::NotFound);
+    let (_, list) = get(format!(
+        "/api/v1/sites/{}/blogs?environment=staging",
+        site_id
+    ))
+    .await;
+    assert_eq!(list.unwrap()["data"][0]["id"], staging_id.as_str());
+    let (status, _) = get(format!("/api/v1/sites/{}/blogs?environment=qa", site_id)).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+
+    let request = serde_json::json!({ "source": "Staging", "target": "Production" });
+    let preview = |key: String| {
+        let client = &ctx.client;
+        let body = request.to_string();
+        async move {
+            let response = client
+                .post(format!("/api/v1/sites/{}/promotions/preview", site_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body)
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+            response.into_json::<serde_json::Value>().await.unwrap()
+        }
+    };
+
+    let changeset = preview(write_key.clone()).await;
+    let items = changeset["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["action"], "Create");
+    assert_eq!(items[0]["slug"], "promoted-post");
+
+    // Applying needs Admin
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/promotions", site_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(request.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let mut apply = request.clone();
+    apply["checksum"] = changeset["checksum"].clone();
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/promotions", site_id))
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .header(ContentType::JSON)
+        .body(apply.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let promotion: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(promotion["created_count"], 1);
+
+    // Exactly one copy is live: the production one
+    let (_, list) = published().await;
+    let list = list.unwrap();
+    assert_eq!(list["meta"]["total_items"], 1);
+    let live_id = list["data"][0]["id"].as_str().unwrap().to_string();
+    assert_ne!(live_id, staging_id);
+    let (status, live) = by_slug().await;
+    assert_eq!(status, Status::Ok);
+    let live = live.unwrap();
+    assert_eq!(live["id"], live_id.as_str());
+    assert_eq!(live["author"], "Test Author");
+    let (_, list) = get(format!("/api/v1/sites/{}/blogs", site_id)).await;
+    assert_eq!(list.unwrap()["meta"]["total_items"], 1);
+
+    // In sync: nothing left to promote
+    let changeset = preview(write_key.clone()).await;
+    assert!(changeset["items"].as_array().unwrap().is_empty());
+
+    let response = ctx
+        .client
+        .put(format!("/api/v1/blogs/{}", staging_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "author": "Second Author" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let changeset = preview(write_key.clone()).await;
+    let items = changeset["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["action"], "Update");
+    assert_eq!(items[0]["areas"], serde_json::json!(["Content"]));
+    assert_eq!(items[0]["changes"][0]["field"], "entity.author");
+    assert_eq!(items[0]["changes"][0]["old_value"], "Test Author");
+
+    // A stale checksum is rejected and nothing changes
+    let mut stale = request.clone();
+    stale["checksum"] = serde_json::json!("0000");
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/promotions", site_id))
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .header(ContentType::JSON)
+        .body(stale.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::PreconditionFailed);
+    let (_, live) = by_slug().await;
+    assert_eq!(live.unwrap()["author"], "Test Author");
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/promotions", site_id))
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .header(ContentType::JSON)
+        .body(request.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let promotion: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(promotion["updated_count"], 1);
+    let (_, live) = by_slug().await;
+    let live = live.unwrap();
+    assert_eq!(live["id"], live_id.as_str());
+    assert_eq!(live["author"], "Second Author");
+    let (_, list) = published().await;
+    assert_eq!(list.unwrap()["meta"]["total_items"], 1);
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/promotions", site_id))
+        .header(Header::new("X-API-Key", admin_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let history: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(history["meta"]["total_items"], 2);
+}
+
+// =========================================================================
+// 33. Site export and import
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_export_and_import_site_archive() {
+    use openyapper::models::media::{MediaFile, StorageProvider};
+    use openyapper::models::site_transfer::{
+        SiteTransferJob, SiteTransferKind, SiteTransferStatus,
+    };
+    use openyapper::services::site_transfer_service::{self, SiteImportOptions};
+    use openyapper::services::storage::{LocalStorage, StorageBackend};
+
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+
+    create_test_blog_with_status(&ctx, &write_key, site_id, "exported-post", "Published").await;
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/redirects", site_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "source_path": "/old", "destination_path": "/new", "status_code": 301,
+                "site_id": site_id
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+
+    let storage_path = format!("{}/2025/01/logo.png", site_id);
+    let binary = b"not really a png".to_vec();
+    let file_path = ctx._temp_dir.path().join(&storage_path);
+    std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+    std::fs::write(&file_path, &binary).unwrap();
+    MediaFile::create_from_upload(
+        &ctx.pool,
+        "logo.png",
+        "logo.png",
+        "image/png",
+        binary.len() as i64,
+        StorageProvider::Local,
+        &storage_path,
+        &format!("/uploads/{}", storage_path),
+        "export-test-checksum",
+        None,
+        None,
+        None,
+        false,
+        None,
+        vec![site_id],
+    )
+    .await
+    .unwrap();
+
+    // Exporting needs Admin
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/export", site_id))
+        .header(Header::new("X-API-Key", write_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/export", site_id))
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Accepted);
+    let job: serde_json::Value = response.into_json().await.unwrap();
+    let job_id = job["id"].as_str().unwrap().to_string();
+
+    let mut job = job;
+    for _ in 0..100 {
+        let response = ctx
+            .client
+            .get(format!("/api/v1/site-transfers/{}", job_id))
+            .header(Header::new("X-API-Key", admin_key.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        job = response.into_json().await.unwrap();
+        if job["status"] == "Completed" || job["status"] == "Failed" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(
+        job["status"], "Completed",
+        "export failed: {}",
+        job["error"]
+    );
+    assert_eq!(job["progress"], 100);
+    assert_eq!(job["report"]["rows"]["contents"], 1);
+    assert_eq!(job["report"]["rows"]["redirects"], 1);
+    assert_eq!(job["report"]["media_files"], 1);
+
+    let response = ctx
+        .client
+        .get(job["download_url"].as_str().unwrap())
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::ZIP));
+    let archive = response.into_bytes().await.unwrap();
+    assert!(archive.starts_with(b"PK"));
+
+    // Site-scoped keys cannot create sites
+    let response = ctx
+        .client
+        .post("/api/v1/sites/import")
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .header(ContentType::ZIP)
+        .body(archive.clone())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // Import into the same database: the slug is taken, the media is reused
+    let import = SiteTransferJob::create(
+        &ctx.pool,
+        SiteTransferKind::Import,
+        None,
+        None,
+        site_transfer_service::instance_id(),
+    )
+    .await
+    .unwrap();
+    let target = tempfile::TempDir::new().unwrap();
+    let storage = LocalStorage::new(
+        target.path().to_string_lossy().to_string(),
+        "/uploads".to_string(),
+    );
+    let key = site_transfer_service::new_archive_key();
+    storage
+        .store(&key, &archive, "application/zip")
+        .await
+        .unwrap();
+    SiteTransferJob::set_archive(&ctx.pool, import.id, Some(&key))
+        .await
+        .unwrap();
+    let report = site_transfer_service::import_site(
+        &ctx.pool,
+        &storage,
+        import.id,
+        &SiteImportOptions {
+            name: Some("Imported Site".to_string()),
+            slug: None,
+            storage_provider: "local".to_string(),
+            owner_clerk_id: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.rows["contents"], 1);
+    assert_eq!(report.rows["redirects"], 1);
+    assert_eq!(report.reused["media_files"], 1);
+
+    let (new_site_id, slug, name): (uuid::Uuid, String, String) =
+        sqlx::query_as("SELECT id, slug::text, name FROM sites WHERE id <> $1")
+            .bind(site_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    let (original_slug,): (String,) = sqlx::query_as("SELECT slug::text FROM sites WHERE id = $1")
+        .bind(site_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(slug, format!("{}-2", original_slug));
+    assert_eq!(Some(slug), report.site_slug);
+    assert_eq!(name, "Imported Site");
+
+    let copies: Vec<(uuid::Uuid, uuid::Uuid)> = sqlx::query_as(
+        "SELECT c.id, cs.site_id FROM contents c \
+         INNER JOIN content_sites cs ON cs.content_id = c.id \
+         WHERE c.slug = 'exported-post' ORDER BY cs.site_id = $1",
+    )
+    .bind(site_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(copies.len(), 2);
+    assert_eq!(copies[0].1, new_site_id);
+    assert_ne!(copies[0].0, copies[1].0);
+
+    let (linked,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM media_sites WHERE site_id = $1 OR site_id = $2")
+            .bind(site_id)
+            .bind(new_site_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(linked, 2);
+    let (redirects,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM redirects WHERE site_id = $1 AND source_path = '/old'",
+    )
+    .bind(new_site_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(redirects, 1);
+
+    // Only jobs whose owner stopped sending heartbeats are failed
+    let other_instance = uuid::Uuid::new_v4();
+    let running = SiteTransferJob::create(
+        &ctx.pool,
+        SiteTransferKind::Export,
+        Some(site_id),
+        None,
+        other_instance,
+    )
+    .await
+    .unwrap();
+    SiteTransferJob::start(&ctx.pool, running.id, 10)
+        .await
+        .unwrap();
+    let stale_after = std::time::Duration::from_secs(300);
+    let failed = SiteTransferJob::fail_stale(&ctx.pool, stale_after)
+        .await
+        .unwrap();
+    assert!(failed.iter().all(|(id, _)| *id != running.id));
+
+    sqlx::query(
+        "UPDATE site_transfer_jobs SET heartbeat_at = NOW() - INTERVAL '10 minutes' WHERE id = $1",
+    )
+    .bind(running.id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let failed = SiteTransferJob::fail_stale(&ctx.pool, stale_after)
+        .await
+        .unwrap();
+    assert!(failed.contains(&(running.id, Some(other_instance))));
+    let running = SiteTransferJob::find_by_id(&ctx.pool, running.id)
+        .await
+        .unwrap();
+    assert_eq!(running.status, SiteTransferStatus::Failed);
+    assert!(running.finished_at.is_some());
+
+    // Expired export archives are handed out once for deletion from storage
+    let export_id: uuid::Uuid = job_id.parse().unwrap();
+    let export = SiteTransferJob::find_by_id(&ctx.pool, export_id)
+        .await
+        .unwrap();
+    let export_archive = export.archive_path.expect("export archive in storage");
+    assert!(export_archive.starts_with("site-transfers/"));
+    let retention = site_transfer_service::ARCHIVE_RETENTION;
+    assert!(SiteTransferJob::take_expired_archives(&ctx.pool, retention)
+        .await
+        .unwrap()
+        .is_empty());
+    sqlx::query(
+        "UPDATE site_transfer_jobs SET finished_at = NOW() - INTERVAL '2 days' WHERE id = $1",
+    )
+    .bind(export_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let expired = SiteTransferJob::take_expired_archives(&ctx.pool, retention)
+        .await
+        .unwrap();
+    assert_eq!(expired, vec![export_archive]);
+    assert!(SiteTransferJob::take_expired_archives(&ctx.pool, retention)
+        .await
+        .unwrap()
+        .is_empty());
+    let response = ctx
+        .client
+        .get(format!("/api/v1/site-transfers/{}/download", export_id))
+        .header(Header::new("X-API-Key", admin_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+// =========================================================================
+// 34. WordPress import
+// =========================================================================
+
+const WORDPRESS_EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>Old Site</title>
+    <link>https://old.example.com</link>
+    <wp:wxr_version>1.2</wp:wxr_version>
+    <wp:author>
+        <wp:author_login><![CDATA[jdoe]]></wp:author_login>
+        <wp:author_display_name><![CDATA[Jane Doe]]></wp:author_display_name>
+    </wp:author>
+    <wp:category>
+        <wp:category_nicename><![CDATA[news]]></wp:category_nicename>
+        <wp:category_parent><![CDATA[]]></wp:category_parent>
+        <wp:cat_name><![CDATA[News]]></wp:cat_name>
+    </wp:category>
+    <wp:category>
+        <wp:category_nicename><![CDATA[releases]]></wp:category_nicename>
+        <wp:category_parent><![CDATA[news]]></wp:category_parent>
+        <wp:cat_name><![CDATA[Releases]]></wp:cat_name>
+    </wp:category>
+    <wp:tag>
+        <wp:tag_slug><![CDATA[rust]]></wp:tag_slug>
+        <wp:tag_name><![CDATA[Rust]]></wp:tag_name>
+    </wp:tag>
+    <item>
+        <title>Cat</title>
+        <wp:post_id>11</wp:post_id>
+        <wp:status><![CDATA[inherit]]></wp:status>
+        <wp:post_type><![CDATA[attachment]]></wp:post_type>
+        <wp:attachment_url><![CDATA[https://old.example.com/wp-content/uploads/2024/05/cat.png]]></wp:attachment_url>
+        <wp:postmeta>
+            <wp:meta_key><![CDATA[_wp_attachment_image_alt]]></wp:meta_key>
+            <wp:meta_value><![CDATA[A cat]]></wp:meta_value>
+        </wp:postmeta>
+    </item>
+    <item>
+        <title>Missing</title>
+        <wp:post_id>14</wp:post_id>
+        <wp:status><![CDATA[inherit]]></wp:status>
+        <wp:post_type><![CDATA[attachment]]></wp:post_type>
+        <wp:attachment_url><![CDATA[https://old.example.com/wp-content/uploads/2024/05/missing.png]]></wp:attachment_url>
+    </item>
+    <item>
+        <title>Hello World</title>
+        <link>https://old.example.com/2024/05/hello-world/</link>
+        <dc:creator><![CDATA[jdoe]]></dc:creator>
+        <content:encoded><![CDATA[<p>Hi <img src="https://old.example.com/wp-content/uploads/2024/05/cat-300x200.png"></p>]]></content:encoded>
+        <excerpt:encoded><![CDATA[Greetings]]></excerpt:encoded>
+        <wp:post_id>10</wp:post_id>
+        <wp:post_date><![CDATA[2024-05-01 09:30:00]]></wp:post_date>
+        <wp:post_name><![CDATA[hello-world]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <category domain="category" nicename="releases"><![CDATA[Releases]]></category>
+        <category domain="post_tag" nicename="rust"><![CDATA[Rust]]></category>
+        <wp:postmeta>
+            <wp:meta_key><![CDATA[_thumbnail_id]]></wp:meta_key>
+            <wp:meta_value><![CDATA[11]]></wp:meta_value>
+        </wp:postmeta>
+    </item>
+    <item>
+        <title>Work in progress</title>
+        <dc:creator><![CDATA[someone]]></dc:creator>
+        <content:encoded><![CDATA[Draft]]></content:encoded>
+        <wp:post_id>12</wp:post_id>
+        <wp:post_name><![CDATA[]]></wp:post_name>
+        <wp:status><![CDATA[draft]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+    </item>
+    <item>
+        <title>Deleted</title>
+        <wp:post_id>13</wp:post_id>
+        <wp:status><![CDATA[trash]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+    </item>
+    <item>
+        <title>Team</title>
+        <link>https://old.example.com/about/team/</link>
+        <content:encoded><![CDATA[<p>Us</p>]]></content:encoded>
+        <wp:post_id>21</wp:post_id>
+        <wp:post_name><![CDATA[team]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_parent>20</wp:post_parent>
+        <wp:post_type><![CDATA[page]]></wp:post_type>
+    </item>
+    <item>
+        <title>About</title>
+        <link>https://old.example.com/about-us/</link>
+        <content:encoded><![CDATA[<p>About</p>]]></content:encoded>
+        <wp:post_id>20</wp:post_id>
+        <wp:post_name><![CDATA[about]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_parent>0</wp:post_parent>
+        <wp:post_type><![CDATA[page]]></wp:post_type>
+    </item>
+    <item>
+        <title>Home</title>
+        <wp:post_id>30</wp:post_id>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_type><![CDATA[nav_menu_item]]></wp:post_type>
+    </item>
+</channel>
+</rss>"#;
+
+#[rocket::async_test]
+#[serial]
+async fn test_import_wordpress_export() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default) \
+         SELECT $1, id, TRUE FROM locales WHERE code = 'en'",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    // The import source directory stands in for old.example.com
+    let uploads = ctx
+        ._temp_dir
+        .path()
+        .join("import-source/wp-content/uploads/2024/05");
+    std::fs::create_dir_all(&uploads).unwrap();
+    image::RgbImage::new(4, 4)
+        .save(uploads.join("cat.png"))
+        .unwrap();
+
+    let import = |key: String| {
+        ctx.client
+            .post(format!("/api/v1/sites/{}/import/wordpress", site_id))
+            .header(Header::new("X-API-Key", key))
+            .header(ContentType::XML)
+            .body(WORDPRESS_EXPORT)
+            .dispatch()
+    };
+
+    // Importing needs Editor
+    let response = import(read_key).await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/import/wordpress", site_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .body("<rss><channel><title>Not WordPress</title></channel></rss>")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = import(write_key.clone()).await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(report["blogs"]["created"], 2);
+    assert_eq!(report["pages"]["created"], 2);
+    assert_eq!(report["categories"]["created"], 2);
+    assert_eq!(report["tags"]["created"], 1);
+    assert_eq!(report["media"]["created"], 1);
+    assert_eq!(report["media"]["failed"], 1);
+    assert_eq!(report["ignored"]["trash"], 1);
+    assert_eq!(report["ignored"]["nav_menu_item"], 1);
+    assert_eq!(report["warnings"].as_array().unwrap().len(), 1);
+
+    // Posts become blogs with author, cover, rewritten media links and taxonomy
+    let (author, status, cover, body, excerpt): (
+        String,
+        String,
+        Option<uuid::Uuid>,
+        String,
+        String,
+    ) = sqlx::query_as(
+        "SELECT b.author, c.status::text, b.cover_image_id, cl.body, cl.excerpt \
+         FROM blogs b \
+         INNER JOIN contents c ON c.id = b.content_id \
+         INNER JOIN content_localizations cl ON cl.content_id = c.id \
+         WHERE c.slug = 'hello-world'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(author, "Jane Doe");
+    assert_eq!(status, "published");
+    assert!(cover.is_some());
+    assert!(!body.contains("wp-content"), "body not rewritten: {}", body);
+    assert!(body.contains(&format!("/uploads/{}/", site_id)));
+    assert_eq!(excerpt, "Greetings");
+
+    let (draft_author, draft_status): (String, String) = sqlx::query_as(
+        "SELECT b.author, c.status::text FROM blogs b \
+         INNER JOIN contents c ON c.id = b.content_id \
+         WHERE c.slug = 'work-in-progress'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(draft_author, "someone");
+    assert_eq!(draft_status, "draft");
+
+    let terms: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT cat.slug::text, cc.is_primary FROM content_categories cc \
+         INNER JOIN categories cat ON cat.id = cc.category_id \
+         INNER JOIN contents c ON c.id = cc.content_id \
+         WHERE c.slug = 'hello-world'",
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(terms, vec![("releases".to_string(), true)]);
+    let (parent_slug,): (String,) = sqlx::query_as(
+        "SELECT p.slug::text FROM categories c \
+         INNER JOIN categories p ON p.id = c.parent_id WHERE c.slug = 'releases'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(parent_slug, "news");
+    let (tagged,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM content_tags ct \
+         INNER JOIN tags t ON t.id = ct.tag_id \
+         INNER JOIN tag_localizations tl ON tl.tag_id = t.id \
+         WHERE t.slug = 'rust' AND tl.name = 'Rust'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(tagged, 1);
+
+    // Pages keep their hierarchy even when a child comes first in the file
+    let (team_parent,): (Option<uuid::Uuid>,) =
+        sqlx::query_as("SELECT parent_page_id FROM pages WHERE route = '/about/team'")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    let (about_id,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM pages WHERE route = '/about'")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(team_parent, Some(about_id));
+
+    // Old permalinks redirect to the new paths
+    for (old, new) in [
+        ("/2024/05/hello-world/", "/blog/hello-world"),
+        ("/2024/05/hello-world", "/blog/hello-world"),
+        ("/about-us/", "/about"),
+    ] {
+        let response = ctx
+            .client
+            .get(format!(
+                "/api/v1/sites/{}/redirects/lookup?path={}",
+                site_id, old
+            ))
+            .header(Header::new("X-API-Key", write_key.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok, "no redirect for {}", old);
+        let body: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(body["destination_path"], new);
+    }
+
+    let (alt,): (Option<String>,) = sqlx::query_as("SELECT alt_text FROM media_metadata")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(alt.as_deref(), Some("A cat"));
+
+    // Running the import again reuses everything
+    let response = import(write_key).await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(report["blogs"]["created"], 0);
+    assert_eq!(report["blogs"]["existing"], 2);
+    assert_eq!(report["pages"]["existing"], 2);
+    assert_eq!(report["categories"]["existing"], 2);
+    assert_eq!(report["media"]["existing"], 1);
+    let (blogs,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM blogs")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(blogs, 2);
+}
+
+// =========================================================================
+// 35. Markdown import/export
+// =========================================================================
+
+fn zip_files(files: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (path, contents) in files {
+        zip.start_file(*path, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn unzip_files(bytes: Vec<u8>) -> std::collections::BTreeMap<String, String> {
+    use std::io::Read;
+
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut files = std::collections::BTreeMap::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).unwrap();
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).unwrap();
+        files.insert(entry.name().to_string(), contents);
+    }
+    files
+}
+
+#[rocket::async_test]
+#[serial]
+async fn test_markdown_import_and_export() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    sqlx::query(
+        "INSERT INTO site_locales (site_id, locale_id, is_default) \
+         SELECT $1, id, code = 'en' FROM locales WHERE code IN ('en', 'de')",
+    )
+    .bind(site_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let archive = zip_files(&[
+        (
+            "my-site/content/blog/hello-world.md",
+            "---\ntitle: Hello World\ndate: 2024-05-01\nauthor: Jane Doe\ntags: [Rust, web]\n\
+             categories: [News]\ncover_image: /uploads/missing.png\nlayout: post\n---\n\n# Hello\n\nWorld\n",
+        ),
+        (
+            "my-site/content/blog/hello-world.de.md",
+            "---\ntitle: Hallo Welt\n---\n\n# Hallo\n",
+        ),
+        (
+            "my-site/content/blog/draft-post/index.md",
+            "---\ntitle: Not yet\ndraft: true\n---\nSoon\n",
+        ),
+        ("my-site/content/pages/index.md", "---\ntitle: Home\n---\nWelcome\n"),
+        (
+            "my-site/content/pages/about/team.md",
+            "---\ntitle: Team\nnavigation: true\n---\nUs\n",
+        ),
+        ("my-site/content/pages/about.md", "---\ntitle: About\n---\nAbout us\n"),
+        ("my-site/content/pages/broken.md", "No front matter here\n"),
+        ("my-site/README.md", "# Not content\n"),
+    ]);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/import/markdown", site_id))
+        .header(Header::new("X-API-Key", key.clone()))
+        .header(ContentType::ZIP)
+        .body(archive)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(report["blogs"]["created"], 2);
+    assert_eq!(report["pages"]["created"], 3);
+    assert_eq!(report["localizations"], 6);
+    let warnings = report["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 2, "{:?}", warnings);
+
+    let (author, published_date, status): (String, chrono::NaiveDate, String) = sqlx::query_as(
+        "SELECT b.author, b.published_date, c.status::text FROM blogs b \
+         INNER JOIN contents c ON c.id = b.content_id WHERE c.slug = 'hello-world'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(author, "Jane Doe");
+    assert_eq!(published_date.to_string(), "2024-05-01");
+    assert_eq!(status, "published");
+    let (draft_status,): (String,) =
+        sqlx::query_as("SELECT status::text FROM contents WHERE slug = 'draft-post'")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(draft_status, "draft");
+
+    let titles: Vec<(String, String)> = sqlx::query_as(
+        "SELECT l.code, cl.title FROM content_localizations cl \
+         INNER JOIN contents c ON c.id = cl.content_id \
+         INNER JOIN locales l ON l.id = cl.locale_id \
+         WHERE c.slug = 'hello-world' ORDER BY l.code",
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        titles,
+        vec![
+            ("de".to_string(), "Hallo Welt".to_string()),
+            ("en".to_string(), "Hello World".to_string())
+        ]
+    );
+    let terms: Vec<(String,)> = sqlx::query_as(
+        "SELECT t.slug::text FROM content_tags ct INNER JOIN tags t ON t.id = ct.tag_id \
+         INNER JOIN contents c ON c.id = ct.content_id \
+         WHERE c.slug = 'hello-world' ORDER BY t.slug",
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(terms, vec![("rust".to_string(),), ("web".to_string(),)]);
+
+    let (team_parent, in_navigation): (Option<uuid::Uuid>, bool) = sqlx::query_as(
+        "SELECT parent_page_id, is_in_navigation FROM pages WHERE route = '/about/team'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    let (about_id,): (uuid::Uuid,) = sqlx::query_as("SELECT id FROM pages WHERE route = '/about'")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(team_parent, Some(about_id));
+    assert!(in_navigation);
+
+    // Export contains published content, localizations as sibling files
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/export/markdown", site_id))
+        .header(Header::new("X-API-Key", key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::ZIP));
+    let files = unzip_files(response.into_bytes().await.unwrap());
+    assert_eq!(
+        files.keys().map(String::as_str).collect::<Vec<_>>(),
+        vec![
+            "blog/hello-world.de.md",
+            "blog/hello-world.md",
+            "pages/about.md",
+            "pages/about/team.md",
+            "pages/index.md",
+        ]
+    );
+    let hello = &files["blog/hello-world.md"];
+    assert!(hello.starts_with("---\ntitle: Hello World\nslug: hello-world\nlocale: en\n"));
+    assert!(hello.contains("date: 2024-05-01\n"));
+    assert!(hello.contains("tags:\n- rust\n- web\n"));
+    assert!(hello.contains("categories:\n- news\n"));
+    assert!(hello.ends_with("---\n\n# Hello\n\nWorld\n"));
+    assert!(files["blog/hello-world.de.md"].contains("locale: de\n"));
+    assert!(files["pages/about/team.md"].contains("route: /about/team\n"));
+
+    // Re-importing an edited export updates instead of duplicating
+    let edited = hello
+        .replace("title: Hello World", "title: Hello Again")
+        .replace("- web\n", "");
+    let mut tree: Vec<(&str, &str)> = files
+        .iter()
+        .filter(|(path, _)| path.as_str() != "blog/hello-world.md")
+        .map(|(path, contents)| (path.as_str(), contents.as_str()))
+        .collect();
+    tree.push(("blog/hello-world.md", &edited));
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/import/markdown", site_id))
+        .header(Header::new("X-API-Key", key.clone()))
+        .header(ContentType::ZIP)
+        .body(zip_files(&tree))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(report["blogs"]["created"], 0);
+    assert_eq!(report["blogs"]["updated"], 1);
+    assert_eq!(report["pages"]["updated"], 3);
+    assert_eq!(report["warnings"].as_array().unwrap().len(), 0);
+
+    let (blogs, pages): (i64, i64) =
+        sqlx::query_as("SELECT (SELECT COUNT(*) FROM blogs), (SELECT COUNT(*) FROM pages)")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!((blogs, pages), (2, 3));
+    let (title,): (String,) = sqlx::query_as(
+        "SELECT cl.title FROM content_localizations cl \
+         INNER JOIN contents c ON c.id = cl.content_id \
+         INNER JOIN locales l ON l.id = cl.locale_id \
+         WHERE c.slug = 'hello-world' AND l.code = 'en'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(title, "Hello Again");
+    let (tags,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM content_tags ct INNER JOIN contents c ON c.id = ct.content_id \
+         WHERE c.slug = 'hello-world'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(tags, 1);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/sites/{}/import/markdown", site_id))
+        .header(Header::new("X-API-Key", key))
+        .header(ContentType::ZIP)
+        .body(zip_files(&[("notes/todo.md", "---\ntitle: x\n---\n")]))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// =========================================================================
+// 36. Content releases
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_publish_schedule_and_roll_back_release() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+
+    let mut content_ids = Vec::new();
+    let mut blog_ids = Vec::new();
+    for slug in ["launch-post", "launch-faq"] {
+        let blog = create_test_blog(&ctx, &write_key, site_id, slug).await;
+        content_ids.push(blog["content_id"].as_str().unwrap().to_string());
+        blog_ids.push(blog["id"].as_str().unwrap().to_string());
+    }
+
+    let (nav_item_id,): (uuid::Uuid,) = sqlx::query_as(
+        "WITH menu AS ( \
+             INSERT INTO navigation_menus (site_id, slug) VALUES ($1, 'primary') RETURNING id \
+         ) \
+         INSERT INTO navigation_items (site_id, menu_id, external_url, is_active) \
+         SELECT $1, id, 'https://example.com/launch', FALSE FROM menu RETURNING id",
+    )
+    .bind(site_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+
+    let create_release = |name: &'static str| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .post(format!("/api/v1/sites/{}/releases", site_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(serde_json::json!({ "name": name }).to_string())
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Created);
+            let release: serde_json::Value = response.into_json().await.unwrap();
+            assert_eq!(release["status"], "Draft");
+            release["id"].as_str().unwrap().to_string()
+        }
+    };
+    let add_item = |release_id: String, key: String, body: serde_json::Value| {
+        let client = &ctx.client;
+        async move {
+            client
+                .post(format!("/api/v1/releases/{}/items", release_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await
+                .status()
+        }
+    };
+    let content_state = || async {
+        let rows: Vec<(String, Option<chrono::DateTime<chrono::Utc>>)> = sqlx::query_as(
+            "SELECT status::text, published_at FROM contents WHERE slug IN ('launch-post', 'launch-faq') ORDER BY slug DESC",
+        )
+        .fetch_all(&ctx.pool)
+        .await
+        .unwrap();
+        let (active,): (bool,) =
+            sqlx::query_as("SELECT is_active FROM navigation_items WHERE id = $1")
+                .bind(nav_item_id)
+                .fetch_one(&ctx.pool)
+                .await
+                .unwrap();
+        (rows, active)
+    };
+
+    let release_id = create_release("Spring campaign").await;
+
+    // Publishing an empty release is rejected
+    let response = ctx
+        .client
+        .post(format!("/api/v1/releases/{}/publish", release_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    for content_id in &content_ids {
+        let body = serde_json::json!({ "content_id": content_id, "target_status": "Published" });
+        assert_eq!(
+            add_item(release_id.clone(), read_key.clone(), body.clone()).await,
+            Status::Forbidden
+        );
+        assert_eq!(
+            add_item(release_id.clone(), write_key.clone(), body).await,
+            Status::Ok
+        );
+    }
+    let body = serde_json::json!({ "navigation_item_id": nav_item_id, "target_active": true });
+    assert_eq!(
+        add_item(release_id.clone(), write_key.clone(), body).await,
+        Status::Ok
+    );
+    let body =
+        serde_json::json!({ "content_id": uuid::Uuid::new_v4(), "target_status": "Published" });
+    assert_eq!(
+        add_item(release_id.clone(), write_key.clone(), body).await,
+        Status::NotFound
+    );
+
+    // Nothing changes before the release is published
+    let (rows, active) = content_state().await;
+    assert!(rows.iter().all(|(status, _)| status == "draft"));
+    assert!(!active);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/releases/{}/publish", release_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let release: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(release["status"], "Published");
+    let items = release["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["entity_type"], "blog");
+    assert_eq!(items[0]["previous_status"], "Draft");
+    assert_eq!(items[2]["previous_active"], false);
+
+    let (rows, active) = content_state().await;
+    assert!(rows
+        .iter()
+        .all(|(status, published_at)| status == "published" && published_at.is_some()));
+    assert!(active);
+
+    let (audits,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM audit_logs WHERE entity_type = 'content_release' AND action = 'publish'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(audits, 1);
+
+    // A published release is frozen
+    let body = serde_json::json!({ "navigation_item_id": nav_item_id, "target_active": false });
+    assert_eq!(
+        add_item(release_id.clone(), write_key.clone(), body).await,
+        Status::Conflict
+    );
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/releases/{}", release_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    // The FAQ is edited after the release, so the rollback leaves it published
+    let response = ctx
+        .client
+        .put(format!("/api/v1/blogs/{}", blog_ids[1]))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "author": "Late Editor" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/releases/{}/rollback", release_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let release: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(release["status"], "RolledBack");
+    let skipped: Vec<bool> = release["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["rollback_skipped"].as_bool().unwrap())
+        .collect();
+    assert_eq!(skipped, vec![false, true, false]);
+
+    let (rows, active) = content_state().await;
+    assert_eq!(rows[0].0, "draft");
+    assert!(rows[0].1.is_none());
+    assert_eq!(rows[1].0, "published");
+    assert!(!active);
+
+    let response = ctx
+        .client
+        .post(format!("/api/v1/releases/{}/rollback", release_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    // Scheduled releases are published by the scheduler tick
+    let scheduled_id = create_release("Summer campaign").await;
+    let body = serde_json::json!({ "content_id": content_ids[0], "target_status": "Published" });
+    assert_eq!(
+        add_item(scheduled_id.clone(), write_key.clone(), body).await,
+        Status::Ok
+    );
+    let response = ctx
+        .client
+        .post(format!("/api/v1/releases/{}/schedule", scheduled_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "scheduled_at": "2020-01-01T00:00:00Z" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let scheduled_at = chrono::Utc::now() + chrono::Duration::hours(1);
+    let response = ctx
+        .client
+        .post(format!("/api/v1/releases/{}/schedule", scheduled_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "scheduled_at": scheduled_at }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let release: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(release["status"], "Scheduled");
+
+    let report = openyapper::services::scheduler_service::run_tick(&ctx.pool)
+        .await
+        .expect("scheduler tick");
+    assert!(report.releases.is_empty());
+
+    sqlx::query(
+        "UPDATE content_releases SET scheduled_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(uuid::Uuid::parse_str(&scheduled_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let report = openyapper::services::scheduler_service::run_tick(&ctx.pool)
+        .await
+        .expect("scheduler tick");
+    assert_eq!(report.releases.len(), 1);
+    assert!(report.failed_releases.is_empty());
+
+    let (rows, _) = content_state().await;
+    assert_eq!(rows[0].0, "published");
+    // Still published from the first release, whose rollback skipped it
+    assert_eq!(rows[1].0, "published");
+
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/sites/{}/releases?status=published",
+            site_id
+        ))
+        .header(Header::new("X-API-Key", read_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let list: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(list["meta"]["total_items"], 1);
+    assert_eq!(list["data"][0]["name"], "Summer campaign");
+}
+
+// =========================================================================
+// 37. Trash
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_restore_and_purge_trash() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+
+    let create_blog = || async {
+        let blog = create_test_blog(&ctx, &write_key, site_id, "trashed-post").await;
+        (
+            blog["id"].as_str().unwrap().to_string(),
+            blog["content_id"].as_str().unwrap().to_string(),
+        )
+    };
+    let delete_blog = |blog_id: String| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .delete(format!("/api/v1/blogs/{}", blog_id))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::NoContent);
+        }
+    };
+    let list_trash = |query: &'static str| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            let response = client
+                .get(format!("/api/v1/sites/{}/trash{}", site_id, query))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+            let trash: serde_json::Value = response.into_json().await.unwrap();
+            trash["data"].as_array().unwrap().clone()
+        }
+    };
+    let restore = |kind: &'static str, id: String| {
+        let client = &ctx.client;
+        let key = write_key.clone();
+        async move {
+            client
+                .post(format!(
+                    "/api/v1/sites/{}/trash/{}/{}/restore",
+                    site_id, kind, id
+                ))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await
+                .status()
+        }
+    };
+
+    // A deleted blog shows up in the trash
+    let (blog_id, content_id) = create_blog().await;
+    delete_blog(blog_id.clone()).await;
+
+    let items = list_trash("").await;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], content_id);
+    assert_eq!(items[0]["kind"], "Content");
+    assert_eq!(items[0]["entity_type"], "blog");
+    assert_eq!(items[0]["entity_id"], blog_id);
+    assert_eq!(items[0]["slug"], "trashed-post");
+    assert!(items[0]["purge_at"].is_string());
+    assert!(list_trash("?kind=media").await.is_empty());
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/sites/{}/trash", site_id))
+        .header(Header::new("X-API-Key", read_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // Restoring fails while another blog uses the slug
+    let (other_blog_id, other_content_id) = create_blog().await;
+    assert_eq!(
+        restore("content", content_id.clone()).await,
+        Status::Conflict
+    );
+
+    delete_blog(other_blog_id).await;
+    assert_eq!(
+        restore("content", content_id.clone()).await,
+        Status::NoContent
+    );
+    assert_eq!(
+        restore("content", content_id.clone()).await,
+        Status::NotFound
+    );
+
+    let response = ctx
+        .client
+        .get(format!("/api/v1/blogs/{}", blog_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let items = list_trash("").await;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], other_content_id);
+
+    // Deleted media keeps its file until it is purged
+    let storage_path = format!("{}/2025/01/photo.jpg", site_id);
+    let file_path = ctx._temp_dir.path().join(&storage_path);
+    std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+    std::fs::write(&file_path, b"jpeg").unwrap();
+
+    let response = ctx
+        .client
+        .post("/api/v1/media")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "filename": "photo.jpg",
+                "original_filename": "photo.jpg",
+                "mime_type": "image/jpeg",
+                "file_size": 4,
+                "storage_path": storage_path,
+                "site_ids": [site_id]
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let media: serde_json::Value = response.into_json().await.unwrap();
+    let media_id = media["id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/media/{}", media_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+    assert!(file_path.exists());
+
+    let items = list_trash("?kind=media").await;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["kind"], "Media");
+    assert_eq!(items[0]["title"], "photo.jpg");
+
+    let purge_media = |key: String| {
+        let client = &ctx.client;
+        let media_id = media_id.clone();
+        async move {
+            client
+                .delete(format!(
+                    "/api/v1/sites/{}/trash/media/{}",
+                    site_id, media_id
+                ))
+                .header(Header::new("X-API-Key", key))
+                .dispatch()
+                .await
+                .status()
+        }
+    };
+    assert_eq!(purge_media(write_key.clone()).await, Status::Forbidden);
+    assert_eq!(purge_media(admin_key.clone()).await, Status::NoContent);
+    assert!(!file_path.exists());
+
+    let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM media_files WHERE id = $1")
+        .bind(uuid::Uuid::parse_str(&media_id).unwrap())
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+
+    // The retention purge only removes items deleted long enough ago
+    let storage = openyapper::services::storage::LocalStorage::new(
+        ctx._temp_dir.path().to_string_lossy().to_string(),
+        "/uploads".to_string(),
+    );
+    let report = openyapper::services::trash_service::purge_expired(&ctx.pool, &storage, 30)
+        .await
+        .unwrap();
+    assert_eq!((report.content, report.media), (0, 0));
+
+    sqlx::query("UPDATE contents SET deleted_at = NOW() - INTERVAL '31 days' WHERE id = $1")
+        .bind(uuid::Uuid::parse_str(&other_content_id).unwrap())
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let report = openyapper::services::trash_service::purge_expired(&ctx.pool, &storage, 30)
+        .await
+        .unwrap();
+    assert_eq!((report.content, report.media), (1, 0));
+    assert!(list_trash("").await.is_empty());
+
+    // Emptying the trash purges everything in it
+    delete_blog(blog_id).await;
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/sites/{}/trash", site_id))
+        .header(Header::new("X-API-Key", admin_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(report["content"], 1);
+    assert_eq!(report["media"], 0);
+    assert!(list_trash("").await.is_empty());
+}
+
+// =========================================================================
+// 38. Image transformations
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_signed_image_transformation() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+
+    let storage_path = format!("{}/2025/01/landscape.png", site_id);
+    let file_path = ctx._temp_dir.path().join(&storage_path);
+    std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+    image::DynamicImage::new_rgb8(800, 400)
+        .save_with_format(&file_path, image::ImageFormat::Png)
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post("/api/v1/media")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "filename": "landscape.png",
+                "original_filename": "landscape.png",
+                "mime_type": "image/png",
+                "file_size": std::fs::metadata(&file_path).unwrap().len(),
+                "storage_path": storage_path,
+                "site_ids": [site_id]
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let media: serde_json::Value = response.into_json().await.unwrap();
+    let media_id = media["id"].as_str().unwrap().to_string();
+
+    // Signing needs an API key and valid options
+    let transform_url = |query: &'static str, key: Option<String>| {
+        let client = &ctx.client;
+        let media_id = media_id.clone();
+        async move {
+            let mut request = client.get(format!(
+                "/api/v1/media/{}/transform-url?{}",
+                media_id, query
+            ));
+            if let Some(key) = key {
+                request = request.header(Header::new("X-API-Key", key));
+            }
+            request.dispatch().await
+        }
+    };
+    let response = transform_url("w=200&h=200&fit=cover&format=webp", None).await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    let response = transform_url("w=200&fit=cover", Some(read_key.clone())).await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let response = transform_url("w=200&h=200&fit=cover&format=webp", Some(read_key.clone())).await;
+    assert_eq!(response.status(), Status::Ok);
+    let signed: serde_json::Value = response.into_json().await.unwrap();
+    let url = signed["url"].as_str().unwrap().to_string();
+
+    // The signed URL works without an API key
+    let response = ctx.client.get(url.clone()).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Content-Type"),
+        Some("image/webp")
+    );
+    assert!(response
+        .headers()
+        .get_one("Cache-Control")
+        .unwrap()
+        .contains("immutable"));
+    let bytes = response.into_bytes().await.unwrap();
+    let image = image::load_from_memory(&bytes).unwrap();
+    assert_eq!((image.width(), image.height()), (200, 200));
+
+    // Changed options or a missing signature are rejected
+    let tampered = url.replace("w=200", "w=400");
+    let response = ctx.client.get(tampered).dispatch().await;
+    assert_eq!(response.status(), Status::Forbidden);
+    let unsigned = url.split("&s=").next().unwrap().to_string();
+    let response = ctx.client.get(unsigned).dispatch().await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // The second request is served from the cache
+    let response = ctx.client.get(url.clone()).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let (cached_path,): (String,) =
+        sqlx::query_as("SELECT storage_path FROM media_transforms WHERE media_file_id = $1")
+            .bind(uuid::Uuid::parse_str(&media_id).unwrap())
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    let cached_file = ctx._temp_dir.path().join(&cached_path);
+    assert!(cached_file.exists());
+
+    // Purging the media file from the trash removes its cached transformations
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/media/{}", media_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+    let response = ctx.client.get(url).dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = ctx
+        .client
+        .delete(format!("/api/v1/sites/{}/trash", site_id))
+        .header(Header::new("X-API-Key", admin_key))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert!(!cached_file.exists());
+    assert!(!file_path.exists());
+}
+
+// =========================================================================
+// 39. Focal point and crops
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_media_art_direction() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+
+    // Black on the left half, white on the right
+    let storage_path = format!("{}/2025/01/split.png", site_id);
+    let file_path = ctx._temp_dir.path().join(&storage_path);
+    std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+    image::RgbImage::from_fn(800, 400, |x, _| {
+        if x < 400 {
+            image::Rgb([0, 0, 0])
+        } else {
+            image::Rgb([255, 255, 255])
+        }
+    })
+    .save_with_format(&file_path, image::ImageFormat::Png)
+    .unwrap();
+
+    let response = ctx
+        .client
+        .post("/api/v1/media")
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .header(ContentType::JSON)
+        .body(
+            serde_json::json!({
+                "filename": "split.png",
+                "original_filename": "split.png",
+                "mime_type": "image/png",
+                "file_size": std::fs::metadata(&file_path).unwrap().len(),
+                "storage_path": storage_path,
+                "width": 800,
+                "height": 400,
+                "site_ids": [site_id]
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+    let media: serde_json::Value = response.into_json().await.unwrap();
+    let media_id = media["id"].as_str().unwrap().to_string();
+
+    let set_art_direction_as = |key: String, body: serde_json::Value| {
+        let client = &ctx.client;
+        let media_id = media_id.clone();
+        async move {
+            client
+                .put(format!("/api/v1/media/{}/art-direction", media_id))
+                .header(Header::new("X-API-Key", key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await
+        }
+    };
+    let set_art_direction = |body: serde_json::Value| set_art_direction_as(write_key.clone(), body);
+
+    // Read keys and keys of other sites cannot change the image
+    let read_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Read).await;
+    let other_site_id = create_test_site(&ctx.pool).await;
+    let other_key = create_test_api_key(&ctx.pool, other_site_id, ApiKeyPermission::Write).await;
+    for key in [read_key, other_key] {
+        let response = set_art_direction_as(
+            key,
+            serde_json::json!({ "focal_point": { "x": 0.1, "y": 0.5 } }),
+        )
+        .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    // Crops must lie inside the image and the focal point within 0..1
+    let response = set_art_direction(serde_json::json!({
+        "crops": { "1:1": { "x": 600, "y": 0, "width": 400, "height": 400 } }
+    }))
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response =
+        set_art_direction(serde_json::json!({ "focal_point": { "x": 1.5, "y": 0.5 } })).await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = set_art_direction(serde_json::json!({
+        "focal_point": { "x": 0.9, "y": 0.5 },
+        "crops": { "1:1": { "x": 400, "y": 0, "width": 400, "height": 400 } }
+    }))
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(body["focal_point"]["x"], 0.9);
+    assert_eq!(body["crops"][0]["name"], "1:1");
+    let sources = body["crops"][0]["sources"].as_array().unwrap();
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[1]["width"], 400);
+    assert_eq!(sources[1]["height"], 400);
+
+    // GET /media/{id} returns the same crops
+    let response = ctx
+        .client
+        .get(format!("/api/v1/media/{}", media_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let fetched: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(fetched["crops"], body["crops"]);
+
+    // A crop source renders the white half
+    let url = sources[0]["url"].as_str().unwrap().to_string();
+    let response = ctx.client.get(url).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let bytes = response.into_bytes().await.unwrap();
+    let image = image::load_from_memory(&bytes).unwrap().to_rgb8();
+    assert_eq!((image.width(), image.height()), (200, 200));
+    assert!(image.get_pixel(100, 100)[0] > 200);
+
+    // Cover keeps the focal point, which lies in the white half
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/media/{}/transform-url?w=100&h=100&fit=cover&format=png",
+            media_id
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let signed: serde_json::Value = response.into_json().await.unwrap();
+    let response = ctx
+        .client
+        .get(signed["url"].as_str().unwrap().to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let bytes = response.into_bytes().await.unwrap();
+    let image = image::load_from_memory(&bytes).unwrap().to_rgb8();
+    assert!(image.get_pixel(50, 50)[0] > 200);
+
+    // Unknown crops cannot be signed
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/media/{}/transform-url?crop=4:3&w=100",
+            media_id
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Moving the focal point discards cached transformations and changes the
+    // signed URL, so downstream caches do not serve the old output
+    let response =
+        set_art_direction(serde_json::json!({ "focal_point": { "x": 0.1, "y": 0.5 } })).await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(body["crops"], serde_json::json!([]));
+    let response = ctx
+        .client
+        .get(format!(
+            "/api/v1/media/{}/transform-url?w=100&h=100&fit=cover&format=png",
+            media_id
+        ))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    let resigned: serde_json::Value = response.into_json().await.unwrap();
+    assert_ne!(resigned["url"], signed["url"]);
+    let response = ctx
+        .client
+        .get(resigned["url"].as_str().unwrap().to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let bytes = response.into_bytes().await.unwrap();
+    let image = image::load_from_memory(&bytes).unwrap().to_rgb8();
+    assert!(image.get_pixel(50, 50)[0] < 50);
+}
+
+// =========================================================================
+// 40. AVIF variants
+// =========================================================================
+
+#[rocket::async_test]
+#[serial]
+async fn test_avif_variant_backfill() {
+    let ctx = test_context().await;
+    cleanup_test_data(&ctx.pool).await;
+
+    let site_id = create_test_site(&ctx.pool).await;
+    let write_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Write).await;
+    let admin_key = create_test_api_key(&ctx.pool, site_id, ApiKeyPermission::Admin).await;
+
+    // AVIF settings are per site and validated
+    let update_settings = |body: serde_json::Value| {
+        let client = &ctx.client;
+        let admin_key = admin_key.clone();
+        async move {
+            client
+                .put(format!("/api/v1/sites/{}/settings", site_id))
+                .header(Header::new("X-API-Key", admin_key))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await
+                .status()
+        }
+    };
+    assert_eq!(
+        update_settings(serde_json::json!({ "avif_speed": 0 })).await,
+        Status::BadRequest
+    );
+    assert_eq!(
+        update_settings(serde_json::json!({ "avif_quality": 50, "avif_speed": 10 })).await,
+        Status::Ok
+    );
+
+    // Records created without the upload pipeline have no variants yet
+    let create_media = |name: &'static str, bytes: Vec<u8>| {
+        let client = &ctx.client;
+        let write_key = write_key.clone();
+        let root = ctx._temp_dir.path().to_path_buf();
+        async move {
+            let storage_path = format!("{}/2025/01/{}", site_id, name);
+            let file_path = root.join(&storage_path);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(&file_path, &bytes).unwrap();
+            let response = client
+                .post("/api/v1/media")
+                .header(Header::new("X-API-Key", write_key))
+                .header(ContentType::JSON)
+                .body(
+                    serde_json::json!({
+                        "filename": name,
+                        "original_filename": name,
+                        "mime_type": "image/png",
+                        "file_size": bytes.len(),
+                        "storage_path": storage_path,
+                        "site_ids": [site_id]
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Created);
+            let media: serde_json::Value = response.into_json().await.unwrap();
+            uuid::Uuid::parse_str(media["id"].as_str().unwrap()).unwrap()
+        }
+    };
+    let mut png = Vec::new();
+    image::DynamicImage::new_rgb8(1300, 120)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    let image_id = create_media("wide.png", png).await;
+    let broken_id = create_media("broken.png", b"not a png".to_vec()).await;
+
+    let storage = openyapper::services::storage::LocalStorage::new(
+        ctx._temp_dir.path().to_string_lossy().to_string(),
+        "/uploads".to_string(),
+    );
+    let report =
+        openyapper::services::media_service::backfill_avif_variants(&ctx.pool, &storage, 10)
+            .await
+            .unwrap();
+    assert_eq!(report.generated, 1);
+    assert_eq!(report.failed, vec![broken_id]);
+
+    let (width, height, path): (i16, i16, String) = sqlx::query_as(
+        "SELECT width, height, storage_path FROM media_variants WHERE media_file_id = $1 AND variant_name = 'avif'",
+    )
+    .bind(image_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!((width, height), (1200, 111));
+    assert!(path.ends_with("wide_avif.avif"));
+    let bytes = std::fs::read(ctx._temp_dir.path().join(&path)).unwrap();
+    assert_eq!(&bytes[4..12], b"ftypavif");
+
+    // GET /media/{id} lists the new variant
+    let response = ctx
+        .client
+        .get(format!("/api/v1/media/{}", image_id))
+        .header(Header::new("X-API-Key", write_key.clone()))
+        .dispatch()
+        .await;
+    let media: serde_json::Value = response.into_json().await.unwrap();
+    assert!(media["variants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|v| v["variant_name"] == "Avif"));
+
+    // Done images and recorded failures are not picked up again
+    let failed_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT avif_failed_at FROM media_files WHERE id = $1")
+            .bind(broken_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(failed_at.is_some());
+    let report =
+        openyapper::services::media_service::backfill_avif_variants(&ctx.pool, &storage, 10)
+            .await
+            .unwrap();
+    assert_eq!(report.generated, 0);
+    assert!(report.failed.is_empty());
+}
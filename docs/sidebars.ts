@@ -52,6 +52,23 @@ const sidebars: SidebarsConfig = {
             'api/endpoints/webhooks',
             'api/endpoints/redirects',
             'api/endpoints/content-templates',
+            'api/endpoints/content-blocks',
+            'api/endpoints/content-versions',
+            'api/endpoints/search',
+            'api/endpoints/preview-tokens',
+            'api/endpoints/edit-locks',
+            'api/endpoints/comments',
+            'api/endpoints/forms',
+            'api/endpoints/global-sections',
+            'api/endpoints/page-templates',
+            'api/endpoints/syndication',
+            'api/endpoints/promotions',
+            'api/endpoints/site-transfers',
+            'api/endpoints/wordpress-import',
+            'api/endpoints/markdown',
+            'api/endpoints/releases',
+            'api/endpoints/trash',
+            'api/endpoints/sitemap',
             'api/endpoints/api-keys',
             'api/endpoints/users',
             'api/endpoints/audit',